@@ -1,16 +1,23 @@
 package main
 
 import (
-	"fmt"
 	"os"
 
 	"github.com/thaodangspace/agentsandbox/internal/cli"
+	"github.com/thaodangspace/agentsandbox/internal/crash"
+	"github.com/thaodangspace/agentsandbox/internal/failure"
 )
 
 func main() {
+	defer crash.HandlePanic(cli.Version())
+
 	if err := cli.Execute(); err != nil {
-		fmt.Fprintf(os.Stderr, "Error: %v\n", err)
-		os.Exit(1)
+		// Classified errors are the user's to fix; only unexpected ones are reported
+		report := ""
+		if failure.KindOf(err) == failure.Unknown {
+			report = crash.ReportError(cli.Version(), err)
+		}
+		cli.PrintError(err, report)
+		os.Exit(failure.ExitCode(err))
 	}
 }
-
@@ -0,0 +1,110 @@
+package main
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"net/http"
+	"sync"
+	"unicode/utf8"
+
+	"github.com/thaodangspace/agentsandbox/core"
+)
+
+// eventStream writes an agent's output as server-sent events, flushing each one so
+// clients see output as the agent prints it. Nothing is sent until the first event, so
+// a run that fails before the agent starts can still be answered with an error status.
+type eventStream struct {
+	w       http.ResponseWriter
+	flusher http.Flusher
+
+	mu      sync.Mutex
+	started bool
+	// pending holds the start of a UTF-8 character split across writes
+	pending []byte
+}
+
+func newEventStream(w http.ResponseWriter) (*eventStream, error) {
+	flusher, ok := w.(http.Flusher)
+	if !ok {
+		return nil, errors.New("the connection cannot stream responses")
+	}
+	return &eventStream{w: w, flusher: flusher}, nil
+}
+
+// Write sends p as an output event, so the stream can take the agent's output directly
+func (e *eventStream) Write(p []byte) (int, error) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	data := append(e.pending, p...)
+	complete := completeUTF8(data)
+	e.pending = append([]byte(nil), data[complete:]...)
+	if complete == 0 {
+		return len(p), nil
+	}
+	if err := e.send("output", map[string]string{"text": string(data[:complete])}); err != nil {
+		return 0, err
+	}
+	return len(p), nil
+}
+
+// finish sends the done event for a run, or answers with an error status when the run
+// failed before anything was streamed
+func (e *eventStream) finish(result *core.RunResult, err error) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if !e.started && result == nil && err != nil {
+		writeError(e.w, err)
+		return
+	}
+	if len(e.pending) > 0 {
+		e.send("output", map[string]string{"text": string(e.pending)})
+		e.pending = nil
+	}
+
+	done := map[string]interface{}{}
+	if result != nil {
+		done["sandbox"] = result.Sandbox
+		done["session"] = result.Session
+		done["exit_code"] = result.ExitCode
+	}
+	if err != nil {
+		kind := core.ErrorKindOf(err)
+		done["error"] = map[string]string{"kind": kind.String(), "message": err.Error()}
+	}
+	e.send("done", done)
+}
+
+// send writes one event; the caller holds e.mu
+func (e *eventStream) send(event string, data interface{}) error {
+	body, err := json.Marshal(data)
+	if err != nil {
+		return err
+	}
+	if !e.started {
+		e.w.Header().Set("Content-Type", "text/event-stream")
+		e.w.Header().Set("Cache-Control", "no-cache")
+		e.w.WriteHeader(http.StatusOK)
+		e.started = true
+	}
+	if _, err := fmt.Fprintf(e.w, "event: %s\ndata: %s\n\n", event, body); err != nil {
+		return err
+	}
+	e.flusher.Flush()
+	return nil
+}
+
+// completeUTF8 returns the length of data without a UTF-8 character cut off at its end
+func completeUTF8(data []byte) int {
+	for i := 1; i <= utf8.UTFMax && i <= len(data); i++ {
+		if utf8.RuneStart(data[len(data)-i]) {
+			if utf8.FullRune(data[len(data)-i:]) {
+				return len(data)
+			}
+			return len(data) - i
+		}
+	}
+	return len(data)
+}
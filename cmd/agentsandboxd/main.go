@@ -0,0 +1,134 @@
+// Command agentsandboxd serves sandbox state over an HTTP API for dashboards and
+// editor plugins. It is built separately from the agentsandbox CLI, so CLI users do
+// not install a server they never run.
+package main
+
+import (
+	"context"
+	"errors"
+	"flag"
+	"fmt"
+	"log"
+	"net"
+	"net/http"
+	"os"
+	"os/signal"
+	"strings"
+	"syscall"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/core"
+)
+
+func main() {
+	addr := flag.String("addr", "127.0.0.1:7878", "Address to listen on")
+	token := flag.String("token", os.Getenv("AGENTSANDBOXD_TOKEN"), "Bearer token clients must send (default from AGENTSANDBOXD_TOKEN); required off loopback")
+	onShutdown := flag.String("on-shutdown", envOr("AGENTSANDBOXD_ON_SHUTDOWN", shutdownKeep), "What to do with sandboxes API runs created or started when shutting down: keep, stop or remove (default from AGENTSANDBOXD_ON_SHUTDOWN)")
+	webhook := &webhookConfig{repos: repoFlag{}}
+	flag.StringVar(&webhook.secret, "webhook-secret", os.Getenv("AGENTSANDBOXD_WEBHOOK_SECRET"), "Secret of the GitHub webhook (default from AGENTSANDBOXD_WEBHOOK_SECRET); enables "+webhookPath)
+	flag.Var(webhook.repos, "webhook-repo", "Local checkout for a repository's webhook runs, as owner/name=/path (repeatable)")
+	flag.StringVar(&webhook.label, "webhook-label", "agent", "Issue label that starts a webhook run")
+	flag.StringVar(&webhook.command, "webhook-command", "/agent fix", "Pull request comment prefix that starts a webhook run")
+	agent := flag.String("webhook-agent", "", "Agent for webhook runs (default from default_agent, else claude)")
+	flag.Parse()
+
+	if err := run(*addr, *token, *onShutdown, webhook, *agent); err != nil {
+		log.Fatalf("agentsandboxd: %v", err)
+	}
+}
+
+// run serves the API until a signal or POST /api/shutdown. Shutting down cancels the
+// runs in flight, including webhook runs, waits for them to finish, then keeps, stops or removes the
+// sandboxes the API created or started as onShutdown says.
+func run(addr, token, onShutdown string, webhook *webhookConfig, agent string) error {
+	if token == "" && !isLoopback(addr) {
+		return fmt.Errorf("refusing to serve on %s without --token; anyone who can reach it could read session logs", addr)
+	}
+	if err := validateShutdownMode(onShutdown); err != nil {
+		return err
+	}
+	if err := webhook.validate(); err != nil {
+		return err
+	}
+	if webhook.secret != "" {
+		if agent == "" {
+			agent = string(core.AgentClaude)
+			if settings, err := core.LoadSettings(); err == nil && settings.DefaultAgent != "" {
+				agent = settings.DefaultAgent
+			}
+		}
+		parsed, err := core.ParseAgent(agent)
+		if err != nil {
+			return err
+		}
+		webhook.agent = parsed
+	}
+	if err := core.CheckDocker(); err != nil {
+		return err
+	}
+
+	listener, err := net.Listen("tcp", addr)
+	if err != nil {
+		return fmt.Errorf("failed to listen on %s: %w", addr, err)
+	}
+	handler := newServer(token)
+	handler.webhook = webhook
+	server := &http.Server{Handler: handler, ReadHeaderTimeout: 10 * time.Second}
+
+	ctx, stop := signal.NotifyContext(context.Background(), os.Interrupt, syscall.SIGTERM)
+	defer stop()
+	released := make(chan struct{})
+	go func() {
+		defer close(released)
+		mode := onShutdown
+		select {
+		case <-ctx.Done():
+		case requested := <-handler.shutdownRequests:
+			if requested != "" {
+				mode = requested
+			}
+		}
+		log.Printf("agentsandboxd shutting down")
+
+		// Runs end with a done event once their docker exec is killed and reaped
+		handler.cancelRuns()
+		shutdownCtx, cancel := context.WithTimeout(context.Background(), 10*time.Second)
+		defer cancel()
+		server.Shutdown(shutdownCtx)
+		handler.waitBackground(shutdownCtx)
+		handler.releaseSandboxes(mode)
+	}()
+
+	log.Printf("agentsandboxd listening on http://%s", listener.Addr())
+	if err := server.Serve(listener); err != nil && !errors.Is(err, http.ErrServerClosed) {
+		return err
+	}
+	<-released
+	return nil
+}
+
+// envOr returns the environment variable key, or fallback when it is unset
+func envOr(key, fallback string) string {
+	if value := os.Getenv(key); value != "" {
+		return value
+	}
+	return fallback
+}
+
+// isLoopback reports whether addr only accepts connections from this machine
+func isLoopback(addr string) bool {
+	host, _, err := net.SplitHostPort(addr)
+	if err != nil {
+		return false
+	}
+	return isLoopbackHost(host)
+}
+
+// isLoopbackHost reports whether host, without a port, names this machine
+func isLoopbackHost(host string) bool {
+	if host == "localhost" {
+		return true
+	}
+	ip := net.ParseIP(strings.Trim(host, "[]"))
+	return ip != nil && ip.IsLoopback()
+}
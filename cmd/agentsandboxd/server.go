@@ -0,0 +1,397 @@
+package main
+
+import (
+	"context"
+	"crypto/rand"
+	"crypto/subtle"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"log"
+	"mime"
+	"net"
+	"net/http"
+	"path/filepath"
+	"strings"
+	"sync"
+
+	"github.com/thaodangspace/agentsandbox/core"
+)
+
+// maxRequestBody is the largest request body the server reads
+const maxRequestBody = 1 << 20
+
+// What happens to the sandboxes the API created or started when the server shuts down
+const (
+	shutdownKeep   = "keep"
+	shutdownStop   = "stop"
+	shutdownRemove = "remove"
+)
+
+// errShuttingDown ends the runs still going when the server shuts down
+var errShuttingDown = errors.New("agentsandboxd is shutting down")
+
+// server answers the HTTP API with what the core package reports
+type server struct {
+	token    string
+	mux      *http.ServeMux
+	// shareKey signs terminal share links; it is made anew each time the server starts
+	shareKey []byte
+
+	// ctx is cancelled when the server shuts down, cancelling the runs in flight
+	ctx    context.Context
+	cancel context.CancelCauseFunc
+	// shutdownRequests receives the container handling asked for with POST /api/shutdown
+	shutdownRequests chan string
+	// background counts the webhook runs in flight
+	background sync.WaitGroup
+
+	// webhook configures POST /api/webhooks/github; nil turns it off
+	webhook *webhookConfig
+
+	mu sync.Mutex
+	// started holds the sandboxes runs created, started or unpaused
+	started map[string]bool
+}
+
+func newServer(token string) *server {
+	ctx, cancel := context.WithCancelCause(context.Background())
+	s := &server{
+		token:            token,
+		mux:              http.NewServeMux(),
+		shareKey:         make([]byte, 32),
+		ctx:              ctx,
+		cancel:           cancel,
+		shutdownRequests: make(chan string, 1),
+		started:          make(map[string]bool),
+	}
+	rand.Read(s.shareKey)
+	s.mux.HandleFunc("GET /healthz", s.health)
+	s.mux.HandleFunc("GET /api/sandboxes", s.listSandboxes)
+	s.mux.HandleFunc("GET /api/sandboxes/{name}", s.getSandbox)
+	s.mux.HandleFunc("GET /api/test-results/{name}", s.testResults)
+	s.mux.HandleFunc("GET /api/logs/{name}", s.sessionLogs)
+	s.mux.HandleFunc("GET /api/logs/{name}/{file}", s.sessionLog)
+	s.mux.HandleFunc("POST /api/run", s.run)
+	s.mux.HandleFunc("POST /api/shutdown", s.shutdown)
+	s.mux.HandleFunc("GET /api/terminal/{name}", s.terminal)
+	s.mux.HandleFunc("POST /api/terminal/{name}/share", s.shareTerminal)
+	s.mux.HandleFunc("POST "+webhookPath, s.githubWebhook)
+	return s
+}
+
+// ServeHTTP checks the bearer token, when one is set, before routing the request.
+// The health check is left open for load balancers and probes. Without a token only
+// requests addressed to a loopback host are served, so a web page cannot reach the
+// server by rebinding its own domain to 127.0.0.1. GitHub webhook deliveries carry a
+// signature instead, which their handler checks, and so do terminal share links, which
+// are meant to be opened from a web page.
+func (s *server) ServeHTTP(w http.ResponseWriter, r *http.Request) {
+	if r.URL.Path == webhookPath || s.isSignedTerminalRequest(r) {
+		s.mux.ServeHTTP(w, r)
+		return
+	}
+	if s.token == "" && !isLoopbackRequest(r) {
+		writeJSON(w, http.StatusForbidden, errorBody("forbidden", "requests without a token must be addressed to localhost"))
+		return
+	}
+	// Browsers send an Origin with every cross-origin POST, even ones they need no
+	// preflight for, and with every WebSocket handshake, which no same-origin policy
+	// guards; no web page has any business starting runs, opening shells or shutting the
+	// server down
+	if (r.Method != http.MethodGet || isWebSocketRequest(r)) && r.Header.Get("Origin") != "" {
+		writeJSON(w, http.StatusForbidden, errorBody("forbidden", "requests from web pages are not accepted"))
+		return
+	}
+	if s.token != "" && r.URL.Path != "/healthz" {
+		token, _ := strings.CutPrefix(r.Header.Get("Authorization"), "Bearer ")
+		if subtle.ConstantTimeCompare([]byte(token), []byte(s.token)) != 1 {
+			writeJSON(w, http.StatusUnauthorized, errorBody("unauthorized", "missing or wrong bearer token"))
+			return
+		}
+	}
+	s.mux.ServeHTTP(w, r)
+}
+
+func (s *server) health(w http.ResponseWriter, r *http.Request) {
+	if err := core.CheckDocker(); err != nil {
+		writeError(w, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, map[string]string{"status": "ok"})
+}
+
+func (s *server) listSandboxes(w http.ResponseWriter, r *http.Request) {
+	sandboxes, err := core.List(r.URL.Query().Get("all") != "false")
+	if err != nil {
+		writeError(w, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, sandboxes)
+}
+
+func (s *server) getSandbox(w http.ResponseWriter, r *http.Request) {
+	name, err := core.Resolve(r.PathValue("name"), "")
+	if err != nil {
+		writeError(w, err)
+		return
+	}
+	sandboxes, err := core.List(true)
+	if err != nil {
+		writeError(w, err)
+		return
+	}
+	for _, sandbox := range sandboxes {
+		if sandbox.Name == name {
+			writeJSON(w, http.StatusOK, sandbox)
+			return
+		}
+	}
+	writeJSON(w, http.StatusNotFound, errorBody(core.KindContainerNotFound.String(), "sandbox "+name+" is not listed"))
+}
+
+func (s *server) testResults(w http.ResponseWriter, r *http.Request) {
+	name, dir, err := resolveSandbox(r.PathValue("name"))
+	if err != nil {
+		writeError(w, err)
+		return
+	}
+	results, err := core.TestResults(name, dir)
+	if err != nil {
+		writeError(w, err)
+		return
+	}
+	writeJSON(w, http.StatusOK, results)
+}
+
+func (s *server) sessionLogs(w http.ResponseWriter, r *http.Request) {
+	name, dir, err := resolveSandbox(r.PathValue("name"))
+	if err != nil {
+		writeError(w, err)
+		return
+	}
+	files, err := core.SessionLogs(name, dir)
+	if err != nil {
+		writeError(w, err)
+		return
+	}
+	names := make([]string, 0, len(files))
+	for _, file := range files {
+		names = append(names, filepath.Base(file))
+	}
+	writeJSON(w, http.StatusOK, names)
+}
+
+func (s *server) sessionLog(w http.ResponseWriter, r *http.Request) {
+	name, dir, err := resolveSandbox(r.PathValue("name"))
+	if err != nil {
+		writeError(w, err)
+		return
+	}
+	files, err := core.SessionLogs(name, dir)
+	if err != nil {
+		writeError(w, err)
+		return
+	}
+	// Only files the sandbox's log listing returns can be read
+	for _, file := range files {
+		if filepath.Base(file) != r.PathValue("file") {
+			continue
+		}
+		events, err := core.ParseLog(file)
+		if err != nil {
+			writeError(w, err)
+			return
+		}
+		writeJSON(w, http.StatusOK, core.RedactLog(events))
+		return
+	}
+	writeJSON(w, http.StatusNotFound, errorBody("not_found", "no session log "+r.PathValue("file")))
+}
+
+// runRequest is the body of POST /api/run
+type runRequest struct {
+	Path   string `json:"path"`
+	Agent  string `json:"agent"`
+	Prompt string `json:"prompt"`
+}
+
+// run sends a prompt to an agent in the sandbox for a project and streams its output as
+// server-sent events: an "output" event per chunk, then a "done" event with the sandbox,
+// the session log and the agent's exit code. Disconnecting or shutting the server down
+// cancels the run.
+func (s *server) run(w http.ResponseWriter, r *http.Request) {
+	// Browsers cannot send a JSON body to another origin without a preflight, which this
+	// server does not answer, so a web page cannot start runs on the user's machine
+	if mediaType, _, _ := mime.ParseMediaType(r.Header.Get("Content-Type")); mediaType != "application/json" {
+		writeJSON(w, http.StatusUnsupportedMediaType, errorBody(core.KindUsage.String(), "the request body must be application/json"))
+		return
+	}
+	var req runRequest
+	if err := json.NewDecoder(io.LimitReader(r.Body, maxRequestBody)).Decode(&req); err != nil {
+		writeJSON(w, http.StatusBadRequest, errorBody(core.KindUsage.String(), "invalid request body: "+err.Error()))
+		return
+	}
+	if req.Path == "" || req.Prompt == "" {
+		writeJSON(w, http.StatusBadRequest, errorBody(core.KindUsage.String(), "path and prompt are required"))
+		return
+	}
+	if req.Agent == "" {
+		if settings, err := core.LoadSettings(); err == nil {
+			req.Agent = settings.DefaultAgent
+		}
+	}
+	agent, err := core.ParseAgent(req.Agent)
+	if err != nil {
+		writeError(w, err)
+		return
+	}
+
+	stream, err := newEventStream(w)
+	if err != nil {
+		writeError(w, err)
+		return
+	}
+	ctx, cancel := context.WithCancelCause(r.Context())
+	defer cancel(nil)
+	stopFollowing := context.AfterFunc(s.ctx, func() { cancel(context.Cause(s.ctx)) })
+	defer stopFollowing()
+
+	result, err := core.Run(ctx, req.Path, agent, req.Prompt, stream)
+	s.track(result)
+	if err != nil && ctx.Err() != nil {
+		err = context.Cause(ctx)
+	}
+	stream.finish(result, err)
+}
+
+// shutdown shuts the server down once the response is sent. The containers query
+// parameter overrides --on-shutdown for the sandboxes runs created or started.
+func (s *server) shutdown(w http.ResponseWriter, r *http.Request) {
+	mode := r.URL.Query().Get("containers")
+	if mode != "" {
+		if err := validateShutdownMode(mode); err != nil {
+			writeJSON(w, http.StatusBadRequest, errorBody(core.KindUsage.String(), err.Error()))
+			return
+		}
+	}
+	select {
+	case s.shutdownRequests <- mode:
+	default:
+		// A shutdown is already on its way
+	}
+	writeJSON(w, http.StatusAccepted, map[string]string{"status": "shutting down"})
+}
+
+// track remembers the sandbox of a run that created or started it
+func (s *server) track(result *core.RunResult) {
+	if result == nil || !result.Started {
+		return
+	}
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	s.started[result.Sandbox] = true
+}
+
+// cancelRuns ends every run in flight; each sends a final done event with the reason
+func (s *server) cancelRuns() {
+	s.cancel(errShuttingDown)
+}
+
+// waitBackground waits for cancelled webhook runs to record their outcome, or until ctx
+// is done
+func (s *server) waitBackground(ctx context.Context) {
+	done := make(chan struct{})
+	go func() {
+		s.background.Wait()
+		close(done)
+	}()
+	select {
+	case <-done:
+	case <-ctx.Done():
+	}
+}
+
+// releaseSandboxes stops or removes the sandboxes runs created or started, as mode says
+func (s *server) releaseSandboxes(mode string) {
+	if mode == shutdownKeep || mode == "" {
+		return
+	}
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	for name := range s.started {
+		var err error
+		if mode == shutdownRemove {
+			err = core.Remove(name)
+		} else {
+			err = core.Stop(name)
+		}
+		if err != nil {
+			log.Printf("Warning: failed to %s %s: %v", mode, name, err)
+		}
+	}
+}
+
+// validateShutdownMode checks a --on-shutdown or containers value
+func validateShutdownMode(mode string) error {
+	switch mode {
+	case shutdownKeep, shutdownStop, shutdownRemove:
+		return nil
+	}
+	return fmt.Errorf("invalid container handling %q (valid: keep, stop, remove)", mode)
+}
+
+// isLoopbackRequest reports whether the request's Host header names this machine
+func isLoopbackRequest(r *http.Request) bool {
+	host := r.Host
+	if h, _, err := net.SplitHostPort(host); err == nil {
+		host = h
+	}
+	return isLoopbackHost(host)
+}
+
+// resolveSandbox returns the container name and project directory for a container or
+// session name
+func resolveSandbox(name string) (string, string, error) {
+	name, err := core.Resolve(name, "")
+	if err != nil {
+		return "", "", err
+	}
+	dir, err := core.Directory(name)
+	if err != nil {
+		return "", "", err
+	}
+	return name, dir, nil
+}
+
+// errorBody is the JSON body of a failed request, shaped like the CLI's
+// --error-format json output
+func errorBody(kind, message string) map[string]interface{} {
+	return map[string]interface{}{
+		"error": map[string]string{"kind": kind, "message": message},
+	}
+}
+
+// writeError answers with err and a status code for its kind
+func writeError(w http.ResponseWriter, err error) {
+	kind := core.ErrorKindOf(err)
+	status := http.StatusInternalServerError
+	switch kind {
+	case core.KindContainerNotFound:
+		status = http.StatusNotFound
+	case core.KindDockerUnavailable:
+		status = http.StatusServiceUnavailable
+	case core.KindPermissionDenied:
+		status = http.StatusForbidden
+	case core.KindUsage:
+		status = http.StatusBadRequest
+	}
+	writeJSON(w, status, errorBody(kind.String(), err.Error()))
+}
+
+func writeJSON(w http.ResponseWriter, status int, body interface{}) {
+	w.Header().Set("Content-Type", "application/json")
+	w.WriteHeader(status)
+	json.NewEncoder(w).Encode(body)
+}
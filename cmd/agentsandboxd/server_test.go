@@ -0,0 +1,166 @@
+package main
+
+import (
+	"errors"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/thaodangspace/agentsandbox/core"
+)
+
+func TestServerToken(t *testing.T) {
+	s := newServer("secret")
+	for _, tt := range []struct {
+		auth string
+		want int
+	}{
+		{"", http.StatusUnauthorized},
+		{"Bearer wrong", http.StatusUnauthorized},
+		{"secret", http.StatusUnauthorized},
+	} {
+		req := httptest.NewRequest(http.MethodGet, "/api/sandboxes", nil)
+		if tt.auth != "" {
+			req.Header.Set("Authorization", tt.auth)
+		}
+		rec := httptest.NewRecorder()
+		s.ServeHTTP(rec, req)
+		if rec.Code != tt.want {
+			t.Errorf("Authorization %q: status %d, want %d", tt.auth, rec.Code, tt.want)
+		}
+	}
+
+	// Unknown routes are only reported once the token checks out
+	req := httptest.NewRequest(http.MethodGet, "/api/unknown", nil)
+	req.Header.Set("Authorization", "Bearer secret")
+	rec := httptest.NewRecorder()
+	s.ServeHTTP(rec, req)
+	if rec.Code != http.StatusNotFound {
+		t.Errorf("unknown route: status %d, want %d", rec.Code, http.StatusNotFound)
+	}
+}
+
+func TestIsLoopback(t *testing.T) {
+	for addr, want := range map[string]bool{
+		"127.0.0.1:7878": true,
+		"localhost:7878": true,
+		"[::1]:7878":     true,
+		":7878":          false,
+		"0.0.0.0:7878":   false,
+		"10.0.0.5:7878":  false,
+		"not-an-address": false,
+	} {
+		if got := isLoopback(addr); got != want {
+			t.Errorf("isLoopback(%q) = %v, want %v", addr, got, want)
+		}
+	}
+}
+
+func TestServerRejectsForeignHostWithoutToken(t *testing.T) {
+	s := newServer("")
+	for host, want := range map[string]int{
+		"127.0.0.1:7878":    http.StatusNotFound,
+		"localhost:7878":    http.StatusNotFound,
+		"[::1]:7878":        http.StatusNotFound,
+		"attacker.example":  http.StatusForbidden,
+		"rebind.test:7878":  http.StatusForbidden,
+		"192.168.1.10:7878": http.StatusForbidden,
+	} {
+		req := httptest.NewRequest(http.MethodGet, "/api/unknown", nil)
+		req.Host = host
+		rec := httptest.NewRecorder()
+		s.ServeHTTP(rec, req)
+		if rec.Code != want {
+			t.Errorf("Host %q: status %d, want %d", host, rec.Code, want)
+		}
+	}
+}
+
+func TestRunRejectsBadRequests(t *testing.T) {
+	s := newServer("")
+	for _, tt := range []struct {
+		name        string
+		contentType string
+		body        string
+		want        int
+	}{
+		{"form post", "text/plain", `{"path": "/tmp", "prompt": "hi"}`, http.StatusUnsupportedMediaType},
+		{"bad json", "application/json", `{`, http.StatusBadRequest},
+		{"no prompt", "application/json", `{"path": "/tmp"}`, http.StatusBadRequest},
+		{"unknown agent", "application/json", `{"path": "/tmp", "agent": "nobody", "prompt": "hi"}`, http.StatusBadRequest},
+	} {
+		req := httptest.NewRequest(http.MethodPost, "/api/run", strings.NewReader(tt.body))
+		req.Header.Set("Content-Type", tt.contentType)
+		rec := httptest.NewRecorder()
+		s.ServeHTTP(rec, req)
+		if rec.Code != tt.want {
+			t.Errorf("%s: status %d, want %d", tt.name, rec.Code, tt.want)
+		}
+	}
+}
+
+func TestEventStream(t *testing.T) {
+	rec := httptest.NewRecorder()
+	stream, err := newEventStream(rec)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// "é" split across two writes arrives in one event
+	stream.Write([]byte("caf\xc3"))
+	stream.Write([]byte("\xa9\n"))
+	stream.finish(&core.RunResult{Sandbox: "box", Session: "s.jsonl", ExitCode: 2}, nil)
+
+	want := "event: output\ndata: {\"text\":\"caf\"}\n\n" +
+		"event: output\ndata: {\"text\":\"é\\n\"}\n\n" +
+		"event: done\ndata: {\"exit_code\":2,\"sandbox\":\"box\",\"session\":\"s.jsonl\"}\n\n"
+	if got := rec.Body.String(); got != want {
+		t.Errorf("stream = %q, want %q", got, want)
+	}
+	if ct := rec.Header().Get("Content-Type"); ct != "text/event-stream" {
+		t.Errorf("Content-Type = %q", ct)
+	}
+}
+
+func TestEventStreamErrorBeforeOutput(t *testing.T) {
+	rec := httptest.NewRecorder()
+	stream, err := newEventStream(rec)
+	if err != nil {
+		t.Fatal(err)
+	}
+	stream.finish(nil, errors.New("docker daemon is not running"))
+	if rec.Code != http.StatusServiceUnavailable {
+		t.Errorf("status %d, want %d", rec.Code, http.StatusServiceUnavailable)
+	}
+}
+
+func TestShutdownRequest(t *testing.T) {
+	s := newServer("")
+
+	req := httptest.NewRequest(http.MethodPost, "/api/shutdown?containers=destroy", nil)
+	rec := httptest.NewRecorder()
+	s.ServeHTTP(rec, req)
+	if rec.Code != http.StatusBadRequest {
+		t.Errorf("unknown handling: status %d, want %d", rec.Code, http.StatusBadRequest)
+	}
+
+	req = httptest.NewRequest(http.MethodPost, "/api/shutdown?containers=remove", nil)
+	rec = httptest.NewRecorder()
+	s.ServeHTTP(rec, req)
+	if rec.Code != http.StatusAccepted {
+		t.Fatalf("status %d, want %d", rec.Code, http.StatusAccepted)
+	}
+	if mode := <-s.shutdownRequests; mode != shutdownRemove {
+		t.Errorf("requested %q, want %q", mode, shutdownRemove)
+	}
+
+	// A web page posting to the server is turned away
+	req = httptest.NewRequest(http.MethodPost, "/api/shutdown", nil)
+	req.Header.Set("Origin", "https://attacker.example")
+	rec = httptest.NewRecorder()
+	s.ServeHTTP(rec, req)
+	if rec.Code != http.StatusForbidden {
+		t.Errorf("cross-origin: status %d, want %d", rec.Code, http.StatusForbidden)
+	}
+}
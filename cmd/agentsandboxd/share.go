@@ -0,0 +1,119 @@
+package main
+
+import (
+	"crypto/hmac"
+	"crypto/sha256"
+	"encoding/base64"
+	"encoding/json"
+	"fmt"
+	"io"
+	"mime"
+	"net/http"
+	"net/url"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/core"
+)
+
+// terminalPathPrefix starts the path of every terminal WebSocket
+const terminalPathPrefix = "/api/terminal/"
+
+const (
+	// defaultShareTTL is how long a share link works unless the request says otherwise
+	defaultShareTTL = time.Hour
+	// maxShareTTL is the longest a share link can work
+	maxShareTTL = 24 * time.Hour
+)
+
+// shareRequest is the body of POST /api/terminal/{name}/share
+type shareRequest struct {
+	Mode string `json:"mode"`
+	TTL  string `json:"ttl"`
+}
+
+// shareTerminal mints a link that opens the terminal of a sandbox, in the mode asked
+// for, without the server's token until it expires. Links are signed with a key made
+// when the server starts, so restarting the server revokes them all.
+func (s *server) shareTerminal(w http.ResponseWriter, r *http.Request) {
+	// As for runs, a JSON body keeps web pages from minting links
+	if mediaType, _, _ := mime.ParseMediaType(r.Header.Get("Content-Type")); mediaType != "application/json" {
+		writeJSON(w, http.StatusUnsupportedMediaType, errorBody(core.KindUsage.String(), "the request body must be application/json"))
+		return
+	}
+	var req shareRequest
+	if err := json.NewDecoder(io.LimitReader(r.Body, maxRequestBody)).Decode(&req); err != nil {
+		writeJSON(w, http.StatusBadRequest, errorBody(core.KindUsage.String(), "invalid request body: "+err.Error()))
+		return
+	}
+	if req.Mode == "" {
+		req.Mode = modeObserve
+	}
+	if req.Mode != modeInteractive && req.Mode != modeObserve {
+		writeJSON(w, http.StatusBadRequest, errorBody(core.KindUsage.String(), fmt.Sprintf("invalid mode %q (valid: interactive, observe)", req.Mode)))
+		return
+	}
+	ttl := defaultShareTTL
+	if req.TTL != "" {
+		var err error
+		if ttl, err = time.ParseDuration(req.TTL); err != nil || ttl <= 0 || ttl > maxShareTTL {
+			writeJSON(w, http.StatusBadRequest, errorBody(core.KindUsage.String(), fmt.Sprintf("invalid ttl %q (a duration up to %s)", req.TTL, maxShareTTL)))
+			return
+		}
+	}
+	name, err := core.Resolve(r.PathValue("name"), "")
+	if err != nil {
+		writeError(w, err)
+		return
+	}
+
+	expires := time.Now().Add(ttl).Truncate(time.Second)
+	writeJSON(w, http.StatusCreated, map[string]string{
+		"path":    s.sharePath(name, req.Mode, expires),
+		"mode":    req.Mode,
+		"expires": expires.UTC().Format(time.RFC3339),
+	})
+}
+
+// sharePath returns the path and query of a signed link to a sandbox's terminal
+func (s *server) sharePath(name, mode string, expires time.Time) string {
+	query := url.Values{
+		"mode":    {mode},
+		"expires": {strconv.FormatInt(expires.Unix(), 10)},
+		"sig":     {s.shareSignature(name, mode, expires.Unix())},
+	}
+	return terminalPathPrefix + url.PathEscape(name) + "?" + query.Encode()
+}
+
+// shareSignature signs a link to the terminal of name in mode until expires
+func (s *server) shareSignature(name, mode string, expires int64) string {
+	mac := hmac.New(sha256.New, s.shareKey)
+	fmt.Fprintf(mac, "%s\n%s\n%d", name, mode, expires)
+	return base64.RawURLEncoding.EncodeToString(mac.Sum(nil))
+}
+
+// isSignedTerminalRequest reports whether r opens a terminal with a share link that is
+// signed for its sandbox and mode and has not expired. Other requests, including ones
+// for other paths that carry a signature, are left to the usual checks.
+func (s *server) isSignedTerminalRequest(r *http.Request) bool {
+	name, ok := strings.CutPrefix(r.URL.Path, terminalPathPrefix)
+	if !ok || name == "" || strings.Contains(name, "/") || r.Method != http.MethodGet {
+		return false
+	}
+	query := r.URL.Query()
+	sig := query.Get("sig")
+	if sig == "" {
+		return false
+	}
+	expires, err := strconv.ParseInt(query.Get("expires"), 10, 64)
+	if err != nil || time.Now().Unix() >= expires {
+		return false
+	}
+	// The terminal handler treats a missing mode as interactive, so the signature must too
+	mode := query.Get("mode")
+	if mode == "" {
+		mode = modeInteractive
+	}
+	return hmac.Equal([]byte(sig), []byte(s.shareSignature(name, mode, expires)))
+}
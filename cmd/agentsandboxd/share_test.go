@@ -0,0 +1,76 @@
+package main
+
+import (
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestIsSignedTerminalRequest(t *testing.T) {
+	s := newServer("secret")
+	expires := time.Now().Add(time.Hour)
+	link := s.sharePath("agentsandbox-api", modeObserve, expires)
+
+	signed := func(target string) bool {
+		return s.isSignedTerminalRequest(httptest.NewRequest(http.MethodGet, target, nil))
+	}
+	if !signed(link) {
+		t.Fatalf("%s is not accepted", link)
+	}
+	for name, target := range map[string]string{
+		"other mode":    strings.Replace(link, "mode=observe", "mode=interactive", 1),
+		"no mode":       strings.Replace(link, "mode=observe&", "", 1),
+		"other sandbox": strings.Replace(link, "agentsandbox-api", "agentsandbox-web", 1),
+		"other path":    strings.Replace(link, terminalPathPrefix, "/api/sandboxes/", 1),
+		"share path":    strings.Replace(link, "agentsandbox-api?", "agentsandbox-api/share?", 1),
+		"expired":       s.sharePath("agentsandbox-api", modeObserve, time.Now().Add(-time.Second)),
+		"other server":  newServer("secret").sharePath("agentsandbox-api", modeObserve, expires),
+		"unsigned":      terminalPathPrefix + "agentsandbox-api?mode=observe",
+	} {
+		if signed(target) {
+			t.Errorf("%s: %s is accepted", name, target)
+		}
+	}
+	if s.isSignedTerminalRequest(httptest.NewRequest(http.MethodPost, link, nil)) {
+		t.Error("a POST with a share link is accepted")
+	}
+}
+
+func TestShareLinkSkipsTokenAndOriginChecks(t *testing.T) {
+	s := newServer("secret")
+	req := httptest.NewRequest(http.MethodGet, s.sharePath("agentsandbox-api", modeObserve, time.Now().Add(time.Hour)), nil)
+	req.Header.Set("Origin", "https://pairing.example")
+	rec := httptest.NewRecorder()
+	s.ServeHTTP(rec, req)
+	// The request reaches the terminal handler, which wants a WebSocket handshake
+	if rec.Code != http.StatusBadRequest || !strings.Contains(rec.Body.String(), "WebSocket") {
+		t.Errorf("status %d, body %s", rec.Code, rec.Body)
+	}
+}
+
+func TestShareTerminalRejectsBadRequests(t *testing.T) {
+	s := newServer("secret")
+	for _, tt := range []struct {
+		contentType string
+		body        string
+		want        int
+	}{
+		{"text/plain", `{}`, http.StatusUnsupportedMediaType},
+		{"application/json", `{`, http.StatusBadRequest},
+		{"application/json", `{"mode": "drive"}`, http.StatusBadRequest},
+		{"application/json", `{"ttl": "48h"}`, http.StatusBadRequest},
+		{"application/json", `{"ttl": "-1m"}`, http.StatusBadRequest},
+		{"application/json", `{"ttl": "soon"}`, http.StatusBadRequest},
+	} {
+		req := httptest.NewRequest(http.MethodPost, "/api/terminal/x/share", strings.NewReader(tt.body))
+		req.Header.Set("Authorization", "Bearer secret")
+		req.Header.Set("Content-Type", tt.contentType)
+		rec := httptest.NewRecorder()
+		s.ServeHTTP(rec, req)
+		if rec.Code != tt.want {
+			t.Errorf("%s %s: status %d, want %d", tt.contentType, tt.body, rec.Code, tt.want)
+		}
+	}
+}
@@ -0,0 +1,223 @@
+package main
+
+import (
+	"context"
+	"encoding/binary"
+	"fmt"
+	"log"
+	"net/http"
+	"net/url"
+	"strconv"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/core"
+)
+
+// Message types of the terminal protocol. Every message is a binary WebSocket message
+// whose first byte is its type.
+const (
+	// terminalData carries output from the server and keystrokes from the client
+	terminalData = 0x00
+	// terminalResize asks for a new size: rows then columns, as big-endian uint16s
+	terminalResize = 0x01
+	// terminalExit is the server's last message: the shell's exit code as a big-endian
+	// int32, or -1 when it had to be killed
+	terminalExit = 0x02
+)
+
+const (
+	// terminalChunk is the most output sent in one message
+	terminalChunk = 16 << 10
+	// terminalQueue is how many chunks of output wait for a slow client before the
+	// shell is made to wait too
+	terminalQueue = 16
+	// pingInterval is how often clients are pinged; one that sends nothing for two
+	// intervals is disconnected
+	pingInterval = 20 * time.Second
+	// defaultRows and defaultCols size a terminal when the client does not
+	defaultRows = 24
+	defaultCols = 80
+)
+
+// Terminal modes: a shell of one's own, or a view of the agent session that drops input
+const (
+	modeInteractive = "interactive"
+	modeObserve     = "observe"
+)
+
+// terminal opens a shell in a sandbox over a WebSocket. The rows and cols query
+// parameters set its size. Disconnecting or shutting the server down hangs it up. With
+// mode=observe it streams the agent session running in the sandbox instead, and
+// ignores what the client types.
+func (s *server) terminal(w http.ResponseWriter, r *http.Request) {
+	// Check the handshake before starting the shell, so failures get a JSON error
+	if err := checkWebSocketHandshake(r); err != nil {
+		writeJSON(w, http.StatusBadRequest, errorBody(core.KindUsage.String(), err.Error()))
+		return
+	}
+	rows, cols, err := terminalSize(r.URL.Query())
+	if err != nil {
+		writeJSON(w, http.StatusBadRequest, errorBody(core.KindUsage.String(), err.Error()))
+		return
+	}
+	mode := r.URL.Query().Get("mode")
+	if mode == "" {
+		mode = modeInteractive
+	}
+	if mode != modeInteractive && mode != modeObserve {
+		writeJSON(w, http.StatusBadRequest, errorBody(core.KindUsage.String(), fmt.Sprintf("invalid mode %q (valid: interactive, observe)", mode)))
+		return
+	}
+	name, err := core.Resolve(r.PathValue("name"), "")
+	if err != nil {
+		writeError(w, err)
+		return
+	}
+
+	s.background.Add(1)
+	defer s.background.Done()
+	ctx, cancel := context.WithCancel(s.ctx)
+	defer cancel()
+	var term core.Terminal
+	if mode == modeObserve {
+		term, err = core.ObserveSession(ctx, name)
+	} else {
+		term, err = core.OpenTerminal(ctx, name, rows, cols)
+	}
+	if err != nil {
+		writeError(w, err)
+		return
+	}
+	ws, err := upgradeWebSocket(w, r)
+	if err != nil {
+		term.Close()
+		log.Printf("Warning: failed to open a terminal WebSocket for %s: %v", name, err)
+		return
+	}
+	relayTerminal(ctx, ws, term)
+}
+
+// relayTerminal passes output from term to the client and keystrokes and resizes back
+// until the shell exits, the client goes away or ctx is done, then closes both. Output
+// waits in a bounded queue, so a client that falls behind holds the shell up rather
+// than filling the server's memory, and one that stops answering pings is dropped.
+func relayTerminal(ctx context.Context, ws *websocketConn, term core.Terminal) {
+	done := make(chan struct{})
+	defer close(done)
+
+	output := make(chan []byte, terminalQueue)
+	go func() {
+		defer close(output)
+		for {
+			buf := make([]byte, 1+terminalChunk)
+			buf[0] = terminalData
+			n, err := term.Read(buf[1:])
+			if n > 0 {
+				select {
+				case output <- buf[:1+n]:
+				case <-done:
+					return
+				}
+			}
+			if err != nil {
+				return
+			}
+		}
+	}()
+
+	clientErr := make(chan error, 1)
+	resizes := make(chan [2]int, 1)
+	go func() {
+		defer close(resizes)
+		for {
+			opcode, message, err := ws.readMessage(2 * pingInterval)
+			if err != nil {
+				clientErr <- err
+				return
+			}
+			if opcode != opBinary || len(message) == 0 {
+				clientErr <- errUnsupportedData
+				return
+			}
+			switch message[0] {
+			case terminalData:
+				// Keys typed after the shell exited are dropped; its exit is reported
+				// with the rest of its output
+				term.Write(message[1:])
+			case terminalResize:
+				if len(message) != 5 {
+					clientErr <- errUnsupportedData
+					return
+				}
+				size := [2]int{int(binary.BigEndian.Uint16(message[1:3])), int(binary.BigEndian.Uint16(message[3:5]))}
+				// Only the latest size matters while a resize is under way
+				select {
+				case <-resizes:
+				default:
+				}
+				resizes <- size
+			default:
+				clientErr <- errUnsupportedData
+				return
+			}
+		}
+	}()
+	go func() {
+		for size := range resizes {
+			if err := term.Resize(size[0], size[1]); err != nil {
+				log.Printf("Warning: %v", err)
+			}
+		}
+	}()
+
+	ping := time.NewTicker(pingInterval)
+	defer ping.Stop()
+	for {
+		select {
+		case chunk, ok := <-output:
+			if !ok {
+				code := term.Close()
+				ws.writeFrame(opBinary, binary.BigEndian.AppendUint32([]byte{terminalExit}, uint32(int32(code))))
+				ws.close(closeNormal, "")
+				return
+			}
+			if err := ws.writeFrame(opBinary, chunk); err != nil {
+				term.Close()
+				ws.conn.Close()
+				return
+			}
+		case <-ping.C:
+			if err := ws.writeFrame(opPing, nil); err != nil {
+				term.Close()
+				ws.conn.Close()
+				return
+			}
+		case err := <-clientErr:
+			term.Close()
+			ws.close(closeFor(err), "")
+			return
+		case <-ctx.Done():
+			term.Close()
+			ws.close(closeGoingAway, errShuttingDown.Error())
+			return
+		}
+	}
+}
+
+// terminalSize reads the rows and cols query parameters
+func terminalSize(query url.Values) (int, int, error) {
+	rows, cols := defaultRows, defaultCols
+	for _, param := range []struct {
+		name  string
+		value *int
+	}{{"rows", &rows}, {"cols", &cols}} {
+		if v := query.Get(param.name); v != "" {
+			n, err := strconv.Atoi(v)
+			if err != nil {
+				return 0, 0, fmt.Errorf("invalid %s %q", param.name, v)
+			}
+			*param.value = n
+		}
+	}
+	return rows, cols, nil
+}
@@ -0,0 +1,169 @@
+package main
+
+import (
+	"bytes"
+	"context"
+	"encoding/binary"
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"net/url"
+	"strings"
+	"sync"
+	"testing"
+)
+
+// fakeTerminal is a shell that prints what the test writes to shell and reports what
+// the client sent
+type fakeTerminal struct {
+	output *io.PipeReader
+	shell  *io.PipeWriter
+	typed  chan []byte
+	sizes  chan [2]int
+
+	closeOnce sync.Once
+	closed    chan struct{}
+}
+
+func newFakeTerminal() *fakeTerminal {
+	output, shell := io.Pipe()
+	return &fakeTerminal{
+		output: output,
+		shell:  shell,
+		typed:  make(chan []byte, 10),
+		sizes:  make(chan [2]int, 10),
+		closed: make(chan struct{}),
+	}
+}
+
+func (f *fakeTerminal) Read(p []byte) (int, error) { return f.output.Read(p) }
+
+func (f *fakeTerminal) Write(p []byte) (int, error) {
+	f.typed <- append([]byte(nil), p...)
+	return len(p), nil
+}
+
+func (f *fakeTerminal) Resize(rows, cols int) error {
+	f.sizes <- [2]int{rows, cols}
+	return nil
+}
+
+func (f *fakeTerminal) Close() int {
+	f.closeOnce.Do(func() {
+		f.output.Close()
+		close(f.closed)
+	})
+	return 3
+}
+
+func TestRelayTerminal(t *testing.T) {
+	ws, client, clientReader := pipeWebSocket(t)
+	term := newFakeTerminal()
+	relayed := make(chan struct{})
+	go func() {
+		relayTerminal(context.Background(), ws, term)
+		close(relayed)
+	}()
+
+	go term.shell.Write([]byte("$ "))
+	if op, payload := readServerFrame(t, clientReader); op != opBinary || !bytes.Equal(payload, []byte("\x00$ ")) {
+		t.Errorf("output frame = %d %q", op, payload)
+	}
+
+	writeClientFrame(t, client, true, opBinary, []byte("\x00ls\r"))
+	if got := <-term.typed; string(got) != "ls\r" {
+		t.Errorf("typed %q, want %q", got, "ls\r")
+	}
+	writeClientFrame(t, client, true, opBinary, []byte{terminalResize, 0, 40, 0, 120})
+	if got := <-term.sizes; got != [2]int{40, 120} {
+		t.Errorf("resized to %v, want [40 120]", got)
+	}
+
+	// The shell exits: the client gets its exit code, then a normal close
+	term.shell.Close()
+	op, payload := readServerFrame(t, clientReader)
+	if op != opBinary || len(payload) != 5 || payload[0] != terminalExit || int32(binary.BigEndian.Uint32(payload[1:])) != 3 {
+		t.Errorf("exit frame = %d %v", op, payload)
+	}
+	op, payload = readServerFrame(t, clientReader)
+	if op != opClose || len(payload) < 2 || binary.BigEndian.Uint16(payload) != closeNormal {
+		t.Errorf("close frame = %d %v", op, payload)
+	}
+	<-relayed
+}
+
+func TestRelayTerminalClosesShellWhenClientLeaves(t *testing.T) {
+	ws, client, clientReader := pipeWebSocket(t)
+	term := newFakeTerminal()
+	relayed := make(chan struct{})
+	go func() {
+		relayTerminal(context.Background(), ws, term)
+		close(relayed)
+	}()
+
+	writeClientFrame(t, client, true, opClose, binary.BigEndian.AppendUint16(nil, closeNormal))
+	if op, _ := readServerFrame(t, clientReader); op != opClose {
+		t.Errorf("server answered a close with opcode %d", op)
+	}
+	<-term.closed
+	<-relayed
+}
+
+func TestRelayTerminalRejectsTextMessages(t *testing.T) {
+	ws, client, clientReader := pipeWebSocket(t)
+	term := newFakeTerminal()
+	go relayTerminal(context.Background(), ws, term)
+
+	writeClientFrame(t, client, true, opText, []byte("ls"))
+	op, payload := readServerFrame(t, clientReader)
+	if op != opClose || len(payload) < 2 || binary.BigEndian.Uint16(payload) != closeUnsupported {
+		t.Errorf("close frame = %d %v", op, payload)
+	}
+	<-term.closed
+}
+
+func TestRelayTerminalHangsUpOnShutdown(t *testing.T) {
+	ws, _, clientReader := pipeWebSocket(t)
+	term := newFakeTerminal()
+	ctx, cancel := context.WithCancel(context.Background())
+	go relayTerminal(ctx, ws, term)
+
+	cancel()
+	op, payload := readServerFrame(t, clientReader)
+	if op != opClose || len(payload) < 2 || binary.BigEndian.Uint16(payload) != closeGoingAway {
+		t.Errorf("close frame = %d %v", op, payload)
+	}
+	<-term.closed
+}
+
+func TestTerminalSize(t *testing.T) {
+	for query, want := range map[string][2]int{
+		"":                 {defaultRows, defaultCols},
+		"rows=40":          {40, defaultCols},
+		"rows=40&cols=120": {40, 120},
+	} {
+		values, _ := url.ParseQuery(query)
+		rows, cols, err := terminalSize(values)
+		if err != nil || [2]int{rows, cols} != want {
+			t.Errorf("terminalSize(%q) = %d, %d, %v, want %v", query, rows, cols, err, want)
+		}
+	}
+	if _, _, err := terminalSize(url.Values{"cols": {"wide"}}); err == nil {
+		t.Error("terminalSize accepted cols=wide")
+	}
+}
+
+func TestTerminalRejectsUnknownMode(t *testing.T) {
+	s := newServer("")
+	req := httptest.NewRequest(http.MethodGet, "/api/terminal/x?mode=watch", nil)
+	req.Host = "localhost:7878"
+	req.Header.Set("Connection", "Upgrade")
+	req.Header.Set("Upgrade", "websocket")
+	req.Header.Set("Sec-WebSocket-Version", "13")
+	req.Header.Set("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ==")
+	rec := httptest.NewRecorder()
+	s.ServeHTTP(rec, req)
+	if rec.Code != http.StatusBadRequest || !strings.Contains(rec.Body.String(), "invalid mode") {
+		t.Errorf("status %d, body %s", rec.Code, rec.Body)
+	}
+}
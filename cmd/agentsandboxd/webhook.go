@@ -0,0 +1,195 @@
+package main
+
+import (
+	"crypto/hmac"
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"fmt"
+	"io"
+	"log"
+	"net/http"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/thaodangspace/agentsandbox/core"
+)
+
+// webhookPath is where GitHub delivers events. Deliveries are authenticated by their
+// signature rather than the bearer token, which GitHub cannot send.
+const webhookPath = "/api/webhooks/github"
+
+// maxWebhookBody is the largest delivery GitHub sends
+const maxWebhookBody = 25 << 20
+
+// webhookConfig says which GitHub deliveries start runs and where
+type webhookConfig struct {
+	// secret is the webhook's secret, which signs every delivery
+	secret string
+	// repos maps a repository's full name, in lower case, to its local checkout
+	repos repoFlag
+	// label starts a run on an issue it is added to
+	label string
+	// command starts a run on a pull request when a comment begins with it
+	command string
+	agent   core.Agent
+}
+
+// validate checks that deliveries can be verified and have somewhere to run
+func (c *webhookConfig) validate() error {
+	if c.secret == "" && len(c.repos) > 0 {
+		return fmt.Errorf("--webhook-repo needs --webhook-secret, or anyone could start runs")
+	}
+	if c.secret != "" && len(c.repos) == 0 {
+		return fmt.Errorf("--webhook-secret needs at least one --webhook-repo owner/name=/path/to/checkout")
+	}
+	return nil
+}
+
+// githubDelivery holds the fields of issues and issue_comment events the webhook reads
+type githubDelivery struct {
+	Action string `json:"action"`
+	Label  struct {
+		Name string `json:"name"`
+	} `json:"label"`
+	Issue struct {
+		Number      int              `json:"number"`
+		PullRequest *json.RawMessage `json:"pull_request"`
+	} `json:"issue"`
+	Comment struct {
+		Body              string `json:"body"`
+		AuthorAssociation string `json:"author_association"`
+	} `json:"comment"`
+	Repository struct {
+		FullName string `json:"full_name"`
+	} `json:"repository"`
+}
+
+// trustedAssociations are the commenters allowed to start runs on a pull request;
+// anyone can comment on a public repository
+var trustedAssociations = map[string]bool{"OWNER": true, "MEMBER": true, "COLLABORATOR": true}
+
+// task returns the run a delivery asks for, or nil and the reason it is ignored
+func (c *webhookConfig) task(event string, body []byte) (*core.IssueRun, string) {
+	var delivery githubDelivery
+	if err := json.Unmarshal(body, &delivery); err != nil {
+		return nil, "invalid payload: " + err.Error()
+	}
+
+	run := &core.IssueRun{Number: delivery.Issue.Number, Agent: c.agent}
+	switch {
+	case event == "issues" && delivery.Action == "labeled":
+		if delivery.Label.Name != c.label {
+			return nil, fmt.Sprintf("label %q does not start runs", delivery.Label.Name)
+		}
+	case event == "issue_comment" && delivery.Action == "created" && delivery.Issue.PullRequest != nil:
+		request, ok := strings.CutPrefix(strings.TrimSpace(delivery.Comment.Body), c.command)
+		if !ok {
+			return nil, "comment does not start with " + c.command
+		}
+		if !trustedAssociations[delivery.Comment.AuthorAssociation] {
+			return nil, "only owners, members and collaborators can start runs"
+		}
+		run.PullRequest = true
+		run.Request = strings.TrimSpace(request)
+	default:
+		return nil, fmt.Sprintf("%s %s events do not start runs", event, delivery.Action)
+	}
+
+	dir, ok := c.repos[strings.ToLower(delivery.Repository.FullName)]
+	if !ok {
+		return nil, fmt.Sprintf("no checkout configured for %s", delivery.Repository.FullName)
+	}
+	run.Dir = dir
+	return run, ""
+}
+
+// githubWebhook starts a run for an issue labeled for the agent or a pull request
+// comment asking for one. Runs happen in the background, since GitHub gives up on a
+// delivery after ten seconds, and comment their outcome when done.
+func (s *server) githubWebhook(w http.ResponseWriter, r *http.Request) {
+	if s.webhook == nil || s.webhook.secret == "" {
+		writeJSON(w, http.StatusNotFound, errorBody("not_found", "webhooks are not configured"))
+		return
+	}
+	body, err := io.ReadAll(io.LimitReader(r.Body, maxWebhookBody))
+	if err != nil {
+		writeJSON(w, http.StatusBadRequest, errorBody(core.KindUsage.String(), "failed to read the delivery: "+err.Error()))
+		return
+	}
+	if !validSignature(s.webhook.secret, body, r.Header.Get("X-Hub-Signature-256")) {
+		writeJSON(w, http.StatusUnauthorized, errorBody("unauthorized", "missing or wrong X-Hub-Signature-256"))
+		return
+	}
+
+	run, reason := s.webhook.task(r.Header.Get("X-GitHub-Event"), body)
+	if run == nil {
+		writeJSON(w, http.StatusOK, map[string]string{"status": "ignored", "reason": reason})
+		return
+	}
+	if s.ctx.Err() != nil {
+		writeJSON(w, http.StatusServiceUnavailable, errorBody("unavailable", errShuttingDown.Error()))
+		return
+	}
+
+	s.background.Add(1)
+	go s.runIssue(*run)
+	writeJSON(w, http.StatusAccepted, map[string]string{"status": "accepted"})
+}
+
+// runIssue runs the agent on an issue in the background until it exits or the server
+// shuts down
+func (s *server) runIssue(run core.IssueRun) {
+	defer s.background.Done()
+
+	log.Printf("Running %s on #%d in %s", run.Agent, run.Number, run.Dir)
+	result, err := core.RunIssue(s.ctx, run, os.Stdout)
+	s.track(result)
+	switch {
+	case err != nil:
+		log.Printf("Warning: run on #%d failed: %v", run.Number, err)
+	case result != nil:
+		log.Printf("Run on #%d finished with exit code %d", run.Number, result.ExitCode)
+	}
+}
+
+// validSignature reports whether header is the HMAC-SHA256 of body with secret, as
+// GitHub signs deliveries
+func validSignature(secret string, body []byte, header string) bool {
+	signature, ok := strings.CutPrefix(header, "sha256=")
+	if !ok {
+		return false
+	}
+	got, err := hex.DecodeString(signature)
+	if err != nil {
+		return false
+	}
+	mac := hmac.New(sha256.New, []byte(secret))
+	mac.Write(body)
+	return hmac.Equal(got, mac.Sum(nil))
+}
+
+// repoFlag collects --webhook-repo owner/name=/path/to/checkout values
+type repoFlag map[string]string
+
+func (f repoFlag) String() string {
+	pairs := make([]string, 0, len(f))
+	for name, dir := range f {
+		pairs = append(pairs, name+"="+dir)
+	}
+	return strings.Join(pairs, ",")
+}
+
+func (f repoFlag) Set(value string) error {
+	name, dir, ok := strings.Cut(value, "=")
+	if !ok || !strings.Contains(name, "/") || dir == "" {
+		return fmt.Errorf("expected owner/name=/path/to/checkout, got %q", value)
+	}
+	dir, err := filepath.Abs(dir)
+	if err != nil {
+		return err
+	}
+	f[strings.ToLower(name)] = dir
+	return nil
+}
@@ -0,0 +1,119 @@
+package main
+
+import (
+	"crypto/hmac"
+	"crypto/sha256"
+	"encoding/hex"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+)
+
+func sign(secret, body string) string {
+	mac := hmac.New(sha256.New, []byte(secret))
+	mac.Write([]byte(body))
+	return "sha256=" + hex.EncodeToString(mac.Sum(nil))
+}
+
+func TestValidSignature(t *testing.T) {
+	body := []byte(`{"action":"labeled"}`)
+	if !validSignature("s3cret", body, sign("s3cret", string(body))) {
+		t.Error("rejected a correct signature")
+	}
+	for _, header := range []string{"", sign("other", string(body)), "sha256=zz", "sha1=" + sign("s3cret", string(body))[7:]} {
+		if validSignature("s3cret", body, header) {
+			t.Errorf("accepted signature %q", header)
+		}
+	}
+}
+
+func TestWebhookTask(t *testing.T) {
+	config := &webhookConfig{
+		secret:  "s3cret",
+		repos:   repoFlag{"acme/api": "/src/api"},
+		label:   "agent",
+		command: "/agent fix",
+		agent:   "claude",
+	}
+	for _, tt := range []struct {
+		name        string
+		event       string
+		body        string
+		wantNumber  int
+		wantPR      bool
+		wantRequest string
+	}{
+		{"labeled issue", "issues", `{"action":"labeled","label":{"name":"agent"},"issue":{"number":7},"repository":{"full_name":"Acme/API"}}`, 7, false, ""},
+		{"other label", "issues", `{"action":"labeled","label":{"name":"bug"},"issue":{"number":7},"repository":{"full_name":"acme/api"}}`, 0, false, ""},
+		{"pr comment", "issue_comment", `{"action":"created","issue":{"number":9,"pull_request":{}},"comment":{"body":"/agent fix the flaky test","author_association":"MEMBER"},"repository":{"full_name":"acme/api"}}`, 9, true, "the flaky test"},
+		{"outside commenter", "issue_comment", `{"action":"created","issue":{"number":9,"pull_request":{}},"comment":{"body":"/agent fix","author_association":"NONE"},"repository":{"full_name":"acme/api"}}`, 0, false, ""},
+		{"issue comment", "issue_comment", `{"action":"created","issue":{"number":9},"comment":{"body":"/agent fix","author_association":"OWNER"},"repository":{"full_name":"acme/api"}}`, 0, false, ""},
+		{"unknown repository", "issues", `{"action":"labeled","label":{"name":"agent"},"issue":{"number":7},"repository":{"full_name":"acme/web"}}`, 0, false, ""},
+	} {
+		run, reason := config.task(tt.event, []byte(tt.body))
+		if tt.wantNumber == 0 {
+			if run != nil {
+				t.Errorf("%s: started a run on #%d", tt.name, run.Number)
+			}
+			continue
+		}
+		if run == nil {
+			t.Errorf("%s: ignored: %s", tt.name, reason)
+			continue
+		}
+		if run.Number != tt.wantNumber || run.PullRequest != tt.wantPR || run.Request != tt.wantRequest || run.Dir != "/src/api" {
+			t.Errorf("%s: run = %+v", tt.name, run)
+		}
+	}
+}
+
+func TestWebhookRequiresSignature(t *testing.T) {
+	s := newServer("token")
+	s.webhook = &webhookConfig{secret: "s3cret", repos: repoFlag{}, label: "agent", command: "/agent fix"}
+
+	body := `{"zen":"Keep it logically awesome."}`
+	for header, want := range map[string]int{
+		"":                   http.StatusUnauthorized,
+		sign("wrong", body):   http.StatusUnauthorized,
+		sign("s3cret", body): http.StatusOK,
+	} {
+		// GitHub sends neither the bearer token nor a loopback Host
+		req := httptest.NewRequest(http.MethodPost, webhookPath, strings.NewReader(body))
+		req.Host = "agents.example.com"
+		req.Header.Set("X-GitHub-Event", "ping")
+		if header != "" {
+			req.Header.Set("X-Hub-Signature-256", header)
+		}
+		rec := httptest.NewRecorder()
+		s.ServeHTTP(rec, req)
+		if rec.Code != want {
+			t.Errorf("signature %q: status %d, want %d", header, rec.Code, want)
+		}
+	}
+}
+
+func TestWebhookOffWithoutSecret(t *testing.T) {
+	s := newServer("")
+	req := httptest.NewRequest(http.MethodPost, webhookPath, strings.NewReader(`{}`))
+	rec := httptest.NewRecorder()
+	s.ServeHTTP(rec, req)
+	if rec.Code != http.StatusNotFound {
+		t.Errorf("status %d, want %d", rec.Code, http.StatusNotFound)
+	}
+}
+
+func TestRepoFlag(t *testing.T) {
+	repos := repoFlag{}
+	if err := repos.Set("Acme/API=/src/api"); err != nil {
+		t.Fatal(err)
+	}
+	if repos["acme/api"] != "/src/api" {
+		t.Errorf("repos = %v", repos)
+	}
+	for _, value := range []string{"acme/api", "api=/src/api", "acme/api="} {
+		if err := repos.Set(value); err == nil {
+			t.Errorf("Set(%q) succeeded", value)
+		}
+	}
+}
@@ -0,0 +1,270 @@
+package main
+
+import (
+	"bufio"
+	"crypto/sha1"
+	"encoding/base64"
+	"encoding/binary"
+	"errors"
+	"io"
+	"net"
+	"net/http"
+	"strings"
+	"sync"
+	"time"
+)
+
+// websocketGUID is appended to the client's key to prove the server speaks WebSocket (RFC 6455)
+const websocketGUID = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11"
+
+// WebSocket frame opcodes
+const (
+	opContinuation = 0x0
+	opText         = 0x1
+	opBinary       = 0x2
+	opClose        = 0x8
+	opPing         = 0x9
+	opPong         = 0xa
+)
+
+// WebSocket close codes
+const (
+	closeNormal      = 1000
+	closeGoingAway   = 1001
+	closeProtocol    = 1002
+	closeUnsupported = 1003
+	closeTooBig      = 1009
+)
+
+const (
+	// maxMessageSize is the largest message read from a client
+	maxMessageSize = 64 << 10
+	// writeWait is how long a client gets to take one frame; one that falls further
+	// behind is disconnected
+	writeWait = 10 * time.Second
+)
+
+var (
+	errNotWebSocket    = errors.New("this endpoint only accepts WebSocket connections")
+	errProtocol        = errors.New("WebSocket protocol error")
+	errMessageTooBig   = errors.New("WebSocket message too big")
+	errUnsupportedData = errors.New("unsupported WebSocket message")
+)
+
+// websocketConn is the server end of a WebSocket connection. Messages are read on one
+// goroutine; frames may be written from several.
+type websocketConn struct {
+	conn net.Conn
+	r    *bufio.Reader
+
+	writeMu sync.Mutex
+	w       *bufio.Writer
+	// closeSent is set once a close frame has been written; nothing may follow it
+	closeSent bool
+}
+
+// isWebSocketRequest reports whether r asks to upgrade to a WebSocket
+func isWebSocketRequest(r *http.Request) bool {
+	return headerHasToken(r.Header, "Connection", "upgrade") && headerHasToken(r.Header, "Upgrade", "websocket")
+}
+
+// checkWebSocketHandshake returns an error when r is not a WebSocket handshake this
+// server can complete
+func checkWebSocketHandshake(r *http.Request) error {
+	if r.Method != http.MethodGet || !isWebSocketRequest(r) {
+		return errNotWebSocket
+	}
+	if r.Header.Get("Sec-WebSocket-Version") != "13" {
+		return errors.New("unsupported WebSocket version; 13 is required")
+	}
+	if key, err := base64.StdEncoding.DecodeString(r.Header.Get("Sec-WebSocket-Key")); err != nil || len(key) != 16 {
+		return errors.New("invalid Sec-WebSocket-Key")
+	}
+	return nil
+}
+
+// websocketAccept returns the Sec-WebSocket-Accept value for a client's key
+func websocketAccept(key string) string {
+	sum := sha1.Sum([]byte(key + websocketGUID))
+	return base64.StdEncoding.EncodeToString(sum[:])
+}
+
+// upgradeWebSocket completes a handshake checked by checkWebSocketHandshake and takes
+// over the connection
+func upgradeWebSocket(w http.ResponseWriter, r *http.Request) (*websocketConn, error) {
+	hijacker, ok := w.(http.Hijacker)
+	if !ok {
+		return nil, errors.New("the connection cannot be upgraded")
+	}
+	conn, rw, err := hijacker.Hijack()
+	if err != nil {
+		return nil, err
+	}
+	// The server's read and write timeouts no longer apply; the heartbeat takes over
+	conn.SetDeadline(time.Time{})
+	rw.WriteString("HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: " +
+		websocketAccept(r.Header.Get("Sec-WebSocket-Key")) + "\r\n\r\n")
+	if err := rw.Flush(); err != nil {
+		conn.Close()
+		return nil, err
+	}
+	return &websocketConn{conn: conn, r: rw.Reader, w: rw.Writer}, nil
+}
+
+// readMessage returns the next text or binary message. Pings are answered and pongs
+// skipped. The client must send something, if only a pong, within idle of each frame.
+// A close frame is answered and reported as io.EOF.
+func (c *websocketConn) readMessage(idle time.Duration) (byte, []byte, error) {
+	var opcode byte
+	var message []byte
+	for {
+		c.conn.SetReadDeadline(time.Now().Add(idle))
+		fin, op, payload, err := c.readFrame()
+		if err != nil {
+			return 0, nil, err
+		}
+		switch op {
+		case opPing:
+			if err := c.writeFrame(opPong, payload); err != nil {
+				return 0, nil, err
+			}
+			continue
+		case opPong:
+			continue
+		case opClose:
+			// Echo the client's status code, as the protocol asks
+			if len(payload) > 2 {
+				payload = payload[:2]
+			}
+			c.writeFrame(opClose, payload)
+			return 0, nil, io.EOF
+		case opContinuation:
+			if opcode == 0 {
+				return 0, nil, errProtocol
+			}
+		case opText, opBinary:
+			if opcode != 0 {
+				return 0, nil, errProtocol
+			}
+			opcode = op
+		default:
+			return 0, nil, errProtocol
+		}
+		if len(message)+len(payload) > maxMessageSize {
+			return 0, nil, errMessageTooBig
+		}
+		message = append(message, payload...)
+		if fin {
+			return opcode, message, nil
+		}
+	}
+}
+
+// readFrame reads one frame and unmasks its payload
+func (c *websocketConn) readFrame() (bool, byte, []byte, error) {
+	var header [2]byte
+	if _, err := io.ReadFull(c.r, header[:]); err != nil {
+		return false, 0, nil, err
+	}
+	fin := header[0]&0x80 != 0
+	opcode := header[0] & 0x0f
+	// No extensions were negotiated, so the reserved bits must be clear, and clients
+	// must mask every frame
+	if header[0]&0x70 != 0 || header[1]&0x80 == 0 {
+		return false, 0, nil, errProtocol
+	}
+
+	length := uint64(header[1] & 0x7f)
+	switch length {
+	case 126:
+		var ext [2]byte
+		if _, err := io.ReadFull(c.r, ext[:]); err != nil {
+			return false, 0, nil, err
+		}
+		length = uint64(binary.BigEndian.Uint16(ext[:]))
+	case 127:
+		var ext [8]byte
+		if _, err := io.ReadFull(c.r, ext[:]); err != nil {
+			return false, 0, nil, err
+		}
+		length = binary.BigEndian.Uint64(ext[:])
+	}
+	if opcode >= opClose && (length > 125 || !fin) {
+		return false, 0, nil, errProtocol
+	}
+	if length > maxMessageSize {
+		return false, 0, nil, errMessageTooBig
+	}
+
+	var mask [4]byte
+	if _, err := io.ReadFull(c.r, mask[:]); err != nil {
+		return false, 0, nil, err
+	}
+	payload := make([]byte, length)
+	if _, err := io.ReadFull(c.r, payload); err != nil {
+		return false, 0, nil, err
+	}
+	for i := range payload {
+		payload[i] ^= mask[i%4]
+	}
+	return fin, opcode, payload, nil
+}
+
+// writeFrame writes one unfragmented frame, giving up after writeWait
+func (c *websocketConn) writeFrame(opcode byte, payload []byte) error {
+	c.writeMu.Lock()
+	defer c.writeMu.Unlock()
+
+	if c.closeSent {
+		return net.ErrClosed
+	}
+	if opcode == opClose {
+		c.closeSent = true
+	}
+	header := []byte{0x80 | opcode}
+	switch n := len(payload); {
+	case n < 126:
+		header = append(header, byte(n))
+	case n <= 0xffff:
+		header = binary.BigEndian.AppendUint16(append(header, 126), uint16(n))
+	default:
+		header = binary.BigEndian.AppendUint64(append(header, 127), uint64(n))
+	}
+
+	c.conn.SetWriteDeadline(time.Now().Add(writeWait))
+	c.w.Write(header)
+	c.w.Write(payload)
+	return c.w.Flush()
+}
+
+// close sends a close frame with code and reason, unless one was sent already, and
+// closes the connection
+func (c *websocketConn) close(code uint16, reason string) {
+	c.writeFrame(opClose, append(binary.BigEndian.AppendUint16(nil, code), reason...))
+	c.conn.Close()
+}
+
+// closeFor returns the close code for an error that ended reading from a client
+func closeFor(err error) uint16 {
+	switch {
+	case errors.Is(err, errProtocol):
+		return closeProtocol
+	case errors.Is(err, errMessageTooBig):
+		return closeTooBig
+	case errors.Is(err, errUnsupportedData):
+		return closeUnsupported
+	}
+	return closeGoingAway
+}
+
+// headerHasToken reports whether a comma-separated header contains token, ignoring case
+func headerHasToken(header http.Header, name, token string) bool {
+	for _, value := range header.Values(name) {
+		for _, field := range strings.Split(value, ",") {
+			if strings.EqualFold(strings.TrimSpace(field), token) {
+				return true
+			}
+		}
+	}
+	return false
+}
@@ -0,0 +1,169 @@
+package main
+
+import (
+	"bufio"
+	"encoding/binary"
+	"errors"
+	"io"
+	"net"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+	"time"
+)
+
+// writeClientFrame writes a masked frame, as browsers do
+func writeClientFrame(t *testing.T, conn net.Conn, fin bool, opcode byte, payload []byte) {
+	t.Helper()
+	first := opcode
+	if fin {
+		first |= 0x80
+	}
+	frame := []byte{first}
+	switch n := len(payload); {
+	case n < 126:
+		frame = append(frame, 0x80|byte(n))
+	default:
+		frame = binary.BigEndian.AppendUint16(append(frame, 0x80|126), uint16(n))
+	}
+	mask := [4]byte{0x12, 0x34, 0x56, 0x78}
+	frame = append(frame, mask[:]...)
+	for i, b := range payload {
+		frame = append(frame, b^mask[i%4])
+	}
+	if _, err := conn.Write(frame); err != nil {
+		t.Errorf("writing frame: %v", err)
+	}
+}
+
+// readServerFrame reads an unmasked frame; it reports errors with t.Errorf, as it also
+// runs on goroutines
+func readServerFrame(t *testing.T, r *bufio.Reader) (byte, []byte) {
+	t.Helper()
+	var header [2]byte
+	if _, err := io.ReadFull(r, header[:]); err != nil {
+		t.Errorf("reading frame: %v", err)
+		return 0, nil
+	}
+	if header[1]&0x80 != 0 {
+		t.Error("server frame is masked")
+	}
+	length := int(header[1] & 0x7f)
+	switch length {
+	case 126:
+		var ext [2]byte
+		io.ReadFull(r, ext[:])
+		length = int(binary.BigEndian.Uint16(ext[:]))
+	case 127:
+		var ext [8]byte
+		io.ReadFull(r, ext[:])
+		length = int(binary.BigEndian.Uint64(ext[:]))
+	}
+	payload := make([]byte, length)
+	if _, err := io.ReadFull(r, payload); err != nil {
+		t.Errorf("reading payload: %v", err)
+	}
+	return header[0] & 0x0f, payload
+}
+
+// pipeWebSocket returns the server end of a WebSocket over an in-memory connection and
+// the client's end
+func pipeWebSocket(t *testing.T) (*websocketConn, net.Conn, *bufio.Reader) {
+	server, client := net.Pipe()
+	t.Cleanup(func() {
+		server.Close()
+		client.Close()
+	})
+	client.SetDeadline(time.Now().Add(10 * time.Second))
+	ws := &websocketConn{conn: server, r: bufio.NewReader(server), w: bufio.NewWriter(server)}
+	return ws, client, bufio.NewReader(client)
+}
+
+func TestWebSocketAccept(t *testing.T) {
+	// The example from RFC 6455, section 1.3
+	if got := websocketAccept("dGhlIHNhbXBsZSBub25jZQ=="); got != "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=" {
+		t.Errorf("websocketAccept = %q", got)
+	}
+}
+
+func TestCheckWebSocketHandshake(t *testing.T) {
+	handshake := func() *http.Request {
+		req := httptest.NewRequest(http.MethodGet, "/api/terminal/x", nil)
+		req.Header.Set("Connection", "keep-alive, Upgrade")
+		req.Header.Set("Upgrade", "websocket")
+		req.Header.Set("Sec-WebSocket-Version", "13")
+		req.Header.Set("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ==")
+		return req
+	}
+	if err := checkWebSocketHandshake(handshake()); err != nil {
+		t.Errorf("valid handshake: %v", err)
+	}
+	for name, change := range map[string]func(*http.Request){
+		"plain GET":   func(r *http.Request) { r.Header.Del("Upgrade") },
+		"old version": func(r *http.Request) { r.Header.Set("Sec-WebSocket-Version", "8") },
+		"short key":   func(r *http.Request) { r.Header.Set("Sec-WebSocket-Key", "c2hvcnQ=") },
+		"no key":      func(r *http.Request) { r.Header.Del("Sec-WebSocket-Key") },
+	} {
+		req := handshake()
+		change(req)
+		if err := checkWebSocketHandshake(req); err == nil {
+			t.Errorf("%s: accepted", name)
+		}
+	}
+}
+
+func TestWebSocketReadMessage(t *testing.T) {
+	ws, client, clientReader := pipeWebSocket(t)
+
+	go func() {
+		writeClientFrame(t, client, true, opPing, []byte("hi"))
+		writeClientFrame(t, client, false, opBinary, []byte("frag"))
+		writeClientFrame(t, client, true, opContinuation, make([]byte, 200))
+	}()
+	pong := make(chan []byte, 1)
+	go func() {
+		op, payload := readServerFrame(t, clientReader)
+		if op != opPong {
+			t.Errorf("server answered a ping with opcode %d", op)
+		}
+		pong <- payload
+	}()
+	opcode, message, err := ws.readMessage(time.Second)
+	if err != nil {
+		t.Fatalf("readMessage: %v", err)
+	}
+	if opcode != opBinary || len(message) != 204 || string(message[:4]) != "frag" {
+		t.Errorf("readMessage = %d, %d bytes", opcode, len(message))
+	}
+	if got := <-pong; string(got) != "hi" {
+		t.Errorf("pong payload %q, want %q", got, "hi")
+	}
+
+	// Unmasked frames break the protocol
+	go client.Write([]byte{0x82, 0x01, 'x'})
+	if _, _, err := ws.readMessage(time.Second); !errors.Is(err, errProtocol) {
+		t.Errorf("unmasked frame: %v, want errProtocol", err)
+	}
+}
+
+func TestWebSocketReadMessageTimesOut(t *testing.T) {
+	ws, _, _ := pipeWebSocket(t)
+	var netErr net.Error
+	if _, _, err := ws.readMessage(10 * time.Millisecond); !errors.As(err, &netErr) || !netErr.Timeout() {
+		t.Errorf("readMessage from a silent client: %v, want a timeout", err)
+	}
+}
+
+func TestServerRejectsWebSocketFromWebPages(t *testing.T) {
+	s := newServer("")
+	req := httptest.NewRequest(http.MethodGet, "/api/terminal/x", nil)
+	req.Host = "localhost:7878"
+	req.Header.Set("Connection", "Upgrade")
+	req.Header.Set("Upgrade", "websocket")
+	req.Header.Set("Origin", "https://attacker.example")
+	rec := httptest.NewRecorder()
+	s.ServeHTTP(rec, req)
+	if rec.Code != http.StatusForbidden {
+		t.Errorf("status %d, want %d", rec.Code, http.StatusForbidden)
+	}
+}
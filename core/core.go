@@ -0,0 +1,267 @@
+// Package core is the public API of agentsandbox for programs that want to manage
+// sandboxes without running the CLI, such as editor plugins and servers.
+//
+// It covers listing, creating, pausing and removing sandboxes, reading settings,
+// finding and parsing session logs, and reading test results. The CLI uses the same
+// code underneath, so sandboxes created here show up in `agentsandbox ls` and the
+// other way round.
+//
+// Functions that talk to Docker need the docker CLI on the PATH; use CheckDocker
+// to report a missing docker up front.
+//
+// Errors can be classified with ErrorKindOf, which returns the same kinds the CLI
+// exits with.
+package core
+
+import (
+	"context"
+	"errors"
+	"io"
+	"os"
+	"os/exec"
+	"path/filepath"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/failure"
+	"github.com/thaodangspace/agentsandbox/internal/headless"
+	"github.com/thaodangspace/agentsandbox/internal/logs"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+	"github.com/thaodangspace/agentsandbox/internal/templates"
+	"github.com/thaodangspace/agentsandbox/internal/testrun"
+)
+
+// CheckDocker returns an error when the docker CLI cannot be run
+func CheckDocker() error {
+	return container.CheckDockerAvailability()
+}
+
+// List returns the sandboxes on this machine, including stopped ones when all is set
+func List(all bool) ([]Sandbox, error) {
+	infos, err := container.ListSandboxes(all)
+	if err != nil {
+		return nil, err
+	}
+	return sandboxesFrom(infos), nil
+}
+
+// ListProject returns the sandboxes for a project directory, including stopped ones
+func ListProject(dir string) ([]Sandbox, error) {
+	infos, err := container.ListProjectContainers(dir)
+	if err != nil {
+		return nil, err
+	}
+	return sandboxesFrom(infos), nil
+}
+
+// Resolve returns the container name for a container or session name. With an empty
+// name it returns the sandbox for dir, or else the last one created.
+func Resolve(name, dir string) (string, error) {
+	return container.ResolveContainer(name, dir)
+}
+
+// Directory returns the project directory a sandbox was created for
+func Directory(name string) (string, error) {
+	return container.GetContainerDirectory(name)
+}
+
+// IsRunning reports whether a sandbox is running
+func IsRunning(name string) (bool, error) {
+	return container.IsContainerRunning(name)
+}
+
+// Create builds the image for a project and starts a sandbox for it with agent,
+// without attaching to it, and returns the sandbox's container name. The project's
+// recorded trust level applies unless opts sets one. Progress is printed to stdout
+// as it is by the CLI.
+func Create(dir string, agent Agent, opts CreateOptions) (string, error) {
+	a, err := config.ValidateAgent(string(agent))
+	if err != nil {
+		return "", failure.Wrap(failure.Usage, err)
+	}
+	createOpts, err := opts.toInternal(dir)
+	if err != nil {
+		return "", failure.Wrap(failure.Usage, err)
+	}
+	name := container.GenerateContainerName(dir, a)
+	if err := container.CreateContainer(name, dir, "", a, "", false, false, nil, createOpts); err != nil {
+		return "", err
+	}
+	return name, nil
+}
+
+// ParseAgent returns the agent with the given name, as accepted by --agent
+func ParseAgent(name string) (Agent, error) {
+	a, err := config.ValidateAgent(name)
+	if err != nil {
+		return "", failure.Wrap(failure.Usage, err)
+	}
+	return Agent(a), nil
+}
+
+// Run sends prompt to agent in the sandbox for dir without attaching to it, creating or
+// starting the sandbox as needed, and streams the agent's output and errors to out.
+// The run is recorded in a session log like `agentsandbox run`. An agent that exits
+// with an error is reported in the result's ExitCode rather than as an error.
+// Cancelling ctx kills the agent's docker exec and returns ctx's error along with the
+// result; the agent itself stops with the sandbox.
+func Run(ctx context.Context, dir string, agent Agent, prompt string, out io.Writer) (*RunResult, error) {
+	if !filepath.IsAbs(dir) {
+		return nil, failure.Errorf(failure.Usage, "project directory %q is not absolute", dir)
+	}
+	if info, err := os.Stat(dir); err != nil || !info.IsDir() {
+		return nil, failure.Errorf(failure.Usage, "project directory %q does not exist", dir)
+	}
+	if _, err := ParseAgent(string(agent)); err != nil {
+		return nil, err
+	}
+
+	outcome, err := headless.RunContext(ctx, dir, string(agent), prompt, map[string]interface{}{"api": true}, out)
+	return runResult(ctx, outcome, err)
+}
+
+// RunIssue runs an agent headlessly on a GitHub issue or pull request, as a webhook
+// would, and comments the outcome on it; see IssueRun. The run is reported like Run's.
+// Commenting needs GITHUB_TOKEN or GH_TOKEN.
+func RunIssue(ctx context.Context, run IssueRun, out io.Writer) (*RunResult, error) {
+	agent, err := ParseAgent(string(run.Agent))
+	if err != nil {
+		return nil, err
+	}
+	outcome, err := headless.RunIssue(ctx, headless.IssueTask{
+		Dir:         run.Dir,
+		Number:      run.Number,
+		PullRequest: run.PullRequest,
+		Request:     run.Request,
+		Agent:       config.Agent(agent),
+	}, out)
+	return runResult(ctx, outcome, err)
+}
+
+// runResult converts the outcome of a headless run, telling the agent's exit code
+// apart from failures to run it
+func runResult(ctx context.Context, outcome *headless.Outcome, err error) (*RunResult, error) {
+	if outcome == nil {
+		return nil, err
+	}
+	result := &RunResult{
+		Sandbox: outcome.Container,
+		Session: filepath.Base(outcome.LogFile),
+		Started: outcome.Started,
+	}
+
+	var exitErr *exec.ExitError
+	switch {
+	case err == nil:
+	case ctx.Err() != nil:
+		result.ExitCode = -1
+		return result, ctx.Err()
+	case errors.As(err, &exitErr):
+		result.ExitCode = exitErr.ExitCode()
+	default:
+		return result, failure.Wrap(failure.AgentLaunchFailed, err)
+	}
+	return result, nil
+}
+
+// Stop stops a sandbox, keeping it to be started again
+func Stop(name string) error {
+	return container.StopContainer(name)
+}
+
+// Pause freezes every process in a sandbox
+func Pause(name string) error {
+	return container.PauseContainer(name)
+}
+
+// Unpause resumes a paused sandbox
+func Unpause(name string) error {
+	return container.UnpauseContainer(name)
+}
+
+// Remove removes a sandbox along with its sidecars and the state kept for it
+func Remove(name string) error {
+	return container.RemoveContainer(name)
+}
+
+// LoadSettings returns the user's settings, with defaults for anything unset
+func LoadSettings() (*Settings, error) {
+	settings, err := config.LoadSettings()
+	if err != nil {
+		return nil, err
+	}
+	return settingsFrom(settings), nil
+}
+
+// LoadProjectSettings returns the settings of the project in dir
+func LoadProjectSettings(dir string) (*ProjectSettings, error) {
+	settings, err := config.LoadProjectSettings(dir)
+	if err != nil {
+		return nil, err
+	}
+	return projectSettingsFrom(settings), nil
+}
+
+// Templates returns the built-in project templates, sorted by name. Set one's name as
+// CreateOptions.Template to bootstrap a sandbox with it.
+func Templates() []Template {
+	all := templates.All()
+	converted := make([]Template, len(all))
+	for i, t := range all {
+		converted[i] = templateFrom(t)
+	}
+	return converted
+}
+
+// SessionLogs returns the paths of the session logs of a sandbox for the project in dir
+func SessionLogs(name, dir string) ([]string, error) {
+	return state.ListSessionLogs(name, dir)
+}
+
+// ParseLog reads the events of a session log, decrypting it if needed
+func ParseLog(path string) ([]LogEvent, error) {
+	events, err := logs.ParseRawLog(path)
+	if err != nil {
+		return nil, err
+	}
+	return eventsFrom(events), nil
+}
+
+// RedactLog masks secrets in session log events, as the CLI does before sharing or
+// serving a log
+func RedactLog(events []LogEvent) []LogEvent {
+	return eventsFrom(logs.RedactEvents(eventsTo(events), logs.LoadRedactor()))
+}
+
+// Prompts returns the prompts the user sent the agent during a session
+func Prompts(events []LogEvent) []string {
+	return logs.Prompts(eventsTo(events))
+}
+
+// WriteLogHTML renders session log events as a standalone HTML page
+func WriteLogHTML(events []LogEvent, outputPath, title string) error {
+	return logs.WriteHTML(eventsTo(events), outputPath, title)
+}
+
+// TestResults returns the results stored by `agentsandbox test` for a sandbox of the
+// project in dir, oldest first
+func TestResults(name, dir string) ([]*TestResult, error) {
+	files, err := state.ListTestResults(name, dir)
+	if err != nil {
+		return nil, err
+	}
+	results := make([]*TestResult, 0, len(files))
+	for _, file := range files {
+		result, err := testrun.Load(file)
+		if err != nil {
+			return nil, err
+		}
+		results = append(results, testResultFrom(result))
+	}
+	return results, nil
+}
+
+// ErrorKindOf classifies err as the CLI does when choosing its exit code
+func ErrorKindOf(err error) ErrorKind {
+	return ErrorKind(failure.KindOf(err))
+}
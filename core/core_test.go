@@ -0,0 +1,47 @@
+package core
+
+import (
+	"errors"
+	"testing"
+
+	"github.com/thaodangspace/agentsandbox/internal/failure"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+func TestCreateOptionsUseRecordedTrust(t *testing.T) {
+	t.Setenv("XDG_STATE_HOME", t.TempDir())
+	dir := t.TempDir()
+	if err := state.SaveTrust(dir, state.TrustRestricted); err != nil {
+		t.Fatal(err)
+	}
+
+	opts, err := CreateOptions{}.toInternal(dir)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if opts.Trust != state.TrustRestricted {
+		t.Errorf("Trust = %q, want the recorded %q", opts.Trust, state.TrustRestricted)
+	}
+
+	opts, err = CreateOptions{Trust: TrustUntrusted}.toInternal(dir)
+	if err != nil || opts.Trust != state.TrustUntrusted {
+		t.Errorf("Trust = %q, %v; want the explicit %q", opts.Trust, err, TrustUntrusted)
+	}
+	if _, err := (CreateOptions{Trust: "sure"}).toInternal(dir); err == nil {
+		t.Error("expected an error for an unknown trust level")
+	}
+	if _, err := (CreateOptions{Template: "no-such-template"}).toInternal(dir); err == nil {
+		t.Error("expected an error for an unknown template")
+	}
+}
+
+func TestErrorKindOf(t *testing.T) {
+	err := failure.Errorf(failure.ContainerNotFound, "container %q does not exist", "x")
+	kind := ErrorKindOf(err)
+	if kind != KindContainerNotFound || kind.String() != "container_not_found" || kind.ExitCode() != failure.ExitCode(err) {
+		t.Errorf("ErrorKindOf() = %v (%d), want container_not_found with the CLI's exit code", kind, kind.ExitCode())
+	}
+	if ErrorKindOf(errors.New("boom")) != KindUnknown {
+		t.Error("expected an unclassified error to be unknown")
+	}
+}
@@ -0,0 +1,32 @@
+package core_test
+
+import (
+	"fmt"
+	"log"
+
+	"github.com/thaodangspace/agentsandbox/core"
+)
+
+func ExampleList() {
+	sandboxes, err := core.List(true)
+	if err != nil {
+		log.Fatal(err)
+	}
+	for _, sandbox := range sandboxes {
+		fmt.Println(sandbox.Name, sandbox.Status)
+	}
+}
+
+func ExampleParseLog() {
+	logFiles, err := core.SessionLogs("agentsandbox-myproject", "/home/me/myproject")
+	if err != nil || len(logFiles) == 0 {
+		log.Fatal("no session logs")
+	}
+	events, err := core.ParseLog(logFiles[len(logFiles)-1])
+	if err != nil {
+		log.Fatal(err)
+	}
+	for _, prompt := range core.Prompts(events) {
+		fmt.Println(prompt)
+	}
+}
@@ -0,0 +1,43 @@
+package core
+
+import (
+	"context"
+	"io"
+
+	"github.com/thaodangspace/agentsandbox/internal/container"
+)
+
+// Terminal is a shell in a sandbox for a client that renders a terminal, such as a web
+// page. Read returns what the terminal shows, escape sequences included, and io.EOF
+// once the shell has exited; Write types into it.
+type Terminal interface {
+	io.ReadWriter
+	// Resize changes the terminal's size in rows and columns
+	Resize(rows, cols int) error
+	// Close ends the shell, hanging up the jobs it started, and returns its exit code,
+	// or -1 when it had to be killed. It can be called more than once.
+	Close() int
+}
+
+// OpenTerminal starts a login shell in the project directory of a running sandbox, on
+// a terminal of rows by cols. Commands run in it are audited like those of an attached
+// session. Cancelling ctx kills the shell's docker exec.
+func OpenTerminal(ctx context.Context, name string, rows, cols int) (Terminal, error) {
+	t, err := container.StartTerminal(ctx, name, rows, cols)
+	if err != nil {
+		return nil, err
+	}
+	return t, nil
+}
+
+// ObserveSession follows the agent session running in a sandbox, from its start, as its
+// terminal shows it, until the session ends. The Terminal it returns discards what is
+// written to it and ignores Resize; closing it leaves the session running. Sessions in
+// images without script(1) are not recorded and cannot be observed.
+func ObserveSession(ctx context.Context, name string) (Terminal, error) {
+	o, err := container.ObserveSession(ctx, name)
+	if err != nil {
+		return nil, err
+	}
+	return o, nil
+}
@@ -0,0 +1,389 @@
+package core
+
+import (
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/failure"
+	"github.com/thaodangspace/agentsandbox/internal/logs"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+	"github.com/thaodangspace/agentsandbox/internal/templates"
+	"github.com/thaodangspace/agentsandbox/internal/testrun"
+)
+
+// The types below are owned by this package and converted to and from the internal
+// ones, so internal changes do not leak into the public API.
+
+// Agent is the coding agent a sandbox runs
+type Agent string
+
+// Supported agents
+const (
+	AgentClaude Agent = "claude"
+	AgentGemini Agent = "gemini"
+	AgentCodex  Agent = "codex"
+	AgentQwen   Agent = "qwen"
+	AgentCursor Agent = "cursor"
+)
+
+// Trust is how far a project is trusted, which decides how locked down its
+// sandboxes are
+type Trust string
+
+// Trust levels, as set with `agentsandbox trust`
+const (
+	// TrustTrusted uses the settings as they are
+	TrustTrusted Trust = state.TrustTrusted
+	// TrustRestricted drops skip-permission flags, sudo, shared networks and secrets
+	TrustRestricted Trust = state.TrustRestricted
+	// TrustUntrusted additionally mounts the project read-only without any network
+	TrustUntrusted Trust = state.TrustUntrusted
+)
+
+// DockerAccess gives the agent a Docker daemon
+type DockerAccess string
+
+// Ways of giving the agent a Docker daemon
+const (
+	// DockerNone gives the agent no Docker daemon
+	DockerNone DockerAccess = ""
+	// DockerSocket mounts the host's Docker socket, which amounts to root on the host
+	DockerSocket DockerAccess = container.DockerSocket
+	// DockerInDocker runs a separate daemon in a privileged sidecar
+	DockerInDocker DockerAccess = container.DockerInDocker
+)
+
+// Sandbox describes a sandbox container as listed by Docker
+type Sandbox struct {
+	Project   string
+	Name      string
+	Directory string
+	// Alias is the session name given with --name, if any
+	Alias string
+	// Agent is the agent the sandbox was created for, when known
+	Agent string
+	// State is docker's container state, e.g. running or exited
+	State string
+	// Status is docker's human-readable status, e.g. "Up 2 hours"
+	Status string
+	// Health is healthy, unhealthy or starting; empty without a health check
+	Health string
+	// Created is when the sandbox was created
+	Created time.Time
+	// ExitCode and Finished describe the last run of a stopped sandbox
+	ExitCode int
+	Finished time.Time
+}
+
+func sandboxFrom(info container.ContainerInfo) Sandbox {
+	return Sandbox{
+		Project:   info.Project,
+		Name:      info.Name,
+		Directory: info.Directory,
+		Alias:     info.Alias,
+		Agent:     info.Agent,
+		State:     info.State,
+		Status:    info.Status,
+		Health:    info.Health,
+		Created:   info.Created,
+		ExitCode:  info.ExitCode,
+		Finished:  info.Finished,
+	}
+}
+
+func sandboxesFrom(infos []container.ContainerInfo) []Sandbox {
+	sandboxes := make([]Sandbox, len(infos))
+	for i, info := range infos {
+		sandboxes[i] = sandboxFrom(info)
+	}
+	return sandboxes
+}
+
+// CreateOptions changes how Create sets up a sandbox; the zero value matches running
+// the CLI with no flags in a project whose trust level was already decided
+type CreateOptions struct {
+	// Template is the name of a built-in template to bootstrap the sandbox with, see Templates
+	Template string
+	// Name is a human-friendly session name accepted in place of the container name
+	Name string
+	// WorkspaceDirs are extra repositories mounted read-write next to the project
+	WorkspaceDirs []string
+	// Network is a shared network, created with `agentsandbox network create`, to join
+	Network string
+	// ProtectGit overrides the git_protection setting: "hooks", "readonly" or "off"
+	ProtectGit string
+	// Trust overrides the project's recorded trust level; empty uses the recorded
+	// level, and trusted for a project without one
+	Trust Trust
+	// NoMask mounts env files unmodified for trusted projects
+	NoMask bool
+	// AllowSecrets skips the sensitive file scan
+	AllowSecrets bool
+	// CacheFrom overrides the registry_cache ref from settings
+	CacheFrom string
+	// GUI shares the host's Wayland and X11 sockets so GUI programs can open windows
+	GUI bool
+	// Docker gives the agent a Docker daemon
+	Docker DockerAccess
+}
+
+// toInternal converts the options for a sandbox of the project in dir
+func (o CreateOptions) toInternal(dir string) (container.CreateOptions, error) {
+	opts := container.CreateOptions{
+		Name:          o.Name,
+		WorkspaceDirs: o.WorkspaceDirs,
+		Network:       o.Network,
+		ProtectGit:    o.ProtectGit,
+		Trust:         string(o.Trust),
+		NoMask:        o.NoMask,
+		AllowSecrets:  o.AllowSecrets,
+		CacheFrom:     o.CacheFrom,
+		GUI:           o.GUI,
+		Docker:        string(o.Docker),
+	}
+	if opts.Trust == "" {
+		opts.Trust = container.RecordedTrust(dir)
+	} else if err := state.ValidateTrust(opts.Trust); err != nil {
+		return opts, err
+	}
+	if o.Template != "" {
+		tmpl, err := templates.Get(o.Template)
+		if err != nil {
+			return opts, err
+		}
+		opts.Template = tmpl
+	}
+	return opts, nil
+}
+
+// Template bootstraps a sandbox for a kind of project, see Templates
+type Template struct {
+	Name        string
+	Description string
+	// Languages are the toolchains the template installs
+	Languages []string
+	// Services are the names of the sidecar services it starts, e.g. "postgres"
+	Services []string
+	// Ports are the ports it publishes
+	Ports []string
+	// SetupCommands run in the sandbox once it is up
+	SetupCommands []string
+}
+
+func templateFrom(t templates.Template) Template {
+	tmpl := Template{
+		Name:          t.Name,
+		Description:   t.Description,
+		Ports:         t.Ports,
+		SetupCommands: t.SetupCommands,
+	}
+	for _, lang := range t.Languages {
+		tmpl.Languages = append(tmpl.Languages, lang.Name())
+	}
+	for _, svc := range t.Services {
+		tmpl.Services = append(tmpl.Services, svc.Name)
+	}
+	return tmpl
+}
+
+// Settings are the parts of the user's global settings from ~/.config/agentsandbox
+// that programs driving sandboxes commonly need. See the README for the full file.
+type Settings struct {
+	// DefaultAgent is the agent started when none is given
+	DefaultAgent string
+	// SkipPermissionFlags maps an agent to the flag that skips its permission prompts
+	SkipPermissionFlags map[string]string
+	// EnvFiles are the patterns of env files masked in sandboxes
+	EnvFiles []string
+	// BaseImage is "ubuntu", "slim" or an apt-based image reference
+	BaseImage string
+	// Platform is the image platform, e.g. linux/arm64; empty means native
+	Platform string
+	// MountStrategy is how the project is mounted: "auto", "bind" or "sync"
+	MountStrategy string
+	// GitProtection is "hooks", "readonly" or "off"
+	GitProtection string
+	// Sudo is "full", "none" or "targeted"
+	Sudo string
+	// CredentialProxy keeps model API keys on the host
+	CredentialProxy bool
+	// NetworkLog records the hosts sessions talk to
+	NetworkLog bool
+	// AlwaysWorktree gives each sandbox its own git worktree
+	AlwaysWorktree bool
+	// ProtectedBranches are glob patterns of branches new sandboxes refuse to start on
+	ProtectedBranches []string
+}
+
+func settingsFrom(s *config.Settings) *Settings {
+	return &Settings{
+		DefaultAgent:        s.DefaultAgent,
+		SkipPermissionFlags: s.SkipPermissionFlags,
+		EnvFiles:            s.EnvFiles,
+		BaseImage:           s.BaseImage,
+		Platform:            s.Platform,
+		MountStrategy:       s.MountStrategy,
+		GitProtection:       s.GitProtection,
+		Sudo:                s.Sudo,
+		CredentialProxy:     s.CredentialProxy,
+		NetworkLog:          s.NetworkLog,
+		AlwaysWorktree:      s.AlwaysWorktree,
+		ProtectedBranches:   s.ProtectedBranches,
+	}
+}
+
+// ProjectSettings are a project's settings from .agentsandbox/settings.json
+type ProjectSettings struct {
+	// EnvFiles replaces the global env file patterns when set
+	EnvFiles []string
+	// Database is the project's development database, if it has one
+	Database *Database
+	// BenchCommand is the command `agentsandbox bench` times
+	BenchCommand string
+}
+
+// Database describes a project's development database sidecar
+type Database struct {
+	// Engine is "postgres" or "mysql"
+	Engine  string
+	Image   string
+	Name    string
+	Migrate string
+	Seed    string
+}
+
+func projectSettingsFrom(s *config.ProjectSettings) *ProjectSettings {
+	settings := &ProjectSettings{EnvFiles: s.EnvFiles, BenchCommand: s.BenchCommand}
+	if s.DB != nil {
+		settings.Database = &Database{
+			Engine:  s.DB.Engine,
+			Image:   s.DB.Image,
+			Name:    s.DB.Name,
+			Migrate: s.DB.Migrate,
+			Seed:    s.DB.Seed,
+		}
+	}
+	return settings
+}
+
+// LogEvent is one event of a session log
+type LogEvent struct {
+	Timestamp string                 `json:"timestamp"`
+	Level     string                 `json:"level"`
+	Message   string                 `json:"message"`
+	Data      map[string]interface{} `json:"data,omitempty"`
+}
+
+func eventsFrom(events []logs.LogEvent) []LogEvent {
+	converted := make([]LogEvent, len(events))
+	for i, e := range events {
+		converted[i] = LogEvent{Timestamp: e.Timestamp, Level: e.Level, Message: e.Message, Data: e.Data}
+	}
+	return converted
+}
+
+func eventsTo(events []LogEvent) []logs.LogEvent {
+	converted := make([]logs.LogEvent, len(events))
+	for i, e := range events {
+		converted[i] = logs.LogEvent{Timestamp: e.Timestamp, Level: e.Level, Message: e.Message, Data: e.Data}
+	}
+	return converted
+}
+
+// TestResult is the outcome of one `agentsandbox test` run
+type TestResult struct {
+	Container string `json:"container"`
+	// Session is the agent session the run followed, if any
+	Session    string     `json:"session,omitempty"`
+	Command    string     `json:"command"`
+	Format     string     `json:"format"`
+	Started    time.Time  `json:"started"`
+	DurationMS int64      `json:"duration_ms"`
+	ExitCode   int        `json:"exit_code"`
+	Passed     int        `json:"passed"`
+	Failed     int        `json:"failed"`
+	Skipped    int        `json:"skipped"`
+	Cases      []TestCase `json:"cases"`
+}
+
+// TestCase is one test of a TestResult
+type TestCase struct {
+	Suite string `json:"suite,omitempty"`
+	Name  string `json:"name"`
+	// Status is "passed", "failed" or "skipped"
+	Status     string `json:"status"`
+	Message    string `json:"message,omitempty"`
+	DurationMS int64  `json:"duration_ms,omitempty"`
+}
+
+func testResultFrom(r *testrun.Result) *TestResult {
+	result := &TestResult{
+		Container:  r.Container,
+		Session:    r.Session,
+		Command:    r.Command,
+		Format:     r.Format,
+		Started:    r.Started,
+		DurationMS: r.DurationMS,
+		ExitCode:   r.ExitCode,
+		Passed:     r.Passed,
+		Failed:     r.Failed,
+		Skipped:    r.Skipped,
+		Cases:      make([]TestCase, len(r.Cases)),
+	}
+	for i, c := range r.Cases {
+		result.Cases[i] = TestCase{Suite: c.Suite, Name: c.Name, Status: c.Status, Message: c.Message, DurationMS: c.DurationMS}
+	}
+	return result
+}
+
+// RunResult is the outcome of a prompt run with Run
+type RunResult struct {
+	// Sandbox is the container name of the sandbox the prompt ran in
+	Sandbox string
+	// Session is the file name of the run's session log, as listed by SessionLogs
+	Session string
+	// ExitCode is the agent's exit code, or -1 when the run was cancelled
+	ExitCode int
+	// Started is set when the sandbox was created, started or unpaused for the run
+	Started bool
+}
+
+// IssueRun asks for an agent to work on a GitHub issue or pull request. An issue is
+// worked on in the issue-N worktree `agentsandbox from-issue` uses; a pull request has
+// its head fetched into a pr-N worktree. The changes stay there for review.
+type IssueRun struct {
+	// Dir is a checkout of the repository, whose origin remote is on GitHub
+	Dir string
+	// Number is the issue or pull request number
+	Number int
+	// PullRequest is set when Number is a pull request
+	PullRequest bool
+	// Request is added to the prompt, such as the text of the comment asking for the run
+	Request string
+	Agent   Agent
+}
+
+// ErrorKind classifies an error; each kind has its own CLI exit code
+type ErrorKind int
+
+// Kinds of error returned by ErrorKindOf. Their values match the CLI's and do not change.
+const (
+	KindUnknown           = ErrorKind(failure.Unknown)
+	KindUsage             = ErrorKind(failure.Usage)
+	KindDockerUnavailable = ErrorKind(failure.DockerUnavailable)
+	KindBuildFailed       = ErrorKind(failure.BuildFailed)
+	KindContainerNotFound = ErrorKind(failure.ContainerNotFound)
+	KindAgentLaunchFailed = ErrorKind(failure.AgentLaunchFailed)
+	KindPermissionDenied  = ErrorKind(failure.PermissionDenied)
+)
+
+// String returns the kind's name as used in JSON errors, e.g. "container_not_found"
+func (k ErrorKind) String() string {
+	return failure.Kind(k).String()
+}
+
+// ExitCode returns the CLI's exit code for the kind
+func (k ErrorKind) ExitCode() int {
+	return failure.Kind(k).ExitCode()
+}
@@ -0,0 +1,122 @@
+package bench
+
+import (
+	"errors"
+	"fmt"
+	"io"
+	"os/exec"
+	"runtime"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// Measurement is one run of the benchmarked command. I/O counts bytes read from and
+// written to block devices; reads served from the page cache are not included.
+type Measurement struct {
+	Where      string
+	Wall       time.Duration
+	CPU        time.Duration
+	ReadBytes  int64
+	WriteBytes int64
+	// CPUKnown and IOKnown are false where the platform does not report them
+	CPUKnown bool
+	IOKnown  bool
+	ExitCode int
+}
+
+// hostShell returns the command running a shell command line on the host
+func hostShell(command string) *exec.Cmd {
+	if runtime.GOOS == "windows" {
+		return exec.Command("cmd", "/C", command)
+	}
+	return exec.Command("sh", "-c", command)
+}
+
+// RunHost runs command in dir on the host, streaming its output to out
+func RunHost(dir, command string, out io.Writer) (Measurement, error) {
+	cmd := hostShell(command)
+	cmd.Dir = dir
+	cmd.Stdout = out
+	cmd.Stderr = out
+
+	m := Measurement{Where: "host"}
+	start := time.Now()
+	err := cmd.Run()
+	m.Wall = time.Since(start)
+	var exitErr *exec.ExitError
+	if errors.As(err, &exitErr) {
+		m.ExitCode = exitErr.ExitCode()
+	} else if err != nil {
+		return m, fmt.Errorf("failed to run %q on the host: %w", command, err)
+	}
+
+	m.CPU, m.CPUKnown = cmd.ProcessState.UserTime()+cmd.ProcessState.SystemTime(), true
+	m.ReadBytes, m.WriteBytes, m.IOKnown = hostIO(cmd.ProcessState)
+	return m, nil
+}
+
+// CgroupStats are the CPU and block I/O counters of a container's cgroup
+type CgroupStats struct {
+	CPU        time.Duration
+	ReadBytes  int64
+	WriteBytes int64
+	CPUKnown   bool
+	IOKnown    bool
+}
+
+// ParseCgroupStats reads cgroup v2 cpu.stat and io.stat contents concatenated together
+func ParseCgroupStats(data string) CgroupStats {
+	var stats CgroupStats
+	for _, line := range strings.Split(data, "\n") {
+		fields := strings.Fields(line)
+		if len(fields) == 2 && fields[0] == "usage_usec" {
+			if usec, err := strconv.ParseInt(fields[1], 10, 64); err == nil {
+				stats.CPU, stats.CPUKnown = time.Duration(usec)*time.Microsecond, true
+			}
+			continue
+		}
+		// io.stat lines look like "8:0 rbytes=1024 wbytes=0 rios=1 wios=0 ..."
+		if len(fields) > 1 && strings.Contains(fields[0], ":") {
+			for _, field := range fields[1:] {
+				key, value, ok := strings.Cut(field, "=")
+				if !ok {
+					continue
+				}
+				n, err := strconv.ParseInt(value, 10, 64)
+				if err != nil {
+					continue
+				}
+				switch key {
+				case "rbytes":
+					stats.ReadBytes += n
+					stats.IOKnown = true
+				case "wbytes":
+					stats.WriteBytes += n
+					stats.IOKnown = true
+				}
+			}
+		}
+	}
+	return stats
+}
+
+// Sub returns the counters accumulated since before
+func (s CgroupStats) Sub(before CgroupStats) CgroupStats {
+	return CgroupStats{
+		CPU:        s.CPU - before.CPU,
+		ReadBytes:  s.ReadBytes - before.ReadBytes,
+		WriteBytes: s.WriteBytes - before.WriteBytes,
+		CPUKnown:   s.CPUKnown && before.CPUKnown,
+		// A device that saw no I/O yet has no io.stat line
+		IOKnown: s.IOKnown || before.IOKnown,
+	}
+}
+
+// Median returns the run with the median wall-clock time
+func Median(runs []Measurement) Measurement {
+	sorted := append([]Measurement{}, runs...)
+	sort.Slice(sorted, func(i, j int) bool { return sorted[i].Wall < sorted[j].Wall })
+	return sorted[len(sorted)/2]
+}
@@ -0,0 +1,38 @@
+package bench
+
+import (
+	"testing"
+	"time"
+)
+
+func TestParseCgroupStats(t *testing.T) {
+	before := ParseCgroupStats(`usage_usec 1000000
+user_usec 800000
+system_usec 200000
+8:0 rbytes=4096 wbytes=0 rios=1 wios=0 dbytes=0 dios=0
+`)
+	after := ParseCgroupStats(`usage_usec 3500000
+user_usec 3000000
+system_usec 500000
+8:0 rbytes=8192 wbytes=1048576 rios=2 wios=40 dbytes=0 dios=0
+8:16 rbytes=4096 wbytes=0 rios=1 wios=0 dbytes=0 dios=0
+`)
+	delta := after.Sub(before)
+	if !delta.CPUKnown || delta.CPU != 2500*time.Millisecond {
+		t.Errorf("CPU = %v (known %v), want 2.5s", delta.CPU, delta.CPUKnown)
+	}
+	if !delta.IOKnown || delta.ReadBytes != 8192 || delta.WriteBytes != 1048576 {
+		t.Errorf("I/O = %d read, %d written, want 8192 and 1048576", delta.ReadBytes, delta.WriteBytes)
+	}
+
+	if stats := ParseCgroupStats(""); stats.CPUKnown || stats.IOKnown {
+		t.Errorf("ParseCgroupStats(\"\") = %+v, want nothing known", stats)
+	}
+}
+
+func TestMedian(t *testing.T) {
+	runs := []Measurement{{Wall: 3 * time.Second}, {Wall: time.Second}, {Wall: 2 * time.Second}}
+	if got := Median(runs); got.Wall != 2*time.Second {
+		t.Errorf("Median() = %v, want 2s", got.Wall)
+	}
+}
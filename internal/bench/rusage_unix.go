@@ -0,0 +1,19 @@
+//go:build !windows
+
+package bench
+
+import (
+	"os"
+	"runtime"
+	"syscall"
+)
+
+// hostIO returns the block I/O of a finished process and its children. Only Linux
+// reports it in 512-byte blocks; other systems count operations.
+func hostIO(state *os.ProcessState) (int64, int64, bool) {
+	usage, ok := state.SysUsage().(*syscall.Rusage)
+	if !ok || runtime.GOOS != "linux" {
+		return 0, 0, false
+	}
+	return int64(usage.Inblock) * 512, int64(usage.Oublock) * 512, true
+}
@@ -0,0 +1,10 @@
+//go:build windows
+
+package bench
+
+import "os"
+
+// hostIO is not available on Windows
+func hostIO(state *os.ProcessState) (int64, int64, bool) {
+	return 0, 0, false
+}
@@ -0,0 +1,134 @@
+package bundle
+
+import (
+	"archive/tar"
+	"compress/gzip"
+	"encoding/json"
+	"fmt"
+	"io"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+// FormatVersion is bumped whenever the bundle layout changes incompatibly
+const FormatVersion = 1
+
+// Files inside a bundle
+const (
+	manifestFile  = "manifest.json"
+	sessionsDir   = "sessions"
+	diffFile      = "changes.diff"
+	containerFile = "container.json"
+	versionsFile  = "agent-versions.txt"
+	reproduceFile = "reproduce.sh"
+)
+
+// Manifest describes the contents of a session bundle
+type Manifest struct {
+	FormatVersion int       `json:"format_version"`
+	Version       string    `json:"agentsandbox_version"`
+	CreatedAt     time.Time `json:"created_at"`
+	Container     string    `json:"container"`
+	Agent         string    `json:"agent"`
+	Project       string    `json:"project"`
+	BaseCommit    string    `json:"base_commit,omitempty"`
+	Sessions      []string  `json:"sessions"`
+	Command       []string  `json:"command,omitempty"`
+	HasDiff       bool      `json:"has_diff"`
+}
+
+// Target is a container, optionally narrowed to a single session, whose logs are exported
+type Target struct {
+	Container string
+	Project   string
+	LogsDir   string
+	// Session limits the export to one session ID; empty exports all sessions
+	Session string
+}
+
+// ResolveTarget finds the logs for a container name or a session ID
+func ResolveTarget(arg string) (*Target, error) {
+	stateDir, err := state.GetStateDir()
+	if err != nil {
+		return nil, err
+	}
+	logsRoot := filepath.Join(stateDir, "logs")
+
+	// Accept session names given with --name
+	if containerName := state.ResolveAlias(arg); containerName != "" {
+		arg = containerName
+	}
+
+	if dirs, _ := filepath.Glob(filepath.Join(logsRoot, "*", arg)); len(dirs) > 0 {
+		return &Target{
+			Container: arg,
+			Project:   filepath.Base(filepath.Dir(dirs[0])),
+			LogsDir:   dirs[0],
+		}, nil
+	}
+
+	for _, prefix := range []string{"session-", "audit-"} {
+		matches, _ := filepath.Glob(filepath.Join(logsRoot, "*", "*", prefix+arg+".jsonl"))
+		if len(matches) > 0 {
+			logsDir := filepath.Dir(matches[0])
+			return &Target{
+				Container: filepath.Base(logsDir),
+				Project:   filepath.Base(filepath.Dir(logsDir)),
+				LogsDir:   logsDir,
+				Session:   arg,
+			}, nil
+		}
+	}
+
+	return nil, fmt.Errorf("no container or session named %s has logs", arg)
+}
+
+// sessionID returns the session identifier of a log file name
+func sessionID(name string) string {
+	name = strings.TrimSuffix(name, filepath.Ext(name))
+	name = strings.TrimPrefix(name, "session-")
+	return strings.TrimPrefix(name, "audit-")
+}
+
+// tarWriter adds files to a gzip-compressed tar archive
+type tarWriter struct {
+	gz *gzip.Writer
+	tw *tar.Writer
+}
+
+func newTarWriter(w io.Writer) *tarWriter {
+	gz := gzip.NewWriter(w)
+	return &tarWriter{gz: gz, tw: tar.NewWriter(gz)}
+}
+
+func (t *tarWriter) addBytes(name string, data []byte, mode int64) error {
+	header := &tar.Header{
+		Name:    name,
+		Mode:    mode,
+		Size:    int64(len(data)),
+		ModTime: time.Now(),
+	}
+	if err := t.tw.WriteHeader(header); err != nil {
+		return err
+	}
+	_, err := t.tw.Write(data)
+	return err
+}
+
+func (t *tarWriter) addJSON(name string, v interface{}) error {
+	data, err := json.MarshalIndent(v, "", "  ")
+	if err != nil {
+		return err
+	}
+	return t.addBytes(name, data, 0644)
+}
+
+func (t *tarWriter) Close() error {
+	if err := t.tw.Close(); err != nil {
+		return err
+	}
+	return t.gz.Close()
+}
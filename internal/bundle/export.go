@@ -0,0 +1,212 @@
+package bundle
+
+import (
+	"bytes"
+	"fmt"
+	"io/fs"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/crash"
+	"github.com/thaodangspace/agentsandbox/internal/git"
+	"github.com/thaodangspace/agentsandbox/internal/logs"
+	"github.com/thaodangspace/agentsandbox/internal/secrets"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+// Export writes a bundle with the target's session logs, the project diff,
+// container metadata, agent versions and a reproduce script
+func Export(target *Target, version, output string) (*Manifest, error) {
+	agent, ok := config.FromContainerName(target.Container)
+	if !ok {
+		agent = config.AgentClaude
+	}
+
+	manifest := &Manifest{
+		FormatVersion: FormatVersion,
+		Version:       version,
+		CreatedAt:     time.Now().UTC(),
+		Container:     target.Container,
+		Agent:         string(agent),
+		Project:       target.Project,
+	}
+
+	if cmd, err := state.LoadContainerRunCommand(target.Container); err == nil && cmd != nil {
+		manifest.Command = redactAll(cmd.Command)
+	}
+
+	f, err := os.Create(output)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create bundle: %w", err)
+	}
+	defer f.Close()
+
+	tw := newTarWriter(f)
+
+	if err := addSessions(tw, target, manifest); err != nil {
+		return nil, err
+	}
+
+	projectDir, _ := container.GetContainerDirectory(target.Container)
+	if projectDir != "" {
+		if commit, err := git.HeadCommit(projectDir); err == nil {
+			manifest.BaseCommit = commit
+			diff, err := git.WorkingTreeDiff(projectDir)
+			if err != nil {
+				fmt.Printf("Warning: failed to collect changes: %v\n", err)
+			} else if diff != "" {
+				manifest.HasDiff = true
+				if err := tw.addBytes(diffFile, []byte(diff), 0644); err != nil {
+					return nil, err
+				}
+			}
+		}
+	}
+
+	if inspect, err := exec.Command("docker", "inspect", target.Container).Output(); err == nil {
+		if err := tw.addBytes(containerFile, []byte(crash.Redact(string(inspect))), 0644); err != nil {
+			return nil, err
+		}
+	}
+
+	if running, _ := container.IsContainerRunning(target.Container); running {
+		health := container.CheckAgentHealth(target.Container, agent)
+		versions := fmt.Sprintf("%s: %s\n", agent.Command(), health.Version)
+		if err := tw.addBytes(versionsFile, []byte(versions), 0644); err != nil {
+			return nil, err
+		}
+	}
+
+	if err := tw.addBytes(reproduceFile, []byte(reproduceScript(manifest)), 0755); err != nil {
+		return nil, err
+	}
+	if err := tw.addJSON(manifestFile, manifest); err != nil {
+		return nil, err
+	}
+
+	if err := tw.Close(); err != nil {
+		return nil, fmt.Errorf("failed to write bundle: %w", err)
+	}
+	return manifest, nil
+}
+
+// addSessions adds the target's JSONL logs with credentials redacted, plus an HTML rendering of each session log
+func addSessions(tw *tarWriter, target *Target, manifest *Manifest) error {
+	entries, err := os.ReadDir(target.LogsDir)
+	if err != nil {
+		return fmt.Errorf("failed to read logs: %w", err)
+	}
+
+	redactor := logs.LoadRedactor()
+	seen := make(map[string]bool)
+	for _, entry := range entries {
+		name := entry.Name()
+		if entry.IsDir() || filepath.Ext(name) != ".jsonl" {
+			continue
+		}
+		id := sessionID(name)
+		if target.Session != "" && id != target.Session {
+			continue
+		}
+
+		path := filepath.Join(target.LogsDir, name)
+		data, err := logs.ReadLog(path)
+		if err != nil {
+			return err
+		}
+		data = []byte(redactor.Redact(string(data)))
+		if err := tw.addBytes(filepath.ToSlash(filepath.Join(sessionsDir, name)), data, 0644); err != nil {
+			return err
+		}
+
+		if strings.HasPrefix(name, "session-") {
+			if err := addSessionHTML(tw, path, redactor); err != nil {
+				return err
+			}
+		}
+
+		if !seen[id] {
+			seen[id] = true
+			manifest.Sessions = append(manifest.Sessions, id)
+		}
+	}
+
+	if len(manifest.Sessions) == 0 {
+		return fmt.Errorf("no session logs found for %s", target.Container)
+	}
+	sort.Strings(manifest.Sessions)
+	return nil
+}
+
+// addSessionHTML adds the HTML viewer for a session log, with its event assets. A log
+// that cannot be rendered is skipped; only failures to write the bundle are returned.
+func addSessionHTML(tw *tarWriter, logFile string, redactor *secrets.Redactor) error {
+	events, err := logs.ParseRawLog(logFile)
+	if err != nil {
+		return nil
+	}
+	events = logs.RedactEvents(events, redactor)
+
+	tmpDir, err := os.MkdirTemp("", "agentsandbox-html-*")
+	if err != nil {
+		return nil
+	}
+	defer os.RemoveAll(tmpDir)
+
+	htmlName := strings.TrimSuffix(filepath.Base(logFile), ".jsonl") + ".html"
+	if err := logs.WriteHTML(events, filepath.Join(tmpDir, htmlName), filepath.Base(logFile)); err != nil {
+		return nil
+	}
+
+	return filepath.WalkDir(tmpDir, func(path string, d fs.DirEntry, err error) error {
+		if err != nil || d.IsDir() {
+			return err
+		}
+		data, err := os.ReadFile(path)
+		if err != nil {
+			return err
+		}
+		rel, err := filepath.Rel(tmpDir, path)
+		if err != nil {
+			return err
+		}
+		return tw.addBytes(filepath.ToSlash(filepath.Join(sessionsDir, rel)), data, 0644)
+	})
+}
+
+// reproduceScript returns a shell script that recreates the sandbox for the bundle
+func reproduceScript(m *Manifest) string {
+	var b bytes.Buffer
+	b.WriteString("#!/bin/sh\n")
+	fmt.Fprintf(&b, "# Reproduce agentsandbox session(s) %s of %s\n", strings.Join(m.Sessions, ", "), m.Container)
+	b.WriteString("# Run from a clone of the project.\n")
+	b.WriteString("set -e\n\n")
+	if m.BaseCommit != "" {
+		fmt.Fprintf(&b, "git checkout %s\n", m.BaseCommit)
+	}
+	if m.HasDiff {
+		b.WriteString("# To start from the agent's final state instead: git apply --binary changes.diff\n")
+	}
+	fmt.Fprintf(&b, "agentsandbox --agent %s\n", m.Agent)
+	if len(m.Command) > 0 {
+		b.WriteString("\n# Command the agent was started with inside the container:\n")
+		for _, line := range m.Command {
+			fmt.Fprintf(&b, "#   %s\n", line)
+		}
+	}
+	return b.String()
+}
+
+func redactAll(values []string) []string {
+	redacted := make([]string, len(values))
+	for i, v := range values {
+		redacted[i] = crash.Redact(v)
+	}
+	return redacted
+}
@@ -0,0 +1,38 @@
+package bundle
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestSessionID(t *testing.T) {
+	tests := map[string]string{
+		"session-20250101-120000.jsonl": "20250101-120000",
+		"audit-20250101-120000.jsonl":   "20250101-120000",
+		"20250101-120000.log":           "20250101-120000",
+	}
+	for name, want := range tests {
+		t.Run(name, func(t *testing.T) {
+			if got := sessionID(name); got != want {
+				t.Errorf("sessionID(%q) = %q, want %q", name, got, want)
+			}
+		})
+	}
+}
+
+func TestReproduceScript(t *testing.T) {
+	script := reproduceScript(&Manifest{
+		Container:  "agentsandbox-api",
+		Agent:      "claude",
+		BaseCommit: "abc123",
+		Sessions:   []string{"20250101-120000"},
+		Command:    []string{"claude --dangerously-skip-permissions"},
+		HasDiff:    true,
+	})
+
+	for _, want := range []string{"git checkout abc123", "git apply --binary changes.diff", "agentsandbox --agent claude", "#   claude --dangerously-skip-permissions"} {
+		if !strings.Contains(script, want) {
+			t.Errorf("reproduce script missing %q:\n%s", want, script)
+		}
+	}
+}
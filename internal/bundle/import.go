@@ -0,0 +1,162 @@
+package bundle
+
+import (
+	"archive/tar"
+	"compress/gzip"
+	"encoding/json"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"github.com/thaodangspace/agentsandbox/internal/logs"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+// Bundle is an exported bundle unpacked into a temporary directory
+type Bundle struct {
+	Dir      string
+	Manifest Manifest
+}
+
+// Open unpacks a bundle and reads its manifest; call Close to remove the files
+func Open(path string) (*Bundle, error) {
+	dir, err := os.MkdirTemp("", "agentsandbox-bundle-")
+	if err != nil {
+		return nil, err
+	}
+	b := &Bundle{Dir: dir}
+
+	if err := extract(path, dir); err != nil {
+		b.Close()
+		return nil, err
+	}
+
+	data, err := os.ReadFile(filepath.Join(dir, manifestFile))
+	if err != nil {
+		b.Close()
+		return nil, fmt.Errorf("bundle has no manifest: %w", err)
+	}
+	if err := json.Unmarshal(data, &b.Manifest); err != nil {
+		b.Close()
+		return nil, fmt.Errorf("failed to parse bundle manifest: %w", err)
+	}
+	if b.Manifest.FormatVersion > FormatVersion {
+		b.Close()
+		return nil, fmt.Errorf("bundle format %d is newer than supported (%d); upgrade agentsandbox", b.Manifest.FormatVersion, FormatVersion)
+	}
+
+	return b, nil
+}
+
+// Close removes the unpacked bundle
+func (b *Bundle) Close() error {
+	return os.RemoveAll(b.Dir)
+}
+
+// SessionLogs returns the unpacked session log files (excluding audit logs)
+func (b *Bundle) SessionLogs() ([]string, error) {
+	return filepath.Glob(filepath.Join(b.Dir, sessionsDir, "session-*.jsonl"))
+}
+
+// DiffPath returns the unpacked changes.diff, or "" when the bundle has none
+func (b *Bundle) DiffPath() string {
+	if !b.Manifest.HasDiff {
+		return ""
+	}
+	return filepath.Join(b.Dir, diffFile)
+}
+
+// Import copies the bundle's session logs into the local log index so they show up
+// under `agentsandbox logs`. Logs that already exist are left untouched.
+func Import(b *Bundle) (int, error) {
+	stateDir, err := state.GetStateDir()
+	if err != nil {
+		return 0, err
+	}
+	logsDir := filepath.Join(stateDir, "logs", filepath.Base(b.Manifest.Project), filepath.Base(b.Manifest.Container))
+	if err := os.MkdirAll(logsDir, 0755); err != nil {
+		return 0, fmt.Errorf("failed to create logs directory: %w", err)
+	}
+	// The container never existed here, so keep state gc from archiving these logs
+	if err := os.WriteFile(filepath.Join(logsDir, state.ImportedMarker), nil, 0644); err != nil {
+		return 0, fmt.Errorf("failed to mark imported logs: %w", err)
+	}
+
+	files, err := filepath.Glob(filepath.Join(b.Dir, sessionsDir, "*.jsonl"))
+	if err != nil {
+		return 0, err
+	}
+
+	imported := 0
+	for _, file := range files {
+		dest := filepath.Join(logsDir, filepath.Base(file))
+		if _, err := os.Stat(dest); err == nil {
+			continue
+		}
+		data, err := os.ReadFile(file)
+		if err != nil {
+			return imported, err
+		}
+		if err := os.WriteFile(dest, data, 0644); err != nil {
+			return imported, fmt.Errorf("failed to import %s: %w", filepath.Base(file), err)
+		}
+		if err := logs.EncryptFile(dest); err != nil {
+			return imported, fmt.Errorf("failed to encrypt %s: %w", filepath.Base(file), err)
+		}
+		imported++
+	}
+
+	return imported, nil
+}
+
+// extract unpacks a gzip-compressed tar archive into dest, rejecting entries that escape it
+func extract(path, dest string) error {
+	f, err := os.Open(path)
+	if err != nil {
+		return fmt.Errorf("failed to open bundle: %w", err)
+	}
+	defer f.Close()
+
+	gz, err := gzip.NewReader(f)
+	if err != nil {
+		return fmt.Errorf("not a bundle: %w", err)
+	}
+	defer gz.Close()
+
+	tr := tar.NewReader(gz)
+	for {
+		header, err := tr.Next()
+		if err == io.EOF {
+			return nil
+		}
+		if err != nil {
+			return fmt.Errorf("failed to read bundle: %w", err)
+		}
+		if header.Typeflag != tar.TypeReg {
+			continue
+		}
+
+		name := filepath.Clean(filepath.FromSlash(header.Name))
+		if filepath.IsAbs(name) || name == ".." || strings.HasPrefix(name, ".."+string(filepath.Separator)) {
+			return fmt.Errorf("bundle contains unsafe path %q", header.Name)
+		}
+
+		target := filepath.Join(dest, name)
+		if err := os.MkdirAll(filepath.Dir(target), 0755); err != nil {
+			return err
+		}
+		out, err := os.OpenFile(target, os.O_CREATE|os.O_WRONLY|os.O_TRUNC, os.FileMode(header.Mode)&0755)
+		if err != nil {
+			return err
+		}
+		if _, err := io.Copy(out, tr); err != nil {
+			out.Close()
+			return err
+		}
+		if err := out.Close(); err != nil {
+			return err
+		}
+	}
+}
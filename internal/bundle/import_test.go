@@ -0,0 +1,78 @@
+package bundle
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func writeBundle(t *testing.T, files map[string]string) string {
+	t.Helper()
+	path := filepath.Join(t.TempDir(), "bundle.tar.gz")
+	f, err := os.Create(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer f.Close()
+
+	tw := newTarWriter(f)
+	for name, content := range files {
+		if err := tw.addBytes(name, []byte(content), 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+	if err := tw.Close(); err != nil {
+		t.Fatal(err)
+	}
+	return path
+}
+
+func TestOpen(t *testing.T) {
+	sessionLog := sessionsDir + "/session-20250101-120000.jsonl"
+	path := writeBundle(t, map[string]string{
+		manifestFile: `{"format_version":1,"container":"agentsandbox-api","agent":"claude","has_diff":true,"sessions":["20250101-120000"]}`,
+		diffFile:     "diff --git a/x b/x\n",
+		sessionLog:   `{"level":"user","message":"add tests"}` + "\n",
+	})
+
+	b, err := Open(path)
+	if err != nil {
+		t.Fatalf("Open() error = %v", err)
+	}
+	defer b.Close()
+
+	if b.Manifest.Container != "agentsandbox-api" {
+		t.Errorf("Container = %q", b.Manifest.Container)
+	}
+	if _, err := os.Stat(b.DiffPath()); err != nil {
+		t.Errorf("diff not extracted: %v", err)
+	}
+
+	prompts, err := b.Prompts()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(prompts) != 1 || prompts[0] != "add tests" {
+		t.Errorf("Prompts() = %v", prompts)
+	}
+}
+
+func TestOpenRejects(t *testing.T) {
+	tests := []struct {
+		name  string
+		files map[string]string
+	}{
+		{"unsafe path", map[string]string{manifestFile: `{"format_version":1}`, "../escape": "x"}},
+		{"missing manifest", map[string]string{diffFile: "x"}},
+		{"newer format", map[string]string{manifestFile: `{"format_version":99}`}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if b, err := Open(writeBundle(t, tt.files)); err == nil {
+				b.Close()
+				t.Error("Open() succeeded, want error")
+			}
+		})
+	}
+}
@@ -0,0 +1,51 @@
+package bundle
+
+import (
+	"fmt"
+
+	"github.com/thaodangspace/agentsandbox/internal/git"
+	"github.com/thaodangspace/agentsandbox/internal/logs"
+)
+
+// Replay checks out the bundle's base commit into a new worktree of the repository in
+// repoDir. Unless skipDiff is set the recorded changes are applied on top.
+func Replay(b *Bundle, repoDir string, skipDiff bool) (string, error) {
+	if b.Manifest.BaseCommit == "" {
+		return "", fmt.Errorf("bundle does not record a base commit; it was exported from a project that is not a git repository")
+	}
+
+	name := "replay-" + b.Manifest.CreatedAt.Format("20060102-150405")
+	if len(b.Manifest.Sessions) > 0 {
+		name = "replay-" + b.Manifest.Sessions[0]
+	}
+	worktreePath, err := git.CreateDetachedWorktree(repoDir, name, b.Manifest.BaseCommit)
+	if err != nil {
+		return "", fmt.Errorf("failed to check out %s: %w", b.Manifest.BaseCommit, err)
+	}
+
+	if diff := b.DiffPath(); diff != "" && !skipDiff {
+		if err := git.ApplyPatch(worktreePath, diff); err != nil {
+			return worktreePath, fmt.Errorf("failed to apply recorded changes: %w", err)
+		}
+	}
+
+	return worktreePath, nil
+}
+
+// Prompts returns the prompts recorded across the bundle's sessions, in session order
+func (b *Bundle) Prompts() ([]string, error) {
+	files, err := b.SessionLogs()
+	if err != nil {
+		return nil, err
+	}
+
+	var prompts []string
+	for _, file := range files {
+		events, err := logs.ParseRawLog(file)
+		if err != nil {
+			return nil, fmt.Errorf("failed to read %s: %w", file, err)
+		}
+		prompts = append(prompts, logs.Prompts(events)...)
+	}
+	return prompts, nil
+}
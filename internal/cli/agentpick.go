@@ -0,0 +1,157 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"regexp"
+	"strings"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/picker"
+	"github.com/thaodangspace/agentsandbox/internal/ui"
+)
+
+// resolveAgent returns the agent to start: --agent when given, then the project's
+// last-used agent on a bare run, then default_agent, then one picked from the agents
+// set up on this host. Without a terminal to pick on it reuses the last-used agent,
+// falling back to Claude.
+func resolveAgent(cmd *cobra.Command, lastUsed string, bare bool, settings *config.Settings) (config.Agent, error) {
+	if cmd.Flags().Changed("agent") {
+		return config.ValidateAgent(agentName)
+	}
+
+	var last config.Agent
+	if lastUsed != "" {
+		last, _ = config.ValidateAgent(lastUsed)
+	}
+	if last != "" && bare {
+		return last, nil
+	}
+	if settings.DefaultAgent != "" {
+		return config.ValidateAgent(settings.DefaultAgent)
+	}
+	if continueFlag || shellMode || !ui.IsTerminal(os.Stdin) {
+		if last != "" {
+			return last, nil
+		}
+		return config.ValidateAgent(agentName)
+	}
+
+	agents := availableAgents(last)
+	if len(agents) == 0 {
+		return config.ValidateAgent(agentName)
+	}
+	if len(agents) == 1 {
+		return agents[0], nil
+	}
+
+	items := make([]picker.Item, len(agents))
+	for i, agent := range agents {
+		agent := agent
+		text := agent.DisplayName()
+		if agent == last {
+			text += " (last used)"
+		}
+		items[i] = picker.Item{
+			Text:    text,
+			Preview: func() []string { return agentPreview(agent) },
+		}
+	}
+	index, err := picker.Pick("AGENT", items)
+	if err != nil {
+		return "", err
+	}
+	if index < 0 {
+		return "", fmt.Errorf("no agent selected; pass --agent or set default_agent in settings")
+	}
+	return agents[index], nil
+}
+
+// availableAgents returns the agents installed or logged in on this host, with last first
+func availableAgents(last config.Agent) []config.Agent {
+	var agents []config.Agent
+	for _, agent := range config.AllAgents() {
+		if !agentSetUp(agent) {
+			continue
+		}
+		if agent == last {
+			agents = append([]config.Agent{agent}, agents...)
+		} else {
+			agents = append(agents, agent)
+		}
+	}
+	return agents
+}
+
+// agentSetUp reports whether the agent has a config directory, credentials or an API
+// key on this host, or is installed on the PATH
+func agentSetUp(agent config.Agent) bool {
+	if agentLoggedIn(agent) {
+		return true
+	}
+	_, err := exec.LookPath(agent.Command())
+	return err == nil
+}
+
+func agentLoggedIn(agent config.Agent) bool {
+	for _, env := range agent.APIKeyEnvs() {
+		if os.Getenv(env) != "" {
+			return true
+		}
+	}
+	home, err := os.UserHomeDir()
+	if err != nil {
+		return false
+	}
+	for _, rel := range agent.AuthPaths() {
+		if _, err := os.Stat(filepath.Join(home, rel)); err == nil {
+			return true
+		}
+	}
+	return false
+}
+
+// agentPreview describes how the agent is set up on the host
+func agentPreview(agent config.Agent) []string {
+	lines := []string{"Command: " + agent.Command()}
+	if agentLoggedIn(agent) {
+		lines = append(lines, "Credentials: found")
+	} else {
+		lines = append(lines, "Credentials: not found (run '"+agent.LoginCommand()+"' in the sandbox)")
+	}
+	if out, err := exec.Command(agent.Command(), "--version").Output(); err == nil {
+		lines = append(lines, "Host version: "+strings.TrimSpace(string(out)))
+	}
+	return lines
+}
+
+// bareInvocation reports whether agentsandbox was run without arguments or flags other
+// than output ones, so the project's saved agent and flags apply
+func bareInvocation(cmd *cobra.Command, args []string) bool {
+	n := cmd.Flags().NFlag()
+	if cmd.Flags().Changed("no-emoji") {
+		n--
+	}
+	return n == 0 && len(args) == 0
+}
+
+var shellSafe = regexp.MustCompile(`^[a-zA-Z0-9_./:=@%+,-]+$`)
+
+// agentFlags appends the arguments given after -- to the agent's skip-permission flag,
+// quoted for the shell command that starts the agent
+func agentFlags(skipPermissionFlag string, args []string) string {
+	parts := []string{}
+	if skipPermissionFlag != "" {
+		parts = append(parts, skipPermissionFlag)
+	}
+	for _, arg := range args {
+		if !shellSafe.MatchString(arg) {
+			arg = "'" + strings.ReplaceAll(arg, "'", `'\''`) + "'"
+		}
+		parts = append(parts, arg)
+	}
+	return strings.Join(parts, " ")
+}
@@ -0,0 +1,97 @@
+package cli
+
+import (
+	"bufio"
+	"fmt"
+	"os"
+	"strings"
+	"time"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+)
+
+var (
+	approveCmd = &cobra.Command{
+		Use:   "approve [container]",
+		Short: "Review commands held by the approval gate",
+		Long: `Watch a container for commands held by the approval gate and approve or deny each one.
+The container defaults to the one for the current directory, then the last used container.`,
+		Args: cobra.MaximumNArgs(1),
+		RunE: runApprove,
+	}
+
+	// Approve flags
+	approveList bool
+)
+
+func init() {
+	approveCmd.Flags().BoolVar(&approveList, "list", false, "List pending commands and exit")
+}
+
+func runApprove(cmd *cobra.Command, args []string) error {
+	currentDir, err := os.Getwd()
+	if err != nil {
+		return fmt.Errorf("failed to get current directory: %w", err)
+	}
+
+	name := ""
+	if len(args) > 0 {
+		name = args[0]
+	}
+	containerName, err := container.ResolveContainer(name, currentDir)
+	if err != nil {
+		return err
+	}
+
+	if approveList {
+		requests, err := container.ListGateRequests(containerName)
+		if err != nil {
+			return err
+		}
+		if len(requests) == 0 {
+			fmt.Println("No commands waiting for approval")
+		}
+		for _, req := range requests {
+			fmt.Printf("%s  %s\n", container.Printable(req.ID, ""), container.Printable(req.Command, "    "))
+		}
+		return nil
+	}
+
+	fmt.Printf("Watching %s for commands that need approval (Ctrl+C to stop)...\n", containerName)
+	reader := bufio.NewReader(os.Stdin)
+	answered := make(map[string]bool)
+
+	for {
+		requests, err := container.ListGateRequests(containerName)
+		if err != nil {
+			return err
+		}
+
+		for _, req := range requests {
+			if answered[req.ID] {
+				continue
+			}
+
+			fmt.Printf("\n%s wants to run:\n  %s\nAllow? [y/N]: ", containerName, container.Printable(req.Command, "  "))
+			input, err := reader.ReadString('\n')
+			if err != nil {
+				return nil
+			}
+			approve := strings.EqualFold(strings.TrimSpace(input), "y")
+
+			if err := container.RespondGateRequest(containerName, req.ID, approve); err != nil {
+				fmt.Printf("Warning: %v\n", err)
+				continue
+			}
+			answered[req.ID] = true
+			if approve {
+				fmt.Println("Approved")
+			} else {
+				fmt.Println("Denied")
+			}
+		}
+
+		time.Sleep(time.Second)
+	}
+}
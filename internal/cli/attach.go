@@ -1,15 +1,14 @@
 package cli
 
 import (
-	"fmt"
-
 	"github.com/spf13/cobra"
 	"github.com/thaodangspace/agentsandbox/internal/config"
 	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/failure"
 )
 
 var attachCmd = &cobra.Command{
-	Use:   "attach [container]",
+	Use:   "attach [container|name]",
 	Short: "Attach to an existing container",
 	Args:  cobra.MaximumNArgs(1),
 	RunE:  runAttach,
@@ -19,12 +18,12 @@ func runAttach(cmd *cobra.Command, args []string) error {
 	var containerName string
 
 	if len(args) > 0 {
-		containerName = args[0]
+		containerName = container.ResolveName(args[0])
 	} else {
 		// Load last container
 		lastContainer, err := container.LoadLastContainer()
 		if err != nil || lastContainer == "" {
-			return fmt.Errorf("no container specified and no previous container found")
+			return failure.Errorf(failure.ContainerNotFound, "no container specified and no previous container found")
 		}
 		containerName = lastContainer
 	}
@@ -37,8 +36,9 @@ func runAttach(cmd *cobra.Command, args []string) error {
 
 	// Load settings
 	settings, _ := config.LoadSettings()
-	skipPermissionFlag := settings.SkipPermissionFlags[string(agent)]
+	dir, _ := container.GetContainerDirectory(containerName)
+	skipPermissionFlag := container.PermissionFlag(settings, agent, container.RecordedTrust(dir))
 
-	return container.ResumeContainer(containerName, agent, false, skipPermissionFlag, shellMode, true)
+	return container.ResumeContainer(containerName, agent, false, skipPermissionFlag, shellMode, true, sessionOptions(settings))
 }
 
@@ -0,0 +1,138 @@
+package cli
+
+import (
+	"fmt"
+	"io"
+	"os"
+	"strings"
+	"time"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/bench"
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+)
+
+var (
+	benchCmd = &cobra.Command{
+		Use:   "bench [container|name]",
+		Short: "Time a build or test command on the host and in a sandbox",
+		Long: `Run the same command in the project on the host and inside the sandbox, and compare
+wall-clock time, CPU time and block I/O. The command comes from --command or from
+bench_command in the project's .agentsandbox/settings.json. --copy adds a run on a copy
+of the project in the container's own filesystem, which shows what the bind mount costs.
+Each location runs --runs times and the median run is reported.`,
+		Args: cobra.MaximumNArgs(1),
+		RunE: runBench,
+	}
+
+	// Bench flags
+	benchCommand string
+	benchRuns    int
+	benchCopy    bool
+	benchQuiet   bool
+)
+
+func init() {
+	benchCmd.Flags().StringVar(&benchCommand, "command", "", "Command to time (default: bench_command from the project settings)")
+	benchCmd.Flags().IntVar(&benchRuns, "runs", 3, "Runs per location; the median is reported")
+	benchCmd.Flags().BoolVar(&benchCopy, "copy", false, "Also run in a copy of the project on the container filesystem")
+	benchCmd.Flags().BoolVarP(&benchQuiet, "quiet", "q", false, "Hide the command's output")
+	rootCmd.AddCommand(benchCmd)
+}
+
+func runBench(cmd *cobra.Command, args []string) error {
+	if err := container.CheckDockerAvailability(); err != nil {
+		return err
+	}
+	if benchRuns < 1 {
+		return fmt.Errorf("--runs must be at least 1")
+	}
+	currentDir, err := os.Getwd()
+	if err != nil {
+		return fmt.Errorf("failed to get current directory: %w", err)
+	}
+	name := ""
+	if len(args) > 0 {
+		name = args[0]
+	}
+	containerName, err := container.ResolveContainer(name, currentDir)
+	if err != nil {
+		return err
+	}
+	if running, _ := container.IsContainerRunning(containerName); !running {
+		return fmt.Errorf("container '%s' is not running", containerName)
+	}
+	projectDir, err := container.GetContainerDirectory(containerName)
+	if err != nil || projectDir == "" {
+		return fmt.Errorf("failed to find the project directory of %s", containerName)
+	}
+
+	command := benchCommand
+	if command == "" {
+		project, err := config.LoadProjectSettings(projectDir)
+		if err != nil {
+			return err
+		}
+		command = project.BenchCommand
+	}
+	if command == "" {
+		return fmt.Errorf("no command to benchmark; pass --command or set bench_command in %s", config.ProjectSettingsPath(projectDir))
+	}
+
+	var out io.Writer = os.Stdout
+	if benchQuiet {
+		out = io.Discard
+	}
+	locations := []func() (bench.Measurement, error){
+		func() (bench.Measurement, error) { return bench.RunHost(projectDir, command, out) },
+		func() (bench.Measurement, error) {
+			return container.BenchSandbox(containerName, projectDir, command, false, out)
+		},
+	}
+	if benchCopy {
+		locations = append(locations, func() (bench.Measurement, error) {
+			return container.BenchSandbox(containerName, projectDir, command, true, out)
+		})
+	}
+
+	var results []bench.Measurement
+	for _, run := range locations {
+		var runs []bench.Measurement
+		for i := 0; i < benchRuns; i++ {
+			m, err := run()
+			if err != nil {
+				return err
+			}
+			fmt.Printf("==> %s, run %d/%d: %s\n", m.Where, i+1, benchRuns, m.Wall.Round(10*time.Millisecond))
+			runs = append(runs, m)
+		}
+		results = append(results, bench.Median(runs))
+	}
+
+	fmt.Printf("\n%s\n\n", command)
+	fmt.Printf("%-32s %10s %9s %10s %10s %10s %5s\n", "WHERE", "WALL", "VS HOST", "CPU", "READ", "WRITTEN", "EXIT")
+	fmt.Println(strings.Repeat("-", 92))
+	host := results[0]
+	for _, m := range results {
+		delta := "-"
+		if m.Where != host.Where && host.Wall > 0 {
+			delta = fmt.Sprintf("%+.0f%%", (float64(m.Wall)/float64(host.Wall)-1)*100)
+		}
+		cpu, read, written := "?", "?", "?"
+		if m.CPUKnown {
+			cpu = m.CPU.Round(10*time.Millisecond).String()
+		}
+		if m.IOKnown {
+			read, written = formatBytes(m.ReadBytes), formatBytes(m.WriteBytes)
+		}
+		fmt.Printf("%-32s %10s %9s %10s %10s %10s %5d\n", m.Where, m.Wall.Round(10*time.Millisecond), delta, cpu, read, written, m.ExitCode)
+	}
+	for _, m := range results {
+		if m.ExitCode != 0 {
+			fmt.Println("\nWarning: the command failed in at least one location, so the timings may not be comparable")
+			break
+		}
+	}
+	return nil
+}
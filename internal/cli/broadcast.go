@@ -0,0 +1,106 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"strings"
+	"time"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/git"
+	"github.com/thaodangspace/agentsandbox/internal/headless"
+)
+
+var (
+	broadcastCmd = &cobra.Command{
+		Use:   "broadcast",
+		Short: "Run a prompt headlessly in every running sandbox and collect the results",
+		Long: `Run a single prompt headlessly in all running sandboxes at once, or in those matching
+--filter, and aggregate the output into one report. Each run is also recorded as a
+session log in its sandbox, as with 'run'.`,
+		RunE: runBroadcast,
+	}
+
+	// Broadcast flags
+	broadcastPrompt   string
+	broadcastFilters  []string
+	broadcastParallel int
+	broadcastReport   string
+)
+
+func init() {
+	broadcastCmd.Flags().StringVar(&broadcastPrompt, "prompt", "", "Prompt text to run in every sandbox")
+	broadcastCmd.Flags().StringSliceVar(&broadcastFilters, "filter", nil, "Only target sandboxes matching key=value (agent, project, branch, name); repeatable")
+	broadcastCmd.Flags().IntVar(&broadcastParallel, "parallel", 4, "Maximum number of sandboxes to run at once")
+	broadcastCmd.Flags().StringVar(&broadcastReport, "report", "", "Write the Markdown report to this file instead of printing it")
+	rootCmd.AddCommand(broadcastCmd)
+}
+
+func runBroadcast(cmd *cobra.Command, args []string) error {
+	if broadcastPrompt == "" {
+		return fmt.Errorf("a prompt is required: use --prompt")
+	}
+	if err := container.CheckDockerAvailability(); err != nil {
+		return err
+	}
+
+	containers, err := container.ListSandboxes(false)
+	if err != nil {
+		return fmt.Errorf("failed to list containers: %w", err)
+	}
+
+	var rows []sandboxRow
+	for _, info := range containers {
+		if info.Directory == "" {
+			fmt.Printf("Warning: skipping %s: its project directory is unknown\n", info.Name)
+			continue
+		}
+		rows = append(rows, sandboxRow{ContainerInfo: info, Branch: git.CurrentBranch(info.Directory)})
+	}
+	rows, err = filterRows(rows, broadcastFilters)
+	if err != nil {
+		return err
+	}
+	if len(rows) == 0 {
+		fmt.Println("No running sandboxes match.")
+		return nil
+	}
+
+	targets := make([]container.ContainerInfo, len(rows))
+	names := make([]string, len(rows))
+	for i, row := range rows {
+		targets[i] = row.ContainerInfo
+		names[i] = row.Name
+	}
+	fmt.Printf("Broadcasting to %d sandbox(es): %s\n", len(targets), strings.Join(names, ", "))
+
+	results := headless.Broadcast(targets, broadcastPrompt, broadcastParallel)
+	report := headless.FormatReport(broadcastPrompt, results)
+
+	failed := 0
+	for _, r := range results {
+		if r.Err != nil {
+			failed++
+		}
+	}
+
+	if broadcastReport == "" {
+		fmt.Print(report)
+	} else {
+		if err := os.WriteFile(broadcastReport, []byte(report), 0644); err != nil {
+			return fmt.Errorf("failed to write report: %w", err)
+		}
+		fmt.Printf("%-40s %-8s %-10s %s\n", "CONTAINER", "STATUS", "DURATION", "FILES CHANGED")
+		fmt.Println(strings.Repeat("-", 80))
+		for _, r := range results {
+			fmt.Printf("%-40s %-8s %-10s %d\n", r.Container, r.Status(), r.Duration.Round(time.Second), r.FilesChanged)
+		}
+		fmt.Printf("Report written to %s\n", broadcastReport)
+	}
+
+	if failed > 0 {
+		return fmt.Errorf("%d of %d sandbox(es) failed", failed, len(results))
+	}
+	return nil
+}
@@ -0,0 +1,134 @@
+package cli
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/failure"
+	"github.com/thaodangspace/agentsandbox/internal/git"
+)
+
+var (
+	changesCmd = &cobra.Command{
+		Use:   "changes [container|name]",
+		Short: "Summarize uncommitted changes across sandboxes, or show one sandbox's changes",
+		Long: `Without arguments, list every running sandbox with the number of files it changed and
+the lines added and removed, across all repositories in its workspace. Given a container
+or session name, show its changed files per repository, or the full patch with --diff.`,
+		Args: cobra.MaximumNArgs(1),
+		RunE: runChanges,
+	}
+
+	// Changes flags
+	changesDiff bool
+)
+
+func init() {
+	changesCmd.Flags().BoolVar(&changesDiff, "diff", false, "Print the full patch for each repository instead of the changed files")
+	rootCmd.AddCommand(changesCmd)
+}
+
+func runChanges(cmd *cobra.Command, args []string) error {
+	if err := container.CheckDockerAvailability(); err != nil {
+		return err
+	}
+	if len(args) == 0 {
+		return showChangesDashboard()
+	}
+
+	containerName := container.ResolveName(args[0])
+	if exists, _ := container.ContainerExists(containerName); !exists {
+		return failure.Errorf(failure.ContainerNotFound, "container '%s' does not exist", args[0])
+	}
+	return showContainerChanges(containerName)
+}
+
+// showChangesDashboard prints one line per running sandbox with its change totals
+func showChangesDashboard() error {
+	containers, err := container.ListSandboxes(false)
+	if err != nil {
+		return fmt.Errorf("failed to list containers: %w", err)
+	}
+	if len(containers) == 0 {
+		fmt.Println("No running Agent Sandbox containers found.")
+		return nil
+	}
+
+	fmt.Printf("%-40s %-20s %6s %8s %8s\n", "CONTAINER", "PROJECT", "FILES", "ADDED", "REMOVED")
+	fmt.Println(strings.Repeat("-", 86))
+	for _, info := range containers {
+		dirs, err := container.WorkspaceDirs(info.Name)
+		if err != nil || len(dirs) == 0 {
+			fmt.Printf("%-40s %-20s %6s %8s %8s\n", info.Name, info.Project, "?", "?", "?")
+			continue
+		}
+
+		files, added, deleted := 0, 0, 0
+		for _, dir := range dirs {
+			stats, err := git.DiffStats(dir)
+			if err != nil {
+				// Not a git repository
+				continue
+			}
+			for _, stat := range stats {
+				files++
+				added += stat.Added
+				deleted += stat.Deleted
+			}
+		}
+		fmt.Printf("%-40s %-20s %6d %8s %8s\n", info.Name, info.Project, files, fmt.Sprintf("+%d", added), fmt.Sprintf("-%d", deleted))
+	}
+	fmt.Println("\nRun 'agentsandbox changes <container>' for the changed files, or add --diff for the patch.")
+	return nil
+}
+
+// showContainerChanges prints the changes in each repository of a container's workspace
+func showContainerChanges(containerName string) error {
+	dirs, err := container.WorkspaceDirs(containerName)
+	if err != nil {
+		return err
+	}
+	if len(dirs) == 0 {
+		return fmt.Errorf("could not determine the project directory of %s", containerName)
+	}
+
+	for i, dir := range dirs {
+		if i > 0 {
+			fmt.Println()
+		}
+		fmt.Printf("== %s\n", dir)
+
+		if changesDiff {
+			diff, err := git.WorkingTreeDiff(dir)
+			if err != nil {
+				fmt.Printf("Warning: failed to diff %s: %v\n", dir, err)
+				continue
+			}
+			if diff == "" {
+				fmt.Println("No changes")
+				continue
+			}
+			fmt.Print(diff)
+			continue
+		}
+
+		stats, err := git.DiffStats(dir)
+		if err != nil {
+			fmt.Printf("Warning: failed to list changes in %s: %v\n", dir, err)
+			continue
+		}
+		if len(stats) == 0 {
+			fmt.Println("No changes")
+		}
+		for _, stat := range stats {
+			if stat.Binary {
+				fmt.Printf("  %-12s %s\n", "binary", stat.Path)
+				continue
+			}
+			fmt.Printf("  %-12s %s\n", fmt.Sprintf("+%d -%d", stat.Added, stat.Deleted), stat.Path)
+		}
+	}
+	return nil
+}
@@ -0,0 +1,132 @@
+package cli
+
+import (
+	"fmt"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/failure"
+)
+
+var (
+	checkpointCmd = &cobra.Command{
+		Use:   "checkpoint <container|name> [checkpoint]",
+		Short: "(experimental) Freeze a sandbox's processes to disk with CRIU",
+		Long: `Freeze a sandbox's processes to disk with docker checkpoint (CRIU) so a long-running
+agent survives a host reboot, then bring it back with 'agentsandbox restore'. The
+container is stopped afterwards unless --leave-running is given.
+
+Experimental: needs a Linux Docker daemon with experimental features enabled and CRIU
+installed, and not every process can be checkpointed.`,
+		Args: cobra.RangeArgs(1, 2),
+		RunE: runCheckpoint,
+	}
+
+	restoreCmd = &cobra.Command{
+		Use:   "restore <container|name> [checkpoint]",
+		Short: "(experimental) Start a stopped sandbox from a checkpoint",
+		Long: `Start a stopped sandbox from a checkpoint made with 'agentsandbox checkpoint', by
+default the most recent one. Use --list to see the available checkpoints.`,
+		Args: cobra.RangeArgs(1, 2),
+		RunE: runRestore,
+	}
+
+	// Checkpoint flags
+	checkpointLeaveRunning bool
+	restoreList            bool
+)
+
+func init() {
+	checkpointCmd.Flags().BoolVar(&checkpointLeaveRunning, "leave-running", false, "Keep the container running after the checkpoint")
+	restoreCmd.Flags().BoolVar(&restoreList, "list", false, "List the container's checkpoints instead of restoring")
+
+	rootCmd.AddCommand(checkpointCmd)
+	rootCmd.AddCommand(restoreCmd)
+}
+
+func runCheckpoint(cmd *cobra.Command, args []string) error {
+	if err := checkpointPreflight(); err != nil {
+		return err
+	}
+
+	containerName := container.ResolveName(args[0])
+	info, err := container.InspectContainer(containerName)
+	if err != nil {
+		return err
+	}
+	if !info.Exists {
+		return failure.Errorf(failure.ContainerNotFound, "container '%s' does not exist", args[0])
+	}
+	if !info.Running || info.Paused {
+		return fmt.Errorf("container '%s' must be running to be checkpointed", args[0])
+	}
+
+	checkpoint := container.NewCheckpointName()
+	if len(args) > 1 {
+		checkpoint = args[1]
+	}
+	fmt.Printf("Checkpointing %s as %s (experimental)...\n", containerName, checkpoint)
+	if err := container.CreateCheckpoint(containerName, checkpoint, checkpointLeaveRunning); err != nil {
+		return err
+	}
+	fmt.Printf("Saved checkpoint %s; run 'agentsandbox restore %s' to resume it\n", checkpoint, args[0])
+	return nil
+}
+
+func runRestore(cmd *cobra.Command, args []string) error {
+	if err := checkpointPreflight(); err != nil {
+		return err
+	}
+
+	containerName := container.ResolveName(args[0])
+	info, err := container.InspectContainer(containerName)
+	if err != nil {
+		return err
+	}
+	if !info.Exists {
+		return failure.Errorf(failure.ContainerNotFound, "container '%s' does not exist", args[0])
+	}
+
+	checkpoints, err := container.ListCheckpoints(containerName)
+	if err != nil {
+		return err
+	}
+	if restoreList {
+		if len(checkpoints) == 0 {
+			fmt.Printf("No checkpoints for %s\n", containerName)
+		}
+		for _, checkpoint := range checkpoints {
+			fmt.Println(checkpoint)
+		}
+		return nil
+	}
+
+	if info.Running {
+		return fmt.Errorf("container '%s' is running; checkpoints can only be restored into a stopped container", args[0])
+	}
+	checkpoint := container.LatestCheckpoint(checkpoints)
+	if len(args) > 1 {
+		checkpoint = args[1]
+	}
+	if checkpoint == "" {
+		return fmt.Errorf("container '%s' has no checkpoints", args[0])
+	}
+
+	fmt.Printf("Restoring %s from checkpoint %s (experimental)...\n", containerName, checkpoint)
+	if err := container.RestoreCheckpoint(containerName, checkpoint); err != nil {
+		return err
+	}
+	fmt.Printf("Restored %s; run 'agentsandbox attach %s' to rejoin it\n", containerName, args[0])
+	return nil
+}
+
+// checkpointPreflight checks Docker is available and can make checkpoints
+func checkpointPreflight() error {
+	if err := container.CheckDockerAvailability(); err != nil {
+		return err
+	}
+	if err := container.CheckpointSupport(); err != nil {
+		return fmt.Errorf("checkpoints are not available: %w", err)
+	}
+	return nil
+}
@@ -0,0 +1,175 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"time"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/clipboard"
+	"github.com/thaodangspace/agentsandbox/internal/service"
+)
+
+var (
+	clipboardCmd = &cobra.Command{
+		Use:   "clipboard",
+		Short: "Manage the host clipboard watcher",
+	}
+
+	clipboardStatusCmd = &cobra.Command{
+		Use:   "status",
+		Short: "Show whether the clipboard watcher is running",
+		RunE:  runClipboardStatus,
+	}
+
+	clipboardStopCmd = &cobra.Command{
+		Use:   "stop",
+		Short: "Stop the clipboard watcher",
+		RunE:  runClipboardStop,
+	}
+
+	clipboardRestartCmd = &cobra.Command{
+		Use:   "restart",
+		Short: "Restart the clipboard watcher",
+		RunE:  runClipboardRestart,
+	}
+
+	clipboardCleanCmd = &cobra.Command{
+		Use:   "clean",
+		Short: "Delete captured clipboard images",
+		Long:  "Delete all captured clipboard images, or only those outside the retention limits with --prune.",
+		RunE:  runClipboardClean,
+	}
+
+	clipboardWatchCmd = &cobra.Command{
+		Use:    "watch",
+		Short:  "Run the clipboard watcher in the foreground",
+		Hidden: true,
+		RunE:   runClipboardWatch,
+	}
+
+	// Clipboard flags
+	clipboardPruneOnly bool
+)
+
+func init() {
+	clipboardCleanCmd.Flags().BoolVar(&clipboardPruneOnly, "prune", false, "Only delete images outside the configured retention limits")
+
+	clipboardCmd.AddCommand(clipboardStatusCmd)
+	clipboardCmd.AddCommand(clipboardStopCmd)
+	clipboardCmd.AddCommand(clipboardRestartCmd)
+	clipboardCmd.AddCommand(clipboardCleanCmd)
+	clipboardCmd.AddCommand(clipboardWatchCmd)
+	rootCmd.AddCommand(clipboardCmd)
+}
+
+func runClipboardStatus(cmd *cobra.Command, args []string) error {
+	status, err := clipboard.GetStatus()
+	if err != nil {
+		return fmt.Errorf("failed to get clipboard status: %w", err)
+	}
+
+	switch {
+	case status.Running:
+		fmt.Printf("Watcher:      running (PID %d)\n", status.PID)
+	case status.StalePID:
+		fmt.Printf("Watcher:      not running (stale PID %d, run 'agentsandbox clipboard restart')\n", status.PID)
+	default:
+		fmt.Println("Watcher:      not running")
+	}
+
+	fmt.Printf("Directory:    %s\n", status.Dir)
+	fmt.Printf("Files:        %d (%s)\n", status.Files, formatBytes(status.Size))
+	if status.LastCapture.IsZero() {
+		fmt.Println("Last capture: never")
+	} else {
+		fmt.Printf("Last capture: %s (%s ago)\n", status.LastCapture.Format(time.RFC3339), time.Since(status.LastCapture).Round(time.Second))
+	}
+
+	return nil
+}
+
+func runClipboardStop(cmd *cobra.Command, args []string) error {
+	stopped, err := clipboard.StopWatcher()
+	if err != nil {
+		return err
+	}
+
+	if stopped {
+		fmt.Println("Clipboard watcher stopped")
+	} else {
+		fmt.Println("Clipboard watcher is not running")
+	}
+	return nil
+}
+
+func runClipboardRestart(cmd *cobra.Command, args []string) error {
+	// Let the service manager own the watcher when it is installed as a service
+	if s, err := service.Find("clipboard-watcher"); err == nil && s.Installed() {
+		if err := s.Restart(); err != nil {
+			return err
+		}
+		fmt.Printf("Clipboard watcher restarted (%s)\n", s.Label())
+		return nil
+	}
+
+	if err := clipboard.RestartWatcher(); err != nil {
+		return err
+	}
+
+	pid, err := clipboard.LoadWatcherPID()
+	if err != nil {
+		return err
+	}
+	fmt.Printf("Clipboard watcher started (PID %d)\n", pid)
+	return nil
+}
+
+func runClipboardClean(cmd *cobra.Command, args []string) error {
+	dir, err := clipboard.GetClipboardDir()
+	if err != nil {
+		return err
+	}
+
+	var removed int
+	if clipboardPruneOnly {
+		removed, err = clipboard.Prune(dir, clipboard.LoadRetention())
+	} else {
+		removed, err = clipboard.Clean(dir)
+	}
+	if err != nil {
+		return fmt.Errorf("failed to clean clipboard directory: %w", err)
+	}
+
+	fmt.Printf("Removed %d clipboard image(s) from %s\n", removed, dir)
+	return nil
+}
+
+func runClipboardWatch(cmd *cobra.Command, args []string) error {
+	dir, err := clipboard.EnsureClipboardDir()
+	if err != nil {
+		return err
+	}
+
+	// Record our own PID so status and stop also work when a service manager started us
+	if err := clipboard.SaveWatcherPID(os.Getpid()); err != nil {
+		return err
+	}
+	defer clipboard.ClearWatcherPID()
+
+	return clipboard.Watch(dir)
+}
+
+// formatBytes renders a byte count in human-readable units
+func formatBytes(size int64) string {
+	const unit = 1024
+	if size < unit {
+		return fmt.Sprintf("%d B", size)
+	}
+	div, exp := int64(unit), 0
+	for n := size / unit; n >= unit; n /= unit {
+		div *= unit
+		exp++
+	}
+	return fmt.Sprintf("%.1f %ciB", float64(size)/float64(div), "KMGTPE"[exp])
+}
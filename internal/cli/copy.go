@@ -0,0 +1,68 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+)
+
+var (
+	pushCmd = &cobra.Command{
+		Use:   "push <host-path> [[container:]path]",
+		Short: "Copy a file or directory into a container",
+		Long: `Copy a file or directory into a container with docker cp.
+The container defaults to the one for the current directory, then the last used container.
+Relative container paths are resolved against the container's project directory.`,
+		Args: cobra.RangeArgs(1, 2),
+		RunE: runPush,
+	}
+
+	pullCmd = &cobra.Command{
+		Use:   "pull [container:]path [host-path]",
+		Short: "Copy a file or directory out of a container",
+		Long: `Copy a file or directory out of a container with docker cp.
+The container defaults to the one for the current directory, then the last used container.
+Relative container paths are resolved against the container's project directory.`,
+		Args: cobra.RangeArgs(1, 2),
+		RunE: runPull,
+	}
+)
+
+func runPush(cmd *cobra.Command, args []string) error {
+	currentDir, err := os.Getwd()
+	if err != nil {
+		return fmt.Errorf("failed to get current directory: %w", err)
+	}
+
+	name, containerPath := "", "."
+	if len(args) > 1 {
+		name, containerPath = container.ParseCopyTarget(args[1])
+	}
+
+	containerName, err := container.ResolveContainer(name, currentDir)
+	if err != nil {
+		return err
+	}
+	return container.CopyToContainer(containerName, args[0], containerPath)
+}
+
+func runPull(cmd *cobra.Command, args []string) error {
+	currentDir, err := os.Getwd()
+	if err != nil {
+		return fmt.Errorf("failed to get current directory: %w", err)
+	}
+
+	name, containerPath := container.ParseCopyTarget(args[0])
+	hostPath := "."
+	if len(args) > 1 {
+		hostPath = args[1]
+	}
+
+	containerName, err := container.ResolveContainer(name, currentDir)
+	if err != nil {
+		return err
+	}
+	return container.CopyFromContainer(containerName, containerPath, hostPath)
+}
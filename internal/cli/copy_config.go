@@ -2,10 +2,14 @@ package cli
 
 import (
 	"fmt"
+	"os"
 
 	"github.com/spf13/cobra"
 	"github.com/thaodangspace/agentsandbox/internal/config"
 	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/failure"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+	"github.com/thaodangspace/agentsandbox/internal/ui"
 )
 
 var copyConfigCmd = &cobra.Command{
@@ -23,7 +27,7 @@ func init() {
 }
 
 func runCopyConfig(cmd *cobra.Command, args []string) error {
-	containerName := args[0]
+	containerName := container.ResolveName(args[0])
 
 	// Verify container exists
 	exists, err := container.ContainerExists(containerName)
@@ -31,7 +35,7 @@ func runCopyConfig(cmd *cobra.Command, args []string) error {
 		return fmt.Errorf("failed to check if container exists: %w", err)
 	}
 	if !exists {
-		return fmt.Errorf("container '%s' does not exist", containerName)
+		return failure.Errorf(failure.ContainerNotFound, "container '%s' does not exist", containerName)
 	}
 
 	// Use the agent specified in the flag
@@ -40,11 +44,16 @@ func runCopyConfig(cmd *cobra.Command, args []string) error {
 		return err
 	}
 
+	level := state.TrustRestricted
+	if dir, err := container.GetContainerDirectory(containerName); err == nil {
+		level = container.RecordedTrust(dir)
+	}
+
 	// Copy configs
-	if err := container.CopyAgentConfigsToContainer(containerName, agent); err != nil {
+	if err := container.CopyAgentConfigsToContainer(os.Stdout, containerName, agent, level); err != nil {
 		return fmt.Errorf("failed to copy configs: %w", err)
 	}
 
-	fmt.Println("\n✅ Successfully copied agent configurations to container!")
+	fmt.Printf("\n%sSuccessfully copied agent configurations to container!\n", ui.Success())
 	return nil
 }
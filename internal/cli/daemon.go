@@ -0,0 +1,125 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"time"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/maintenance"
+)
+
+const maintenanceUnit = "agentsandbox-maintenance"
+
+var (
+	daemonCmd = &cobra.Command{
+		Use:   "daemon",
+		Short: "Run background maintenance (log rotation, clipboard pruning, idle stop, image GC)",
+		RunE:  runDaemon,
+	}
+
+	daemonInstallTimerCmd = &cobra.Command{
+		Use:   "install-timer",
+		Short: "Write a systemd user timer that runs one maintenance pass periodically",
+		RunE:  runDaemonInstallTimer,
+	}
+
+	// Daemon flags
+	daemonInterval time.Duration
+	daemonOnce     bool
+)
+
+func init() {
+	daemonCmd.Flags().DurationVar(&daemonInterval, "interval", time.Hour, "Time between maintenance passes")
+	daemonCmd.Flags().BoolVar(&daemonOnce, "once", false, "Run a single maintenance pass and exit")
+	daemonInstallTimerCmd.Flags().DurationVar(&daemonInterval, "interval", time.Hour, "Time between maintenance passes")
+
+	daemonCmd.AddCommand(daemonInstallTimerCmd)
+	rootCmd.AddCommand(daemonCmd)
+}
+
+func runDaemon(cmd *cobra.Command, args []string) error {
+	if daemonInterval <= 0 {
+		return fmt.Errorf("--interval must be positive")
+	}
+
+	for {
+		// Reload settings each pass so edits apply without a restart
+		settings, err := config.LoadSettings()
+		if err != nil {
+			fmt.Printf("Warning: failed to load settings: %v\n", err)
+			settings = config.DefaultSettings()
+		}
+
+		report := maintenance.RunOnce(settings)
+		fmt.Printf("%s maintenance: %d clipboard image(s) pruned, %d log file(s) removed, %d idle container(s) stopped\n",
+			time.Now().Format("2006-01-02 15:04:05"), report.ClipboardPruned, report.LogsRemoved, len(report.Stopped))
+		if report.StateGC != nil && !report.StateGC.Empty() {
+			printGCReport(report.StateGC, false)
+		}
+
+		if daemonOnce {
+			return nil
+		}
+		time.Sleep(daemonInterval)
+	}
+}
+
+func runDaemonInstallTimer(cmd *cobra.Command, args []string) error {
+	if daemonInterval < time.Minute {
+		return fmt.Errorf("--interval must be at least 1m")
+	}
+
+	executable, err := os.Executable()
+	if err != nil {
+		return fmt.Errorf("failed to locate agentsandbox binary: %w", err)
+	}
+
+	homeDir, err := os.UserHomeDir()
+	if err != nil {
+		return fmt.Errorf("failed to get home directory: %w", err)
+	}
+	unitDir := filepath.Join(homeDir, ".config", "systemd", "user")
+	if err := os.MkdirAll(unitDir, 0755); err != nil {
+		return fmt.Errorf("failed to create systemd user directory: %w", err)
+	}
+
+	service := fmt.Sprintf(`[Unit]
+Description=agentsandbox maintenance
+
+[Service]
+Type=oneshot
+ExecStart=%s daemon --once
+`, executable)
+
+	timer := fmt.Sprintf(`[Unit]
+Description=Run agentsandbox maintenance periodically
+
+[Timer]
+OnBootSec=5min
+OnUnitActiveSec=%ds
+Persistent=true
+
+[Install]
+WantedBy=timers.target
+`, int(daemonInterval.Seconds()))
+
+	files := map[string]string{
+		maintenanceUnit + ".service": service,
+		maintenanceUnit + ".timer":   timer,
+	}
+	for name, content := range files {
+		path := filepath.Join(unitDir, name)
+		if err := os.WriteFile(path, []byte(content), 0644); err != nil {
+			return fmt.Errorf("failed to write %s: %w", path, err)
+		}
+		fmt.Printf("Wrote %s\n", path)
+	}
+
+	fmt.Println("Enable it with:")
+	fmt.Printf("  systemctl --user daemon-reload && systemctl --user enable --now %s.timer\n", maintenanceUnit)
+	fmt.Println("Then set \"maintenance\": { \"background\": true } to skip housekeeping on every start.")
+	return nil
+}
@@ -0,0 +1,54 @@
+package cli
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/failure"
+)
+
+// errorFormat is "text" or "json", set with --error-format
+var errorFormat string
+
+// jsonError is the shape of an error printed with --error-format json
+type jsonError struct {
+	Error struct {
+		Kind     string `json:"kind"`
+		Message  string `json:"message"`
+		ExitCode int    `json:"exit_code"`
+	} `json:"error"`
+	// CrashReport is the path of the crash report written for the error, if any
+	CrashReport string `json:"crash_report,omitempty"`
+}
+
+// usageError tags flag parsing errors so they exit with the usage code
+func usageError(cmd *cobra.Command, err error) error {
+	return failure.Wrap(failure.Usage, err)
+}
+
+// PrintError prints the error a command failed with to stderr, as a line of text or
+// with --error-format json as a JSON object with its kind and exit code. report is
+// the path of the crash report written for it, if any.
+func PrintError(err error, report string) {
+	if errorFormat != "json" {
+		fmt.Fprintf(os.Stderr, "Error: %v\n", err)
+		if report != "" {
+			fmt.Fprintf(os.Stderr, "Crash report written to %s\n", report)
+		}
+		return
+	}
+
+	var out jsonError
+	out.Error.Kind = failure.KindOf(err).String()
+	out.Error.Message = err.Error()
+	out.Error.ExitCode = failure.ExitCode(err)
+	out.CrashReport = report
+	data, marshalErr := json.Marshal(out)
+	if marshalErr != nil {
+		fmt.Fprintf(os.Stderr, "Error: %v\n", err)
+		return
+	}
+	fmt.Fprintln(os.Stderr, string(data))
+}
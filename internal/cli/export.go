@@ -0,0 +1,47 @@
+package cli
+
+import (
+	"fmt"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/bundle"
+)
+
+var (
+	exportCmd = &cobra.Command{
+		Use:   "export <container|session>",
+		Short: "Export session logs, changes and metadata as a shareable bundle",
+		Args:  cobra.ExactArgs(1),
+		RunE:  runExport,
+	}
+
+	// Export flags
+	exportOutput string
+)
+
+func init() {
+	exportCmd.Flags().StringVarP(&exportOutput, "output", "o", "", "Bundle file to write (default: <container|session>.tar.gz)")
+}
+
+func runExport(cmd *cobra.Command, args []string) error {
+	target, err := bundle.ResolveTarget(args[0])
+	if err != nil {
+		return err
+	}
+
+	output := exportOutput
+	if output == "" {
+		output = args[0] + ".tar.gz"
+	}
+
+	manifest, err := bundle.Export(target, Version(), output)
+	if err != nil {
+		return err
+	}
+
+	fmt.Printf("Exported %d session(s) of %s to %s\n", len(manifest.Sessions), manifest.Container, output)
+	if manifest.HasDiff {
+		fmt.Println("Included uncommitted changes from the project as changes.diff")
+	}
+	return nil
+}
@@ -0,0 +1,105 @@
+package cli
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"time"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/forge"
+	"github.com/thaodangspace/agentsandbox/internal/git"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+var fromIssueCmd = &cobra.Command{
+	Use:   "from-issue <url|number>",
+	Short: "Start a sandbox that works on a GitHub issue",
+	Long: `Fetch a GitHub issue, create an issue-<number> worktree and a container for it, and
+run the agent headlessly with the issue title and body as its prompt.
+
+A plain issue number refers to the repository of the "origin" remote.`,
+	Args: cobra.ExactArgs(1),
+	RunE: runFromIssue,
+}
+
+func runFromIssue(cmd *cobra.Command, args []string) error {
+	agent, err := config.ValidateAgent(agentName)
+	if err != nil {
+		return err
+	}
+
+	currentDir, err := os.Getwd()
+	if err != nil {
+		return fmt.Errorf("failed to get current directory: %w", err)
+	}
+
+	remote, number, err := forge.ParseIssueRef(args[0])
+	if err != nil {
+		return err
+	}
+	if remote.Host == "" {
+		remoteURL, err := git.RemoteURL(currentDir, "origin")
+		if err != nil {
+			return err
+		}
+		if remote, err = git.ParseRemoteURL(remoteURL); err != nil {
+			return err
+		}
+	}
+
+	issue, err := forge.FetchIssue(remote, number)
+	if err != nil {
+		return err
+	}
+	fmt.Printf("Issue #%d: %s\n", issue.Number, issue.Title)
+
+	settings, err := config.LoadSettings()
+	if err != nil {
+		fmt.Printf("Warning: failed to load settings: %v\n", err)
+		settings = config.DefaultSettings()
+	}
+	level := container.RecordedTrust(currentDir)
+	skipPermissionFlag := container.PermissionFlag(settings, agent, level)
+
+	if err := container.CheckDockerAvailability(); err != nil {
+		return err
+	}
+
+	branch := fmt.Sprintf("issue-%d", issue.Number)
+	worktreePath, err := git.CreateWorktree(currentDir, branch)
+	if err != nil {
+		return fmt.Errorf("failed to create worktree for branch %s: %w", branch, err)
+	}
+
+	containerName := container.GenerateContainerName(worktreePath, agent)
+	if exists, _ := container.ContainerExists(containerName); exists {
+		fmt.Printf("Reusing existing container: %s\n", containerName)
+	} else {
+		fmt.Printf("Starting %s Agent Sandbox container: %s\n", agent.DisplayName(), containerName)
+		if err := container.CreateContainer(containerName, worktreePath, "", agent, skipPermissionFlag, false, false, nil, container.CreateOptions{Trust: level}); err != nil {
+			return fmt.Errorf("failed to create container: %w", err)
+		}
+	}
+
+	link := state.IssueLink{
+		Number:    issue.Number,
+		Title:     issue.Title,
+		URL:       issue.URL,
+		Branch:    branch,
+		Timestamp: time.Now(),
+	}
+	if err := state.SaveIssueLink(containerName, worktreePath, link); err != nil {
+		fmt.Printf("Warning: failed to record issue link: %v\n", err)
+	}
+
+	if err := container.RunHeadless(context.Background(), containerName, worktreePath, agent, skipPermissionFlag, issue.Prompt(), os.Stdout, os.Stderr); err != nil {
+		return err
+	}
+
+	fmt.Printf("\nReview the changes in %s, then open a pull request with:\n", worktreePath)
+	fmt.Printf("  agentsandbox pr --container %s --title %q\n", containerName, issue.Title)
+	return nil
+}
@@ -0,0 +1,162 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strconv"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/image"
+	"github.com/thaodangspace/agentsandbox/internal/language"
+	"github.com/thaodangspace/agentsandbox/internal/templates"
+)
+
+var (
+	imageCmd = &cobra.Command{
+		Use:   "image",
+		Short: "Inspect and customize the sandbox image",
+	}
+
+	imageShowCmd = &cobra.Command{
+		Use:   "show-dockerfile",
+		Short: "Print the Dockerfile that would be used for this directory",
+		RunE:  runImageShow,
+	}
+
+	imageEditCmd = &cobra.Command{
+		Use:   "edit",
+		Short: "Write the generated Dockerfile to .agentsandbox/Dockerfile for customization",
+		RunE:  runImageEdit,
+	}
+
+	// Image flags
+	imageTemplate string
+	imageForce    bool
+)
+
+func init() {
+	imageCmd.PersistentFlags().StringVar(&imageTemplate, "template", "", "Project template to include in the generated Dockerfile")
+	imageEditCmd.Flags().BoolVar(&imageForce, "force", false, "Overwrite an existing .agentsandbox/Dockerfile")
+
+	imageCmd.AddCommand(imageShowCmd)
+	imageCmd.AddCommand(imageEditCmd)
+	rootCmd.AddCommand(imageCmd)
+}
+
+// generatedDockerfile renders the Dockerfile for the current directory, ignoring any customization
+func generatedDockerfile(currentDir string) (string, error) {
+	agent, err := config.ValidateAgent(agentName)
+	if err != nil {
+		return "", err
+	}
+
+	settings, err := config.LoadSettings()
+	if err != nil {
+		settings = config.DefaultSettings()
+	}
+	spec := image.Spec{
+		Username:     os.Getenv("USER"),
+		Agent:        agent,
+		Languages:    language.DetectProjectLanguages(currentDir),
+		BaseImage:    settings.BaseImage,
+		Sudo:         settings.Sudo,
+		SudoCommands: settings.SudoCommands,
+	}
+	if spec.Username == "" {
+		spec.Username = "ubuntu"
+	}
+	if spec.Browser, err = image.WantsBrowser(settings.BrowserTesting, currentDir); err != nil {
+		return "", err
+	}
+
+	if imageTemplate != "" {
+		tmpl, err := templates.Get(imageTemplate)
+		if err != nil {
+			return "", err
+		}
+		spec.Template = tmpl
+		spec.Languages = tmpl.MergeLanguages(spec.Languages)
+	}
+
+	uid, gid, err := image.HostIDs()
+	if err != nil {
+		return "", err
+	}
+	uidNum, _ := strconv.Atoi(uid)
+	gidNum, _ := strconv.Atoi(gid)
+
+	return image.Generate(spec, uidNum, gidNum), nil
+}
+
+func runImageShow(cmd *cobra.Command, args []string) error {
+	currentDir, err := os.Getwd()
+	if err != nil {
+		return fmt.Errorf("failed to get current directory: %w", err)
+	}
+
+	custom, err := config.LoadProjectDockerfile(currentDir)
+	if err != nil {
+		return fmt.Errorf("failed to read custom Dockerfile: %w", err)
+	}
+	if custom != "" {
+		fmt.Fprintf(os.Stderr, "# Using custom Dockerfile: %s\n", config.ProjectDockerfilePath(currentDir))
+		fmt.Print(custom)
+		return nil
+	}
+
+	content, err := generatedDockerfile(currentDir)
+	if err != nil {
+		return err
+	}
+	fmt.Print(content)
+	return nil
+}
+
+func runImageEdit(cmd *cobra.Command, args []string) error {
+	currentDir, err := os.Getwd()
+	if err != nil {
+		return fmt.Errorf("failed to get current directory: %w", err)
+	}
+
+	path := config.ProjectDockerfilePath(currentDir)
+	if _, err := os.Stat(path); err == nil && !imageForce {
+		fmt.Printf("%s already exists (use --force to regenerate it)\n", path)
+		return openEditor(path)
+	}
+
+	content, err := generatedDockerfile(currentDir)
+	if err != nil {
+		return err
+	}
+
+	if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+		return fmt.Errorf("failed to create %s: %w", config.ProjectDirName, err)
+	}
+	if err := os.WriteFile(path, []byte(content), 0644); err != nil {
+		return fmt.Errorf("failed to write Dockerfile: %w", err)
+	}
+
+	fmt.Printf("Wrote %s\n", path)
+	fmt.Println("New containers for this directory will be built from it. Delete the file to go back to the generated Dockerfile.")
+	return openEditor(path)
+}
+
+// openEditor opens a file in $VISUAL or $EDITOR when one is configured
+func openEditor(path string) error {
+	editor := os.Getenv("VISUAL")
+	if editor == "" {
+		editor = os.Getenv("EDITOR")
+	}
+	if editor == "" {
+		return nil
+	}
+
+	cmd := exec.Command("sh", "-c", fmt.Sprintf("%s \"$1\"", editor), "sh", path)
+	cmd.Stdin = os.Stdin
+	cmd.Stdout = os.Stdout
+	cmd.Stderr = os.Stderr
+	return cmd.Run()
+}
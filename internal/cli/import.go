@@ -0,0 +1,125 @@
+package cli
+
+import (
+	"context"
+	"fmt"
+	"os"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/bundle"
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+)
+
+var (
+	importCmd = &cobra.Command{
+		Use:   "import <bundle.tar.gz>",
+		Short: "Add the sessions in an exported bundle to the local logs",
+		Args:  cobra.ExactArgs(1),
+		RunE:  runImport,
+	}
+
+	replayCmd = &cobra.Command{
+		Use:   "replay <bundle.tar.gz>",
+		Short: "Recreate a bundle's changes in a fresh worktree",
+		Long: `Check out the bundle's base commit into a new worktree of the current repository
+and apply the recorded changes. With --run the changes are not applied; instead a
+sandbox is started for the worktree and the recorded prompts are re-run headlessly.`,
+		Args: cobra.ExactArgs(1),
+		RunE: runReplay,
+	}
+
+	// Replay flags
+	replayRun bool
+)
+
+func init() {
+	replayCmd.Flags().BoolVar(&replayRun, "run", false, "Re-run the recorded prompts headlessly instead of applying the recorded diff")
+}
+
+func runImport(cmd *cobra.Command, args []string) error {
+	b, err := bundle.Open(args[0])
+	if err != nil {
+		return err
+	}
+	defer b.Close()
+
+	imported, err := bundle.Import(b)
+	if err != nil {
+		return err
+	}
+
+	fmt.Printf("Imported %d log file(s) for %s (%s)\n", imported, b.Manifest.Container, b.Manifest.Project)
+	fmt.Printf("View them with: agentsandbox logs list --container %s\n", b.Manifest.Container)
+	return nil
+}
+
+func runReplay(cmd *cobra.Command, args []string) error {
+	b, err := bundle.Open(args[0])
+	if err != nil {
+		return err
+	}
+	defer b.Close()
+
+	currentDir, err := os.Getwd()
+	if err != nil {
+		return fmt.Errorf("failed to get current directory: %w", err)
+	}
+
+	worktreePath, err := bundle.Replay(b, currentDir, replayRun)
+	if err != nil {
+		return err
+	}
+	fmt.Printf("Checked out %s into %s\n", b.Manifest.BaseCommit, worktreePath)
+
+	if !replayRun {
+		if b.Manifest.HasDiff {
+			fmt.Println("Applied the recorded changes")
+		}
+		return nil
+	}
+
+	prompts, err := b.Prompts()
+	if err != nil {
+		return err
+	}
+	if len(prompts) == 0 {
+		return fmt.Errorf("bundle does not contain any recorded prompts")
+	}
+
+	agent, err := config.ValidateAgent(b.Manifest.Agent)
+	if err != nil {
+		return err
+	}
+
+	settings, err := config.LoadSettings()
+	if err != nil {
+		fmt.Printf("Warning: failed to load settings: %v\n", err)
+		settings = config.DefaultSettings()
+	}
+	level := container.RecordedTrust(worktreePath)
+	skipPermissionFlag := container.PermissionFlag(settings, agent, level)
+
+	if err := container.CheckDockerAvailability(); err != nil {
+		return err
+	}
+
+	containerName := container.GenerateContainerName(worktreePath, agent)
+	fmt.Printf("Starting %s Agent Sandbox container: %s\n", agent.DisplayName(), containerName)
+	if err := container.CreateContainer(containerName, worktreePath, "", agent, skipPermissionFlag, false, false, nil, container.CreateOptions{Trust: level}); err != nil {
+		return fmt.Errorf("failed to create container: %w", err)
+	}
+
+	for i, prompt := range prompts {
+		fmt.Printf("\n=== Prompt %d/%d ===\n%s\n\n", i+1, len(prompts), prompt)
+		if err := container.RunHeadless(context.Background(), containerName, worktreePath, agent, skipPermissionFlag, prompt, os.Stdout, os.Stderr); err != nil {
+			return err
+		}
+	}
+
+	fmt.Printf("\nReplay finished in %s\n", worktreePath)
+	if b.Manifest.HasDiff {
+		fmt.Printf("Run 'agentsandbox replay %s' without --run to compare with the recorded changes\n", args[0])
+	}
+	return nil
+}
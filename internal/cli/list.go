@@ -4,12 +4,18 @@ import (
 	"bufio"
 	"fmt"
 	"os"
-	"strconv"
+	"sort"
 	"strings"
+	"time"
 
 	"github.com/spf13/cobra"
 	"github.com/thaodangspace/agentsandbox/internal/config"
 	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/git"
+	"github.com/thaodangspace/agentsandbox/internal/logs"
+	"github.com/thaodangspace/agentsandbox/internal/picker"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+	"github.com/thaodangspace/agentsandbox/internal/ui"
 )
 
 var (
@@ -23,18 +29,39 @@ var (
 	listAllCmd = &cobra.Command{
 		Use:     "list-all",
 		Aliases: []string{"ps"},
-		Short:   "List all running Agent Sandbox containers and optionally attach",
+		Short:   "List all running Agent Sandbox containers and optionally attach or restart",
+		Long: `List all running Agent Sandbox containers and optionally attach. With --all, stopped
+containers are included with their exit code and when they stopped, and picking one
+restarts it.`,
 		RunE:    runListAll,
 	}
+
+	// List flags
+	listSort     string
+	listFilters  []string
+	listNoAttach bool
+	listAll      bool
 )
 
+func init() {
+	for _, c := range []*cobra.Command{listCmd, listAllCmd} {
+		c.Flags().StringVar(&listSort, "sort", "project", "Sort by name, project, state, age or last-session")
+		c.Flags().StringSliceVar(&listFilters, "filter", nil, "Only show containers matching key=value (state, health, agent, project, branch, name); repeatable")
+		c.Flags().BoolVar(&listNoAttach, "no-attach", false, "Print the table instead of opening the picker")
+	}
+	listAllCmd.Flags().BoolVarP(&listAll, "all", "a", false, "Include stopped containers")
+}
+
+// previewEvents is how many recent log events the picker preview shows
+const previewEvents = 10
+
 func runList(cmd *cobra.Command, args []string) error {
 	currentDir, err := os.Getwd()
 	if err != nil {
 		return fmt.Errorf("failed to get current directory: %w", err)
 	}
 
-	containers, err := container.ListContainers(currentDir)
+	containers, err := container.ListProjectContainers(currentDir)
 	if err != nil {
 		return fmt.Errorf("failed to list containers: %w", err)
 	}
@@ -57,109 +84,218 @@ func runList(cmd *cobra.Command, args []string) error {
 		return nil
 	}
 
-	// Display table
-	fmt.Printf("\n%-5s %s\n", "No.", "Container")
-	fmt.Println(strings.Repeat("-", 80))
-	for i, name := range containers {
-		fmt.Printf("%-5d %s\n", i+1, name)
-	}
+	return showContainers(containers)
+}
 
-	// Prompt for selection
-	fmt.Print("Select a container to attach (number, or press Enter to cancel): ")
-	reader := bufio.NewReader(os.Stdin)
-	input, _ := reader.ReadString('\n')
-	input = strings.TrimSpace(input)
+func runListAll(cmd *cobra.Command, args []string) error {
+	containers, err := container.ListSandboxes(listAll)
+	if err != nil {
+		return fmt.Errorf("failed to list containers: %w", err)
+	}
 
-	if input == "" {
+	if len(containers) == 0 {
+		if listAll {
+			fmt.Println("No Agent Sandbox containers found.")
+		} else {
+			fmt.Println("No running Agent Sandbox containers found.")
+		}
 		return nil
 	}
 
-	num, err := strconv.Atoi(input)
-	if err != nil || num < 1 || num > len(containers) {
-		fmt.Println("Invalid selection")
-		return nil
+	return showContainers(containers)
+}
+
+// sandboxRow is a container with the host-side details shown by ls and ps
+type sandboxRow struct {
+	container.ContainerInfo
+	Branch      string
+	LastSession time.Time
+}
+
+// showContainers applies --filter and --sort, then prints the table or opens the picker
+func showContainers(containers []container.ContainerInfo) error {
+	rows := make([]sandboxRow, len(containers))
+	for i, info := range containers {
+		rows[i] = sandboxRow{ContainerInfo: info}
+		if info.Directory != "" {
+			rows[i].Branch = git.CurrentBranch(info.Directory)
+			rows[i].LastSession = state.LastSessionTime(info.Name, info.Directory)
+		}
 	}
 
-	// Prompt for attach mode
-	fmt.Print("Choose attach mode:\n  1) Attach with agent\n  2) Attach to shell only\nEnter choice: ")
-	modeInput, _ := reader.ReadString('\n')
-	modeInput = strings.TrimSpace(modeInput)
+	rows, err := filterRows(rows, listFilters)
+	if err != nil {
+		return err
+	}
+	if err := sortRows(rows, listSort); err != nil {
+		return err
+	}
+	if len(rows) == 0 {
+		fmt.Println("No containers match the filter.")
+		return nil
+	}
 
-	shellMode := false
-	switch modeInput {
-	case "1":
-		shellMode = false
-	case "2":
-		shellMode = true
-	default:
-		fmt.Println("Invalid choice")
+	if listNoAttach || !ui.IsTerminal(os.Stdin) || !ui.IsTerminal(os.Stdout) {
+		printContainerTable(rows)
 		return nil
 	}
+	return pickAndAttach(rows)
+}
 
-	selected := containers[num-1]
-	agent, ok := config.FromContainerName(selected)
-	if !ok {
-		agent = config.AgentClaude
+// filterRows keeps the rows matching every key=value filter, case-insensitively
+func filterRows(rows []sandboxRow, filters []string) ([]sandboxRow, error) {
+	fields := map[string]func(sandboxRow) string{
+		"state":   func(r sandboxRow) string { return r.State },
+		"health":  func(r sandboxRow) string { return r.Health },
+		"agent":   func(r sandboxRow) string { return r.Agent },
+		"project": func(r sandboxRow) string { return r.Project },
+		"branch":  func(r sandboxRow) string { return r.Branch },
+		"name":    func(r sandboxRow) string { return r.Name + " " + r.Alias },
 	}
 
-	settings, _ := config.LoadSettings()
-	skipPermissionFlag := settings.SkipPermissionFlags[string(agent)]
+	for _, filter := range filters {
+		key, value, ok := strings.Cut(filter, "=")
+		field, known := fields[strings.ToLower(key)]
+		if !ok || !known {
+			return nil, fmt.Errorf("invalid filter %q: use key=value with key one of state, health, agent, project, branch, name", filter)
+		}
 
-	return container.ResumeContainer(selected, agent, false, skipPermissionFlag, shellMode, true)
+		var kept []sandboxRow
+		for _, row := range rows {
+			if strings.Contains(strings.ToLower(field(row)), strings.ToLower(value)) {
+				kept = append(kept, row)
+			}
+		}
+		rows = kept
+	}
+	return rows, nil
 }
 
-func runListAll(cmd *cobra.Command, args []string) error {
-	containers, err := container.ListAllContainers()
-	if err != nil {
-		return fmt.Errorf("failed to list containers: %w", err)
+// sortRows orders rows by the --sort key; times sort most recent first
+func sortRows(rows []sandboxRow, key string) error {
+	var less func(a, b sandboxRow) bool
+	switch key {
+	case "name":
+		less = func(a, b sandboxRow) bool { return a.Name < b.Name }
+	case "project":
+		less = func(a, b sandboxRow) bool { return a.Project < b.Project }
+	case "state":
+		less = func(a, b sandboxRow) bool { return a.State > b.State }
+	case "age":
+		less = func(a, b sandboxRow) bool { return a.Created.After(b.Created) }
+	case "last-session":
+		less = func(a, b sandboxRow) bool { return a.LastSession.After(b.LastSession) }
+	default:
+		return fmt.Errorf("invalid sort key %q: use name, project, state, age or last-session", key)
 	}
+	sort.SliceStable(rows, func(i, j int) bool { return less(rows[i], rows[j]) })
+	return nil
+}
 
-	if len(containers) == 0 {
-		fmt.Println("No running Agent Sandbox containers found.")
-		return nil
+// printContainerTable prints rows with their state colour-coded
+func printContainerTable(rows []sandboxRow) {
+	fmt.Printf("%-40s %-20s %-10s %-10s %-18s %-8s %-20s %s\n", "CONTAINER", "PROJECT", "STATE", "HEALTH", "UPTIME / EXIT", "AGENT", "BRANCH", "LAST SESSION")
+	fmt.Println(strings.Repeat("-", 147))
+	for _, row := range rows {
+		name := row.Name
+		if row.Alias != "" {
+			name = fmt.Sprintf("%s (%s)", row.Name, row.Alias)
+		}
+		last := "-"
+		if !row.LastSession.IsZero() {
+			last = formatAge(row.LastSession) + " ago"
+		}
+		health := row.Health
+		if health == "" {
+			health = "-"
+		}
+		line := fmt.Sprintf("%-40s %-20s %-10s %-10s %-18s %-8s %-20s %s", name, row.Project, row.State, health, runStatus(row.ContainerInfo), row.Agent, row.Branch, last)
+		if row.Health == "unhealthy" {
+			fmt.Println(ui.Colorize("31", line))
+			continue
+		}
+		fmt.Println(colorByState(row.State, line))
 	}
+}
 
-	// Display table
-	fmt.Printf("\n%-5s %-20s %-40s %s\n", "No.", "Project", "Container", "Directory")
-	fmt.Println(strings.Repeat("-", 120))
-	for i, info := range containers {
-		fmt.Printf("%-5d %-20s %-40s %s\n", i+1, info.Project, info.Name, info.Directory)
+// runStatus extracts how long a running container has been up from docker's status,
+// or how a stopped one exited and how long ago
+func runStatus(info container.ContainerInfo) string {
+	if info.State != "running" && info.State != "paused" {
+		if info.Finished.IsZero() {
+			return "-"
+		}
+		return fmt.Sprintf("exit %d, %s ago", info.ExitCode, formatAge(info.Finished))
+	}
+	if !strings.HasPrefix(info.Status, "Up ") {
+		return "-"
 	}
+	up, _, _ := strings.Cut(strings.TrimPrefix(info.Status, "Up "), " (")
+	return up
+}
 
-	// Prompt for selection
-	fmt.Print("Select a container to attach (number, or press Enter to cancel): ")
-	reader := bufio.NewReader(os.Stdin)
-	input, _ := reader.ReadString('\n')
-	input = strings.TrimSpace(input)
+// colorByState colours a table line green for running, blue for paused and grey for stopped containers
+func colorByState(containerState, line string) string {
+	switch containerState {
+	case "running":
+		return ui.Colorize("32", line)
+	case "paused":
+		return ui.Colorize("34", line)
+	case "exited", "created", "dead":
+		return ui.Colorize("90", line)
+	default:
+		return ui.Colorize("33", line)
+	}
+}
 
-	if input == "" {
-		return nil
+// pickAndAttach lets the user choose a container with the fuzzy picker and attaches to it
+func pickAndAttach(rows []sandboxRow) error {
+	items := make([]picker.Item, len(rows))
+	for i := range rows {
+		row := rows[i]
+		items[i] = picker.Item{
+			Text:    containerRow(row),
+			Preview: func() []string { return containerPreview(row.ContainerInfo) },
+		}
 	}
 
-	num, err := strconv.Atoi(input)
-	if err != nil || num < 1 || num > len(containers) {
-		fmt.Println("Invalid selection")
+	index, err := picker.Pick(containerHeader(), items)
+	if err != nil {
+		fmt.Println(err)
 		return nil
 	}
+	if index < 0 {
+		return nil
+	}
+	selected := rows[index]
 
-	// Prompt for attach mode
-	fmt.Print("Choose attach mode:\n  1) Attach with agent\n  2) Attach to shell only\nEnter choice: ")
+	// Prompt for attach mode; stopped and paused containers can also just be resumed
+	stopped := selected.State != "running"
+	prompt := "Choose attach mode:\n  1) Attach with agent\n  2) Attach to shell only\n"
+	if selected.State == "paused" {
+		prompt += "  3) Unpause without attaching\n"
+	} else if stopped {
+		prompt += "  3) Restart without attaching\n"
+	}
+	fmt.Print(prompt + "Enter choice: ")
+	reader := bufio.NewReader(os.Stdin)
 	modeInput, _ := reader.ReadString('\n')
 	modeInput = strings.TrimSpace(modeInput)
 
 	shellMode := false
-	switch modeInput {
-	case "1":
+	attach := true
+	switch {
+	case modeInput == "1":
 		shellMode = false
-	case "2":
+	case modeInput == "2":
 		shellMode = true
+	case modeInput == "3" && stopped:
+		attach = false
 	default:
 		fmt.Println("Invalid choice")
 		return nil
 	}
 
-	selected := containers[num-1]
-
 	// Change to the container's directory if available
 	if selected.Directory != "" {
 		if err := os.Chdir(selected.Directory); err != nil {
@@ -171,10 +307,73 @@ func runListAll(cmd *cobra.Command, args []string) error {
 	if !ok {
 		agent = config.AgentClaude
 	}
+	if selected.Agent != "" {
+		agent = config.Agent(selected.Agent)
+	}
 
 	settings, _ := config.LoadSettings()
-	skipPermissionFlag := settings.SkipPermissionFlags[string(agent)]
+	skipPermissionFlag := container.PermissionFlag(settings, agent, container.RecordedTrust(selected.Directory))
+
+	return container.ResumeContainer(selected.Name, agent, false, skipPermissionFlag, shellMode, attach, sessionOptions(settings))
+}
+
+func containerHeader() string {
+	return fmt.Sprintf("%-20s %-20s %-8s %-8s %-6s %s", "PROJECT", "BRANCH", "AGENT", "STATE", "AGE", "CONTAINER")
+}
 
-	return container.ResumeContainer(selected.Name, agent, false, skipPermissionFlag, shellMode, true)
+// containerRow formats a container as a picker row
+func containerRow(row sandboxRow) string {
+	name := row.Name
+	if row.Alias != "" {
+		name = fmt.Sprintf("%s (%s)", row.Name, row.Alias)
+	}
+	return fmt.Sprintf("%-20s %-20s %-8s %-8s %-6s %s", row.Project, row.Branch, row.Agent, row.State, formatAge(row.Created), name)
 }
 
+// containerPreview shows the container's status and the tail of its latest session log
+func containerPreview(info container.ContainerInfo) []string {
+	lines := []string{fmt.Sprintf("%s  %s", info.Status, info.Directory)}
+	if !info.Finished.IsZero() && info.State != "running" {
+		lines = append(lines, fmt.Sprintf("Exited with code %d at %s", info.ExitCode, info.Finished.Local().Format("2006-01-02 15:04:05")))
+	}
+	if info.Directory == "" {
+		return lines
+	}
+
+	logFiles, err := state.ListSessionLogs(info.Name, info.Directory)
+	if err != nil || len(logFiles) == 0 {
+		return append(lines, "No session logs yet")
+	}
+	sort.Strings(logFiles)
+
+	events, err := logs.ParseRawLog(logFiles[len(logFiles)-1])
+	if err != nil {
+		return append(lines, fmt.Sprintf("Failed to read session log: %v", err))
+	}
+	if len(events) > previewEvents {
+		events = events[len(events)-previewEvents:]
+	}
+	for _, event := range events {
+		message, _, _ := strings.Cut(event.Message, "\n")
+		lines = append(lines, fmt.Sprintf("%-7s %s", event.Level, message))
+	}
+	return lines
+}
+
+// formatAge renders how long ago t was in a compact form such as 5m, 3h or 2d
+func formatAge(t time.Time) string {
+	if t.IsZero() {
+		return ""
+	}
+	d := time.Since(t)
+	switch {
+	case d < time.Minute:
+		return "now"
+	case d < time.Hour:
+		return fmt.Sprintf("%dm", int(d.Minutes()))
+	case d < 48*time.Hour:
+		return fmt.Sprintf("%dh", int(d.Hours()))
+	default:
+		return fmt.Sprintf("%dd", int(d.Hours()/24))
+	}
+}
@@ -1,14 +1,24 @@
 package cli
 
 import (
+	"context"
+	"encoding/base64"
 	"fmt"
 	"os"
 	"os/exec"
+	"os/signal"
 	"path/filepath"
 	"runtime"
+	"sort"
+	"strings"
+	"syscall"
+	"time"
 
 	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/bundle"
+	"github.com/thaodangspace/agentsandbox/internal/container"
 	"github.com/thaodangspace/agentsandbox/internal/logs"
+	"github.com/thaodangspace/agentsandbox/internal/picker"
 	"github.com/thaodangspace/agentsandbox/internal/state"
 )
 
@@ -25,10 +35,40 @@ var (
 	}
 
 	logsViewCmd = &cobra.Command{
-		Use:   "view <log-file>",
+		Use:   "view [log-file|container|session]",
 		Short: "View a session log as HTML",
-		Args:  cobra.ExactArgs(1),
-		RunE:  runLogsView,
+		Long: `Render a session log as HTML. Give the path of a log file, or a container, session name
+or session ID to view its latest session log. --pick chooses one of its sessions instead.
+Without an argument, --latest and --pick look at the sessions of the current directory.
+With --serve the page is served on localhost and reloads as the session continues.`,
+		Args: cobra.MaximumNArgs(1),
+		RunE: runLogsView,
+	}
+
+	logsAuditCmd = &cobra.Command{
+		Use:   "audit [session]",
+		Short: "List audited sessions or show the commands run in one",
+		Args:  cobra.MaximumNArgs(1),
+		RunE:  runLogsAudit,
+	}
+
+	logsNetworkCmd = &cobra.Command{
+		Use:   "network [session]",
+		Short: "List sessions with network logs or show the hosts one contacted",
+		Long: `List sessions recorded with network_log enabled, or show the hosts a session
+connected to through the logging proxy with connection counts, bytes and time spent.`,
+		Args: cobra.MaximumNArgs(1),
+		RunE: runLogsNetwork,
+	}
+
+	logsKeyCmd = &cobra.Command{
+		Use:   "key",
+		Short: "Print the log encryption key so it can be backed up",
+		Long: `Print the key that encrypted logs are sealed with, creating it if needed. Keep a copy
+somewhere safe: without it encrypted logs cannot be read. Set AGENTSANDBOX_LOG_KEY to the
+printed value to read the logs on a machine without access to the keyring.`,
+		Args: cobra.NoArgs,
+		RunE: runLogsKey,
 	}
 
 	logsCleanCmd = &cobra.Command{
@@ -41,19 +81,43 @@ var (
 	containerFilter string
 	outputPath      string
 	openBrowser     bool
+	viewLatest      bool
+	viewPick        bool
+	serveLog        bool
+	servePort       int
 	daysOld         int
 )
 
 func init() {
-	logsListCmd.Flags().StringVar(&containerFilter, "container", "", "Filter by container name")
+	logsListCmd.Flags().StringVar(&containerFilter, "container", "", "Filter by container or session name")
 	logsViewCmd.Flags().StringVar(&outputPath, "output", "", "Output HTML file path (default: same as log with .html extension)")
 	logsViewCmd.Flags().BoolVar(&openBrowser, "open", false, "Open in browser after generating")
+	logsViewCmd.Flags().BoolVar(&viewLatest, "latest", false, "View the most recent session log")
+	logsViewCmd.Flags().BoolVar(&viewPick, "pick", false, "Choose the session log to view")
+	logsViewCmd.MarkFlagsMutuallyExclusive("latest", "pick")
+	logsViewCmd.Flags().BoolVar(&serveLog, "serve", false, "Serve the log on a local web server that reloads as the session continues")
+	logsViewCmd.Flags().IntVar(&servePort, "port", 0, "Port for --serve (default: any free port)")
+	logsViewCmd.MarkFlagsMutuallyExclusive("serve", "output")
 	logsCleanCmd.Flags().IntVar(&daysOld, "days", 30, "Keep logs newer than this many days")
-	logsCleanCmd.Flags().StringVar(&containerFilter, "container", "", "Filter by container name")
+	logsCleanCmd.Flags().StringVar(&containerFilter, "container", "", "Filter by container or session name")
+	logsAuditCmd.Flags().StringVar(&containerFilter, "container", "", "Filter by container or session name")
+	logsNetworkCmd.Flags().StringVar(&containerFilter, "container", "", "Filter by container or session name")
 
 	logsCmd.AddCommand(logsListCmd)
 	logsCmd.AddCommand(logsViewCmd)
 	logsCmd.AddCommand(logsCleanCmd)
+	logsCmd.AddCommand(logsAuditCmd)
+	logsCmd.AddCommand(logsNetworkCmd)
+	logsCmd.AddCommand(logsKeyCmd)
+}
+
+func runLogsKey(cmd *cobra.Command, args []string) error {
+	key, err := logs.LogKey(true)
+	if err != nil {
+		return err
+	}
+	fmt.Println(base64.StdEncoding.EncodeToString(key))
+	return nil
 }
 
 func runLogsList(cmd *cobra.Command, args []string) error {
@@ -64,7 +128,7 @@ func runLogsList(cmd *cobra.Command, args []string) error {
 
 	var containers []string
 	if containerFilter != "" {
-		containers = []string{containerFilter}
+		containers = []string{resolveContainerFilter()}
 	} else {
 		containers, err = state.ListContainersWithLogs(currentDir)
 		if err != nil {
@@ -98,7 +162,16 @@ func runLogsList(cmd *cobra.Command, args []string) error {
 }
 
 func runLogsView(cmd *cobra.Command, args []string) error {
-	logFile := args[0]
+	logFile, err := resolveViewLog(args)
+	if err != nil {
+		return err
+	}
+	if logFile == "" {
+		return nil
+	}
+	if serveLog {
+		return serveLogFile(logFile)
+	}
 
 	// Parse log file
 	events, err := logs.ParseRawLog(logFile)
@@ -112,9 +185,9 @@ func runLogsView(cmd *cobra.Command, args []string) error {
 		output = logFile[:len(logFile)-len(filepath.Ext(logFile))] + ".html"
 	}
 
-	// Generate HTML
+	// Generate HTML, without any credentials the log may contain
 	title := filepath.Base(logFile)
-	if err := logs.WriteHTML(events, output, title); err != nil {
+	if err := logs.WriteHTML(logs.RedactEvents(events, logs.LoadRedactor()), output, title); err != nil {
 		return fmt.Errorf("failed to generate HTML: %w", err)
 	}
 
@@ -132,6 +205,254 @@ func runLogsView(cmd *cobra.Command, args []string) error {
 	return nil
 }
 
+// serveLogFile serves the log's HTML view on localhost until interrupted
+func serveLogFile(logFile string) error {
+	ctx, stop := signal.NotifyContext(context.Background(), os.Interrupt, syscall.SIGTERM)
+	defer stop()
+
+	addr := fmt.Sprintf("127.0.0.1:%d", servePort)
+	return logs.Serve(ctx, logFile, addr, logs.LoadRedactor(), func(url string) {
+		fmt.Printf("Serving %s at %s (Ctrl+C to stop)\n", filepath.Base(logFile), url)
+		if openBrowser {
+			if err := openInBrowser(url); err != nil {
+				fmt.Printf("Failed to open browser: %v\n", err)
+			}
+		}
+	})
+}
+
+// resolveViewLog returns the session log for logs view: a log file, or the latest or a
+// picked session log of a container, session or the current directory. It returns an
+// empty path when the pick is cancelled.
+func resolveViewLog(args []string) (string, error) {
+	if len(args) == 1 {
+		if info, err := os.Stat(args[0]); err == nil && !info.IsDir() {
+			return args[0], nil
+		}
+	}
+
+	var logFiles []string
+	if len(args) == 1 {
+		target, err := bundle.ResolveTarget(args[0])
+		if err != nil {
+			return "", fmt.Errorf("%s is not a log file: %w", args[0], err)
+		}
+		if target.Session != "" {
+			logFile := filepath.Join(target.LogsDir, "session-"+target.Session+".jsonl")
+			if _, err := os.Stat(logFile); err != nil {
+				return "", fmt.Errorf("session %s has no session log", target.Session)
+			}
+			return logFile, nil
+		}
+		logFiles, _ = filepath.Glob(filepath.Join(target.LogsDir, "session-*.jsonl"))
+	} else {
+		if !viewLatest && !viewPick {
+			return "", fmt.Errorf("specify a log file, container or session, or use --latest or --pick")
+		}
+		currentDir, err := os.Getwd()
+		if err != nil {
+			return "", fmt.Errorf("failed to get current directory: %w", err)
+		}
+		containers, err := state.ListContainersWithLogs(currentDir)
+		if err != nil {
+			return "", fmt.Errorf("failed to list containers: %w", err)
+		}
+		for _, containerName := range containers {
+			files, err := state.ListSessionLogs(containerName, currentDir)
+			if err != nil {
+				continue
+			}
+			logFiles = append(logFiles, files...)
+		}
+	}
+	if len(logFiles) == 0 {
+		return "", fmt.Errorf("no session logs found")
+	}
+
+	// Session log names start with their timestamp, newest last
+	sort.Slice(logFiles, func(i, j int) bool {
+		return filepath.Base(logFiles[i]) < filepath.Base(logFiles[j])
+	})
+	if !viewPick {
+		return logFiles[len(logFiles)-1], nil
+	}
+
+	items := make([]picker.Item, len(logFiles))
+	for i := range logFiles {
+		logFile := logFiles[len(logFiles)-1-i]
+		items[i] = picker.Item{
+			Text:    fmt.Sprintf("%-30s %s", strings.TrimSuffix(filepath.Base(logFile), ".jsonl"), filepath.Base(filepath.Dir(logFile))),
+			Preview: func() []string { return sessionPreview(logFile) },
+		}
+	}
+	index, err := picker.Pick(fmt.Sprintf("%-30s %s", "SESSION", "CONTAINER"), items)
+	if err != nil {
+		return "", err
+	}
+	if index < 0 {
+		return "", nil
+	}
+	return logFiles[len(logFiles)-1-index], nil
+}
+
+// sessionPreview shows the first events of a session log
+func sessionPreview(logFile string) []string {
+	events, err := logs.ParseRawLog(logFile)
+	if err != nil {
+		return []string{fmt.Sprintf("Failed to read session log: %v", err)}
+	}
+	if len(events) > previewEvents {
+		events = events[:previewEvents]
+	}
+	var lines []string
+	for _, event := range events {
+		message, _, _ := strings.Cut(event.Message, "\n")
+		lines = append(lines, fmt.Sprintf("%-7s %s", event.Level, message))
+	}
+	return lines
+}
+
+// logContainers returns the --container filter, or the containers with logs for the
+// current directory
+func logContainers(currentDir string) ([]string, error) {
+	if containerFilter != "" {
+		return []string{resolveContainerFilter()}, nil
+	}
+	containers, err := state.ListContainersWithLogs(currentDir)
+	if err != nil {
+		return nil, fmt.Errorf("failed to list containers: %w", err)
+	}
+	return containers, nil
+}
+
+func runLogsAudit(cmd *cobra.Command, args []string) error {
+	currentDir, err := os.Getwd()
+	if err != nil {
+		return fmt.Errorf("failed to get current directory: %w", err)
+	}
+
+	containers, err := logContainers(currentDir)
+	if err != nil {
+		return err
+	}
+
+	var auditFiles []string
+	for _, containerName := range containers {
+		files, err := state.ListAuditLogs(containerName, currentDir)
+		if err != nil {
+			fmt.Printf("Warning: failed to list audit logs for %s: %v\n", containerName, err)
+			continue
+		}
+		auditFiles = append(auditFiles, files...)
+	}
+
+	if len(args) == 0 {
+		if len(auditFiles) == 0 {
+			fmt.Println("No audit logs found.")
+			return nil
+		}
+		for _, file := range auditFiles {
+			entries, err := logs.ParseAuditLog(file)
+			if err != nil {
+				continue
+			}
+			fmt.Printf("%s  %s  %d commands\n", logs.AuditSessionID(file), filepath.Base(filepath.Dir(file)), logs.CommandCount(entries))
+		}
+		return nil
+	}
+
+	auditFile := ""
+	if _, err := os.Stat(args[0]); err == nil {
+		auditFile = args[0]
+	} else {
+		for _, file := range auditFiles {
+			if logs.AuditSessionID(file) == args[0] {
+				auditFile = file
+				break
+			}
+		}
+	}
+	if auditFile == "" {
+		return fmt.Errorf("no audit log found for session %s", args[0])
+	}
+
+	entries, err := logs.ParseAuditLog(auditFile)
+	if err != nil {
+		return fmt.Errorf("failed to parse audit log: %w", err)
+	}
+	for _, entry := range entries {
+		if entry.Malformed != "" {
+			fmt.Printf("Warning: malformed audit log line\n    %s\n", container.Printable(entry.Malformed, "    "))
+			continue
+		}
+		fmt.Printf("%s  %s\n    $ %s\n", container.Printable(entry.Timestamp, ""), container.Printable(entry.Cwd, ""), container.Printable(entry.Command, "      "))
+	}
+	return nil
+}
+
+func runLogsNetwork(cmd *cobra.Command, args []string) error {
+	currentDir, err := os.Getwd()
+	if err != nil {
+		return fmt.Errorf("failed to get current directory: %w", err)
+	}
+
+	containers, err := logContainers(currentDir)
+	if err != nil {
+		return err
+	}
+
+	var networkFiles []string
+	for _, containerName := range containers {
+		files, err := state.ListNetworkLogs(containerName, currentDir)
+		if err != nil {
+			fmt.Printf("Warning: failed to list network logs for %s: %v\n", containerName, err)
+			continue
+		}
+		networkFiles = append(networkFiles, files...)
+	}
+
+	if len(args) == 0 {
+		if len(networkFiles) == 0 {
+			fmt.Println("No network logs found. Set \"network_log\": true to record them.")
+			return nil
+		}
+		for _, file := range networkFiles {
+			entries, err := logs.ParseNetworkLog(file)
+			if err != nil {
+				continue
+			}
+			fmt.Printf("%s  %s  %d hosts\n", logs.NetworkSessionID(file), filepath.Base(filepath.Dir(file)), len(logs.SummarizeNetwork(entries)))
+		}
+		return nil
+	}
+
+	networkFile := ""
+	if _, err := os.Stat(args[0]); err == nil {
+		networkFile = args[0]
+	} else {
+		for _, file := range networkFiles {
+			if logs.NetworkSessionID(file) == args[0] {
+				networkFile = file
+				break
+			}
+		}
+	}
+	if networkFile == "" {
+		return fmt.Errorf("no network log found for session %s", args[0])
+	}
+
+	entries, err := logs.ParseNetworkLog(networkFile)
+	if err != nil {
+		return fmt.Errorf("failed to parse network log: %w", err)
+	}
+	fmt.Printf("%-40s %6s %6s %10s %10s %8s\n", "HOST", "CONNS", "FAILED", "SENT", "RECEIVED", "TIME")
+	for _, host := range logs.SummarizeNetwork(entries) {
+		fmt.Printf("%-40s %6d %6d %10s %10s %8s\n", host.Host, host.Connections, host.Errors,
+			formatBytes(host.BytesSent), formatBytes(host.BytesReceived), host.Duration.Round(time.Second))
+	}
+	return nil
+}
+
 func runLogsClean(cmd *cobra.Command, args []string) error {
 	currentDir, err := os.Getwd()
 	if err != nil {
@@ -140,7 +461,7 @@ func runLogsClean(cmd *cobra.Command, args []string) error {
 
 	var containers []string
 	if containerFilter != "" {
-		containers = []string{containerFilter}
+		containers = []string{resolveContainerFilter()}
 	} else {
 		containers, err = state.ListContainersWithLogs(currentDir)
 		if err != nil {
@@ -193,3 +514,10 @@ func openInBrowser(filepath string) error {
 	return cmd.Start()
 }
 
+// resolveContainerFilter maps a session name passed to --container to its container
+func resolveContainerFilter() string {
+	if containerName := state.ResolveAlias(containerFilter); containerName != "" {
+		return containerName
+	}
+	return containerFilter
+}
@@ -0,0 +1,95 @@
+package cli
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+)
+
+var (
+	networkCmd = &cobra.Command{
+		Use:   "network",
+		Short: "Manage networks shared between sandboxes",
+	}
+
+	networkCreateCmd = &cobra.Command{
+		Use:   "create <name>",
+		Short: "Create a network that sandboxes join with --network-join",
+		Args:  cobra.ExactArgs(1),
+		RunE:  runNetworkCreate,
+	}
+
+	networkListCmd = &cobra.Command{
+		Use:     "list",
+		Aliases: []string{"ls"},
+		Short:   "List shared networks and their members",
+		RunE:    runNetworkList,
+	}
+
+	networkRemoveCmd = &cobra.Command{
+		Use:   "rm <name>",
+		Short: "Remove a shared network that has no members",
+		Args:  cobra.ExactArgs(1),
+		RunE:  runNetworkRemove,
+	}
+)
+
+func init() {
+	networkCmd.AddCommand(networkCreateCmd)
+	networkCmd.AddCommand(networkListCmd)
+	networkCmd.AddCommand(networkRemoveCmd)
+	rootCmd.AddCommand(networkCmd)
+}
+
+func runNetworkCreate(cmd *cobra.Command, args []string) error {
+	if err := container.CheckDockerAvailability(); err != nil {
+		return err
+	}
+	if err := container.CreateNetwork(args[0]); err != nil {
+		return err
+	}
+	fmt.Printf("Created network %s\n", args[0])
+	fmt.Printf("Start sandboxes with --network-join %s; they can reach each other's dev servers by container name\n", args[0])
+	return nil
+}
+
+func runNetworkList(cmd *cobra.Command, args []string) error {
+	if err := container.CheckDockerAvailability(); err != nil {
+		return err
+	}
+	networks, err := container.ListNetworks()
+	if err != nil {
+		return err
+	}
+	if len(networks) == 0 {
+		fmt.Println("No shared networks found.")
+		return nil
+	}
+
+	fmt.Printf("%-20s %s\n", "NETWORK", "MEMBERS")
+	fmt.Println(strings.Repeat("-", 80))
+	for _, network := range networks {
+		members := strings.Join(network.Members, ", ")
+		if members == "" {
+			members = "-"
+		}
+		fmt.Printf("%-20s %s\n", network.Name, members)
+	}
+	return nil
+}
+
+func runNetworkRemove(cmd *cobra.Command, args []string) error {
+	if err := container.CheckDockerAvailability(); err != nil {
+		return err
+	}
+	if !container.NetworkExists(args[0]) {
+		return fmt.Errorf("network %s does not exist", args[0])
+	}
+	if err := container.RemoveNetwork(args[0]); err != nil {
+		return err
+	}
+	fmt.Printf("Removed network %s\n", args[0])
+	return nil
+}
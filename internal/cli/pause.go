@@ -0,0 +1,83 @@
+package cli
+
+import (
+	"fmt"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/failure"
+)
+
+var (
+	pauseCmd = &cobra.Command{
+		Use:   "pause <container|name>...",
+		Short: "Freeze containers to free their CPU without losing state",
+		Args:  cobra.MinimumNArgs(1),
+		RunE:  runPause,
+	}
+
+	unpauseCmd = &cobra.Command{
+		Use:   "unpause <container|name>...",
+		Short: "Resume paused containers where they left off",
+		Args:  cobra.MinimumNArgs(1),
+		RunE:  runUnpause,
+	}
+)
+
+func init() {
+	rootCmd.AddCommand(pauseCmd)
+	rootCmd.AddCommand(unpauseCmd)
+}
+
+func runPause(cmd *cobra.Command, args []string) error {
+	return forEachContainer(args, func(name string, info container.ContainerState) error {
+		if !info.Running {
+			return fmt.Errorf("container '%s' is not running", name)
+		}
+		if info.Paused {
+			fmt.Printf("Container %s is already paused\n", name)
+			return nil
+		}
+		if err := container.PauseContainer(name); err != nil {
+			return err
+		}
+		fmt.Printf("Paused %s; run 'agentsandbox unpause %s' to resume it\n", name, name)
+		return nil
+	})
+}
+
+func runUnpause(cmd *cobra.Command, args []string) error {
+	return forEachContainer(args, func(name string, info container.ContainerState) error {
+		if !info.Paused {
+			fmt.Printf("Container %s is not paused\n", name)
+			return nil
+		}
+		if err := container.UnpauseContainer(name); err != nil {
+			return err
+		}
+		fmt.Printf("Unpaused %s\n", name)
+		return nil
+	})
+}
+
+// forEachContainer resolves each argument to an existing container and calls fn with its state
+func forEachContainer(args []string, fn func(name string, info container.ContainerState) error) error {
+	if err := container.CheckDockerAvailability(); err != nil {
+		return err
+	}
+
+	for _, arg := range args {
+		containerName := container.ResolveName(arg)
+		info, err := container.InspectContainer(containerName)
+		if err != nil {
+			return err
+		}
+		if !info.Exists {
+			return failure.Errorf(failure.ContainerNotFound, "container '%s' does not exist", arg)
+		}
+		if err := fn(containerName, info); err != nil {
+			return err
+		}
+	}
+	return nil
+}
@@ -0,0 +1,153 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/forge"
+	"github.com/thaodangspace/agentsandbox/internal/git"
+	"github.com/thaodangspace/agentsandbox/internal/logs"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+var (
+	prCmd = &cobra.Command{
+		Use:   "pr",
+		Short: "Commit and push a sandbox's changes and open a pull request",
+		Long: `Commit the changes in a sandbox's worktree on its branch, push the branch with your
+host git credentials and open a GitHub pull request or GitLab merge request that
+includes a summary of the agent session.
+
+Requires GITHUB_TOKEN (or GH_TOKEN) for GitHub and GITLAB_TOKEN for GitLab.`,
+		RunE: runPR,
+	}
+
+	// PR flags
+	prContainer string
+	prTitle     string
+	prBody      string
+	prBase      string
+	prRemote    string
+	prDraft     bool
+)
+
+func init() {
+	prCmd.Flags().StringVar(&prContainer, "container", "", "Container whose changes to submit (default: the one for the current directory)")
+	prCmd.Flags().StringVar(&prTitle, "title", "", "Pull request title, also used as the commit message")
+	prCmd.Flags().StringVar(&prBody, "body", "", "Text placed above the session summary in the pull request")
+	prCmd.Flags().StringVar(&prBase, "base", "", "Branch to merge into (default: the remote's default branch)")
+	prCmd.Flags().StringVar(&prRemote, "remote", "origin", "Git remote to push to")
+	prCmd.Flags().BoolVar(&prDraft, "draft", false, "Open the pull request as a draft")
+	prCmd.MarkFlagRequired("title")
+}
+
+func runPR(cmd *cobra.Command, args []string) error {
+	currentDir, err := os.Getwd()
+	if err != nil {
+		return fmt.Errorf("failed to get current directory: %w", err)
+	}
+
+	containerName, err := container.ResolveContainer(prContainer, currentDir)
+	if err != nil {
+		return err
+	}
+
+	projectDir, err := container.GetContainerDirectory(containerName)
+	if err != nil || projectDir == "" {
+		return fmt.Errorf("failed to find the project directory of %s", containerName)
+	}
+
+	settings, err := config.LoadSettings()
+	if err != nil {
+		settings = config.DefaultSettings()
+	}
+
+	branch := git.CurrentBranch(projectDir)
+	if branch == "" {
+		return fmt.Errorf("%s is not on a branch", projectDir)
+	}
+	if _, ok := git.MatchBranch(branch, settings.ProtectedBranches); ok {
+		return fmt.Errorf("%s is on protected branch %s; start the agent with --worktree <branch> to submit its changes", containerName, branch)
+	}
+
+	remoteURL, err := git.RemoteURL(projectDir, prRemote)
+	if err != nil {
+		return err
+	}
+	remote, err := git.ParseRemoteURL(remoteURL)
+	if err != nil {
+		return err
+	}
+
+	base := prBase
+	if base == "" {
+		base = git.DefaultBranch(projectDir, prRemote)
+	}
+
+	committed, err := git.CommitAll(projectDir, prTitle)
+	if err != nil {
+		return err
+	}
+	if committed {
+		fmt.Printf("Committed sandbox changes on %s\n", branch)
+	}
+
+	if err := git.Push(projectDir, prRemote, branch); err != nil {
+		return err
+	}
+
+	url, err := forge.Open(remote, forge.PullRequest{
+		Title: prTitle,
+		Body:  prDescription(containerName, projectDir, prRemote+"/"+base),
+		Head:  branch,
+		Base:  base,
+		Draft: prDraft,
+	})
+	if err != nil {
+		return err
+	}
+
+	fmt.Printf("Opened %s\n", url)
+	return nil
+}
+
+// prDescription combines the --body text with a summary of the latest agent session
+func prDescription(containerName, projectDir, base string) string {
+	var b strings.Builder
+	if prBody != "" {
+		b.WriteString(prBody)
+		b.WriteString("\n\n")
+	}
+
+	if link, err := state.LoadIssueLink(containerName, projectDir); err == nil && link != nil {
+		fmt.Fprintf(&b, "Closes %s\n\n", link.URL)
+	}
+
+	b.WriteString("### Agent session\n\n")
+	if agent, ok := config.FromContainerName(containerName); ok {
+		fmt.Fprintf(&b, "- Agent: %s\n", agent.DisplayName())
+	}
+	fmt.Fprintf(&b, "- Sandbox: `%s`\n", containerName)
+
+	if auditLogs, err := state.ListAuditLogs(containerName, projectDir); err == nil && len(auditLogs) > 0 {
+		sort.Strings(auditLogs)
+		latest := auditLogs[len(auditLogs)-1]
+		fmt.Fprintf(&b, "- Session: `%s`\n", logs.AuditSessionID(filepath.Base(latest)))
+		if entries, err := logs.ParseAuditLog(latest); err == nil {
+			fmt.Fprintf(&b, "- Commands run: %d\n", logs.CommandCount(entries))
+		}
+	}
+
+	if stat, err := git.DiffStat(projectDir, base); err == nil && stat != "" {
+		fmt.Fprintf(&b, "\n```\n%s\n```\n", stat)
+	}
+
+	b.WriteString("\n_Created with agentsandbox._\n")
+	return b.String()
+}
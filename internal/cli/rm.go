@@ -0,0 +1,41 @@
+package cli
+
+import (
+	"fmt"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/failure"
+)
+
+var rmCmd = &cobra.Command{
+	Use:   "rm <container|name>...",
+	Short: "Remove containers by container or session name",
+	Args:  cobra.MinimumNArgs(1),
+	RunE:  runRm,
+}
+
+func init() {
+	rootCmd.AddCommand(rmCmd)
+}
+
+func runRm(cmd *cobra.Command, args []string) error {
+	if err := container.CheckDockerAvailability(); err != nil {
+		return err
+	}
+
+	for _, arg := range args {
+		containerName := container.ResolveName(arg)
+		exists, err := container.ContainerExists(containerName)
+		if err != nil {
+			return fmt.Errorf("failed to check if container exists: %w", err)
+		}
+		if !exists {
+			return failure.Errorf(failure.ContainerNotFound, "container '%s' does not exist", arg)
+		}
+		if err := container.RemoveContainer(containerName); err != nil {
+			return err
+		}
+	}
+	return nil
+}
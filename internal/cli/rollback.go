@@ -0,0 +1,86 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/git"
+)
+
+var (
+	rollbackCmd = &cobra.Command{
+		Use:   "rollback [snapshot]",
+		Short: "Restore the workspace to a snapshot taken before a session",
+		Long: `Restore the current repository's working tree to a snapshot taken before an agent
+session, by default the most recent one. Snapshots are taken when workspace_snapshots
+is enabled in settings. The working tree is snapshotted again first, so a rollback can
+itself be undone; the index and HEAD are not changed.`,
+		Args: cobra.MaximumNArgs(1),
+		RunE: runRollback,
+	}
+
+	// Rollback flags
+	rollbackList bool
+)
+
+func init() {
+	rollbackCmd.Flags().BoolVar(&rollbackList, "list", false, "List the repository's snapshots instead of restoring")
+	rootCmd.AddCommand(rollbackCmd)
+}
+
+func runRollback(cmd *cobra.Command, args []string) error {
+	currentDir, err := os.Getwd()
+	if err != nil {
+		return fmt.Errorf("failed to get current directory: %w", err)
+	}
+	root := git.RepoRoot(currentDir)
+	if root == "" {
+		return fmt.Errorf("%s is not in a git repository", currentDir)
+	}
+
+	snapshots, err := git.ListSnapshots(root)
+	if err != nil {
+		return err
+	}
+	if rollbackList {
+		if len(snapshots) == 0 {
+			fmt.Println("No workspace snapshots for this repository.")
+		}
+		for _, snapshot := range snapshots {
+			fmt.Printf("%-16s %s  %s\n", snapshot.Name, snapshot.Time.Format("2006-01-02 15:04:05"), snapshot.Message)
+		}
+		return nil
+	}
+	if len(snapshots) == 0 {
+		return fmt.Errorf("no workspace snapshots for this repository; enable workspace_snapshots in settings")
+	}
+
+	target := snapshots[len(snapshots)-1]
+	if len(args) > 0 {
+		found := false
+		for _, snapshot := range snapshots {
+			if snapshot.Name == args[0] {
+				target, found = snapshot, true
+			}
+		}
+		if !found {
+			return fmt.Errorf("no snapshot named %s; run 'agentsandbox rollback --list'", args[0])
+		}
+	}
+
+	current, err := git.CreateSnapshot(root, "before rollback to "+target.Name)
+	if err != nil {
+		return fmt.Errorf("failed to snapshot the workspace before rolling back: %w", err)
+	}
+	if current.Commit == target.Commit {
+		fmt.Printf("The workspace already matches snapshot %s\n", target.Name)
+		return nil
+	}
+	if err := git.RestoreSnapshot(root, target, current); err != nil {
+		return err
+	}
+	fmt.Printf("Restored %s to snapshot %s (%s)\n", root, target.Name, target.Message)
+	fmt.Printf("The previous state was saved as %s; run 'agentsandbox rollback %s' to return to it\n", current.Name, current.Name)
+	return nil
+}
@@ -3,11 +3,19 @@ package cli
 import (
 	"fmt"
 	"os"
+	"path/filepath"
+	"time"
 
 	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/clipboard"
 	"github.com/thaodangspace/agentsandbox/internal/config"
 	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/failure"
 	"github.com/thaodangspace/agentsandbox/internal/git"
+	"github.com/thaodangspace/agentsandbox/internal/i18n"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+	"github.com/thaodangspace/agentsandbox/internal/templates"
+	"github.com/thaodangspace/agentsandbox/internal/ui"
 )
 
 var (
@@ -19,6 +27,24 @@ var (
 	shellMode      bool
 	noClipboard    bool
 	ports          []string
+	templateName   string
+	cacheFrom      string
+	protectGit     string
+	noMask         bool
+	allowSecrets   bool
+	maxDuration    time.Duration
+	allowProtected bool
+	switchBranch   string
+	sessionName    string
+	workspaceDirs  []string
+	networkJoin    string
+	gui            bool
+	dockerSocket   bool
+	dind           bool
+	noEmoji        bool
+	plainOutput    bool
+	resetDefaults  bool
+	agentArgs      []string
 
 	// Root command
 	rootCmd = &cobra.Command{
@@ -27,18 +53,42 @@ var (
 		Long: `Agent Sandbox creates isolated Docker containers with AI development agents.
 Compatible with Claude, Gemini, Codex, Qwen, and Cursor development agents.`,
 		Version: "0.2.0",
+		Args:    agentArgsOnly,
 		RunE:    runStart,
 	}
 )
 
 func init() {
 	rootCmd.PersistentFlags().StringVar(&agentName, "agent", "claude", "Agent to start in the container (claude, gemini, codex, qwen, cursor)")
+	rootCmd.PersistentFlags().BoolVar(&noEmoji, "no-emoji", false, "Print plain ASCII markers instead of emoji and box drawing")
+	rootCmd.PersistentFlags().BoolVar(&plainOutput, "plain", false, "Print without emoji, colour or progress spinners (default from output)")
+	rootCmd.PersistentFlags().StringVar(&errorFormat, "error-format", "text", "Print errors as text or json, with their kind and exit code")
+	// Errors are printed by PrintError, in the format asked for
+	rootCmd.SilenceErrors = true
+	rootCmd.SetFlagErrorFunc(usageError)
+	cobra.OnInitialize(configureOutput)
 	rootCmd.Flags().BoolVar(&continueFlag, "continue", false, "Resume the last created container")
 	rootCmd.Flags().StringVar(&addDir, "add-dir", "", "Additional directory to mount read-only inside the container")
 	rootCmd.Flags().StringVar(&worktree, "worktree", "", "Create and use a git worktree for the specified branch")
+	rootCmd.Flags().StringVar(&switchBranch, "branch", "", "Check out this branch (creating it if needed) and reuse the directory's existing container")
 	rootCmd.Flags().BoolVar(&shellMode, "shell", false, "Attach to container shell without starting the agent")
 	rootCmd.Flags().BoolVar(&noClipboard, "no-clipboard", false, "Disable clipboard image sharing between host and container")
 	rootCmd.Flags().StringSliceVarP(&ports, "port", "p", []string{}, "Publish container port to host (format: HOST_PORT:CONTAINER_PORT, can be specified multiple times)")
+	rootCmd.Flags().StringVar(&templateName, "template", "", "Project template to bootstrap the container with (rails, nextjs, ml, go-service)")
+	rootCmd.Flags().BoolVar(&noMask, "no-mask", false, "Mount env files unmasked (for trusted sessions only)")
+	rootCmd.Flags().BoolVar(&allowSecrets, "allow-secrets", false, "Mount the project without scanning for key material and credentials")
+	rootCmd.Flags().BoolVar(&allowProtected, "allow-protected", false, "Run the agent even when the project is on a protected branch")
+	rootCmd.Flags().DurationVar(&maxDuration, "max-duration", 0, "Interrupt the agent after this long, e.g. 45m (default from session.max_duration)")
+	rootCmd.Flags().StringVar(&sessionName, "name", "", "Name for the session, accepted wherever a container name is (attach, rm, logs --container)")
+	rootCmd.Flags().StringSliceVar(&workspaceDirs, "workspace-dir", nil, "Additional repository to mount read-write and track changes in (repeatable)")
+	rootCmd.Flags().StringVar(&networkJoin, "network-join", "", "Join a shared network created with 'network create' so sandboxes can reach each other by container name")
+	rootCmd.Flags().BoolVar(&resetDefaults, "reset", false, "Forget the agent and flags saved for this project instead of reusing them")
+	rootCmd.Flags().StringVar(&protectGit, "protect-git", "", "Mount .git/hooks and .git/config (hooks) or all of .git (readonly) read-only; off to allow writes (default from git_protection)")
+	rootCmd.Flags().BoolVar(&gui, "gui", false, "Share the host's Wayland or X11 display so headed browsers and GUI tools can open windows")
+	rootCmd.Flags().BoolVar(&dockerSocket, "docker-socket", false, "Mount the host's Docker socket so the agent can build and run containers (equivalent to root on the host)")
+	rootCmd.Flags().BoolVar(&dind, "dind", false, "Give the agent its own Docker daemon in a privileged sidecar instead of the host's")
+	rootCmd.MarkFlagsMutuallyExclusive("docker-socket", "dind")
+	rootCmd.Flags().StringVar(&cacheFrom, "cache-from", "", "Registry repository to reuse image build cache from (overrides registry_cache.ref)")
 
 	// Add subcommands
 	rootCmd.AddCommand(listCmd)
@@ -47,37 +97,84 @@ func init() {
 	rootCmd.AddCommand(logsCmd)
 	rootCmd.AddCommand(startCmd)
 	rootCmd.AddCommand(attachCmd)
+	rootCmd.AddCommand(templatesCmd)
+	rootCmd.AddCommand(shareCmd)
+	rootCmd.AddCommand(pushCmd)
+	rootCmd.AddCommand(pullCmd)
+	rootCmd.AddCommand(approveCmd)
+	rootCmd.AddCommand(exportCmd)
+	rootCmd.AddCommand(importCmd)
+	rootCmd.AddCommand(replayCmd)
+	rootCmd.AddCommand(prCmd)
+	rootCmd.AddCommand(fromIssueCmd)
+	rootCmd.AddCommand(runCmd)
 }
 
 // Execute runs the root command
 func Execute() error {
+	// Upgrade state written by older versions, unless asked only to report on it
+	if target, _, err := rootCmd.Find(os.Args[1:]); err != nil || target != stateMigrateCmd {
+		migrateState()
+	}
+	// Mention sandboxes that crashed or ran out of memory since the last run
+	if len(os.Args) < 2 || os.Args[1] != cobra.ShellCompRequestCmd {
+		container.ReportPostMortems()
+	}
 	return rootCmd.Execute()
 }
 
-// runStart is the default action (start a new container)
-func runStart(cmd *cobra.Command, args []string) error {
-	// Validate agent
-	agent, err := config.ValidateAgent(agentName)
+// configureOutput applies --no-emoji, --plain and the output and language settings before a command runs
+func configureOutput() {
+	settings, err := config.LoadSettings()
 	if err != nil {
-		return err
+		settings = config.DefaultSettings()
 	}
+	ui.Configure(plainOutput || settings.Output == "plain", noEmoji)
+	i18n.Configure(settings.Language)
+}
 
-	// Get current directory
-	currentDir, err := os.Getwd()
-	if err != nil {
-		return fmt.Errorf("failed to get current directory: %w", err)
+// Version returns the CLI version string
+func Version() string {
+	return rootCmd.Version
+}
+
+// agentArgsOnly accepts arguments only after --, where they are passed to the agent,
+// so a mistyped subcommand is still reported as unknown
+func agentArgsOnly(cmd *cobra.Command, args []string) error {
+	if len(args) > 0 && cmd.ArgsLenAtDash() != 0 {
+		return failure.Errorf(failure.Usage, "unknown command %q for %q", args[0], cmd.CommandPath())
 	}
+	return nil
+}
 
-	// Handle worktree
-	if worktree != "" {
-		worktreePath, err := git.CreateWorktree(currentDir, worktree)
+// runStart is the default action (start a new container)
+func runStart(cmd *cobra.Command, args []string) error {
+	// Resolve template
+	opts := container.CreateOptions{CacheFrom: cacheFrom, NoMask: noMask, AllowSecrets: allowSecrets, ProtectGit: protectGit, GUI: gui}
+	if templateName != "" {
+		tmpl, err := templates.Get(templateName)
 		if err != nil {
-			return fmt.Errorf("failed to create worktree for branch %s: %w", worktree, err)
+			return err
 		}
-		currentDir = worktreePath
-		if err := os.Chdir(currentDir); err != nil {
-			return fmt.Errorf("failed to change directory to worktree: %w", err)
+		opts.Template = tmpl
+	}
+
+	if sessionName != "" {
+		if err := state.ValidateAlias(sessionName); err != nil {
+			return err
 		}
+		opts.Name = sessionName
+	}
+	if dockerSocket {
+		opts.Docker = container.DockerSocket
+	} else if dind {
+		opts.Docker = container.DockerInDocker
+	}
+
+	// Get current directory
+	currentDir, err := os.Getwd()
+	if err != nil {
+		return fmt.Errorf("failed to get current directory: %w", err)
 	}
 
 	// Load settings
@@ -87,51 +184,282 @@ func runStart(cmd *cobra.Command, args []string) error {
 		settings = config.DefaultSettings()
 	}
 
-	// Check Docker availability
-	if err := container.CheckDockerAvailability(); err != nil {
+	// A bare run reuses the agent and flags last used for this project
+	bare := bareInvocation(cmd, args)
+	restoredContinue := false
+	defaults, err := state.LoadProjectDefaults(currentDir)
+	if err != nil {
+		fmt.Printf("Warning: failed to load saved project flags: %v\n", err)
+	}
+	if resetDefaults {
+		if err := state.ClearProjectDefaults(currentDir); err != nil {
+			return fmt.Errorf("failed to clear saved project flags: %w", err)
+		}
+		defaults = state.ProjectDefaults{}
+		fmt.Printf("Forgot the agent and flags saved for %s\n", currentDir)
+	} else if bare && defaults.Agent != "" {
+		shellMode, continueFlag, agentArgs = defaults.Shell, defaults.Continue, defaults.AgentArgs
+		restoredContinue = defaults.Continue
+		fmt.Println("Using the agent and flags last used here (run with --reset to forget them)")
+	}
+	if len(args) > 0 {
+		agentArgs = args
+	}
+
+	// Choose the agent, asking when neither --agent nor default_agent decides it
+	agent, err := resolveAgent(cmd, defaults.Agent, bare, settings)
+	if err != nil {
+		return err
+	}
+	saved := state.ProjectDefaults{Agent: string(agent), Shell: shellMode, Continue: continueFlag, AgentArgs: agentArgs}
+	if err := state.SaveProjectDefaults(currentDir, saved); err != nil {
+		fmt.Printf("Warning: failed to save project flags: %v\n", err)
+	}
+
+	// Decide how far to trust the project before anything from it runs
+	opts.Trust, err = resolveTrust(container.TrustRoot(currentDir), defaults.Agent != "")
+	if err != nil {
 		return err
 	}
 
-	// Get skip permission flag
-	skipPermissionFlag := settings.SkipPermissionFlags[agentName]
+	// Switch the project to another branch in place so its container can be reused
+	if switchBranch != "" {
+		if worktree != "" {
+			return fmt.Errorf("--branch and --worktree cannot be used together")
+		}
+		if err := switchProjectBranch(currentDir, switchBranch, settings.ProtectedBranches); err != nil {
+			return err
+		}
+	}
+
+	// Handle worktree; with always_worktree every new container gets its own
+	branch := worktree
+	autoWorktree := false
+	if branch == "" && switchBranch == "" && settings.AlwaysWorktree && !continueFlag && !git.IsWorktreePath(currentDir) {
+		if _, err := git.HeadCommit(currentDir); err == nil {
+			branch = fmt.Sprintf("%s-%s", container.Sanitize(filepath.Base(currentDir)), time.Now().Format("20060102-150405"))
+			autoWorktree = true
+		}
+	}
+	if branch != "" {
+		worktreePath, err := git.CreateWorktree(currentDir, branch)
+		if err != nil {
+			return fmt.Errorf("failed to create worktree for branch %s: %w", branch, err)
+		}
+		currentDir = worktreePath
+		if err := os.Chdir(currentDir); err != nil {
+			return fmt.Errorf("failed to change directory to worktree: %w", err)
+		}
+	}
+
+	// Enforce clipboard retention even when the watcher is not running, unless the daemon handles it
+	if !settings.Maintenance.Background {
+		if dir, err := clipboard.GetClipboardDir(); err == nil {
+			if _, err := clipboard.Prune(dir, clipboard.RetentionFromSettings(settings.Clipboard)); err != nil {
+				fmt.Printf("Warning: failed to prune clipboard images: %v\n", err)
+			}
+		}
+	}
 
-	// Handle continue flag
+	// Get skip permission flag and extra agent arguments
+	skipPermissionFlag := agentFlags(container.PermissionFlag(settings, agent, opts.Trust), agentArgs)
+	opts.Session = sessionOptions(settings)
+
+	// Handle continue flag. A saved --continue belongs to this project, so it continues
+	// this project's container rather than whichever was used last anywhere.
+	if continueFlag && restoredContinue {
+		projectContainer, _, err := container.FindProjectContainer(currentDir, agent)
+		if err != nil {
+			return err
+		}
+		if projectContainer == "" {
+			continueFlag = false
+		} else {
+			return handleContinue(projectContainer, agent, settings, agentArgs, opts.Session)
+		}
+	}
 	if continueFlag {
-		return handleContinue(agent, skipPermissionFlag)
+		return handleContinue("", agent, settings, agentArgs, opts.Session)
 	}
 
-	// Check for existing container
-	existing, err := container.FindExistingContainer(currentDir, agent)
+	// Check for existing container; a successful lookup also shows Docker is available
+	existing, projectDir, err := container.FindProjectContainer(currentDir, agent)
 	if err != nil {
+		if err := container.CheckDockerAvailability(); err != nil {
+			return err
+		}
 		fmt.Printf("Warning: failed to check for existing container: %v\n", err)
 	}
 
 	if existing != "" {
-		fmt.Printf("Found existing container: %s\n", existing)
-		fmt.Println("Attaching to existing container instead of creating a new one...")
-		return container.ResumeContainer(existing, agent, false, skipPermissionFlag, shellMode, true)
+		fmt.Println(i18n.T("container.found_existing", existing))
+		if len(workspaceDirs) > 0 {
+			fmt.Println("Warning: --workspace-dir only applies to new containers; remove the existing one to change its workspace")
+		}
+		if networkJoin != "" {
+			fmt.Println("Warning: --network-join only applies to new containers; use 'docker network connect' for existing ones")
+		}
+		if gui {
+			fmt.Println("Warning: --gui only applies to new containers; remove the existing one to share the display")
+		}
+		if opts.Docker != "" {
+			fmt.Println("Warning: --docker-socket and --dind only apply to new containers; remove the existing one to add Docker")
+		}
+		if projectDir != currentDir {
+			fmt.Println(i18n.T("container.subdirectory", projectDir))
+		}
+		if sessionName != "" {
+			saveSessionName(sessionName, existing)
+		}
+		fmt.Println(i18n.T("container.attaching_existing"))
+		return container.ResumeContainer(existing, agent, false, skipPermissionFlag, shellMode, true, opts.Session)
+	}
+
+	// A new container mounts the whole repository even when started from a subdirectory
+	if root := git.RepoRoot(currentDir); root != "" && root != currentDir {
+		currentDir = root
+	}
+
+	// Keep agents from editing mainline branches directly
+	if branch == "" && switchBranch == "" && !allowProtected {
+		if err := checkProtectedBranch(currentDir, settings.ProtectedBranches); err != nil {
+			return err
+		}
+	}
+
+	opts.WorkspaceDirs, err = resolveWorkspaceDirs(currentDir, workspaceDirs)
+	if err != nil {
+		return err
+	}
+
+	if networkJoin != "" {
+		if !container.NetworkExists(networkJoin) {
+			return fmt.Errorf("network %s does not exist; create it with 'agentsandbox network create %s'", networkJoin, networkJoin)
+		}
+		opts.Network = networkJoin
 	}
 
 	// Generate container name
 	containerName := container.GenerateContainerName(currentDir, agent)
+	if autoWorktree {
+		if err := state.SaveContainerWorktree(containerName, currentDir); err != nil {
+			fmt.Printf("Warning: failed to record worktree: %v\n", err)
+		}
+	}
 
-	fmt.Printf("Starting %s Agent Sandbox container: %s\n", agent.DisplayName(), containerName)
-	fmt.Printf("Container %s started successfully!\n", containerName)
-	fmt.Printf("To attach to the container manually, run: docker exec -it %s /bin/bash\n", containerName)
+	if sessionName != "" {
+		saveSessionName(sessionName, containerName)
+	}
+
+	fmt.Println(i18n.T("container.starting", agent.DisplayName(), containerName))
+	fmt.Println(i18n.T("container.started", containerName))
+	fmt.Println(i18n.T("container.attach_hint", containerName))
 
 	// Create and start the container
-	if err := container.CreateContainer(containerName, currentDir, addDir, agent, skipPermissionFlag, shellMode, true, ports); err != nil {
+	if err := container.CreateContainer(containerName, currentDir, addDir, agent, skipPermissionFlag, shellMode, true, ports, opts); err != nil {
 		return fmt.Errorf("failed to create container: %w", err)
 	}
 
 	return nil
 }
 
-// handleContinue handles the --continue flag
-func handleContinue(agent config.Agent, skipPermissionFlag string) error {
-	containerName, err := container.LoadLastContainer()
+// resolveWorkspaceDirs makes --workspace-dir paths absolute and checks they are
+// directories outside the project, which is already mounted
+func resolveWorkspaceDirs(projectDir string, dirs []string) ([]string, error) {
+	var resolved []string
+	for _, dir := range dirs {
+		abs, err := filepath.Abs(dir)
+		if err != nil {
+			return nil, fmt.Errorf("invalid workspace directory %s: %w", dir, err)
+		}
+		info, err := os.Stat(abs)
+		if err != nil || !info.IsDir() {
+			return nil, fmt.Errorf("workspace directory %s does not exist", dir)
+		}
+		if container.IsWithin(projectDir, abs) || container.IsWithin(abs, projectDir) {
+			return nil, fmt.Errorf("workspace directory %s overlaps the project directory %s", dir, projectDir)
+		}
+		resolved = append(resolved, abs)
+	}
+	return resolved, nil
+}
+
+// saveSessionName records the --name given for a container
+func saveSessionName(name, containerName string) {
+	if err := state.SaveAlias(name, containerName); err != nil {
+		fmt.Printf("Warning: failed to save session name: %v\n", err)
+		return
+	}
+	fmt.Println(i18n.T("session.name", name))
+}
+
+// sessionOptions combines the --max-duration flag with the session settings
+func sessionOptions(settings *config.Settings) container.SessionOptions {
+	opts := container.SessionOptions{
+		MaxDuration:   maxDuration,
+		StopOnTimeout: settings.Session.StopOnTimeout,
+	}
+	if opts.MaxDuration == 0 && settings.Session.MaxDuration != "" {
+		d, err := time.ParseDuration(settings.Session.MaxDuration)
+		if err != nil {
+			fmt.Printf("Warning: invalid session.max_duration %q: %v\n", settings.Session.MaxDuration, err)
+		} else {
+			opts.MaxDuration = d
+		}
+	}
+	return opts
+}
+
+// switchProjectBranch checks out branch in dir after making sure no work would be lost
+func switchProjectBranch(dir, branch string, protected []string) error {
+	if git.CurrentBranch(dir) == branch {
+		return nil
+	}
+	if pattern, ok := git.MatchBranch(branch, protected); ok && !allowProtected {
+		return fmt.Errorf("%s is a protected branch (matches %q): pass --allow-protected to switch to it anyway", branch, pattern)
+	}
+
+	dirty, err := git.HasUncommittedChanges(dir)
 	if err != nil {
-		return fmt.Errorf("failed to load last container: %w", err)
+		return err
+	}
+	if dirty {
+		return fmt.Errorf("%s has uncommitted changes; commit or stash them before switching to %s", dir, branch)
+	}
+
+	if err := git.Checkout(dir, branch); err != nil {
+		return err
+	}
+	fmt.Printf("Switched %s to branch %s\n", dir, branch)
+	return nil
+}
+
+// checkProtectedBranch refuses to start on a branch matching one of the protected patterns
+func checkProtectedBranch(dir string, patterns []string) error {
+	branch := git.CurrentBranch(dir)
+	if branch == "" {
+		return nil
+	}
+	if pattern, ok := git.MatchBranch(branch, patterns); ok {
+		return fmt.Errorf("%s is a protected branch (matches %q): use --worktree <branch> to work on a separate branch, or --allow-protected to run here anyway", branch, pattern)
+	}
+	return nil
+}
+
+// handleContinue handles the --continue flag, continuing containerName or, when empty,
+// the last container used. That can belong to another project, so its skip-permission
+// flag follows that project's trust.
+func handleContinue(containerName string, agent config.Agent, settings *config.Settings, agentArgs []string, limits container.SessionOptions) error {
+	if err := container.CheckDockerAvailability(); err != nil {
+		return err
+	}
+
+	if containerName == "" {
+		var err error
+		containerName, err = container.LoadLastContainer()
+		if err != nil {
+			return fmt.Errorf("failed to load last container: %w", err)
+		}
 	}
 
 	if containerName == "" {
@@ -143,6 +471,13 @@ func handleContinue(agent config.Agent, skipPermissionFlag string) error {
 		agent = extractedAgent
 	}
 
-	return container.ResumeContainer(containerName, agent, true, skipPermissionFlag, shellMode, true)
+	// Without its project directory the trust is unknown, so no permissions are skipped
+	level := state.TrustRestricted
+	if dir, err := container.GetContainerDirectory(containerName); err == nil {
+		level = container.RecordedTrust(dir)
+	}
+	skipPermissionFlag := agentFlags(container.PermissionFlag(settings, agent, level), agentArgs)
+
+	return container.ResumeContainer(containerName, agent, true, skipPermissionFlag, shellMode, true, limits)
 }
 
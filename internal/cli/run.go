@@ -0,0 +1,94 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"strings"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/headless"
+	"github.com/thaodangspace/agentsandbox/internal/prompts"
+)
+
+var (
+	runCmd = &cobra.Command{
+		Use:   "run",
+		Short: "Run a prompt headlessly in the project's sandbox",
+		Long: `Run a single prompt headlessly in the sandbox for the current directory and record
+it as a session log.
+
+Prompts can be shared with the project as Markdown files in .agentsandbox/prompts/
+and run by name with --prompt-file. {{branch}}, {{files_changed}} and {{project}}
+are substituted, as are variables given with --var.`,
+		RunE: runRun,
+	}
+
+	// Run flags
+	runPrompt     string
+	runPromptFile string
+	runVars       []string
+	runList       bool
+)
+
+func init() {
+	runCmd.Flags().StringVar(&runPrompt, "prompt", "", "Prompt text to run")
+	runCmd.Flags().StringVar(&runPromptFile, "prompt-file", "", "Prompt from .agentsandbox/prompts (name without .md) or a path to a file")
+	runCmd.Flags().StringArrayVar(&runVars, "var", nil, "Template variable as key=value (can be specified multiple times)")
+	runCmd.Flags().BoolVar(&runList, "list", false, "List the project's prompt library")
+}
+
+func runRun(cmd *cobra.Command, args []string) error {
+	currentDir, err := os.Getwd()
+	if err != nil {
+		return fmt.Errorf("failed to get current directory: %w", err)
+	}
+
+	if runList {
+		library, err := prompts.List(currentDir)
+		if err != nil {
+			return err
+		}
+		if len(library) == 0 {
+			fmt.Printf("No prompts found in %s\n", prompts.Dir)
+			return nil
+		}
+		for _, p := range library {
+			fmt.Printf("%-20s %s\n", p.Name, p.Summary)
+		}
+		return nil
+	}
+
+	text := runPrompt
+	data := map[string]interface{}{}
+	switch {
+	case runPrompt != "" && runPromptFile != "":
+		return fmt.Errorf("use either --prompt or --prompt-file, not both")
+	case runPromptFile != "":
+		if text, err = prompts.Load(currentDir, runPromptFile); err != nil {
+			return err
+		}
+		data["prompt_file"] = runPromptFile
+	case runPrompt == "":
+		return fmt.Errorf("a prompt is required: use --prompt or --prompt-file (see --list)")
+	}
+
+	vars := prompts.Variables(currentDir)
+	for _, v := range runVars {
+		key, value, ok := strings.Cut(v, "=")
+		if !ok {
+			return fmt.Errorf("invalid --var %q, expected key=value", v)
+		}
+		vars[key] = value
+	}
+
+	text, missing := prompts.Render(text, vars)
+	if len(missing) > 0 {
+		fmt.Printf("Warning: no value for %s; pass --var name=value\n", strings.Join(missing, ", "))
+	}
+
+	logFile, err := headless.Run(currentDir, agentName, text, data)
+	if logFile != "" {
+		fmt.Printf("Session log: %s\n", logFile)
+	}
+	return err
+}
@@ -0,0 +1,141 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"strings"
+	"time"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/schedule"
+	"github.com/thaodangspace/agentsandbox/internal/ui"
+)
+
+var (
+	scheduleCmd = &cobra.Command{
+		Use:   "schedule",
+		Short: "Manage recurring headless agent tasks",
+	}
+
+	scheduleAddCmd = &cobra.Command{
+		Use:   "add <cron>",
+		Short: "Schedule a prompt for the current project, e.g. add \"0 6 * * *\" --prompt \"update deps\"",
+		Args:  cobra.ExactArgs(1),
+		RunE:  runScheduleAdd,
+	}
+
+	scheduleListCmd = &cobra.Command{
+		Use:     "list",
+		Aliases: []string{"ls"},
+		Short:   "List scheduled tasks",
+		RunE:    runScheduleList,
+	}
+
+	scheduleRemoveCmd = &cobra.Command{
+		Use:     "remove <id>",
+		Aliases: []string{"rm"},
+		Short:   "Remove a scheduled task",
+		Args:    cobra.ExactArgs(1),
+		RunE:    runScheduleRemove,
+	}
+
+	scheduleRunDueCmd = &cobra.Command{
+		Use:   "run-due",
+		Short: "Run every task that is due (call this from cron, e.g. every minute)",
+		RunE:  runScheduleRunDue,
+	}
+
+	// Schedule flags
+	schedulePrompt string
+)
+
+func init() {
+	scheduleAddCmd.Flags().StringVar(&schedulePrompt, "prompt", "", "Prompt to run headlessly")
+	scheduleAddCmd.MarkFlagRequired("prompt")
+
+	scheduleCmd.AddCommand(scheduleAddCmd)
+	scheduleCmd.AddCommand(scheduleListCmd)
+	scheduleCmd.AddCommand(scheduleRemoveCmd)
+	scheduleCmd.AddCommand(scheduleRunDueCmd)
+	rootCmd.AddCommand(scheduleCmd)
+}
+
+func runScheduleAdd(cmd *cobra.Command, args []string) error {
+	agent, err := config.ValidateAgent(agentName)
+	if err != nil {
+		return err
+	}
+
+	currentDir, err := os.Getwd()
+	if err != nil {
+		return fmt.Errorf("failed to get current directory: %w", err)
+	}
+
+	task, err := schedule.Add(schedule.Task{
+		Cron:   args[0],
+		Prompt: schedulePrompt,
+		Dir:    currentDir,
+		Agent:  string(agent),
+	})
+	if err != nil {
+		return err
+	}
+
+	next, _ := task.NextRun()
+	fmt.Printf("Scheduled task %s, next run %s\n", task.ID, next.Format("2006-01-02 15:04"))
+	fmt.Println("Make sure 'agentsandbox schedule run-due' runs regularly, e.g. from crontab:")
+	fmt.Println("  * * * * * agentsandbox schedule run-due")
+	return nil
+}
+
+func runScheduleList(cmd *cobra.Command, args []string) error {
+	tasks, err := schedule.Load()
+	if err != nil {
+		return err
+	}
+
+	if len(tasks) == 0 {
+		fmt.Println("No scheduled tasks")
+		return nil
+	}
+
+	fmt.Printf("%-4s %-15s %-8s %-17s %-30s %s\n", "ID", "Cron", "Agent", "Next run", "Project", "Prompt")
+	fmt.Println(strings.Repeat("-", 100))
+	for _, task := range tasks {
+		next := "-"
+		if t, err := task.NextRun(); err == nil && !t.IsZero() {
+			next = t.Format("2006-01-02 15:04")
+		}
+		fmt.Printf("%-4s %-15s %-8s %-17s %-30s %s\n", task.ID, task.Cron, task.Agent, next, task.Dir, ui.Truncate(task.Prompt, 40))
+	}
+	return nil
+}
+
+func runScheduleRemove(cmd *cobra.Command, args []string) error {
+	if err := schedule.Remove(args[0]); err != nil {
+		return err
+	}
+	fmt.Printf("Removed scheduled task %s\n", args[0])
+	return nil
+}
+
+func runScheduleRunDue(cmd *cobra.Command, args []string) error {
+	// Claim due tasks before running any, so an overlapping run-due does not start them twice
+	tasks, err := schedule.ClaimDue(time.Now())
+	if err != nil {
+		return err
+	}
+
+	for _, task := range tasks {
+		fmt.Printf("Running scheduled task %s in %s\n", task.ID, task.Dir)
+		logFile, err := schedule.Run(task)
+		if err != nil {
+			fmt.Printf("Warning: scheduled task %s failed: %v\n", task.ID, err)
+		}
+		if logFile != "" {
+			fmt.Printf("Session log: %s\n", logFile)
+		}
+	}
+	return nil
+}
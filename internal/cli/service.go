@@ -0,0 +1,119 @@
+package cli
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/clipboard"
+	"github.com/thaodangspace/agentsandbox/internal/service"
+)
+
+var (
+	serviceCmd = &cobra.Command{
+		Use:   "service",
+		Short: "Run the clipboard watcher and maintenance daemon under systemd or launchd",
+	}
+
+	serviceInstallCmd = &cobra.Command{
+		Use:   "install [service...]",
+		Short: "Install and start user services (all of them by default)",
+		RunE:  runServiceInstall,
+	}
+
+	serviceUninstallCmd = &cobra.Command{
+		Use:   "uninstall [service...]",
+		Short: "Stop and remove user services (all of them by default)",
+		RunE:  runServiceUninstall,
+	}
+
+	serviceStatusCmd = &cobra.Command{
+		Use:   "status",
+		Short: "Show the state of each user service",
+		RunE:  runServiceStatus,
+	}
+)
+
+func init() {
+	serviceCmd.AddCommand(serviceInstallCmd)
+	serviceCmd.AddCommand(serviceUninstallCmd)
+	serviceCmd.AddCommand(serviceStatusCmd)
+	rootCmd.AddCommand(serviceCmd)
+}
+
+// selectedServices resolves service names from args, defaulting to every service
+func selectedServices(args []string) ([]service.Service, error) {
+	if len(args) == 0 {
+		return service.Services(), nil
+	}
+
+	var services []service.Service
+	for _, name := range args {
+		s, err := service.Find(name)
+		if err != nil {
+			return nil, err
+		}
+		services = append(services, s)
+	}
+	return services, nil
+}
+
+func runServiceInstall(cmd *cobra.Command, args []string) error {
+	services, err := selectedServices(args)
+	if err != nil {
+		return err
+	}
+
+	for _, s := range services {
+		// Replace a watcher started the old way so two don't run at once
+		if s.Name == "clipboard-watcher" {
+			if _, err := clipboard.StopWatcher(); err != nil {
+				fmt.Printf("Warning: %v\n", err)
+			}
+		}
+
+		path, err := s.Install()
+		if err != nil {
+			return fmt.Errorf("failed to install %s: %w", s.Name, err)
+		}
+		fmt.Printf("Installed %s (%s)\n", s.Name, path)
+	}
+	return nil
+}
+
+func runServiceUninstall(cmd *cobra.Command, args []string) error {
+	services, err := selectedServices(args)
+	if err != nil {
+		return err
+	}
+
+	for _, s := range services {
+		removed, err := s.Uninstall()
+		if err != nil {
+			return fmt.Errorf("failed to uninstall %s: %w", s.Name, err)
+		}
+		if removed {
+			fmt.Printf("Uninstalled %s\n", s.Name)
+		} else {
+			fmt.Printf("%s is not installed\n", s.Name)
+		}
+	}
+	return nil
+}
+
+func runServiceStatus(cmd *cobra.Command, args []string) error {
+	fmt.Printf("%-20s %-15s %s\n", "SERVICE", "STATE", "UNIT")
+	fmt.Println(strings.Repeat("-", 80))
+
+	for _, s := range service.Services() {
+		path, err := s.UnitPath()
+		if err != nil {
+			return err
+		}
+		if !s.Installed() {
+			path = "-"
+		}
+		fmt.Printf("%-20s %-15s %s\n", s.Name, s.State(), path)
+	}
+	return nil
+}
@@ -0,0 +1,63 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"path"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/share"
+)
+
+var (
+	shareCmd = &cobra.Command{
+		Use:   "share [file...]",
+		Short: "Copy files into a container's share directory",
+		Long: fmt.Sprintf(`Copy files or directories into the share directory of the current project's container,
+or the one given with --container, which is mounted at %s inside it. Each container
+has its own share directory, removed along with the container. Run without arguments
+to list the shared files. Inside a container, the 'share' command copies files the
+other way.`, share.ContainerPath),
+		RunE: runShare,
+	}
+
+	// Share flags
+	shareContainer string
+)
+
+func init() {
+	shareCmd.Flags().StringVar(&shareContainer, "container", "", "Container or session name to share with (default: the current project's)")
+}
+
+func runShare(cmd *cobra.Command, args []string) error {
+	currentDir, err := os.Getwd()
+	if err != nil {
+		return fmt.Errorf("failed to get current directory: %w", err)
+	}
+	containerName, err := container.ResolveContainer(shareContainer, currentDir)
+	if err != nil {
+		return err
+	}
+
+	if len(args) == 0 {
+		names, err := share.List(containerName)
+		if err != nil {
+			return fmt.Errorf("failed to list shared files: %w", err)
+		}
+		if len(names) == 0 {
+			fmt.Println("No shared files")
+			return nil
+		}
+		for _, name := range names {
+			fmt.Println(name)
+		}
+		return nil
+	}
+
+	added, err := share.Add(containerName, args)
+	for _, name := range added {
+		fmt.Printf("Shared %s -> %s in %s\n", name, path.Join(share.ContainerPath, name), containerName)
+	}
+	return err
+}
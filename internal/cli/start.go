@@ -7,6 +7,7 @@ import (
 var startCmd = &cobra.Command{
 	Use:   "start",
 	Short: "Start a new agent sandbox container (alias for default behavior)",
+	Args:  agentArgsOnly,
 	RunE:  runStart,
 }
 
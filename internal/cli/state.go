@@ -0,0 +1,143 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"sort"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+var (
+	stateCmd = &cobra.Command{
+		Use:   "state",
+		Short: "Inspect and maintain agentsandbox's local state",
+	}
+
+	stateGCCmd = &cobra.Command{
+		Use:   "gc",
+		Short: "Remove state and archive logs of containers that no longer exist",
+		RunE:  runStateGC,
+	}
+
+	stateMigrateCmd = &cobra.Command{
+		Use:   "migrate",
+		Short: "Upgrade state written by older versions of agentsandbox",
+		Long: `Upgrade state written by older versions of agentsandbox. This happens automatically
+on the first run after an upgrade; state files, and the files a migration moves, are
+backed up to <state>/backups first.`,
+		RunE: runStateMigrate,
+	}
+
+	// State flags
+	stateGCDryRun      bool
+	stateMigrateDryRun bool
+)
+
+func init() {
+	stateGCCmd.Flags().BoolVar(&stateGCDryRun, "dry-run", false, "Only report what would be cleaned up")
+
+	stateMigrateCmd.Flags().BoolVar(&stateMigrateDryRun, "dry-run", false, "Only report the pending migrations and what they would change")
+
+	stateCmd.AddCommand(stateGCCmd)
+	stateCmd.AddCommand(stateMigrateCmd)
+	rootCmd.AddCommand(stateCmd)
+}
+
+func runStateGC(cmd *cobra.Command, args []string) error {
+	if err := container.CheckDockerAvailability(); err != nil {
+		return err
+	}
+
+	existing, err := container.ContainerNames()
+	if err != nil {
+		return err
+	}
+
+	report, err := state.CollectGarbage(existing, stateGCDryRun)
+	if err != nil {
+		return fmt.Errorf("failed to clean up state: %w", err)
+	}
+
+	if report.Empty() {
+		fmt.Println("No orphaned state found")
+		return nil
+	}
+	printGCReport(report, stateGCDryRun)
+	return nil
+}
+
+// printGCReport lists what a state cleanup removed or archived
+func printGCReport(report *state.GCReport, dryRun bool) {
+	removed, archived := "Removed", "Archived"
+	if dryRun {
+		removed, archived = "Would remove", "Would archive"
+	}
+
+	for _, path := range report.Files {
+		fmt.Printf("%s %s\n", removed, path)
+	}
+	for _, path := range report.EmptyLogDirs {
+		fmt.Printf("%s empty log directory %s\n", removed, path)
+	}
+
+	dirs := make([]string, 0, len(report.Archived))
+	for dir := range report.Archived {
+		dirs = append(dirs, dir)
+	}
+	sort.Strings(dirs)
+	for _, dir := range dirs {
+		fmt.Printf("%s %s -> %s\n", archived, dir, report.Archived[dir])
+	}
+
+	for _, alias := range report.Aliases {
+		fmt.Printf("%s session name %s\n", removed, alias)
+	}
+	if report.LastContainer != "" {
+		fmt.Printf("%s last container entry %s\n", removed, report.LastContainer)
+	}
+}
+
+func runStateMigrate(cmd *cobra.Command, args []string) error {
+	report, err := state.Migrate(stateMigrateDryRun)
+	if err != nil {
+		return err
+	}
+
+	if len(report.Steps) == 0 {
+		fmt.Printf("State is up to date (version %d)\n", report.From)
+		return nil
+	}
+	verb := "Migrated"
+	if stateMigrateDryRun {
+		verb = "Would migrate"
+	}
+	fmt.Printf("%s state from version %d to %d\n", verb, report.From, report.To)
+	for _, step := range report.Steps {
+		fmt.Printf("  %d. %s\n", step.Version, step.Description)
+		if len(step.Changes) == 0 {
+			fmt.Println("     nothing to change")
+		}
+		for _, change := range step.Changes {
+			fmt.Printf("     %s\n", change)
+		}
+	}
+	if report.Backup != "" {
+		fmt.Printf("Backup of the previous state: %s\n", report.Backup)
+	}
+	return nil
+}
+
+// migrateState upgrades state written by older versions, warning instead of failing
+func migrateState() {
+	report, err := state.Migrate(false)
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "Warning: failed to migrate state: %v\n", err)
+		return
+	}
+	if report.Changed() {
+		fmt.Fprintf(os.Stderr, "Migrated agentsandbox state to version %d (backup in %s)\n", report.To, report.Backup)
+	}
+}
@@ -0,0 +1,28 @@
+package cli
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/templates"
+)
+
+var templatesCmd = &cobra.Command{
+	Use:   "templates",
+	Short: "List the built-in project templates usable with --template",
+	RunE:  runTemplates,
+}
+
+func runTemplates(cmd *cobra.Command, args []string) error {
+	fmt.Printf("\n%-12s %-45s %s\n", "Template", "Description", "Services")
+	fmt.Println(strings.Repeat("-", 80))
+	for _, tmpl := range templates.All() {
+		var services []string
+		for _, svc := range tmpl.Services {
+			services = append(services, svc.Name)
+		}
+		fmt.Printf("%-12s %-45s %s\n", tmpl.Name, tmpl.Description, strings.Join(services, ", "))
+	}
+	return nil
+}
@@ -0,0 +1,157 @@
+package cli
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"strings"
+	"time"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+	"github.com/thaodangspace/agentsandbox/internal/testrun"
+)
+
+var (
+	testCmd = &cobra.Command{
+		Use:   "test [container|name]",
+		Short: "Run the project's tests in a sandbox and record the results",
+		Long: `Run the project's test command inside the sandbox and store the per-test results with
+its session logs. The command is detected from the project (cargo test, go test, jest,
+vitest, pytest, phpunit, npm test or rake test); --command runs another one, and --junit
+reads its results from a JUnit report it writes. --last shows the most recent stored
+result without running anything, and --json prints results as JSON for other tools.`,
+		Args: cobra.MaximumNArgs(1),
+		RunE: runTest,
+	}
+
+	// Test flags
+	testCommand string
+	testJUnit   string
+	testLast    bool
+	testJSON    bool
+)
+
+func init() {
+	testCmd.Flags().StringVar(&testCommand, "command", "", "Test command to run instead of the detected one")
+	testCmd.Flags().StringVar(&testJUnit, "junit", "", "JUnit report written by --command, as a path inside the container")
+	testCmd.Flags().BoolVar(&testLast, "last", false, "Show the most recent stored result instead of running the tests")
+	testCmd.Flags().BoolVar(&testJSON, "json", false, "Print the result as JSON")
+	rootCmd.AddCommand(testCmd)
+}
+
+func runTest(cmd *cobra.Command, args []string) error {
+	if err := container.CheckDockerAvailability(); err != nil {
+		return err
+	}
+	currentDir, err := os.Getwd()
+	if err != nil {
+		return fmt.Errorf("failed to get current directory: %w", err)
+	}
+	name := ""
+	if len(args) > 0 {
+		name = args[0]
+	}
+	containerName, err := container.ResolveContainer(name, currentDir)
+	if err != nil {
+		return err
+	}
+	projectDir, err := container.GetContainerDirectory(containerName)
+	if err != nil || projectDir == "" {
+		return fmt.Errorf("failed to find the project directory of %s", containerName)
+	}
+
+	if testLast {
+		files, err := state.ListTestResults(containerName, projectDir)
+		if err != nil {
+			return err
+		}
+		if len(files) == 0 {
+			return fmt.Errorf("no test results recorded for %s; run 'agentsandbox test' first", containerName)
+		}
+		result, err := testrun.Load(files[len(files)-1])
+		if err != nil {
+			return fmt.Errorf("failed to read test result: %w", err)
+		}
+		return reportTestResult(result, "")
+	}
+
+	test, err := testCommandFor(projectDir)
+	if err != nil {
+		return err
+	}
+	if running, _ := container.IsContainerRunning(containerName); !running {
+		return fmt.Errorf("container '%s' is not running", containerName)
+	}
+
+	// Keep the JSON on stdout parseable
+	out := os.Stdout
+	if testJSON {
+		out = os.Stderr
+	}
+	fmt.Fprintf(out, "Running %s in %s\n", test.Run, containerName)
+	result, err := container.RunTests(containerName, projectDir, *test, out)
+	if err != nil {
+		return err
+	}
+	file, err := container.SaveTestResult(projectDir, result)
+	if err != nil {
+		fmt.Fprintf(out, "Warning: %v\n", err)
+	}
+	return reportTestResult(result, file)
+}
+
+// testCommandFor returns the command given with --command, or the one detected for the project
+func testCommandFor(projectDir string) (*testrun.Command, error) {
+	if testCommand != "" {
+		if testJUnit != "" {
+			return &testrun.Command{Run: testCommand, Format: testrun.FormatJUnit, Report: testJUnit}, nil
+		}
+		return &testrun.Command{Run: testCommand, Format: testrun.FormatText}, nil
+	}
+	if testJUnit != "" {
+		return nil, fmt.Errorf("--junit needs --command")
+	}
+	test := testrun.Detect(projectDir)
+	if test == nil {
+		return nil, fmt.Errorf("no test command detected in %s; pass one with --command", projectDir)
+	}
+	return test, nil
+}
+
+// reportTestResult prints a summary and the failures, and fails when the tests did
+func reportTestResult(result *testrun.Result, file string) error {
+	if testJSON {
+		data, err := json.MarshalIndent(result, "", "  ")
+		if err != nil {
+			return err
+		}
+		fmt.Println(string(data))
+	} else {
+		fmt.Println()
+		for _, c := range result.Cases {
+			if c.Status != testrun.StatusFailed {
+				continue
+			}
+			fmt.Printf("FAIL %s\n", strings.TrimSpace(c.Suite+" "+c.Name))
+			if c.Message != "" {
+				fmt.Printf("    %s\n", strings.ReplaceAll(c.Message, "\n", "\n    "))
+			}
+		}
+		duration := (time.Duration(result.DurationMS) * time.Millisecond).Round(100 * time.Millisecond)
+		if result.Cases != nil {
+			fmt.Printf("%d passed, %d failed, %d skipped in %s (exit code %d)\n", result.Passed, result.Failed, result.Skipped, duration, result.ExitCode)
+		} else {
+			fmt.Printf("Exit code %d after %s\n", result.ExitCode, duration)
+		}
+		if file != "" {
+			fmt.Printf("Result saved to %s\n", file)
+		}
+	}
+
+	if !result.OK() {
+		return fmt.Errorf("tests failed in %s", result.Container)
+	}
+	return nil
+}
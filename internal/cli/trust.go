@@ -0,0 +1,117 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/picker"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+	"github.com/thaodangspace/agentsandbox/internal/ui"
+)
+
+var trustCmd = &cobra.Command{
+	Use:   "trust [trusted|restricted|untrusted]",
+	Short: "Show or set how far the current project is trusted",
+	Long: `Show or set the current project's trust level.
+
+  trusted     settings apply as they are
+  restricted  no skip-permission flags, no sudo and no shared networks; env files,
+              sensitive files and git hooks are always masked
+  untrusted   restricted, and the project is mounted read-only without network access
+
+The level applies to containers created after it changes.`,
+	Args:      cobra.MaximumNArgs(1),
+	ValidArgs: []string{state.TrustTrusted, state.TrustRestricted, state.TrustUntrusted},
+	RunE:      runTrust,
+}
+
+func init() {
+	rootCmd.AddCommand(trustCmd)
+}
+
+func runTrust(cmd *cobra.Command, args []string) error {
+	projectDir, err := trustProjectDir()
+	if err != nil {
+		return err
+	}
+
+	if len(args) == 0 {
+		level, err := state.LoadTrust(projectDir)
+		if err != nil {
+			return err
+		}
+		if level == "" {
+			fmt.Printf("%s has no trust level yet; you will be asked on the next start\n", projectDir)
+			return nil
+		}
+		fmt.Printf("%s is %s\n", projectDir, level)
+		return nil
+	}
+
+	if err := state.SaveTrust(projectDir, args[0]); err != nil {
+		return err
+	}
+	fmt.Printf("%s is now %s; remove its container for this to apply\n", projectDir, args[0])
+	return nil
+}
+
+// trustProjectDir returns the directory trust is recorded for: the repository root, or
+// the current directory outside a repository
+func trustProjectDir() (string, error) {
+	currentDir, err := os.Getwd()
+	if err != nil {
+		return "", fmt.Errorf("failed to get current directory: %w", err)
+	}
+	return container.TrustRoot(currentDir), nil
+}
+
+// resolveTrust returns the project's trust level, asking on the first run in a new
+// project. Projects used before trust levels existed, and runs without a terminal to
+// ask on, are trusted as before.
+func resolveTrust(projectDir string, usedBefore bool) (string, error) {
+	level, err := state.LoadTrust(projectDir)
+	if err != nil {
+		fmt.Printf("Warning: failed to load trust level: %v\n", err)
+		return state.TrustTrusted, nil
+	}
+	if level != "" {
+		return level, nil
+	}
+	if !usedBefore && !ui.IsTerminal(os.Stdin) {
+		return state.TrustTrusted, nil
+	}
+
+	level = state.TrustTrusted
+	if !usedBefore {
+		levels := []string{state.TrustTrusted, state.TrustRestricted, state.TrustUntrusted}
+		items := []picker.Item{
+			{Text: "Trust this project", Preview: func() []string {
+				return []string{"Settings apply as they are, including skip-permission flags and sudo."}
+			}},
+			{Text: "Restricted", Preview: func() []string {
+				return []string{"No skip-permission flags, no sudo and no shared networks.", "Env files, sensitive files and git hooks are always masked."}
+			}},
+			{Text: "Untrusted", Preview: func() []string {
+				return []string{"Restricted, and the project is mounted read-only without network access."}
+			}},
+		}
+		fmt.Printf("%s has not been started in a sandbox before. Do you trust its contents?\n", projectDir)
+		index, err := picker.Pick("TRUST", items)
+		if err != nil {
+			return "", err
+		}
+		if index < 0 {
+			return "", fmt.Errorf("no trust level selected; run 'agentsandbox trust <level>' to set one")
+		}
+		level = levels[index]
+	}
+
+	if err := state.SaveTrust(projectDir, level); err != nil {
+		fmt.Printf("Warning: failed to save trust level: %v\n", err)
+	} else if level != state.TrustTrusted {
+		fmt.Printf("Recorded %s as %s; change it with 'agentsandbox trust'\n", projectDir, level)
+	}
+	return level, nil
+}
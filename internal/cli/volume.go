@@ -0,0 +1,115 @@
+package cli
+
+import (
+	"fmt"
+
+	"github.com/spf13/cobra"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+)
+
+var (
+	volumeCmd = &cobra.Command{
+		Use:   "volume",
+		Short: "Back up and restore sandbox volumes such as node_modules",
+	}
+
+	volumeBackupCmd = &cobra.Command{
+		Use:   "backup <dir> [container|name...]",
+		Short: "Save the volumes of every sandbox, or the given ones, to a directory",
+		Args:  cobra.MinimumNArgs(1),
+		RunE:  runVolumeBackup,
+	}
+
+	volumeRestoreCmd = &cobra.Command{
+		Use:   "restore <dir> [container|name...]",
+		Short: "Restore volumes saved with 'volume backup' into their sandboxes",
+		Long: `Restore volumes saved with 'volume backup' into their sandboxes. Each archive is
+unpacked at the same mount point of the container with the same name, so a sandbox
+recreated after pruning Docker gets its dependencies back without downloading them.`,
+		Args: cobra.MinimumNArgs(1),
+		RunE: runVolumeRestore,
+	}
+)
+
+func init() {
+	volumeCmd.AddCommand(volumeBackupCmd)
+	volumeCmd.AddCommand(volumeRestoreCmd)
+	rootCmd.AddCommand(volumeCmd)
+}
+
+func runVolumeBackup(cmd *cobra.Command, args []string) error {
+	if err := container.CheckDockerAvailability(); err != nil {
+		return err
+	}
+
+	names := resolveNames(args[1:])
+	if len(names) == 0 {
+		sandboxes, err := container.ListSandboxes(true)
+		if err != nil {
+			return fmt.Errorf("failed to list containers: %w", err)
+		}
+		for _, info := range sandboxes {
+			names = append(names, info.Name)
+		}
+	}
+
+	volumes, err := container.SandboxVolumes(names)
+	if err != nil {
+		return err
+	}
+	if len(volumes) == 0 {
+		fmt.Println("No sandbox volumes to back up.")
+		return nil
+	}
+	if err := container.BackupVolumes(args[0], volumes); err != nil {
+		return err
+	}
+	fmt.Printf("Backed up %d volume(s) to %s\n", len(volumes), args[0])
+	return nil
+}
+
+func runVolumeRestore(cmd *cobra.Command, args []string) error {
+	if err := container.CheckDockerAvailability(); err != nil {
+		return err
+	}
+
+	volumes, err := container.LoadVolumeBackups(args[0])
+	if err != nil {
+		return err
+	}
+	wanted := make(map[string]bool)
+	for _, name := range resolveNames(args[1:]) {
+		wanted[name] = true
+	}
+
+	restored := 0
+	for _, v := range volumes {
+		if len(wanted) > 0 && !wanted[v.Container] {
+			continue
+		}
+		exists, err := container.ContainerExists(v.Container)
+		if err != nil {
+			return fmt.Errorf("failed to check if container exists: %w", err)
+		}
+		if !exists {
+			fmt.Printf("Skipping %s:%s; the container no longer exists\n", v.Container, v.Destination)
+			continue
+		}
+		fmt.Printf("Restoring %s:%s\n", v.Container, v.Destination)
+		if err := container.RestoreVolume(args[0], v); err != nil {
+			return err
+		}
+		restored++
+	}
+	fmt.Printf("Restored %d volume(s)\n", restored)
+	return nil
+}
+
+// resolveNames maps session names to their containers
+func resolveNames(args []string) []string {
+	names := make([]string, len(args))
+	for i, arg := range args {
+		names[i] = container.ResolveName(arg)
+	}
+	return names
+}
@@ -0,0 +1,31 @@
+//go:build !windows
+
+package clipboard
+
+import (
+	"os"
+	"os/exec"
+	"syscall"
+)
+
+// detach starts the command in its own session so it outlives the CLI
+func detach(cmd *exec.Cmd) {
+	cmd.SysProcAttr = &syscall.SysProcAttr{Setsid: true}
+}
+
+// IsProcessRunning checks if a process with the given PID is alive
+func IsProcessRunning(pid int) bool {
+	if pid <= 0 {
+		return false
+	}
+	process, err := os.FindProcess(pid)
+	if err != nil {
+		return false
+	}
+	return process.Signal(syscall.Signal(0)) == nil
+}
+
+// terminate asks the process to exit gracefully
+func terminate(process *os.Process) error {
+	return process.Signal(syscall.SIGTERM)
+}
@@ -0,0 +1,25 @@
+//go:build windows
+
+package clipboard
+
+import (
+	"os"
+	"os/exec"
+)
+
+// detach is a no-op on Windows; the child keeps running after the CLI exits
+func detach(cmd *exec.Cmd) {}
+
+// IsProcessRunning checks if a process with the given PID is alive
+func IsProcessRunning(pid int) bool {
+	if pid <= 0 {
+		return false
+	}
+	_, err := os.FindProcess(pid)
+	return err == nil
+}
+
+// terminate stops the process; Windows has no graceful termination signal
+func terminate(process *os.Process) error {
+	return process.Kill()
+}
@@ -0,0 +1,132 @@
+package clipboard
+
+import (
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+// Retention limits how many captured images are kept. Zero disables a limit.
+type Retention struct {
+	MaxFiles int
+	MaxBytes int64
+	MaxAge   time.Duration
+}
+
+// RetentionFromSettings converts the clipboard settings into a retention policy
+func RetentionFromSettings(s config.ClipboardSettings) Retention {
+	return Retention{
+		MaxFiles: s.MaxFiles,
+		MaxBytes: int64(s.MaxSizeMB) * 1024 * 1024,
+		MaxAge:   time.Duration(s.MaxAgeDays) * 24 * time.Hour,
+	}
+}
+
+// LoadRetention returns the retention policy from the user's settings
+func LoadRetention() Retention {
+	settings, err := config.LoadSettings()
+	if err != nil {
+		settings = config.DefaultSettings()
+	}
+	return RetentionFromSettings(settings.Clipboard)
+}
+
+type capturedImage struct {
+	name    string
+	size    int64
+	modTime time.Time
+}
+
+// isCapturedImage reports whether a file name was written by the watcher
+func isCapturedImage(name string) bool {
+	if !strings.HasPrefix(name, "clipboard-") {
+		return false
+	}
+	return strings.HasSuffix(name, ".png") || strings.HasSuffix(name, ".jpg") || strings.HasSuffix(name, ".jpeg")
+}
+
+// listImages returns the captured images in the directory, newest first
+func listImages(clipboardDir string) ([]capturedImage, error) {
+	entries, err := os.ReadDir(clipboardDir)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return nil, nil
+		}
+		return nil, err
+	}
+
+	var images []capturedImage
+	for _, entry := range entries {
+		if entry.IsDir() || !isCapturedImage(entry.Name()) {
+			continue
+		}
+		info, err := entry.Info()
+		if err != nil {
+			continue
+		}
+		images = append(images, capturedImage{name: entry.Name(), size: info.Size(), modTime: info.ModTime()})
+	}
+
+	sort.Slice(images, func(i, j int) bool {
+		return images[i].modTime.After(images[j].modTime)
+	})
+	return images, nil
+}
+
+// Prune deletes captured images that exceed the retention policy and returns how many were removed.
+// The newest image is always kept so the "latest" links stay valid.
+func Prune(clipboardDir string, retention Retention) (int, error) {
+	images, err := listImages(clipboardDir)
+	if err != nil {
+		return 0, err
+	}
+
+	removed := 0
+	var total int64
+	for i, img := range images {
+		total += img.size
+
+		expired := i > 0 && ((retention.MaxFiles > 0 && i >= retention.MaxFiles) ||
+			(retention.MaxBytes > 0 && total > retention.MaxBytes) ||
+			(retention.MaxAge > 0 && time.Since(img.modTime) > retention.MaxAge))
+		if !expired {
+			continue
+		}
+
+		if err := os.Remove(filepath.Join(clipboardDir, img.name)); err != nil && !os.IsNotExist(err) {
+			return removed, err
+		}
+		removed++
+	}
+
+	return removed, nil
+}
+
+// Clean deletes every captured image and the "latest" links and returns how many images were removed
+func Clean(clipboardDir string) (int, error) {
+	images, err := listImages(clipboardDir)
+	if err != nil {
+		return 0, err
+	}
+
+	removed := 0
+	for _, img := range images {
+		if err := os.Remove(filepath.Join(clipboardDir, img.name)); err != nil && !os.IsNotExist(err) {
+			return removed, err
+		}
+		removed++
+	}
+
+	for _, link := range []string{"latest", "latest.png", "latest.jpg"} {
+		path := filepath.Join(clipboardDir, link)
+		if info, err := os.Lstat(path); err == nil && info.Mode()&os.ModeSymlink != 0 {
+			os.Remove(path)
+		}
+	}
+
+	return removed, nil
+}
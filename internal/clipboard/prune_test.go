@@ -0,0 +1,92 @@
+package clipboard
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+)
+
+func writeImage(t *testing.T, dir string, index int, size int, age time.Duration) string {
+	t.Helper()
+	name := fmt.Sprintf("clipboard-%03d.png", index)
+	path := filepath.Join(dir, name)
+	if err := os.WriteFile(path, make([]byte, size), 0644); err != nil {
+		t.Fatal(err)
+	}
+	modTime := time.Now().Add(-age)
+	if err := os.Chtimes(path, modTime, modTime); err != nil {
+		t.Fatal(err)
+	}
+	return name
+}
+
+func remaining(t *testing.T, dir string) int {
+	t.Helper()
+	images, err := listImages(dir)
+	if err != nil {
+		t.Fatal(err)
+	}
+	return len(images)
+}
+
+func TestPrune(t *testing.T) {
+	tests := []struct {
+		name      string
+		retention Retention
+		want      int
+	}{
+		{"no limits", Retention{}, 5},
+		{"max files", Retention{MaxFiles: 2}, 2},
+		{"max bytes", Retention{MaxBytes: 250}, 2},
+		{"max age", Retention{MaxAge: 150 * time.Minute}, 3},
+		{"newest always kept", Retention{MaxAge: time.Second}, 1},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			dir := t.TempDir()
+			for i := 0; i < 5; i++ {
+				writeImage(t, dir, i, 100, time.Duration(i)*time.Hour+time.Minute)
+			}
+
+			removed, err := Prune(dir, tt.retention)
+			if err != nil {
+				t.Fatalf("Prune() error = %v", err)
+			}
+			if got := remaining(t, dir); got != tt.want {
+				t.Errorf("Prune() left %d images, want %d", got, tt.want)
+			}
+			if removed != 5-tt.want {
+				t.Errorf("Prune() removed = %d, want %d", removed, 5-tt.want)
+			}
+		})
+	}
+}
+
+func TestClean(t *testing.T) {
+	dir := t.TempDir()
+	name := writeImage(t, dir, 0, 10, 0)
+	writeImage(t, dir, 1, 10, time.Hour)
+	if err := os.Symlink(name, filepath.Join(dir, "latest")); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(dir, "notes.txt"), []byte("keep"), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	removed, err := Clean(dir)
+	if err != nil {
+		t.Fatalf("Clean() error = %v", err)
+	}
+	if removed != 2 {
+		t.Errorf("Clean() removed = %d, want 2", removed)
+	}
+	if _, err := os.Lstat(filepath.Join(dir, "latest")); !os.IsNotExist(err) {
+		t.Errorf("Clean() left the latest link behind")
+	}
+	if _, err := os.Stat(filepath.Join(dir, "notes.txt")); err != nil {
+		t.Errorf("Clean() removed an unrelated file: %v", err)
+	}
+}
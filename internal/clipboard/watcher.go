@@ -8,13 +8,12 @@ import (
 	"path/filepath"
 	"strings"
 	"time"
-)
 
-const (
-	maxImages     = 10
-	checkInterval = 500 * time.Millisecond
+	"github.com/thaodangspace/agentsandbox/internal/paths"
 )
 
+const checkInterval = 500 * time.Millisecond
+
 // FeatureEnabled returns whether clipboard integration is currently enabled
 // Currently disabled due to stability issues
 func FeatureEnabled() bool {
@@ -23,11 +22,11 @@ func FeatureEnabled() bool {
 
 // GetClipboardDir returns the clipboard directory path
 func GetClipboardDir() (string, error) {
-	homeDir, err := os.UserHomeDir()
+	cacheDir, err := paths.CacheDir()
 	if err != nil {
 		return "", err
 	}
-	return filepath.Join(homeDir, ".config", "agentsandbox", "clipboard"), nil
+	return filepath.Join(cacheDir, "clipboard"), nil
 }
 
 // EnsureClipboardDir creates the clipboard directory if it doesn't exist
@@ -46,17 +45,12 @@ func EnsureClipboardDir() (string, error) {
 
 // GetWatcherPIDFile returns the path to the watcher PID file
 func GetWatcherPIDFile() (string, error) {
-	homeDir, err := os.UserHomeDir()
+	stateDir, err := paths.EnsureDir(paths.StateDir())
 	if err != nil {
 		return "", err
 	}
 
-	configDir := filepath.Join(homeDir, ".config", "agentsandbox")
-	if err := os.MkdirAll(configDir, 0755); err != nil {
-		return "", err
-	}
-
-	return filepath.Join(configDir, "clipboard_watcher.pid"), nil
+	return filepath.Join(stateDir, "clipboard_watcher.pid"), nil
 }
 
 // SaveWatcherPID saves the watcher process PID
@@ -107,12 +101,6 @@ func ClearWatcherPID() error {
 	return nil
 }
 
-// IsProcessRunning checks if a process is running on Linux
-func IsProcessRunning(pid int) bool {
-	_, err := os.Stat(fmt.Sprintf("/proc/%d", pid))
-	return err == nil
-}
-
 // Watch starts watching the clipboard for images
 // This is a simplified implementation that can be enhanced
 func Watch(clipboardDir string) error {
@@ -193,7 +181,7 @@ func Watch(clipboardDir string) error {
 			lastHash = hash
 
 			// Cleanup old images
-			if err := cleanupOldImages(clipboardDir); err != nil {
+			if _, err := Prune(clipboardDir, LoadRetention()); err != nil {
 				fmt.Printf("Warning: failed to cleanup old images: %v\n", err)
 			}
 		}
@@ -202,87 +190,155 @@ func Watch(clipboardDir string) error {
 	return nil
 }
 
-// cleanupOldImages removes old clipboard images, keeping only maxImages
-func cleanupOldImages(clipboardDir string) error {
-	entries, err := os.ReadDir(clipboardDir)
+// GetWatcherLogFile returns the path of the watcher's output log
+func GetWatcherLogFile() (string, error) {
+	pidFile, err := GetWatcherPIDFile()
 	if err != nil {
+		return "", err
+	}
+	return filepath.Join(filepath.Dir(pidFile), "clipboard_watcher.log"), nil
+}
+
+// StartWatcher starts the clipboard watcher as a background process
+func StartWatcher() error {
+	// Check if already running
+	pid, err := LoadWatcherPID()
+	if err == nil && pid > 0 && IsProcessRunning(pid) {
+		// Already running
+		return nil
+	}
+
+	// Clear stale PID
+	ClearWatcherPID()
+
+	if _, err := EnsureClipboardDir(); err != nil {
 		return err
 	}
 
-	var imageFiles []os.DirEntry
-	for _, entry := range entries {
-		if entry.IsDir() {
-			continue
-		}
+	exe, err := os.Executable()
+	if err != nil {
+		return fmt.Errorf("failed to locate agentsandbox executable: %w", err)
+	}
 
-		name := entry.Name()
-		if strings.HasPrefix(name, "clipboard-") &&
-			(strings.HasSuffix(name, ".png") || strings.HasSuffix(name, ".jpg") || strings.HasSuffix(name, ".jpeg")) {
-			imageFiles = append(imageFiles, entry)
-		}
+	logPath, err := GetWatcherLogFile()
+	if err != nil {
+		return err
 	}
+	logFile, err := os.OpenFile(logPath, os.O_CREATE|os.O_WRONLY|os.O_APPEND, 0644)
+	if err != nil {
+		return fmt.Errorf("failed to open watcher log: %w", err)
+	}
+	defer logFile.Close()
 
-	if len(imageFiles) <= maxImages {
-		return nil
+	cmd := exec.Command(exe, "clipboard", "watch")
+	cmd.Stdout = logFile
+	cmd.Stderr = logFile
+	detach(cmd)
+
+	if err := cmd.Start(); err != nil {
+		return fmt.Errorf("failed to start clipboard watcher: %w", err)
 	}
 
-	// Sort by modification time (oldest first)
-	type fileInfo struct {
-		name    string
-		modTime time.Time
+	if err := SaveWatcherPID(cmd.Process.Pid); err != nil {
+		return err
 	}
+	return cmd.Process.Release()
+}
 
-	var files []fileInfo
-	for _, entry := range imageFiles {
-		info, err := entry.Info()
-		if err != nil {
-			continue
-		}
-		files = append(files, fileInfo{name: entry.Name(), modTime: info.ModTime()})
+// StopWatcher terminates the background watcher and clears its PID file.
+// It returns false when no watcher was running.
+func StopWatcher() (bool, error) {
+	pid, err := LoadWatcherPID()
+	if err != nil {
+		return false, err
 	}
 
-	// Sort oldest first
-	for i := 0; i < len(files)-1; i++ {
-		for j := i + 1; j < len(files); j++ {
-			if files[i].modTime.After(files[j].modTime) {
-				files[i], files[j] = files[j], files[i]
-			}
-		}
+	if pid <= 0 || !IsProcessRunning(pid) {
+		return false, ClearWatcherPID()
 	}
 
-	// Delete oldest files
-	toDelete := len(files) - maxImages
-	for i := 0; i < toDelete; i++ {
-		filePath := filepath.Join(clipboardDir, files[i].name)
-		if err := os.Remove(filePath); err != nil && !os.IsNotExist(err) {
-			return err
+	process, err := os.FindProcess(pid)
+	if err != nil {
+		return false, err
+	}
+	if err := terminate(process); err != nil {
+		return false, fmt.Errorf("failed to stop watcher (PID %d): %w", pid, err)
+	}
+
+	// Give the watcher a moment to exit before forcing it
+	deadline := time.Now().Add(3 * time.Second)
+	for IsProcessRunning(pid) && time.Now().Before(deadline) {
+		time.Sleep(100 * time.Millisecond)
+	}
+	if IsProcessRunning(pid) {
+		if err := process.Kill(); err != nil {
+			return false, fmt.Errorf("failed to kill watcher (PID %d): %w", pid, err)
 		}
 	}
 
-	return nil
+	return true, ClearWatcherPID()
 }
 
-// StartWatcher starts the clipboard watcher as a background process
-func StartWatcher() error {
-	// Check if already running
+// RestartWatcher stops any running watcher and starts a fresh one
+func RestartWatcher() error {
+	if _, err := StopWatcher(); err != nil {
+		return err
+	}
+	return StartWatcher()
+}
+
+// Status describes the state of the clipboard watcher and its directory
+type Status struct {
+	Running     bool
+	PID         int
+	StalePID    bool
+	Dir         string
+	LastCapture time.Time
+	Files       int
+	Size        int64
+}
+
+// GetStatus reports whether the watcher is alive along with clipboard directory statistics
+func GetStatus() (Status, error) {
+	var status Status
+
 	pid, err := LoadWatcherPID()
-	if err == nil && pid > 0 && IsProcessRunning(pid) {
-		// Already running
-		return nil
+	if err != nil {
+		return status, err
 	}
+	status.PID = pid
+	status.Running = pid > 0 && IsProcessRunning(pid)
+	status.StalePID = pid > 0 && !status.Running
 
-	// Clear stale PID
-	ClearWatcherPID()
+	dir, err := GetClipboardDir()
+	if err != nil {
+		return status, err
+	}
+	status.Dir = dir
 
-	_, err = EnsureClipboardDir()
+	entries, err := os.ReadDir(dir)
 	if err != nil {
-		return err
+		if os.IsNotExist(err) {
+			return status, nil
+		}
+		return status, err
 	}
 
-	// Start watcher in background (would need to be a separate binary or goroutine)
-	// For now, this is a placeholder
-	fmt.Println("Clipboard watcher functionality available but currently disabled")
+	for _, entry := range entries {
+		if entry.IsDir() || entry.Type()&os.ModeSymlink != 0 {
+			continue
+		}
+		info, err := entry.Info()
+		if err != nil {
+			continue
+		}
 
-	return nil
-}
+		status.Files++
+		status.Size += info.Size()
+		if strings.HasPrefix(entry.Name(), "clipboard-") && info.ModTime().After(status.LastCapture) {
+			status.LastCapture = info.ModTime()
+		}
+	}
 
+	return status, nil
+}
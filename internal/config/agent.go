@@ -34,6 +34,16 @@ func (a Agent) Command() string {
 	}
 }
 
+// HeadlessArgs returns the command line that runs a single prompt non-interactively
+func (a Agent) HeadlessArgs(prompt string) []string {
+	switch a {
+	case AgentCodex:
+		return []string{a.Command(), "exec", prompt}
+	default:
+		return []string{a.Command(), "-p", prompt}
+	}
+}
+
 // CacheArg returns the environment variable name for cache busting
 func (a Agent) CacheArg() string {
 	switch a {
@@ -70,6 +80,56 @@ func (a Agent) DisplayName() string {
 	}
 }
 
+// AuthPaths returns home-relative paths whose presence indicates the agent is logged in
+func (a Agent) AuthPaths() []string {
+	switch a {
+	case AgentClaude:
+		return []string{".claude/.credentials.json", ".claude.json"}
+	case AgentGemini:
+		return []string{".gemini/oauth_creds.json", ".gemini/.env"}
+	case AgentCodex:
+		return []string{".codex/auth.json"}
+	case AgentQwen:
+		return []string{".qwen/oauth_creds.json", ".qwen/.env"}
+	case AgentCursor:
+		return []string{".cursor/cli-config.json", ".config/cursor/auth.json"}
+	default:
+		return []string{}
+	}
+}
+
+// APIKeyEnvs returns environment variables that authenticate the agent without a login
+func (a Agent) APIKeyEnvs() []string {
+	switch a {
+	case AgentClaude:
+		return []string{"ANTHROPIC_API_KEY"}
+	case AgentGemini:
+		return []string{"GEMINI_API_KEY", "GOOGLE_API_KEY"}
+	case AgentCodex:
+		return []string{"OPENAI_API_KEY"}
+	case AgentQwen:
+		return []string{"DASHSCOPE_API_KEY", "OPENAI_API_KEY"}
+	case AgentCursor:
+		return []string{"CURSOR_API_KEY"}
+	default:
+		return []string{}
+	}
+}
+
+// LoginCommand returns the command that starts the agent's interactive login flow
+func (a Agent) LoginCommand() string {
+	switch a {
+	case AgentClaude:
+		return "claude /login"
+	case AgentCodex:
+		return "codex login"
+	case AgentCursor:
+		return "cursor-agent login"
+	default:
+		return a.Command()
+	}
+}
+
 // String implements the Stringer interface
 func (a Agent) String() string {
 	return a.DisplayName()
@@ -0,0 +1,88 @@
+package config
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+)
+
+// ProjectDirName is the per-project directory holding agentsandbox customizations
+const ProjectDirName = ".agentsandbox"
+
+// ProjectDir returns the per-project configuration directory for a project root
+func ProjectDir(projectDir string) string {
+	return filepath.Join(projectDir, ProjectDirName)
+}
+
+// ProjectDockerfilePath returns the path of the project's custom Dockerfile
+func ProjectDockerfilePath(projectDir string) string {
+	return filepath.Join(ProjectDir(projectDir), "Dockerfile")
+}
+
+// LoadProjectDockerfile returns the project's custom Dockerfile, or an empty string if none exists
+func LoadProjectDockerfile(projectDir string) (string, error) {
+	data, err := os.ReadFile(ProjectDockerfilePath(projectDir))
+	if err != nil {
+		if os.IsNotExist(err) {
+			return "", nil
+		}
+		return "", err
+	}
+	return string(data), nil
+}
+
+// ProjectSettings holds per-project overrides of the global settings
+type ProjectSettings struct {
+	// EnvFiles replaces the global env_files list when set; an empty list disables masking
+	EnvFiles []string `json:"env_files" mapstructure:"env_files"`
+	// DB provisions a database sidecar and brings its schema up to date on creation
+	DB *DBSettings `json:"db,omitempty" mapstructure:"db"`
+	// BenchCommand is the command `agentsandbox bench` times, e.g. "cargo build"
+	BenchCommand string `json:"bench_command,omitempty" mapstructure:"bench_command"`
+}
+
+// DBSettings describes the project's development database. Empty commands fall back
+// to those of the detected migration tool.
+type DBSettings struct {
+	// Engine is "postgres" (default) or "mysql"
+	Engine string `json:"engine,omitempty" mapstructure:"engine"`
+	// Image overrides the engine's default image, e.g. "postgis/postgis:16-3.4"
+	Image string `json:"image,omitempty" mapstructure:"image"`
+	// Name is the database to create; defaults to "app"
+	Name    string `json:"name,omitempty" mapstructure:"name"`
+	Migrate string `json:"migrate,omitempty" mapstructure:"migrate"`
+	Seed    string `json:"seed,omitempty" mapstructure:"seed"`
+}
+
+// ProjectSettingsPath returns the path of the project's settings file
+func ProjectSettingsPath(projectDir string) string {
+	return filepath.Join(ProjectDir(projectDir), "settings.json")
+}
+
+// LoadProjectSettings reads the project's settings, returning empty settings if none exist
+func LoadProjectSettings(projectDir string) (*ProjectSettings, error) {
+	settings := &ProjectSettings{}
+
+	data, err := os.ReadFile(ProjectSettingsPath(projectDir))
+	if err != nil {
+		if os.IsNotExist(err) {
+			return settings, nil
+		}
+		return settings, err
+	}
+
+	if err := json.Unmarshal(data, settings); err != nil {
+		return &ProjectSettings{}, fmt.Errorf("invalid %s: %w", ProjectSettingsPath(projectDir), err)
+	}
+	return settings, nil
+}
+
+// EnvFilePatterns returns the env file patterns to mask for a project,
+// preferring the project's own list over the global one
+func EnvFilePatterns(settings *Settings, project *ProjectSettings) []string {
+	if project != nil && project.EnvFiles != nil {
+		return project.EnvFiles
+	}
+	return settings.EnvFiles
+}
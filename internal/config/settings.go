@@ -6,12 +6,199 @@ import (
 	"path/filepath"
 
 	"github.com/spf13/viper"
+	"github.com/thaodangspace/agentsandbox/internal/paths"
+	"github.com/thaodangspace/agentsandbox/internal/secrets"
 )
 
 // Settings represents the application settings
 type Settings struct {
-	SkipPermissionFlags  map[string]string `json:"skip_permission_flags" mapstructure:"skip_permission_flags"`
-	EnvFiles             []string          `json:"env_files" mapstructure:"env_files"`
+	SkipPermissionFlags map[string]string   `json:"skip_permission_flags" mapstructure:"skip_permission_flags"`
+	EnvFiles            []string            `json:"env_files" mapstructure:"env_files"`
+	SandboxEnv          map[string]string   `json:"sandbox_env,omitempty" mapstructure:"sandbox_env"`
+	SecretScan          string              `json:"secret_scan,omitempty" mapstructure:"secret_scan"`
+	ApprovalGate        ApprovalGate        `json:"approval_gate" mapstructure:"approval_gate"`
+	Session             SessionSettings     `json:"session" mapstructure:"session"`
+	Watchdog            WatchdogSettings    `json:"watchdog" mapstructure:"watchdog"`
+	TagCommits          bool                `json:"tag_commits" mapstructure:"tag_commits"`
+	ProtectedBranches   []string            `json:"protected_branches" mapstructure:"protected_branches"`
+	AlwaysWorktree      bool                `json:"always_worktree" mapstructure:"always_worktree"`
+	CrashReports        CrashReportSettings `json:"crash_reports" mapstructure:"crash_reports"`
+	Platform            string              `json:"platform,omitempty" mapstructure:"platform"`
+	BaseImage           string              `json:"base_image,omitempty" mapstructure:"base_image"`
+	RegistryCache       RegistryCache       `json:"registry_cache" mapstructure:"registry_cache"`
+	Clipboard           ClipboardSettings   `json:"clipboard" mapstructure:"clipboard"`
+	Maintenance         MaintenanceSettings `json:"maintenance" mapstructure:"maintenance"`
+	Redaction           RedactionSettings   `json:"redaction" mapstructure:"redaction"`
+	Container           ContainerSettings   `json:"container" mapstructure:"container"`
+	// Output is "plain" to print without emoji, box drawing or colour
+	Output string `json:"output,omitempty" mapstructure:"output"`
+	// DefaultAgent is started when --agent is not given; empty asks which agent to use
+	DefaultAgent string `json:"default_agent,omitempty" mapstructure:"default_agent"`
+	// Language selects the message language ("en" or "vi"); empty follows LC_ALL, LC_MESSAGES and LANG
+	Language string `json:"language,omitempty" mapstructure:"language"`
+	// LogEncryption encrypts session and audit logs with a key kept in the OS keyring
+	LogEncryption bool `json:"log_encryption" mapstructure:"log_encryption"`
+	// WorkspaceSnapshots commits the project's working tree to a hidden ref before each
+	// session so `agentsandbox rollback` can undo the agent's edits
+	WorkspaceSnapshots bool `json:"workspace_snapshots" mapstructure:"workspace_snapshots"`
+	// GitProtection mounts parts of the project's .git read-only: "hooks" or "readonly"; empty or "off" leaves it writable
+	GitProtection string `json:"git_protection,omitempty" mapstructure:"git_protection"`
+	// CredentialProxy keeps API keys on the host and authenticates agent requests through a local proxy
+	CredentialProxy bool `json:"credential_proxy" mapstructure:"credential_proxy"`
+	// DNSPolicy filters the sandbox's DNS lookups through a dnsmasq sidecar
+	DNSPolicy DNSPolicySettings `json:"dns_policy" mapstructure:"dns_policy"`
+	// NetworkLog sends sessions' HTTP(S) traffic through a host-side proxy that records the hosts contacted
+	NetworkLog bool `json:"network_log" mapstructure:"network_log"`
+	// GitHubApp mints sandbox tokens scoped to the project's repository
+	GitHubApp GitHubAppSettings `json:"github_app" mapstructure:"github_app"`
+	// BrowserTesting adds Chromium and its libraries to the image: "auto" (default) when the
+	// project uses Playwright or Puppeteer, "on" for every project, or "off"
+	BrowserTesting string `json:"browser_testing,omitempty" mapstructure:"browser_testing"`
+	// Sudo is "full" (default), "none", or "targeted" to allow only SudoCommands
+	Sudo string `json:"sudo,omitempty" mapstructure:"sudo"`
+	// SudoCommands are absolute paths; targeted mode defaults to apt-get
+	SudoCommands []string `json:"sudo_commands,omitempty" mapstructure:"sudo_commands"`
+	// Kubernetes installs cluster tooling and optionally shares one kubeconfig context
+	Kubernetes KubernetesSettings `json:"kubernetes" mapstructure:"kubernetes"`
+	// CloudCredentials gives each session short-lived cloud credentials minted on the host
+	CloudCredentials CloudCredentialSettings `json:"cloud_credentials" mapstructure:"cloud_credentials"`
+	// MountStrategy is "auto" (default: sync on macOS when mutagen is installed), "bind" or "sync"
+	MountStrategy string `json:"mount_strategy,omitempty" mapstructure:"mount_strategy"`
+	// MountConsistency is added to bind mounts: "consistent", "cached" or "delegated"
+	MountConsistency string `json:"mount_consistency,omitempty" mapstructure:"mount_consistency"`
+}
+
+// CloudCredentialSettings selects the cloud identities sessions act as. Credentials
+// expire shortly after the session's time limit, or after an hour without one.
+type CloudCredentialSettings struct {
+	AWS AWSCredentialSettings `json:"aws" mapstructure:"aws"`
+	GCP GCPCredentialSettings `json:"gcp" mapstructure:"gcp"`
+}
+
+// AWSCredentialSettings names the role assumed for each session. An empty RoleARN
+// disables AWS credentials.
+type AWSCredentialSettings struct {
+	RoleARN string `json:"role_arn,omitempty" mapstructure:"role_arn"`
+	// Profile is the host profile used to assume the role; empty uses the default chain
+	Profile string `json:"profile,omitempty" mapstructure:"profile"`
+	Region  string `json:"region,omitempty" mapstructure:"region"`
+}
+
+// GCPCredentialSettings names the service account impersonated for each session. An
+// empty ServiceAccount disables GCP credentials.
+type GCPCredentialSettings struct {
+	ServiceAccount string `json:"service_account,omitempty" mapstructure:"service_account"`
+	Project        string `json:"project,omitempty" mapstructure:"project"`
+}
+
+// KubernetesSettings adds kubectl, helm and kind to the image. With Context set, a
+// kubeconfig holding only that context is mounted read-only.
+type KubernetesSettings struct {
+	Tools bool `json:"tools" mapstructure:"tools"`
+	// Context is the kubeconfig context the sandbox may use; empty shares no cluster
+	Context string `json:"context,omitempty" mapstructure:"context"`
+	// Kubeconfig is the host kubeconfig to read the context from; empty uses kubectl's default
+	Kubeconfig string `json:"kubeconfig,omitempty" mapstructure:"kubeconfig"`
+}
+
+// DNSPolicySettings lists the domains a sandbox may or may not resolve. Domains
+// include their subdomains.
+type DNSPolicySettings struct {
+	// Mode is "allow" to resolve only Domains or "deny" to block them; empty turns the policy off
+	Mode    string   `json:"mode,omitempty" mapstructure:"mode"`
+	Domains []string `json:"domains" mapstructure:"domains"`
+	// Upstream are the resolvers queries are forwarded to; empty uses the host's
+	Upstream []string `json:"upstream,omitempty" mapstructure:"upstream"`
+}
+
+// GitHubAppSettings identifies a GitHub App installed on the repositories agents work on.
+// A zero AppID disables scoped tokens.
+type GitHubAppSettings struct {
+	AppID int64 `json:"app_id" mapstructure:"app_id"`
+	// PrivateKey is the path to the app's PEM private key
+	PrivateKey string `json:"private_key,omitempty" mapstructure:"private_key"`
+}
+
+// ClipboardSettings controls how long captured clipboard images are kept.
+// A zero value disables the corresponding limit.
+type ClipboardSettings struct {
+	MaxFiles   int `json:"max_files" mapstructure:"max_files"`
+	MaxSizeMB  int `json:"max_size_mb" mapstructure:"max_size_mb"`
+	MaxAgeDays int `json:"max_age_days" mapstructure:"max_age_days"`
+}
+
+// MaintenanceSettings controls the housekeeping done by `agentsandbox daemon`.
+// A zero value disables the corresponding task.
+type MaintenanceSettings struct {
+	// Background leaves all housekeeping to the daemon instead of running it on every start
+	Background       bool `json:"background" mapstructure:"background"`
+	LogRetentionDays int  `json:"log_retention_days" mapstructure:"log_retention_days"`
+	IdleStopMinutes  int  `json:"idle_stop_minutes" mapstructure:"idle_stop_minutes"`
+	ImageGC          bool `json:"image_gc" mapstructure:"image_gc"`
+	// StateGC prunes state and archives logs of containers that no longer exist
+	StateGC bool `json:"state_gc" mapstructure:"state_gc"`
+}
+
+// ContainerSettings controls how docker runs sandbox containers
+type ContainerSettings struct {
+	// Restart is a docker restart policy such as "unless-stopped"; empty means never restart
+	Restart string `json:"restart,omitempty" mapstructure:"restart"`
+	// HealthCheck probes new containers periodically so wedged sandboxes show as unhealthy
+	HealthCheck bool `json:"health_check" mapstructure:"health_check"`
+	// Ulimits maps docker ulimit names such as nofile, nproc and fsize to "soft" or "soft:hard"
+	Ulimits map[string]string `json:"ulimits,omitempty" mapstructure:"ulimits"`
+	// Bandwidth caps the sandbox's network traffic
+	Bandwidth BandwidthSettings `json:"bandwidth" mapstructure:"bandwidth"`
+}
+
+// BandwidthSettings are tc rates such as "20mbit"; empty leaves that direction unlimited
+type BandwidthSettings struct {
+	Download string `json:"download,omitempty" mapstructure:"download"`
+	Upload   string `json:"upload,omitempty" mapstructure:"upload"`
+}
+
+// RedactionSettings controls removal of credentials from session logs and exports
+type RedactionSettings struct {
+	Enabled bool `json:"enabled" mapstructure:"enabled"`
+	// Patterns are extra regular expressions to redact, in addition to the built-in ones
+	Patterns []string `json:"patterns" mapstructure:"patterns"`
+}
+
+// SessionSettings limits attached agent sessions
+type SessionSettings struct {
+	// MaxDuration is a Go duration such as "45m"; empty means no limit
+	MaxDuration   string `json:"max_duration,omitempty" mapstructure:"max_duration"`
+	StopOnTimeout bool   `json:"stop_on_timeout" mapstructure:"stop_on_timeout"`
+}
+
+// WatchdogSettings controls detection of stuck agent sessions. Zero disables a check.
+type WatchdogSettings struct {
+	IdleMinutes     int `json:"idle_minutes" mapstructure:"idle_minutes"`
+	RepeatThreshold int `json:"repeat_threshold" mapstructure:"repeat_threshold"`
+	// Action is "notify" (default) or "interrupt" to also send the agent SIGINT
+	Action string `json:"action,omitempty" mapstructure:"action"`
+}
+
+// ApprovalGate holds commands matching Patterns inside the sandbox until they are approved on the host
+type ApprovalGate struct {
+	Enabled bool `json:"enabled" mapstructure:"enabled"`
+	// Patterns are extended regular expressions matched against each command
+	Patterns       []string `json:"patterns" mapstructure:"patterns"`
+	TimeoutSeconds int      `json:"timeout_seconds" mapstructure:"timeout_seconds"`
+}
+
+// RegistryCache configures a registry used to share image build cache between machines
+type RegistryCache struct {
+	// Ref is the repository holding cache images, e.g. ghcr.io/acme/agentsandbox-cache
+	Ref string `json:"ref,omitempty" mapstructure:"ref"`
+	// Push uploads freshly built layers so other machines can reuse them
+	Push bool `json:"push" mapstructure:"push"`
+}
+
+// CrashReportSettings controls the opt-in crash reporter
+type CrashReportSettings struct {
+	Enabled  bool   `json:"enabled" mapstructure:"enabled"`
+	Endpoint string `json:"endpoint,omitempty" mapstructure:"endpoint"`
 }
 
 // DefaultSettings returns the default settings
@@ -31,17 +218,55 @@ func DefaultSettings() *Settings {
 			".env.test.local",
 			".env.production.local",
 		},
+		ProtectedBranches: []string{"main", "master", "release/*"},
+		Clipboard: ClipboardSettings{
+			MaxFiles:   10,
+			MaxSizeMB:  100,
+			MaxAgeDays: 7,
+		},
+		Maintenance: MaintenanceSettings{
+			LogRetentionDays: 30,
+			StateGC:          true,
+		},
+		ApprovalGate: ApprovalGate{
+			Patterns: []string{
+				`(^|[;&|[:space:]])rm[[:space:]]+-[[:alpha:]]*(rf|fr|Rf|fR)`,
+				`(^|[;&|[:space:]])git[[:space:]]+push`,
+				`(curl|wget)[^|]*\|[[:space:]]*(sudo[[:space:]]+)?(ba|z)?sh`,
+				`(^|[;&|[:space:]])docker[[:space:]]`,
+				`(^|[;&|[:space:]])kubectl[[:space:]]`,
+			},
+			TimeoutSeconds: 300,
+		},
+		Watchdog: WatchdogSettings{
+			IdleMinutes:     15,
+			RepeatThreshold: 50,
+			Action:          "notify",
+		},
+		Redaction: RedactionSettings{
+			Enabled: true,
+		},
+		Container: ContainerSettings{
+			HealthCheck: true,
+		},
 	}
 }
 
+// Redactor returns the filter applied to session logs, or nil when redaction is disabled
+func (s *Settings) Redactor() (*secrets.Redactor, error) {
+	if !s.Redaction.Enabled {
+		return nil, nil
+	}
+	return secrets.NewRedactor(s.Redaction.Patterns)
+}
+
 // LoadSettings loads settings from the configuration file
 func LoadSettings() (*Settings, error) {
-	homeDir, err := os.UserHomeDir()
+	configDir, err := paths.ConfigDir()
 	if err != nil {
 		return DefaultSettings(), nil
 	}
 
-	configDir := filepath.Join(homeDir, ".config", "agentsandbox")
 	configFile := filepath.Join(configDir, "settings.json")
 
 	// Check if config file exists
@@ -65,16 +290,11 @@ func LoadSettings() (*Settings, error) {
 
 // Save saves the settings to the configuration file
 func (s *Settings) Save() error {
-	homeDir, err := os.UserHomeDir()
+	configDir, err := paths.EnsureDir(paths.ConfigDir())
 	if err != nil {
 		return err
 	}
 
-	configDir := filepath.Join(homeDir, ".config", "agentsandbox")
-	if err := os.MkdirAll(configDir, 0755); err != nil {
-		return err
-	}
-
 	configFile := filepath.Join(configDir, "settings.json")
 	data, err := json.MarshalIndent(s, "", "    ")
 	if err != nil {
@@ -86,11 +306,7 @@ func (s *Settings) Save() error {
 
 // GetConfigDir returns the application configuration directory
 func GetConfigDir() (string, error) {
-	homeDir, err := os.UserHomeDir()
-	if err != nil {
-		return "", err
-	}
-	return filepath.Join(homeDir, ".config", "agentsandbox"), nil
+	return paths.ConfigDir()
 }
 
 // GetClaudeConfigDir finds the Claude configuration directory
@@ -125,12 +341,11 @@ func GetClaudeConfigDir() string {
 
 // SetupViper configures viper for settings management
 func SetupViper() error {
-	homeDir, err := os.UserHomeDir()
+	configDir, err := paths.ConfigDir()
 	if err != nil {
 		return err
 	}
 
-	configDir := filepath.Join(homeDir, ".config", "agentsandbox")
 	viper.SetConfigName("settings")
 	viper.SetConfigType("json")
 	viper.AddConfigPath(configDir)
@@ -139,6 +354,7 @@ func SetupViper() error {
 	defaults := DefaultSettings()
 	viper.SetDefault("skip_permission_flags", defaults.SkipPermissionFlags)
 	viper.SetDefault("env_files", defaults.EnvFiles)
+	viper.SetDefault("clipboard", defaults.Clipboard)
 
 	// Read config (ignore error if file doesn't exist)
 	_ = viper.ReadInConfig()
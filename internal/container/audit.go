@@ -0,0 +1,133 @@
+package container
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"path"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/logs"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+const (
+	// auditHookPath is sourced by every bash started in an audited session
+	auditHookPath = "/etc/agentsandbox/audit.sh"
+	// auditDir holds in-progress audit logs inside the container
+	auditDir = "/tmp/agentsandbox-audit"
+)
+
+// auditHook records each command bash runs as a JSON line and, when the approval gate
+// is enabled, holds matching commands until the host approves them. It is loaded through
+// BASH_ENV for non-interactive shells (agent tool calls) and /etc/profile.d for login shells.
+const auditHook = `# agentsandbox execution audit hook
+if [ -n "${BASH_VERSION:-}" ] && [ -z "${__agentsandbox_hook:-}" ] && [ -n "${AGENTSANDBOX_AUDIT_LOG:-}${AGENTSANDBOX_GATE:-}" ]; then
+    __agentsandbox_hook=1
+    # Sets __agentsandbox_json to $1 escaped for a JSON string; control characters
+    # other than newline, tab and carriage return become \u00XX
+    __agentsandbox_json_escape() {
+        local s=$1 i hex c
+        s=${s//\\/\\\\}; s=${s//\"/\\\"}; s=${s//$'\n'/\\n}; s=${s//$'\t'/\\t}; s=${s//$'\r'/\\r}
+        if [[ $s == *[[:cntrl:]]* ]]; then
+            for ((i = 1; i < 32; i++)); do
+                printf -v hex '%02x' "$i"
+                printf -v c "\\x$hex"
+                s=${s//"$c"/\\u00$hex}
+            done
+        fi
+        __agentsandbox_json=$s
+    }
+    __agentsandbox_audit_log() {
+        [ -n "${AGENTSANDBOX_AUDIT_LOG:-}" ] || return 0
+        local cmd cwd ts
+        __agentsandbox_json_escape "$BASH_COMMAND"; cmd=$__agentsandbox_json
+        __agentsandbox_json_escape "$PWD"; cwd=$__agentsandbox_json
+        printf -v ts '%(%Y-%m-%dT%H:%M:%S%z)T' -1
+        printf '{"timestamp":"%s","pid":%d,"ppid":%d,"cwd":"%s","command":"%s"}\n' \
+            "$ts" "$$" "$PPID" "$cwd" "$cmd" >> "$AGENTSANDBOX_AUDIT_LOG" 2>/dev/null
+        return 0
+    }
+` + gateHook + `
+    __agentsandbox_hook_run() {
+        case "$BASH_COMMAND" in __agentsandbox*) return 0 ;; esac
+        __agentsandbox_audit_log
+        __agentsandbox_gate_check
+    }
+    trap '__agentsandbox_hook_run' DEBUG
+fi
+`
+
+// auditSession identifies the audit log of a single attach
+type auditSession struct {
+	ID            string
+	ContainerPath string
+}
+
+// newAuditSession returns a session named after the current time
+func newAuditSession() auditSession {
+	id := time.Now().Format("20060102-150405")
+	return auditSession{
+		ID:            id,
+		ContainerPath: path.Join(auditDir, fmt.Sprintf("audit-%s.jsonl", id)),
+	}
+}
+
+// Env returns the docker exec arguments that enable the audit hook
+func (s auditSession) Env() []string {
+	return []string{
+		"-e", "BASH_ENV=" + auditHookPath,
+		"-e", "AGENTSANDBOX_AUDIT_LOG=" + s.ContainerPath,
+	}
+}
+
+// installAuditHook writes the audit hook into the container; it is idempotent so
+// containers created by older versions pick it up on their next attach
+func installAuditHook(containerName string) error {
+	script := fmt.Sprintf("mkdir -p %[1]s %[2]s && chmod 1777 %[2]s && cat > %[3]s && ln -sf %[3]s /etc/profile.d/agentsandbox-audit.sh",
+		path.Dir(auditHookPath), auditDir, auditHookPath)
+	cmd := exec.Command("docker", "exec", "-i", "--user", "root", containerName, "sh", "-c", script)
+	cmd.Stdin = strings.NewReader(auditHook)
+	if output, err := cmd.CombinedOutput(); err != nil {
+		return fmt.Errorf("%w\nOutput: %s", err, string(output))
+	}
+	return nil
+}
+
+// collectAuditLog copies the session's audit log to the host logs directory and returns its path
+func collectAuditLog(containerName, currentDir string, session auditSession) (string, error) {
+	logsDir, err := state.GetLogsDir(containerName, currentDir)
+	if err != nil {
+		return "", err
+	}
+
+	hostPath := filepath.Join(logsDir, path.Base(session.ContainerPath))
+	output, err := exec.Command("docker", "cp", fmt.Sprintf("%s:%s", containerName, session.ContainerPath), hostPath).CombinedOutput()
+	if err != nil {
+		if strings.Contains(string(output), "Could not find the file") || strings.Contains(string(output), "No such") {
+			// Nothing was executed during the session
+			return "", nil
+		}
+		return "", fmt.Errorf("failed to copy audit log: %w\nOutput: %s", err, string(output))
+	}
+
+	exec.Command("docker", "exec", "--user", "root", containerName, "rm", "-f", session.ContainerPath).Run()
+	if err := logs.EncryptFile(hostPath); err != nil {
+		return hostPath, fmt.Errorf("failed to encrypt audit log: %w", err)
+	}
+	return hostPath, nil
+}
+
+// finishAuditSession collects the audit log and reports where it was stored
+func finishAuditSession(containerName, currentDir string, session auditSession) {
+	hostPath, err := collectAuditLog(containerName, currentDir, session)
+	if err != nil {
+		fmt.Fprintf(os.Stderr, "Warning: %v\n", err)
+		return
+	}
+	if hostPath != "" {
+		fmt.Printf("Audit log saved: agentsandbox logs audit %s\n", session.ID)
+	}
+}
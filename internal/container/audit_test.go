@@ -0,0 +1,40 @@
+package container
+
+import (
+	"os"
+	"os/exec"
+	"path/filepath"
+	"testing"
+
+	"github.com/thaodangspace/agentsandbox/internal/logs"
+)
+
+func TestAuditHookEscapesControlCharacters(t *testing.T) {
+	if _, err := exec.LookPath("bash"); err != nil {
+		t.Skip("bash not installed")
+	}
+	dir := t.TempDir()
+	hook := filepath.Join(dir, "audit.sh")
+	if err := os.WriteFile(hook, []byte(auditHook), 0644); err != nil {
+		t.Fatal(err)
+	}
+	auditFile := filepath.Join(dir, "audit-test.jsonl")
+
+	// Raw control characters in the command text, as an agent could send to hide a command
+	cmd := exec.Command("bash", "-c", "echo '\x1b[2K\x07hidden\x1f' \"quoted\\\\\" >/dev/null")
+	cmd.Env = append(os.Environ(), "BASH_ENV="+hook, "AGENTSANDBOX_AUDIT_LOG="+auditFile)
+	if output, err := cmd.CombinedOutput(); err != nil {
+		t.Fatalf("bash: %v\n%s", err, output)
+	}
+
+	entries, err := logs.ParseAuditLog(auditFile)
+	if err != nil {
+		t.Fatalf("ParseAuditLog() error = %v", err)
+	}
+	if len(entries) != 1 || entries[0].Malformed != "" {
+		t.Fatalf("ParseAuditLog() = %+v, want one well-formed entry", entries)
+	}
+	if want := "echo '\x1b[2K\x07hidden\x1f' \"quoted\\\\\" > /dev/null"; entries[0].Command != want {
+		t.Errorf("Command = %q, want %q", entries[0].Command, want)
+	}
+}
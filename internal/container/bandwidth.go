@@ -0,0 +1,73 @@
+package container
+
+import (
+	"fmt"
+	"os/exec"
+	"regexp"
+	"strings"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+var ratePattern = regexp.MustCompile(`^[0-9]+(\.[0-9]+)?([kmgt]?bit|[kmgt]?bps)$`)
+
+// bandwidthScript returns the tc commands that cap traffic on every interface of the
+// container. Uploads are shaped with a token bucket; downloads can only be policed,
+// which drops excess packets so TCP senders slow down.
+func bandwidthScript(limits config.BandwidthSettings) (string, error) {
+	for _, rate := range []string{limits.Download, limits.Upload} {
+		if rate != "" && !ratePattern.MatchString(strings.ToLower(rate)) {
+			return "", fmt.Errorf("invalid bandwidth rate %q: use a tc rate such as 20mbit or 500kbit", rate)
+		}
+	}
+
+	var steps []string
+	if limits.Upload != "" {
+		steps = append(steps, fmt.Sprintf(`tc qdisc replace dev "$dev" root tbf rate %s burst 64kb latency 400ms`, limits.Upload))
+	}
+	if limits.Download != "" {
+		steps = append(steps,
+			`tc qdisc del dev "$dev" ingress 2>/dev/null || true`,
+			`tc qdisc add dev "$dev" handle ffff: ingress`,
+			fmt.Sprintf(`tc filter add dev "$dev" parent ffff: protocol all u32 match u32 0 0 police rate %s burst 256k drop flowid :1`, limits.Download),
+		)
+	}
+	if len(steps) == 0 {
+		return "", nil
+	}
+	return fmt.Sprintf(`set -e
+command -v tc >/dev/null || { echo "tc is not installed; remove the agentsandbox images to rebuild with iproute2" >&2; exit 1; }
+for dev in $(ls /sys/class/net); do
+  [ "$dev" = lo ] && continue
+  %s
+done`, strings.Join(steps, "\n  ")), nil
+}
+
+// limitBandwidth applies the bandwidth setting to a running container. tc needs
+// CAP_NET_ADMIN, which only this privileged exec gets, so the agent cannot lift the limit.
+func limitBandwidth(containerName string) {
+	settings, _ := config.LoadSettings()
+	limits := settings.Container.Bandwidth
+	script, err := bandwidthScript(limits)
+	if err != nil {
+		fmt.Printf("Warning: %v\n", err)
+		return
+	}
+	if script == "" {
+		return
+	}
+
+	output, err := exec.Command("docker", "exec", "--privileged", "--user", "root", containerName, "sh", "-c", script).CombinedOutput()
+	if err != nil {
+		fmt.Printf("Warning: failed to limit bandwidth: %v\n%s", err, string(output))
+		return
+	}
+	fmt.Printf("Limiting bandwidth (download %s, upload %s)\n", rateOrUnlimited(limits.Download), rateOrUnlimited(limits.Upload))
+}
+
+func rateOrUnlimited(rate string) string {
+	if rate == "" {
+		return "unlimited"
+	}
+	return rate
+}
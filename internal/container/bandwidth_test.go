@@ -0,0 +1,36 @@
+package container
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+func TestBandwidthScript(t *testing.T) {
+	script, err := bandwidthScript(config.BandwidthSettings{})
+	if err != nil || script != "" {
+		t.Errorf("bandwidthScript() without limits = %q, %v; want nothing", script, err)
+	}
+
+	script, err = bandwidthScript(config.BandwidthSettings{Download: "20mbit", Upload: "5mbit"})
+	if err != nil {
+		t.Fatal(err)
+	}
+	for _, want := range []string{"root tbf rate 5mbit", "police rate 20mbit", `[ "$dev" = lo ] && continue`} {
+		if !strings.Contains(script, want) {
+			t.Errorf("bandwidthScript() should contain %q, got:\n%s", want, script)
+		}
+	}
+
+	upload, _ := bandwidthScript(config.BandwidthSettings{Upload: "1mbit"})
+	if strings.Contains(upload, "ingress") {
+		t.Error("bandwidthScript() should leave downloads alone without a download limit")
+	}
+
+	for _, rate := range []string{"fast", "20", "20mbit; reboot"} {
+		if _, err := bandwidthScript(config.BandwidthSettings{Download: rate}); err == nil {
+			t.Errorf("expected rate %q to be rejected", rate)
+		}
+	}
+}
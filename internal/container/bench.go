@@ -0,0 +1,61 @@
+package container
+
+import (
+	"errors"
+	"fmt"
+	"io"
+	"os/exec"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/bench"
+)
+
+const (
+	// benchCopyDir is where --copy places the project on the container's own filesystem
+	benchCopyDir = "/tmp/agentsandbox-bench"
+	cgroupStats  = "cat /sys/fs/cgroup/cpu.stat /sys/fs/cgroup/io.stat 2>/dev/null"
+)
+
+// containerCgroupStats reads the container's cgroup counters; they cover everything
+// running in it, so an idle agent adds a little to the measurement
+func containerCgroupStats(containerName string) bench.CgroupStats {
+	output, _ := exec.Command("docker", "exec", containerName, "sh", "-c", cgroupStats).Output()
+	return bench.ParseCgroupStats(string(output))
+}
+
+// BenchSandbox runs command in the project directory inside the sandbox and measures
+// it. With inCopy, it runs in a copy of the project on the container's own filesystem
+// instead of the bind mount, to show what the mount costs.
+func BenchSandbox(containerName, projectDir, command string, inCopy bool, out io.Writer) (bench.Measurement, error) {
+	m := bench.Measurement{Where: "sandbox (bind mount)"}
+	dir := projectDir
+	if inCopy {
+		m.Where = "sandbox (container filesystem)"
+		dir = benchCopyDir
+		prepare := fmt.Sprintf("rm -rf %[1]s && cp -a %[2]q %[1]s", benchCopyDir, projectDir)
+		if output, err := containerShell(containerName, prepare).CombinedOutput(); err != nil {
+			return m, fmt.Errorf("failed to copy the project for benchmarking: %w\nOutput: %s", err, string(output))
+		}
+		defer containerShell(containerName, "rm -rf "+benchCopyDir).Run()
+	}
+
+	cmd := containerShell(containerName, fmt.Sprintf("cd %q && %s", dir, command))
+	cmd.Stdout = out
+	cmd.Stderr = out
+
+	before := containerCgroupStats(containerName)
+	start := time.Now()
+	err := cmd.Run()
+	m.Wall = time.Since(start)
+	delta := containerCgroupStats(containerName).Sub(before)
+
+	var exitErr *exec.ExitError
+	if errors.As(err, &exitErr) {
+		m.ExitCode = exitErr.ExitCode()
+	} else if err != nil {
+		return m, fmt.Errorf("failed to run %q in %s: %w", command, containerName, err)
+	}
+	m.CPU, m.CPUKnown = delta.CPU, delta.CPUKnown
+	m.ReadBytes, m.WriteBytes, m.IOKnown = delta.ReadBytes, delta.WriteBytes, delta.IOKnown
+	return m, nil
+}
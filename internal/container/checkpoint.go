@@ -0,0 +1,99 @@
+package container
+
+import (
+	"fmt"
+	"os/exec"
+	"sort"
+	"strings"
+	"time"
+)
+
+// checkpointPrefix starts the names of checkpoints made without an explicit name. The
+// timestamp that follows makes them sort oldest first.
+const checkpointPrefix = "agentsandbox-"
+
+// CheckpointSupport reports why docker checkpoint cannot be used, or nil when it can.
+// Checkpoints need a Linux daemon with experimental features enabled and CRIU installed.
+func CheckpointSupport() error {
+	output, err := exec.Command("docker", "version", "--format", "{{.Server.Os}} {{.Server.Experimental}}").Output()
+	if err != nil {
+		return fmt.Errorf("failed to query the Docker daemon: %w", err)
+	}
+	osType, experimental, _ := strings.Cut(strings.TrimSpace(string(output)), " ")
+	if osType != "linux" {
+		return fmt.Errorf("checkpoints need a Linux Docker daemon, found %s", osType)
+	}
+	if experimental != "true" {
+		return fmt.Errorf(`checkpoints need experimental features: set "experimental": true in /etc/docker/daemon.json and restart Docker`)
+	}
+	if _, err := exec.LookPath("criu"); err != nil {
+		return fmt.Errorf("checkpoints need CRIU: install the criu package")
+	}
+	return nil
+}
+
+// NewCheckpointName returns the name used for a checkpoint made now
+func NewCheckpointName() string {
+	return checkpointPrefix + time.Now().Format("20060102-150405")
+}
+
+// CreateCheckpoint saves the memory and processes of a running container with CRIU.
+// Unless leaveRunning is set the container is stopped afterwards, ready to be restored
+// after a reboot.
+func CreateCheckpoint(name, checkpoint string, leaveRunning bool) error {
+	args := []string{"checkpoint", "create"}
+	if leaveRunning {
+		args = append(args, "--leave-running")
+	}
+	args = append(args, name, checkpoint)
+	if output, err := exec.Command("docker", args...).CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to checkpoint container %s: %w\nOutput: %s", name, err, string(output))
+	}
+	return nil
+}
+
+// RestoreCheckpoint starts a stopped container from a checkpoint
+func RestoreCheckpoint(name, checkpoint string) error {
+	if output, err := exec.Command("docker", "start", "--checkpoint", checkpoint, name).CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to restore container %s from checkpoint %s: %w\nOutput: %s", name, checkpoint, err, string(output))
+	}
+	return nil
+}
+
+// ListCheckpoints returns a container's checkpoints, sorted by name
+func ListCheckpoints(name string) ([]string, error) {
+	output, err := exec.Command("docker", "checkpoint", "ls", name).Output()
+	if err != nil {
+		return nil, fmt.Errorf("failed to list checkpoints: %w", err)
+	}
+	return parseCheckpointList(output), nil
+}
+
+// parseCheckpointList parses the table printed by docker checkpoint ls
+func parseCheckpointList(output []byte) []string {
+	var checkpoints []string
+	for i, line := range strings.Split(string(output), "\n") {
+		line = strings.TrimSpace(line)
+		if i == 0 || line == "" {
+			continue
+		}
+		checkpoints = append(checkpoints, line)
+	}
+	sort.Strings(checkpoints)
+	return checkpoints
+}
+
+// LatestCheckpoint returns the most recent checkpoint named by NewCheckpointName,
+// falling back to the last one by name
+func LatestCheckpoint(checkpoints []string) string {
+	latest := ""
+	for _, checkpoint := range checkpoints {
+		if strings.HasPrefix(checkpoint, checkpointPrefix) {
+			latest = checkpoint
+		}
+	}
+	if latest == "" && len(checkpoints) > 0 {
+		latest = checkpoints[len(checkpoints)-1]
+	}
+	return latest
+}
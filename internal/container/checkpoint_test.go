@@ -0,0 +1,31 @@
+package container
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestParseCheckpointList(t *testing.T) {
+	output := []byte(`CHECKPOINT NAME
+agentsandbox-20250113-143000
+before-upgrade
+agentsandbox-20250112-090000
+
+`)
+
+	checkpoints := parseCheckpointList(output)
+	want := []string{"agentsandbox-20250112-090000", "agentsandbox-20250113-143000", "before-upgrade"}
+	if !reflect.DeepEqual(checkpoints, want) {
+		t.Fatalf("parseCheckpointList() = %v, want %v", checkpoints, want)
+	}
+
+	if got := LatestCheckpoint(checkpoints); got != "agentsandbox-20250113-143000" {
+		t.Errorf("LatestCheckpoint() = %q, want the newest timestamped checkpoint", got)
+	}
+	if got := LatestCheckpoint([]string{"a", "b"}); got != "b" {
+		t.Errorf("LatestCheckpoint() = %q, want b", got)
+	}
+	if got := LatestCheckpoint(nil); got != "" {
+		t.Errorf("LatestCheckpoint(nil) = %q, want empty", got)
+	}
+}
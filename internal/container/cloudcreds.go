@@ -0,0 +1,153 @@
+package container
+
+import (
+	"encoding/json"
+	"fmt"
+	"os/exec"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+const (
+	// defaultCredentialLifetime applies to sessions without a time limit
+	defaultCredentialLifetime = time.Hour
+	// STS accepts 15 minutes to 12 hours, further capped by the role's maximum
+	minCredentialLifetime = 15 * time.Minute
+	maxCredentialLifetime = 12 * time.Hour
+	// gcloudTokenFile holds the session's GCP access token inside the container
+	gcloudTokenFile = "/tmp/agentsandbox-gcloud-token"
+)
+
+// credentialLifetime returns how long a session's cloud credentials stay valid: the
+// session limit plus the grace period an interrupted agent gets, rounded up to a minute
+func credentialLifetime(limit time.Duration) time.Duration {
+	if limit <= 0 {
+		return defaultCredentialLifetime
+	}
+	lifetime := (limit + sessionGracePeriod + time.Minute - 1).Truncate(time.Minute)
+	if lifetime < minCredentialLifetime {
+		return minCredentialLifetime
+	}
+	if lifetime > maxCredentialLifetime {
+		return maxCredentialLifetime
+	}
+	return lifetime
+}
+
+// cloudCredentialEnv returns the docker exec arguments giving a session the configured
+// cloud identities. Failures are warnings so a session still starts without them.
+func cloudCredentialEnv(containerName, username string, creds config.CloudCredentialSettings, limit time.Duration) []string {
+	lifetime := credentialLifetime(limit)
+	var args []string
+	if creds.AWS.RoleARN != "" {
+		env, err := awsCredentialEnv(containerName, creds.AWS, lifetime)
+		if err != nil {
+			fmt.Printf("Warning: no AWS credentials for this session: %v\n", err)
+		} else {
+			args = append(args, env...)
+		}
+	}
+	if creds.GCP.ServiceAccount != "" {
+		env, err := gcpCredentialEnv(containerName, username, creds.GCP, lifetime)
+		if err != nil {
+			fmt.Printf("Warning: no GCP credentials for this session: %v\n", err)
+		} else {
+			args = append(args, env...)
+		}
+	}
+	return args
+}
+
+// stsCredentials is the part of `aws sts assume-role` output a session needs
+type stsCredentials struct {
+	Credentials struct {
+		AccessKeyID     string    `json:"AccessKeyId"`
+		SecretAccessKey string    `json:"SecretAccessKey"`
+		SessionToken    string    `json:"SessionToken"`
+		Expiration      time.Time `json:"Expiration"`
+	} `json:"Credentials"`
+}
+
+// awsAssumeRoleArgs returns the host aws CLI arguments assuming the role for a session.
+// The session name identifies the sandbox in CloudTrail.
+func awsAssumeRoleArgs(containerName string, aws config.AWSCredentialSettings, lifetime time.Duration) []string {
+	sessionName := containerName
+	if len(sessionName) > 64 {
+		sessionName = sessionName[:64]
+	}
+	args := []string{
+		"sts", "assume-role",
+		"--role-arn", aws.RoleARN,
+		"--role-session-name", sessionName,
+		"--duration-seconds", strconv.Itoa(int(lifetime.Seconds())),
+		"--output", "json",
+	}
+	if aws.Profile != "" {
+		args = append(args, "--profile", aws.Profile)
+	}
+	return args
+}
+
+// awsCredentialEnv assumes the configured role on the host and returns its temporary
+// credentials as environment variables; the host's own keys never enter the container
+func awsCredentialEnv(containerName string, aws config.AWSCredentialSettings, lifetime time.Duration) ([]string, error) {
+	output, err := exec.Command("aws", awsAssumeRoleArgs(containerName, aws, lifetime)...).Output()
+	if err != nil {
+		return nil, fmt.Errorf("failed to assume %s: %w%s", aws.RoleARN, err, exitStderr(err))
+	}
+	var sts stsCredentials
+	if err := json.Unmarshal(output, &sts); err != nil {
+		return nil, fmt.Errorf("failed to parse assume-role output: %w", err)
+	}
+	fmt.Printf("AWS credentials for %s until %s\n", aws.RoleARN, sts.Credentials.Expiration.Local().Format("15:04"))
+
+	env := []string{
+		"-e", "AWS_ACCESS_KEY_ID=" + sts.Credentials.AccessKeyID,
+		"-e", "AWS_SECRET_ACCESS_KEY=" + sts.Credentials.SecretAccessKey,
+		"-e", "AWS_SESSION_TOKEN=" + sts.Credentials.SessionToken,
+	}
+	if aws.Region != "" {
+		env = append(env, "-e", "AWS_REGION="+aws.Region, "-e", "AWS_DEFAULT_REGION="+aws.Region)
+	}
+	return env, nil
+}
+
+// gcpCredentialEnv mints an access token for the configured service account on the host
+// and writes it to a file only the sandbox user can read, where gcloud picks it up
+func gcpCredentialEnv(containerName, username string, gcp config.GCPCredentialSettings, lifetime time.Duration) ([]string, error) {
+	output, err := exec.Command("gcloud", "auth", "print-access-token",
+		"--impersonate-service-account="+gcp.ServiceAccount,
+		fmt.Sprintf("--lifetime=%d", int(lifetime.Seconds()))).Output()
+	if err != nil {
+		return nil, fmt.Errorf("failed to impersonate %s: %w%s", gcp.ServiceAccount, err, exitStderr(err))
+	}
+	token := strings.TrimSpace(string(output))
+
+	cmd := exec.Command("docker", "exec", "-i", "--user", username, containerName,
+		"sh", "-c", fmt.Sprintf("umask 077 && cat > %s", gcloudTokenFile))
+	cmd.Stdin = strings.NewReader(token)
+	if output, err := cmd.CombinedOutput(); err != nil {
+		return nil, fmt.Errorf("failed to write GCP token: %w\nOutput: %s", err, string(output))
+	}
+	fmt.Printf("GCP credentials for %s until %s\n", gcp.ServiceAccount, time.Now().Add(lifetime).Format("15:04"))
+
+	env := []string{
+		"-e", "CLOUDSDK_AUTH_ACCESS_TOKEN_FILE=" + gcloudTokenFile,
+		"-e", "GOOGLE_OAUTH_ACCESS_TOKEN=" + token,
+	}
+	if gcp.Project != "" {
+		env = append(env, "-e", "CLOUDSDK_CORE_PROJECT="+gcp.Project, "-e", "GOOGLE_CLOUD_PROJECT="+gcp.Project)
+	}
+	return env, nil
+}
+
+// exitStderr formats what a failed command printed to stderr, if anything
+func exitStderr(err error) string {
+	if exitErr, ok := err.(*exec.ExitError); ok && len(exitErr.Stderr) > 0 {
+		return "\nOutput: " + strings.TrimSpace(string(exitErr.Stderr))
+	}
+	return ""
+}
@@ -0,0 +1,43 @@
+package container
+
+import (
+	"reflect"
+	"testing"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+func TestCredentialLifetime(t *testing.T) {
+	tests := []struct {
+		limit time.Duration
+		want  time.Duration
+	}{
+		{0, time.Hour},
+		{5 * time.Minute, 15 * time.Minute},
+		{45 * time.Minute, 46 * time.Minute},
+		{2*time.Hour + 10*time.Second, 2*time.Hour + time.Minute},
+		{24 * time.Hour, 12 * time.Hour},
+	}
+	for _, tt := range tests {
+		if got := credentialLifetime(tt.limit); got != tt.want {
+			t.Errorf("credentialLifetime(%v) = %v, want %v", tt.limit, got, tt.want)
+		}
+	}
+}
+
+func TestAWSAssumeRoleArgs(t *testing.T) {
+	aws := config.AWSCredentialSettings{RoleARN: "arn:aws:iam::123456789012:role/agent", Profile: "dev"}
+	got := awsAssumeRoleArgs("agentsandbox-app", aws, 46*time.Minute)
+	want := []string{
+		"sts", "assume-role",
+		"--role-arn", "arn:aws:iam::123456789012:role/agent",
+		"--role-session-name", "agentsandbox-app",
+		"--duration-seconds", "2760",
+		"--output", "json",
+		"--profile", "dev",
+	}
+	if !reflect.DeepEqual(got, want) {
+		t.Errorf("awsAssumeRoleArgs() = %v, want %v", got, want)
+	}
+}
@@ -0,0 +1,117 @@
+package container
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"os/exec"
+	"path"
+	"path/filepath"
+	"strings"
+
+	"github.com/thaodangspace/agentsandbox/internal/git"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+const (
+	// commitHooksDir is used as core.hooksPath for git commands run during a session
+	commitHooksDir = "/etc/agentsandbox/git-hooks"
+	// CommitTrailer is added to the message of every commit made during a session
+	CommitTrailer = "Agent-Sandbox-Session"
+)
+
+// commitHookNames are the client-side hooks forwarded to the repository's own hooks
+var commitHookNames = []string{
+	"applypatch-msg", "pre-applypatch", "post-applypatch",
+	"pre-commit", "pre-merge-commit", "prepare-commit-msg", "commit-msg", "post-commit",
+	"pre-rebase", "post-checkout", "post-merge", "pre-push", "post-rewrite", "push-to-checkout",
+}
+
+// commitHook dispatches every hook to the repository's configured hooks directory, so
+// overriding core.hooksPath does not disable the project's own hooks, and tags commit
+// messages with the session trailer.
+const commitHook = `#!/bin/sh
+hook=$(basename "$0")
+if [ "$hook" = prepare-commit-msg ] && [ -n "${AGENTSANDBOX_SESSION:-}" ]; then
+    git interpret-trailers --in-place --if-exists addIfDifferent \
+        --trailer "` + CommitTrailer + `: $AGENTSANDBOX_SESSION" "$1" || true
+fi
+hooks=$(git config --local --get core.hooksPath || echo "$(git rev-parse --git-common-dir)/hooks")
+if [ -x "$hooks/$hook" ]; then
+    exec "$hooks/$hook" "$@"
+fi
+`
+
+// commitHookEnv returns the docker exec arguments that tell commitHook the session
+func commitHookEnv(sessionID string) []string {
+	return []string{"-e", "AGENTSANDBOX_SESSION=" + sessionID}
+}
+
+// commitHookConfig routes git hooks through commitHook
+var commitHookConfig = [][2]string{{"core.hooksPath", commitHooksDir}}
+
+// gitConfigEnv returns docker exec arguments that set git config for a session. Git
+// reads GIT_CONFIG_COUNT on top of every config file, so nothing in the container changes.
+func gitConfigEnv(entries [][2]string) []string {
+	if len(entries) == 0 {
+		return nil
+	}
+	args := []string{"-e", fmt.Sprintf("GIT_CONFIG_COUNT=%d", len(entries))}
+	for i, entry := range entries {
+		args = append(args,
+			"-e", fmt.Sprintf("GIT_CONFIG_KEY_%d=%s", i, entry[0]),
+			"-e", fmt.Sprintf("GIT_CONFIG_VALUE_%d=%s", i, entry[1]),
+		)
+	}
+	return args
+}
+
+// installCommitHooks writes the dispatching hook into the container under every hook name
+func installCommitHooks(containerName string) error {
+	dispatcher := path.Join(commitHooksDir, "dispatch")
+	var links []string
+	for _, name := range commitHookNames {
+		links = append(links, fmt.Sprintf("ln -sf dispatch %s", path.Join(commitHooksDir, name)))
+	}
+	script := fmt.Sprintf("mkdir -p %s && cat > %s && chmod 755 %s && %s",
+		commitHooksDir, dispatcher, dispatcher, strings.Join(links, " && "))
+
+	cmd := exec.Command("docker", "exec", "-i", "--user", "root", containerName, "sh", "-c", script)
+	cmd.Stdin = strings.NewReader(commitHook)
+	if output, err := cmd.CombinedOutput(); err != nil {
+		return fmt.Errorf("%w\nOutput: %s", err, string(output))
+	}
+	return nil
+}
+
+// sessionCommits is the metadata recorded for the commits of one session
+type sessionCommits struct {
+	Session string   `json:"session"`
+	Commits []string `json:"commits"`
+}
+
+// recordSessionCommits finds the commits tagged with the session trailer and saves
+// their hashes next to the session's logs
+func recordSessionCommits(containerName, currentDir, sessionID string) error {
+	commits, err := git.CommitsWithTrailer(currentDir, fmt.Sprintf("%s: %s", CommitTrailer, sessionID))
+	if err != nil || len(commits) == 0 {
+		// Not a git repository, or nothing was committed
+		return nil
+	}
+
+	logsDir, err := state.GetLogsDir(containerName, currentDir)
+	if err != nil {
+		return err
+	}
+
+	data, err := json.MarshalIndent(sessionCommits{Session: sessionID, Commits: commits}, "", "  ")
+	if err != nil {
+		return err
+	}
+	if err := os.WriteFile(filepath.Join(logsDir, fmt.Sprintf("commits-%s.json", sessionID)), data, 0644); err != nil {
+		return fmt.Errorf("failed to record session commits: %w", err)
+	}
+
+	fmt.Printf("Recorded %d commit(s) tagged %s: %s\n", len(commits), CommitTrailer, sessionID)
+	return nil
+}
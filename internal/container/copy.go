@@ -0,0 +1,86 @@
+package container
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"path"
+	"strings"
+)
+
+// ParseCopyTarget splits a "[container:]path" argument into its container and path parts
+func ParseCopyTarget(arg string) (string, string) {
+	idx := strings.Index(arg, ":")
+	if idx <= 0 || strings.ContainsAny(arg[:idx], "/\\.") {
+		return "", arg
+	}
+	return arg[:idx], arg[idx+1:]
+}
+
+// ResolveContainer picks the container a command acts on: the explicit container or
+// session name, then the container for the current directory, then the last used container
+func ResolveContainer(name, currentDir string) (string, error) {
+	if name != "" {
+		name = ResolveName(name)
+		if exists, _ := ContainerExists(name); !exists {
+			return "", failure.Errorf(failure.ContainerNotFound, "container '%s' does not exist", name)
+		}
+		return name, nil
+	}
+
+	if containers, err := ListContainers(currentDir); err == nil && len(containers) > 0 {
+		return containers[0], nil
+	}
+
+	last, err := LoadLastContainer()
+	if err != nil || last == "" {
+		return "", failure.Errorf(failure.ContainerNotFound, "no container found for this directory and no previous container found")
+	}
+	return last, nil
+}
+
+// resolveContainerPath makes a relative path absolute against the container's working directory
+func resolveContainerPath(containerName, containerPath string) string {
+	if path.IsAbs(containerPath) {
+		return containerPath
+	}
+
+	workDir, err := GetContainerDirectory(containerName)
+	if err != nil || workDir == "" {
+		username := os.Getenv("USER")
+		if username == "" {
+			username = "ubuntu"
+		}
+		workDir = fmt.Sprintf("/home/%s", username)
+	}
+	return path.Join(workDir, containerPath)
+}
+
+// CopyToContainer copies a host file or directory into the container
+func CopyToContainer(containerName, hostPath, containerPath string) error {
+	if _, err := os.Stat(hostPath); err != nil {
+		return fmt.Errorf("failed to read %s: %w", hostPath, err)
+	}
+
+	dst := resolveContainerPath(containerName, containerPath)
+	fmt.Printf("Copying %s -> %s:%s\n", hostPath, containerName, dst)
+	return runDockerCp(hostPath, fmt.Sprintf("%s:%s", containerName, dst))
+}
+
+// CopyFromContainer copies a file or directory out of the container to the host
+func CopyFromContainer(containerName, containerPath, hostPath string) error {
+	src := resolveContainerPath(containerName, containerPath)
+	fmt.Printf("Copying %s:%s -> %s\n", containerName, src, hostPath)
+	return runDockerCp(fmt.Sprintf("%s:%s", containerName, src), hostPath)
+}
+
+// runDockerCp runs docker cp with its progress output attached to the terminal
+func runDockerCp(src, dst string) error {
+	cmd := exec.Command("docker", "cp", src, dst)
+	cmd.Stdout = os.Stdout
+	cmd.Stderr = os.Stderr
+	if err := cmd.Run(); err != nil {
+		return fmt.Errorf("docker cp failed: %w", err)
+	}
+	return nil
+}
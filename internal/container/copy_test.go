@@ -0,0 +1,27 @@
+package container
+
+import "testing"
+
+func TestParseCopyTarget(t *testing.T) {
+	tests := []struct {
+		name      string
+		arg       string
+		container string
+		path      string
+	}{
+		{"plain relative path", "src/main.go", "", "src/main.go"},
+		{"plain absolute path", "/tmp/out", "", "/tmp/out"},
+		{"container and path", "agentsandbox-app:/tmp/out", "agentsandbox-app", "/tmp/out"},
+		{"container and relative path", "agentsandbox-app:build", "agentsandbox-app", "build"},
+		{"colon inside path", "./dir:name", "", "./dir:name"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			container, path := ParseCopyTarget(tt.arg)
+			if container != tt.container || path != tt.path {
+				t.Errorf("ParseCopyTarget(%q) = (%q, %q), want (%q, %q)", tt.arg, container, path, tt.container, tt.path)
+			}
+		})
+	}
+}
@@ -0,0 +1,48 @@
+package container
+
+import (
+	"fmt"
+	"io"
+	"os/exec"
+	"path"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/credproxy"
+)
+
+// startCredentialProxy starts the host-side proxy holding the agent's API key, or
+// returns nil when the agent or key is not available to proxy
+func startCredentialProxy(agent config.Agent) *credproxy.Proxy {
+	provider, ok := credproxy.ProviderFor(agent)
+	if !ok {
+		fmt.Printf("Warning: the credential proxy does not support %s; using its own login\n", agent.DisplayName())
+		return nil
+	}
+	key := provider.HostKey()
+	if key == "" {
+		fmt.Printf("Warning: credential_proxy is enabled but %s is not set on the host\n", provider.KeyEnvs[0])
+		return nil
+	}
+	proxy, err := credproxy.Start(provider, key)
+	if err != nil {
+		fmt.Printf("Warning: %v\n", err)
+		return nil
+	}
+	fmt.Printf("Authenticating %s requests through the host; the API key stays outside the container\n", provider.Name)
+	return proxy
+}
+
+// removeCredentialFiles deletes the agent's copied login files from the container
+// when the credential proxy authenticates it instead
+func removeCredentialFiles(out io.Writer, containerName string, agent config.Agent, username string) {
+	provider, ok := credproxy.ProviderFor(agent)
+	if !ok {
+		return
+	}
+	for _, rel := range provider.CredentialFiles {
+		target := path.Join("/home", username, rel)
+		if output, err := exec.Command("docker", "exec", "--user", "root", containerName, "rm", "-f", target).CombinedOutput(); err != nil {
+			fmt.Fprintf(out, "Warning: failed to remove %s: %v\nOutput: %s\n", target, err, string(output))
+		}
+	}
+}
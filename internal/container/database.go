@@ -0,0 +1,48 @@
+package container
+
+import (
+	"fmt"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/templates"
+)
+
+// databaseTemplate sets up the project's database and records where the sandbox reaches it
+type databaseTemplate struct {
+	*templates.Template
+	url string
+}
+
+// projectDatabase reads the db section of the project's settings. A database the stack
+// template already starts is reused, so only the migrations and seeds are added.
+// Restricted projects get no database: the section picks the image and the commands
+// setup runs, with sudo, before the agent starts, so the repository would control them.
+func projectDatabase(projectDir string, stack *templates.Template, restricted bool) (*databaseTemplate, error) {
+	project, err := config.LoadProjectSettings(projectDir)
+	if err != nil {
+		fmt.Printf("Warning: failed to load project settings: %v\n", err)
+		return nil, nil
+	}
+	if project.DB == nil {
+		return nil, nil
+	}
+	if restricted {
+		fmt.Println("Skipping the database in the project's settings: restricted projects do not run repository-configured setup")
+		return nil, nil
+	}
+
+	tmpl, url, err := templates.Database(*project.DB, projectDir)
+	if err != nil {
+		return nil, err
+	}
+	if stack != nil {
+		for _, svc := range stack.Services {
+			if svc.Name == tmpl.Services[0].Name {
+				tmpl.Services = nil
+				break
+			}
+		}
+	}
+	fmt.Printf("Setting up the %s\n", tmpl.Description)
+	return &databaseTemplate{Template: tmpl, url: url}, nil
+}
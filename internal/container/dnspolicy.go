@@ -0,0 +1,146 @@
+package container
+
+import (
+	"bufio"
+	"fmt"
+	"io"
+	"net"
+	"os"
+	"os/exec"
+	"regexp"
+	"strings"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/templates"
+)
+
+// DNS policy modes
+const (
+	DNSAllow = "allow"
+	DNSDeny  = "deny"
+)
+
+const (
+	dnsImage      = "agentsandbox-dnsmasq:latest"
+	dnsDockerfile = "FROM alpine:3.20\nRUN apk add --no-cache dnsmasq\n"
+	// dnsFallback is the resolver docker itself falls back to when the host only has local ones
+	dnsFallback = "8.8.8.8"
+)
+
+var domainPattern = regexp.MustCompile(`^[a-z0-9]([a-z0-9-]*[a-z0-9])?(\.[a-z0-9]([a-z0-9-]*[a-z0-9])?)*$`)
+
+// ValidateDNSPolicy checks the dns_policy setting
+func ValidateDNSPolicy(policy config.DNSPolicySettings) error {
+	switch policy.Mode {
+	case "":
+		return nil
+	case DNSAllow, DNSDeny:
+	default:
+		return fmt.Errorf("invalid dns_policy mode %q: use allow or deny", policy.Mode)
+	}
+	for _, domain := range policy.Domains {
+		if !domainPattern.MatchString(normalizeDomain(domain)) {
+			return fmt.Errorf("invalid domain %q in dns_policy", domain)
+		}
+	}
+	for _, server := range policy.Upstream {
+		if net.ParseIP(server) == nil {
+			return fmt.Errorf("invalid upstream resolver %q in dns_policy: use an IP address", server)
+		}
+	}
+	return nil
+}
+
+// normalizeDomain strips a leading wildcard, which dnsmasq implies for every domain
+func normalizeDomain(domain string) string {
+	domain = strings.ToLower(strings.TrimSpace(domain))
+	domain = strings.TrimPrefix(domain, "*.")
+	return strings.Trim(domain, ".")
+}
+
+// dnsmasqArgs returns the dnsmasq command line enforcing the policy. In allow mode
+// every name resolves to NXDOMAIN except the listed domains, which are forwarded;
+// dnsmasq prefers the most specific match.
+func dnsmasqArgs(policy config.DNSPolicySettings, upstream []string) []string {
+	args := []string{
+		"dnsmasq", "--keep-in-foreground", "--no-resolv", "--no-hosts",
+		"--listen-address=127.0.0.1", "--bind-interfaces",
+		"--log-queries", "--log-facility=-",
+	}
+	for _, domain := range policy.Domains {
+		domain = normalizeDomain(domain)
+		if policy.Mode == DNSAllow {
+			for _, server := range upstream {
+				args = append(args, fmt.Sprintf("--server=/%s/%s", domain, server))
+			}
+		} else {
+			args = append(args, fmt.Sprintf("--address=/%s/", domain))
+		}
+	}
+	if policy.Mode == DNSAllow {
+		return append(args, "--address=/#/")
+	}
+	for _, server := range upstream {
+		args = append(args, "--server="+server)
+	}
+	return args
+}
+
+// hostResolvers returns the host's nameservers that a container can reach, skipping
+// loopback ones such as systemd-resolved's stub, the way docker does
+func hostResolvers() []string {
+	var servers []string
+	for _, path := range []string{"/run/systemd/resolve/resolv.conf", "/etc/resolv.conf"} {
+		file, err := os.Open(path)
+		if err != nil {
+			continue
+		}
+		scanner := bufio.NewScanner(file)
+		for scanner.Scan() {
+			fields := strings.Fields(scanner.Text())
+			if len(fields) < 2 || fields[0] != "nameserver" {
+				continue
+			}
+			if ip := net.ParseIP(fields[1]); ip != nil && !ip.IsLoopback() {
+				servers = append(servers, fields[1])
+			}
+		}
+		file.Close()
+		if len(servers) > 0 {
+			return servers
+		}
+	}
+	return []string{dnsFallback}
+}
+
+// ensureDNSImage builds the small dnsmasq image the DNS sidecar runs, once
+func ensureDNSImage() error {
+	if exec.Command("docker", "image", "inspect", dnsImage).Run() == nil {
+		return nil
+	}
+	fmt.Println("Building the DNS policy image...")
+	cmd := exec.Command("docker", "build", "-t", dnsImage, "-")
+	cmd.Stdin = strings.NewReader(dnsDockerfile)
+	if output, err := cmd.CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to build %s: %w\nOutput: %s", dnsImage, err, string(output))
+	}
+	return nil
+}
+
+// startDNSPolicy runs dnsmasq in the sandbox's network namespace, where the sandbox was
+// told to find its resolver. Its query log is available with docker logs. Nothing stops
+// the sandbox from asking another resolver, using DNS over HTTPS or connecting to an IP
+// address; the policy only covers lookups through resolv.conf.
+func startDNSPolicy(out io.Writer, containerName string, policy config.DNSPolicySettings) error {
+	upstream := policy.Upstream
+	if len(upstream) == 0 {
+		upstream = hostResolvers()
+	}
+	svc := templates.Service{Name: "dns", Image: dnsImage, Command: dnsmasqArgs(policy, upstream)}
+	if err := startService(out, containerName, svc); err != nil {
+		return err
+	}
+	fmt.Fprintf(out, "DNS %s list of %d domains enforced; queries are logged by 'docker logs %s'\n",
+		policy.Mode, len(policy.Domains), serviceContainerName(containerName, "dns"))
+	return nil
+}
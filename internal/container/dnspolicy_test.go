@@ -0,0 +1,60 @@
+package container
+
+import (
+	"reflect"
+	"testing"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+func TestDnsmasqArgs(t *testing.T) {
+	base := []string{
+		"dnsmasq", "--keep-in-foreground", "--no-resolv", "--no-hosts",
+		"--listen-address=127.0.0.1", "--bind-interfaces",
+		"--log-queries", "--log-facility=-",
+	}
+
+	allow := config.DNSPolicySettings{Mode: DNSAllow, Domains: []string{"*.github.com", "registry.npmjs.org."}}
+	want := append(append([]string{}, base...),
+		"--server=/github.com/10.0.0.2",
+		"--server=/registry.npmjs.org/10.0.0.2",
+		"--address=/#/",
+	)
+	if got := dnsmasqArgs(allow, []string{"10.0.0.2"}); !reflect.DeepEqual(got, want) {
+		t.Errorf("allow: dnsmasqArgs() = %v, want %v", got, want)
+	}
+
+	deny := config.DNSPolicySettings{Mode: DNSDeny, Domains: []string{"pastebin.com"}}
+	want = append(append([]string{}, base...),
+		"--address=/pastebin.com/",
+		"--server=1.1.1.1",
+		"--server=9.9.9.9",
+	)
+	if got := dnsmasqArgs(deny, []string{"1.1.1.1", "9.9.9.9"}); !reflect.DeepEqual(got, want) {
+		t.Errorf("deny: dnsmasqArgs() = %v, want %v", got, want)
+	}
+}
+
+func TestValidateDNSPolicy(t *testing.T) {
+	valid := []config.DNSPolicySettings{
+		{},
+		{Mode: DNSAllow, Domains: []string{"*.github.com", "pypi.org"}},
+		{Mode: DNSDeny, Domains: []string{"example.com"}, Upstream: []string{"1.1.1.1"}},
+	}
+	for _, policy := range valid {
+		if err := ValidateDNSPolicy(policy); err != nil {
+			t.Errorf("ValidateDNSPolicy(%+v) = %v", policy, err)
+		}
+	}
+
+	invalid := []config.DNSPolicySettings{
+		{Mode: "block"},
+		{Mode: DNSAllow, Domains: []string{"github.com/evil"}},
+		{Mode: DNSDeny, Upstream: []string{"dns.google"}},
+	}
+	for _, policy := range invalid {
+		if err := ValidateDNSPolicy(policy); err == nil {
+			t.Errorf("expected %+v to be rejected", policy)
+		}
+	}
+}
@@ -0,0 +1,75 @@
+package container
+
+import (
+	"fmt"
+	"io"
+	"os/exec"
+	"strings"
+)
+
+// Ways of giving a sandbox a Docker daemon
+const (
+	// DockerSocket mounts the host's Docker socket, which amounts to root on the host
+	DockerSocket = "socket"
+	// DockerInDocker runs a separate daemon in a privileged sidecar
+	DockerInDocker = "dind"
+)
+
+const (
+	dockerSocketPath = "/var/run/docker.sock"
+	dindImage        = "docker:dind"
+	// dindHost is where the sidecar's daemon listens, on loopback in the sandbox's
+	// network namespace so nothing else on the bridge can reach it
+	dindHost = "tcp://127.0.0.1:2375"
+)
+
+// dockerRunArgs returns the docker run arguments for the sandbox itself
+func dockerRunArgs(mode string) []string {
+	switch mode {
+	case DockerSocket:
+		fmt.Println("Warning: mounting the Docker socket gives the agent full control of the host's Docker, which is equivalent to root on the host")
+		return []string{"-v", dockerSocketPath + ":" + dockerSocketPath}
+	case DockerInDocker:
+		return []string{"-e", "DOCKER_HOST=" + dindHost}
+	}
+	return nil
+}
+
+// grantDockerSocket adds the sandbox user to the group owning the mounted socket.
+// docker exec looks up supplementary groups when a session starts, so this applies to
+// every session after it.
+func grantDockerSocket(containerName, username string) error {
+	script := fmt.Sprintf(`gid=$(stat -c %%g %[1]s)
+group=$(getent group "$gid" | cut -d: -f1)
+if [ -z "$group" ]; then group=docker-host; groupadd -g "$gid" "$group"; fi
+usermod -aG "$group" %[2]s`, dockerSocketPath, username)
+	if output, err := exec.Command("docker", "exec", "--user", "root", containerName, "sh", "-c", script).CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to grant access to the Docker socket: %w\nOutput: %s", err, string(output))
+	}
+	return nil
+}
+
+// startDind runs a Docker daemon in a privileged sidecar sharing the sandbox's network
+// namespace. The project directories are mounted at the same paths so bind mounts in
+// nested containers find the project, and images and containers stay separate from
+// the host's.
+func startDind(out io.Writer, containerName string, dirs []string) error {
+	name := serviceContainerName(containerName, "docker")
+	args := []string{
+		"run", "-d", "--privileged",
+		"--name", name,
+		"--network", fmt.Sprintf("container:%s", containerName),
+		"--label", fmt.Sprintf("%s=%s", serviceParentLabel, containerName),
+		"-e", "DOCKER_TLS_CERTDIR=",
+	}
+	for _, dir := range dirs {
+		args = append(args, "-v", fmt.Sprintf("%s:%s", dir, dir))
+	}
+	args = append(args, dindImage, "--host="+dindHost)
+
+	fmt.Fprintf(out, "Starting a Docker daemon for the sandbox (%s)\n", dindImage)
+	if output, err := exec.Command("docker", args...).CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to start Docker-in-Docker: %w\nOutput: %s", err, strings.TrimSpace(string(output)))
+	}
+	return nil
+}
@@ -0,0 +1,18 @@
+package container
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestDockerRunArgs(t *testing.T) {
+	if args := dockerRunArgs(""); args != nil {
+		t.Errorf("dockerRunArgs(\"\") = %v, want none", args)
+	}
+	if got, want := dockerRunArgs(DockerSocket), []string{"-v", "/var/run/docker.sock:/var/run/docker.sock"}; !reflect.DeepEqual(got, want) {
+		t.Errorf("dockerRunArgs(socket) = %v, want %v", got, want)
+	}
+	if got, want := dockerRunArgs(DockerInDocker), []string{"-e", "DOCKER_HOST=tcp://127.0.0.1:2375"}; !reflect.DeepEqual(got, want) {
+		t.Errorf("dockerRunArgs(dind) = %v, want %v", got, want)
+	}
+}
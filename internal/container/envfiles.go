@@ -0,0 +1,265 @@
+package container
+
+import (
+	"bufio"
+	"fmt"
+	"io/fs"
+	"os"
+	"path"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"github.com/thaodangspace/agentsandbox/internal/secrets"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+	"github.com/thaodangspace/agentsandbox/internal/ui"
+)
+
+// sandboxEnvTemplate is the project file whose contents replace masked env files
+const sandboxEnvTemplate = ".env.sandbox"
+
+// envKeys returns the variable names defined in an env file, in order of appearance
+func envKeys(content string) []string {
+	var keys []string
+	seen := make(map[string]bool)
+
+	scanner := bufio.NewScanner(strings.NewReader(content))
+	for scanner.Scan() {
+		line := strings.TrimSpace(scanner.Text())
+		if line == "" || strings.HasPrefix(line, "#") {
+			continue
+		}
+		line = strings.TrimPrefix(line, "export ")
+
+		key, _, found := strings.Cut(line, "=")
+		key = strings.TrimSpace(key)
+		if !found || key == "" || seen[key] {
+			continue
+		}
+		seen[key] = true
+		keys = append(keys, key)
+	}
+	return keys
+}
+
+// sanitizedEnv builds the content mounted in place of a masked env file.
+// A project .env.sandbox template is used verbatim; otherwise the keys of the original
+// file are kept and given the fake values from settings (or left empty).
+func sanitizedEnv(original, template string, fakes map[string]string) string {
+	if template != "" {
+		return template
+	}
+	if len(fakes) == 0 {
+		return ""
+	}
+
+	var b strings.Builder
+	b.WriteString("# Generated by agentsandbox: real values are masked\n")
+
+	keys := envKeys(original)
+	seen := make(map[string]bool)
+	for _, key := range keys {
+		seen[key] = true
+	}
+	var extra []string
+	for key := range fakes {
+		if !seen[key] {
+			extra = append(extra, key)
+		}
+	}
+	sort.Strings(extra)
+
+	for _, key := range append(keys, extra...) {
+		fmt.Fprintf(&b, "%s=%s\n", key, fakes[key])
+	}
+	return b.String()
+}
+
+// writeEnvOverlay writes the sanitized replacement for an env file to a temp file and returns its path
+func writeEnvOverlay(currentDir, envPath string, fakes map[string]string) (string, error) {
+	var template string
+	if data, err := os.ReadFile(filepath.Join(currentDir, sandboxEnvTemplate)); err == nil {
+		template = string(data)
+	}
+
+	var original string
+	if len(fakes) > 0 && template == "" {
+		if data, err := os.ReadFile(envPath); err == nil {
+			original = string(data)
+		}
+	}
+
+	tempFile, err := os.CreateTemp("", "env-overlay-*")
+	if err != nil {
+		return "", err
+	}
+	defer tempFile.Close()
+
+	if _, err := tempFile.WriteString(sanitizedEnv(original, template, fakes)); err != nil {
+		return "", err
+	}
+	return tempFile.Name(), nil
+}
+
+// matchEnvPattern reports whether a slash-separated relative path matches a glob pattern.
+// In addition to path.Match syntax, a "**" segment matches any number of directories.
+func matchEnvPattern(pattern, rel string) bool {
+	return matchSegments(strings.Split(pattern, "/"), strings.Split(rel, "/"))
+}
+
+func matchSegments(pattern, parts []string) bool {
+	if len(pattern) == 0 {
+		return len(parts) == 0
+	}
+
+	if pattern[0] == "**" {
+		for i := 0; i <= len(parts); i++ {
+			if matchSegments(pattern[1:], parts[i:]) {
+				return true
+			}
+		}
+		return false
+	}
+
+	if len(parts) == 0 {
+		return false
+	}
+	if ok, err := path.Match(pattern[0], parts[0]); err != nil || !ok {
+		return false
+	}
+	return matchSegments(pattern[1:], parts[1:])
+}
+
+// walkRoot returns the directory below which a pattern can match, so only that subtree is walked
+func walkRoot(pattern string) string {
+	var fixed []string
+	for _, segment := range strings.Split(pattern, "/") {
+		if strings.ContainsAny(segment, "*?[") {
+			break
+		}
+		fixed = append(fixed, segment)
+	}
+	if len(fixed) == len(strings.Split(pattern, "/")) {
+		fixed = fixed[:len(fixed)-1]
+	}
+	return strings.Join(fixed, "/")
+}
+
+// maskedEnvFiles expands the env file patterns against the project and returns
+// the matching files as sorted slash-separated relative paths
+func maskedEnvFiles(currentDir string, patterns []string) []string {
+	matched := make(map[string]bool)
+
+	for _, pattern := range patterns {
+		pattern = strings.TrimPrefix(filepath.ToSlash(pattern), "./")
+		if pattern == "" {
+			continue
+		}
+
+		root := walkRoot(pattern)
+		filepath.WalkDir(filepath.Join(currentDir, filepath.FromSlash(root)), func(p string, d fs.DirEntry, err error) error {
+			if err != nil {
+				return nil
+			}
+			rel, err := filepath.Rel(currentDir, p)
+			if err != nil {
+				return nil
+			}
+			rel = filepath.ToSlash(rel)
+
+			if d.IsDir() {
+				if name := d.Name(); rel != "." && (name == ".git" || name == "node_modules") {
+					return filepath.SkipDir
+				}
+				// Only descend when the pattern can match below this directory
+				if !strings.Contains(pattern, "**") && rel != "." && strings.Count(rel, "/") >= strings.Count(pattern, "/") {
+					return filepath.SkipDir
+				}
+				return nil
+			}
+
+			if d.Type().IsRegular() && rel != sandboxEnvTemplate && matchEnvPattern(pattern, rel) {
+				matched[rel] = true
+			}
+			return nil
+		})
+	}
+
+	files := make([]string, 0, len(matched))
+	for rel := range matched {
+		files = append(files, rel)
+	}
+	sort.Strings(files)
+	return files
+}
+
+// secretMounts scans the project for key material and returns the docker arguments that mask it.
+// The mode comes from the secret_scan setting: "mask" (default), "warn" or "off".
+// Files already masked as env files are skipped. The empty files mounted over them are
+// kept in the container's secrets directory until the container is removed.
+func secretMounts(containerName, currentDir, mode string, masked map[string]bool) []string {
+	if mode == "off" {
+		return nil
+	}
+
+	var findings []secrets.Finding
+	for _, f := range secrets.Scan(currentDir) {
+		if !masked[f.Path] {
+			findings = append(findings, f)
+		}
+	}
+	if len(findings) == 0 {
+		return nil
+	}
+
+	if mode == "warn" {
+		fmt.Printf("%sThe project contains files that look like credentials and will be visible to the agent:\n", ui.Warning())
+		for _, f := range findings {
+			fmt.Printf("  %s (%s)\n", f.Path, f.Reason)
+		}
+		fmt.Println("Set \"secret_scan\": \"mask\" in settings to hide them.")
+		return nil
+	}
+
+	overlayDir, err := secretOverlayDir(containerName)
+	if err != nil {
+		fmt.Printf("Warning: failed to mask credential files: %v\n", err)
+		return nil
+	}
+
+	var args []string
+	for _, f := range findings {
+		overlay, err := os.CreateTemp(overlayDir, "secret-overlay-*")
+		if err != nil {
+			fmt.Printf("Warning: failed to mask %s: %v\n", f.Path, err)
+			continue
+		}
+		overlay.Close()
+
+		hostPath := filepath.Join(currentDir, filepath.FromSlash(f.Path))
+		args = append(args, "-v", fmt.Sprintf("%s:%s:ro", overlay.Name(), hostPath))
+		fmt.Printf("Masking %s (%s)\n", f.Path, f.Reason)
+	}
+	fmt.Println("Use --allow-secrets to mount these files for this session.")
+	return args
+}
+
+// secretOverlayDir creates and returns the directory holding a container's secret overlays
+func secretOverlayDir(containerName string) (string, error) {
+	stateDir, err := state.GetStateDir()
+	if err != nil {
+		return "", err
+	}
+	dir := filepath.Join(stateDir, fmt.Sprintf("%s.secrets", containerName))
+	if err := os.MkdirAll(dir, 0700); err != nil {
+		return "", fmt.Errorf("failed to create %s: %w", dir, err)
+	}
+	return dir, nil
+}
+
+// clearSecretOverlays removes the secret overlays of a removed container
+func clearSecretOverlays(containerName string) {
+	if stateDir, err := state.GetStateDir(); err == nil {
+		os.RemoveAll(filepath.Join(stateDir, fmt.Sprintf("%s.secrets", containerName)))
+	}
+}
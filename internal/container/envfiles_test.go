@@ -0,0 +1,101 @@
+package container
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestEnvKeys(t *testing.T) {
+	content := `# comment
+DATABASE_URL=postgres://prod
+export API_KEY="secret"
+
+INVALID LINE
+DATABASE_URL=duplicate
+`
+	keys := envKeys(content)
+	want := []string{"DATABASE_URL", "API_KEY"}
+	if len(keys) != len(want) {
+		t.Fatalf("envKeys() = %v, want %v", keys, want)
+	}
+	for i := range want {
+		if keys[i] != want[i] {
+			t.Errorf("envKeys()[%d] = %v, want %v", i, keys[i], want[i])
+		}
+	}
+}
+
+func TestSanitizedEnv(t *testing.T) {
+	original := "DATABASE_URL=postgres://prod\nAPI_KEY=secret\n"
+
+	tests := []struct {
+		name     string
+		template string
+		fakes    map[string]string
+		want     string
+	}{
+		{"no template or fakes masks everything", "", nil, ""},
+		{"template is used verbatim", "API_KEY=dummy\n", map[string]string{"API_KEY": "ignored"}, "API_KEY=dummy\n"},
+		{
+			"fakes fill original keys",
+			"",
+			map[string]string{"API_KEY": "dummy", "EXTRA": "1"},
+			"# Generated by agentsandbox: real values are masked\nDATABASE_URL=\nAPI_KEY=dummy\nEXTRA=1\n",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := sanitizedEnv(original, tt.template, tt.fakes); got != tt.want {
+				t.Errorf("sanitizedEnv() = %q, want %q", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestMatchEnvPattern(t *testing.T) {
+	tests := []struct {
+		pattern string
+		rel     string
+		want    bool
+	}{
+		{".env", ".env", true},
+		{".env*", ".env.local", true},
+		{".env*", "app/.env", false},
+		{"secrets/**", "secrets/prod/key.json", true},
+		{"secrets/**", "other/key.json", false},
+		{"**/.env", "services/api/.env", true},
+		{"**/.env", ".env", true},
+		{"config/*.yml", "config/db.yml", true},
+		{"config/*.yml", "config/nested/db.yml", false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.pattern+" "+tt.rel, func(t *testing.T) {
+			if got := matchEnvPattern(tt.pattern, tt.rel); got != tt.want {
+				t.Errorf("matchEnvPattern(%q, %q) = %v, want %v", tt.pattern, tt.rel, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestMaskedEnvFiles(t *testing.T) {
+	dir := t.TempDir()
+	for _, rel := range []string{".env", ".env.local", ".env.sandbox", "secrets/prod/key.json", "src/.env", "node_modules/pkg/.env"} {
+		path := filepath.Join(dir, filepath.FromSlash(rel))
+		if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(path, []byte("KEY=value\n"), 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+
+	got := maskedEnvFiles(dir, []string{".env*", "secrets/**", "**/.env"})
+	want := []string{".env", ".env.local", "secrets/prod/key.json", "src/.env"}
+	if strings.Join(got, ",") != strings.Join(want, ",") {
+		t.Errorf("maskedEnvFiles() = %v, want %v", got, want)
+	}
+}
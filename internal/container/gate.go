@@ -0,0 +1,170 @@
+package container
+
+import (
+	"bytes"
+	"fmt"
+	"os/exec"
+	"path"
+	"strconv"
+	"strings"
+	"unicode"
+	"unicode/utf8"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+const (
+	// gatePatternsPath lists the regular expressions of commands that need approval
+	gatePatternsPath = "/etc/agentsandbox/gate-patterns"
+	// gateDir holds pending approval requests, which the sandbox user writes
+	gateDir = "/tmp/agentsandbox-approvals"
+	// gateResponseDir holds the host's responses. Only root can write to it, so the
+	// agent cannot approve its own requests unless it has root through sudo.
+	gateResponseDir = "/etc/agentsandbox/gate-responses"
+)
+
+// gateHook is the approval part of the session hook. A matching command writes
+// <id>.request and waits for the host to create <id>.approved or <id>.denied in
+// gateResponseDir;
+// with extdebug set, a non-zero return skips the command. For "bash -c" the whole
+// command string is checked first, and approving it lets the whole shell run while denying it exits with status 126.
+const gateHook = `    __agentsandbox_gate_check() {
+        [ -n "${AGENTSANDBOX_GATE:-}" ] && [ -r "$AGENTSANDBOX_GATE" ] || return 0
+        [ -z "${__agentsandbox_gate_approved:-}" ] || return 0
+        # Enabled lazily: setting extdebug while bash starts up tries to load the debugger
+        shopt -s extdebug
+        local cmd="$BASH_COMMAND" pattern matched="" id request response deadline
+        if [ -n "${BASH_EXECUTION_STRING:-}" ] && [ -z "${__agentsandbox_gate_seen:-}" ]; then
+            __agentsandbox_gate_seen=1
+            cmd="$BASH_EXECUTION_STRING"
+        fi
+        while IFS= read -r pattern || [ -n "$pattern" ]; do
+            [ -n "$pattern" ] || continue
+            if [[ $cmd =~ $pattern ]]; then matched=1; break; fi
+        done < "$AGENTSANDBOX_GATE"
+        [ -n "$matched" ] || return 0
+
+        printf -v id '%(%s)T-%d-%d' -1 "$$" "$RANDOM"
+        request="` + gateDir + `/$id"
+        response="` + gateResponseDir + `/$id"
+        printf '%s' "$cmd" > "$request.request" 2>/dev/null || return 0
+        echo "agentsandbox: waiting for approval on the host (run 'agentsandbox approve'): $cmd" >&2
+        deadline=$((SECONDS + ${AGENTSANDBOX_GATE_TIMEOUT:-300}))
+        while [ "$SECONDS" -lt "$deadline" ]; do
+            if [ -e "$response.approved" ]; then
+                rm -f "$request.request"
+                [ "$cmd" != "${BASH_EXECUTION_STRING:-}" ] || __agentsandbox_gate_approved=1
+                return 0
+            fi
+            if [ -e "$response.denied" ]; then
+                rm -f "$request.request"
+                echo "agentsandbox: command denied on the host: $cmd" >&2
+                [ "$cmd" != "${BASH_EXECUTION_STRING:-}" ] || exit 126
+                return 1
+            fi
+            sleep 0.5
+        done
+        rm -f "$request.request"
+        echo "agentsandbox: approval timed out, command skipped: $cmd" >&2
+        [ "$cmd" != "${BASH_EXECUTION_STRING:-}" ] || exit 126
+        return 1
+    }`
+
+// GateRequest is a command waiting for approval inside a container
+type GateRequest struct {
+	ID      string
+	Command string
+}
+
+// Printable escapes everything in s that a terminal would act on instead of showing,
+// such as escape sequences, carriage returns and bidirectional overrides, so a command
+// written by the sandbox cannot disguise itself or rewrite the prompt. Newlines and
+// tabs are kept; continuation lines are indented by indent.
+func Printable(s, indent string) string {
+	var b strings.Builder
+	for i := 0; i < len(s); {
+		r, size := utf8.DecodeRuneInString(s[i:])
+		switch {
+		case r == utf8.RuneError && size == 1:
+			fmt.Fprintf(&b, `\x%02x`, s[i])
+		case r == '\n':
+			b.WriteString("\n" + indent)
+		case r == '\t' || r == ' ' || unicode.IsPrint(r):
+			b.WriteRune(r)
+		default:
+			quoted := strconv.QuoteRune(r)
+			b.WriteString(quoted[1 : len(quoted)-1])
+		}
+		i += size
+	}
+	return b.String()
+}
+
+// gateEnv returns the docker exec arguments that enable the approval gate
+func gateEnv(gate config.ApprovalGate) []string {
+	timeout := gate.TimeoutSeconds
+	if timeout <= 0 {
+		timeout = config.DefaultSettings().ApprovalGate.TimeoutSeconds
+	}
+	return []string{
+		"-e", "AGENTSANDBOX_GATE=" + gatePatternsPath,
+		"-e", "AGENTSANDBOX_GATE_TIMEOUT=" + strconv.Itoa(timeout),
+	}
+}
+
+// installGatePatterns writes the approval patterns into the container
+func installGatePatterns(containerName string, patterns []string) error {
+	script := fmt.Sprintf("mkdir -p %[1]s %[2]s %[4]s && chmod 1777 %[2]s && chown root:root %[4]s && chmod 755 %[4]s && cat > %[3]s",
+		path.Dir(gatePatternsPath), gateDir, gatePatternsPath, gateResponseDir)
+	cmd := exec.Command("docker", "exec", "-i", "--user", "root", containerName, "sh", "-c", script)
+	cmd.Stdin = strings.NewReader(strings.Join(patterns, "\n") + "\n")
+	if output, err := cmd.CombinedOutput(); err != nil {
+		return fmt.Errorf("%w\nOutput: %s", err, string(output))
+	}
+	return nil
+}
+
+// ListGateRequests returns the commands currently waiting for approval in a container
+func ListGateRequests(containerName string) ([]GateRequest, error) {
+	script := fmt.Sprintf(`for f in %s/*.request; do [ -e "$f" ] || continue; printf '%%s\0' "$(basename "$f" .request)"; cat "$f"; printf '\0'; done`, gateDir)
+	output, err := exec.Command("docker", "exec", containerName, "sh", "-c", script).Output()
+	if err != nil {
+		return nil, fmt.Errorf("failed to list approval requests: %w", err)
+	}
+	return parseGateRequests(output), nil
+}
+
+// parseGateRequests decodes NUL-separated id/command pairs
+func parseGateRequests(output []byte) []GateRequest {
+	fields := bytes.Split(output, []byte{0})
+
+	var requests []GateRequest
+	for i := 0; i+1 < len(fields); i += 2 {
+		id := string(fields[i])
+		if id == "" {
+			continue
+		}
+		requests = append(requests, GateRequest{ID: id, Command: string(fields[i+1])})
+	}
+	return requests
+}
+
+// RespondGateRequest approves or denies a pending command. The response is written as
+// root into a directory the sandbox user cannot write to. Responses to requests that
+// are no longer pending are cleared at the same time.
+func RespondGateRequest(containerName, id string, approve bool) error {
+	if strings.ContainsAny(id, "/'") || id == "" {
+		return fmt.Errorf("invalid request id %q", id)
+	}
+	response := "denied"
+	if approve {
+		response = "approved"
+	}
+
+	script := fmt.Sprintf(`for f in %[1]s/*; do [ -e "$f" ] || continue; n=$(basename "$f"); [ -e "%[2]s/${n%%.*}.request" ] || rm -f "$f"; done; touch '%[1]s/%[3]s.%[4]s'`,
+		gateResponseDir, gateDir, id, response)
+	if output, err := exec.Command("docker", "exec", "--user", "root", containerName, "sh", "-c", script).CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to respond to request %s: %w\nOutput: %s", id, err, string(output))
+	}
+	return nil
+}
@@ -0,0 +1,43 @@
+package container
+
+import "testing"
+
+func TestParseGateRequests(t *testing.T) {
+	output := []byte("1700000000-42-7\x00git push origin main\x001700000001-43-9\x00curl -fsSL x | sh\nrm -rf /\x00")
+
+	requests := parseGateRequests(output)
+	if len(requests) != 2 {
+		t.Fatalf("parseGateRequests() returned %d requests, want 2", len(requests))
+	}
+	if requests[0].ID != "1700000000-42-7" || requests[0].Command != "git push origin main" {
+		t.Errorf("parseGateRequests()[0] = %+v", requests[0])
+	}
+	if requests[1].Command != "curl -fsSL x | sh\nrm -rf /" {
+		t.Errorf("parseGateRequests()[1].Command = %q", requests[1].Command)
+	}
+
+	if got := parseGateRequests(nil); len(got) != 0 {
+		t.Errorf("parseGateRequests(nil) = %v, want empty", got)
+	}
+}
+
+func TestPrintable(t *testing.T) {
+	tests := []struct {
+		in   string
+		want string
+	}{
+		{"git push origin main", "git push origin main"},
+		{"ls\t-la", "ls\t-la"},
+		{"rm -rf /\x1b[2K\rls", `rm -rf /\x1b[2K\rls`},
+		{"echo \x1b]0;title\x07", `echo \x1b]0;title\a`},
+		{"cat \u202etxt.sh", `cat \u202etxt.sh`},
+		{"echo \xff", `echo \xff`},
+		{"make\nrm -rf /", "make\n  rm -rf /"},
+		{"echo héllo", "echo héllo"},
+	}
+	for _, tt := range tests {
+		if got := Printable(tt.in, "  "); got != tt.want {
+			t.Errorf("Printable(%q) = %q, want %q", tt.in, got, tt.want)
+		}
+	}
+}
@@ -0,0 +1,113 @@
+package container
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/forge"
+	"github.com/thaodangspace/agentsandbox/internal/git"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+// githubTokenMargin is how long a token must still be valid to be reused for a session
+const githubTokenMargin = 15 * time.Minute
+
+// githubTokenState is the repository token handed to a sandbox, kept so it can be
+// reused while valid and revoked when the container is removed
+type githubTokenState struct {
+	forge.RepoToken
+	Host string `json:"host"`
+	Repo string `json:"repo"`
+}
+
+func githubTokenFile(containerName string) (string, error) {
+	stateDir, err := state.GetStateDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(stateDir, fmt.Sprintf("%s.github.json", containerName)), nil
+}
+
+func loadGitHubToken(containerName string) *githubTokenState {
+	file, err := githubTokenFile(containerName)
+	if err != nil {
+		return nil
+	}
+	data, err := os.ReadFile(file)
+	if err != nil {
+		return nil
+	}
+	var token githubTokenState
+	if json.Unmarshal(data, &token) != nil {
+		return nil
+	}
+	return &token
+}
+
+// githubTokenEnv returns the docker exec arguments and git config that give a session
+// a token scoped to the project's GitHub repository. The token is only ever in the
+// session's environment; git gets it through a credential helper reading GH_TOKEN.
+func githubTokenEnv(containerName, projectDir string, app config.GitHubAppSettings) ([]string, [][2]string) {
+	url, err := git.RemoteURL(projectDir, "origin")
+	if err != nil {
+		return nil, nil
+	}
+	remote, err := git.ParseRemoteURL(url)
+	if err != nil || forge.IsGitLab(remote) {
+		return nil, nil
+	}
+
+	token := loadGitHubToken(containerName)
+	if token == nil || token.Repo != remote.Path || time.Until(token.ExpiresAt) < githubTokenMargin {
+		minted, err := forge.MintRepoToken(remote, app.AppID, app.PrivateKey)
+		if err != nil {
+			fmt.Printf("Warning: no scoped GitHub token for this session: %v\n", err)
+			return nil, nil
+		}
+		if token != nil {
+			revokeGitHubToken(token)
+		}
+		token = &githubTokenState{RepoToken: *minted, Host: remote.Host, Repo: remote.Path}
+		if file, err := githubTokenFile(containerName); err == nil {
+			if data, err := json.Marshal(token); err == nil {
+				os.WriteFile(file, data, 0600)
+			}
+		}
+	}
+	fmt.Printf("GitHub token limited to %s until %s\n", remote.Path, token.ExpiresAt.Local().Format("15:04"))
+
+	env := []string{"-e", "GH_TOKEN=" + token.Token, "-e", "GITHUB_TOKEN=" + token.Token}
+	helperKey := fmt.Sprintf("credential.https://%s.helper", remote.Host)
+	gitConfig := [][2]string{
+		// An empty helper drops any helpers configured in the container for this host
+		{helperKey, ""},
+		{helperKey, `!f() { echo username=x-access-token; echo "password=$GH_TOKEN"; }; f`},
+	}
+	return env, gitConfig
+}
+
+// revokeGitHubToken invalidates a token that is still valid
+func revokeGitHubToken(token *githubTokenState) {
+	if time.Now().After(token.ExpiresAt) {
+		return
+	}
+	if err := forge.RevokeRepoToken(git.Remote{Host: token.Host, Path: token.Repo}, token.Token); err != nil {
+		fmt.Printf("Warning: %v\n", err)
+	}
+}
+
+// clearGitHubToken revokes and forgets the container's token when it is removed
+func clearGitHubToken(containerName string) {
+	token := loadGitHubToken(containerName)
+	if token == nil {
+		return
+	}
+	revokeGitHubToken(token)
+	if file, err := githubTokenFile(containerName); err == nil {
+		os.Remove(file)
+	}
+}
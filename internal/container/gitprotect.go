@@ -0,0 +1,52 @@
+package container
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+)
+
+// Git protection modes for the project's .git directory
+const (
+	// GitProtectHooks mounts .git/hooks and .git/config read-only, so the agent cannot
+	// install hooks that later run on the host or point remotes elsewhere
+	GitProtectHooks = "hooks"
+	// GitProtectReadOnly mounts all of .git read-only; the agent cannot commit
+	GitProtectReadOnly = "readonly"
+)
+
+// ValidateGitProtection checks a git_protection setting or --protect-git value
+func ValidateGitProtection(mode string) error {
+	switch mode {
+	case "", "off", GitProtectHooks, GitProtectReadOnly:
+		return nil
+	}
+	return fmt.Errorf("invalid git protection %q: use off, hooks or readonly", mode)
+}
+
+// gitProtectionMounts returns the read-only mounts protecting dir's .git directory.
+// Worktrees, whose .git is a file pointing elsewhere, are left alone.
+func gitProtectionMounts(dir, mode string) []string {
+	gitDir := filepath.Join(dir, ".git")
+	if info, err := os.Stat(gitDir); err != nil || !info.IsDir() {
+		return nil
+	}
+
+	var paths []string
+	switch mode {
+	case GitProtectReadOnly:
+		paths = []string{gitDir}
+	case GitProtectHooks:
+		for _, name := range []string{"hooks", "config"} {
+			if _, err := os.Stat(filepath.Join(gitDir, name)); err == nil {
+				paths = append(paths, filepath.Join(gitDir, name))
+			}
+		}
+	}
+
+	var args []string
+	for _, path := range paths {
+		args = append(args, "-v", fmt.Sprintf("%s:%s:ro", path, path))
+	}
+	return args
+}
@@ -0,0 +1,44 @@
+package container
+
+import (
+	"os"
+	"path/filepath"
+	"reflect"
+	"testing"
+)
+
+func TestGitProtectionMounts(t *testing.T) {
+	dir := t.TempDir()
+	gitDir := filepath.Join(dir, ".git")
+	if err := os.MkdirAll(filepath.Join(gitDir, "hooks"), 0755); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(gitDir, "config"), []byte("[core]\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	hooks := filepath.Join(gitDir, "hooks")
+	config := filepath.Join(gitDir, "config")
+	want := []string{"-v", hooks + ":" + hooks + ":ro", "-v", config + ":" + config + ":ro"}
+	if got := gitProtectionMounts(dir, GitProtectHooks); !reflect.DeepEqual(got, want) {
+		t.Errorf("hooks mounts = %v, want %v", got, want)
+	}
+
+	want = []string{"-v", gitDir + ":" + gitDir + ":ro"}
+	if got := gitProtectionMounts(dir, GitProtectReadOnly); !reflect.DeepEqual(got, want) {
+		t.Errorf("readonly mounts = %v, want %v", got, want)
+	}
+
+	if got := gitProtectionMounts(dir, "off"); len(got) != 0 {
+		t.Errorf("off mounts = %v, want none", got)
+	}
+
+	// A worktree's .git is a file
+	worktree := t.TempDir()
+	if err := os.WriteFile(filepath.Join(worktree, ".git"), []byte("gitdir: /elsewhere\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	if got := gitProtectionMounts(worktree, GitProtectReadOnly); len(got) != 0 {
+		t.Errorf("worktree mounts = %v, want none", got)
+	}
+}
@@ -0,0 +1,83 @@
+package container
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"regexp"
+	"runtime"
+)
+
+// x11Display matches displays served from a local socket, such as ":0" or "unix:1.0".
+// TCP displays like "localhost:10.0" from ssh -X are not reachable from a container.
+var x11Display = regexp.MustCompile(`^(unix)?:[0-9]+(\.[0-9]+)?$`)
+
+// guiArgs returns the docker run arguments that share the host's Wayland and X11
+// sockets with the sandbox, so headed browsers and GUI tools can open windows
+func guiArgs(username string) ([]string, error) {
+	if runtime.GOOS != "linux" {
+		return nil, fmt.Errorf("--gui needs a Linux host with a Wayland or X11 session")
+	}
+
+	uid := os.Getuid()
+	runtimeDir := fmt.Sprintf("/run/user/%d", uid)
+	var args []string
+	found := false
+
+	if display, hostRuntime := os.Getenv("WAYLAND_DISPLAY"), os.Getenv("XDG_RUNTIME_DIR"); display != "" && hostRuntime != "" {
+		socket := display
+		if !filepath.IsAbs(socket) {
+			socket = filepath.Join(hostRuntime, display)
+		}
+		if _, err := os.Stat(socket); err == nil {
+			args = append(args,
+				"-v", fmt.Sprintf("%s:%s/%s", socket, runtimeDir, filepath.Base(socket)),
+				"-e", "WAYLAND_DISPLAY="+filepath.Base(socket),
+				"-e", "XDG_RUNTIME_DIR="+runtimeDir,
+				"-e", "XDG_SESSION_TYPE=wayland",
+			)
+			found = true
+		}
+	}
+
+	if display := os.Getenv("DISPLAY"); x11Display.MatchString(display) {
+		if _, err := os.Stat("/tmp/.X11-unix"); err == nil {
+			args = append(args, "-v", "/tmp/.X11-unix:/tmp/.X11-unix", "-e", "DISPLAY="+display)
+			if xauth := xauthorityFile(); xauth != "" {
+				target := fmt.Sprintf("/home/%s/.Xauthority", username)
+				args = append(args, "-v", xauth+":"+target+":ro", "-e", "XAUTHORITY="+target)
+			}
+			found = true
+		}
+	} else if display != "" {
+		fmt.Printf("Warning: DISPLAY=%s is not a local X11 display and cannot be shared with the sandbox\n", display)
+	}
+
+	if !found {
+		return nil, fmt.Errorf("--gui found no Wayland or X11 socket; run it from a graphical session")
+	}
+
+	// GPU acceleration where available, and room for Chromium's shared memory
+	if _, err := os.Stat("/dev/dri"); err == nil {
+		args = append(args, "--device", "/dev/dri")
+	}
+	return append(args, "--shm-size", "1g"), nil
+}
+
+// xauthorityFile returns the host's X authority file, if there is one
+func xauthorityFile() string {
+	if path := os.Getenv("XAUTHORITY"); path != "" {
+		if _, err := os.Stat(path); err == nil {
+			return path
+		}
+	}
+	home, err := os.UserHomeDir()
+	if err != nil {
+		return ""
+	}
+	path := filepath.Join(home, ".Xauthority")
+	if _, err := os.Stat(path); err != nil {
+		return ""
+	}
+	return path
+}
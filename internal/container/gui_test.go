@@ -0,0 +1,16 @@
+package container
+
+import "testing"
+
+func TestX11Display(t *testing.T) {
+	for _, display := range []string{":0", ":1.0", "unix:2"} {
+		if !x11Display.MatchString(display) {
+			t.Errorf("%q should be a local display", display)
+		}
+	}
+	for _, display := range []string{"localhost:10.0", "host:0", "", ":"} {
+		if x11Display.MatchString(display) {
+			t.Errorf("%q should not be a local display", display)
+		}
+	}
+}
@@ -0,0 +1,48 @@
+package container
+
+import (
+	"context"
+	"fmt"
+	"io"
+	"os"
+	"os/exec"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+// RunHeadless runs a single prompt through the agent non-interactively, streaming its
+// standard output and standard error to stdout and stderr. Cancelling ctx kills the
+// docker exec.
+func RunHeadless(ctx context.Context, containerName, currentDir string, agent config.Agent, skipPermissionFlag, prompt string, stdout, stderr io.Writer) error {
+	username := os.Getenv("USER")
+	if username == "" {
+		username = "ubuntu"
+	}
+
+	limitBandwidth(containerName)
+
+	args := []string{
+		"exec",
+		"--user", username,
+		"-e", fmt.Sprintf("HOME=/home/%s", username),
+	}
+	args = append(args, localeEnvArgs()...)
+	args = append(args,
+		"-w", currentDir,
+		containerName,
+		// Pass the prompt as an argument so it never goes through shell parsing
+		"/bin/bash", "-lc", `export PATH="$HOME/.cargo/bin:$HOME/.local/bin:$PATH" && exec "$@"`, "bash",
+	)
+	args = append(args, agent.HeadlessArgs(prompt)...)
+	if skipPermissionFlag != "" {
+		args = append(args, skipPermissionFlag)
+	}
+
+	cmd := exec.CommandContext(ctx, "docker", args...)
+	cmd.Stdout = stdout
+	cmd.Stderr = stderr
+	if err := cmd.Run(); err != nil {
+		return fmt.Errorf("%s exited with error: %w", agent.DisplayName(), err)
+	}
+	return nil
+}
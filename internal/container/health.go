@@ -0,0 +1,241 @@
+package container
+
+import (
+	"bufio"
+	"fmt"
+	"os"
+	"os/exec"
+	"regexp"
+	"strings"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+// AgentHealth describes whether an agent is ready to run inside a container
+type AgentHealth struct {
+	BinaryOK bool
+	Version  string
+	AuthOK   bool
+}
+
+// Healthy reports whether the agent binary is present and authenticated
+func (h AgentHealth) Healthy() bool {
+	return h.BinaryOK && h.AuthOK
+}
+
+// containerShell runs a bash login command inside the container as the sandbox user
+func containerShell(containerName, script string) *exec.Cmd {
+	username := os.Getenv("USER")
+	if username == "" {
+		username = "ubuntu"
+	}
+
+	return exec.Command("docker", "exec",
+		"--user", username,
+		"-e", fmt.Sprintf("HOME=/home/%s", username),
+		containerName, "/bin/bash", "-lc",
+		"export PATH=\"$HOME/.cargo/bin:$HOME/.local/bin:$PATH\" && "+script)
+}
+
+// authMarker is printed by the health probe when agent credentials are present
+const authMarker = "__agentsandbox_auth_ok"
+
+// CheckAgentHealth probes the agent binary and its credentials inside the container
+// with a single docker exec
+func CheckAgentHealth(containerName string, agent config.Agent) AgentHealth {
+	var health AgentHealth
+
+	var probes []string
+	for _, path := range agent.AuthPaths() {
+		probes = append(probes, fmt.Sprintf("test -s \"$HOME/%s\"", path))
+	}
+	for _, env := range agent.APIKeyEnvs() {
+		probes = append(probes, fmt.Sprintf("test -n \"${%s:-}\"", env))
+	}
+
+	script := fmt.Sprintf("%s --version", agent.Command())
+	if len(probes) > 0 {
+		script += fmt.Sprintf(" && { { %s; } && echo %s || true; }", strings.Join(probes, " || "), authMarker)
+	} else {
+		health.AuthOK = true
+	}
+
+	output, err := containerShell(containerName, script).Output()
+	if err != nil {
+		return health
+	}
+
+	health.BinaryOK = true
+	out := string(output)
+	if strings.Contains(out, authMarker) {
+		health.AuthOK = true
+		out = strings.Replace(out, authMarker, "", 1)
+	}
+	health.Version = strings.TrimSpace(out)
+
+	return health
+}
+
+// ensureAgentReady runs the health check and lets the user repair a broken agent before attaching.
+// A recent passing check recorded in warm is reused. It returns false when the attach should be aborted.
+func ensureAgentReady(containerName, currentDir string, agent config.Agent, warm *warmState) (bool, error) {
+	if warm.recentlyHealthy(string(agent)) {
+		return true, nil
+	}
+
+	health := CheckAgentHealth(containerName, agent)
+	if health.Healthy() {
+		warm.Healthy[string(agent)] = time.Now()
+		return true, nil
+	}
+
+	if !health.BinaryOK {
+		fmt.Printf("\n⚠️  %s (%s) was not found in container %s.\n", agent.DisplayName(), agent.Command(), containerName)
+	} else {
+		fmt.Printf("\n⚠️  %s %s is installed but no credentials were found in container %s.\n", agent.DisplayName(), health.Version, containerName)
+	}
+
+	if !stdinIsTerminal() {
+		fmt.Println("Continuing anyway because the session is not interactive.")
+		return true, nil
+	}
+
+	fmt.Println("Choose how to proceed:")
+	if health.BinaryOK {
+		fmt.Printf("  1) Run the %s login flow now\n", agent.DisplayName())
+	} else {
+		fmt.Println("  1) Rebuild the image on the next start (removes the cached image)")
+	}
+	fmt.Println("  2) Open a shell in the container to fix it manually")
+	fmt.Println("  3) Continue anyway")
+	fmt.Print("Enter choice (or press Enter to cancel): ")
+
+	reader := bufio.NewReader(os.Stdin)
+	input, _ := reader.ReadString('\n')
+
+	switch strings.TrimSpace(input) {
+	case "1":
+		if health.BinaryOK {
+			if err := runInteractive(containerName, currentDir, agent.LoginCommand()); err != nil {
+				fmt.Printf("Warning: login flow exited with error: %v\n", err)
+			}
+			return true, nil
+		}
+		return false, removeContainerImage(containerName)
+	case "2":
+		if err := runInteractive(containerName, currentDir, ""); err != nil {
+			fmt.Printf("Warning: shell exited with error: %v\n", err)
+		}
+		return true, nil
+	case "3":
+		return true, nil
+	default:
+		return false, nil
+	}
+}
+
+// runInteractive runs a command (or a login shell when command is empty) attached to the terminal
+func runInteractive(containerName, currentDir, command string) error {
+	username := os.Getenv("USER")
+	if username == "" {
+		username = "ubuntu"
+	}
+
+	args := []string{
+		"exec", "-it",
+		"--user", username,
+		"-e", fmt.Sprintf("HOME=/home/%s", username),
+	}
+	if currentDir != "" {
+		args = append(args, "-w", currentDir)
+	}
+	args = append(args, containerName, "/bin/bash", "-l")
+	if command != "" {
+		args = append(args, "-c", "export PATH=\"$HOME/.cargo/bin:$HOME/.local/bin:$PATH\" && "+command)
+	}
+
+	cmd := exec.Command("docker", args...)
+	cmd.Stdin = os.Stdin
+	cmd.Stdout = os.Stdout
+	cmd.Stderr = os.Stderr
+	return cmd.Run()
+}
+
+// removeContainerImage untags the image backing a container so the next start rebuilds it
+func removeContainerImage(containerName string) error {
+	output, err := exec.Command("docker", "inspect", "-f", "{{.Config.Image}}", containerName).Output()
+	if err != nil {
+		return fmt.Errorf("failed to inspect container image: %w", err)
+	}
+
+	imageName := strings.TrimSpace(string(output))
+	if out, err := exec.Command("docker", "rmi", "-f", imageName).CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to remove image %s: %w\nOutput: %s", imageName, err, string(out))
+	}
+
+	fmt.Printf("Removed image %s.\n", imageName)
+	fmt.Println("Run 'agentsandbox cleanup' and then 'agentsandbox' to create a fresh container with a rebuilt image.")
+	return nil
+}
+
+// stdinIsTerminal reports whether stdin is attached to a terminal
+func stdinIsTerminal() bool {
+	info, err := os.Stdin.Stat()
+	if err != nil {
+		return false
+	}
+	return info.Mode()&os.ModeCharDevice != 0
+}
+
+// healthCmd fails when the container can no longer fork or write to /tmp, as happens
+// after a fork bomb or when memory runs out
+const healthCmd = "touch /tmp/.agentsandbox-health && rm -f /tmp/.agentsandbox-health"
+
+var restartPattern = regexp.MustCompile(`^(no|always|unless-stopped|on-failure(:[0-9]+)?)$`)
+
+// ValidateRestartPolicy checks that policy is a docker restart policy
+func ValidateRestartPolicy(policy string) error {
+	if policy != "" && !restartPattern.MatchString(policy) {
+		return fmt.Errorf("invalid restart policy %q: use no, always, unless-stopped or on-failure[:N]", policy)
+	}
+	return nil
+}
+
+// runPolicyArgs returns the docker run arguments for the health check and restart policy
+func runPolicyArgs(settings config.ContainerSettings) ([]string, error) {
+	if err := ValidateRestartPolicy(settings.Restart); err != nil {
+		return nil, err
+	}
+
+	var args []string
+	if settings.Restart != "" {
+		args = append(args, "--restart", settings.Restart)
+	}
+	if settings.HealthCheck {
+		args = append(args,
+			"--health-cmd", healthCmd,
+			"--health-interval", "30s",
+			"--health-timeout", "10s",
+			"--health-retries", "3",
+		)
+	}
+	return args, nil
+}
+
+// parseHealth extracts the health check result from docker's status, e.g.
+// "Up 2 hours (unhealthy)". It is empty for containers without a health check.
+func parseHealth(status string) string {
+	_, rest, ok := strings.Cut(status, "(")
+	if !ok {
+		return ""
+	}
+	health := strings.TrimSuffix(rest, ")")
+	switch health {
+	case "healthy", "unhealthy":
+		return health
+	case "health: starting":
+		return "starting"
+	}
+	return ""
+}
@@ -0,0 +1,32 @@
+package container
+
+import "testing"
+
+func TestParseHealth(t *testing.T) {
+	tests := map[string]string{
+		"Up 2 hours":                      "",
+		"Up 2 hours (healthy)":            "healthy",
+		"Up 5 minutes (unhealthy)":        "unhealthy",
+		"Up 3 seconds (health: starting)": "starting",
+		"Exited (137) 2 minutes ago":      "",
+		"Up 1 minute (Paused)":            "",
+	}
+	for status, want := range tests {
+		if got := parseHealth(status); got != want {
+			t.Errorf("parseHealth(%q) = %q, want %q", status, got, want)
+		}
+	}
+}
+
+func TestValidateRestartPolicy(t *testing.T) {
+	for _, policy := range []string{"", "no", "always", "unless-stopped", "on-failure", "on-failure:3"} {
+		if err := ValidateRestartPolicy(policy); err != nil {
+			t.Errorf("ValidateRestartPolicy(%q) = %v", policy, err)
+		}
+	}
+	for _, policy := range []string{"sometimes", "on-failure:x", "always "} {
+		if err := ValidateRestartPolicy(policy); err == nil {
+			t.Errorf("ValidateRestartPolicy(%q) accepted an invalid policy", policy)
+		}
+	}
+}
@@ -0,0 +1,55 @@
+package container
+
+import (
+	"fmt"
+	"os/exec"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// StopIdleContainers stops running sandboxes that have no attached sessions and have not
+// been used for at least idle. It returns the names of the stopped containers.
+func StopIdleContainers(idle time.Duration) ([]string, error) {
+	containers, err := ListAllContainers()
+	if err != nil || len(containers) == 0 {
+		return nil, err
+	}
+
+	names := make([]string, len(containers))
+	for i, c := range containers {
+		names[i] = c.Name
+	}
+	args := append([]string{"inspect", "-f", "{{.Name}} {{.Id}} {{len .ExecIDs}} {{.State.StartedAt}}"}, names...)
+	output, _ := exec.Command("docker", args...).Output()
+
+	var stopped []string
+	for _, line := range strings.Split(string(output), "\n") {
+		fields := strings.Fields(line)
+		if len(fields) != 4 {
+			continue
+		}
+		name, id := strings.TrimPrefix(fields[0], "/"), fields[1]
+
+		// Any exec means a session (or setup) is in progress
+		if execs, err := strconv.Atoi(fields[2]); err != nil || execs > 0 {
+			continue
+		}
+
+		lastUsed, _ := time.Parse(time.RFC3339Nano, fields[3])
+		if used := loadWarmState(name, id).LastUsed; used.After(lastUsed) {
+			lastUsed = used
+		}
+		if time.Since(lastUsed) < idle {
+			continue
+		}
+
+		if err := StopContainer(name); err != nil {
+			fmt.Printf("Warning: %v\n", err)
+			continue
+		}
+		stopped = append(stopped, name)
+	}
+
+	return stopped, nil
+}
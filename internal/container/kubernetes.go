@@ -0,0 +1,66 @@
+package container
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+// sandboxKubeconfig is where the scoped kubeconfig is mounted. It sits outside the home
+// directory so docker does not create a root-owned ~/.kube that kubectl cannot cache in.
+const sandboxKubeconfig = "/etc/agentsandbox/kubeconfig"
+
+func kubeconfigFile(containerName string) (string, error) {
+	stateDir, err := state.GetStateDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(stateDir, fmt.Sprintf("%s.kubeconfig", containerName)), nil
+}
+
+// kubectlViewArgs returns the host kubectl arguments printing a self-contained
+// kubeconfig with only the configured context, its cluster and its user
+func kubectlViewArgs(k config.KubernetesSettings) []string {
+	args := []string{"config", "view", "--minify", "--flatten", "--context", k.Context}
+	if k.Kubeconfig != "" {
+		args = append(args, "--kubeconfig", k.Kubeconfig)
+	}
+	return args
+}
+
+// writeScopedKubeconfig extracts the configured context with the host's kubectl and
+// stores it in the state directory for mounting
+func writeScopedKubeconfig(containerName string, k config.KubernetesSettings) (string, error) {
+	output, err := exec.Command("kubectl", kubectlViewArgs(k)...).Output()
+	if err != nil {
+		return "", fmt.Errorf("failed to read kubeconfig context %s: %w%s", k.Context, err, exitStderr(err))
+	}
+	file, err := kubeconfigFile(containerName)
+	if err != nil {
+		return "", err
+	}
+	if err := os.WriteFile(file, output, 0600); err != nil {
+		return "", fmt.Errorf("failed to write kubeconfig: %w", err)
+	}
+	return file, nil
+}
+
+// kubeconfigArgs mounts the scoped kubeconfig read-only, so the agent cannot add
+// contexts or switch to another cluster
+func kubeconfigArgs(file string) []string {
+	return []string{
+		"-v", fmt.Sprintf("%s:%s:ro", file, sandboxKubeconfig),
+		"-e", "KUBECONFIG=" + sandboxKubeconfig,
+	}
+}
+
+// clearKubeconfig removes the scoped kubeconfig of a removed container
+func clearKubeconfig(containerName string) {
+	if file, err := kubeconfigFile(containerName); err == nil {
+		os.Remove(file)
+	}
+}
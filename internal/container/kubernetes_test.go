@@ -0,0 +1,33 @@
+package container
+
+import (
+	"reflect"
+	"testing"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+func TestKubectlViewArgs(t *testing.T) {
+	got := kubectlViewArgs(config.KubernetesSettings{Context: "kind-test"})
+	want := []string{"config", "view", "--minify", "--flatten", "--context", "kind-test"}
+	if !reflect.DeepEqual(got, want) {
+		t.Errorf("kubectlViewArgs() = %v, want %v", got, want)
+	}
+
+	got = kubectlViewArgs(config.KubernetesSettings{Context: "staging", Kubeconfig: "/home/me/.kube/staging"})
+	want = []string{"config", "view", "--minify", "--flatten", "--context", "staging", "--kubeconfig", "/home/me/.kube/staging"}
+	if !reflect.DeepEqual(got, want) {
+		t.Errorf("kubectlViewArgs() = %v, want %v", got, want)
+	}
+}
+
+func TestKubeconfigArgs(t *testing.T) {
+	got := kubeconfigArgs("/state/agentsandbox-app.kubeconfig")
+	want := []string{
+		"-v", "/state/agentsandbox-app.kubeconfig:/etc/agentsandbox/kubeconfig:ro",
+		"-e", "KUBECONFIG=/etc/agentsandbox/kubeconfig",
+	}
+	if !reflect.DeepEqual(got, want) {
+		t.Errorf("kubeconfigArgs() = %v, want %v", got, want)
+	}
+}
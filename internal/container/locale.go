@@ -0,0 +1,105 @@
+package container
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+)
+
+// defaultLang is used in the sandbox when the host does not set a locale, so tools
+// still print UTF-8 box drawing and emoji
+const defaultLang = "C.UTF-8"
+
+// localeVars are the host locale variables passed into the sandbox
+var localeVars = []string{
+	"LANG", "LANGUAGE", "LC_ALL", "LC_CTYPE", "LC_NUMERIC", "LC_TIME", "LC_COLLATE",
+	"LC_MONETARY", "LC_MESSAGES", "LC_PAPER", "LC_NAME", "LC_ADDRESS", "LC_TELEPHONE",
+	"LC_MEASUREMENT", "LC_IDENTIFICATION",
+}
+
+// localeEnvArgs returns docker -e arguments carrying the host's timezone and locale
+func localeEnvArgs() []string {
+	var args []string
+	if tz := hostTimezone(); tz != "" {
+		args = append(args, "-e", "TZ="+tz)
+	}
+	if os.Getenv("LANG") == "" {
+		args = append(args, "-e", "LANG="+defaultLang)
+	}
+	for _, name := range localeVars {
+		if value := os.Getenv(name); value != "" {
+			args = append(args, "-e", name+"="+value)
+		}
+	}
+	return args
+}
+
+// terminalEnvArgs returns docker -e arguments describing the host terminal, which
+// docker exec would otherwise report as a plain xterm
+func terminalEnvArgs() []string {
+	term := os.Getenv("TERM")
+	if term == "" || term == "dumb" {
+		term = "xterm-256color"
+	}
+	args := []string{"-e", "TERM=" + term}
+	if colorTerm := os.Getenv("COLORTERM"); colorTerm != "" {
+		args = append(args, "-e", "COLORTERM="+colorTerm)
+	}
+	return args
+}
+
+// hostTimezone returns the host's IANA timezone name, from $TZ or /etc/localtime
+func hostTimezone() string {
+	if tz := strings.TrimPrefix(os.Getenv("TZ"), ":"); tz != "" {
+		return tz
+	}
+	if target, err := os.Readlink("/etc/localtime"); err == nil {
+		if tz := timezoneFromPath(target); tz != "" {
+			return tz
+		}
+	}
+	if data, err := os.ReadFile("/etc/timezone"); err == nil {
+		return strings.TrimSpace(string(data))
+	}
+	return ""
+}
+
+// timezoneFromPath extracts the zone name from the target of /etc/localtime, such as
+// /usr/share/zoneinfo/Europe/Berlin or /var/db/timezone/zoneinfo/Europe/Berlin on macOS
+func timezoneFromPath(path string) string {
+	_, zone, ok := strings.Cut(filepath.ToSlash(path), "zoneinfo/")
+	if !ok {
+		return ""
+	}
+	return zone
+}
+
+// ensureLocale generates the host's locale in the container if it is missing, so
+// programs do not fall back to ASCII with "cannot set locale" warnings
+func ensureLocale(containerName string) error {
+	lang := os.Getenv("LC_ALL")
+	if lang == "" {
+		lang = os.Getenv("LANG")
+	}
+	name, charset, ok := strings.Cut(lang, ".")
+	if !ok || name == "C" || name == "POSIX" || strings.ContainsAny(lang, "'\"\\/ ") {
+		return nil
+	}
+
+	// locale -a lists en_US.UTF-8 as en_US.utf8. Debian's locale-gen reads /etc/locale.gen
+	// while Ubuntu's takes the locale as an argument.
+	script := fmt.Sprintf(`locale -a 2>/dev/null | grep -qix '%[1]s\.%[3]s' && exit 0
+command -v locale-gen >/dev/null || exit 0
+if [ -f /etc/locale.gen ]; then
+  sed -i 's/^# *\(%[1]s\.%[2]s %[2]s\)/\1/' /etc/locale.gen
+  grep -q '^%[1]s\.%[2]s ' /etc/locale.gen || echo '%[1]s.%[2]s %[2]s' >> /etc/locale.gen
+fi
+locale-gen '%[1]s.%[2]s' >/dev/null`, name, charset, strings.ReplaceAll(charset, "-", ""))
+
+	if output, err := exec.Command("docker", "exec", "--user", "root", containerName, "sh", "-c", script).CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to generate locale %s: %w\nOutput: %s", lang, err, string(output))
+	}
+	return nil
+}
@@ -0,0 +1,18 @@
+package container
+
+import "testing"
+
+func TestTimezoneFromPath(t *testing.T) {
+	tests := map[string]string{
+		"/usr/share/zoneinfo/Europe/Berlin":          "Europe/Berlin",
+		"../usr/share/zoneinfo/America/New_York":     "America/New_York",
+		"/var/db/timezone/zoneinfo/Asia/Ho_Chi_Minh": "Asia/Ho_Chi_Minh",
+		"/usr/share/zoneinfo/UTC":                    "UTC",
+		"/etc/localtime.bak":                         "",
+	}
+	for path, want := range tests {
+		if got := timezoneFromPath(path); got != want {
+			t.Errorf("timezoneFromPath(%q) = %q, want %q", path, got, want)
+		}
+	}
+}
@@ -1,12 +1,18 @@
 package container
 
 import (
+	"encoding/json"
 	"fmt"
 	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/git"
+	"github.com/thaodangspace/agentsandbox/internal/share"
+	"github.com/thaodangspace/agentsandbox/internal/state"
 	"os"
 	"os/exec"
 	"path/filepath"
+	"strconv"
 	"strings"
+	"time"
 )
 
 // CheckDockerAvailability checks if Docker is installed and running
@@ -38,16 +44,87 @@ func CleanupContainers(currentDir string) error {
 			continue
 		}
 
-		fmt.Printf("Removing container %s\n", name)
-		rmCmd := exec.Command("docker", "rm", "-f", name)
-		if err := rmCmd.Run(); err != nil {
-			return fmt.Errorf("failed to remove container %s: %w", name, err)
+		if err := RemoveContainer(name); err != nil {
+			return err
 		}
 	}
 
 	return nil
 }
 
+// RemoveContainer removes a container together with the state kept for it
+func RemoveContainer(name string) error {
+	fmt.Printf("Removing container %s\n", name)
+	network := containerNetwork(name)
+	removeServices(name)
+	rmCmd := exec.Command("docker", "rm", "-f", name)
+	if err := rmCmd.Run(); err != nil {
+		return fmt.Errorf("failed to remove container %s: %w", name, err)
+	}
+	if network != "" {
+		removeNetworkIfUnused(network)
+	}
+	stopSync(name)
+
+	clearWarmState(name)
+	clearGitHubToken(name)
+	clearKubeconfig(name)
+	clearSecretOverlays(name)
+	if err := share.RemoveShareDir(name); err != nil {
+		fmt.Printf("Warning: failed to remove share directory: %v\n", err)
+	}
+	removeAutoWorktree(name)
+	if err := state.RemoveAliases(name); err != nil {
+		fmt.Printf("Warning: failed to forget container name: %v\n", err)
+	}
+	return nil
+}
+
+// ResolveName maps a session name given with --name to its container. Anything that
+// is not a known name is returned unchanged, so real container names keep working.
+func ResolveName(name string) string {
+	if containerName := state.ResolveAlias(name); containerName != "" {
+		return containerName
+	}
+	if strings.HasPrefix(name, "agentsandbox-") {
+		return name
+	}
+
+	// Fall back to the label in case the state file was lost
+	output, err := exec.Command("docker", "ps", "-a", "--filter", fmt.Sprintf("label=%s=%s", nameLabel, name), "--format", "{{.Names}}").Output()
+	if err == nil {
+		if names := strings.Fields(string(output)); len(names) == 1 {
+			return names[0]
+		}
+	}
+	return name
+}
+
+// removeAutoWorktree deletes the worktree created for a container by always_worktree
+func removeAutoWorktree(name string) {
+	worktreePath, err := state.LoadContainerWorktree(name)
+	if err != nil || worktreePath == "" {
+		return
+	}
+
+	if _, err := os.Stat(worktreePath); err == nil {
+		if err := git.RemoveWorktree(worktreePath); err != nil {
+			// Keep the record so a later cleanup can retry once the changes are dealt with
+			fmt.Printf("Warning: %v\n", err)
+			return
+		}
+		fmt.Printf("Removed worktree %s\n", worktreePath)
+
+		if err := git.DeleteMergedBranch(worktreePath, filepath.Base(worktreePath)); err != nil {
+			fmt.Printf("Note: %v\n", err)
+		}
+	}
+
+	if err := state.ClearContainerWorktree(name); err != nil {
+		fmt.Printf("Warning: failed to clear worktree state: %v\n", err)
+	}
+}
+
 // ListContainers returns a list of containers for the current directory
 func ListContainers(currentDir string) ([]string, error) {
 	dirName := Sanitize(filepath.Base(currentDir))
@@ -55,16 +132,14 @@ func ListContainers(currentDir string) ([]string, error) {
 		return []string{}, nil
 	}
 
-	cmd := exec.Command("docker", "ps", "-a", "--format", "{{.Names}}")
+	cmd := exec.Command("docker", "ps", "-a", "--format", psNamesFormat)
 	output, err := cmd.Output()
 	if err != nil {
 		return nil, fmt.Errorf("failed to list containers: %w", err)
 	}
 
 	var containers []string
-	names := strings.Split(string(output), "\n")
-	for _, name := range names {
-		name = strings.TrimSpace(name)
+	for _, name := range sandboxNames(output) {
 		if isContainerForDir(name, dirName) {
 			containers = append(containers, name)
 		}
@@ -73,6 +148,22 @@ func ListContainers(currentDir string) ([]string, error) {
 	return containers, nil
 }
 
+// ContainerNames returns the names of every container known to docker, running or not
+func ContainerNames() (map[string]bool, error) {
+	output, err := exec.Command("docker", "ps", "-a", "--format", "{{.Names}}").Output()
+	if err != nil {
+		return nil, fmt.Errorf("failed to list containers: %w", err)
+	}
+
+	names := make(map[string]bool)
+	for _, name := range strings.Split(string(output), "\n") {
+		if name = strings.TrimSpace(name); name != "" {
+			names[name] = true
+		}
+	}
+	return names, nil
+}
+
 // isContainerForDir returns true if the container name belongs to the given directory.
 // It supports both the current "agentsandbox-{dir}" format and the legacy
 // format that inserted the directory between dashes ("-dir-").
@@ -113,39 +204,256 @@ func FindExistingContainer(currentDir string, agent config.Agent) (string, error
 	return "", nil
 }
 
+// FindProjectContainer finds the container for dir, also matching a container created for
+// a parent directory in the same repository, so running from a subdirectory reuses it.
+// It returns the container and the project directory it was created for.
+func FindProjectContainer(dir string, agent config.Agent) (string, string, error) {
+	if name, err := FindExistingContainer(dir, agent); err != nil || name != "" {
+		return name, dir, err
+	}
+
+	// Worktrees have their own root, so they never match the main checkout's container
+	root := git.RepoRoot(dir)
+
+	// Labelled containers record their project directory; prefer the closest one
+	containers, err := ListSandboxes(true)
+	if err != nil {
+		return "", "", err
+	}
+	var best ContainerInfo
+	for _, info := range containers {
+		if !IsWithin(info.Directory, dir) || (root != "" && !IsWithin(root, info.Directory)) {
+			continue
+		}
+		if len(info.Directory) > len(best.Directory) {
+			best = info
+		}
+	}
+	if best.Name != "" {
+		return best.Name, best.Directory, nil
+	}
+
+	// Fall back to the container named after the repository root
+	if root != "" && root != dir {
+		name, err := FindExistingContainer(root, agent)
+		if err != nil || name == "" {
+			return "", "", err
+		}
+		return name, root, nil
+	}
+	return "", "", nil
+}
+
+// IsWithin reports whether path is dir or one of its subdirectories
+func IsWithin(dir, path string) bool {
+	if dir == "" || path == "" {
+		return false
+	}
+	rel, err := filepath.Rel(dir, path)
+	return err == nil && rel != ".." && !strings.HasPrefix(rel, "../")
+}
+
 // ContainerInfo represents information about a running container
 type ContainerInfo struct {
 	Project   string
 	Name      string
 	Directory string
+	// Alias is the session name given with --name, if any
+	Alias string
+	// Agent is the agent the container was created for, when labelled
+	Agent string
+	// State is docker's container state, e.g. running or exited
+	State string
+	// Status is docker's human-readable status, e.g. "Up 2 hours"
+	Status string
+	// Health is healthy, unhealthy or starting; empty without a health check
+	Health string
+	// Created is when the container was created
+	Created time.Time
+	// ExitCode and Finished describe the last run of a stopped container
+	ExitCode int
+	Finished time.Time
 }
 
+// Labels set on sandbox containers so they can be listed without inspecting each one
+const (
+	projectLabel = "agentsandbox.project"
+	agentLabel   = "agentsandbox.agent"
+	nameLabel    = "agentsandbox.name"
+	// workspaceLabel lists extra workspace directories separated by the path list separator
+	workspaceLabel = "agentsandbox.workspace"
+)
+
 // ListAllContainers returns a list of all running agentsandbox containers
 func ListAllContainers() ([]ContainerInfo, error) {
-	cmd := exec.Command("docker", "ps", "--format", "{{.Names}}")
+	return ListSandboxes(false)
+}
+
+// ListProjectContainers returns the containers for a directory, including stopped ones
+func ListProjectContainers(currentDir string) ([]ContainerInfo, error) {
+	dirName := Sanitize(filepath.Base(currentDir))
+	all, err := ListSandboxes(true)
+	if err != nil {
+		return nil, err
+	}
+
+	var containers []ContainerInfo
+	for _, info := range all {
+		if isContainerForDir(info.Name, dirName) {
+			containers = append(containers, info)
+		}
+	}
+	return containers, nil
+}
+
+// ListSandboxes returns agentsandbox containers, including stopped ones when all is set
+func ListSandboxes(all bool) ([]ContainerInfo, error) {
+	args := []string{"ps", "--filter", "name=agentsandbox-", "--format", "{{json .}}"}
+	if all {
+		args = append(args, "-a")
+	}
+	cmd := exec.Command("docker", args...)
 	output, err := cmd.Output()
 	if err != nil {
 		return nil, fmt.Errorf("failed to list containers: %w", err)
 	}
 
+	containers, err := parseContainerList(output)
+	if err != nil {
+		return nil, err
+	}
+
+	// Containers created before labels were added need their mounts inspected,
+	// which is done in one batch
+	var unlabeled []string
+	for _, info := range containers {
+		if info.Directory == "" {
+			unlabeled = append(unlabeled, info.Name)
+		}
+	}
+	if len(unlabeled) > 0 {
+		dirs := inspectDirectories(unlabeled)
+		for i := range containers {
+			if containers[i].Directory == "" {
+				containers[i].Directory = dirs[containers[i].Name]
+			}
+		}
+	}
+
+	// docker ps only gives a relative time for stopped containers, so inspect them
+	var stopped []string
+	for _, info := range containers {
+		if info.State != "running" && info.State != "paused" {
+			stopped = append(stopped, info.Name)
+		}
+	}
+	if len(stopped) > 0 {
+		exits := inspectExits(stopped)
+		for i := range containers {
+			if exit, ok := exits[containers[i].Name]; ok {
+				containers[i].ExitCode = exit.code
+				containers[i].Finished = exit.finished
+			}
+		}
+	}
+
+	// Names given to existing containers are only recorded in state
+	if aliases, err := state.LoadAliases(); err == nil && len(aliases) > 0 {
+		for alias, name := range aliases {
+			for i := range containers {
+				if containers[i].Name == name && containers[i].Alias == "" {
+					containers[i].Alias = alias
+				}
+			}
+		}
+	}
+
+	return containers, nil
+}
+
+// psEntry is the subset of `docker ps --format '{{json .}}'` output that is used
+type psEntry struct {
+	Names     string `json:"Names"`
+	Labels    string `json:"Labels"`
+	State     string `json:"State"`
+	Status    string `json:"Status"`
+	CreatedAt string `json:"CreatedAt"`
+}
+
+// psTimeLayout is the format of CreatedAt in docker ps output
+const psTimeLayout = "2006-01-02 15:04:05 -0700 MST"
+
+// parseContainerList parses JSON lines from docker ps into sandbox containers
+func parseContainerList(output []byte) ([]ContainerInfo, error) {
 	var containers []ContainerInfo
-	names := strings.Split(string(output), "\n")
-	for _, name := range names {
-		name = strings.TrimSpace(name)
-		if strings.HasPrefix(name, "agentsandbox-") {
-			project := ExtractProjectName(name)
-			dir, _ := GetContainerDirectory(name)
-			containers = append(containers, ContainerInfo{
-				Project:   project,
-				Name:      name,
-				Directory: dir,
-			})
+	for _, line := range strings.Split(string(output), "\n") {
+		line = strings.TrimSpace(line)
+		if line == "" {
+			continue
+		}
+
+		var entry psEntry
+		if err := json.Unmarshal([]byte(line), &entry); err != nil {
+			return nil, fmt.Errorf("failed to parse docker ps output: %w", err)
+		}
+
+		name := entry.Names
+		labels := parseLabels(entry.Labels)
+		if !strings.HasPrefix(name, "agentsandbox-") || isServiceContainer(labels) {
+			continue
 		}
+		created, _ := time.Parse(psTimeLayout, entry.CreatedAt)
+		containers = append(containers, ContainerInfo{
+			Project:   ExtractProjectName(name),
+			Name:      name,
+			Directory: labels[projectLabel],
+			Alias:     labels[nameLabel],
+			Agent:     labels[agentLabel],
+			State:     entry.State,
+			Status:    entry.Status,
+			Health:    parseHealth(entry.Status),
+			Created:   created,
+		})
 	}
 
 	return containers, nil
 }
 
+// parseLabels parses docker's "key=value,key=value" label list. Commas inside a
+// value (such as a project path) are kept with that value.
+func parseLabels(s string) map[string]string {
+	labels := make(map[string]string)
+	var key string
+	for _, part := range strings.Split(s, ",") {
+		k, v, ok := strings.Cut(part, "=")
+		if ok && !strings.Contains(k, "/") {
+			key = k
+			labels[key] = v
+		} else if key != "" {
+			labels[key] += "," + part
+		}
+	}
+	return labels
+}
+
+// inspectDirectories returns the project directory of each container using a single docker inspect
+func inspectDirectories(names []string) map[string]string {
+	args := append([]string{"inspect", "-f",
+		"{{.Name}}{{range .Mounts}}{{if and .RW (eq .Source .Destination)}}|{{.Source}}{{end}}{{end}}"}, names...)
+	// docker inspect still prints the containers it found when one of them has gone away
+	output, _ := exec.Command("docker", args...).Output()
+
+	dirs := make(map[string]string)
+	for _, line := range strings.Split(string(output), "\n") {
+		fields := strings.Split(strings.TrimSpace(line), "|")
+		if len(fields) < 2 {
+			continue
+		}
+		dirs[strings.TrimPrefix(fields[0], "/")] = projectMount(fields[1:])
+	}
+	return dirs
+}
+
 // ExtractProjectName extracts the project name from a container name
 func ExtractProjectName(name string) string {
 	if !strings.HasPrefix(name, "agentsandbox-") {
@@ -156,17 +464,88 @@ func ExtractProjectName(name string) string {
 	return strings.TrimPrefix(name, "agentsandbox-")
 }
 
+// containerExit is how a stopped container's last run ended
+type containerExit struct {
+	code     int
+	finished time.Time
+}
+
+// inspectExits returns the exit code and finish time of each container using a single docker inspect
+func inspectExits(names []string) map[string]containerExit {
+	args := append([]string{"inspect", "-f", "{{.Name}}|{{.State.ExitCode}}|{{.State.FinishedAt}}"}, names...)
+	// docker inspect still prints the containers it found when one of them has gone away
+	output, _ := exec.Command("docker", args...).Output()
+	return parseExits(output)
+}
+
+// parseExits parses name|exit code|finished-at lines from docker inspect. Containers
+// that never ran report a zero finish time, which is left unset.
+func parseExits(output []byte) map[string]containerExit {
+	exits := make(map[string]containerExit)
+	for _, line := range strings.Split(string(output), "\n") {
+		fields := strings.Split(strings.TrimSpace(line), "|")
+		if len(fields) != 3 {
+			continue
+		}
+		code, err := strconv.Atoi(fields[1])
+		if err != nil {
+			continue
+		}
+		exit := containerExit{code: code}
+		if finished, err := time.Parse(time.RFC3339Nano, fields[2]); err == nil && finished.Year() > 1 {
+			exit.finished = finished
+		}
+		exits[strings.TrimPrefix(fields[0], "/")] = exit
+	}
+	return exits
+}
+
 // GetContainerDirectory returns the mounted directory of a container
 func GetContainerDirectory(name string) (string, error) {
-	cmd := exec.Command("docker", "inspect", "-f",
-		"{{range .Mounts}}{{if and .RW (eq .Source .Destination)}}{{.Source}}\n{{end}}{{end}}",
-		name)
+	format := fmt.Sprintf("{{index .Config.Labels %q}}\n{{range .Mounts}}{{if and .RW (eq .Source .Destination)}}{{.Source}}\n{{end}}{{end}}", projectLabel)
+	cmd := exec.Command("docker", "inspect", "-f", format, name)
 	output, err := cmd.Output()
 	if err != nil {
 		return "", fmt.Errorf("failed to inspect container: %w", err)
 	}
 
-	paths := strings.Split(string(output), "\n")
+	// Untrusted projects are mounted read-only, so only the label finds them
+	lines := strings.Split(string(output), "\n")
+	if dir := projectMount(lines[1:]); dir != "" {
+		return dir, nil
+	}
+	return projectMount(lines[:1]), nil
+}
+
+// WorkspaceDirs returns a container's project directory followed by the extra
+// workspace directories it was created with
+func WorkspaceDirs(name string) ([]string, error) {
+	format := fmt.Sprintf("{{index .Config.Labels %q}}\n{{index .Config.Labels %q}}", projectLabel, workspaceLabel)
+	output, err := exec.Command("docker", "inspect", "-f", format, name).Output()
+	if err != nil {
+		return nil, fmt.Errorf("failed to inspect container: %w", err)
+	}
+
+	project, extra, _ := strings.Cut(strings.TrimRight(string(output), "\n"), "\n")
+	if project == "" {
+		// Containers created before labels were added
+		project, _ = GetContainerDirectory(name)
+	}
+
+	var dirs []string
+	if project != "" {
+		dirs = append(dirs, project)
+	}
+	for _, dir := range filepath.SplitList(extra) {
+		if dir != "" {
+			dirs = append(dirs, dir)
+		}
+	}
+	return dirs, nil
+}
+
+// projectMount picks the project directory out of a container's same-path read-write mounts
+func projectMount(paths []string) string {
 	for _, path := range paths {
 		path = strings.TrimSpace(path)
 		if path == "" {
@@ -185,10 +564,10 @@ func GetContainerDirectory(name string) (string, error) {
 		}
 
 		// This looks like a regular project directory
-		return path, nil
+		return path
 	}
 
-	return "", nil
+	return ""
 }
 
 // IsContainerRunning checks if a container is currently running
@@ -212,39 +591,13 @@ func ContainerExists(name string) (bool, error) {
 
 // LoadLastContainer loads the last used container name
 func LoadLastContainer() (string, error) {
-	homeDir, err := os.UserHomeDir()
-	if err != nil {
-		return "", err
-	}
-
-	stateDir := filepath.Join(homeDir, ".config", "agentsandbox")
-	lastFile := filepath.Join(stateDir, "last_container")
-
-	data, err := os.ReadFile(lastFile)
-	if err != nil {
-		if os.IsNotExist(err) {
-			return "", nil
-		}
-		return "", err
-	}
-
-	return strings.TrimSpace(string(data)), nil
+	name, err := state.LoadLastContainer()
+	return strings.TrimSpace(name), err
 }
 
 // SaveLastContainer saves the last used container name
 func SaveLastContainer(name string) error {
-	homeDir, err := os.UserHomeDir()
-	if err != nil {
-		return err
-	}
-
-	stateDir := filepath.Join(homeDir, ".config", "agentsandbox")
-	if err := os.MkdirAll(stateDir, 0755); err != nil {
-		return err
-	}
-
-	lastFile := filepath.Join(stateDir, "last_container")
-	return os.WriteFile(lastFile, []byte(name), 0644)
+	return state.SaveLastContainer(name)
 }
 
 // ImageInfo represents information about an agentsandbox Docker image
@@ -256,9 +609,12 @@ type ImageInfo struct {
 	Size    string
 }
 
-// ListAgentSandboxImages returns a list of all agentsandbox Docker images
+// ListAgentSandboxImages returns a list of all agentsandbox Docker images,
+// including the base and language layers the final images are built from
 func ListAgentSandboxImages() ([]ImageInfo, error) {
-	cmd := exec.Command("docker", "images", "--format", "{{.Repository}}:{{.Tag}}\t{{.ID}}\t{{.CreatedAt}}\t{{.Size}}", "agentsandbox-image")
+	cmd := exec.Command("docker", "images",
+		"--filter", "reference=agentsandbox-*",
+		"--format", "{{.Repository}}:{{.Tag}}\t{{.ID}}\t{{.CreatedAt}}\t{{.Size}}")
 	output, err := cmd.Output()
 	if err != nil {
 		return nil, fmt.Errorf("failed to list images: %w", err)
@@ -1,6 +1,10 @@
 package container
 
-import "testing"
+import (
+	"strings"
+	"testing"
+	"time"
+)
 
 func TestIsContainerForDir(t *testing.T) {
 	tests := []struct {
@@ -25,3 +29,90 @@ func TestIsContainerForDir(t *testing.T) {
 		})
 	}
 }
+
+func TestIsWithin(t *testing.T) {
+	tests := []struct {
+		dir  string
+		path string
+		want bool
+	}{
+		{"/home/me/api", "/home/me/api", true},
+		{"/home/me/api", "/home/me/api/cmd/server", true},
+		{"/home/me/api", "/home/me/api-v2", false},
+		{"/home/me/api", "/home/me", false},
+		{"/home/me/api", "/home/me/web", false},
+		{"", "/home/me/api", false},
+	}
+	for _, tt := range tests {
+		t.Run(tt.path, func(t *testing.T) {
+			if got := IsWithin(tt.dir, tt.path); got != tt.want {
+				t.Errorf("IsWithin(%q, %q) = %v, want %v", tt.dir, tt.path, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestParseContainerList(t *testing.T) {
+	output := []byte(`{"Names":"agentsandbox-api","Labels":"agentsandbox.agent=claude,agentsandbox.project=/home/me/api","State":"running","Status":"Up 2 hours","CreatedAt":"2025-01-13 14:30:00 +0000 UTC"}
+{"Names":"agentsandbox-api-svc-postgres","Labels":"agentsandbox.parent=agentsandbox-api"}
+{"Names":"agentsandbox-my-svc-tools","Labels":"agentsandbox.project=/home/me/my-svc-tools"}
+{"Names":"agentsandbox-legacy","Labels":"","State":"exited","Status":"Exited (0) 3 days ago"}
+{"Names":"other-agentsandbox-x","Labels":""}
+{"Names":"agentsandbox-odd","Labels":"agentsandbox.project=/home/me/a,b,agentsandbox.agent=codex"}
+`)
+
+	containers, err := parseContainerList(output)
+	if err != nil {
+		t.Fatalf("parseContainerList() error = %v", err)
+	}
+
+	want := []ContainerInfo{
+		{Project: "api", Name: "agentsandbox-api", Directory: "/home/me/api", Agent: "claude", State: "running", Status: "Up 2 hours",
+			Created: time.Date(2025, 1, 13, 14, 30, 0, 0, time.UTC)},
+		{Project: "my-svc-tools", Name: "agentsandbox-my-svc-tools", Directory: "/home/me/my-svc-tools"},
+		{Project: "legacy", Name: "agentsandbox-legacy", State: "exited", Status: "Exited (0) 3 days ago"},
+		{Project: "odd", Name: "agentsandbox-odd", Directory: "/home/me/a,b", Agent: "codex"},
+	}
+	if len(containers) != len(want) {
+		t.Fatalf("parseContainerList() = %+v, want %+v", containers, want)
+	}
+	for i := range want {
+		got := containers[i]
+		// Parsed times carry a fabricated zone, so compare instants separately
+		if !got.Created.Equal(want[i].Created) {
+			t.Errorf("container %d created = %v, want %v", i, got.Created, want[i].Created)
+		}
+		got.Created, want[i].Created = time.Time{}, time.Time{}
+		if got != want[i] {
+			t.Errorf("container %d = %+v, want %+v", i, got, want[i])
+		}
+	}
+}
+
+func TestParseExits(t *testing.T) {
+	output := []byte(`/agentsandbox-api|137|2025-01-13T14:30:00.123456789Z
+/agentsandbox-new|0|0001-01-01T00:00:00Z
+garbage
+`)
+
+	exits := parseExits(output)
+	if len(exits) != 2 {
+		t.Fatalf("parseExits() = %+v, want 2 entries", exits)
+	}
+	api := exits["agentsandbox-api"]
+	if api.code != 137 || !api.finished.Equal(time.Date(2025, 1, 13, 14, 30, 0, 123456789, time.UTC)) {
+		t.Errorf("agentsandbox-api = %+v", api)
+	}
+	if created := exits["agentsandbox-new"]; created.code != 0 || !created.finished.IsZero() {
+		t.Errorf("agentsandbox-new = %+v, want no finish time", created)
+	}
+}
+
+func TestSandboxNames(t *testing.T) {
+	output := []byte("agentsandbox-api\t\nagentsandbox-api-svc-postgres\tagentsandbox-api\nagentsandbox-my-svc-tools\t\n\n")
+	got := sandboxNames(output)
+	want := []string{"agentsandbox-api", "agentsandbox-my-svc-tools"}
+	if strings.Join(got, ",") != strings.Join(want, ",") {
+		t.Errorf("sandboxNames() = %q, want %q", got, want)
+	}
+}
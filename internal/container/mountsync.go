@@ -0,0 +1,156 @@
+package container
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"runtime"
+	"strings"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+// Ways of giving the sandbox the project directory
+const (
+	// MountAuto syncs on macOS when mutagen is installed and bind mounts otherwise
+	MountAuto = "auto"
+	// MountBind bind mounts the project directory
+	MountBind = "bind"
+	// MountSync keeps the project in a volume that mutagen syncs with the host
+	MountSync = "sync"
+)
+
+// syncVolumePrefix names the volumes holding synced projects
+const syncVolumePrefix = "agentsandbox-sync-"
+
+// ValidateMountStrategy checks the mount_strategy and mount_consistency settings
+func ValidateMountStrategy(strategy, consistency string) error {
+	switch strategy {
+	case "", MountAuto, MountBind, MountSync:
+	default:
+		return fmt.Errorf("invalid mount_strategy %q: use auto, bind or sync", strategy)
+	}
+	switch consistency {
+	case "", "consistent", "cached", "delegated":
+	default:
+		return fmt.Errorf("invalid mount_consistency %q: use consistent, cached or delegated", consistency)
+	}
+	return nil
+}
+
+// resolveMountStrategy picks the strategy for a new container. Bind mounts through
+// Docker Desktop's file sharing are what makes large repositories slow on macOS.
+func resolveMountStrategy(strategy string) string {
+	if strategy == MountSync || strategy == MountBind {
+		return strategy
+	}
+	if runtime.GOOS != "darwin" {
+		return MountBind
+	}
+	if _, err := exec.LookPath("mutagen"); err == nil {
+		return MountSync
+	}
+	if enabled, known := dockerDesktopVirtioFS(); known && !enabled {
+		fmt.Println("Tip: enable VirtioFS in Docker Desktop (Settings > General) or install mutagen for faster file access")
+	}
+	return MountBind
+}
+
+// dockerDesktopVirtioFS reports whether Docker Desktop shares files with VirtioFS, and
+// whether that could be determined from its settings file
+func dockerDesktopVirtioFS() (bool, bool) {
+	home, err := os.UserHomeDir()
+	if err != nil {
+		return false, false
+	}
+	dir := filepath.Join(home, "Library", "Group Containers", "group.com.docker")
+	// Newer releases renamed both the file and the key
+	for _, candidate := range []struct{ file, key string }{
+		{"settings-store.json", "UseVirtualizationFrameworkVirtioFS"},
+		{"settings.json", "useVirtualizationFrameworkVirtioFS"},
+	} {
+		data, err := os.ReadFile(filepath.Join(dir, candidate.file))
+		if err != nil {
+			continue
+		}
+		var settings map[string]any
+		if json.Unmarshal(data, &settings) != nil {
+			continue
+		}
+		if enabled, ok := settings[candidate.key].(bool); ok {
+			return enabled, true
+		}
+	}
+	return false, false
+}
+
+// bindMode returns the options suffix of a bind mount
+func bindMode(readOnly bool, consistency string) string {
+	var options []string
+	if readOnly {
+		options = append(options, "ro")
+	}
+	if consistency != "" {
+		options = append(options, consistency)
+	}
+	if len(options) == 0 {
+		return ""
+	}
+	return ":" + strings.Join(options, ",")
+}
+
+// syncVolume returns the volume holding a synced container's project
+func syncVolume(containerName string) string {
+	return syncVolumePrefix + containerName
+}
+
+// syncArgs returns the mutagen arguments creating the container's sync session. Env
+// files masked in the sandbox are not synced, and node_modules stays per-container as
+// with bind mounts.
+func syncArgs(containerName, username, dir string, ignores []string) []string {
+	args := []string{
+		"sync", "create",
+		"--name", containerName,
+		"--label", "agentsandbox=true",
+		"--sync-mode", "two-way-resolved",
+		"--ignore", "node_modules",
+	}
+	for _, pattern := range ignores {
+		args = append(args, "--ignore", pattern)
+	}
+	return append(args, dir, fmt.Sprintf("docker://%s@%s%s", username, containerName, dir))
+}
+
+// startSync gives the user the project volume and syncs the project into it, waiting
+// for the first pass to finish so the agent starts with every file in place
+func startSync(out io.Writer, containerName, username, dir string, settings *config.Settings) error {
+	chown := exec.Command("docker", "exec", "--user", "root", containerName, "chown", username+":", dir)
+	if output, err := chown.CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to prepare the project volume: %w\nOutput: %s", err, string(output))
+	}
+
+	var ignores []string
+	if project, err := config.LoadProjectSettings(dir); err == nil {
+		ignores = config.EnvFilePatterns(settings, project)
+	}
+	fmt.Fprintln(out, "Syncing the project into the sandbox with mutagen")
+	if output, err := exec.Command("mutagen", syncArgs(containerName, username, dir, ignores)...).CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to start mutagen sync: %w\nOutput: %s", err, string(output))
+	}
+	if output, err := exec.Command("mutagen", "sync", "flush", containerName).CombinedOutput(); err != nil {
+		return fmt.Errorf("initial sync did not finish: %w\nOutput: %s", err, string(output))
+	}
+	return nil
+}
+
+// stopSync ends a removed container's sync session and deletes its project volume.
+// Both are absent for bind-mounted containers.
+func stopSync(containerName string) {
+	if _, err := exec.LookPath("mutagen"); err == nil {
+		exec.Command("mutagen", "sync", "terminate", containerName).Run()
+	}
+	exec.Command("docker", "volume", "rm", syncVolume(containerName)).Run()
+}
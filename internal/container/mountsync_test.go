@@ -0,0 +1,52 @@
+package container
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestBindMode(t *testing.T) {
+	tests := []struct {
+		readOnly    bool
+		consistency string
+		want        string
+	}{
+		{false, "", ""},
+		{true, "", ":ro"},
+		{false, "cached", ":cached"},
+		{true, "delegated", ":ro,delegated"},
+	}
+	for _, tt := range tests {
+		if got := bindMode(tt.readOnly, tt.consistency); got != tt.want {
+			t.Errorf("bindMode(%v, %q) = %q, want %q", tt.readOnly, tt.consistency, got, tt.want)
+		}
+	}
+}
+
+func TestSyncArgs(t *testing.T) {
+	got := syncArgs("agentsandbox-app", "dev", "/Users/dev/app", []string{".env*"})
+	want := []string{
+		"sync", "create",
+		"--name", "agentsandbox-app",
+		"--label", "agentsandbox=true",
+		"--sync-mode", "two-way-resolved",
+		"--ignore", "node_modules",
+		"--ignore", ".env*",
+		"/Users/dev/app", "docker://dev@agentsandbox-app/Users/dev/app",
+	}
+	if !reflect.DeepEqual(got, want) {
+		t.Errorf("syncArgs() = %v, want %v", got, want)
+	}
+}
+
+func TestValidateMountStrategy(t *testing.T) {
+	if err := ValidateMountStrategy("sync", "cached"); err != nil {
+		t.Errorf("ValidateMountStrategy() error = %v", err)
+	}
+	if err := ValidateMountStrategy("nfs", ""); err == nil {
+		t.Error("expected an error for an unknown strategy")
+	}
+	if err := ValidateMountStrategy("", "fast"); err == nil {
+		t.Error("expected an error for an unknown consistency")
+	}
+}
@@ -2,12 +2,12 @@ package container
 
 import (
 	"fmt"
-	"os/exec"
 	"path/filepath"
 	"regexp"
 	"strings"
 
 	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/git"
 )
 
 // Sanitize removes or replaces characters that aren't safe for container names
@@ -36,14 +36,10 @@ func Sanitize(s string) string {
 
 // GetCurrentBranch returns the current git branch name
 func GetCurrentBranch(dir string) string {
-	cmd := exec.Command("git", "rev-parse", "--abbrev-ref", "HEAD")
-	cmd.Dir = dir
-	output, err := cmd.Output()
-	if err != nil {
+	branch := git.CurrentBranch(dir)
+	if branch == "" {
 		return "unknown"
 	}
-
-	branch := strings.TrimSpace(string(output))
 	return Sanitize(branch)
 }
 
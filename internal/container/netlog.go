@@ -0,0 +1,42 @@
+package container
+
+import (
+	"fmt"
+	"path/filepath"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/logs"
+	"github.com/thaodangspace/agentsandbox/internal/netlog"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+// startNetworkLog starts the host-side proxy that records the hosts a session contacts.
+// It refuses the hosts the DNS policy blocks, since it resolves names on the host.
+func startNetworkLog(policy config.DNSPolicySettings) *netlog.Proxy {
+	proxy, err := netlog.Start(policy)
+	if err != nil {
+		fmt.Printf("Warning: %v\n", err)
+		return nil
+	}
+	fmt.Println("Logging the hosts this session connects to through an HTTP proxy")
+	return proxy
+}
+
+// finishNetworkLog stops the proxy and saves the session's connections next to its audit log
+func finishNetworkLog(containerName, currentDir, sessionID string, proxy *netlog.Proxy) {
+	entries := proxy.Close()
+	if len(entries) == 0 {
+		return
+	}
+	logsDir, err := state.GetLogsDir(containerName, currentDir)
+	if err != nil {
+		fmt.Printf("Warning: %v\n", err)
+		return
+	}
+	if err := logs.WriteNetworkLog(filepath.Join(logsDir, fmt.Sprintf("network-%s.jsonl", sessionID)), entries); err != nil {
+		fmt.Printf("Warning: failed to save network log: %v\n", err)
+		return
+	}
+	fmt.Printf("Network log saved: %d connections to %d hosts; agentsandbox logs network %s\n",
+		len(entries), len(logs.SummarizeNetwork(entries)), sessionID)
+}
@@ -0,0 +1,137 @@
+package container
+
+import (
+	"fmt"
+	"os/exec"
+	"regexp"
+	"strconv"
+	"strings"
+)
+
+// networkLabel marks networks created by agentsandbox and records, on containers,
+// which shared network they joined
+const networkLabel = "agentsandbox.network"
+
+var networkPattern = regexp.MustCompile(`^[a-zA-Z0-9][a-zA-Z0-9_.-]*$`)
+
+// NetworkInfo describes a shared network and its member containers
+type NetworkInfo struct {
+	Name    string
+	Members []string
+}
+
+// NetworkName returns the docker network used for a shared network name
+func NetworkName(name string) string {
+	return "agentsandbox-net-" + name
+}
+
+// ValidateNetworkName checks that name can be used as a shared network name
+func ValidateNetworkName(name string) error {
+	if !networkPattern.MatchString(name) {
+		return fmt.Errorf("invalid network name %q: use letters, digits, '.', '_' and '-'", name)
+	}
+	return nil
+}
+
+// NetworkExists reports whether the shared network has been created
+func NetworkExists(name string) bool {
+	return exec.Command("docker", "network", "inspect", NetworkName(name)).Run() == nil
+}
+
+// CreateNetwork creates a shared network that sandboxes can join with --network-join
+func CreateNetwork(name string) error {
+	if err := ValidateNetworkName(name); err != nil {
+		return err
+	}
+	if NetworkExists(name) {
+		return fmt.Errorf("network %s already exists", name)
+	}
+
+	cmd := exec.Command("docker", "network", "create", "--label", fmt.Sprintf("%s=%s", networkLabel, name), NetworkName(name))
+	if output, err := cmd.CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to create network: %w\nOutput: %s", err, string(output))
+	}
+	return nil
+}
+
+// RemoveNetwork removes a shared network; docker refuses while containers are attached
+func RemoveNetwork(name string) error {
+	if output, err := exec.Command("docker", "network", "rm", NetworkName(name)).CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to remove network %s: %w\nOutput: %s", name, err, string(output))
+	}
+	return nil
+}
+
+// ListNetworks returns the shared networks created by agentsandbox with their members
+func ListNetworks() ([]NetworkInfo, error) {
+	output, err := exec.Command("docker", "network", "ls", "--filter", "label="+networkLabel,
+		"--format", fmt.Sprintf("{{.Label %q}}", networkLabel)).Output()
+	if err != nil {
+		return nil, fmt.Errorf("failed to list networks: %w", err)
+	}
+
+	var networks []NetworkInfo
+	for _, name := range strings.Fields(string(output)) {
+		members, err := networkMembers(name)
+		if err != nil {
+			return nil, err
+		}
+		networks = append(networks, NetworkInfo{Name: name, Members: members})
+	}
+	return networks, nil
+}
+
+// networkMembers lists the containers, running or stopped, that joined the shared network
+func networkMembers(name string) ([]string, error) {
+	output, err := exec.Command("docker", "ps", "-a", "--filter", fmt.Sprintf("label=%s=%s", networkLabel, name),
+		"--format", "{{.Names}}").Output()
+	if err != nil {
+		return nil, fmt.Errorf("failed to list network members: %w", err)
+	}
+	return strings.Fields(string(output)), nil
+}
+
+// containerNetwork returns the shared network a container joined, if any
+func containerNetwork(containerName string) string {
+	output, err := exec.Command("docker", "inspect", "-f", fmt.Sprintf("{{index .Config.Labels %q}}", networkLabel), containerName).Output()
+	if err != nil {
+		return ""
+	}
+	return strings.TrimSpace(string(output))
+}
+
+// networkArgs returns the docker run arguments that join a shared network. The session
+// name, if any, is added as an alias so peers can use it as a hostname too.
+func networkArgs(name, alias string) []string {
+	args := []string{
+		"--network", NetworkName(name),
+		"--label", fmt.Sprintf("%s=%s", networkLabel, name),
+	}
+	if alias != "" {
+		args = append(args, "--network-alias", alias)
+	}
+	return args
+}
+
+// removeNetworkIfUnused deletes a shared network once its last member is gone
+func removeNetworkIfUnused(name string) {
+	members, err := networkMembers(name)
+	if err != nil || len(members) > 0 {
+		return
+	}
+
+	// Containers attached outside agentsandbox keep the network alive
+	output, err := exec.Command("docker", "network", "inspect", "-f", "{{len .Containers}}", NetworkName(name)).Output()
+	if err != nil {
+		return
+	}
+	if attached, err := strconv.Atoi(strings.TrimSpace(string(output))); err != nil || attached > 0 {
+		return
+	}
+
+	if err := RemoveNetwork(name); err != nil {
+		fmt.Printf("Warning: %v\n", err)
+		return
+	}
+	fmt.Printf("Removed network %s, which has no members left\n", name)
+}
@@ -0,0 +1,31 @@
+package container
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestNetworkArgs(t *testing.T) {
+	got := networkArgs("team", "frontend")
+	want := []string{"--network", "agentsandbox-net-team", "--label", "agentsandbox.network=team", "--network-alias", "frontend"}
+	if !reflect.DeepEqual(got, want) {
+		t.Errorf("networkArgs() = %v, want %v", got, want)
+	}
+
+	if got := networkArgs("team", ""); len(got) != 4 {
+		t.Errorf("networkArgs() without alias = %v, want no --network-alias", got)
+	}
+}
+
+func TestValidateNetworkName(t *testing.T) {
+	for _, name := range []string{"team", "web.api", "a_b-1"} {
+		if err := ValidateNetworkName(name); err != nil {
+			t.Errorf("ValidateNetworkName(%q) = %v, want nil", name, err)
+		}
+	}
+	for _, name := range []string{"", "-team", "my team", "a/b"} {
+		if err := ValidateNetworkName(name); err == nil {
+			t.Errorf("ValidateNetworkName(%q) = nil, want error", name)
+		}
+	}
+}
@@ -0,0 +1,23 @@
+package container
+
+import (
+	"fmt"
+	"os/exec"
+)
+
+// PauseContainer freezes every process in a container with docker pause. Memory and
+// open files are kept, so UnpauseContainer resumes exactly where it stopped.
+func PauseContainer(name string) error {
+	if output, err := exec.Command("docker", "pause", name).CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to pause container %s: %w\nOutput: %s", name, err, string(output))
+	}
+	return nil
+}
+
+// UnpauseContainer resumes a container frozen with PauseContainer
+func UnpauseContainer(name string) error {
+	if output, err := exec.Command("docker", "unpause", name).CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to unpause container %s: %w\nOutput: %s", name, err, string(output))
+	}
+	return nil
+}
@@ -0,0 +1,172 @@
+package container
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"regexp"
+	"strings"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+const (
+	// postMortemLogLines is how much of the container's output is kept
+	postMortemLogLines = 50
+	// postMortemKernelLines is how many matching kernel messages are kept
+	postMortemKernelLines = 20
+)
+
+// kernelPattern matches kernel messages about the OOM killer and killed processes
+var kernelPattern = regexp.MustCompile(`(?i)oom|out of memory|killed process`)
+
+// PostMortem records why a sandbox container stopped unexpectedly
+type PostMortem struct {
+	Container   string    `json:"container"`
+	CollectedAt time.Time `json:"collected_at"`
+	FinishedAt  string    `json:"finished_at"`
+	ExitCode    int       `json:"exit_code"`
+	OOMKilled   bool      `json:"oom_killed"`
+	Error       string    `json:"error,omitempty"`
+	// Logs is the tail of the container's output
+	Logs []string `json:"logs,omitempty"`
+	// Kernel holds host kernel messages about the OOM killer, when readable
+	Kernel []string `json:"kernel,omitempty"`
+}
+
+// exitState is the subset of docker inspect's State used for post-mortems
+type exitState struct {
+	Running    bool   `json:"Running"`
+	OOMKilled  bool   `json:"OOMKilled"`
+	ExitCode   int    `json:"ExitCode"`
+	Error      string `json:"Error"`
+	FinishedAt string `json:"FinishedAt"`
+}
+
+// unexpected reports whether the container stopped on its own rather than being stopped.
+// docker stop ends the container's shell with SIGTERM or SIGKILL (143 or 137).
+func (s exitState) unexpected() bool {
+	if s.Running {
+		return false
+	}
+	if s.OOMKilled || s.Error != "" {
+		return true
+	}
+	return s.ExitCode != 0 && s.ExitCode != 137 && s.ExitCode != 143
+}
+
+// Reason describes in a few words why the container stopped
+func (p *PostMortem) Reason() string {
+	switch {
+	case p.OOMKilled:
+		return "killed for running out of memory"
+	case p.Error != "":
+		return p.Error
+	default:
+		return fmt.Sprintf("exited with code %d", p.ExitCode)
+	}
+}
+
+func postMortemFile(containerName string) (string, error) {
+	stateDir, err := state.GetStateDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(stateDir, fmt.Sprintf("%s.postmortem.json", containerName)), nil
+}
+
+// CollectPostMortem saves a post-mortem for a container that stopped unexpectedly and
+// returns its path. It returns an empty path when the container is running, was stopped
+// normally, or this stop was already recorded.
+func CollectPostMortem(containerName string) (string, *PostMortem, error) {
+	output, err := exec.Command("docker", "inspect", "-f", "{{json .State}}", containerName).Output()
+	if err != nil {
+		return "", nil, nil
+	}
+	var st exitState
+	if err := json.Unmarshal(output, &st); err != nil {
+		return "", nil, fmt.Errorf("failed to parse container state: %w", err)
+	}
+	if !st.unexpected() {
+		return "", nil, nil
+	}
+
+	file, err := postMortemFile(containerName)
+	if err != nil {
+		return "", nil, err
+	}
+	var previous PostMortem
+	if data, err := os.ReadFile(file); err == nil && json.Unmarshal(data, &previous) == nil && previous.FinishedAt == st.FinishedAt {
+		return "", nil, nil
+	}
+
+	pm := &PostMortem{
+		Container:   containerName,
+		CollectedAt: time.Now(),
+		FinishedAt:  st.FinishedAt,
+		ExitCode:    st.ExitCode,
+		OOMKilled:   st.OOMKilled,
+		Error:       st.Error,
+	}
+	if logs, err := exec.Command("docker", "logs", "--tail", fmt.Sprint(postMortemLogLines), containerName).CombinedOutput(); err == nil {
+		pm.Logs = splitLines(string(logs))
+	}
+	// Reading the kernel log usually needs privileges, so this is best effort
+	if dmesg, err := exec.Command("dmesg").Output(); err == nil {
+		pm.Kernel = kernelMessages(string(dmesg))
+	}
+
+	data, err := json.MarshalIndent(pm, "", "  ")
+	if err != nil {
+		return "", nil, err
+	}
+	if err := os.WriteFile(file, data, 0644); err != nil {
+		return "", nil, fmt.Errorf("failed to save post-mortem: %w", err)
+	}
+	return file, pm, nil
+}
+
+// ReportPostMortems collects post-mortems for sandboxes that stopped unexpectedly since
+// the last check and mentions each one
+func ReportPostMortems() {
+	if _, err := exec.LookPath("docker"); err != nil {
+		return
+	}
+	output, err := exec.Command("docker", "ps", "-a", "--filter", "name=agentsandbox-", "--filter", "status=exited",
+		"--format", psNamesFormat).Output()
+	if err != nil {
+		return
+	}
+	for _, name := range sandboxNames(output) {
+		file, pm, err := CollectPostMortem(name)
+		if err != nil || file == "" {
+			continue
+		}
+		fmt.Fprintf(os.Stderr, "Sandbox %s stopped unexpectedly (%s). Post-mortem: %s\n", name, pm.Reason(), file)
+	}
+}
+
+// kernelMessages returns the last kernel log lines about the OOM killer
+func kernelMessages(dmesg string) []string {
+	var matches []string
+	for _, line := range splitLines(dmesg) {
+		if kernelPattern.MatchString(line) {
+			matches = append(matches, line)
+		}
+	}
+	if len(matches) > postMortemKernelLines {
+		matches = matches[len(matches)-postMortemKernelLines:]
+	}
+	return matches
+}
+
+func splitLines(s string) []string {
+	s = strings.TrimRight(s, "\n")
+	if s == "" {
+		return nil
+	}
+	return strings.Split(s, "\n")
+}
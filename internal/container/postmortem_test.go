@@ -0,0 +1,40 @@
+package container
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestExitStateUnexpected(t *testing.T) {
+	tests := []struct {
+		state exitState
+		want  bool
+	}{
+		{exitState{Running: true}, false},
+		{exitState{ExitCode: 0}, false},
+		{exitState{ExitCode: 137}, false},
+		{exitState{ExitCode: 143}, false},
+		{exitState{ExitCode: 137, OOMKilled: true}, true},
+		{exitState{ExitCode: 1}, true},
+		{exitState{ExitCode: 128, Error: "failed to create shim task"}, true},
+	}
+	for _, tt := range tests {
+		if got := tt.state.unexpected(); got != tt.want {
+			t.Errorf("%+v.unexpected() = %v, want %v", tt.state, got, tt.want)
+		}
+	}
+}
+
+func TestKernelMessages(t *testing.T) {
+	dmesg := "[1.0] usb 1-1: new device\n" +
+		"[2.0] bash invoked oom-killer: gfp_mask=0x100cca\n" +
+		"[2.1] Memory cgroup out of memory: Killed process 4242 (node)\n" +
+		"[3.0] eth0: link up\n"
+	want := []string{
+		"[2.0] bash invoked oom-killer: gfp_mask=0x100cca",
+		"[2.1] Memory cgroup out of memory: Killed process 4242 (node)",
+	}
+	if got := kernelMessages(dmesg); !reflect.DeepEqual(got, want) {
+		t.Errorf("kernelMessages() = %q, want %q", got, want)
+	}
+}
@@ -0,0 +1,80 @@
+package container
+
+import (
+	"bufio"
+	"fmt"
+	"os"
+	"os/exec"
+	"strings"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/ui"
+)
+
+// daemonRecoveryTimeout is how long to wait for the Docker daemon to come back
+const daemonRecoveryTimeout = 2 * time.Minute
+
+// dockerReachable reports whether the Docker daemon answers requests
+func dockerReachable() bool {
+	return exec.Command("docker", "info", "--format", "{{.ID}}").Run() == nil
+}
+
+// sessionLostToDaemon reports whether a failed session was cut off by the Docker daemon
+// going away or restarting the container, rather than by the agent itself exiting
+func sessionLostToDaemon(containerName, startedAt string) bool {
+	if !dockerReachable() {
+		return true
+	}
+
+	info, err := InspectContainer(containerName)
+	if err != nil || !info.Exists {
+		return false
+	}
+	return !info.Running || (startedAt != "" && info.StartedAt != startedAt)
+}
+
+// recoverFromDaemonRestart waits for the Docker daemon and makes sure the container is running again
+func recoverFromDaemonRestart(containerName string) error {
+	fmt.Printf("\n%sLost the connection to the container; the Docker daemon appears to have restarted.\n", ui.Warning())
+
+	if !dockerReachable() {
+		fmt.Println("Waiting for the Docker daemon to come back...")
+		deadline := time.Now().Add(daemonRecoveryTimeout)
+		for !dockerReachable() {
+			if time.Now().After(deadline) {
+				return fmt.Errorf("the Docker daemon did not respond within %s", daemonRecoveryTimeout)
+			}
+			time.Sleep(2 * time.Second)
+		}
+	}
+
+	info, err := InspectContainer(containerName)
+	if err != nil {
+		return err
+	}
+	if !info.Exists {
+		return fmt.Errorf("container %s no longer exists", containerName)
+	}
+	if !info.Running {
+		fmt.Printf("Restarting container %s...\n", containerName)
+		if output, err := exec.Command("docker", "start", containerName).CombinedOutput(); err != nil {
+			return fmt.Errorf("failed to restart container: %w\nOutput: %s", err, string(output))
+		}
+	}
+
+	fmt.Println("Container is running again. The session log captured so far has been kept.")
+	return nil
+}
+
+// confirmReattach asks whether to resume the agent's conversation after recovery
+func confirmReattach(agent config.Agent) bool {
+	if !stdinIsTerminal() {
+		return false
+	}
+
+	fmt.Printf("Reattach to %s with --continue? [Y/n]: ", agent.DisplayName())
+	answer, _ := bufio.NewReader(os.Stdin).ReadString('\n')
+	answer = strings.ToLower(strings.TrimSpace(answer))
+	return answer == "" || answer == "y" || answer == "yes"
+}
@@ -2,156 +2,28 @@ package container
 
 import (
 	"fmt"
+	"io"
 	"net"
 	"os"
 	"os/exec"
 	"path/filepath"
 	"strconv"
 	"strings"
+	"time"
 
 	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/crash"
+	"github.com/thaodangspace/agentsandbox/internal/credproxy"
+	"github.com/thaodangspace/agentsandbox/internal/failure"
+	"github.com/thaodangspace/agentsandbox/internal/i18n"
+	"github.com/thaodangspace/agentsandbox/internal/image"
 	"github.com/thaodangspace/agentsandbox/internal/language"
+	"github.com/thaodangspace/agentsandbox/internal/share"
 	"github.com/thaodangspace/agentsandbox/internal/state"
+	"github.com/thaodangspace/agentsandbox/internal/templates"
+	"github.com/thaodangspace/agentsandbox/internal/ui"
 )
 
-const dockerfileBaseTemplate = `FROM ubuntu:22.04
-
-ENV DEBIAN_FRONTEND=noninteractive
-
-# Install basic tools
-RUN apt-get update && apt-get install -y \
-    curl \
-    wget \
-    git \
-		vim \
-    build-essential \
-    sudo \
-    ca-certificates \
-    && rm -rf /var/lib/apt/lists/*
-
-# Language toolchains (inserted dynamically)
-%s
-
-# Create user
-ARG USERNAME=ubuntu
-ARG USER_UID=1000
-ARG USER_GID=1000
-
-RUN set -e; \
-    if ! getent group $USER_GID >/dev/null; then \
-        groupadd --gid $USER_GID $USERNAME; \
-    fi && \
-    useradd --uid $USER_UID --gid $USER_GID -m -s /bin/bash $USERNAME && \
-    echo "$USERNAME ALL=(ALL) NOPASSWD:ALL" >> /etc/sudoers
-
-# Install AI agents (with cache busting args)
-ARG CLAUDE_CACHE_BUST
-ARG GEMINI_CACHE_BUST
-ARG CODEX_CACHE_BUST
-ARG QWEN_CACHE_BUST
-ARG CURSOR_CACHE_BUST
-
-USER $USERNAME
-WORKDIR /home/$USERNAME
-
-# Add Go to PATH if installed
-ENV PATH="/usr/local/go/bin:${PATH}"
-
-# Install Claude (example - adjust per actual installation method)
-RUN curl -fsSL https://claude.ai/install.sh | bash || true
-
-CMD ["/bin/bash"]
-`
-
-func CreateDockerfile(username string, uid, gid int, languages []language.Language) (string, error) {
-	tempDir := os.TempDir()
-	dockerfilePath := filepath.Join(tempDir, "Dockerfile.agentsandbox")
-
-	var languageInstalls []string
-	for _, lang := range languages {
-		cmd := lang.DockerfileInstallCmd()
-		if cmd != "" {
-			languageInstalls = append(languageInstalls, cmd)
-		}
-	}
-	languageSection := strings.Join(languageInstalls, "\n\n")
-	if languageSection == "" {
-		languageSection = "# No language toolchains detected"
-	}
-
-	content := fmt.Sprintf(dockerfileBaseTemplate, languageSection)
-	content = strings.Replace(content, "ARG USERNAME=ubuntu", fmt.Sprintf("ARG USERNAME=%s", username), 1)
-	content = strings.Replace(content, "ARG USER_UID=1000", fmt.Sprintf("ARG USER_UID=%d", uid), 1)
-	content = strings.Replace(content, "ARG USER_GID=1000", fmt.Sprintf("ARG USER_GID=%d", gid), 1)
-
-	if err := os.WriteFile(dockerfilePath, []byte(content), 0o644); err != nil {
-		return "", fmt.Errorf("failed to write Dockerfile: %w", err)
-	}
-
-	return dockerfilePath, nil
-}
-
-func BuildDockerImage(username string, languages []language.Language) (string, error) {
-	tag := language.GenerateImageTag(languages)
-	imageName := fmt.Sprintf("agentsandbox-image:%s", tag)
-
-	checkCmd := exec.Command("docker", "image", "inspect", imageName)
-	if err := checkCmd.Run(); err == nil {
-		fmt.Printf("Using cached image: %s\n", imageName)
-		return imageName, nil
-	}
-
-	uidCmd := exec.Command("id", "-u")
-	uidOutput, err := uidCmd.Output()
-	if err != nil {
-		return "", fmt.Errorf("failed to get host UID: %w", err)
-	}
-
-	gidCmd := exec.Command("id", "-g")
-	gidOutput, err := gidCmd.Output()
-	if err != nil {
-		return "", fmt.Errorf("failed to get host GID: %w", err)
-	}
-
-	uid := strings.TrimSpace(string(uidOutput))
-	gid := strings.TrimSpace(string(gidOutput))
-
-	dockerfilePath, err := CreateDockerfile(username,
-		parseInt(uid), parseInt(gid), languages)
-	if err != nil {
-		return "", err
-	}
-	defer os.Remove(dockerfilePath)
-
-	fmt.Printf("Building Docker image: %s\n", imageName)
-	if len(languages) > 0 {
-		names := make([]string, len(languages))
-		for i, l := range languages {
-			names[i] = l.Name()
-		}
-		fmt.Printf("Including toolchains: %s\n", strings.Join(names, ", "))
-	}
-
-	cmd := exec.Command("docker", "build",
-		"-t", imageName,
-		"--build-arg", fmt.Sprintf("USERNAME=%s", username),
-		"--build-arg", fmt.Sprintf("USER_UID=%s", uid),
-		"--build-arg", fmt.Sprintf("USER_GID=%s", gid),
-		"-f", dockerfilePath,
-		".")
-
-	cmd.Dir = filepath.Dir(dockerfilePath)
-	cmd.Stdout = os.Stdout
-	cmd.Stderr = os.Stderr
-
-	if err := cmd.Run(); err != nil {
-		return "", fmt.Errorf("Docker build failed: %w", err)
-	}
-
-	fmt.Println("Docker image built successfully")
-	return imageName, nil
-}
-
 // validatePortMapping validates a port mapping string
 // Accepts formats: PORT, HOST_PORT:CONTAINER_PORT, IP:HOST_PORT:CONTAINER_PORT
 func validatePortMapping(portSpec string) error {
@@ -262,6 +134,33 @@ func findAvailablePort() string {
 	return strconv.Itoa(addr.Port)
 }
 
+// CreateOptions holds optional settings applied when creating a container
+type CreateOptions struct {
+	Template *templates.Template
+	// CacheFrom overrides the registry_cache ref from settings
+	CacheFrom string
+	// Session limits the attached agent session
+	Session SessionOptions
+	// NoMask mounts env files unmodified for trusted sessions
+	NoMask bool
+	// AllowSecrets skips the sensitive file scan
+	AllowSecrets bool
+	// Name is a human-friendly session name accepted in place of the container name
+	Name string
+	// WorkspaceDirs are extra repositories mounted read-write next to the project
+	WorkspaceDirs []string
+	// Network is a shared network, created with `network create`, to join
+	Network string
+	// ProtectGit overrides the git_protection setting
+	ProtectGit string
+	// Trust is the project's trust level; empty behaves like trusted
+	Trust string
+	// GUI shares the host's Wayland and X11 sockets so GUI programs can open windows
+	GUI bool
+	// Docker gives the agent a Docker daemon: DockerSocket or DockerInDocker
+	Docker string
+}
+
 func CreateContainer(
 	containerName string,
 	currentDir string,
@@ -271,6 +170,7 @@ func CreateContainer(
 	shellMode bool,
 	attach bool,
 	ports []string,
+	opts CreateOptions,
 ) error {
 	username := os.Getenv("USER")
 	if username == "" {
@@ -279,45 +179,224 @@ func CreateContainer(
 
 	// Detect project languages
 	languages := language.DetectProjectLanguages(currentDir)
+	if opts.Template != nil {
+		fmt.Println(i18n.T("template.using", opts.Template.Name, opts.Template.Description))
+		languages = opts.Template.MergeLanguages(languages)
+		ports = mergePorts(ports, opts.Template.Ports)
+	}
 	if len(languages) > 0 {
 		names := make([]string, len(languages))
 		for i, l := range languages {
 			names[i] = l.Name()
 		}
-		fmt.Printf("Detected languages: %s\n", strings.Join(names, ", "))
+		fmt.Println(i18n.T("languages.detected", strings.Join(names, ", ")))
+	}
+
+	// Restricted and untrusted projects get the safe choice for anything that would widen access
+	restricted := opts.Trust == state.TrustRestricted || opts.Trust == state.TrustUntrusted
+	untrusted := opts.Trust == state.TrustUntrusted
+	if restricted {
+		fmt.Printf("Project is %s: no sudo, no shared networks or share directory, no project Dockerfile, env files and sensitive files masked\n", opts.Trust)
+		opts.NoMask, opts.AllowSecrets, opts.Network = false, false, ""
+		// A display socket lets programs read your input, and Docker access is root on the host
+		if opts.GUI || opts.Docker != "" {
+			fmt.Printf("Warning: --gui, --docker-socket and --dind are ignored for %s projects\n", opts.Trust)
+			opts.GUI, opts.Docker = false, ""
+		}
+	}
+	if untrusted {
+		fmt.Println("Mounting the project read-only without network access")
+		ports = nil
+	}
+
+	settings, _ := config.LoadSettings()
+	platform := image.ResolvePlatform(settings.Platform)
+
+	if err := ValidateMountStrategy(settings.MountStrategy, settings.MountConsistency); err != nil {
+		return err
+	}
+	projectMode := bindMode(untrusted, settings.MountConsistency)
+	// A synced copy could be changed by the agent, so read-only projects stay bind mounted
+	mountStrategy := MountBind
+	if !untrusted {
+		mountStrategy = resolveMountStrategy(settings.MountStrategy)
+	}
+	projectVolume := fmt.Sprintf("%s:%s%s", currentDir, currentDir, projectMode)
+	if mountStrategy == MountSync {
+		if _, err := exec.LookPath("mutagen"); err != nil {
+			return fmt.Errorf("mount_strategy sync needs mutagen on the PATH (https://mutagen.io)")
+		}
+		projectVolume = fmt.Sprintf("%s:%s", syncVolume(containerName), currentDir)
+	}
+
+	cache := settings.RegistryCache
+	if opts.CacheFrom != "" {
+		cache.Ref = opts.CacheFrom
+	}
+
+	// The repository's Dockerfile could ship a setuid shell past "no sudo"
+	var customDockerfile string
+	if restricted {
+		dockerfile := config.ProjectDockerfilePath(currentDir)
+		if _, err := os.Stat(dockerfile); err == nil {
+			fmt.Printf("Ignoring %s for %s projects\n", dockerfile, opts.Trust)
+		}
+	} else {
+		var err error
+		customDockerfile, err = config.LoadProjectDockerfile(currentDir)
+		if err != nil {
+			fmt.Printf("Warning: failed to read custom Dockerfile: %v\n", err)
+		}
 	}
 
-	imageName, err := BuildDockerImage(username, languages)
+	if err := image.ValidateSudo(settings.Sudo, settings.SudoCommands); err != nil {
+		return err
+	}
+	browser, err := image.WantsBrowser(settings.BrowserTesting, currentDir)
 	if err != nil {
 		return err
 	}
+	if browser {
+		fmt.Println("Including Chromium for headless browser tests")
+	}
+	progress := ui.NewProgress(os.Stdout)
+	out := progress.Start("Building image")
+	imageName, err := image.Build(image.Spec{
+		Username:         username,
+		Agent:            agent,
+		Languages:        languages,
+		Template:         opts.Template,
+		Browser:          browser,
+		DockerCLI:        opts.Docker != "",
+		Kubernetes:       settings.Kubernetes.Tools,
+		BaseImage:        settings.BaseImage,
+		Sudo:             settings.Sudo,
+		SudoCommands:     settings.SudoCommands,
+		Platform:         platform,
+		CustomDockerfile: customDockerfile,
+		Cache:            cache,
+	}, out)
+	progress.End(err)
+	if err != nil {
+		return failure.Wrap(failure.BuildFailed, err)
+	}
 
 	args := []string{
 		"run", "-d", "-it",
 		"--name", containerName,
-		"-v", fmt.Sprintf("%s:%s", currentDir, currentDir),
+		"--label", fmt.Sprintf("%s=%s", projectLabel, currentDir),
+		"--label", fmt.Sprintf("%s=%s", agentLabel, agent),
+		"-v", projectVolume,
+		// Lets the sandbox reach host-side helpers such as the credential proxy on Linux too
+		"--add-host", credproxy.ContainerHost + ":host-gateway",
+	}
+	if opts.Name != "" {
+		args = append(args, "--label", fmt.Sprintf("%s=%s", nameLabel, opts.Name))
+	}
+	if platform != "" {
+		args = append(args, "--platform", platform)
+	}
+	if untrusted {
+		args = append(args, "--network", "none")
+	}
+	args = append(args, localeEnvArgs()...)
+	policyArgs, err := runPolicyArgs(settings.Container)
+	if err != nil {
+		return err
+	}
+	args = append(args, policyArgs...)
+	limitArgs, err := ulimitArgs(settings.Container.Ulimits, restricted)
+	if err != nil {
+		return err
+	}
+	args = append(args, limitArgs...)
+	if err := ValidateDNSPolicy(settings.DNSPolicy); err != nil {
+		return err
+	}
+	// Untrusted sandboxes have no network to resolve names on
+	dnsPolicy := settings.DNSPolicy.Mode != "" && !untrusted
+	if opts.GUI {
+		gui, err := guiArgs(username)
+		if err != nil {
+			return err
+		}
+		fmt.Println("Sharing the host display with the sandbox")
+		args = append(args, gui...)
+	}
+	args = append(args, dockerRunArgs(opts.Docker)...)
+	// Cluster credentials are secrets, so restricted projects only get the tools
+	if settings.Kubernetes.Context != "" && !restricted {
+		kubeconfig, err := writeScopedKubeconfig(containerName, settings.Kubernetes)
+		if err != nil {
+			return err
+		}
+		fmt.Printf("Sharing kubeconfig context %s read-only\n", settings.Kubernetes.Context)
+		args = append(args, kubeconfigArgs(kubeconfig)...)
+	}
+	dbTemplate, err := projectDatabase(currentDir, opts.Template, restricted)
+	if err != nil {
+		return err
+	}
+	if dbTemplate != nil {
+		args = append(args, "-e", "DATABASE_URL="+dbTemplate.url)
+	}
+	if dnsPolicy {
+		if err := ensureDNSImage(); err != nil {
+			return err
+		}
+		// The DNS sidecar listens on loopback in the sandbox's network namespace
+		args = append(args, "--dns", "127.0.0.1")
+	}
+	if opts.Network != "" {
+		args = append(args, networkArgs(opts.Network, opts.Name)...)
+		fmt.Printf("Joining network %s; other members can reach this sandbox as %s\n", opts.Network, containerName)
 	}
 
-	// If package.json exists, create an anonymous volume for node_modules
-	// This excludes the host's node_modules and creates a container-specific one
-	// The volume will be removed when the container is removed
-	packageJSON := filepath.Join(currentDir, "package.json")
-	if _, err := os.Stat(packageJSON); err == nil {
-		args = append(args, "-v", fmt.Sprintf("%s/node_modules", currentDir))
-		fmt.Println("Excluding host's node_modules (container will have its own ephemeral node_modules)")
+	if opts.NoMask {
+		fmt.Println("Env file masking disabled for this session")
+	}
+	if opts.AllowSecrets {
+		fmt.Println("Sensitive file scan disabled for this session")
+	}
+	if opts.ProtectGit == "" {
+		opts.ProtectGit = settings.GitProtection
+	}
+	if err := ValidateGitProtection(opts.ProtectGit); err != nil {
+		return err
+	}
+	if restricted && (opts.ProtectGit == "" || opts.ProtectGit == "off") {
+		opts.ProtectGit = GitProtectHooks
+	}
+	switch opts.ProtectGit {
+	case GitProtectHooks:
+		fmt.Println("Mounting .git/hooks and .git/config read-only")
+	case GitProtectReadOnly:
+		fmt.Println("Mounting .git read-only; the agent will not be able to commit")
+	}
+	args = append(args, projectMounts(containerName, currentDir, settings, opts)...)
+
+	// Extra workspace repositories get the same treatment as the project itself
+	if len(opts.WorkspaceDirs) > 0 {
+		args = append(args, "--label", fmt.Sprintf("%s=%s", workspaceLabel, strings.Join(opts.WorkspaceDirs, string(filepath.ListSeparator))))
+		for _, dir := range opts.WorkspaceDirs {
+			fmt.Printf("Mounting workspace directory: %s\n", dir)
+			args = append(args, "-v", fmt.Sprintf("%s:%s%s", dir, dir, projectMode))
+			args = append(args, projectMounts(containerName, dir, settings, opts)...)
+		}
 	}
 
-	settings, _ := config.LoadSettings()
-	for _, envFile := range settings.EnvFiles {
-		envPath := filepath.Join(currentDir, envFile)
-		if _, err := os.Stat(envPath); err == nil {
-			tempFile, err := os.CreateTemp("", "env-overlay-*")
-			if err == nil {
-				tempFile.Close()
-				containerEnvPath := filepath.Join(currentDir, envFile)
-				args = append(args, "-v", fmt.Sprintf("%s:%s:ro", tempFile.Name(), containerEnvPath))
-				fmt.Printf("Excluding %s from container mount\n", envFile)
-			}
+	if opts.Template != nil {
+		for _, mount := range opts.Template.Mounts {
+			args = append(args, "-v", fmt.Sprintf("%s:%s", mount.Volume, resolveHomePath(mount.Target, username)))
+		}
+	}
+
+	// The share directory is writable from both sides, so only trusted projects get it
+	if !restricted {
+		if shareDir, err := share.EnsureShareDir(containerName); err == nil {
+			args = append(args, "-v", fmt.Sprintf("%s:%s", shareDir, share.ContainerPath))
+		} else {
+			fmt.Printf("Warning: failed to prepare share directory: %v\n", err)
 		}
 	}
 
@@ -328,7 +407,7 @@ func CreateContainer(
 
 	// Port mapping
 	if len(ports) > 0 {
-		fmt.Println("Exposing ports:")
+		fmt.Println(i18n.T("ports.exposing"))
 		for _, portSpec := range ports {
 			if err := validatePortMapping(portSpec); err != nil {
 				return fmt.Errorf("invalid port mapping '%s': %w", portSpec, err)
@@ -363,28 +442,172 @@ func CreateContainer(
 
 	args = append(args, imageName, "/bin/bash")
 
+	crash.RecordDockerCommand(args)
+	progress.Start("Starting container")
 	cmd := exec.Command("docker", args...)
 	output, err := cmd.CombinedOutput()
+	progress.End(err)
 	if err != nil {
 		return fmt.Errorf("failed to create container: %w\nOutput: %s", err, string(output))
 	}
 
-	fmt.Printf("Container %s started successfully!\n", containerName)
+	fmt.Println(i18n.T("container.started", containerName))
 
-	fmt.Println("\nCopying agent configurations from host to container...")
-	if err := CopyAgentConfigsToContainer(containerName, agent); err != nil {
-		fmt.Printf("Warning: failed to copy agent configs: %v\n", err)
+	// Record the container right away so an interrupted setup can still be resumed and cleaned up
+	if err := SaveLastContainer(containerName); err != nil {
+		fmt.Printf("Warning: failed to record last container: %v\n", err)
 	}
 
-	agentCmd := BuildAgentCommand(currentDir, agent, false, skipPermissionFlag)
-	if err := state.SaveContainerRunCommand(containerName, []string{agentCmd}); err != nil {
-		fmt.Printf("Warning: failed to save container command: %v\n", err)
+	// These steps do not depend on each other, so they run at the same time to
+	// shorten cold starts. Templates run afterwards since they need the project
+	// synced and names resolving. The first step, the project sync, is the only one
+	// whose failure stops the setup.
+	steps := []setupStep{
+		{run: func(out io.Writer) error {
+			if mountStrategy == MountSync {
+				return startSync(out, containerName, username, currentDir, settings)
+			}
+			return nil
+		}},
+		{run: func(out io.Writer) error {
+			if !dnsPolicy {
+				return nil
+			}
+			if err := startDNSPolicy(out, containerName, settings.DNSPolicy); err != nil {
+				return fmt.Errorf("%w\nThe sandbox cannot resolve any names until the DNS sidecar runs", err)
+			}
+			return nil
+		}},
+		{run: func(out io.Writer) error {
+			switch opts.Docker {
+			case DockerSocket:
+				return grantDockerSocket(containerName, username)
+			case DockerInDocker:
+				return startDind(out, containerName, append([]string{currentDir}, opts.WorkspaceDirs...))
+			}
+			return nil
+		}},
+		{warning: "failed to save container command", run: func(io.Writer) error {
+			agentCmd := BuildAgentCommand(currentDir, agent, false, skipPermissionFlag)
+			return state.SaveContainerRunCommand(containerName, []string{agentCmd})
+		}},
+		{warning: "failed to copy agent configs", run: func(out io.Writer) error {
+			return CopyAgentConfigsToContainer(out, containerName, agent, opts.Trust)
+		}},
+		{warning: "failed to install share helper", run: func(io.Writer) error {
+			if restricted {
+				return nil
+			}
+			return installShareHelper(containerName)
+		}},
+		{run: func(io.Writer) error {
+			// Other toolchains install themselves on first use, which needs sudo
+			if !restricted && (settings.Sudo == "" || settings.Sudo == image.SudoFull) {
+				return installToolShims(containerName, username, languages)
+			}
+			return nil
+		}},
+		{run: func(io.Writer) error {
+			return ensureLocale(containerName)
+		}},
+	}
+	out = progress.Start("Setting up the sandbox")
+	errs := runSetupSteps(steps, out)
+	progress.End(errs[0])
+	warnSetupErrors(steps[1:], errs[1:])
+	if errs[0] != nil {
+		return fmt.Errorf("%w\nThe sandbox's copy of the project may be incomplete; remove it and set \"mount_strategy\": \"bind\" to use a bind mount", errs[0])
+	}
+
+	if opts.Template != nil || dbTemplate != nil {
+		out = progress.Start("Running setup commands")
+	}
+	if opts.Template != nil {
+		if err := applyTemplate(out, containerName, currentDir, username, opts.Template); err != nil {
+			fmt.Fprintf(out, "Warning: failed to apply template %s: %v\n", opts.Template.Name, err)
+		}
+	}
+	if dbTemplate != nil {
+		if err := applyTemplate(out, containerName, currentDir, username, dbTemplate.Template); err != nil {
+			fmt.Fprintf(out, "Warning: failed to set up the database: %v\n", err)
+		}
+	}
+	progress.End(nil)
+
+	// Setup above may need root; the agent does not get it
+	if restricted {
+		if err := revokeSudo(containerName, username); err != nil {
+			fmt.Printf("Warning: %v\n", err)
+		}
 	}
 
 	if attach {
-		return AttachToContainer(containerName, currentDir, agent, false, skipPermissionFlag, shellMode)
+		// Start in the subdirectory the command was run from
+		workDir := currentDir
+		if cwd, err := os.Getwd(); err == nil && IsWithin(currentDir, cwd) {
+			workDir = cwd
+		}
+		return AttachToContainer(containerName, workDir, agent, false, skipPermissionFlag, shellMode, opts.Session)
+	}
+
+	return nil
+}
+
+// projectMounts returns the extra mounts for a read-write project directory: a
+// container-only node_modules, masked env files, hidden sensitive files and a
+// protected .git
+func projectMounts(containerName, dir string, settings *config.Settings, opts CreateOptions) []string {
+	var args []string
+
+	// If package.json exists, create an anonymous volume for node_modules
+	// This excludes the host's node_modules and creates a container-specific one
+	// The volume will be removed when the container is removed
+	// A read-only project has nowhere to put it, and nothing can be installed anyway
+	packageJSON := filepath.Join(dir, "package.json")
+	if _, err := os.Stat(packageJSON); err == nil && opts.Trust != state.TrustUntrusted {
+		args = append(args, "-v", fmt.Sprintf("%s/node_modules", dir))
+		fmt.Println("Excluding host's node_modules (container will have its own ephemeral node_modules)")
+	}
+
+	masked := make(map[string]bool)
+	if !opts.NoMask {
+		// The repository's own env_files could turn masking off, so below trusted only
+		// the user's settings count
+		var projectSettings *config.ProjectSettings
+		if opts.Trust != state.TrustRestricted && opts.Trust != state.TrustUntrusted {
+			var err error
+			projectSettings, err = config.LoadProjectSettings(dir)
+			if err != nil {
+				fmt.Printf("Warning: failed to load project settings: %v\n", err)
+			}
+		}
+
+		for _, envFile := range maskedEnvFiles(dir, config.EnvFilePatterns(settings, projectSettings)) {
+			envPath := filepath.Join(dir, filepath.FromSlash(envFile))
+			overlay, err := writeEnvOverlay(dir, envPath, settings.SandboxEnv)
+			if err == nil {
+				args = append(args, "-v", fmt.Sprintf("%s:%s:ro", overlay, envPath))
+				masked[envFile] = true
+				fmt.Printf("Excluding %s from container mount\n", envFile)
+			}
+		}
+	}
+
+	if !opts.AllowSecrets {
+		args = append(args, secretMounts(containerName, dir, settings.SecretScan, masked)...)
 	}
+	args = append(args, gitProtectionMounts(dir, opts.ProtectGit)...)
+	return args
+}
 
+// installShareHelper writes the in-container share command used to hand files to the host
+func installShareHelper(containerName string) error {
+	cmd := exec.Command("docker", "exec", "-i", "--user", "root", containerName,
+		"sh", "-c", fmt.Sprintf("cat > %[1]s && chmod 755 %[1]s", share.HelperPath))
+	cmd.Stdin = strings.NewReader(share.HelperScript)
+	if output, err := cmd.CombinedOutput(); err != nil {
+		return fmt.Errorf("%w\nOutput: %s", err, string(output))
+	}
 	return nil
 }
 
@@ -395,33 +618,40 @@ func ResumeContainer(
 	skipPermissionFlag string,
 	shellMode bool,
 	attach bool,
+	limits SessionOptions,
 ) error {
-	fmt.Printf("Resuming container: %s\n", containerName)
+	fmt.Println(i18n.T("container.resuming", containerName))
 
-	exists, err := ContainerExists(containerName)
-	if err != nil || !exists {
-		return fmt.Errorf("container '%s' does not exist", containerName)
-	}
-
-	running, err := IsContainerRunning(containerName)
+	info, err := InspectContainer(containerName)
 	if err != nil {
 		return err
 	}
+	if !info.Exists {
+		return failure.Errorf(failure.ContainerNotFound, "container '%s' does not exist", containerName)
+	}
 
-	if !running {
-		fmt.Printf("Starting stopped container: %s\n", containerName)
+	if info.Paused {
+		fmt.Println(i18n.T("container.unpausing", containerName))
+		if err := UnpauseContainer(containerName); err != nil {
+			return err
+		}
+	} else if !info.Running {
+		fmt.Println(i18n.T("container.starting_stopped", containerName))
 		cmd := exec.Command("docker", "start", containerName)
 		if err := cmd.Run(); err != nil {
 			return fmt.Errorf("failed to start container: %w", err)
 		}
-		fmt.Printf("Container %s is running\n", containerName)
+		if err := restartServices(containerName); err != nil {
+			fmt.Printf("Warning: failed to restart services: %v\n", err)
+		}
+		fmt.Println(i18n.T("container.running", containerName))
 	} else {
-		fmt.Println("Container is already running")
+		fmt.Println(i18n.T("container.already_running"))
 	}
 
 	if attach {
 		currentDir, _ := os.Getwd()
-		return AttachToContainer(containerName, currentDir, agent, agentContinue, skipPermissionFlag, shellMode)
+		return AttachToContainer(containerName, currentDir, agent, agentContinue, skipPermissionFlag, shellMode, limits)
 	}
 
 	return nil
@@ -429,7 +659,8 @@ func ResumeContainer(
 
 func BuildAgentCommand(currentDir string, agent config.Agent, agentContinue bool, skipPermissionFlag string) string {
 	// Use host path in the container
-	cmd := fmt.Sprintf("cd %s && export PATH=\"$HOME/.cargo/bin:$HOME/.local/bin:$PATH\" && %s",
+	// exec makes the agent take over the shell's PID, which recordAgentPID records
+	cmd := fmt.Sprintf("cd %s && export PATH=\"$HOME/.cargo/bin:$HOME/.local/bin:$PATH\" && exec %s",
 		currentDir, agent.Command())
 
 	if agentContinue {
@@ -450,7 +681,32 @@ func AttachToContainer(
 	agentContinue bool,
 	skipPermissionFlag string,
 	shellMode bool,
+	limits SessionOptions,
 ) error {
+	snapshotWorkspace(containerName, currentDir)
+	for {
+		// A restarted container gets a fresh network namespace without the limit
+		limitBandwidth(containerName)
+		reattach, err := attachSession(containerName, currentDir, agent, agentContinue, skipPermissionFlag, shellMode, limits)
+		if err != nil || !reattach {
+			return err
+		}
+		// Pick the conversation back up after the container was recovered
+		agentContinue = true
+	}
+}
+
+// attachSession runs one attached session and reports whether it should be resumed
+// because the Docker daemon restarted underneath it
+func attachSession(
+	containerName string,
+	currentDir string,
+	agent config.Agent,
+	agentContinue bool,
+	skipPermissionFlag string,
+	shellMode bool,
+	limits SessionOptions,
+) (bool, error) {
 	username := os.Getenv("USER")
 	if username == "" {
 		username = "ubuntu"
@@ -463,34 +719,220 @@ func AttachToContainer(
 		"--user", username,
 		"-e", fmt.Sprintf("HOME=/home/%s", username),
 	)
+	// Match the host's timezone, locale and terminal so output and TUIs render as they would locally
+	args = append(args, localeEnvArgs()...)
+	args = append(args, terminalEnvArgs()...)
 
 	if currentDir != "" {
 		args = append(args, "-w", currentDir)
 	}
 
+	settings, _ := config.LoadSettings()
+
+	// Setup already done in this container is skipped on warm attaches
+	info, _ := InspectContainer(containerName)
+	warm := loadWarmState(containerName, info.ID)
+
+	// Logs belong to the project even when attaching from one of its subdirectories
+	projectDir := currentDir
+	if IsWithin(info.Project, currentDir) {
+		projectDir = info.Project
+	}
+
+	// Record every command run during the session
+	session := newAuditSession()
+	if err := warm.ensureScript(containerName, auditHookPath, auditHook, installAuditHook); err != nil {
+		fmt.Printf("Warning: failed to install audit hook: %v\n", err)
+	} else {
+		args = append(args, session.Env()...)
+		defer finishAuditSession(containerName, projectDir, session)
+
+		// Hold dangerous commands until they are approved on the host
+		if settings.ApprovalGate.Enabled {
+			if err := installGatePatterns(containerName, settings.ApprovalGate.Patterns); err != nil {
+				fmt.Printf("Warning: failed to enable approval gate: %v\n", err)
+			} else {
+				args = append(args, gateEnv(settings.ApprovalGate)...)
+				fmt.Println("Approval gate enabled: run 'agentsandbox approve' in another terminal to review held commands")
+			}
+		}
+	}
+
+	// Git config for the session, passed through the environment
+	var gitConfig [][2]string
+
+	// Tag commits made during the session so history can be traced back to its logs
+	if settings.TagCommits {
+		if err := warm.ensureScript(containerName, commitHooksDir, commitHook, installCommitHooks); err != nil {
+			fmt.Printf("Warning: failed to install git hooks: %v\n", err)
+		} else {
+			args = append(args, commitHookEnv(session.ID)...)
+			gitConfig = append(gitConfig, commitHookConfig...)
+			defer func() {
+				if err := recordSessionCommits(containerName, projectDir, session.ID); err != nil {
+					fmt.Printf("Warning: %v\n", err)
+				}
+			}()
+		}
+	}
+
+	// Give the session a GitHub token that only reaches the project's repository
+	if settings.GitHubApp.AppID != 0 {
+		tokenEnv, tokenConfig := githubTokenEnv(containerName, projectDir, settings.GitHubApp)
+		args = append(args, tokenEnv...)
+		gitConfig = append(gitConfig, tokenConfig...)
+	}
+	args = append(args, gitConfigEnv(gitConfig)...)
+
+	// Short-lived cloud credentials that expire with the session. They are secrets, so
+	// like the kubeconfig they are withheld from restricted projects.
+	if isRestricted(RecordedTrust(projectDir)) {
+		if settings.CloudCredentials.AWS.RoleARN != "" || settings.CloudCredentials.GCP.ServiceAccount != "" {
+			fmt.Println("Cloud credentials are not shared with restricted projects")
+		}
+	} else {
+		args = append(args, cloudCredentialEnv(containerName, username, settings.CloudCredentials, limits.MaxDuration)...)
+	}
+
+	// Authenticate the agent through a host-side proxy so its API key stays outside the container
+	if settings.CredentialProxy {
+		if proxy := startCredentialProxy(agent); proxy != nil {
+			args = append(args, proxy.EnvArgs()...)
+			defer proxy.Close()
+		}
+	}
+
+	// Record which hosts the session talks to, without decrypting any of it
+	if settings.NetworkLog {
+		if proxy := startNetworkLog(settings.DNSPolicy); proxy != nil {
+			args = append(args, proxy.EnvArgs()...)
+			defer finishNetworkLog(containerName, projectDir, session.ID, proxy)
+		}
+	}
+
 	args = append(args, containerName, "/bin/bash", "-l")
 
+	// Remember when the container was last used so the maintenance daemon leaves it running
+	defer warm.touch(containerName)
+
 	if shellMode {
+		warm.touch(containerName)
 		cmd := exec.Command("docker", args...)
 		cmd.Stdin = os.Stdin
 		cmd.Stdout = os.Stdout
 		cmd.Stderr = os.Stderr
-		return cmd.Run()
+		if err := cmd.Start(); err != nil {
+			return false, err
+		}
+		relay := relaySignals(containerName, "", cmd)
+		err := cmd.Wait()
+		if sig := relay.Stop(); sig != nil {
+			reportSignalDetach(containerName, sig)
+			return false, nil
+		}
+		return false, err
 	}
 
-	agentCmd := BuildAgentCommand(currentDir, agent, agentContinue, skipPermissionFlag)
-	args = append(args, "-c", agentCmd)
+	// Verify the agent can actually start before dropping into the session
+	ready, err := ensureAgentReady(containerName, currentDir, agent, warm)
+	if err != nil {
+		return false, failure.Wrap(failure.AgentLaunchFailed, err)
+	}
+	if !ready {
+		return false, nil
+	}
+	warm.touch(containerName)
 
+	agentCmd := BuildAgentCommand(currentDir, agent, agentContinue, skipPermissionFlag)
+	// Record the session output in the container so failures can be diagnosed and the
+	// watchdog can read it, while the agent keeps the terminal itself
+	transcript := sessionTranscriptPath(session.ID)
+	defer removeTranscript(containerName, transcript)
+	defer saveTranscriptLog(containerName, projectDir, transcript, time.Now())
+	// Signals for the agent go to the PID it records, not to whatever matches its name
+	pidFile := agentPIDPath(session.ID)
+	defer exec.Command("docker", "exec", containerName, "rm", "-f", pidFile).Run()
+	args = append(args, "-c", recordTranscript(recordAgentPID(agentCmd, pidFile), transcript))
+
+	crash.RecordDockerCommand(args)
 	cmd := exec.Command("docker", args...)
 
+	wd := newWatchdog(settings.Watchdog, watchdogTrigger(containerName, agent, pidFile, settings.Watchdog))
 	cmd.Stdin = os.Stdin
 	cmd.Stdout = os.Stdout
 	cmd.Stderr = os.Stderr
 
-	return cmd.Run()
+	if err := cmd.Start(); err != nil {
+		return false, failure.Errorf(failure.AgentLaunchFailed, "failed to start %s: %w", agent.DisplayName(), err)
+	}
+	timer := startSessionTimer(containerName, agent, pidFile, cmd, limits)
+	// Ctrl+C, kill or closing the terminal end the agent cleanly so the deferred log collection still runs
+	relay := relaySignals(containerName, pidFile, cmd)
+	stopWatchdog := make(chan struct{})
+	if wd.enabled() {
+		go wd.run(stopWatchdog)
+		go wd.follow(containerName, transcript, stopWatchdog)
+	}
+	err = cmd.Wait()
+	close(stopWatchdog)
+	sig := relay.Stop()
+
+	if timer.Stop() {
+		fmt.Println(i18n.T("session.time_limit", limits.MaxDuration))
+		if limits.StopOnTimeout {
+			if err := StopContainer(containerName); err != nil {
+				fmt.Printf("Warning: %v\n", err)
+			}
+		}
+		return false, nil
+	}
+
+	if sig != nil {
+		reportSignalDetach(containerName, sig)
+		return false, nil
+	}
+
+	if err != nil {
+		if isUserInterrupt(err) {
+			return false, nil
+		}
+		if sessionLostToDaemon(containerName, info.StartedAt) {
+			if err := recoverFromDaemonRestart(containerName); err != nil {
+				return false, failure.Errorf(failure.DockerUnavailable, "%s session ended because Docker became unavailable: %w", agent.DisplayName(), err)
+			}
+			if confirmReattach(agent) {
+				return true, nil
+			}
+			fmt.Println(i18n.T("session.resume_hint"))
+			return false, nil
+		}
+		if file, pm, _ := CollectPostMortem(containerName); file != "" {
+			fmt.Println(i18n.T("postmortem.saved", file))
+			return false, fmt.Errorf("%s session ended because the container stopped: %s", agent.DisplayName(), pm.Reason())
+		}
+		reportAttachFailure(containerName, currentDir, agent, agentCmd, err, readTranscriptTail(containerName, transcript))
+		return false, failure.Errorf(failure.AgentLaunchFailed, "failed to start %s: %w", agent.DisplayName(), err)
+	}
+
+	return false, nil
 }
 
-func CopyAgentConfigsToContainer(containerName string, agent config.Agent) error {
+// sshWithheld returns why ~/.ssh is not copied into a sandbox, or "" when it is. Keys
+// reach every repository the user can push to, so they are kept from projects that
+// are not trusted and from sandboxes that already get a token scoped to their repository.
+func sshWithheld(settings *config.Settings, level string) string {
+	if isRestricted(level) {
+		return fmt.Sprintf("the project is %s", level)
+	}
+	if settings.GitHubApp.AppID != 0 {
+		return "the sandbox gets a scoped GitHub token"
+	}
+	return ""
+}
+
+// CopyAgentConfigsToContainer copies the agent's configuration and, unless sshWithheld
+// says otherwise, ~/.ssh into the container. level is the project's trust level.
+func CopyAgentConfigsToContainer(out io.Writer, containerName string, agent config.Agent, level string) error {
 	homeDir, err := os.UserHomeDir()
 	if err != nil {
 		return fmt.Errorf("failed to get home directory: %w", err)
@@ -501,13 +943,16 @@ func CopyAgentConfigsToContainer(containerName string, agent config.Agent) error
 		username = "ubuntu"
 	}
 
-	fmt.Println("\nCopying agent configurations from host to container...")
+	fmt.Fprintf(out, "\n%s\n", i18n.T("configs.copying"))
 
+	settings, _ := config.LoadSettings()
 	sshDir := filepath.Join(homeDir, ".ssh")
-	if _, err := os.Stat(sshDir); err == nil {
+	if reason := sshWithheld(settings, level); reason != "" {
+		fmt.Fprintf(out, "Not copying ~/.ssh: %s\n", reason)
+	} else if _, err := os.Stat(sshDir); err == nil {
 		containerSSHPath := fmt.Sprintf("/home/%s/.ssh", username)
-		if err := copyConfigToContainer(containerName, sshDir, containerSSHPath, username); err != nil {
-			fmt.Printf("Warning: failed to copy .ssh directory: %v\n", err)
+		if err := copyConfigToContainer(out, containerName, sshDir, containerSSHPath, username); err != nil {
+			fmt.Fprintf(out, "Warning: failed to copy .ssh directory: %v\n", err)
 		}
 	}
 
@@ -515,14 +960,14 @@ func CopyAgentConfigsToContainer(containerName string, agent config.Agent) error
 	if agent == config.AgentClaude {
 		claudeConfig := config.GetClaudeConfigDir()
 		if claudeConfig != "" {
-			if err := copyConfigToContainer(containerName, claudeConfig, fmt.Sprintf("/home/%s/.claude", username), username); err != nil {
-				fmt.Printf("Warning: failed to copy Claude config directory: %v\n", err)
+			if err := copyConfigToContainer(out, containerName, claudeConfig, fmt.Sprintf("/home/%s/.claude", username), username); err != nil {
+				fmt.Fprintf(out, "Warning: failed to copy Claude config directory: %v\n", err)
 			}
 		}
 		claudeJSON := filepath.Join(homeDir, ".claude.json")
 		if _, err := os.Stat(claudeJSON); err == nil {
-			if err := copyConfigToContainer(containerName, claudeJSON, fmt.Sprintf("/home/%s/.claude.json", username), username); err != nil {
-				fmt.Printf("Warning: failed to copy .claude.json: %v\n", err)
+			if err := copyConfigToContainer(out, containerName, claudeJSON, fmt.Sprintf("/home/%s/.claude.json", username), username); err != nil {
+				fmt.Fprintf(out, "Warning: failed to copy .claude.json: %v\n", err)
 			}
 		}
 		agentNames = []string{"claude"}
@@ -534,16 +979,16 @@ func CopyAgentConfigsToContainer(containerName string, agent config.Agent) error
 		configDir := filepath.Join(homeDir, "."+agentName)
 		if _, err := os.Stat(configDir); err == nil {
 			containerPath := fmt.Sprintf("/home/%s/.%s", username, agentName)
-			if err := copyConfigToContainer(containerName, configDir, containerPath, username); err != nil {
-				fmt.Printf("Warning: failed to copy %s config directory: %v\n", agentName, err)
+			if err := copyConfigToContainer(out, containerName, configDir, containerPath, username); err != nil {
+				fmt.Fprintf(out, "Warning: failed to copy %s config directory: %v\n", agentName, err)
 			}
 		}
 
 		configJSON := filepath.Join(homeDir, "."+agentName+".json")
 		if _, err := os.Stat(configJSON); err == nil {
 			containerPath := fmt.Sprintf("/home/%s/.%s.json", username, agentName)
-			if err := copyConfigToContainer(containerName, configJSON, containerPath, username); err != nil {
-				fmt.Printf("Warning: failed to copy .%s.json: %v\n", agentName, err)
+			if err := copyConfigToContainer(out, containerName, configJSON, containerPath, username); err != nil {
+				fmt.Fprintf(out, "Warning: failed to copy .%s.json: %v\n", agentName, err)
 			}
 		}
 
@@ -553,17 +998,21 @@ func CopyAgentConfigsToContainer(containerName string, agent config.Agent) error
 			mkdirCmd := exec.Command("docker", "exec", containerName, "mkdir", "-p", fmt.Sprintf("/home/%s/.config", username))
 			_ = mkdirCmd.Run()
 
-			if err := copyConfigToContainer(containerName, configPath, containerPath, username); err != nil {
-				fmt.Printf("Warning: failed to copy %s config from .config: %v\n", agentName, err)
+			if err := copyConfigToContainer(out, containerName, configPath, containerPath, username); err != nil {
+				fmt.Fprintf(out, "Warning: failed to copy %s config from .config: %v\n", agentName, err)
 			}
 		}
 	}
 
-	fmt.Println("Configuration copy completed!")
+	if settings.CredentialProxy {
+		removeCredentialFiles(out, containerName, agent, username)
+	}
+
+	fmt.Fprintln(out, i18n.T("configs.copied"))
 	return nil
 }
 
-func copyConfigToContainer(containerName, hostPath, containerPath, username string) error {
+func copyConfigToContainer(out io.Writer, containerName, hostPath, containerPath, username string) error {
 	uidCmd := exec.Command("id", "-u")
 	uidOutput, err := uidCmd.Output()
 	if err != nil {
@@ -584,7 +1033,7 @@ func copyConfigToContainer(containerName, hostPath, containerPath, username stri
 	}
 
 	if hostInfo.IsDir() {
-		rmCmd := exec.Command("docker", "exec", containerName, "sudo", "rm", "-rf", containerPath)
+		rmCmd := exec.Command("docker", "exec", "--user", "root", containerName, "rm", "-rf", containerPath)
 		_ = rmCmd.Run()
 	}
 
@@ -594,30 +1043,30 @@ func copyConfigToContainer(containerName, hostPath, containerPath, username stri
 		return fmt.Errorf("docker cp failed: %w\nOutput: %s", err, string(output))
 	}
 
-	fmt.Printf("✓ Copied %s to container:%s\n", hostPath, containerPath)
+	fmt.Fprintf(out, "%sCopied %s to container:%s\n", ui.Check(), hostPath, containerPath)
 
-	chownCmd := exec.Command("docker", "exec", containerName, "sudo", "chown", "-R", fmt.Sprintf("%s:%s", uid, gid), containerPath)
+	chownCmd := exec.Command("docker", "exec", "--user", "root", containerName, "chown", "-R", fmt.Sprintf("%s:%s", uid, gid), containerPath)
 	chownOutput, err := chownCmd.CombinedOutput()
 	if err != nil {
 		return fmt.Errorf("failed to set ownership: %w\nOutput: %s", err, string(chownOutput))
 	}
 
 	if filepath.Base(hostPath) == ".ssh" || strings.HasSuffix(containerPath, ".ssh") {
-		chmodDirCmd := exec.Command("docker", "exec", containerName, "sudo", "chmod", "700", containerPath)
+		chmodDirCmd := exec.Command("docker", "exec", "--user", "root", containerName, "chmod", "700", containerPath)
 		chmodDirOutput, err := chmodDirCmd.CombinedOutput()
 		if err != nil {
 			return fmt.Errorf("failed to set .ssh directory permissions: %w\nOutput: %s", err, string(chmodDirOutput))
 		}
 
-		chmodFilesCmd := exec.Command("docker", "exec", containerName, "sudo", "find", containerPath, "-type", "f", "-exec", "chmod", "600", "{}", ";")
+		chmodFilesCmd := exec.Command("docker", "exec", "--user", "root", containerName, "find", containerPath, "-type", "f", "-exec", "chmod", "600", "{}", ";")
 		chmodFilesOutput, err := chmodFilesCmd.CombinedOutput()
 		if err != nil {
 			return fmt.Errorf("failed to set .ssh file permissions: %w\nOutput: %s", err, string(chmodFilesOutput))
 		}
 
-		fmt.Printf("✓ Set strict SSH permissions (700 for directory, 600 for files)\n")
+		fmt.Fprintf(out, "%sSet strict SSH permissions (700 for directory, 600 for files)\n", ui.Check())
 	} else {
-		chmodCmd := exec.Command("docker", "exec", containerName, "sudo", "chmod", "-R", "u+rwX", containerPath)
+		chmodCmd := exec.Command("docker", "exec", "--user", "root", containerName, "chmod", "-R", "u+rwX", containerPath)
 		chmodOutput, err := chmodCmd.CombinedOutput()
 		if err != nil {
 			return fmt.Errorf("failed to set permissions: %w\nOutput: %s", err, string(chmodOutput))
@@ -626,9 +1075,3 @@ func copyConfigToContainer(containerName, hostPath, containerPath, username stri
 
 	return nil
 }
-
-func parseInt(s string) int {
-	var result int
-	fmt.Sscanf(s, "%d", &result)
-	return result
-}
@@ -2,6 +2,9 @@ package container
 
 import (
 	"testing"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/state"
 )
 
 func TestValidatePortMapping(t *testing.T) {
@@ -43,3 +46,26 @@ func TestValidatePortMapping(t *testing.T) {
 		})
 	}
 }
+
+func TestSSHWithheld(t *testing.T) {
+	settings := config.DefaultSettings()
+	withApp := config.DefaultSettings()
+	withApp.GitHubApp.AppID = 123456
+
+	tests := []struct {
+		settings *config.Settings
+		level    string
+		withheld bool
+	}{
+		{settings, state.TrustTrusted, false},
+		{settings, "", false},
+		{settings, state.TrustRestricted, true},
+		{settings, state.TrustUntrusted, true},
+		{withApp, state.TrustTrusted, true},
+	}
+	for _, tt := range tests {
+		if got := sshWithheld(tt.settings, tt.level) != ""; got != tt.withheld {
+			t.Errorf("sshWithheld(app %d, %q) withheld = %v, want %v", tt.settings.GitHubApp.AppID, tt.level, got, tt.withheld)
+		}
+	}
+}
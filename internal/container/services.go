@@ -0,0 +1,205 @@
+package container
+
+import (
+	"fmt"
+	"io"
+	"os/exec"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/templates"
+)
+
+const serviceParentLabel = "agentsandbox.parent"
+
+// serviceContainerName returns the name of a sidecar service container
+func serviceContainerName(containerName, service string) string {
+	return fmt.Sprintf("%s-svc-%s", containerName, service)
+}
+
+// isServiceContainer reports whether a container's labels mark it as a sidecar service.
+// Sidecars are told apart by label rather than name, since a project may have "-svc-"
+// in its own name.
+func isServiceContainer(labels map[string]string) bool {
+	return labels[serviceParentLabel] != ""
+}
+
+// psNamesFormat prints each container's name and, for sidecars, its sandbox
+var psNamesFormat = fmt.Sprintf("{{.Names}}\t{{.Label %q}}", serviceParentLabel)
+
+// sandboxNames returns the container names in docker ps output printed with
+// psNamesFormat, leaving out sidecar services
+func sandboxNames(output []byte) []string {
+	var names []string
+	for _, line := range strings.Split(string(output), "\n") {
+		name, parent, _ := strings.Cut(strings.TrimSpace(line), "\t")
+		if name == "" || isServiceContainer(map[string]string{serviceParentLabel: parent}) {
+			continue
+		}
+		names = append(names, name)
+	}
+	return names
+}
+
+// resolveHomePath expands a leading "~/" to the sandbox user's home directory
+func resolveHomePath(path, username string) string {
+	if strings.HasPrefix(path, "~/") {
+		return fmt.Sprintf("/home/%s/%s", username, strings.TrimPrefix(path, "~/"))
+	}
+	return path
+}
+
+// mergePorts appends template ports that are not already published
+func mergePorts(ports, extra []string) []string {
+	merged := append([]string{}, ports...)
+	for _, port := range extra {
+		found := false
+		for _, existing := range ports {
+			if existing == port || strings.HasSuffix(existing, ":"+port) {
+				found = true
+				break
+			}
+		}
+		if !found {
+			merged = append(merged, port)
+		}
+	}
+	return merged
+}
+
+// applyTemplate starts the template's services, fixes volume ownership and runs setup commands
+func applyTemplate(out io.Writer, containerName, currentDir, username string, tmpl *templates.Template) error {
+	for _, svc := range tmpl.Services {
+		if err := startService(out, containerName, svc); err != nil {
+			return err
+		}
+	}
+	for _, svc := range tmpl.Services {
+		waitForService(out, containerName, svc)
+	}
+
+	for _, mount := range tmpl.Mounts {
+		target := resolveHomePath(mount.Target, username)
+		chownCmd := exec.Command("docker", "exec", "--user", "root", containerName, "chown", "-R", username+":", target)
+		if output, err := chownCmd.CombinedOutput(); err != nil {
+			fmt.Fprintf(out, "Warning: failed to set ownership of %s: %v\n%s", target, err, string(output))
+		}
+	}
+
+	for _, setup := range tmpl.SetupCommands {
+		fmt.Fprintf(out, "Running setup: %s\n", setup)
+		cmd := containerShell(containerName, fmt.Sprintf("cd %s && %s", currentDir, setup))
+		cmd.Stdout = out
+		cmd.Stderr = out
+		if err := cmd.Run(); err != nil {
+			fmt.Fprintf(out, "Warning: setup command failed: %v\n", err)
+		}
+	}
+
+	return nil
+}
+
+// startService runs a sidecar sharing the sandbox's network namespace so it is reachable on localhost
+func startService(out io.Writer, containerName string, svc templates.Service) error {
+	name := serviceContainerName(containerName, svc.Name)
+
+	args := []string{
+		"run", "-d",
+		"--name", name,
+		"--network", fmt.Sprintf("container:%s", containerName),
+		"--label", fmt.Sprintf("%s=%s", serviceParentLabel, containerName),
+	}
+
+	keys := make([]string, 0, len(svc.Env))
+	for key := range svc.Env {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	for _, key := range keys {
+		args = append(args, "-e", fmt.Sprintf("%s=%s", key, svc.Env[key]))
+	}
+	args = append(args, svc.Image)
+	args = append(args, svc.Command...)
+
+	fmt.Fprintf(out, "Starting service %s (%s)\n", svc.Name, svc.Image)
+	output, err := exec.Command("docker", args...).CombinedOutput()
+	if err != nil {
+		return fmt.Errorf("failed to start service %s: %w\nOutput: %s", svc.Name, err, string(output))
+	}
+	return nil
+}
+
+// serviceReadyTimeout bounds how long setup waits for a service's readiness check
+const serviceReadyTimeout = 2 * time.Minute
+
+// waitForService runs the service's readiness check until it passes, so setup commands
+// do not race a database that is still initialising
+func waitForService(out io.Writer, containerName string, svc templates.Service) {
+	if len(svc.Ready) == 0 {
+		return
+	}
+	name := serviceContainerName(containerName, svc.Name)
+	deadline := time.Now().Add(serviceReadyTimeout)
+	for {
+		args := append([]string{"exec", name}, svc.Ready...)
+		if exec.Command("docker", args...).Run() == nil {
+			return
+		}
+		if time.Now().After(deadline) {
+			fmt.Fprintf(out, "Warning: service %s was not ready after %s; running setup anyway\n", svc.Name, serviceReadyTimeout)
+			return
+		}
+		time.Sleep(time.Second)
+	}
+}
+
+// listServices returns the sidecar containers attached to a sandbox
+func listServices(containerName string) ([]string, error) {
+	cmd := exec.Command("docker", "ps", "-a",
+		"--filter", fmt.Sprintf("label=%s=%s", serviceParentLabel, containerName),
+		"--format", "{{.Names}}")
+	output, err := cmd.Output()
+	if err != nil {
+		return nil, fmt.Errorf("failed to list services: %w", err)
+	}
+
+	var services []string
+	for _, name := range strings.Split(string(output), "\n") {
+		name = strings.TrimSpace(name)
+		if name != "" {
+			services = append(services, name)
+		}
+	}
+	return services, nil
+}
+
+// removeServices removes a sandbox's sidecars together with their anonymous volumes
+func removeServices(containerName string) {
+	services, err := listServices(containerName)
+	if err != nil {
+		fmt.Printf("Warning: %v\n", err)
+		return
+	}
+	for _, name := range services {
+		if output, err := exec.Command("docker", "rm", "-f", "-v", name).CombinedOutput(); err != nil {
+			fmt.Printf("Warning: failed to remove service %s: %v\nOutput: %s\n", name, err, string(output))
+		}
+	}
+}
+
+// restartServices restarts sidecars after their sandbox was started again.
+// Sidecars share the sandbox's network namespace, so they must be restarted to rejoin it.
+func restartServices(containerName string) error {
+	services, err := listServices(containerName)
+	if err != nil {
+		return err
+	}
+
+	for _, name := range services {
+		if err := exec.Command("docker", "restart", name).Run(); err != nil {
+			return fmt.Errorf("failed to restart service %s: %w", name, err)
+		}
+	}
+	return nil
+}
@@ -0,0 +1,95 @@
+package container
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"sync"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+// sessionGracePeriod is how long an interrupted agent gets to exit before it is killed
+const sessionGracePeriod = 30 * time.Second
+
+// SessionOptions controls limits applied to an attached agent session
+type SessionOptions struct {
+	// MaxDuration interrupts the agent after this long; zero means no limit
+	MaxDuration time.Duration
+	// StopOnTimeout stops the container once the time limit was reached
+	StopOnTimeout bool
+}
+
+// sessionTimer interrupts an agent session once it exceeds its time limit
+type sessionTimer struct {
+	mu      sync.Mutex
+	expired bool
+	timers  []*time.Timer
+}
+
+// startSessionTimer arms the time limit for a running agent command, whose PID is
+// recorded in pidFile
+func startSessionTimer(containerName string, agent config.Agent, pidFile string, cmd *exec.Cmd, opts SessionOptions) *sessionTimer {
+	st := &sessionTimer{}
+	if opts.MaxDuration <= 0 {
+		return st
+	}
+
+	st.timers = append(st.timers, time.AfterFunc(opts.MaxDuration, func() {
+		st.mu.Lock()
+		st.expired = true
+		st.mu.Unlock()
+
+		fmt.Fprintf(os.Stderr, "\r\n⏱  Session reached its %s limit, interrupting %s...\r\n", opts.MaxDuration, agent.DisplayName())
+		signalAgent(containerName, pidFile, "INT")
+
+		st.mu.Lock()
+		defer st.mu.Unlock()
+		st.timers = append(st.timers, time.AfterFunc(sessionGracePeriod, func() {
+			signalAgent(containerName, pidFile, "KILL")
+			if cmd.Process != nil {
+				cmd.Process.Kill()
+			}
+		}))
+	}))
+	return st
+}
+
+// Stop disarms the timer and reports whether the time limit was reached
+func (st *sessionTimer) Stop() bool {
+	st.mu.Lock()
+	defer st.mu.Unlock()
+	for _, t := range st.timers {
+		t.Stop()
+	}
+	return st.expired
+}
+
+// agentPIDPath is where a session's agent records its PID inside the container
+func agentPIDPath(sessionID string) string {
+	return fmt.Sprintf("/tmp/agentsandbox-agent-%s.pid", sessionID)
+}
+
+// recordAgentPID prefixes an agent command built by BuildAgentCommand so the shell that
+// runs it writes its PID to pidFile before it execs the agent, which keeps the PID
+func recordAgentPID(agentCmd, pidFile string) string {
+	return fmt.Sprintf("echo $$ > %s && %s", pidFile, agentCmd)
+}
+
+// signalAgent sends a signal to the agent whose PID is recorded in pidFile. Only that
+// process is signalled, not the other sessions' agents or anything else in the
+// container whose command line happens to match.
+func signalAgent(containerName, pidFile, signal string) {
+	script := fmt.Sprintf(`pid=$(cat %s 2>/dev/null) && [ -n "$pid" ] && kill -%s "$pid"`, pidFile, signal)
+	exec.Command("docker", "exec", containerName, "sh", "-c", script).Run()
+}
+
+// StopContainer stops a container, keeping it to be started again
+func StopContainer(containerName string) error {
+	fmt.Printf("Stopping container %s...\n", containerName)
+	if output, err := exec.Command("docker", "stop", containerName).CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to stop container: %w\nOutput: %s", err, string(output))
+	}
+	return nil
+}
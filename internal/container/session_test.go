@@ -0,0 +1,53 @@
+package container
+
+import (
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+func TestRecordAgentPIDRecordsTheAgent(t *testing.T) {
+	pidFile := filepath.Join(t.TempDir(), "agent.pid")
+	// A stand-in for BuildAgentCommand's output with a long-running agent
+	agentCmd := strings.Replace(BuildAgentCommand(t.TempDir(), config.AgentClaude, false, ""), config.AgentClaude.Command(), "sleep 30", 1)
+	cmd := exec.Command("sh", "-c", recordAgentPID(agentCmd, pidFile))
+	if err := cmd.Start(); err != nil {
+		t.Fatal(err)
+	}
+	defer func() {
+		cmd.Process.Kill()
+		cmd.Wait()
+	}()
+
+	var recorded string
+	for deadline := time.Now().Add(5 * time.Second); time.Now().Before(deadline); time.Sleep(10 * time.Millisecond) {
+		if data, _ := os.ReadFile(pidFile); strings.HasSuffix(string(data), "\n") {
+			recorded = strings.TrimSpace(string(data))
+			break
+		}
+	}
+	// The recorded PID is the shell's
+	if recorded != strconv.Itoa(cmd.Process.Pid) {
+		t.Errorf("recorded PID %q, want %d", recorded, cmd.Process.Pid)
+	}
+	// and, once the shell has execed it, the agent runs under that PID
+	var args string
+	for deadline := time.Now().Add(5 * time.Second); time.Now().Before(deadline); time.Sleep(10 * time.Millisecond) {
+		output, err := exec.Command("ps", "-o", "args=", "-p", recorded).Output()
+		if err != nil {
+			t.Skipf("ps is not available: %v", err)
+		}
+		if args = strings.TrimSpace(string(output)); strings.HasPrefix(args, "sleep") {
+			break
+		}
+	}
+	if !strings.HasPrefix(args, "sleep") {
+		t.Errorf("PID %s runs %q, want the agent", recorded, args)
+	}
+}
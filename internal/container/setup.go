@@ -0,0 +1,46 @@
+package container
+
+import (
+	"fmt"
+	"io"
+	"sync"
+)
+
+// setupStep is a piece of container setup that does not depend on the others. It
+// prints to out rather than stdout, since steps share the output. A failed step is
+// reported as a warning prefixed with warning, if set.
+type setupStep struct {
+	warning string
+	run     func(out io.Writer) error
+}
+
+// runSetupSteps runs independent setup steps at the same time and returns their
+// errors in step order once all of them are done, with nil for steps that succeeded
+func runSetupSteps(steps []setupStep, out io.Writer) []error {
+	errs := make([]error, len(steps))
+	var wg sync.WaitGroup
+	for i, step := range steps {
+		wg.Add(1)
+		go func(i int, step setupStep) {
+			defer wg.Done()
+			errs[i] = step.run(out)
+		}(i, step)
+	}
+	wg.Wait()
+	return errs
+}
+
+// warnSetupErrors prints a warning for each failed setup step. Warnings are printed
+// after every step has finished so they do not interleave with each other.
+func warnSetupErrors(steps []setupStep, errs []error) {
+	for i, err := range errs {
+		if err == nil {
+			continue
+		}
+		if steps[i].warning != "" {
+			fmt.Printf("Warning: %s: %v\n", steps[i].warning, err)
+		} else {
+			fmt.Printf("Warning: %v\n", err)
+		}
+	}
+}
@@ -0,0 +1,31 @@
+package container
+
+import (
+	"errors"
+	"io"
+	"testing"
+	"time"
+)
+
+func TestRunSetupSteps(t *testing.T) {
+	failed := errors.New("failed")
+	release := make(chan struct{})
+	steps := []setupStep{
+		// Blocks until the last step runs, which only works if steps run concurrently
+		{run: func(io.Writer) error { <-release; return nil }},
+		{run: func(io.Writer) error { return failed }},
+		{run: func(io.Writer) error { close(release); return nil }},
+	}
+
+	done := make(chan []error)
+	go func() { done <- runSetupSteps(steps, io.Discard) }()
+
+	select {
+	case errs := <-done:
+		if len(errs) != 3 || errs[0] != nil || !errors.Is(errs[1], failed) || errs[2] != nil {
+			t.Errorf("runSetupSteps() = %v, want [nil failed nil]", errs)
+		}
+	case <-time.After(5 * time.Second):
+		t.Fatal("runSetupSteps() did not run the steps concurrently")
+	}
+}
@@ -0,0 +1,35 @@
+package container
+
+import (
+	"fmt"
+	"os/exec"
+	"strings"
+
+	"github.com/thaodangspace/agentsandbox/internal/language"
+)
+
+// installToolShims lets the agent use toolchains the image was built without: the
+// first use of one of their commands installs the toolchain, so creating a container
+// never waits on languages the project may not need
+func installToolShims(containerName, username string, present []language.Language) error {
+	have := make(map[language.Language]bool)
+	for _, lang := range present {
+		have[lang] = true
+	}
+	var missing []language.Language
+	for _, lang := range language.All() {
+		if !have[lang] {
+			missing = append(missing, lang)
+		}
+	}
+	if len(missing) == 0 {
+		return nil
+	}
+
+	cmd := exec.Command("docker", "exec", "-i", "--user", "root", containerName, "sh", "-s", "--", username)
+	cmd.Stdin = strings.NewReader(language.ShimSetupScript(missing))
+	if output, err := cmd.CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to install toolchain shims: %w\nOutput: %s", err, string(output))
+	}
+	return nil
+}
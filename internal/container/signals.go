@@ -0,0 +1,100 @@
+package container
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"os/signal"
+	"sync"
+	"syscall"
+	"time"
+)
+
+// signalNames maps the signals that end a session (Ctrl+C, kill, terminal close) to the names kill takes
+var signalNames = map[os.Signal]string{
+	os.Interrupt:    "INT",
+	syscall.SIGTERM: "TERM",
+	syscall.SIGHUP:  "HUP",
+}
+
+// signalRelay catches termination signals while a session runs so agentsandbox is not
+// killed before it has collected the session's logs and recorded its state
+type signalRelay struct {
+	ch   chan os.Signal
+	done chan struct{}
+
+	mu       sync.Mutex
+	received os.Signal
+	kill     *time.Timer
+}
+
+// relaySignals forwards termination signals to the session. With an agent, whose PID is
+// recorded in pidFile, the signal is sent to the agent inside the container and the docker
+// exec client is killed if it has not exited after the grace period; without one (an
+// empty pidFile) it goes straight to the docker exec client.
+func relaySignals(containerName, pidFile string, cmd *exec.Cmd) *signalRelay {
+	r := &signalRelay{
+		ch:   make(chan os.Signal, 1),
+		done: make(chan struct{}),
+	}
+	for sig := range signalNames {
+		signal.Notify(r.ch, sig)
+	}
+
+	go func() {
+		for {
+			select {
+			case sig := <-r.ch:
+				r.forward(containerName, pidFile, cmd, sig)
+			case <-r.done:
+				return
+			}
+		}
+	}()
+	return r
+}
+
+func (r *signalRelay) forward(containerName, pidFile string, cmd *exec.Cmd, sig os.Signal) {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	if r.received != nil {
+		// A second signal means the user wants out now
+		if cmd.Process != nil {
+			cmd.Process.Kill()
+		}
+		return
+	}
+	r.received = sig
+
+	if pidFile == "" {
+		if cmd.Process != nil {
+			cmd.Process.Signal(sig)
+		}
+		return
+	}
+
+	signalAgent(containerName, pidFile, signalNames[sig])
+	r.kill = time.AfterFunc(sessionGracePeriod, func() {
+		if cmd.Process != nil {
+			cmd.Process.Kill()
+		}
+	})
+}
+
+// Stop restores default signal handling and returns the signal that ended the session, if any
+func (r *signalRelay) Stop() os.Signal {
+	signal.Stop(r.ch)
+	close(r.done)
+
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	if r.kill != nil {
+		r.kill.Stop()
+	}
+	return r.received
+}
+
+// reportSignalDetach tells the user the session ended because of a signal
+func reportSignalDetach(containerName string, sig os.Signal) {
+	fmt.Fprintf(os.Stderr, "\r\nSession ended by %s; saving logs. Container %s keeps running; resume with 'agentsandbox --continue'.\r\n", sig, containerName)
+}
@@ -0,0 +1,29 @@
+package container
+
+import (
+	"fmt"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/git"
+)
+
+// snapshotWorkspace records the project's working tree before a session when
+// workspace_snapshots is enabled, so `agentsandbox rollback` can restore it
+func snapshotWorkspace(containerName, currentDir string) {
+	settings, _ := config.LoadSettings()
+	if !settings.WorkspaceSnapshots {
+		return
+	}
+
+	root := git.RepoRoot(currentDir)
+	if root == "" {
+		fmt.Println("Warning: workspace snapshots need a git repository; continuing without one")
+		return
+	}
+	snapshot, err := git.CreateSnapshot(root, "before session in "+containerName)
+	if err != nil {
+		fmt.Printf("Warning: failed to snapshot the workspace: %v\n", err)
+		return
+	}
+	fmt.Printf("Workspace snapshot %s saved; run 'agentsandbox rollback' to restore it\n", snapshot.Name)
+}
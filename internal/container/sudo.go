@@ -0,0 +1,16 @@
+package container
+
+import (
+	"fmt"
+	"os/exec"
+)
+
+// revokeSudo removes whatever sudo rule the sudo setting gave the user.
+// Setup commands run as root through docker exec --user root, so they are unaffected.
+func revokeSudo(containerName, username string) error {
+	script := fmt.Sprintf(`sed -i '/^%s ALL=/d' /etc/sudoers`, username)
+	if output, err := exec.Command("docker", "exec", "--user", "root", containerName, "sh", "-c", script).CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to revoke sudo: %w\nOutput: %s", err, string(output))
+	}
+	return nil
+}
@@ -0,0 +1,78 @@
+package container
+
+import (
+	"bytes"
+	"errors"
+	"fmt"
+	"io"
+	"os/exec"
+	"path"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/state"
+	"github.com/thaodangspace/agentsandbox/internal/testrun"
+)
+
+// RunTests runs a test command in the sandbox's project directory, streaming its output
+// to out, and returns the structured result. A failing test command is not an error.
+func RunTests(containerName, projectDir string, test testrun.Command, out io.Writer) (*testrun.Result, error) {
+	script := fmt.Sprintf("cd %q && %s", projectDir, test.Run)
+	if test.Report != "" {
+		script = fmt.Sprintf("mkdir -p %q && rm -f %q && %s", path.Dir(test.Report), test.Report, script)
+	}
+
+	var output bytes.Buffer
+	cmd := containerShell(containerName, script)
+	cmd.Stdout = io.MultiWriter(out, &output)
+	cmd.Stderr = io.MultiWriter(out, &output)
+
+	result := &testrun.Result{
+		Container: containerName,
+		Command:   test.Run,
+		Format:    test.Format,
+		Started:   time.Now(),
+	}
+	err := cmd.Run()
+	result.DurationMS = time.Since(result.Started).Milliseconds()
+	var exitErr *exec.ExitError
+	if errors.As(err, &exitErr) {
+		result.ExitCode = exitErr.ExitCode()
+	} else if err != nil {
+		return nil, fmt.Errorf("failed to run tests: %w", err)
+	}
+
+	var report []byte
+	if test.Report != "" {
+		report, err = exec.Command("docker", "exec", containerName, "cat", test.Report).Output()
+		if err != nil {
+			fmt.Fprintf(out, "Warning: no test report at %s; only the exit code was recorded\n", test.Report)
+			return result, nil
+		}
+	}
+	cases, err := testrun.Parse(test.Format, output.String(), report)
+	if err != nil {
+		fmt.Fprintf(out, "Warning: %v\n", err)
+	}
+	result.SetCases(cases)
+	return result, nil
+}
+
+// SaveTestResult stores a result with the container's session logs, linked to its
+// latest session, and returns the file it was written to
+func SaveTestResult(projectDir string, result *testrun.Result) (string, error) {
+	if sessions, err := state.ListSessionLogs(result.Container, projectDir); err == nil && len(sessions) > 0 {
+		latest := filepath.Base(sessions[len(sessions)-1])
+		result.Session = strings.TrimSuffix(strings.TrimPrefix(latest, "session-"), ".jsonl")
+	}
+	logsDir, err := state.GetLogsDir(result.Container, projectDir)
+	if err != nil {
+		return "", err
+	}
+	file := filepath.Join(logsDir, fmt.Sprintf("tests-%s.json", result.Started.Format("20060102-150405")))
+	if err := testrun.Save(file, result); err != nil {
+		return "", fmt.Errorf("failed to save test result: %w", err)
+	}
+	return file, nil
+}
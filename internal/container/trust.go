@@ -0,0 +1,43 @@
+package container
+
+import (
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/git"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+// TrustRoot returns the directory trust is recorded for: the repository containing dir,
+// or dir itself outside a repository. Worktrees share the trust of the main checkout.
+func TrustRoot(dir string) string {
+	if root := git.MainRepoRoot(dir); root != "" {
+		return root
+	}
+	return dir
+}
+
+// RecordedTrust returns the trust level recorded for the project containing dir without
+// asking, treating unknown projects as trusted
+func RecordedTrust(dir string) string {
+	if dir == "" {
+		return state.TrustTrusted
+	}
+	level, err := state.LoadTrust(TrustRoot(dir))
+	if err != nil || level == "" {
+		return state.TrustTrusted
+	}
+	return level
+}
+
+// PermissionFlag returns the agent's skip-permission flag, or nothing for a project that
+// is not trusted
+func PermissionFlag(settings *config.Settings, agent config.Agent, level string) string {
+	if level != state.TrustTrusted {
+		return ""
+	}
+	return settings.SkipPermissionFlags[string(agent)]
+}
+
+// isRestricted reports whether level withholds secrets and shared access from a sandbox
+func isRestricted(level string) bool {
+	return level == state.TrustRestricted || level == state.TrustUntrusted
+}
@@ -0,0 +1,50 @@
+package container
+
+import (
+	"os/exec"
+	"path/filepath"
+	"testing"
+
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+func TestRecordedTrustCoversWorktrees(t *testing.T) {
+	if _, err := exec.LookPath("git"); err != nil {
+		t.Skip("git not installed")
+	}
+	t.Setenv("XDG_STATE_HOME", t.TempDir())
+	t.Setenv("GIT_AUTHOR_NAME", "test")
+	t.Setenv("GIT_AUTHOR_EMAIL", "test@example.com")
+	t.Setenv("GIT_COMMITTER_NAME", "test")
+	t.Setenv("GIT_COMMITTER_EMAIL", "test@example.com")
+
+	root, err := filepath.EvalSymlinks(t.TempDir())
+	if err != nil {
+		t.Fatal(err)
+	}
+	worktree := filepath.Join(root, ".agentsandbox-worktrees", "feature")
+	for _, args := range [][]string{
+		{"init", "-q"},
+		{"commit", "-q", "--allow-empty", "-m", "initial"},
+		{"worktree", "add", "-q", "-b", "feature", worktree},
+	} {
+		cmd := exec.Command("git", args...)
+		cmd.Dir = root
+		if output, err := cmd.CombinedOutput(); err != nil {
+			t.Fatalf("git %v: %v\n%s", args, err, output)
+		}
+	}
+	if err := state.SaveTrust(root, state.TrustRestricted); err != nil {
+		t.Fatal(err)
+	}
+
+	if got := TrustRoot(worktree); got != root {
+		t.Errorf("TrustRoot(worktree) = %q, want %q", got, root)
+	}
+	if got := RecordedTrust(worktree); got != state.TrustRestricted {
+		t.Errorf("RecordedTrust(worktree) = %q, want %q", got, state.TrustRestricted)
+	}
+	if got := RecordedTrust(root); got != state.TrustRestricted {
+		t.Errorf("RecordedTrust(root) = %q, want %q", got, state.TrustRestricted)
+	}
+}
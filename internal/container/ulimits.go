@@ -0,0 +1,58 @@
+package container
+
+import (
+	"fmt"
+	"regexp"
+	"sort"
+)
+
+// ulimitNames are the limits docker run --ulimit accepts
+var ulimitNames = map[string]bool{
+	"core": true, "cpu": true, "data": true, "fsize": true, "locks": true, "memlock": true,
+	"msgqueue": true, "nice": true, "nofile": true, "nproc": true, "rss": true, "rtprio": true,
+	"rttime": true, "sigpending": true, "stack": true,
+}
+
+var ulimitPattern = regexp.MustCompile(`^(-1|[0-9]+)(:(-1|[0-9]+))?$`)
+
+// hardenedUlimits apply to restricted and untrusted projects unless the ulimits setting
+// overrides them: enough descriptors and processes for builds and test runners, and no
+// single file over 4 GiB in the bind mount
+var hardenedUlimits = map[string]string{
+	"nofile": "8192:16384",
+	"nproc":  "4096",
+	"fsize":  "4294967296",
+}
+
+// ulimitArgs returns the docker run --ulimit arguments for the ulimits setting, on top of
+// the hardened defaults when hardened is set. Values are "soft" or "soft:hard"; fsize
+// is in bytes.
+func ulimitArgs(limits map[string]string, hardened bool) ([]string, error) {
+	merged := make(map[string]string)
+	if hardened {
+		for name, value := range hardenedUlimits {
+			merged[name] = value
+		}
+	}
+	for name, value := range limits {
+		if !ulimitNames[name] {
+			return nil, fmt.Errorf("unknown ulimit %q", name)
+		}
+		if !ulimitPattern.MatchString(value) {
+			return nil, fmt.Errorf("invalid ulimit %s=%q: use a number or soft:hard", name, value)
+		}
+		merged[name] = value
+	}
+
+	names := make([]string, 0, len(merged))
+	for name := range merged {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+
+	var args []string
+	for _, name := range names {
+		args = append(args, "--ulimit", name+"="+merged[name])
+	}
+	return args, nil
+}
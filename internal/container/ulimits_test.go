@@ -0,0 +1,33 @@
+package container
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestUlimitArgs(t *testing.T) {
+	args, err := ulimitArgs(nil, false)
+	if err != nil || len(args) != 0 {
+		t.Errorf("ulimitArgs(nil) = %v, %v; want none", args, err)
+	}
+
+	args, err = ulimitArgs(map[string]string{"nofile": "1024", "core": "0"}, true)
+	if err != nil {
+		t.Fatal(err)
+	}
+	want := []string{
+		"--ulimit", "core=0",
+		"--ulimit", "fsize=4294967296",
+		"--ulimit", "nofile=1024",
+		"--ulimit", "nproc=4096",
+	}
+	if !reflect.DeepEqual(args, want) {
+		t.Errorf("ulimitArgs() = %v, want %v", args, want)
+	}
+
+	for _, limits := range []map[string]string{{"files": "10"}, {"nofile": "lots"}, {"nproc": "10:"}} {
+		if _, err := ulimitArgs(limits, false); err == nil {
+			t.Errorf("expected %v to be rejected", limits)
+		}
+	}
+}
@@ -0,0 +1,151 @@
+package container
+
+import (
+	"compress/gzip"
+	"encoding/json"
+	"fmt"
+	"os"
+	"os/exec"
+	"path"
+	"path/filepath"
+	"strings"
+)
+
+// volumeManifest lists the archives written by BackupVolumes
+const volumeManifest = "volumes.json"
+
+// VolumeBackup is a docker volume mounted in a sandbox and the archive holding its contents
+type VolumeBackup struct {
+	Container   string `json:"container"`
+	Volume      string `json:"volume"`
+	Destination string `json:"destination"`
+	Archive     string `json:"archive"`
+}
+
+// SandboxVolumes returns the docker volumes mounted in the named containers, such as
+// the container-only node_modules of Node.js projects
+func SandboxVolumes(names []string) ([]VolumeBackup, error) {
+	if len(names) == 0 {
+		return nil, nil
+	}
+	args := append([]string{"inspect", "-f",
+		`{{.Name}}{{range .Mounts}}{{if eq .Type "volume"}}|{{.Name}}={{.Destination}}{{end}}{{end}}`}, names...)
+	output, err := exec.Command("docker", args...).Output()
+	if err != nil {
+		return nil, fmt.Errorf("failed to inspect volumes: %w", err)
+	}
+	return parseVolumeMounts(output), nil
+}
+
+// parseVolumeMounts parses name|volume=destination lines from docker inspect
+func parseVolumeMounts(output []byte) []VolumeBackup {
+	var volumes []VolumeBackup
+	for _, line := range strings.Split(string(output), "\n") {
+		fields := strings.Split(strings.TrimSpace(line), "|")
+		name := strings.TrimPrefix(fields[0], "/")
+		for _, field := range fields[1:] {
+			volume, destination, ok := strings.Cut(field, "=")
+			if !ok || volume == "" || destination == "" {
+				continue
+			}
+			volumes = append(volumes, VolumeBackup{
+				Container:   name,
+				Volume:      volume,
+				Destination: destination,
+				Archive:     volumeArchiveName(name, destination),
+			})
+		}
+	}
+	return volumes
+}
+
+// volumeArchiveName names the archive for a container's volume after its mount point
+func volumeArchiveName(containerName, destination string) string {
+	return fmt.Sprintf("%s.%s.tar.gz", containerName, strings.ReplaceAll(strings.Trim(destination, "/"), "/", "_"))
+}
+
+// BackupVolumes copies each volume to a gzip-compressed tar in dir and records them in
+// a manifest there. docker cp works on stopped containers, so nothing has to be running.
+func BackupVolumes(dir string, volumes []VolumeBackup) error {
+	if err := os.MkdirAll(dir, 0755); err != nil {
+		return err
+	}
+	for _, v := range volumes {
+		fmt.Printf("Backing up %s:%s\n", v.Container, v.Destination)
+		if err := backupVolume(filepath.Join(dir, v.Archive), v); err != nil {
+			return err
+		}
+	}
+
+	data, err := json.MarshalIndent(volumes, "", "  ")
+	if err != nil {
+		return err
+	}
+	return os.WriteFile(filepath.Join(dir, volumeManifest), data, 0644)
+}
+
+func backupVolume(archive string, v VolumeBackup) error {
+	out, err := os.Create(archive)
+	if err != nil {
+		return err
+	}
+	defer out.Close()
+	gz := gzip.NewWriter(out)
+
+	cmd := exec.Command("docker", "cp", v.Container+":"+v.Destination, "-")
+	cmd.Stdout = gz
+	if output, err := combinedStderr(cmd); err != nil {
+		return fmt.Errorf("failed to copy %s from %s: %w\nOutput: %s", v.Destination, v.Container, err, output)
+	}
+	if err := gz.Close(); err != nil {
+		return err
+	}
+	return out.Close()
+}
+
+// LoadVolumeBackups reads the manifest written by BackupVolumes
+func LoadVolumeBackups(dir string) ([]VolumeBackup, error) {
+	data, err := os.ReadFile(filepath.Join(dir, volumeManifest))
+	if err != nil {
+		if os.IsNotExist(err) {
+			return nil, fmt.Errorf("no volume backup found in %s", dir)
+		}
+		return nil, err
+	}
+	var volumes []VolumeBackup
+	if err := json.Unmarshal(data, &volumes); err != nil {
+		return nil, fmt.Errorf("failed to parse %s: %w", volumeManifest, err)
+	}
+	return volumes, nil
+}
+
+// RestoreVolume unpacks a backed-up volume into the same mount point of its container.
+// A container recreated since the backup gets its dependencies back in its new volume.
+func RestoreVolume(dir string, v VolumeBackup) error {
+	in, err := os.Open(filepath.Join(dir, filepath.Base(v.Archive)))
+	if err != nil {
+		return err
+	}
+	defer in.Close()
+	gz, err := gzip.NewReader(in)
+	if err != nil {
+		return fmt.Errorf("failed to read %s: %w", v.Archive, err)
+	}
+	defer gz.Close()
+
+	// The archive holds the mount point itself, so it is unpacked into its parent
+	cmd := exec.Command("docker", "cp", "-", v.Container+":"+path.Dir(v.Destination))
+	cmd.Stdin = gz
+	if output, err := combinedStderr(cmd); err != nil {
+		return fmt.Errorf("failed to restore %s in %s: %w\nOutput: %s", v.Destination, v.Container, err, output)
+	}
+	return nil
+}
+
+// combinedStderr runs cmd and returns what it wrote to stderr
+func combinedStderr(cmd *exec.Cmd) (string, error) {
+	var stderr strings.Builder
+	cmd.Stderr = &stderr
+	err := cmd.Run()
+	return stderr.String(), err
+}
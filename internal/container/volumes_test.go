@@ -0,0 +1,22 @@
+package container
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestParseVolumeMounts(t *testing.T) {
+	output := []byte(`/agentsandbox-web|4f2a=/home/me/web/node_modules|agentsandbox-cargo=/home/me/.cargo/registry
+/agentsandbox-api
+`)
+
+	want := []VolumeBackup{
+		{Container: "agentsandbox-web", Volume: "4f2a", Destination: "/home/me/web/node_modules",
+			Archive: "agentsandbox-web.home_me_web_node_modules.tar.gz"},
+		{Container: "agentsandbox-web", Volume: "agentsandbox-cargo", Destination: "/home/me/.cargo/registry",
+			Archive: "agentsandbox-web.home_me_.cargo_registry.tar.gz"},
+	}
+	if got := parseVolumeMounts(output); !reflect.DeepEqual(got, want) {
+		t.Errorf("parseVolumeMounts() = %+v, want %+v", got, want)
+	}
+}
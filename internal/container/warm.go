@@ -0,0 +1,158 @@
+package container
+
+import (
+	"crypto/sha256"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+// healthCacheTTL is how long a passing agent health check is trusted
+const healthCacheTTL = time.Hour
+
+// ContainerState is the subset of docker inspect needed on the attach path
+type ContainerState struct {
+	Exists  bool
+	Running bool
+	// Paused containers still count as running
+	Paused bool
+	ID     string
+	// StartedAt changes whenever the container is restarted
+	StartedAt string
+	// Project is the directory the container was created for, when labelled
+	Project string
+}
+
+// InspectContainer returns whether a container exists and is running with a single docker
+// call. A container docker does not know is reported as not existing; any other failure,
+// such as an unreachable daemon, is returned as an error.
+func InspectContainer(name string) (ContainerState, error) {
+	format := fmt.Sprintf("{{.Id}} {{.State.Running}} {{.State.Paused}} {{.State.StartedAt}} {{index .Config.Labels %q}}", projectLabel)
+	output, err := exec.Command("docker", "inspect", "-f", format, name).Output()
+	if err != nil {
+		var exitErr *exec.ExitError
+		if !errors.As(err, &exitErr) {
+			return ContainerState{}, fmt.Errorf("failed to inspect container %s: %w", name, err)
+		}
+		if isNoSuchObject(exitErr.Stderr) {
+			return ContainerState{}, nil
+		}
+		return ContainerState{}, fmt.Errorf("failed to inspect container %s: %w\nOutput: %s", name, err, strings.TrimSpace(string(exitErr.Stderr)))
+	}
+
+	// The project path comes last since it may contain spaces
+	fields := strings.SplitN(strings.TrimRight(string(output), "\n"), " ", 5)
+	if len(fields) != 5 {
+		return ContainerState{}, fmt.Errorf("unexpected docker inspect output %q", string(output))
+	}
+	return ContainerState{Exists: true, ID: fields[0], Running: fields[1] == "true", Paused: fields[2] == "true", StartedAt: fields[3], Project: fields[4]}, nil
+}
+
+// isNoSuchObject reports whether docker inspect failed because the object does not
+// exist, as opposed to docker or its daemon failing
+func isNoSuchObject(stderr []byte) bool {
+	message := strings.ToLower(string(stderr))
+	return strings.Contains(message, "no such object") || strings.Contains(message, "no such container")
+}
+
+// warmState remembers setup already done in a container so warm attaches can skip it.
+// It is tied to the container ID, so a recreated container with the same name starts fresh.
+type warmState struct {
+	ID string `json:"id"`
+	// Hooks maps an installed script to the hash of its content
+	Hooks map[string]string `json:"hooks"`
+	// Healthy maps an agent to the time its health check last passed
+	Healthy map[string]time.Time `json:"healthy"`
+	// LastUsed is when a session last attached to or detached from the container
+	LastUsed time.Time `json:"last_used,omitempty"`
+}
+
+func warmStateFile(containerName string) (string, error) {
+	stateDir, err := state.GetStateDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(stateDir, fmt.Sprintf("%s.warm.json", containerName)), nil
+}
+
+// loadWarmState returns the cached setup state for the container with the given ID
+func loadWarmState(containerName, id string) *warmState {
+	ws := &warmState{ID: id, Hooks: map[string]string{}, Healthy: map[string]time.Time{}}
+	if id == "" {
+		return ws
+	}
+
+	file, err := warmStateFile(containerName)
+	if err != nil {
+		return ws
+	}
+	data, err := os.ReadFile(file)
+	if err != nil {
+		return ws
+	}
+
+	var cached warmState
+	if json.Unmarshal(data, &cached) != nil || cached.ID != id {
+		return ws
+	}
+	if cached.Hooks != nil {
+		ws.Hooks = cached.Hooks
+	}
+	if cached.Healthy != nil {
+		ws.Healthy = cached.Healthy
+	}
+	ws.LastUsed = cached.LastUsed
+	return ws
+}
+
+// touch records that the container is in use and saves the state
+func (ws *warmState) touch(containerName string) {
+	ws.LastUsed = time.Now()
+	ws.save(containerName)
+}
+
+func (ws *warmState) save(containerName string) {
+	if ws.ID == "" {
+		return
+	}
+	file, err := warmStateFile(containerName)
+	if err != nil {
+		return
+	}
+	if data, err := json.Marshal(ws); err == nil {
+		os.WriteFile(file, data, 0644)
+	}
+}
+
+// ensureScript runs install unless content was already installed in this container
+func (ws *warmState) ensureScript(containerName, key, content string, install func(string) error) error {
+	sum := fmt.Sprintf("%x", sha256.Sum256([]byte(content)))
+	if ws.Hooks[key] == sum {
+		return nil
+	}
+	if err := install(containerName); err != nil {
+		return err
+	}
+	ws.Hooks[key] = sum
+	return nil
+}
+
+// recentlyHealthy reports whether the agent passed its health check within healthCacheTTL
+func (ws *warmState) recentlyHealthy(agent string) bool {
+	checked, ok := ws.Healthy[agent]
+	return ok && time.Since(checked) < healthCacheTTL
+}
+
+// clearWarmState forgets cached setup for a removed container
+func clearWarmState(containerName string) {
+	if file, err := warmStateFile(containerName); err == nil {
+		os.Remove(file)
+	}
+}
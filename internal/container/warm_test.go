@@ -0,0 +1,58 @@
+package container
+
+import (
+	"testing"
+	"time"
+)
+
+func TestWarmState(t *testing.T) {
+	t.Setenv("HOME", t.TempDir())
+	t.Setenv("XDG_STATE_HOME", "")
+
+	ws := loadWarmState("agentsandbox-app", "id-1")
+	installs := 0
+	install := func(string) error {
+		installs++
+		return nil
+	}
+
+	if err := ws.ensureScript("agentsandbox-app", "hook", "v1", install); err != nil {
+		t.Fatal(err)
+	}
+	ws.Healthy["claude"] = time.Now()
+	ws.save("agentsandbox-app")
+
+	ws = loadWarmState("agentsandbox-app", "id-1")
+	ws.ensureScript("agentsandbox-app", "hook", "v1", install)
+	if installs != 1 {
+		t.Errorf("unchanged script installed %d times, want 1", installs)
+	}
+	if !ws.recentlyHealthy("claude") || ws.recentlyHealthy("codex") {
+		t.Errorf("recentlyHealthy() = %v", ws.Healthy)
+	}
+
+	ws.ensureScript("agentsandbox-app", "hook", "v2", install)
+	if installs != 2 {
+		t.Errorf("changed script not reinstalled")
+	}
+
+	// A recreated container has a new ID and must be set up again
+	ws = loadWarmState("agentsandbox-app", "id-2")
+	if len(ws.Hooks) != 0 || ws.recentlyHealthy("claude") {
+		t.Errorf("state of a previous container was reused: %+v", ws)
+	}
+}
+
+func TestIsNoSuchObject(t *testing.T) {
+	for stderr, want := range map[string]bool{
+		"Error: No such object: agentsandbox-app\n":                                            true,
+		"Error response from daemon: No such container: agentsandbox-app\n":                    true,
+		"Cannot connect to the Docker daemon at unix:///var/run/docker.sock. Is it running?\n": false,
+		"permission denied while trying to connect to the Docker daemon socket\n":              false,
+		"": false,
+	} {
+		if got := isNoSuchObject([]byte(stderr)); got != want {
+			t.Errorf("isNoSuchObject(%q) = %v, want %v", stderr, got, want)
+		}
+	}
+}
@@ -0,0 +1,158 @@
+package container
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"runtime"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/ui"
+)
+
+// watchdogInterval is how often the watchdog checks the session for inactivity
+const watchdogInterval = 15 * time.Second
+
+// watchdog is an io.Writer on the session output that detects stuck agents:
+// no output for a long time, or the same line printed over and over
+type watchdog struct {
+	mu       sync.Mutex
+	settings config.WatchdogSettings
+	trigger  func(reason string)
+
+	partial     string
+	lastLine    string
+	repeats     int
+	repeatFired bool
+	lastOutput  time.Time
+	idleFired   bool
+}
+
+func newWatchdog(settings config.WatchdogSettings, trigger func(reason string)) *watchdog {
+	return &watchdog{settings: settings, trigger: trigger, lastOutput: time.Now()}
+}
+
+// enabled reports whether any detection is configured
+func (w *watchdog) enabled() bool {
+	return w.settings.IdleMinutes > 0 || w.settings.RepeatThreshold > 0
+}
+
+func (w *watchdog) Write(p []byte) (int, error) {
+	var reason string
+
+	w.mu.Lock()
+	w.lastOutput = time.Now()
+	w.idleFired = false
+
+	data := w.partial + string(p)
+	parts := strings.Split(data, "\n")
+	w.partial = parts[len(parts)-1]
+
+	for _, line := range parts[:len(parts)-1] {
+		line = strings.TrimSpace(stripANSI(line))
+		if line == "" {
+			continue
+		}
+		if line != w.lastLine {
+			w.lastLine = line
+			w.repeats = 1
+			w.repeatFired = false
+			continue
+		}
+		w.repeats++
+		if w.settings.RepeatThreshold > 0 && w.repeats >= w.settings.RepeatThreshold && !w.repeatFired {
+			w.repeatFired = true
+			reason = fmt.Sprintf("the same line was printed %d times: %q", w.repeats, line)
+		}
+	}
+	w.mu.Unlock()
+
+	if reason != "" {
+		w.trigger(reason)
+	}
+	return len(p), nil
+}
+
+// check fires the trigger when the session has been silent for longer than the idle limit
+func (w *watchdog) check(now time.Time) {
+	if w.settings.IdleMinutes <= 0 {
+		return
+	}
+
+	w.mu.Lock()
+	idle := now.Sub(w.lastOutput)
+	limit := time.Duration(w.settings.IdleMinutes) * time.Minute
+	fire := idle >= limit && !w.idleFired
+	if fire {
+		w.idleFired = true
+	}
+	w.mu.Unlock()
+
+	if fire {
+		w.trigger(fmt.Sprintf("no output for %s", idle.Round(time.Minute)))
+	}
+}
+
+// run checks for inactivity until stop is closed
+func (w *watchdog) run(stop <-chan struct{}) {
+	ticker := time.NewTicker(watchdogInterval)
+	defer ticker.Stop()
+
+	for {
+		select {
+		case <-stop:
+			return
+		case now := <-ticker.C:
+			w.check(now)
+		}
+	}
+}
+
+// follow feeds the watchdog the session transcript as it is written, until stop is closed
+func (w *watchdog) follow(containerName, transcript string, stop <-chan struct{}) {
+	cmd := exec.Command("docker", "exec", containerName, "tail", "-n", "0", "-F", transcript)
+	cmd.Stdout = w
+	if err := cmd.Start(); err != nil {
+		return
+	}
+	<-stop
+	cmd.Process.Kill()
+	cmd.Wait()
+}
+
+// watchdogTrigger returns the action taken when an agent, whose PID is recorded in
+// pidFile, looks stuck
+func watchdogTrigger(containerName string, agent config.Agent, pidFile string, settings config.WatchdogSettings) func(reason string) {
+	return func(reason string) {
+		message := fmt.Sprintf("%s in %s may be stuck: %s", agent.DisplayName(), containerName, reason)
+		fmt.Fprintf(os.Stderr, "\a\r\n%s%s\r\n", ui.Warning(), message)
+		notifyDesktop("Agent Sandbox", message)
+
+		if settings.Action == "interrupt" {
+			fmt.Fprintf(os.Stderr, "Interrupting %s...\r\n", agent.DisplayName())
+			signalAgent(containerName, pidFile, "INT")
+		}
+	}
+}
+
+// notifyDesktop shows a desktop notification when the host supports it
+func notifyDesktop(title, message string) {
+	var cmd *exec.Cmd
+
+	switch runtime.GOOS {
+	case "linux":
+		if _, err := exec.LookPath("notify-send"); err != nil {
+			return
+		}
+		cmd = exec.Command("notify-send", title, message)
+	case "darwin":
+		cmd = exec.Command("osascript", "-e", fmt.Sprintf("display notification %q with title %q", message, title))
+	default:
+		return
+	}
+
+	cmd.Start()
+}
@@ -0,0 +1,56 @@
+package container
+
+import (
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+func TestWatchdogRepeatedOutput(t *testing.T) {
+	var reasons []string
+	w := newWatchdog(config.WatchdogSettings{RepeatThreshold: 3}, func(reason string) {
+		reasons = append(reasons, reason)
+	})
+
+	w.Write([]byte("\x1b[32mRetrying request\x1b[0m\nRetrying request\n"))
+	if len(reasons) != 0 {
+		t.Fatalf("watchdog fired too early: %v", reasons)
+	}
+
+	w.Write([]byte("Retrying request\nRetrying request\n"))
+	if len(reasons) != 1 || !strings.Contains(reasons[0], "Retrying request") {
+		t.Fatalf("watchdog reasons = %v, want one repeat alert", reasons)
+	}
+
+	w.Write([]byte("Done\nRetrying request\nRetrying request\nRetrying request\n"))
+	if len(reasons) != 2 {
+		t.Errorf("watchdog did not re-arm after new output: %v", reasons)
+	}
+}
+
+func TestWatchdogIdle(t *testing.T) {
+	var reasons []string
+	w := newWatchdog(config.WatchdogSettings{IdleMinutes: 10}, func(reason string) {
+		reasons = append(reasons, reason)
+	})
+
+	start := w.lastOutput
+	w.check(start.Add(5 * time.Minute))
+	if len(reasons) != 0 {
+		t.Fatalf("watchdog fired before the idle limit: %v", reasons)
+	}
+
+	w.check(start.Add(11 * time.Minute))
+	w.check(start.Add(12 * time.Minute))
+	if len(reasons) != 1 {
+		t.Fatalf("watchdog reasons = %v, want exactly one idle alert", reasons)
+	}
+
+	w.Write([]byte("working\n"))
+	w.check(w.lastOutput.Add(11 * time.Minute))
+	if len(reasons) != 2 {
+		t.Errorf("watchdog did not re-arm after new output: %v", reasons)
+	}
+}
@@ -0,0 +1,268 @@
+package container
+
+import (
+	"context"
+	"crypto/rand"
+	"encoding/hex"
+	"errors"
+	"fmt"
+	"os"
+	"os/exec"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/failure"
+)
+
+// terminalStatePrefix starts the path of the file where a terminal's shell records its
+// PID and terminal device inside the container
+const terminalStatePrefix = "/tmp/agentsandbox-terminal-"
+
+// terminalCloseTimeout is how long a hung-up shell gets to exit before its docker exec
+// is killed
+const terminalCloseTimeout = 5 * time.Second
+
+// Terminal is a login shell in a container, on a pseudo-terminal that script(1) opens
+// inside the container, so it needs no terminal on the host. Read returns what the
+// terminal shows and Write types into it. Commands run in it are audited like those of
+// an attached session.
+type Terminal struct {
+	containerName string
+	stateFile     string
+	cmd           *exec.Cmd
+	input         *os.File
+	output        *os.File
+	// finish collects the audit log and marks the container used once the shell ends
+	finish func()
+
+	closeOnce sync.Once
+	exitCode  int
+}
+
+// StartTerminal starts a login shell in a running container on a terminal of rows by
+// cols, in the project directory. Cancelling ctx kills its docker exec; Close also ends
+// the shell.
+func StartTerminal(ctx context.Context, containerName string, rows, cols int) (*Terminal, error) {
+	if rows < 1 || cols < 1 || rows > 1000 || cols > 1000 {
+		return nil, failure.Errorf(failure.Usage, "invalid terminal size %dx%d", cols, rows)
+	}
+	info, err := inspectRunning(containerName)
+	if err != nil {
+		return nil, err
+	}
+
+	username := os.Getenv("USER")
+	if username == "" {
+		username = "ubuntu"
+	}
+	id := make([]byte, 8)
+	if _, err := rand.Read(id); err != nil {
+		return nil, err
+	}
+	t := &Terminal{containerName: containerName, stateFile: terminalStatePrefix + hex.EncodeToString(id)}
+
+	args := []string{"exec", "-i", "--user", username, "-e", fmt.Sprintf("HOME=/home/%s", username), "-e", "TERM=xterm-256color"}
+	args = append(args, localeEnvArgs()...)
+	if info.Project != "" {
+		args = append(args, "-w", info.Project)
+	}
+	// Keep the maintenance daemon from stopping the container under the shell
+	warm := loadWarmState(containerName, info.ID)
+	t.finish = func() { warm.touch(containerName) }
+	session := newAuditSession()
+	if err := warm.ensureScript(containerName, auditHookPath, auditHook, installAuditHook); err != nil {
+		fmt.Printf("Warning: failed to install audit hook: %v\n", err)
+	} else {
+		args = append(args, session.Env()...)
+		t.finish = func() {
+			warm.touch(containerName)
+			finishAuditSession(containerName, info.Project, session)
+		}
+	}
+	// The shell records its PID and terminal so Resize and Close can reach it
+	shell := fmt.Sprintf(`printf '%%s\n%%s\n' "$$" "$(tty)" > %s; stty rows %d cols %d; exec /bin/bash -l`, t.stateFile, rows, cols)
+	args = append(args, containerName, "script", "-qfec", shell, "/dev/null")
+
+	t.cmd = exec.CommandContext(ctx, "docker", args...)
+	if t.input, t.output, err = startWithPipes(t.cmd); err != nil {
+		return nil, failure.Errorf(failure.AgentLaunchFailed, "failed to start a shell in %s: %w", containerName, err)
+	}
+	warm.touch(containerName)
+	return t, nil
+}
+
+// startWithPipes starts cmd with its stdin and its merged stdout and stderr on pipes,
+// and returns their other ends
+func startWithPipes(cmd *exec.Cmd) (*os.File, *os.File, error) {
+	input, stdin, err := os.Pipe()
+	if err != nil {
+		return nil, nil, err
+	}
+	output, stdout, err := os.Pipe()
+	if err != nil {
+		input.Close()
+		stdin.Close()
+		return nil, nil, err
+	}
+	cmd.Stdin = input
+	cmd.Stdout = stdout
+	cmd.Stderr = stdout
+	err = cmd.Start()
+	input.Close()
+	stdout.Close()
+	if err != nil {
+		stdin.Close()
+		output.Close()
+		return nil, nil, err
+	}
+	return stdin, output, nil
+}
+
+// Read returns the next output of the terminal; it returns io.EOF once the shell has exited
+func (t *Terminal) Read(p []byte) (int, error) {
+	return t.output.Read(p)
+}
+
+// Write types p into the terminal
+func (t *Terminal) Write(p []byte) (int, error) {
+	return t.input.Write(p)
+}
+
+// Resize changes the size of the terminal, which signals the programs running in it
+func (t *Terminal) Resize(rows, cols int) error {
+	if rows < 1 || cols < 1 || rows > 1000 || cols > 1000 {
+		return failure.Errorf(failure.Usage, "invalid terminal size %dx%d", cols, rows)
+	}
+	script := fmt.Sprintf(`tty=$(sed -n 2p %s) && stty -F "$tty" rows %d cols %d`, t.stateFile, rows, cols)
+	if output, err := exec.Command("docker", "exec", t.containerName, "sh", "-c", script).CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to resize terminal: %w\nOutput: %s", err, string(output))
+	}
+	return nil
+}
+
+// Close hangs up the shell, which passes the hangup on to the jobs it started, as
+// closing a terminal window does, and returns the shell's exit code, or -1 when it had
+// to be killed. It can be called more than once.
+func (t *Terminal) Close() int {
+	t.closeOnce.Do(func() {
+		script := fmt.Sprintf(`pid=$(sed -n 1p %[1]s 2>/dev/null) && [ -n "$pid" ] && kill -HUP "$pid"; rm -f %[1]s`, t.stateFile)
+		exec.Command("docker", "exec", t.containerName, "sh", "-c", script).Run()
+		t.input.Close()
+
+		done := make(chan error, 1)
+		go func() { done <- t.cmd.Wait() }()
+		var err error
+		select {
+		case err = <-done:
+		case <-time.After(terminalCloseTimeout):
+			t.cmd.Process.Kill()
+			err = <-done
+		}
+		t.output.Close()
+
+		var exitErr *exec.ExitError
+		switch {
+		case err == nil:
+		case errors.As(err, &exitErr) && exitErr.ExitCode() >= 0:
+			t.exitCode = exitErr.ExitCode()
+		default:
+			t.exitCode = -1
+		}
+		t.finish()
+	})
+	return t.exitCode
+}
+
+// Observer follows what the terminal of the agent session running in a container
+// shows, from the session's transcript. It cannot type into the session: Write
+// discards its input and Resize does nothing.
+type Observer struct {
+	cmd    *exec.Cmd
+	input  *os.File
+	output *os.File
+
+	closeOnce sync.Once
+}
+
+// ObserveSession follows the most recent agent session running in a container, from
+// its start, until the session ends or Close is called. Sessions on images without
+// script(1) are not recorded and cannot be observed.
+func ObserveSession(ctx context.Context, containerName string) (*Observer, error) {
+	if _, err := inspectRunning(containerName); err != nil {
+		return nil, err
+	}
+	find := fmt.Sprintf("ls -t %s 2>/dev/null | head -n 1", sessionTranscriptPath("*"))
+	found, err := exec.Command("docker", "exec", containerName, "sh", "-c", find).Output()
+	if err != nil {
+		return nil, fmt.Errorf("failed to look for agent sessions in %s: %w", containerName, err)
+	}
+	transcript := strings.TrimSpace(string(found))
+	if transcript == "" {
+		return nil, failure.Errorf(failure.Usage, "no recorded agent session is running in '%s'", containerName)
+	}
+
+	// tail skips script's header line and stops when the transcript is removed at the
+	// end of the session or when the observer closes its input
+	follow := fmt.Sprintf(`exec 3<&0
+tail -n +2 -f %[1]s & t=$!
+{ cat <&3 >/dev/null; kill $t; } 2>/dev/null &
+while [ -e %[1]s ] && kill -0 $t 2>/dev/null; do sleep 1; done
+kill $t 2>/dev/null`, transcript)
+	o := &Observer{cmd: exec.CommandContext(ctx, "docker", "exec", "-i", containerName, "sh", "-c", follow)}
+	if o.input, o.output, err = startWithPipes(o.cmd); err != nil {
+		return nil, fmt.Errorf("failed to follow the session in %s: %w", containerName, err)
+	}
+	return o, nil
+}
+
+// Read returns the next output of the session; it returns io.EOF once the session has ended
+func (o *Observer) Read(p []byte) (int, error) {
+	return o.output.Read(p)
+}
+
+// Write discards p; observers cannot type into the session
+func (o *Observer) Write(p []byte) (int, error) {
+	return len(p), nil
+}
+
+// Resize does nothing; the session keeps the size of the terminal it runs on
+func (o *Observer) Resize(rows, cols int) error {
+	return nil
+}
+
+// Close stops following the session, which carries on, and returns 0. It can be called
+// more than once.
+func (o *Observer) Close() int {
+	o.closeOnce.Do(func() {
+		o.input.Close()
+		done := make(chan struct{})
+		go func() {
+			o.cmd.Wait()
+			close(done)
+		}()
+		select {
+		case <-done:
+		case <-time.After(terminalCloseTimeout):
+			o.cmd.Process.Kill()
+			<-done
+		}
+		o.output.Close()
+	})
+	return 0
+}
+
+// inspectRunning returns the state of a container that must be running
+func inspectRunning(containerName string) (ContainerState, error) {
+	info, err := InspectContainer(containerName)
+	if err != nil {
+		return info, err
+	}
+	if !info.Exists {
+		return info, failure.Errorf(failure.ContainerNotFound, "container '%s' does not exist", containerName)
+	}
+	if !info.Running || info.Paused {
+		return info, failure.Errorf(failure.Usage, "container '%s' is not running", containerName)
+	}
+	return info, nil
+}
@@ -0,0 +1,188 @@
+package crash
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"os"
+	"path/filepath"
+	"regexp"
+	"runtime"
+	"runtime/debug"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/paths"
+)
+
+// Report is the anonymized payload written for a crash or fatal error
+type Report struct {
+	Version       string    `json:"version"`
+	OS            string    `json:"os"`
+	Arch          string    `json:"arch"`
+	GoVersion     string    `json:"go_version"`
+	Timestamp     time.Time `json:"timestamp"`
+	Kind          string    `json:"kind"`
+	Error         string    `json:"error"`
+	Command       []string  `json:"command,omitempty"`
+	DockerCommand []string  `json:"docker_command,omitempty"`
+	Stack         string    `json:"stack,omitempty"`
+}
+
+var (
+	mu            sync.Mutex
+	lastDockerCmd []string
+)
+
+var secretPatterns = []*regexp.Regexp{
+	regexp.MustCompile(`(?i)((?:api[_-]?key|token|secret|password|passwd|auth)[A-Za-z0-9_]*\s*[=:]\s*)\S+`),
+	regexp.MustCompile(`sk-[A-Za-z0-9_\-]{10,}`),
+	regexp.MustCompile(`gh[pousr]_[A-Za-z0-9]{20,}`),
+	regexp.MustCompile(`AKIA[0-9A-Z]{16}`),
+	regexp.MustCompile(`eyJ[A-Za-z0-9_\-]+\.[A-Za-z0-9_\-]+\.[A-Za-z0-9_\-]+`),
+}
+
+// RecordDockerCommand remembers the most recent docker invocation for inclusion in crash reports
+func RecordDockerCommand(args []string) {
+	mu.Lock()
+	defer mu.Unlock()
+	lastDockerCmd = append([]string{"docker"}, args...)
+}
+
+// Redact removes secrets and host-identifying details from a string
+func Redact(s string) string {
+	for _, pattern := range secretPatterns {
+		s = pattern.ReplaceAllStringFunc(s, func(match string) string {
+			sub := pattern.FindStringSubmatch(match)
+			if len(sub) > 1 {
+				return sub[1] + "[REDACTED]"
+			}
+			return "[REDACTED]"
+		})
+	}
+
+	if homeDir, err := os.UserHomeDir(); err == nil && homeDir != "" && homeDir != "/" {
+		s = strings.ReplaceAll(s, homeDir, "~")
+	}
+	// Only whole words, so a short name does not mangle every word containing it
+	if username := os.Getenv("USER"); len(username) > 2 {
+		s = regexp.MustCompile(`\b`+regexp.QuoteMeta(username)+`\b`).ReplaceAllString(s, "<user>")
+	}
+
+	return s
+}
+
+func redactAll(values []string) []string {
+	if len(values) == 0 {
+		return nil
+	}
+	redacted := make([]string, len(values))
+	for i, v := range values {
+		redacted[i] = Redact(v)
+	}
+	return redacted
+}
+
+// HandlePanic writes a crash report for a recovered panic and re-panics.
+// It must be deferred directly from main.
+func HandlePanic(version string) {
+	r := recover()
+	if r == nil {
+		return
+	}
+
+	if path, err := write(version, "panic", fmt.Sprintf("%v", r), debug.Stack()); err == nil && path != "" {
+		fmt.Fprintf(os.Stderr, "Crash report written to %s\n", path)
+	}
+	panic(r)
+}
+
+// ReportError writes a crash report for an unexpected error returned by the CLI and
+// returns its path, or "" when none was written. The caller tells the user about it,
+// so the message can follow the CLI's error format.
+func ReportError(version string, err error) string {
+	path, werr := write(version, "error", err.Error(), nil)
+	if werr != nil {
+		return ""
+	}
+	return path
+}
+
+// write stores a report when crash reporting is enabled and returns its path
+func write(version, kind, message string, stack []byte) (string, error) {
+	settings, err := config.LoadSettings()
+	if err != nil || !settings.CrashReports.Enabled {
+		return "", nil
+	}
+
+	mu.Lock()
+	dockerCmd := append([]string{}, lastDockerCmd...)
+	mu.Unlock()
+
+	report := Report{
+		Version:       version,
+		OS:            runtime.GOOS,
+		Arch:          runtime.GOARCH,
+		GoVersion:     runtime.Version(),
+		Timestamp:     time.Now().UTC(),
+		Kind:          kind,
+		Error:         Redact(message),
+		Command:       redactAll(os.Args[1:]),
+		DockerCommand: redactAll(dockerCmd),
+		Stack:         Redact(string(stack)),
+	}
+
+	data, err := json.MarshalIndent(report, "", "  ")
+	if err != nil {
+		return "", err
+	}
+
+	stateDir, err := paths.StateDir()
+	if err != nil {
+		return "", err
+	}
+	reportsDir := filepath.Join(stateDir, "crash-reports")
+	if err := os.MkdirAll(reportsDir, 0755); err != nil {
+		return "", err
+	}
+
+	// Several reports can be written in the same second, e.g. by parallel runs
+	file, err := os.CreateTemp(reportsDir, fmt.Sprintf("crash-%s-*.json", report.Timestamp.Format("20060102-150405")))
+	if err != nil {
+		return "", err
+	}
+	path := file.Name()
+	if _, err := file.Write(data); err != nil {
+		file.Close()
+		return "", err
+	}
+	if err := file.Close(); err != nil {
+		return "", err
+	}
+
+	if endpoint := settings.CrashReports.Endpoint; endpoint != "" {
+		if err := send(endpoint, data); err != nil {
+			fmt.Fprintf(os.Stderr, "Warning: failed to send crash report: %v\n", err)
+		}
+	}
+
+	return path, nil
+}
+
+// send posts the report to the user-configured endpoint
+func send(endpoint string, data []byte) error {
+	client := &http.Client{Timeout: 5 * time.Second}
+	resp, err := client.Post(endpoint, "application/json", bytes.NewReader(data))
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode >= 300 {
+		return fmt.Errorf("endpoint returned %s", resp.Status)
+	}
+	return nil
+}
@@ -0,0 +1,200 @@
+package credproxy
+
+import (
+	"context"
+	"crypto/rand"
+	"crypto/subtle"
+	"encoding/hex"
+	"errors"
+	"fmt"
+	"net"
+	"net/http"
+	"net/http/httputil"
+	"net/url"
+	"os"
+	"strings"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+// ContainerHost is how a sandbox reaches the host; containers are created with a
+// host-gateway entry for it
+const ContainerHost = "host.docker.internal"
+
+// Provider is a model API the proxy can authenticate requests to
+type Provider struct {
+	Name     string
+	Upstream string
+	// KeyEnvs are host environment variables holding the API key, in order of preference
+	KeyEnvs []string
+	// BaseURLEnv points the agent at the proxy instead of Upstream
+	BaseURLEnv string
+	// Header carries the key; "Authorization" means a bearer token
+	Header string
+	// CredentialFiles are home-relative login files removed from sandboxes using the
+	// proxy, so only the session token is left inside
+	CredentialFiles []string
+}
+
+// providers maps each agent to the API it talks to. Qwen and Cursor have no
+// documented way to change their base URL.
+var providers = map[config.Agent]Provider{
+	config.AgentClaude: {
+		Name:            "Anthropic",
+		Upstream:        "https://api.anthropic.com",
+		KeyEnvs:         []string{"ANTHROPIC_API_KEY"},
+		BaseURLEnv:      "ANTHROPIC_BASE_URL",
+		Header:          "x-api-key",
+		CredentialFiles: []string{".claude/.credentials.json"},
+	},
+	config.AgentCodex: {
+		Name:            "OpenAI",
+		Upstream:        "https://api.openai.com/v1",
+		KeyEnvs:         []string{"OPENAI_API_KEY"},
+		BaseURLEnv:      "OPENAI_BASE_URL",
+		Header:          "Authorization",
+		CredentialFiles: []string{".codex/auth.json"},
+	},
+	config.AgentGemini: {
+		Name:            "Gemini",
+		Upstream:        "https://generativelanguage.googleapis.com",
+		KeyEnvs:         []string{"GEMINI_API_KEY", "GOOGLE_API_KEY"},
+		BaseURLEnv:      "GOOGLE_GEMINI_BASE_URL",
+		Header:          "x-goog-api-key",
+		CredentialFiles: []string{".gemini/oauth_creds.json", ".gemini/.env"},
+	},
+}
+
+// ProviderFor returns the API an agent uses, if the proxy supports it
+func ProviderFor(agent config.Agent) (Provider, bool) {
+	p, ok := providers[agent]
+	return p, ok
+}
+
+// HostKey returns the provider's API key from the host environment
+func (p Provider) HostKey() string {
+	for _, env := range p.KeyEnvs {
+		if key := os.Getenv(env); key != "" {
+			return key
+		}
+	}
+	return ""
+}
+
+// Proxy forwards requests from a sandbox to a provider, swapping the session token the
+// sandbox was given for the real API key. The key never enters the container, and the
+// token stops working when the proxy is closed at the end of the session.
+type Proxy struct {
+	provider Provider
+	key      string
+	token    string
+	upstream *url.URL
+	listener net.Listener
+	server   *http.Server
+}
+
+// Start listens on a random port on every interface, since containers on Linux reach
+// the host through the docker bridge rather than loopback. Requests without the
+// session token are rejected, so the port is of no use to anyone else.
+func Start(provider Provider, key string) (*Proxy, error) {
+	upstream, err := url.Parse(provider.Upstream)
+	if err != nil {
+		return nil, fmt.Errorf("invalid upstream %s: %w", provider.Upstream, err)
+	}
+	token, err := newToken()
+	if err != nil {
+		return nil, err
+	}
+	listener, err := net.Listen("tcp", ":0")
+	if err != nil {
+		return nil, fmt.Errorf("failed to start credential proxy: %w", err)
+	}
+
+	p := &Proxy{provider: provider, key: key, token: token, upstream: upstream, listener: listener}
+	p.server = &http.Server{Handler: p, ReadHeaderTimeout: 10 * time.Second}
+	go func() {
+		if err := p.server.Serve(listener); err != nil && !errors.Is(err, http.ErrServerClosed) {
+			fmt.Fprintf(os.Stderr, "Warning: credential proxy stopped: %v\n", err)
+		}
+	}()
+	return p, nil
+}
+
+// newToken returns a random token handed to the sandbox in place of the API key
+func newToken() (string, error) {
+	b := make([]byte, 24)
+	if _, err := rand.Read(b); err != nil {
+		return "", err
+	}
+	return "agentsandbox-" + hex.EncodeToString(b), nil
+}
+
+// Port is the port the proxy listens on
+func (p *Proxy) Port() int {
+	return p.listener.Addr().(*net.TCPAddr).Port
+}
+
+// EnvArgs returns docker exec arguments pointing the agent at the proxy
+func (p *Proxy) EnvArgs() []string {
+	base := fmt.Sprintf("http://%s:%d%s", ContainerHost, p.Port(), p.upstream.Path)
+	args := []string{"-e", fmt.Sprintf("%s=%s", p.provider.BaseURLEnv, base)}
+	for _, env := range p.provider.KeyEnvs {
+		args = append(args, "-e", fmt.Sprintf("%s=%s", env, p.token))
+	}
+	return args
+}
+
+// Close stops the proxy, which invalidates the session token
+func (p *Proxy) Close() error {
+	ctx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
+	defer cancel()
+	return p.server.Shutdown(ctx)
+}
+
+// ServeHTTP checks the session token and forwards the request with the real key
+func (p *Proxy) ServeHTTP(w http.ResponseWriter, r *http.Request) {
+	if subtle.ConstantTimeCompare([]byte(p.requestToken(r)), []byte(p.token)) != 1 {
+		http.Error(w, "invalid agentsandbox session token", http.StatusUnauthorized)
+		return
+	}
+
+	proxy := &httputil.ReverseProxy{
+		Rewrite: func(pr *httputil.ProxyRequest) {
+			pr.Out.URL.Scheme = p.upstream.Scheme
+			pr.Out.URL.Host = p.upstream.Host
+			pr.Out.Host = p.upstream.Host
+			// Agents already include the upstream's path prefix, from the base URL
+			query := pr.Out.URL.Query()
+			if query.Has("key") {
+				query.Del("key")
+				pr.Out.URL.RawQuery = query.Encode()
+			}
+			pr.Out.Header.Del("x-api-key")
+			pr.Out.Header.Del("x-goog-api-key")
+			pr.Out.Header.Del("Authorization")
+			if p.provider.Header == "Authorization" {
+				pr.Out.Header.Set("Authorization", "Bearer "+p.key)
+			} else {
+				pr.Out.Header.Set(p.provider.Header, p.key)
+			}
+		},
+		// Stream responses such as server-sent events as they arrive
+		FlushInterval: -1,
+	}
+	proxy.ServeHTTP(w, r)
+}
+
+// requestToken finds the token wherever the agent's SDK puts the API key
+func (p *Proxy) requestToken(r *http.Request) string {
+	if token := r.Header.Get("x-api-key"); token != "" {
+		return token
+	}
+	if token := r.Header.Get("x-goog-api-key"); token != "" {
+		return token
+	}
+	if token, ok := strings.CutPrefix(r.Header.Get("Authorization"), "Bearer "); ok {
+		return token
+	}
+	return r.URL.Query().Get("key")
+}
@@ -0,0 +1,57 @@
+package credproxy
+
+import (
+	"fmt"
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+)
+
+func TestProxySwapsToken(t *testing.T) {
+	var gotKey, gotPath, gotQuery string
+	upstream := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		gotKey = r.Header.Get("x-goog-api-key")
+		gotPath = r.URL.Path
+		gotQuery = r.URL.RawQuery
+		io.WriteString(w, "ok")
+	}))
+	defer upstream.Close()
+
+	provider, _ := ProviderFor("gemini")
+	provider.Upstream = upstream.URL
+	p, err := Start(provider, "real-key")
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer p.Close()
+
+	args := strings.Join(p.EnvArgs(), " ")
+	if strings.Contains(args, "real-key") || !strings.Contains(args, "GEMINI_API_KEY="+p.token) {
+		t.Fatalf("EnvArgs() = %s, want the session token and not the key", args)
+	}
+
+	url := func(query string) string {
+		return fmt.Sprintf("http://127.0.0.1:%d/v1beta/models?%s", p.Port(), query)
+	}
+
+	resp, err := http.Get(url("key=" + p.token + "&alt=sse"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	resp.Body.Close()
+	if resp.StatusCode != http.StatusOK || gotKey != "real-key" || gotPath != "/v1beta/models" || gotQuery != "alt=sse" {
+		t.Errorf("status %d, upstream saw key %q path %q query %q", resp.StatusCode, gotKey, gotPath, gotQuery)
+	}
+
+	gotKey = ""
+	resp, err = http.Get(url("key=guess"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	resp.Body.Close()
+	if resp.StatusCode != http.StatusUnauthorized || gotKey != "" {
+		t.Errorf("a wrong token got status %d and reached upstream with %q", resp.StatusCode, gotKey)
+	}
+}
@@ -0,0 +1,127 @@
+// Package failure classifies the errors agentsandbox exits with, so scripts and
+// editor plugins can tell them apart by exit code or JSON instead of by message.
+package failure
+
+import (
+	"errors"
+	"fmt"
+	"io/fs"
+	"os/exec"
+	"strings"
+)
+
+// Kind is a class of error with its own exit code
+type Kind int
+
+// Kinds of error. Their exit codes are part of the CLI's interface; add new kinds at
+// the end rather than renumbering.
+const (
+	// Unknown is any error not classified below
+	Unknown Kind = iota
+	// Usage is an unknown command or flag, or an invalid flag value
+	Usage
+	// DockerUnavailable means docker is not installed or its daemon is not answering
+	DockerUnavailable
+	// BuildFailed means the sandbox image could not be built
+	BuildFailed
+	// ContainerNotFound means the named or remembered container does not exist
+	ContainerNotFound
+	// AgentLaunchFailed means the agent could not be started or exited with an error
+	AgentLaunchFailed
+	// PermissionDenied means the user lacks permission for a file or the Docker socket
+	PermissionDenied
+)
+
+var kindNames = map[Kind]string{
+	Unknown:           "unknown",
+	Usage:             "usage",
+	DockerUnavailable: "docker_unavailable",
+	BuildFailed:       "build_failed",
+	ContainerNotFound: "container_not_found",
+	AgentLaunchFailed: "agent_launch_failed",
+	PermissionDenied:  "permission_denied",
+}
+
+// String returns the kind's name as used in JSON errors, e.g. "container_not_found"
+func (k Kind) String() string {
+	if name, ok := kindNames[k]; ok {
+		return name
+	}
+	return kindNames[Unknown]
+}
+
+// ExitCode returns the process exit code for the kind: 1 for unknown errors and 2 for
+// usage errors, as is conventional, and 3 onwards for the rest
+func (k Kind) ExitCode() int {
+	if _, ok := kindNames[k]; !ok || k == Unknown {
+		return 1
+	}
+	return int(k) + 1
+}
+
+// Error is an error tagged with its kind
+type Error struct {
+	Kind Kind
+	Err  error
+}
+
+func (e *Error) Error() string {
+	return e.Err.Error()
+}
+
+func (e *Error) Unwrap() error {
+	return e.Err
+}
+
+// Wrap tags err with kind, returning nil for a nil err
+func Wrap(kind Kind, err error) error {
+	if err == nil {
+		return nil
+	}
+	return &Error{Kind: kind, Err: err}
+}
+
+// Errorf formats an error like fmt.Errorf and tags it with kind
+func Errorf(kind Kind, format string, args ...interface{}) error {
+	return &Error{Kind: kind, Err: fmt.Errorf(format, args...)}
+}
+
+// KindOf returns the kind of err. Docker and permission problems are recognised from
+// the error itself even when a caller tagged it otherwise, since they are the cause
+// the user has to fix; anything else takes the outermost tag.
+func KindOf(err error) Kind {
+	if err == nil {
+		return Unknown
+	}
+	if errors.Is(err, fs.ErrPermission) {
+		return PermissionDenied
+	}
+
+	message := err.Error()
+	switch {
+	case strings.Contains(message, "permission denied while trying to connect to the Docker daemon"):
+		return PermissionDenied
+	case strings.Contains(message, "Cannot connect to the Docker daemon"),
+		strings.Contains(message, "error during connect"),
+		strings.Contains(message, "docker daemon is not running"),
+		isMissingDocker(err):
+		return DockerUnavailable
+	}
+
+	var tagged *Error
+	if errors.As(err, &tagged) {
+		return tagged.Kind
+	}
+	return Unknown
+}
+
+// ExitCode returns the process exit code for err
+func ExitCode(err error) int {
+	return KindOf(err).ExitCode()
+}
+
+// isMissingDocker reports whether err came from running docker when it is not on the PATH
+func isMissingDocker(err error) bool {
+	var execErr *exec.Error
+	return errors.As(err, &execErr) && execErr.Name == "docker"
+}
@@ -0,0 +1,49 @@
+package failure
+
+import (
+	"errors"
+	"fmt"
+	"io/fs"
+	"os/exec"
+	"testing"
+)
+
+func TestKindOf(t *testing.T) {
+	notFound := Errorf(ContainerNotFound, "container '%s' does not exist", "x")
+	tests := []struct {
+		name string
+		err  error
+		want Kind
+	}{
+		{"untagged", errors.New("boom"), Unknown},
+		{"tagged", notFound, ContainerNotFound},
+		{"wrapped tag", fmt.Errorf("attach: %w", notFound), ContainerNotFound},
+		{"outermost tag", Wrap(BuildFailed, Wrap(AgentLaunchFailed, errors.New("x"))), BuildFailed},
+		{"daemon down", Wrap(BuildFailed, errors.New("Cannot connect to the Docker daemon at unix:///var/run/docker.sock")), DockerUnavailable},
+		{"no docker", &exec.Error{Name: "docker", Err: exec.ErrNotFound}, DockerUnavailable},
+		{"socket permission", errors.New("permission denied while trying to connect to the Docker daemon socket"), PermissionDenied},
+		{"file permission", fmt.Errorf("save: %w", fs.ErrPermission), PermissionDenied},
+	}
+	for _, tt := range tests {
+		if got := KindOf(tt.err); got != tt.want {
+			t.Errorf("%s: KindOf() = %s, want %s", tt.name, got, tt.want)
+		}
+	}
+}
+
+func TestExitCode(t *testing.T) {
+	if ExitCode(errors.New("boom")) != 1 || Usage.ExitCode() != 2 {
+		t.Errorf("unknown errors should exit 1 and usage errors 2")
+	}
+	seen := map[int]Kind{}
+	for kind := range kindNames {
+		code := kind.ExitCode()
+		if other, ok := seen[code]; ok {
+			t.Errorf("%s and %s share exit code %d", kind, other, code)
+		}
+		seen[code] = kind
+	}
+	if Kind(99).ExitCode() != 1 || Kind(99).String() != "unknown" {
+		t.Errorf("undefined kinds should behave like Unknown")
+	}
+}
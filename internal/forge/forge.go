@@ -0,0 +1,153 @@
+package forge
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"net/url"
+	"os"
+	"strings"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/git"
+)
+
+// PullRequest is a request to merge Head into Base
+type PullRequest struct {
+	Title string
+	Body  string
+	Head  string
+	Base  string
+	Draft bool
+}
+
+// Open creates a pull request (GitHub) or merge request (GitLab) and returns its URL.
+// Hosts containing "gitlab" use the GitLab API with GITLAB_TOKEN; all others use the
+// GitHub API with GITHUB_TOKEN or GH_TOKEN.
+func Open(remote git.Remote, pr PullRequest) (string, error) {
+	if IsGitLab(remote) {
+		return openGitLab(remote, pr)
+	}
+	return openGitHub(remote, pr)
+}
+
+// IsGitLab reports whether the remote is hosted on GitLab
+func IsGitLab(remote git.Remote) bool {
+	return strings.Contains(remote.Host, "gitlab")
+}
+
+func openGitHub(remote git.Remote, pr PullRequest) (string, error) {
+	token := githubToken()
+	if token == "" {
+		return "", fmt.Errorf("set GITHUB_TOKEN or GH_TOKEN to open pull requests on %s", remote.Host)
+	}
+
+	payload := map[string]interface{}{
+		"title": pr.Title,
+		"body":  pr.Body,
+		"head":  pr.Head,
+		"base":  pr.Base,
+		"draft": pr.Draft,
+	}
+	var result struct {
+		HTMLURL string `json:"html_url"`
+	}
+	if err := post(githubAPI(remote, "pulls"), githubHeaders(token), payload, &result); err != nil {
+		return "", fmt.Errorf("failed to create pull request: %w", err)
+	}
+	return result.HTMLURL, nil
+}
+
+// githubToken returns the GitHub API token from the environment
+func githubToken() string {
+	if token := os.Getenv("GITHUB_TOKEN"); token != "" {
+		return token
+	}
+	return os.Getenv("GH_TOKEN")
+}
+
+// githubAPI returns the URL of a repository API resource, handling GitHub Enterprise hosts
+func githubAPI(remote git.Remote, resource string) string {
+	return fmt.Sprintf("%s/repos/%s/%s", githubAPIBase(remote), remote.Path, resource)
+}
+
+// githubHeaders returns the request headers for the GitHub API; token may be empty
+func githubHeaders(token string) map[string]string {
+	headers := map[string]string{"Accept": "application/vnd.github+json"}
+	if token != "" {
+		headers["Authorization"] = "Bearer " + token
+	}
+	return headers
+}
+
+func openGitLab(remote git.Remote, pr PullRequest) (string, error) {
+	token := os.Getenv("GITLAB_TOKEN")
+	if token == "" {
+		return "", fmt.Errorf("set GITLAB_TOKEN to open merge requests on %s", remote.Host)
+	}
+
+	title := pr.Title
+	if pr.Draft {
+		title = "Draft: " + title
+	}
+	payload := map[string]interface{}{
+		"title":         title,
+		"description":   pr.Body,
+		"source_branch": pr.Head,
+		"target_branch": pr.Base,
+	}
+	headers := map[string]string{"PRIVATE-TOKEN": token}
+
+	var result struct {
+		WebURL string `json:"web_url"`
+	}
+	endpoint := fmt.Sprintf("https://%s/api/v4/projects/%s/merge_requests", remote.Host, url.PathEscape(remote.Path))
+	if err := post(endpoint, headers, payload, &result); err != nil {
+		return "", fmt.Errorf("failed to create merge request: %w", err)
+	}
+	return result.WebURL, nil
+}
+
+// post sends a JSON request and decodes the JSON response into result
+func post(endpoint string, headers map[string]string, payload, result interface{}) error {
+	data, err := json.Marshal(payload)
+	if err != nil {
+		return err
+	}
+	return send(http.MethodPost, endpoint, headers, bytes.NewReader(data), result)
+}
+
+// send performs an API request and decodes the JSON response into result, if any
+func send(method, endpoint string, headers map[string]string, body io.Reader, result interface{}) error {
+	req, err := http.NewRequest(method, endpoint, body)
+	if err != nil {
+		return err
+	}
+	if body != nil {
+		req.Header.Set("Content-Type", "application/json")
+	}
+	for key, value := range headers {
+		req.Header.Set(key, value)
+	}
+
+	client := &http.Client{Timeout: 30 * time.Second}
+	resp, err := client.Do(req)
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+
+	data, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return err
+	}
+	if resp.StatusCode >= 300 {
+		return fmt.Errorf("%s returned %s: %s", endpoint, resp.Status, strings.TrimSpace(string(data)))
+	}
+	if result == nil {
+		return nil
+	}
+	return json.Unmarshal(data, result)
+}
@@ -0,0 +1,74 @@
+package forge
+
+import (
+	"fmt"
+	"net/http"
+	"strconv"
+	"strings"
+
+	"github.com/thaodangspace/agentsandbox/internal/git"
+)
+
+// Issue is a GitHub issue used to seed an agent session
+type Issue struct {
+	Number int    `json:"number"`
+	Title  string `json:"title"`
+	Body   string `json:"body"`
+	URL    string `json:"html_url"`
+}
+
+// ParseIssueRef parses an issue URL such as https://github.com/acme/api/issues/42.
+// It returns a zero Remote when ref is a plain issue number.
+func ParseIssueRef(ref string) (git.Remote, int, error) {
+	ref = strings.TrimPrefix(strings.TrimSpace(ref), "#")
+	if n, err := strconv.Atoi(ref); err == nil && n > 0 {
+		return git.Remote{}, n, nil
+	}
+
+	idx := strings.Index(ref, "/issues/")
+	if idx < 0 {
+		return git.Remote{}, 0, fmt.Errorf("%q is not an issue number or GitHub issue URL", ref)
+	}
+	remote, err := git.ParseRemoteURL(ref[:idx])
+	if err != nil {
+		return git.Remote{}, 0, err
+	}
+	rest := ref[idx+len("/issues/"):]
+	if end := strings.IndexAny(rest, "/#?"); end >= 0 {
+		rest = rest[:end]
+	}
+	number, err := strconv.Atoi(rest)
+	if err != nil || number <= 0 {
+		return git.Remote{}, 0, fmt.Errorf("%q is not a GitHub issue URL", ref)
+	}
+	return remote, number, nil
+}
+
+// FetchIssue fetches an issue through the GitHub API; GITHUB_TOKEN or GH_TOKEN is
+// only required for private repositories
+func FetchIssue(remote git.Remote, number int) (*Issue, error) {
+	var issue Issue
+	endpoint := githubAPI(remote, fmt.Sprintf("issues/%d", number))
+	if err := send(http.MethodGet, endpoint, githubHeaders(githubToken()), nil, &issue); err != nil {
+		return nil, fmt.Errorf("failed to fetch issue #%d: %w", number, err)
+	}
+	return &issue, nil
+}
+
+// Prompt returns the initial agent prompt for the issue
+func (i *Issue) Prompt() string {
+	return fmt.Sprintf("Resolve GitHub issue #%d: %s\n\n%s\n\nIssue: %s", i.Number, i.Title, strings.TrimSpace(i.Body), i.URL)
+}
+
+// Comment posts a comment on an issue or pull request; GITHUB_TOKEN or GH_TOKEN is required
+func Comment(remote git.Remote, number int, body string) error {
+	token := githubToken()
+	if token == "" {
+		return fmt.Errorf("set GITHUB_TOKEN or GH_TOKEN to comment on %s", remote.Host)
+	}
+	endpoint := githubAPI(remote, fmt.Sprintf("issues/%d/comments", number))
+	if err := post(endpoint, githubHeaders(token), map[string]string{"body": body}, nil); err != nil {
+		return fmt.Errorf("failed to comment on #%d: %w", number, err)
+	}
+	return nil
+}
@@ -0,0 +1,37 @@
+package forge
+
+import "testing"
+
+func TestParseIssueRef(t *testing.T) {
+	tests := []struct {
+		ref      string
+		wantPath string
+		wantNum  int
+		wantErr  bool
+	}{
+		{ref: "42", wantNum: 42},
+		{ref: "#7", wantNum: 7},
+		{ref: "https://github.com/acme/api/issues/42", wantPath: "acme/api", wantNum: 42},
+		{ref: "https://github.com/acme/api/issues/42#issuecomment-1", wantPath: "acme/api", wantNum: 42},
+		{ref: "https://github.com/acme/api/pull/42", wantErr: true},
+		{ref: "0", wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.ref, func(t *testing.T) {
+			remote, number, err := ParseIssueRef(tt.ref)
+			if tt.wantErr {
+				if err == nil {
+					t.Errorf("ParseIssueRef() succeeded, want error")
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("ParseIssueRef() error = %v", err)
+			}
+			if remote.Path != tt.wantPath || number != tt.wantNum {
+				t.Errorf("ParseIssueRef() = %q #%d, want %q #%d", remote.Path, number, tt.wantPath, tt.wantNum)
+			}
+		})
+	}
+}
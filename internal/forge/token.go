@@ -0,0 +1,131 @@
+package forge
+
+import (
+	"crypto"
+	"crypto/rand"
+	"crypto/rsa"
+	"crypto/sha256"
+	"crypto/x509"
+	"encoding/base64"
+	"encoding/json"
+	"encoding/pem"
+	"fmt"
+	"net/http"
+	"os"
+	"path"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/git"
+)
+
+// RepoToken is a GitHub App installation token limited to one repository
+type RepoToken struct {
+	Token     string    `json:"token"`
+	ExpiresAt time.Time `json:"expires_at"`
+}
+
+// repoTokenPermissions are what an agent needs to push branches and open pull requests
+var repoTokenPermissions = map[string]string{
+	"contents":      "write",
+	"pull_requests": "write",
+	"issues":        "read",
+}
+
+// MintRepoToken creates an installation token of the GitHub App appID that only grants
+// access to the remote's repository and expires after an hour. Personal access tokens
+// cannot be created through the API, so a GitHub App installed on the repository is
+// needed; keyFile is its PEM private key.
+func MintRepoToken(remote git.Remote, appID int64, keyFile string) (*RepoToken, error) {
+	jwt, err := appJWT(appID, keyFile, time.Now())
+	if err != nil {
+		return nil, err
+	}
+	headers := githubHeaders(jwt)
+
+	var installation struct {
+		ID int64 `json:"id"`
+	}
+	if err := send(http.MethodGet, githubAPI(remote, "installation"), headers, nil, &installation); err != nil {
+		return nil, fmt.Errorf("the GitHub App is not installed on %s: %w", remote.Path, err)
+	}
+
+	payload := map[string]interface{}{
+		"repositories": []string{path.Base(remote.Path)},
+		"permissions":  repoTokenPermissions,
+	}
+	var token RepoToken
+	endpoint := fmt.Sprintf("%s/app/installations/%d/access_tokens", githubAPIBase(remote), installation.ID)
+	if err := post(endpoint, headers, payload, &token); err != nil {
+		return nil, fmt.Errorf("failed to create a token for %s: %w", remote.Path, err)
+	}
+	return &token, nil
+}
+
+// RevokeRepoToken invalidates a token created by MintRepoToken before it expires
+func RevokeRepoToken(remote git.Remote, token string) error {
+	endpoint := githubAPIBase(remote) + "/installation/token"
+	if err := send(http.MethodDelete, endpoint, githubHeaders(token), nil, nil); err != nil {
+		return fmt.Errorf("failed to revoke GitHub token: %w", err)
+	}
+	return nil
+}
+
+// appJWT returns the short-lived JSON Web Token that authenticates as the GitHub App
+func appJWT(appID int64, keyFile string, now time.Time) (string, error) {
+	data, err := os.ReadFile(keyFile)
+	if err != nil {
+		return "", fmt.Errorf("failed to read GitHub App private key: %w", err)
+	}
+	key, err := parseRSAKey(data)
+	if err != nil {
+		return "", err
+	}
+
+	header := base64.RawURLEncoding.EncodeToString([]byte(`{"alg":"RS256","typ":"JWT"}`))
+	// Backdated to allow for clock drift; GitHub accepts at most ten minutes
+	claims, err := json.Marshal(map[string]interface{}{
+		"iat": now.Add(-time.Minute).Unix(),
+		"exp": now.Add(9 * time.Minute).Unix(),
+		"iss": fmt.Sprint(appID),
+	})
+	if err != nil {
+		return "", err
+	}
+	signed := header + "." + base64.RawURLEncoding.EncodeToString(claims)
+
+	digest := sha256.Sum256([]byte(signed))
+	signature, err := rsa.SignPKCS1v15(rand.Reader, key, crypto.SHA256, digest[:])
+	if err != nil {
+		return "", err
+	}
+	return signed + "." + base64.RawURLEncoding.EncodeToString(signature), nil
+}
+
+// parseRSAKey parses the PKCS#1 key GitHub issues, or a PKCS#8 conversion of it
+func parseRSAKey(data []byte) (*rsa.PrivateKey, error) {
+	block, _ := pem.Decode(data)
+	if block == nil {
+		return nil, fmt.Errorf("GitHub App private key is not PEM encoded")
+	}
+	if key, err := x509.ParsePKCS1PrivateKey(block.Bytes); err == nil {
+		return key, nil
+	}
+	parsed, err := x509.ParsePKCS8PrivateKey(block.Bytes)
+	if err != nil {
+		return nil, fmt.Errorf("failed to parse GitHub App private key: %w", err)
+	}
+	key, ok := parsed.(*rsa.PrivateKey)
+	if !ok {
+		return nil, fmt.Errorf("GitHub App private key is not an RSA key")
+	}
+	return key, nil
+}
+
+// githubAPIBase returns the API root, handling GitHub Enterprise hosts
+func githubAPIBase(remote git.Remote) string {
+	if remote.Host != "github.com" {
+		return fmt.Sprintf("https://%s/api/v3", remote.Host)
+	}
+	return "https://api.github.com"
+}
+
@@ -0,0 +1,83 @@
+package git
+
+import (
+	"fmt"
+	"path"
+	"path/filepath"
+	"strings"
+)
+
+// CurrentBranch returns the branch checked out in dir, or "" for a detached HEAD
+// or a directory that is not a git repository
+func CurrentBranch(dir string) string {
+	cmd := hostGit(dir, "symbolic-ref", "--quiet", "--short", "HEAD")
+	output, err := cmd.Output()
+	if err != nil {
+		return ""
+	}
+	return strings.TrimSpace(string(output))
+}
+
+// RepoRoot returns the top-level directory of the repository or worktree containing dir,
+// or "" when dir is not inside a git repository
+func RepoRoot(dir string) string {
+	cmd := hostGit(dir, "rev-parse", "--show-toplevel")
+	output, err := cmd.Output()
+	if err != nil {
+		return ""
+	}
+	return strings.TrimSpace(string(output))
+}
+
+// MainRepoRoot returns the top-level directory of the main checkout of the repository
+// containing dir, which for a linked worktree is the checkout it was added from, or ""
+// when dir is not inside a git repository
+func MainRepoRoot(dir string) string {
+	cmd := hostGit(dir, "rev-parse", "--path-format=absolute", "--git-common-dir")
+	output, err := cmd.Output()
+	if err != nil {
+		return ""
+	}
+	// A repository whose git directory lives elsewhere has no main checkout to find
+	commonDir := strings.TrimSpace(string(output))
+	if filepath.Base(commonDir) != ".git" {
+		return RepoRoot(dir)
+	}
+	return filepath.Dir(commonDir)
+}
+
+// MatchBranch returns the first pattern matching branch; patterns use path.Match
+// syntax, so "release/*" matches "release/1.2" but not "release/1.2/hotfix"
+func MatchBranch(branch string, patterns []string) (string, bool) {
+	for _, pattern := range patterns {
+		if ok, _ := path.Match(pattern, branch); ok {
+			return pattern, true
+		}
+	}
+	return "", false
+}
+
+// HasUncommittedChanges reports whether dir has staged, unstaged or untracked changes
+func HasUncommittedChanges(dir string) (bool, error) {
+	cmd := hostGit(dir, "status", "--porcelain")
+	output, err := cmd.Output()
+	if err != nil {
+		return false, fmt.Errorf("git status failed: %w", err)
+	}
+	return strings.TrimSpace(string(output)) != "", nil
+}
+
+// Checkout switches dir to branch, creating the branch from HEAD if it does not exist
+func Checkout(dir, branch string) error {
+	args := []string{"checkout", branch}
+	verifyCmd := hostGit(dir, "rev-parse", "--verify", "--quiet", branch)
+	if verifyCmd.Run() != nil {
+		args = []string{"checkout", "-b", branch}
+	}
+
+	cmd := hostGit(dir, args...)
+	if output, err := cmd.CombinedOutput(); err != nil {
+		return fmt.Errorf("git checkout failed: %w\nOutput: %s", err, strings.TrimSpace(string(output)))
+	}
+	return nil
+}
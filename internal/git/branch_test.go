@@ -0,0 +1,27 @@
+package git
+
+import "testing"
+
+func TestMatchBranch(t *testing.T) {
+	patterns := []string{"main", "master", "release/*"}
+
+	tests := []struct {
+		branch string
+		want   bool
+	}{
+		{"main", true},
+		{"master", true},
+		{"release/1.2", true},
+		{"release/1.2/hotfix", false},
+		{"feature/main", false},
+		{"mainline", false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.branch, func(t *testing.T) {
+			if _, got := MatchBranch(tt.branch, patterns); got != tt.want {
+				t.Errorf("MatchBranch(%q) = %v, want %v", tt.branch, got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,163 @@
+package git
+
+import (
+	"bytes"
+	"errors"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strconv"
+	"strings"
+)
+
+// HeadCommit returns the commit currently checked out in dir
+func HeadCommit(dir string) (string, error) {
+	cmd := hostGit(dir, "rev-parse", "HEAD")
+	output, err := cmd.Output()
+	if err != nil {
+		return "", fmt.Errorf("not a git repository: %w", err)
+	}
+	return strings.TrimSpace(string(output)), nil
+}
+
+// WorkingTreeDiff returns a binary-safe patch of all uncommitted changes in dir,
+// including untracked files, without touching the index
+func WorkingTreeDiff(dir string) (string, error) {
+	cmd := hostGit(dir, "diff", "--binary", "--no-ext-diff", "--no-textconv", "HEAD")
+	tracked, err := cmd.Output()
+	if err != nil {
+		return "", fmt.Errorf("git diff failed: %w", err)
+	}
+
+	var b strings.Builder
+	b.Write(tracked)
+
+	cmd = hostGit(dir, "ls-files", "--others", "--exclude-standard", "-z")
+	output, err := cmd.Output()
+	if err != nil {
+		return "", fmt.Errorf("git ls-files failed: %w", err)
+	}
+
+	for _, file := range strings.Split(string(output), "\x00") {
+		if file == "" {
+			continue
+		}
+		cmd := hostGit(dir, "diff", "--binary", "--no-ext-diff", "--no-textconv", "--no-index", "--", "/dev/null", file)
+		patch, err := cmd.Output()
+		// --no-index exits with status 1 when the files differ
+		var exitErr *exec.ExitError
+		if err != nil && !(errors.As(err, &exitErr) && exitErr.ExitCode() == 1) {
+			return "", fmt.Errorf("git diff failed for %s: %w", file, err)
+		}
+		b.Write(patch)
+	}
+
+	return b.String(), nil
+}
+
+// ApplyPatch applies a patch produced by WorkingTreeDiff to the working tree in dir
+func ApplyPatch(dir, patchFile string) error {
+	cmd := hostGit(dir, "apply", "--binary", "--whitespace=nowarn", patchFile)
+	if output, err := cmd.CombinedOutput(); err != nil {
+		return fmt.Errorf("git apply failed: %w\nOutput: %s", err, string(output))
+	}
+	return nil
+}
+
+// CommitsWithTrailer returns the hashes of commits on any ref whose message contains trailer
+func CommitsWithTrailer(dir, trailer string) ([]string, error) {
+	cmd := hostGit(dir, "log", "--all", "--format=%H", "--fixed-strings", "--grep="+trailer)
+	output, err := cmd.Output()
+	if err != nil {
+		return nil, fmt.Errorf("git log failed: %w", err)
+	}
+	return strings.Fields(string(output)), nil
+}
+
+// ChangedFiles returns the tracked files that differ from HEAD followed by untracked files
+func ChangedFiles(dir string) ([]string, error) {
+	return ChangedFilesSince(dir, "HEAD")
+}
+
+// ChangedFilesSince returns the tracked files that differ from commit, whether committed
+// since or not, followed by untracked files
+func ChangedFilesSince(dir, commit string) ([]string, error) {
+	var files []string
+	for _, args := range [][]string{
+		{"diff", "--name-only", commit},
+		{"ls-files", "--others", "--exclude-standard"},
+	} {
+		cmd := hostGit(dir, args...)
+		output, err := cmd.Output()
+		if err != nil {
+			return nil, fmt.Errorf("git %s failed: %w", args[0], err)
+		}
+		files = append(files, strings.Fields(string(output))...)
+	}
+	return files, nil
+}
+
+// FileStat is the number of lines added and deleted in one changed file
+type FileStat struct {
+	Path    string
+	Added   int
+	Deleted int
+	// Binary files have no line counts
+	Binary bool
+}
+
+// DiffStats returns per-file line counts for uncommitted changes in dir; untracked
+// files count as entirely added
+func DiffStats(dir string) ([]FileStat, error) {
+	cmd := hostGit(dir, "diff", "--numstat", "--no-renames", "HEAD")
+	output, err := cmd.Output()
+	if err != nil {
+		return nil, fmt.Errorf("git diff failed: %w", err)
+	}
+	stats := parseNumstat(string(output))
+
+	cmd = hostGit(dir, "ls-files", "--others", "--exclude-standard", "-z")
+	output, err = cmd.Output()
+	if err != nil {
+		return nil, fmt.Errorf("git ls-files failed: %w", err)
+	}
+	for _, file := range strings.Split(string(output), "\x00") {
+		if file == "" {
+			continue
+		}
+		stat := FileStat{Path: file}
+		if data, err := os.ReadFile(filepath.Join(dir, file)); err == nil {
+			if bytes.IndexByte(data, 0) >= 0 {
+				stat.Binary = true
+			} else {
+				stat.Added = bytes.Count(data, []byte("\n"))
+				if len(data) > 0 && data[len(data)-1] != '\n' {
+					stat.Added++
+				}
+			}
+		}
+		stats = append(stats, stat)
+	}
+	return stats, nil
+}
+
+// parseNumstat parses `git diff --numstat` output, where binary files show "-" counts
+func parseNumstat(output string) []FileStat {
+	var stats []FileStat
+	for _, line := range strings.Split(output, "\n") {
+		fields := strings.SplitN(line, "\t", 3)
+		if len(fields) != 3 {
+			continue
+		}
+		stat := FileStat{Path: fields[2]}
+		if fields[0] == "-" && fields[1] == "-" {
+			stat.Binary = true
+		} else {
+			stat.Added, _ = strconv.Atoi(fields[0])
+			stat.Deleted, _ = strconv.Atoi(fields[1])
+		}
+		stats = append(stats, stat)
+	}
+	return stats
+}
@@ -0,0 +1,22 @@
+package git
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestParseNumstat(t *testing.T) {
+	output := "3\t1\tinternal/cli/root.go\n-\t-\tdocs/logo.png\n0\t12\tmy file.txt\n"
+	want := []FileStat{
+		{Path: "internal/cli/root.go", Added: 3, Deleted: 1},
+		{Path: "docs/logo.png", Binary: true},
+		{Path: "my file.txt", Deleted: 12},
+	}
+	if got := parseNumstat(output); !reflect.DeepEqual(got, want) {
+		t.Errorf("parseNumstat() = %+v, want %+v", got, want)
+	}
+
+	if got := parseNumstat(""); len(got) != 0 {
+		t.Errorf("parseNumstat(\"\") = %+v, want none", got)
+	}
+}
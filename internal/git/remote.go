@@ -0,0 +1,126 @@
+package git
+
+import (
+	"fmt"
+	"net/url"
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+// Remote identifies a repository on a hosting service
+type Remote struct {
+	Host string
+	// Path is the repository path without ".git", e.g. "owner/repo" or "group/subgroup/repo"
+	Path string
+}
+
+// RemoteURL returns the fetch URL of the named remote
+func RemoteURL(dir, name string) (string, error) {
+	cmd := hostGit(dir, "remote", "get-url", name)
+	output, err := cmd.Output()
+	if err != nil {
+		return "", fmt.Errorf("remote %s not found", name)
+	}
+	return strings.TrimSpace(string(output)), nil
+}
+
+// ParseRemoteURL parses https, ssh:// and scp-style (git@host:owner/repo) remote URLs
+func ParseRemoteURL(raw string) (Remote, error) {
+	var host, repoPath string
+
+	if strings.Contains(raw, "://") {
+		u, err := url.Parse(raw)
+		if err != nil {
+			return Remote{}, fmt.Errorf("invalid remote URL %q: %w", raw, err)
+		}
+		host, repoPath = u.Hostname(), u.Path
+	} else if at := strings.Index(raw, "@"); at >= 0 && strings.Contains(raw[at:], ":") {
+		rest := raw[at+1:]
+		colon := strings.Index(rest, ":")
+		host, repoPath = rest[:colon], rest[colon+1:]
+	} else {
+		return Remote{}, fmt.Errorf("unsupported remote URL %q", raw)
+	}
+
+	repoPath = strings.TrimSuffix(strings.Trim(repoPath, "/"), ".git")
+	if host == "" || !strings.Contains(repoPath, "/") {
+		return Remote{}, fmt.Errorf("unsupported remote URL %q", raw)
+	}
+	return Remote{Host: host, Path: repoPath}, nil
+}
+
+// DefaultBranch returns the branch the remote's HEAD points to, falling back to "main"
+func DefaultBranch(dir, remote string) string {
+	cmd := hostGit(dir, "symbolic-ref", "--short", fmt.Sprintf("refs/remotes/%s/HEAD", remote))
+	output, err := cmd.Output()
+	if err != nil {
+		return "main"
+	}
+	return strings.TrimPrefix(strings.TrimSpace(string(output)), remote+"/")
+}
+
+// CommitAll stages every change in dir and commits it; it returns false when there was nothing to commit.
+// Hooks, filters and other programs the sandbox could have configured do not run.
+func CommitAll(dir, message string) (bool, error) {
+	addCmd := hostGit(dir, "add", "-A")
+	if output, err := addCmd.CombinedOutput(); err != nil {
+		return false, fmt.Errorf("git add failed: %w\nOutput: %s", err, string(output))
+	}
+
+	// Exit status 0 means the index matches HEAD
+	diffCmd := hostGit(dir, "diff", "--cached", "--quiet")
+	if diffCmd.Run() == nil {
+		return false, nil
+	}
+
+	commitCmd := hostGit(dir, "commit", "-m", message)
+	commitCmd.Stdout = os.Stdout
+	commitCmd.Stderr = os.Stderr
+	if err := commitCmd.Run(); err != nil {
+		return false, fmt.Errorf("git commit failed: %w", err)
+	}
+	return true, nil
+}
+
+// Push pushes branch to remote and sets it as upstream, using the host's git credentials.
+// Hooks such as pre-push and repository-configured ssh or credential programs do not run.
+func Push(dir, remote, branch string) error {
+	cmd := hostGit(dir, "push", "--set-upstream", remote, branch)
+	cmd.Stdin = os.Stdin
+	cmd.Stdout = os.Stdout
+	cmd.Stderr = os.Stderr
+	if err := cmd.Run(); err != nil {
+		return fmt.Errorf("git push failed: %w", err)
+	}
+	return nil
+}
+
+// DiffStat returns `git diff --stat` between the merge base of base and HEAD
+func DiffStat(dir, base string) (string, error) {
+	cmd := hostGit(dir, "diff", "--stat", "--no-ext-diff", "--no-textconv", base+"...HEAD")
+	output, err := cmd.Output()
+	if err != nil {
+		return "", fmt.Errorf("git diff failed: %w", err)
+	}
+	return strings.TrimRight(string(output), "\n"), nil
+}
+
+// FetchPullRequest fetches a GitHub pull request's head from remote into branch. When
+// branch is already checked out in its agentsandbox worktree, which git will not update
+// from a fetch, the worktree is fast-forwarded instead.
+func FetchPullRequest(dir, remote string, number int, branch string) error {
+	ref := fmt.Sprintf("pull/%d/head", number)
+
+	cmd := hostGit(dir, "fetch", remote, fmt.Sprintf("+%s:%s", ref, branch))
+	if root := RepoRoot(dir); root != "" {
+		worktreePath := filepath.Join(root, ".agentsandbox-worktrees", branch)
+		if _, err := os.Stat(worktreePath); err == nil {
+			cmd = hostGit(worktreePath, "pull", "--ff-only", remote, ref)
+		}
+	}
+	if output, err := cmd.CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to fetch pull request #%d: %w\nOutput: %s", number, err, string(output))
+	}
+	return nil
+}
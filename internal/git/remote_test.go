@@ -0,0 +1,70 @@
+package git
+
+import (
+	"os"
+	"os/exec"
+	"path/filepath"
+	"testing"
+)
+
+func TestParseRemoteURL(t *testing.T) {
+	tests := []struct {
+		url  string
+		want Remote
+	}{
+		{"git@github.com:acme/api.git", Remote{Host: "github.com", Path: "acme/api"}},
+		{"https://github.com/acme/api", Remote{Host: "github.com", Path: "acme/api"}},
+		{"https://token@gitlab.example.com/group/sub/api.git", Remote{Host: "gitlab.example.com", Path: "group/sub/api"}},
+		{"ssh://git@gitlab.com:2222/group/api.git", Remote{Host: "gitlab.com", Path: "group/api"}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.url, func(t *testing.T) {
+			got, err := ParseRemoteURL(tt.url)
+			if err != nil {
+				t.Fatalf("ParseRemoteURL() error = %v", err)
+			}
+			if got != tt.want {
+				t.Errorf("ParseRemoteURL() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+
+	for _, bad := range []string{"/srv/git/api.git", "https://github.com/api"} {
+		if _, err := ParseRemoteURL(bad); err == nil {
+			t.Errorf("ParseRemoteURL(%q) succeeded, want error", bad)
+		}
+	}
+}
+
+func TestCommitAllSkipsSandboxHooks(t *testing.T) {
+	if _, err := exec.LookPath("git"); err != nil {
+		t.Skip("git not installed")
+	}
+	dir := t.TempDir()
+	t.Setenv("GIT_AUTHOR_NAME", "test")
+	t.Setenv("GIT_AUTHOR_EMAIL", "test@example.com")
+	t.Setenv("GIT_COMMITTER_NAME", "test")
+	t.Setenv("GIT_COMMITTER_EMAIL", "test@example.com")
+	if output, err := exec.Command("git", "init", "-q", dir).CombinedOutput(); err != nil {
+		t.Fatalf("git init: %v\n%s", err, output)
+	}
+
+	// A hook the sandbox could have planted; it must not run on the host
+	marker := filepath.Join(t.TempDir(), "ran")
+	hook := "#!/bin/sh\ntouch " + marker + "\nexit 1\n"
+	if err := os.WriteFile(filepath.Join(dir, ".git", "hooks", "pre-commit"), []byte(hook), 0755); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(dir, "file.txt"), []byte("change\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	committed, err := CommitAll(dir, "test")
+	if err != nil || !committed {
+		t.Fatalf("CommitAll() = %v, %v; want a commit", committed, err)
+	}
+	if _, err := os.Stat(marker); err == nil {
+		t.Error("the repository's pre-commit hook ran")
+	}
+}
@@ -0,0 +1,82 @@
+package git
+
+import (
+	"fmt"
+	"os/exec"
+	"regexp"
+	"strings"
+)
+
+// sandboxSafeConfig stops git from running programs the sandbox can set up in a
+// repository it has write access to: hooks in .git/hooks or core.hooksPath, a
+// core.fsmonitor command, which git runs on add, status and commit, an external diff
+// program, commit and tag signing, and the ext:: transport, which runs the remote URL
+var sandboxSafeConfig = []string{
+	"-c", "core.hooksPath=/dev/null",
+	"-c", "core.fsmonitor=false",
+	"-c", "diff.external=",
+	"-c", "commit.gpgSign=false",
+	"-c", "tag.gpgSign=false",
+	"-c", "protocol.ext.allow=never",
+}
+
+// unsafeRepoKey matches repository config keys naming a program git would run that
+// sandboxSafeConfig cannot switch off: clean and smudge filters and diff and merge
+// drivers, which have names of the repository's choosing, includes that could define
+// them, and credential, ssh, askpass and signing programs, where overriding the key
+// would also throw away the user's own setting. Keys are as `git config --list` prints
+// them, with the section and variable names in lower case.
+var unsafeRepoKey = regexp.MustCompile(`^(filter\..+|diff\..+\.(textconv|command)|merge\..+\.driver|include\.path|includeif\..+|credential\.(.+\.)?helper|core\.sshcommand|core\.askpass|core\.gitproxy|gpg\.(.+\.)?program|remote\..+\.(uploadpack|receivepack))$`)
+
+// refsOnlyCommands read refs and config without running anything the repository
+// configures, so they skip the config check; trust lookups depend on them working
+var refsOnlyCommands = map[string]bool{
+	"rev-parse":    true,
+	"symbolic-ref": true,
+	"for-each-ref": true,
+	"update-ref":   true,
+	"remote":       true,
+}
+
+// hostGit returns a git command for the host to run in dir, a repository mounted
+// into a sandbox, with sandboxSafeConfig applied. When the repository's own config
+// names a program matching unsafeRepoKey, the command fails without running.
+func hostGit(dir string, args ...string) *exec.Cmd {
+	cmd := exec.Command("git", append(append([]string{}, sandboxSafeConfig...), args...)...)
+	cmd.Dir = dir
+	if len(args) > 0 && !refsOnlyCommands[args[0]] {
+		if err := checkRepoConfig(dir); err != nil {
+			cmd.Err = err
+		}
+	}
+	return cmd
+}
+
+// checkRepoConfig returns an error naming the first unsafe key in the repository and
+// worktree config of dir
+func checkRepoConfig(dir string) error {
+	cmd := exec.Command("git", "config", "--list", "--show-scope")
+	cmd.Dir = dir
+	output, err := cmd.Output()
+	if err != nil {
+		// Outside a repository there is no repository config; git reports the rest
+		return nil
+	}
+	return unsafeConfigEntry(string(output))
+}
+
+// unsafeConfigEntry checks `git config --list --show-scope` output for unsafe keys set
+// by the repository rather than the user
+func unsafeConfigEntry(output string) error {
+	for _, line := range strings.Split(output, "\n") {
+		scope, entry, ok := strings.Cut(line, "\t")
+		if !ok || (scope != "local" && scope != "worktree") {
+			continue
+		}
+		key, _, _ := strings.Cut(entry, "=")
+		if unsafeRepoKey.MatchString(key) {
+			return fmt.Errorf("refusing to run git on the host: the repository config sets %s, which the sandbox could have written to run a program on the host; remove it with 'git config --unset-all %s' if you set it yourself", key, key)
+		}
+	}
+	return nil
+}
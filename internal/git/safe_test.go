@@ -0,0 +1,137 @@
+package git
+
+import (
+	"os"
+	"os/exec"
+	"path/filepath"
+	"testing"
+)
+
+func TestUnsafeConfigEntry(t *testing.T) {
+	unsafe := []string{
+		"filter.lfs.clean=sh -c evil",
+		"filter.x.smudge=evil",
+		"filter.x.process=evil",
+		"diff.x.textconv=evil",
+		"diff.x.command=evil",
+		"merge.x.driver=evil",
+		"include.path=../evil.config",
+		"includeif.gitdir:/.path=../evil.config",
+		"credential.helper=!evil",
+		"credential.https://github.com.helper=!evil",
+		"core.sshcommand=evil",
+		"core.askpass=evil",
+		"core.gitproxy=evil",
+		"gpg.program=evil",
+		"gpg.ssh.program=evil",
+		"remote.origin.uploadpack=evil",
+		"remote.origin.receivepack=evil",
+	}
+	for _, entry := range unsafe {
+		for _, scope := range []string{"local", "worktree"} {
+			if err := unsafeConfigEntry(scope + "\t" + entry + "\n"); err == nil {
+				t.Errorf("unsafeConfigEntry(%s %q) = nil, want error", scope, entry)
+			}
+		}
+	}
+
+	safe := "global\tcredential.helper=osxkeychain\n" +
+		"global\tcore.sshcommand=ssh -i ~/.ssh/work\n" +
+		"system\tfilter.lfs.clean=git-lfs clean -- %f\n" +
+		"local\tcore.bare=false\n" +
+		"local\tremote.origin.url=git@github.com:acme/api.git\n" +
+		"local\tdiff.x.binary=true\n"
+	if err := unsafeConfigEntry(safe); err != nil {
+		t.Errorf("unsafeConfigEntry(user config) = %v, want nil", err)
+	}
+}
+
+// initSandboxRepo creates a repository with one commit, as the sandbox would see it
+func initSandboxRepo(t *testing.T) string {
+	t.Helper()
+	if _, err := exec.LookPath("git"); err != nil {
+		t.Skip("git not installed")
+	}
+	dir := t.TempDir()
+	t.Setenv("GIT_AUTHOR_NAME", "test")
+	t.Setenv("GIT_AUTHOR_EMAIL", "test@example.com")
+	t.Setenv("GIT_COMMITTER_NAME", "test")
+	t.Setenv("GIT_COMMITTER_EMAIL", "test@example.com")
+	for _, args := range [][]string{
+		{"init", "-q"},
+		{"commit", "-q", "--allow-empty", "-m", "initial"},
+	} {
+		cmd := exec.Command("git", args...)
+		cmd.Dir = dir
+		if output, err := cmd.CombinedOutput(); err != nil {
+			t.Fatalf("git %v: %v\n%s", args, err, output)
+		}
+	}
+	return dir
+}
+
+func setRepoConfig(t *testing.T, dir, key, value string) {
+	t.Helper()
+	cmd := exec.Command("git", "config", key, value)
+	cmd.Dir = dir
+	if output, err := cmd.CombinedOutput(); err != nil {
+		t.Fatalf("git config %s: %v\n%s", key, err, output)
+	}
+}
+
+func TestHostGitRefusesPlantedPrograms(t *testing.T) {
+	for _, key := range []string{
+		"filter.evil.clean",
+		"diff.evil.textconv",
+		"credential.helper",
+		"core.sshCommand",
+		"gpg.program",
+	} {
+		t.Run(key, func(t *testing.T) {
+			dir := initSandboxRepo(t)
+			marker := filepath.Join(t.TempDir(), "ran")
+			setRepoConfig(t, dir, key, "touch "+marker)
+			if err := os.WriteFile(filepath.Join(dir, ".gitattributes"), []byte("* filter=evil diff=evil\n"), 0644); err != nil {
+				t.Fatal(err)
+			}
+			if err := os.WriteFile(filepath.Join(dir, "file.txt"), []byte("change\n"), 0644); err != nil {
+				t.Fatal(err)
+			}
+
+			if _, err := CommitAll(dir, "sandbox changes"); err == nil {
+				t.Error("CommitAll() succeeded, want the planted config refused")
+			}
+			if _, err := WorkingTreeDiff(dir); err == nil {
+				t.Error("WorkingTreeDiff() succeeded, want the planted config refused")
+			}
+			if _, err := os.Stat(marker); err == nil {
+				t.Errorf("%s ran on the host", key)
+			}
+			if CurrentBranch(dir) == "" {
+				t.Error("CurrentBranch() failed, want ref lookups to keep working")
+			}
+		})
+	}
+}
+
+func TestWorkingTreeDiffSkipsExternalDiff(t *testing.T) {
+	dir := initSandboxRepo(t)
+	marker := filepath.Join(t.TempDir(), "ran")
+	setRepoConfig(t, dir, "diff.external", "touch "+marker)
+	if err := os.WriteFile(filepath.Join(dir, "file.txt"), []byte("change\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := CommitAll(dir, "track file"); err != nil {
+		t.Fatalf("CommitAll() error = %v", err)
+	}
+	if err := os.WriteFile(filepath.Join(dir, "file.txt"), []byte("changed again\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	if _, err := WorkingTreeDiff(dir); err != nil {
+		t.Fatalf("WorkingTreeDiff() error = %v", err)
+	}
+	if _, err := os.Stat(marker); err == nil {
+		t.Error("diff.external ran on the host")
+	}
+}
@@ -0,0 +1,178 @@
+package git
+
+import (
+	"errors"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// snapshotRefPrefix holds snapshot commits outside branches and tags, so they never
+// show up in git log or get pushed
+const snapshotRefPrefix = "refs/agentsandbox/snapshots/"
+
+// MaxSnapshots is how many snapshots are kept per repository; older ones are pruned
+const MaxSnapshots = 20
+
+// Snapshot is a commit of the whole working tree stored on a hidden ref
+type Snapshot struct {
+	// Name is the ref without its prefix, a timestamp such as 20250113-143000
+	Name    string
+	Commit  string
+	Time    time.Time
+	Message string
+}
+
+// Ref returns the full ref the snapshot is stored on
+func (s Snapshot) Ref() string {
+	return snapshotRefPrefix + s.Name
+}
+
+// runGit runs git in dir with extra environment and returns its trimmed output. It goes
+// through hostGit, since the previous session's agent could have set up hooks, an
+// fsmonitor, or the clean and smudge filters that add and restore would run.
+func runGit(dir string, env []string, args ...string) (string, error) {
+	cmd := hostGit(dir, args...)
+	if len(env) > 0 {
+		cmd.Env = append(os.Environ(), env...)
+	}
+	output, err := cmd.Output()
+	if err != nil {
+		var exitErr *exec.ExitError
+		if errors.As(err, &exitErr) {
+			return "", fmt.Errorf("git %s failed: %w\nOutput: %s", args[0], err, string(exitErr.Stderr))
+		}
+		return "", fmt.Errorf("git %s failed: %w", args[0], err)
+	}
+	return strings.TrimSpace(string(output)), nil
+}
+
+// CreateSnapshot commits every tracked and untracked, non-ignored file in the
+// repository at dir to a hidden ref, without touching the index, HEAD or the working
+// tree. Nothing is stored when the tree matches the latest snapshot, which is returned.
+func CreateSnapshot(dir, message string) (Snapshot, error) {
+	// Stage into a copy of the index so the user's staged changes are left alone;
+	// copying it lets git skip hashing files that have not changed
+	index, err := os.CreateTemp("", "agentsandbox-snapshot-index-")
+	if err != nil {
+		return Snapshot{}, err
+	}
+	index.Close()
+	defer os.Remove(index.Name())
+	if gitIndex, err := runGit(dir, nil, "rev-parse", "--git-path", "index"); err == nil {
+		if !filepath.IsAbs(gitIndex) {
+			gitIndex = filepath.Join(dir, gitIndex)
+		}
+		if data, err := os.ReadFile(gitIndex); err == nil {
+			if err := os.WriteFile(index.Name(), data, 0600); err != nil {
+				return Snapshot{}, err
+			}
+		} else {
+			os.Remove(index.Name())
+		}
+	}
+	env := []string{"GIT_INDEX_FILE=" + index.Name()}
+
+	if _, err := runGit(dir, env, "add", "-A"); err != nil {
+		return Snapshot{}, err
+	}
+	tree, err := runGit(dir, env, "write-tree")
+	if err != nil {
+		return Snapshot{}, err
+	}
+
+	snapshots, err := ListSnapshots(dir)
+	if err != nil {
+		return Snapshot{}, err
+	}
+	if n := len(snapshots); n > 0 {
+		if latest, err := runGit(dir, nil, "rev-parse", snapshots[n-1].Commit+"^{tree}"); err == nil && latest == tree {
+			return snapshots[n-1], nil
+		}
+	}
+
+	args := []string{"commit-tree", tree, "-m", message}
+	if head, err := HeadCommit(dir); err == nil {
+		args = append(args, "-p", head)
+	}
+	commit, err := runGit(dir, nil, args...)
+	if err != nil {
+		return Snapshot{}, err
+	}
+
+	now := time.Now()
+	snapshot := Snapshot{Name: now.Format("20060102-150405"), Commit: commit, Time: now, Message: message}
+	// Two snapshots in the same second must not overwrite each other
+	if n := len(snapshots); n > 0 && snapshots[n-1].Name >= snapshot.Name {
+		snapshot.Name = snapshots[n-1].Name + "-1"
+	}
+	if _, err := runGit(dir, nil, "update-ref", snapshot.Ref(), commit); err != nil {
+		return Snapshot{}, err
+	}
+
+	snapshots = append(snapshots, snapshot)
+	for len(snapshots) > MaxSnapshots {
+		if _, err := runGit(dir, nil, "update-ref", "-d", snapshots[0].Ref()); err != nil {
+			return snapshot, err
+		}
+		snapshots = snapshots[1:]
+	}
+	return snapshot, nil
+}
+
+// ListSnapshots returns the repository's snapshots, oldest first
+func ListSnapshots(dir string) ([]Snapshot, error) {
+	output, err := runGit(dir, nil, "for-each-ref", "--sort=refname",
+		"--format=%(refname)%00%(objectname)%00%(creatordate:unix)%00%(contents:subject)", snapshotRefPrefix)
+	if err != nil {
+		return nil, err
+	}
+	return parseSnapshots(output), nil
+}
+
+// parseSnapshots parses NUL-separated ref, commit, time and subject lines from git for-each-ref
+func parseSnapshots(output string) []Snapshot {
+	var snapshots []Snapshot
+	for _, line := range strings.Split(output, "\n") {
+		fields := strings.Split(line, "\x00")
+		if len(fields) != 4 || !strings.HasPrefix(fields[0], snapshotRefPrefix) {
+			continue
+		}
+		unix, _ := strconv.ParseInt(fields[2], 10, 64)
+		snapshots = append(snapshots, Snapshot{
+			Name:    strings.TrimPrefix(fields[0], snapshotRefPrefix),
+			Commit:  fields[1],
+			Time:    time.Unix(unix, 0),
+			Message: fields[3],
+		})
+	}
+	return snapshots
+}
+
+// RestoreSnapshot makes the working tree of the repository rooted at dir match
+// snapshot. current must be a snapshot of the working tree as it is now; files it has
+// that snapshot lacks are deleted. The index and HEAD are left alone, so the restored
+// files show up as changes.
+func RestoreSnapshot(dir string, snapshot, current Snapshot) error {
+	added, err := runGit(dir, nil, "diff", "--name-only", "-z", "--no-ext-diff", "--no-textconv", "--no-renames", "--diff-filter=A", snapshot.Commit, current.Commit)
+	if err != nil {
+		return err
+	}
+	for _, file := range strings.Split(added, "\x00") {
+		if file == "" {
+			continue
+		}
+		if err := os.Remove(filepath.Join(dir, filepath.FromSlash(file))); err != nil && !os.IsNotExist(err) {
+			return err
+		}
+	}
+
+	if _, err := runGit(dir, nil, "restore", "--source="+snapshot.Commit, "--worktree", "--", "."); err != nil {
+		return err
+	}
+	return nil
+}
@@ -0,0 +1,92 @@
+package git
+
+import (
+	"os"
+	"os/exec"
+	"path/filepath"
+	"testing"
+	"time"
+)
+
+func TestParseSnapshots(t *testing.T) {
+	output := "refs/agentsandbox/snapshots/20250113-143000\x00abc123\x001736778600\x00before agentsandbox-api\n" +
+		"refs/heads/main\x00def456\x001736778600\x00not a snapshot\n" +
+		"garbage"
+
+	snapshots := parseSnapshots(output)
+	if len(snapshots) != 1 {
+		t.Fatalf("parseSnapshots() = %+v, want one snapshot", snapshots)
+	}
+	s := snapshots[0]
+	if s.Name != "20250113-143000" || s.Commit != "abc123" || s.Message != "before agentsandbox-api" ||
+		!s.Time.Equal(time.Date(2025, 1, 13, 14, 30, 0, 0, time.UTC)) {
+		t.Errorf("unexpected snapshot %+v", s)
+	}
+	if s.Ref() != "refs/agentsandbox/snapshots/20250113-143000" {
+		t.Errorf("Ref() = %q", s.Ref())
+	}
+}
+
+func TestCreateSnapshotIgnoresFSMonitor(t *testing.T) {
+	if _, err := exec.LookPath("git"); err != nil {
+		t.Skip("git not installed")
+	}
+	dir := t.TempDir()
+	t.Setenv("GIT_AUTHOR_NAME", "test")
+	t.Setenv("GIT_AUTHOR_EMAIL", "test@example.com")
+	t.Setenv("GIT_COMMITTER_NAME", "test")
+	t.Setenv("GIT_COMMITTER_EMAIL", "test@example.com")
+
+	// An fsmonitor command the sandbox could have configured; git add would run it
+	marker := filepath.Join(t.TempDir(), "ran")
+	script := filepath.Join(t.TempDir(), "monitor.sh")
+	if err := os.WriteFile(script, []byte("#!/bin/sh\ntouch "+marker+"\n"), 0755); err != nil {
+		t.Fatal(err)
+	}
+	for _, args := range [][]string{{"init", "-q"}, {"config", "core.fsmonitor", script}} {
+		cmd := exec.Command("git", args...)
+		cmd.Dir = dir
+		if output, err := cmd.CombinedOutput(); err != nil {
+			t.Fatalf("git %v: %v\n%s", args, err, output)
+		}
+	}
+	if err := os.WriteFile(filepath.Join(dir, "file.txt"), []byte("content\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	if _, err := CreateSnapshot(dir, "test"); err != nil {
+		t.Fatalf("CreateSnapshot() error = %v", err)
+	}
+	if _, err := os.Stat(marker); err == nil {
+		t.Error("the repository's fsmonitor command ran")
+	}
+}
+
+func TestSnapshotsRefusePlantedFilter(t *testing.T) {
+	dir := initSandboxRepo(t)
+	if err := os.WriteFile(filepath.Join(dir, "file.txt"), []byte("content\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	before, err := CreateSnapshot(dir, "before")
+	if err != nil {
+		t.Fatalf("CreateSnapshot() error = %v", err)
+	}
+
+	// A filter the agent could have configured; add runs clean and restore runs smudge
+	marker := filepath.Join(t.TempDir(), "ran")
+	setRepoConfig(t, dir, "filter.evil.clean", "touch "+marker)
+	setRepoConfig(t, dir, "filter.evil.smudge", "touch "+marker)
+	if err := os.WriteFile(filepath.Join(dir, ".gitattributes"), []byte("* filter=evil\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	if _, err := CreateSnapshot(dir, "after"); err == nil {
+		t.Error("CreateSnapshot() succeeded, want the planted filter refused")
+	}
+	if err := RestoreSnapshot(dir, before, before); err == nil {
+		t.Error("RestoreSnapshot() succeeded, want the planted filter refused")
+	}
+	if _, err := os.Stat(marker); err == nil {
+		t.Error("the repository's filter ran on the host")
+	}
+}
@@ -11,8 +11,7 @@ import (
 // CreateWorktree creates a git worktree for the specified branch
 func CreateWorktree(baseDir, branch string) (string, error) {
 	// Get the git repository root
-	cmd := exec.Command("git", "rev-parse", "--show-toplevel")
-	cmd.Dir = baseDir
+	cmd := hostGit(baseDir, "rev-parse", "--show-toplevel")
 	output, err := cmd.Output()
 	if err != nil {
 		return "", fmt.Errorf("not a git repository: %w", err)
@@ -34,21 +33,19 @@ func CreateWorktree(baseDir, branch string) (string, error) {
 	}
 
 	// Check if branch exists
-	branchCmd := exec.Command("git", "rev-parse", "--verify", branch)
-	branchCmd.Dir = root
+	branchCmd := hostGit(root, "rev-parse", "--verify", branch)
 	branchExists := branchCmd.Run() == nil
 
 	// Create worktree
 	var wtCmd *exec.Cmd
 	if branchExists {
 		// Branch exists, checkout existing branch
-		wtCmd = exec.Command("git", "worktree", "add", "--force", worktreePath, branch)
+		wtCmd = hostGit(root, "worktree", "add", "--force", worktreePath, branch)
 	} else {
 		// Create new branch
-		wtCmd = exec.Command("git", "worktree", "add", "--force", "-b", branch, worktreePath)
+		wtCmd = hostGit(root, "worktree", "add", "--force", "-b", branch, worktreePath)
 	}
 
-	wtCmd.Dir = root
 	wtCmd.Stdout = os.Stdout
 	wtCmd.Stderr = os.Stderr
 
@@ -59,3 +56,54 @@ func CreateWorktree(baseDir, branch string) (string, error) {
 	return worktreePath, nil
 }
 
+
+// CreateDetachedWorktree checks out commit into a new detached worktree named name
+func CreateDetachedWorktree(baseDir, name, commit string) (string, error) {
+	cmd := hostGit(baseDir, "rev-parse", "--show-toplevel")
+	output, err := cmd.Output()
+	if err != nil {
+		return "", fmt.Errorf("not a git repository: %w", err)
+	}
+
+	root := strings.TrimSpace(string(output))
+	worktreePath := filepath.Join(root, ".agentsandbox-worktrees", name)
+	if _, err := os.Stat(worktreePath); err == nil {
+		return "", fmt.Errorf("worktree %s already exists", worktreePath)
+	}
+
+	wtCmd := hostGit(root, "worktree", "add", "--detach", worktreePath, commit)
+	if output, err := wtCmd.CombinedOutput(); err != nil {
+		return "", fmt.Errorf("git worktree add failed: %w\nOutput: %s", err, string(output))
+	}
+
+	return worktreePath, nil
+}
+
+// RemoveWorktree removes a worktree created by CreateWorktree. Worktrees with
+// uncommitted changes are kept and an error is returned.
+func RemoveWorktree(worktreePath string) error {
+	cmd := hostGit(worktreeRoot(worktreePath), "worktree", "remove", worktreePath)
+	if output, err := cmd.CombinedOutput(); err != nil {
+		return fmt.Errorf("git worktree remove failed: %w\nOutput: %s", err, strings.TrimSpace(string(output)))
+	}
+	return nil
+}
+
+// DeleteMergedBranch deletes the branch of a removed worktree unless it has unmerged commits
+func DeleteMergedBranch(worktreePath, branch string) error {
+	cmd := hostGit(worktreeRoot(worktreePath), "branch", "-d", branch)
+	if output, err := cmd.CombinedOutput(); err != nil {
+		return fmt.Errorf("kept branch %s: %s", branch, strings.TrimSpace(string(output)))
+	}
+	return nil
+}
+
+// IsWorktreePath reports whether dir is inside a worktree created by agentsandbox
+func IsWorktreePath(dir string) bool {
+	return strings.Contains(filepath.ToSlash(dir), "/.agentsandbox-worktrees/")
+}
+
+// worktreeRoot returns the main working tree that contains the worktrees directory
+func worktreeRoot(worktreePath string) string {
+	return filepath.Dir(filepath.Dir(worktreePath))
+}
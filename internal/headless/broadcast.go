@@ -0,0 +1,118 @@
+package headless
+
+import (
+	"bytes"
+	"context"
+	"fmt"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/git"
+)
+
+// Result is the outcome of a broadcast prompt in one sandbox
+type Result struct {
+	Container string
+	Dir       string
+	Output    string
+	LogFile   string
+	Duration  time.Duration
+	// FilesChanged counts uncommitted files in the project after the run
+	FilesChanged int
+	Err          error
+}
+
+// Broadcast runs prompt headlessly in every target at once, at most parallel at a time,
+// and returns the results in the order of targets
+func Broadcast(targets []container.ContainerInfo, prompt string, parallel int) []Result {
+	settings, err := config.LoadSettings()
+	if err != nil {
+		settings = config.DefaultSettings()
+	}
+	if parallel < 1 {
+		parallel = 1
+	}
+
+	results := make([]Result, len(targets))
+	slots := make(chan struct{}, parallel)
+	var wg sync.WaitGroup
+	for i, target := range targets {
+		wg.Add(1)
+		go func(i int, target container.ContainerInfo) {
+			defer wg.Done()
+			slots <- struct{}{}
+			defer func() { <-slots }()
+
+			agent := config.AgentClaude
+			if target.Agent != "" {
+				agent = config.Agent(target.Agent)
+			}
+
+			var output bytes.Buffer
+			started := time.Now()
+			data := map[string]interface{}{"broadcast": true}
+			skipPermissionFlag := container.PermissionFlag(settings, agent, container.RecordedTrust(target.Directory))
+			logFile, err := RunIn(context.Background(), target.Name, target.Directory, agent, skipPermissionFlag, prompt, data, &output)
+
+			result := Result{
+				Container: target.Name,
+				Dir:       target.Directory,
+				Output:    output.String(),
+				LogFile:   logFile,
+				Duration:  time.Since(started),
+				Err:       err,
+			}
+			if files, err := git.ChangedFiles(target.Directory); err == nil {
+				result.FilesChanged = len(files)
+			}
+			results[i] = result
+		}(i, target)
+	}
+	wg.Wait()
+	return results
+}
+
+// FormatReport renders broadcast results as a single Markdown report
+func FormatReport(prompt string, results []Result) string {
+	failed := 0
+	for _, r := range results {
+		if r.Err != nil {
+			failed++
+		}
+	}
+
+	var b strings.Builder
+	b.WriteString("# Broadcast report\n\n")
+	fmt.Fprintf(&b, "Prompt: %s\n\n", prompt)
+	fmt.Fprintf(&b, "%d sandbox(es), %d succeeded, %d failed\n\n", len(results), len(results)-failed, failed)
+
+	b.WriteString("| Container | Status | Duration | Files changed |\n")
+	b.WriteString("| --- | --- | --- | --- |\n")
+	for _, r := range results {
+		fmt.Fprintf(&b, "| %s | %s | %s | %d |\n", r.Container, r.Status(), r.Duration.Round(time.Second), r.FilesChanged)
+	}
+
+	for _, r := range results {
+		fmt.Fprintf(&b, "\n## %s\n\n", r.Container)
+		fmt.Fprintf(&b, "Directory: %s\n", r.Dir)
+		if r.LogFile != "" {
+			fmt.Fprintf(&b, "Session log: %s\n", r.LogFile)
+		}
+		if r.Err != nil {
+			fmt.Fprintf(&b, "Error: %v\n", r.Err)
+		}
+		fmt.Fprintf(&b, "\n```\n%s\n```\n", strings.TrimRight(r.Output, "\n"))
+	}
+	return b.String()
+}
+
+// Status is "ok" or "failed"
+func (r Result) Status() string {
+	if r.Err != nil {
+		return "failed"
+	}
+	return "ok"
+}
@@ -0,0 +1,30 @@
+package headless
+
+import (
+	"errors"
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestFormatReport(t *testing.T) {
+	results := []Result{
+		{Container: "agentsandbox-api", Dir: "/src/api", Output: "all tests passed\n", LogFile: "/logs/api.jsonl", Duration: 90 * time.Second, FilesChanged: 2},
+		{Container: "agentsandbox-web", Dir: "/src/web", Output: "FAIL src/app.test.ts\n", Duration: 30 * time.Second, Err: errors.New("exit status 1")},
+	}
+
+	report := FormatReport("run the tests", results)
+	for _, want := range []string{
+		"Prompt: run the tests",
+		"2 sandbox(es), 1 succeeded, 1 failed",
+		"| agentsandbox-api | ok | 1m30s | 2 |",
+		"| agentsandbox-web | failed | 30s | 0 |",
+		"## agentsandbox-web",
+		"Error: exit status 1",
+		"```\nFAIL src/app.test.ts\n```",
+	} {
+		if !strings.Contains(report, want) {
+			t.Errorf("report missing %q:\n%s", want, report)
+		}
+	}
+}
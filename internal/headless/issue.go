@@ -0,0 +1,150 @@
+package headless
+
+import (
+	"context"
+	"fmt"
+	"io"
+	"strings"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/forge"
+	"github.com/thaodangspace/agentsandbox/internal/git"
+	"github.com/thaodangspace/agentsandbox/internal/logs"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+// reportLines is how many of the agent's last output lines an issue comment quotes
+const reportLines = 40
+
+// IssueTask asks for a headless run on a GitHub issue or pull request of a local
+// checkout, as a webhook delivery does
+type IssueTask struct {
+	// Dir is the checkout of the repository whose origin remote the issue belongs to
+	Dir string
+	// Number is the issue or pull request number
+	Number int
+	// PullRequest is set when Number is a pull request
+	PullRequest bool
+	// Request is what a comment asked for, added to the prompt
+	Request string
+	Agent   config.Agent
+}
+
+// RunIssue runs the agent headlessly on an issue or pull request and comments the
+// outcome on it. An issue gets the issue-N worktree `agentsandbox from-issue` uses; a
+// pull request has its head fetched into a pr-N worktree. The sandbox gets the trust
+// level recorded for the checkout, and the changes stay in the worktree for review.
+func RunIssue(ctx context.Context, task IssueTask, stream io.Writer) (*Outcome, error) {
+	remoteURL, err := git.RemoteURL(task.Dir, "origin")
+	if err != nil {
+		return nil, err
+	}
+	remote, err := git.ParseRemoteURL(remoteURL)
+	if err != nil {
+		return nil, err
+	}
+	issue, err := forge.FetchIssue(remote, task.Number)
+	if err != nil {
+		return nil, err
+	}
+
+	branch := fmt.Sprintf("issue-%d", task.Number)
+	prompt := issue.Prompt()
+	if task.PullRequest {
+		branch = fmt.Sprintf("pr-%d", task.Number)
+		if err := git.FetchPullRequest(task.Dir, "origin", task.Number, branch); err != nil {
+			return nil, err
+		}
+		prompt = fmt.Sprintf("Work on GitHub pull request #%d: %s\n\n%s\n\nPull request: %s", issue.Number, issue.Title, strings.TrimSpace(issue.Body), issue.URL)
+	}
+	if request := strings.TrimSpace(task.Request); request != "" {
+		prompt += "\n\nRequest: " + request
+	}
+
+	worktreePath, err := git.CreateWorktree(task.Dir, branch)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create worktree for branch %s: %w", branch, err)
+	}
+
+	settings, err := config.LoadSettings()
+	if err != nil {
+		settings = config.DefaultSettings()
+	}
+	level := container.RecordedTrust(task.Dir)
+	skipPermissionFlag := container.PermissionFlag(settings, task.Agent, level)
+
+	containerName := container.GenerateContainerName(worktreePath, task.Agent)
+	started := true
+	if exists, _ := container.ContainerExists(containerName); exists {
+		info, err := container.InspectContainer(containerName)
+		if err != nil {
+			return nil, err
+		}
+		started = !info.Running || info.Paused
+		if err := container.ResumeContainer(containerName, task.Agent, false, skipPermissionFlag, false, false, container.SessionOptions{}); err != nil {
+			return nil, err
+		}
+	} else {
+		opts := container.CreateOptions{Trust: level}
+		if err := container.CreateContainer(containerName, worktreePath, "", task.Agent, skipPermissionFlag, false, false, nil, opts); err != nil {
+			return nil, fmt.Errorf("failed to create container: %w", err)
+		}
+	}
+
+	link := state.IssueLink{Number: issue.Number, Title: issue.Title, URL: issue.URL, Branch: branch, Timestamp: time.Now()}
+	if err := state.SaveIssueLink(containerName, worktreePath, link); err != nil {
+		fmt.Printf("Warning: failed to record issue link: %v\n", err)
+	}
+
+	// The agent may commit its work, so changes are counted from where it started
+	base, err := git.HeadCommit(worktreePath)
+	if err != nil {
+		return nil, err
+	}
+
+	var output timedLines
+	data := map[string]interface{}{"issue": issue.Number, "webhook": true}
+	logFile, runErr := RunIn(ctx, containerName, worktreePath, task.Agent, skipPermissionFlag, prompt, data, io.MultiWriter(stream, &output))
+	outcome := &Outcome{Container: containerName, Started: started, LogFile: logFile}
+
+	changed, _ := git.ChangedFilesSince(worktreePath, base)
+	var lines []string
+	for _, line := range output.Lines() {
+		lines = append(lines, logs.RenderTerminal(line.Text))
+	}
+	report := logs.LoadRedactor().Redact(FormatIssueReport(branch, worktreePath, changed, lines, runErr))
+	if err := forge.Comment(remote, task.Number, report); err != nil {
+		fmt.Printf("Warning: %v\n", err)
+	}
+	return outcome, runErr
+}
+
+// FormatIssueReport renders the comment posted on an issue after a run: whether the
+// agent succeeded, the files it changed and the end of its output
+func FormatIssueReport(branch, worktreePath string, changed, output []string, runErr error) string {
+	var b strings.Builder
+	if runErr != nil {
+		fmt.Fprintf(&b, "The agent failed on branch `%s`: %v\n\n", branch, runErr)
+	} else {
+		fmt.Fprintf(&b, "The agent finished on branch `%s`.\n\n", branch)
+	}
+
+	if len(changed) == 0 {
+		b.WriteString("No files were changed.\n")
+	} else {
+		fmt.Fprintf(&b, "%d file(s) changed, waiting for review in `%s`:\n\n", len(changed), worktreePath)
+		for _, file := range changed {
+			fmt.Fprintf(&b, "- `%s`\n", file)
+		}
+	}
+
+	if len(output) > reportLines {
+		output = output[len(output)-reportLines:]
+	}
+	if len(output) > 0 {
+		fmt.Fprintf(&b, "\n<details><summary>Agent output</summary>\n\n```\n%s\n```\n\n</details>\n", strings.Join(output, "\n"))
+	}
+	return b.String()
+}
@@ -0,0 +1,40 @@
+package headless
+
+import (
+	"errors"
+	"strings"
+	"testing"
+)
+
+func TestFormatIssueReport(t *testing.T) {
+	output := make([]string, 50)
+	for i := range output {
+		output[i] = "line"
+	}
+	output[len(output)-1] = "all tests passed"
+
+	report := FormatIssueReport("issue-7", "/src/api/.agentsandbox-worktrees/issue-7", []string{"main.go"}, output, nil)
+	for _, want := range []string{
+		"The agent finished on branch `issue-7`.",
+		"1 file(s) changed, waiting for review in `/src/api/.agentsandbox-worktrees/issue-7`",
+		"- `main.go`",
+		"all tests passed\n```",
+	} {
+		if !strings.Contains(report, want) {
+			t.Errorf("report missing %q:\n%s", want, report)
+		}
+	}
+	if lines := strings.Count(report, "line\n"); lines != reportLines-1 {
+		t.Errorf("report quotes %d output lines, want the last %d", lines+1, reportLines)
+	}
+
+	report = FormatIssueReport("pr-9", "/wt", nil, nil, errors.New("exit status 1"))
+	for _, want := range []string{"The agent failed on branch `pr-9`: exit status 1", "No files were changed."} {
+		if !strings.Contains(report, want) {
+			t.Errorf("report missing %q:\n%s", want, report)
+		}
+	}
+	if strings.Contains(report, "<details>") {
+		t.Errorf("report quotes output it does not have:\n%s", report)
+	}
+}
@@ -0,0 +1,132 @@
+package headless
+
+import (
+	"context"
+	"fmt"
+	"io"
+	"os"
+	"strings"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/git"
+	"github.com/thaodangspace/agentsandbox/internal/logs"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+// Outcome says where a headless run happened and where it was logged
+type Outcome struct {
+	// Container is the sandbox the prompt ran in
+	Container string
+	// Started is set when the sandbox was created, started or unpaused for the run
+	Started bool
+	// LogFile is the run's session log
+	LogFile string
+}
+
+// Run executes prompt in the sandbox for dir, creating or starting the container as
+// needed. The prompt and the agent's output are written to a new session log, and the
+// project's uncommitted changes afterwards are saved beside it as a .diff file. data is
+// attached to the logged prompt.
+func Run(dir, agentName, prompt string, data map[string]interface{}) (string, error) {
+	outcome, err := RunContext(context.Background(), dir, agentName, prompt, data, os.Stdout)
+	if outcome == nil {
+		return "", err
+	}
+	return outcome.LogFile, err
+}
+
+// RunContext is Run with the agent's output streamed to stream. Cancelling ctx kills the
+// agent's docker exec. The outcome is nil when no sandbox could be readied, and set
+// alongside the agent's error otherwise.
+func RunContext(ctx context.Context, dir, agentName, prompt string, data map[string]interface{}, stream io.Writer) (*Outcome, error) {
+	agent, err := config.ValidateAgent(agentName)
+	if err != nil {
+		return nil, err
+	}
+
+	settings, err := config.LoadSettings()
+	if err != nil {
+		settings = config.DefaultSettings()
+	}
+	level := container.RecordedTrust(dir)
+	skipPermissionFlag := container.PermissionFlag(settings, agent, level)
+
+	containerName, started, err := ensureContainer(dir, agent, skipPermissionFlag, level)
+	if err != nil {
+		return nil, err
+	}
+
+	logFile, err := RunIn(ctx, containerName, dir, agent, skipPermissionFlag, prompt, data, stream)
+	return &Outcome{Container: containerName, Started: started, LogFile: logFile}, err
+}
+
+// RunIn executes prompt in an existing, running container, streaming the agent's output
+// and errors to stream. It records the session log and diff like Run.
+func RunIn(ctx context.Context, containerName, dir string, agent config.Agent, skipPermissionFlag, prompt string, data map[string]interface{}, stream io.Writer) (string, error) {
+	logFile, err := state.PrepareSessionLog(containerName, dir)
+	if err != nil {
+		return "", fmt.Errorf("failed to create session log: %w", err)
+	}
+
+	var output timedLines
+	started := time.Now()
+	// Errors the agent prints belong in the session log with the rest of its output
+	combined := io.MultiWriter(stream, &output)
+	runErr := container.RunHeadless(ctx, containerName, dir, agent, skipPermissionFlag, prompt, combined, combined)
+
+	events := []logs.LogEvent{{
+		Timestamp: started.Format(time.RFC3339),
+		Level:     "user",
+		Message:   prompt,
+		Data:      data,
+	}}
+	for _, line := range output.Lines() {
+		events = append(events, logs.LogEvent{Timestamp: line.At.Format(time.RFC3339), Level: "info", Message: logs.RenderTerminal(line.Text)})
+	}
+	if runErr != nil {
+		events = append(events, logs.LogEvent{Timestamp: time.Now().Format(time.RFC3339), Level: "error", Message: runErr.Error()})
+	}
+	if err := logs.WriteEvents(logFile, logs.RedactEvents(events, logs.LoadRedactor())); err != nil {
+		fmt.Printf("Warning: failed to write session log: %v\n", err)
+	}
+
+	if diff, err := git.WorkingTreeDiff(dir); err == nil && diff != "" {
+		diffFile := strings.TrimSuffix(logFile, ".jsonl") + ".diff"
+		if err := os.WriteFile(diffFile, []byte(diff), 0644); err != nil {
+			fmt.Printf("Warning: failed to save changes: %v\n", err)
+		}
+	}
+
+	return logFile, runErr
+}
+
+// ensureContainer returns a running sandbox for dir, creating one with the trust level
+// if needed, and whether it was not already running
+func ensureContainer(dir string, agent config.Agent, skipPermissionFlag, level string) (string, bool, error) {
+	if err := container.CheckDockerAvailability(); err != nil {
+		return "", false, err
+	}
+
+	existing, err := container.FindExistingContainer(dir, agent)
+	if err != nil {
+		return "", false, err
+	}
+	if existing != "" {
+		info, err := container.InspectContainer(existing)
+		if err != nil {
+			return "", false, err
+		}
+		if err := container.ResumeContainer(existing, agent, false, skipPermissionFlag, false, false, container.SessionOptions{}); err != nil {
+			return "", false, err
+		}
+		return existing, !info.Running || info.Paused, nil
+	}
+
+	containerName := container.GenerateContainerName(dir, agent)
+	if err := container.CreateContainer(containerName, dir, "", agent, skipPermissionFlag, false, false, nil, container.CreateOptions{Trust: level}); err != nil {
+		return "", false, fmt.Errorf("failed to create container: %w", err)
+	}
+	return containerName, true, nil
+}
@@ -0,0 +1,57 @@
+package headless
+
+import (
+	"bytes"
+	"sync"
+	"time"
+)
+
+// timedLine is one line of agent output and the time it started arriving
+type timedLine struct {
+	At   time.Time
+	Text string
+}
+
+// timedLines is an io.Writer that splits output into lines and records when each
+// began, so the session log keeps the real gaps between them
+type timedLines struct {
+	mu      sync.Mutex
+	lines   []timedLine
+	partial bytes.Buffer
+	started time.Time
+}
+
+func (w *timedLines) Write(p []byte) (int, error) {
+	w.mu.Lock()
+	defer w.mu.Unlock()
+
+	now := time.Now()
+	rest := p
+	for len(rest) > 0 {
+		if w.partial.Len() == 0 {
+			w.started = now
+		}
+		i := bytes.IndexByte(rest, '\n')
+		if i < 0 {
+			w.partial.Write(rest)
+			break
+		}
+		w.partial.Write(rest[:i])
+		w.lines = append(w.lines, timedLine{At: w.started, Text: w.partial.String()})
+		w.partial.Reset()
+		rest = rest[i+1:]
+	}
+	return len(p), nil
+}
+
+// Lines returns every line written so far, including an unterminated last line
+func (w *timedLines) Lines() []timedLine {
+	w.mu.Lock()
+	defer w.mu.Unlock()
+
+	lines := append([]timedLine(nil), w.lines...)
+	if w.partial.Len() > 0 {
+		lines = append(lines, timedLine{At: w.started, Text: w.partial.String()})
+	}
+	return lines
+}
@@ -0,0 +1,31 @@
+package headless
+
+import (
+	"testing"
+	"time"
+)
+
+func TestTimedLines(t *testing.T) {
+	var w timedLines
+	w.Write([]byte("first\nsec"))
+	time.Sleep(20 * time.Millisecond)
+	w.Write([]byte("ond\n"))
+	time.Sleep(20 * time.Millisecond)
+	w.Write([]byte("third"))
+
+	lines := w.Lines()
+	if len(lines) != 3 {
+		t.Fatalf("got %d lines, want 3", len(lines))
+	}
+	for i, want := range []string{"first", "second", "third"} {
+		if lines[i].Text != want {
+			t.Errorf("line %d = %q, want %q", i, lines[i].Text, want)
+		}
+	}
+	if !lines[0].At.Equal(lines[1].At) {
+		t.Errorf("second line should be stamped when it started arriving")
+	}
+	if !lines[2].At.After(lines[1].At) {
+		t.Errorf("third line should be stamped after the second")
+	}
+}
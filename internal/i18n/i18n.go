@@ -0,0 +1,71 @@
+package i18n
+
+import (
+	"fmt"
+	"os"
+	"strings"
+)
+
+// DefaultLanguage is used when neither the settings nor the environment name a supported language
+const DefaultLanguage = "en"
+
+var current = DefaultLanguage
+
+// Configure selects the language for user-facing messages. An empty lang falls
+// back to LC_ALL, LC_MESSAGES and LANG, in that order, like other CLI tools.
+func Configure(lang string) {
+	if lang == "" {
+		lang = environmentLanguage()
+	}
+	current = normalize(lang)
+}
+
+// Language returns the language messages are printed in
+func Language() string {
+	return current
+}
+
+// Supported returns the languages that have a message catalog
+func Supported() []string {
+	langs := make([]string, 0, len(catalogs))
+	for lang := range catalogs {
+		langs = append(langs, lang)
+	}
+	return langs
+}
+
+// T returns the message for key in the configured language, formatted with args.
+// Messages missing from a translation fall back to English, then to the key itself.
+func T(key string, args ...interface{}) string {
+	msg, ok := catalogs[current][key]
+	if !ok {
+		if msg, ok = catalogs[DefaultLanguage][key]; !ok {
+			msg = key
+		}
+	}
+	if len(args) == 0 {
+		return msg
+	}
+	return fmt.Sprintf(msg, args...)
+}
+
+func environmentLanguage() string {
+	for _, name := range []string{"LC_ALL", "LC_MESSAGES", "LANG"} {
+		if v := os.Getenv(name); v != "" {
+			return v
+		}
+	}
+	return ""
+}
+
+// normalize turns a language setting or locale such as vi_VN.UTF-8 into a catalog name
+func normalize(lang string) string {
+	lang = strings.ToLower(lang)
+	if i := strings.IndexAny(lang, "_-.@"); i >= 0 {
+		lang = lang[:i]
+	}
+	if _, ok := catalogs[lang]; !ok {
+		return DefaultLanguage
+	}
+	return lang
+}
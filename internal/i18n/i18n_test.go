@@ -0,0 +1,59 @@
+package i18n
+
+import (
+	"regexp"
+	"testing"
+)
+
+func TestConfigure(t *testing.T) {
+	t.Cleanup(func() { Configure(DefaultLanguage) })
+
+	t.Setenv("LC_ALL", "")
+	t.Setenv("LC_MESSAGES", "")
+	t.Setenv("LANG", "vi_VN.UTF-8")
+	Configure("")
+	if Language() != "vi" {
+		t.Errorf("LANG=vi_VN.UTF-8 should select vi, got %s", Language())
+	}
+
+	Configure("en")
+	if Language() != "en" {
+		t.Errorf("the setting should override LANG, got %s", Language())
+	}
+
+	t.Setenv("LANG", "C.UTF-8")
+	Configure("")
+	if Language() != DefaultLanguage {
+		t.Errorf("an unsupported locale should fall back to %s, got %s", DefaultLanguage, Language())
+	}
+}
+
+func TestT(t *testing.T) {
+	t.Cleanup(func() { Configure(DefaultLanguage) })
+
+	Configure("vi")
+	if got := T("session.name", "demo"); got != "Tên phiên: demo" {
+		t.Errorf("unexpected translation %q", got)
+	}
+	if got := T("no.such.key"); got != "no.such.key" {
+		t.Errorf("a missing key should be returned as is, got %q", got)
+	}
+}
+
+var verb = regexp.MustCompile(`%[-+# 0-9.]*[a-zA-Z]`)
+
+// Every translation must take the same arguments as the English message
+func TestCatalogsMatchEnglish(t *testing.T) {
+	for lang, messages := range catalogs {
+		for key, msg := range messages {
+			en, ok := catalogs[DefaultLanguage][key]
+			if !ok {
+				t.Errorf("%s: %s has no English message", lang, key)
+				continue
+			}
+			if a, b := verb.FindAllString(en, -1), verb.FindAllString(msg, -1); len(a) != len(b) {
+				t.Errorf("%s: %s takes %d arguments, English takes %d", lang, key, len(b), len(a))
+			}
+		}
+	}
+}
@@ -0,0 +1,50 @@
+package i18n
+
+// catalogs maps a language to its messages. Keys group messages by the command or
+// step that prints them; values are fmt format strings.
+var catalogs = map[string]map[string]string{
+	"en": {
+		"configs.copied":               "Configuration copy completed!",
+		"configs.copying":              "Copying agent configurations from host to container...",
+		"container.already_running":    "Container is already running",
+		"container.attach_hint":        "To attach to the container manually, run: docker exec -it %s /bin/bash",
+		"container.attaching_existing": "Attaching to existing container instead of creating a new one...",
+		"container.found_existing":     "Found existing container: %s",
+		"container.resuming":           "Resuming container: %s",
+		"container.running":            "Container %s is running",
+		"container.started":            "Container %s started successfully!",
+		"container.starting":           "Starting %s Agent Sandbox container: %s",
+		"container.starting_stopped":   "Starting stopped container: %s",
+		"container.unpausing":          "Unpausing container: %s",
+		"container.subdirectory":       "Using the container for %s from its subdirectory",
+		"languages.detected":           "Detected languages: %s",
+		"ports.exposing":               "Exposing ports:",
+		"postmortem.saved":             "Post-mortem saved to %s",
+		"session.name":                 "Session name: %s",
+		"session.resume_hint":          "Run 'agentsandbox --continue' to resume the session.",
+		"session.time_limit":           "Session stopped after reaching its %s time limit",
+		"template.using":               "Using template: %s (%s)",
+	},
+	"vi": {
+		"configs.copied":               "Đã sao chép cấu hình xong!",
+		"configs.copying":              "Đang sao chép cấu hình agent từ máy chủ vào container...",
+		"container.already_running":    "Container đã đang chạy",
+		"container.attach_hint":        "Để tự gắn vào container, hãy chạy: docker exec -it %s /bin/bash",
+		"container.attaching_existing": "Gắn vào container hiện có thay vì tạo container mới...",
+		"container.found_existing":     "Đã tìm thấy container hiện có: %s",
+		"container.resuming":           "Đang tiếp tục container: %s",
+		"container.running":            "Container %s đang chạy",
+		"container.started":            "Đã khởi động container %s thành công!",
+		"container.starting":           "Đang khởi động container Agent Sandbox cho %s: %s",
+		"container.starting_stopped":   "Đang khởi động container đã dừng: %s",
+		"container.unpausing":          "Đang tiếp tục container đã tạm dừng: %s",
+		"container.subdirectory":       "Dùng container của %s từ thư mục con",
+		"languages.detected":           "Ngôn ngữ phát hiện được: %s",
+		"ports.exposing":               "Mở các cổng:",
+		"postmortem.saved":             "Đã lưu báo cáo sự cố vào %s",
+		"session.name":                 "Tên phiên: %s",
+		"session.resume_hint":          "Chạy 'agentsandbox --continue' để tiếp tục phiên.",
+		"session.time_limit":           "Phiên đã dừng khi đạt giới hạn thời gian %s",
+		"template.using":               "Dùng mẫu: %s (%s)",
+	},
+}
@@ -0,0 +1,55 @@
+package image
+
+import (
+	"regexp"
+	"strings"
+)
+
+const defaultBaseImage = "ubuntu:22.04"
+
+// baseImageAliases maps the supported base_image setting values to image references
+var baseImageAliases = map[string]string{
+	"":            defaultBaseImage,
+	"ubuntu":      defaultBaseImage,
+	"slim":        "debian:bookworm-slim",
+	"debian-slim": "debian:bookworm-slim",
+}
+
+var nonTagChars = regexp.MustCompile(`[^a-z0-9]+`)
+
+// resolveBaseImage turns a base_image setting into an image reference.
+// Unknown values are used verbatim and must be apt-based (Debian or Ubuntu).
+func resolveBaseImage(setting string) string {
+	setting = strings.TrimSpace(setting)
+	if image, ok := baseImageAliases[strings.ToLower(setting)]; ok {
+		return image
+	}
+	return setting
+}
+
+// isSlimBaseImage reports whether the image is a minimal variant that should get a reduced package set
+func isSlimBaseImage(image string) bool {
+	return strings.Contains(image, "slim") || strings.Contains(image, "minimal")
+}
+
+// basePackages returns the apt packages installed on top of the base image
+func basePackages(image string) []string {
+	// locales and tzdata let the sandbox use the host's locale and timezone; iproute2
+	// provides tc for the bandwidth limit
+	packages := []string{"curl", "wget", "git", "openssh-client", "sudo", "ca-certificates", "locales", "tzdata", "iproute2"}
+	if isSlimBaseImage(image) {
+		return append(packages, "procps", "less")
+	}
+	return append(packages, "vim", "build-essential")
+}
+
+// baseImageTagSuffix returns the image tag suffix for non-default base images
+func baseImageTagSuffix(image string) string {
+	if image == defaultBaseImage {
+		return ""
+	}
+	if image == baseImageAliases["slim"] {
+		return "slim"
+	}
+	return strings.Trim(nonTagChars.ReplaceAllString(strings.ToLower(image), "-"), "-")
+}
@@ -0,0 +1,49 @@
+package image
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestResolveBaseImage(t *testing.T) {
+	tests := []struct {
+		setting string
+		want    string
+	}{
+		{"", "ubuntu:22.04"},
+		{"ubuntu", "ubuntu:22.04"},
+		{"slim", "debian:bookworm-slim"},
+		{"Slim", "debian:bookworm-slim"},
+		{"ubuntu:24.04", "ubuntu:24.04"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.setting, func(t *testing.T) {
+			if got := resolveBaseImage(tt.setting); got != tt.want {
+				t.Errorf("resolveBaseImage(%q) = %v, want %v", tt.setting, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestSpecTagBaseImage(t *testing.T) {
+	if got := (Spec{}).Tag(); got != "base" {
+		t.Errorf("Tag() = %v, want base", got)
+	}
+	if got := (Spec{BaseImage: "slim"}).Tag(); got != "base-slim" {
+		t.Errorf("Tag() = %v, want base-slim", got)
+	}
+	if got := (Spec{BaseImage: "ubuntu:24.04"}).Tag(); got != "base-ubuntu-24-04" {
+		t.Errorf("Tag() = %v, want base-ubuntu-24-04", got)
+	}
+}
+
+func TestGenerateSlim(t *testing.T) {
+	content := Generate(Spec{Username: "dev", BaseImage: "slim"}, 1000, 1000)
+	if !strings.HasPrefix(content, "FROM debian:bookworm-slim\n") {
+		t.Errorf("Generate() should start from debian:bookworm-slim, got %q", strings.SplitN(content, "\n", 2)[0])
+	}
+	if strings.Contains(content, "build-essential") {
+		t.Error("Generate() should not install build-essential on slim images")
+	}
+}
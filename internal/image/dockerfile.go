@@ -0,0 +1,168 @@
+package image
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+// baseLayerTemplate installs the OS packages and creates the sandbox user
+const baseLayerTemplate = `FROM %s
+
+ENV DEBIAN_FRONTEND=noninteractive
+
+# Install basic tools
+RUN apt-get update && apt-get install -y --no-install-recommends \
+%s
+    && rm -rf /var/lib/apt/lists/*
+
+# Create user
+ARG USERNAME=%s
+ARG USER_UID=%d
+ARG USER_GID=%d
+
+RUN set -e; \
+    if ! getent group $USER_GID >/dev/null; then \
+        groupadd --gid $USER_GID $USERNAME; \
+    fi && \
+    useradd --uid $USER_UID --gid $USER_GID -m -s /bin/bash $USERNAME%s
+
+# Add Go to PATH if installed
+ENV PATH="/usr/local/go/bin:${PATH}"
+`
+
+// langLayerTemplate installs the project's language toolchains
+const langLayerTemplate = `# Set automatically by BuildKit; empty with the legacy builder
+ARG TARGETARCH
+
+# Language toolchains (inserted dynamically)
+%s
+`
+
+// agentLayerTemplate installs the AI agents as the sandbox user
+const agentLayerTemplate = `# Install AI agents (with cache busting args)
+ARG CLAUDE_CACHE_BUST
+ARG GEMINI_CACHE_BUST
+ARG CODEX_CACHE_BUST
+ARG QWEN_CACHE_BUST
+ARG CURSOR_CACHE_BUST
+%s
+USER %s
+WORKDIR /home/%s
+
+# Install Claude
+RUN curl -fsSL https://claude.ai/install.sh | bash || true
+%s
+CMD ["/bin/bash"]
+`
+
+// nodeForAgents installs Node.js for npm-distributed agents when no toolchain layer provided it
+const nodeForAgents = `RUN command -v node >/dev/null 2>&1 || (curl -fsSL https://deb.nodesource.com/setup_22.x | bash - \
+    && apt-get install -y nodejs && rm -rf /var/lib/apt/lists/*)`
+
+// browserTools installs Chromium with the system libraries and fonts Playwright and
+// Puppeteer need. Browsers go to a shared path so the sandbox user can use them and
+// add the revision a project's Playwright version expects.
+const browserTools = `# Headless browser testing
+ENV PLAYWRIGHT_BROWSERS_PATH=/ms-playwright
+` + nodeForAgents + `
+RUN npx -y playwright@latest install --with-deps chromium \
+    && apt-get install -y --no-install-recommends fonts-noto-color-emoji xvfb \
+    && rm -rf /var/lib/apt/lists/* \
+    && chmod -R a+rwX /ms-playwright`
+
+// dockerCLI installs the static docker client and the compose plugin; the daemon comes
+// from the host's socket or a Docker-in-Docker sidecar
+const dockerCLI = `# Docker client
+RUN ARCH=$(uname -m) \
+    && curl -fsSL "https://download.docker.com/linux/static/stable/${ARCH}/docker-27.3.1.tgz" \
+        | tar -xz -C /usr/local/bin --strip-components=1 docker/docker \
+    && mkdir -p /usr/local/lib/docker/cli-plugins \
+    && curl -fsSL -o /usr/local/lib/docker/cli-plugins/docker-compose \
+        "https://github.com/docker/compose/releases/download/v2.29.7/docker-compose-linux-${ARCH}" \
+    && chmod +x /usr/local/lib/docker/cli-plugins/docker-compose`
+
+// kubernetesTools installs kubectl, helm and kind for agents working on a test cluster
+const kubernetesTools = `# Kubernetes tooling
+RUN ARCH=$(dpkg --print-architecture) \
+    && curl -fsSL -o /usr/local/bin/kubectl \
+        "https://dl.k8s.io/release/$(curl -fsSL https://dl.k8s.io/release/stable.txt)/bin/linux/${ARCH}/kubectl" \
+    && curl -fsSL -o /usr/local/bin/kind "https://kind.sigs.k8s.io/dl/v0.24.0/kind-linux-${ARCH}" \
+    && chmod +x /usr/local/bin/kubectl /usr/local/bin/kind \
+    && curl -fsSL https://raw.githubusercontent.com/helm/helm/main/scripts/get-helm-3 | bash`
+
+// agentNpmPackages lists agents distributed through npm
+var agentNpmPackages = map[config.Agent]string{
+	config.AgentGemini: "@google/gemini-cli",
+	config.AgentCodex:  "@openai/codex",
+	config.AgentQwen:   "@qwen-code/qwen-code",
+}
+
+// baseLayerBody renders the base layer Dockerfile
+func baseLayerBody(spec Spec, uid, gid int) string {
+	baseImage := resolveBaseImage(spec.BaseImage)
+	var packageLines []string
+	for _, pkg := range basePackages(baseImage) {
+		packageLines = append(packageLines, fmt.Sprintf("    %s \\", pkg))
+	}
+
+	return fmt.Sprintf(baseLayerTemplate, baseImage, strings.Join(packageLines, "\n"), spec.Username, uid, gid, spec.sudoersStep())
+}
+
+// langLayerBody renders the toolchain layer without its FROM line
+func langLayerBody(spec Spec) string {
+	var languageInstalls []string
+	for _, lang := range spec.Languages {
+		cmd := lang.DockerfileInstallCmd()
+		if cmd != "" {
+			languageInstalls = append(languageInstalls, cmd)
+		}
+	}
+	if spec.Browser {
+		languageInstalls = append(languageInstalls, browserTools)
+	}
+	if spec.DockerCLI {
+		languageInstalls = append(languageInstalls, dockerCLI)
+	}
+	if spec.Kubernetes {
+		languageInstalls = append(languageInstalls, kubernetesTools)
+	}
+	if spec.Template != nil && spec.Template.DockerfileFragment != "" {
+		languageInstalls = append(languageInstalls,
+			fmt.Sprintf("# Template: %s\n%s", spec.Template.Name, spec.Template.DockerfileFragment))
+	}
+	languageSection := strings.Join(languageInstalls, "\n\n")
+	if languageSection == "" {
+		languageSection = "# No language toolchains detected"
+	}
+
+	return fmt.Sprintf(langLayerTemplate, languageSection)
+}
+
+// agentLayerBody renders the agent layer without its FROM line
+func agentLayerBody(spec Spec) string {
+	rootSteps := ""
+	userSteps := ""
+
+	if pkg, ok := agentNpmPackages[spec.Agent]; ok {
+		rootSteps = fmt.Sprintf("\n# Install %s\n%s\nRUN npm install -g %s\n", spec.Agent.DisplayName(), nodeForAgents, pkg)
+	} else if spec.Agent == config.AgentCursor {
+		userSteps = "\n# Install Cursor\nRUN curl -fsSL https://cursor.com/install | bash || true\n"
+	}
+
+	return fmt.Sprintf(agentLayerTemplate, rootSteps, spec.Username, spec.Username, userSteps)
+}
+
+// Generate renders the complete single-stage Dockerfile for the spec
+func Generate(spec Spec, uid, gid int) string {
+	if spec.CustomDockerfile != "" {
+		return spec.CustomDockerfile
+	}
+
+	return strings.Join([]string{
+		baseLayerBody(spec, uid, gid),
+		langLayerBody(spec),
+		agentLayerBody(spec),
+	}, "\n")
+}
@@ -0,0 +1,188 @@
+package image
+
+import (
+	"crypto/sha256"
+	"fmt"
+	"io"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strconv"
+	"strings"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/crash"
+)
+
+const layerHashLabel = "agentsandbox.layer.hash"
+
+// Layer is one image in the base → language → agent chain
+type Layer struct {
+	Kind       string
+	Ref        string
+	Dockerfile string
+}
+
+// Plan returns the layers needed for the spec, parents first
+func Plan(spec Spec, uid, gid int) []Layer {
+	if spec.CustomDockerfile != "" {
+		return []Layer{{Kind: "custom", Ref: spec.Ref(), Dockerfile: spec.CustomDockerfile}}
+	}
+
+	baseRef := "agentsandbox-base:" + spec.baseTag()
+	langRef := "agentsandbox-lang:" + spec.langTag()
+
+	return []Layer{
+		{Kind: "base", Ref: baseRef, Dockerfile: baseLayerBody(spec, uid, gid)},
+		{Kind: "language", Ref: langRef, Dockerfile: fmt.Sprintf("FROM %s\n\n%s", baseRef, langLayerBody(spec))},
+		{Kind: "agent", Ref: spec.Ref(), Dockerfile: fmt.Sprintf("FROM %s\n\n%s", langRef, agentLayerBody(spec))},
+	}
+}
+
+// HostIDs returns the host user's UID and GID
+func HostIDs() (string, string, error) {
+	uidOutput, err := exec.Command("id", "-u").Output()
+	if err != nil {
+		return "", "", fmt.Errorf("failed to get host UID: %w", err)
+	}
+
+	gidOutput, err := exec.Command("id", "-g").Output()
+	if err != nil {
+		return "", "", fmt.Errorf("failed to get host GID: %w", err)
+	}
+
+	return strings.TrimSpace(string(uidOutput)), strings.TrimSpace(string(gidOutput)), nil
+}
+
+// Build makes sure every layer of the spec's image is up to date and returns the final image reference.
+// A layer is rebuilt only when its Dockerfile or its parent layer changed. Build
+// progress, including docker build's own output, is written to out.
+func Build(spec Spec, out io.Writer) (string, error) {
+	uid, gid, err := HostIDs()
+	if err != nil {
+		return "", err
+	}
+	uidNum, _ := strconv.Atoi(uid)
+	gidNum, _ := strconv.Atoi(gid)
+
+	buildArgs := []string{
+		"--build-arg", fmt.Sprintf("USERNAME=%s", spec.Username),
+		"--build-arg", fmt.Sprintf("USER_UID=%s", uid),
+		"--build-arg", fmt.Sprintf("USER_GID=%s", gid),
+	}
+
+	if spec.CustomDockerfile != "" {
+		fmt.Fprintf(out, "Using custom Dockerfile from %s\n", config.ProjectDirName)
+	} else if len(spec.Languages) > 0 {
+		names := make([]string, len(spec.Languages))
+		for i, l := range spec.Languages {
+			names[i] = l.Name()
+		}
+		fmt.Fprintf(out, "Including toolchains: %s\n", strings.Join(names, ", "))
+	}
+
+	layers := Plan(spec, uidNum, gidNum)
+	parentID := ""
+	for _, layer := range layers {
+		id, err := ensureLayer(out, layer, parentID, spec.Platform, buildArgs, spec.Cache)
+		if err != nil {
+			return "", err
+		}
+		parentID = id
+	}
+
+	return layers[len(layers)-1].Ref, nil
+}
+
+// inspectLayer returns the image ID and layer hash label of an existing image
+func inspectLayer(ref string) (string, string, bool) {
+	output, err := exec.Command("docker", "image", "inspect", "-f",
+		fmt.Sprintf("{{.Id}}|{{index .Config.Labels %q}}", layerHashLabel), ref).Output()
+	if err != nil {
+		return "", "", false
+	}
+
+	parts := strings.SplitN(strings.TrimSpace(string(output)), "|", 2)
+	if len(parts) != 2 {
+		return "", "", false
+	}
+	return parts[0], parts[1], true
+}
+
+// cacheRef returns the registry reference a layer is cached under,
+// e.g. agentsandbox-lang:go -> <registry ref>:lang-go
+func cacheRef(cache config.RegistryCache, layer Layer) string {
+	tag := strings.TrimPrefix(layer.Ref, "agentsandbox-")
+	tag = strings.ReplaceAll(tag, ":", "-")
+	return fmt.Sprintf("%s:%s", strings.TrimSuffix(cache.Ref, "/"), tag)
+}
+
+// pushCache publishes a freshly built layer to the registry cache
+func pushCache(out io.Writer, layer Layer, remote string) {
+	if err := exec.Command("docker", "tag", layer.Ref, remote).Run(); err != nil {
+		fmt.Fprintf(out, "Warning: failed to tag %s for the registry cache: %v\n", layer.Ref, err)
+		return
+	}
+
+	fmt.Fprintf(out, "Pushing %s layer to registry cache: %s\n", layer.Kind, remote)
+	cmd := exec.Command("docker", "push", "--quiet", remote)
+	if output, err := cmd.CombinedOutput(); err != nil {
+		fmt.Fprintf(out, "Warning: failed to push %s: %v\n%s", remote, err, string(output))
+	}
+}
+
+// ensureLayer builds a layer unless an image with a matching hash already exists
+func ensureLayer(out io.Writer, layer Layer, parentID, platform string, buildArgs []string, cache config.RegistryCache) (string, error) {
+	sum := sha256.Sum256([]byte(layer.Dockerfile + parentID + platform))
+	hash := fmt.Sprintf("%x", sum[:8])
+
+	if id, existing, ok := inspectLayer(layer.Ref); ok && existing == hash {
+		fmt.Fprintf(out, "Using cached %s layer: %s\n", layer.Kind, layer.Ref)
+		return id, nil
+	}
+
+	buildDir, err := os.MkdirTemp("", "agentsandbox-build-")
+	if err != nil {
+		return "", fmt.Errorf("failed to create build directory: %w", err)
+	}
+	defer os.RemoveAll(buildDir)
+
+	dockerfilePath := filepath.Join(buildDir, "Dockerfile")
+	if err := os.WriteFile(dockerfilePath, []byte(layer.Dockerfile), 0o644); err != nil {
+		return "", fmt.Errorf("failed to write Dockerfile: %w", err)
+	}
+
+	args := []string{"build", "-t", layer.Ref, "--label", fmt.Sprintf("%s=%s", layerHashLabel, hash)}
+	args = append(args, buildArgs...)
+	args = append(args, "-f", dockerfilePath)
+	remote := ""
+	if cache.Ref != "" {
+		// Inline cache metadata lets other machines reuse the layers with --cache-from
+		remote = cacheRef(cache, layer)
+		args = append(args, "--cache-from", remote, "--build-arg", "BUILDKIT_INLINE_CACHE=1")
+	}
+	if platform != "" {
+		args = append(args, "--platform", platform)
+	}
+	args = append(args, buildDir)
+
+	fmt.Fprintf(out, "Building %s layer: %s\n", layer.Kind, layer.Ref)
+	crash.RecordDockerCommand(args)
+	cmd := exec.Command("docker", args...)
+	cmd.Stdout = out
+	cmd.Stderr = out
+
+	if err := cmd.Run(); err != nil {
+		return "", fmt.Errorf("Docker build failed for %s layer: %w", layer.Kind, err)
+	}
+
+	if remote != "" && cache.Push {
+		pushCache(out, layer, remote)
+	}
+
+	id, _, ok := inspectLayer(layer.Ref)
+	if !ok {
+		return "", fmt.Errorf("built %s layer %s could not be inspected", layer.Kind, layer.Ref)
+	}
+	return id, nil
+}
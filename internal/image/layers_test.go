@@ -0,0 +1,70 @@
+package image
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+func TestPlanLayers(t *testing.T) {
+	spec := Spec{Username: "dev", Agent: config.AgentCodex}
+	layers := Plan(spec, 1000, 1000)
+	if len(layers) != 3 {
+		t.Fatalf("Plan() returned %d layers, want 3", len(layers))
+	}
+
+	wantRefs := []string{"agentsandbox-base:ubuntu", "agentsandbox-lang:base", "agentsandbox-image:base-codex"}
+	for i, want := range wantRefs {
+		if layers[i].Ref != want {
+			t.Errorf("layer %d ref = %v, want %v", i, layers[i].Ref, want)
+		}
+	}
+	if !strings.HasPrefix(layers[2].Dockerfile, "FROM agentsandbox-lang:base\n") {
+		t.Errorf("agent layer should build FROM the language layer, got %q", strings.SplitN(layers[2].Dockerfile, "\n", 2)[0])
+	}
+	if !strings.Contains(layers[2].Dockerfile, "@openai/codex") {
+		t.Error("agent layer should install the codex npm package")
+	}
+
+	custom := Plan(Spec{CustomDockerfile: "FROM scratch\n"}, 1000, 1000)
+	if len(custom) != 1 || custom[0].Kind != "custom" {
+		t.Errorf("Plan() with a custom Dockerfile should return a single custom layer, got %+v", custom)
+	}
+}
+
+func TestCacheRef(t *testing.T) {
+	cache := config.RegistryCache{Ref: "ghcr.io/acme/agentsandbox-cache/"}
+	layer := Layer{Ref: "agentsandbox-lang:go-nodejs"}
+
+	want := "ghcr.io/acme/agentsandbox-cache:lang-go-nodejs"
+	if got := cacheRef(cache, layer); got != want {
+		t.Errorf("cacheRef() = %v, want %v", got, want)
+	}
+}
+
+func TestGenerateBrowser(t *testing.T) {
+	spec := Spec{Username: "dev", Browser: true}
+	if !strings.Contains(Generate(spec, 1000, 1000), "playwright@latest install --with-deps chromium") {
+		t.Error("Generate() should install Chromium when Browser is set")
+	}
+	if strings.Contains(Generate(Spec{Username: "dev"}, 1000, 1000), "ms-playwright") {
+		t.Error("Generate() should leave browsers out by default")
+	}
+	if got := spec.Tag(); got != "base-browser" {
+		t.Errorf("Tag() = %v, want base-browser", got)
+	}
+}
+
+func TestGenerateKubernetes(t *testing.T) {
+	spec := Spec{Username: "dev", Kubernetes: true}
+	dockerfile := Generate(spec, 1000, 1000)
+	for _, tool := range []string{"bin/linux/${ARCH}/kubectl", "kind-linux-${ARCH}", "get-helm-3"} {
+		if !strings.Contains(dockerfile, tool) {
+			t.Errorf("Generate() should install %s when Kubernetes is set", tool)
+		}
+	}
+	if got := spec.Tag(); got != "base-k8s" {
+		t.Errorf("Tag() = %v, want base-k8s", got)
+	}
+}
@@ -0,0 +1,55 @@
+package image
+
+import (
+	"fmt"
+	"os/exec"
+	"strings"
+)
+
+// normalizeArch maps kernel and Docker architecture names to Go/OCI names
+func normalizeArch(arch string) string {
+	switch strings.ToLower(strings.TrimSpace(arch)) {
+	case "x86_64", "amd64":
+		return "amd64"
+	case "aarch64", "arm64", "arm64/v8":
+		return "arm64"
+	case "armv7l", "arm":
+		return "arm"
+	default:
+		return strings.ToLower(strings.TrimSpace(arch))
+	}
+}
+
+// DockerArchitecture returns the architecture of the Docker daemon (amd64, arm64, ...)
+func DockerArchitecture() (string, error) {
+	output, err := exec.Command("docker", "info", "--format", "{{.Architecture}}").Output()
+	if err != nil {
+		return "", fmt.Errorf("failed to query docker architecture: %w", err)
+	}
+	return normalizeArch(string(output)), nil
+}
+
+// platformArch returns the architecture component of a platform string such as linux/arm64
+func platformArch(platform string) string {
+	parts := strings.Split(platform, "/")
+	if len(parts) < 2 {
+		return normalizeArch(platform)
+	}
+	return normalizeArch(strings.Join(parts[1:], "/"))
+}
+
+// ResolvePlatform validates the configured platform and warns when it requires emulation
+func ResolvePlatform(platform string) string {
+	platform = strings.TrimSpace(platform)
+	if platform == "" {
+		return ""
+	}
+	if !strings.Contains(platform, "/") {
+		platform = "linux/" + platform
+	}
+
+	if daemonArch, err := DockerArchitecture(); err == nil && daemonArch != platformArch(platform) {
+		fmt.Printf("Warning: building for %s on a %s Docker host; the sandbox will run under emulation\n", platform, daemonArch)
+	}
+	return platform
+}
@@ -0,0 +1,24 @@
+package image
+
+import "testing"
+
+func TestPlatformArch(t *testing.T) {
+	tests := []struct {
+		platform string
+		want     string
+	}{
+		{"linux/amd64", "amd64"},
+		{"linux/arm64", "arm64"},
+		{"linux/arm64/v8", "arm64"},
+		{"aarch64", "arm64"},
+		{"x86_64", "amd64"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.platform, func(t *testing.T) {
+			if got := platformArch(tt.platform); got != tt.want {
+				t.Errorf("platformArch(%q) = %v, want %v", tt.platform, got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,106 @@
+package image
+
+import (
+	"crypto/sha256"
+	"fmt"
+	"strings"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/language"
+	"github.com/thaodangspace/agentsandbox/internal/templates"
+)
+
+// Spec describes the inputs that determine a generated sandbox image
+type Spec struct {
+	Username  string
+	Agent     config.Agent
+	Languages []language.Language
+	Template  *templates.Template
+	// Browser adds Chromium and the libraries headless browser tests need
+	Browser bool
+	// DockerCLI adds the docker client and compose plugin for sandboxes given a daemon
+	DockerCLI bool
+	// Kubernetes adds kubectl, helm and kind
+	Kubernetes bool
+	// BaseImage is the base_image setting: "ubuntu" (default), "slim", or an apt-based image reference
+	BaseImage string
+	// Sudo is the sudo setting: "full" (default), "none" or "targeted"
+	Sudo string
+	// SudoCommands are the commands allowed without a password when Sudo is "targeted"
+	SudoCommands []string
+	// Platform is the target platform passed to docker build (e.g. linux/arm64); empty means native
+	Platform string
+	// CustomDockerfile replaces the generated Dockerfile when set (from .agentsandbox/Dockerfile)
+	CustomDockerfile string
+	// Cache is the shared registry build cache; it does not affect the image contents
+	Cache config.RegistryCache
+}
+
+// WantsBrowser applies the browser_testing setting to the project in dir
+func WantsBrowser(setting, dir string) (bool, error) {
+	switch setting {
+	case "", "auto":
+		return language.UsesBrowserTesting(dir), nil
+	case "on":
+		return true, nil
+	case "off":
+		return false, nil
+	}
+	return false, fmt.Errorf("invalid browser_testing setting %q: use auto, on or off", setting)
+}
+
+// baseTag returns the tag of the base layer
+func (s Spec) baseTag() string {
+	tag := baseImageTagSuffix(resolveBaseImage(s.BaseImage))
+	if tag == "" {
+		tag = "ubuntu"
+	}
+	return tag + s.sudoSuffix() + s.archSuffix()
+}
+
+// langTag returns the tag of the toolchain layer
+func (s Spec) langTag() string {
+	tag := language.GenerateImageTag(s.Languages)
+	if suffix := baseImageTagSuffix(resolveBaseImage(s.BaseImage)); suffix != "" {
+		tag += "-" + suffix
+	}
+	if s.Browser {
+		tag += "-browser"
+	}
+	if s.DockerCLI {
+		tag += "-docker"
+	}
+	if s.Kubernetes {
+		tag += "-k8s"
+	}
+	if s.Template != nil {
+		tag += "-" + s.Template.Name
+	}
+	return tag + s.sudoSuffix() + s.archSuffix()
+}
+
+func (s Spec) archSuffix() string {
+	if s.Platform == "" {
+		return ""
+	}
+	return "-" + strings.ReplaceAll(platformArch(s.Platform), "/", "")
+}
+
+// Tag returns the cache tag of the final image built from this spec
+func (s Spec) Tag() string {
+	if s.CustomDockerfile != "" {
+		sum := sha256.Sum256([]byte(s.CustomDockerfile + s.Platform))
+		return fmt.Sprintf("custom-%x", sum[:6])
+	}
+
+	tag := s.langTag()
+	if s.Agent != "" && s.Agent != config.AgentClaude {
+		tag += "-" + string(s.Agent)
+	}
+	return tag
+}
+
+// Ref returns the full reference of the final image built from this spec
+func (s Spec) Ref() string {
+	return "agentsandbox-image:" + s.Tag()
+}
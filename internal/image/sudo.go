@@ -0,0 +1,71 @@
+package image
+
+import (
+	"crypto/sha256"
+	"fmt"
+	"path"
+	"strings"
+)
+
+// Sudo modes for the sudo setting
+const (
+	// SudoFull grants the sandbox user passwordless sudo for everything (the default)
+	SudoFull = "full"
+	// SudoNone leaves the sandbox user without sudo
+	SudoNone = "none"
+	// SudoTargeted allows passwordless sudo for the sudo_commands list only
+	SudoTargeted = "targeted"
+)
+
+// defaultSudoCommands are allowed in targeted mode when sudo_commands is empty
+var defaultSudoCommands = []string{"/usr/bin/apt-get"}
+
+// ValidateSudo checks the sudo and sudo_commands settings
+func ValidateSudo(mode string, commands []string) error {
+	switch mode {
+	case "", SudoFull, SudoNone:
+		return nil
+	case SudoTargeted:
+		for _, command := range commands {
+			// sudoers separates commands with commas and arguments with spaces
+			if !path.IsAbs(command) || strings.ContainsAny(command, ",:=\\\"'\n\t ") {
+				return fmt.Errorf("invalid sudo command %q: use an absolute path without arguments", command)
+			}
+		}
+		return nil
+	}
+	return fmt.Errorf("invalid sudo setting %q: use full, none or targeted", mode)
+}
+
+func (s Spec) sudoCommands() []string {
+	if len(s.SudoCommands) == 0 {
+		return defaultSudoCommands
+	}
+	return s.SudoCommands
+}
+
+// sudoersStep returns the shell appended to the user creation step to write the sudoers rule
+func (s Spec) sudoersStep() string {
+	var rule string
+	switch s.Sudo {
+	case SudoNone:
+		return ""
+	case SudoTargeted:
+		rule = "$USERNAME ALL=(root) NOPASSWD: " + strings.Join(s.sudoCommands(), ", ")
+	default:
+		rule = "$USERNAME ALL=(ALL) NOPASSWD:ALL"
+	}
+	return fmt.Sprintf(" && \\\n    echo \"%s\" >> /etc/sudoers", rule)
+}
+
+// sudoSuffix keeps images built with different sudo rules apart
+func (s Spec) sudoSuffix() string {
+	switch s.Sudo {
+	case SudoNone:
+		return "-nosudo"
+	case SudoTargeted:
+		sum := sha256.Sum256([]byte(strings.Join(s.sudoCommands(), "\n")))
+		return fmt.Sprintf("-sudo%x", sum[:3])
+	}
+	return ""
+}
@@ -0,0 +1,53 @@
+package image
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestGenerateSudo(t *testing.T) {
+	full := Generate(Spec{Username: "dev"}, 1000, 1000)
+	if !strings.Contains(full, `echo "$USERNAME ALL=(ALL) NOPASSWD:ALL" >> /etc/sudoers`) {
+		t.Error("Generate() should grant full sudo by default")
+	}
+
+	none := Generate(Spec{Username: "dev", Sudo: SudoNone}, 1000, 1000)
+	if strings.Contains(none, "/etc/sudoers") {
+		t.Error("Generate() should not write a sudoers rule with sudo none")
+	}
+
+	targeted := Generate(Spec{Username: "dev", Sudo: SudoTargeted, SudoCommands: []string{"/usr/bin/apt-get", "/usr/bin/dpkg"}}, 1000, 1000)
+	if !strings.Contains(targeted, `echo "$USERNAME ALL=(root) NOPASSWD: /usr/bin/apt-get, /usr/bin/dpkg" >> /etc/sudoers`) {
+		t.Errorf("Generate() should limit sudo to the listed commands, got:\n%s", targeted)
+	}
+}
+
+func TestSpecTagSudo(t *testing.T) {
+	if got := (Spec{Sudo: SudoFull}).Tag(); got != "base" {
+		t.Errorf("Tag() = %v, want base", got)
+	}
+	if got := (Spec{Sudo: SudoNone}).Tag(); got != "base-nosudo" {
+		t.Errorf("Tag() = %v, want base-nosudo", got)
+	}
+	apt := Spec{Sudo: SudoTargeted}.Tag()
+	dpkg := Spec{Sudo: SudoTargeted, SudoCommands: []string{"/usr/bin/dpkg"}}.Tag()
+	if !strings.HasPrefix(apt, "base-sudo") || apt == dpkg {
+		t.Errorf("targeted tags should depend on the command list, got %v and %v", apt, dpkg)
+	}
+}
+
+func TestValidateSudo(t *testing.T) {
+	for _, mode := range []string{"", SudoFull, SudoNone, SudoTargeted} {
+		if err := ValidateSudo(mode, nil); err != nil {
+			t.Errorf("ValidateSudo(%q) = %v", mode, err)
+		}
+	}
+	if err := ValidateSudo("some", nil); err == nil {
+		t.Error("expected an unknown mode to be rejected")
+	}
+	for _, command := range []string{"apt-get", "/usr/bin/apt-get install", "/bin/true, ALL"} {
+		if err := ValidateSudo(SudoTargeted, []string{command}); err == nil {
+			t.Errorf("expected sudo command %q to be rejected", command)
+		}
+	}
+}
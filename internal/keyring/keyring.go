@@ -0,0 +1,59 @@
+package keyring
+
+import (
+	"errors"
+	"fmt"
+	"os/exec"
+	"runtime"
+	"strings"
+)
+
+// service is the name secrets are stored under in the OS keyring
+const service = "agentsandbox"
+
+// ErrNotFound is returned by Get when no secret is stored for the account
+var ErrNotFound = errors.New("secret not found in keyring")
+
+// Get returns the secret stored for account. It uses the macOS keychain through
+// security(1) and the Secret Service (GNOME Keyring, KWallet) through secret-tool(1).
+func Get(account string) (string, error) {
+	var cmd *exec.Cmd
+	switch runtime.GOOS {
+	case "darwin":
+		cmd = exec.Command("security", "find-generic-password", "-s", service, "-a", account, "-w")
+	case "linux":
+		cmd = exec.Command("secret-tool", "lookup", "service", service, "account", account)
+	default:
+		return "", fmt.Errorf("no supported keyring on %s", runtime.GOOS)
+	}
+
+	output, err := cmd.Output()
+	secret := strings.TrimSpace(string(output))
+	if errors.Is(err, exec.ErrNotFound) {
+		return "", fmt.Errorf("no keyring available: %w", err)
+	}
+	if err != nil || secret == "" {
+		// Both tools exit non-zero when nothing is stored
+		return "", ErrNotFound
+	}
+	return secret, nil
+}
+
+// Set stores secret for account, replacing any previous value
+func Set(account, secret string) error {
+	var cmd *exec.Cmd
+	switch runtime.GOOS {
+	case "darwin":
+		cmd = exec.Command("security", "add-generic-password", "-U", "-s", service, "-a", account, "-w", secret)
+	case "linux":
+		cmd = exec.Command("secret-tool", "store", "--label", "agentsandbox "+account, "service", service, "account", account)
+		cmd.Stdin = strings.NewReader(secret)
+	default:
+		return fmt.Errorf("no supported keyring on %s", runtime.GOOS)
+	}
+
+	if output, err := cmd.CombinedOutput(); err != nil {
+		return fmt.Errorf("failed to store %s in keyring: %w\nOutput: %s", account, err, string(output))
+	}
+	return nil
+}
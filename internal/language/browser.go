@@ -0,0 +1,38 @@
+package language
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+// browserConfigs are files that only exist in projects running browser tests
+var browserConfigs = []string{
+	"playwright.config.ts", "playwright.config.js", "playwright.config.mjs", "playwright.config.cjs",
+	".puppeteerrc.cjs", ".puppeteerrc.js", ".puppeteerrc.json", "puppeteer.config.cjs", "puppeteer.config.js",
+}
+
+// browserManifests are dependency files that mention Playwright or Puppeteer when the
+// project uses them, for Node and Python projects alike
+var browserManifests = []string{"package.json", "requirements.txt", "requirements-dev.txt", "pyproject.toml"}
+
+// UsesBrowserTesting reports whether the project in dir drives a headless browser
+// with Playwright or Puppeteer
+func UsesBrowserTesting(dir string) bool {
+	for _, file := range browserConfigs {
+		if _, err := os.Stat(filepath.Join(dir, file)); err == nil {
+			return true
+		}
+	}
+	for _, file := range browserManifests {
+		data, err := os.ReadFile(filepath.Join(dir, file))
+		if err != nil {
+			continue
+		}
+		content := string(data)
+		if strings.Contains(content, "playwright") || strings.Contains(content, "puppeteer") {
+			return true
+		}
+	}
+	return false
+}
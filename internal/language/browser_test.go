@@ -0,0 +1,28 @@
+package language
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestUsesBrowserTesting(t *testing.T) {
+	cases := map[string]struct {
+		file, content string
+		want          bool
+	}{
+		"playwright config": {"playwright.config.ts", "export default {}", true},
+		"node dependency":   {"package.json", `{"devDependencies": {"puppeteer": "^22.0.0"}}`, true},
+		"python dependency": {"requirements.txt", "pytest\nplaywright==1.45.0\n", true},
+		"plain node":        {"package.json", `{"dependencies": {"express": "^4.0.0"}}`, false},
+	}
+	for name, tc := range cases {
+		dir := t.TempDir()
+		if err := os.WriteFile(filepath.Join(dir, tc.file), []byte(tc.content), 0644); err != nil {
+			t.Fatal(err)
+		}
+		if got := UsesBrowserTesting(dir); got != tc.want {
+			t.Errorf("%s: UsesBrowserTesting() = %v, want %v", name, got, tc.want)
+		}
+	}
+}
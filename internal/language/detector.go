@@ -114,7 +114,7 @@ func (l Language) DockerfileInstallCmd() string {
 		return `RUN apt-get update && apt-get install -y python3 python3-pip python3-venv \
     && rm -rf /var/lib/apt/lists/*`
 	case LanguageGo:
-		return `RUN ARCH=$(dpkg --print-architecture) && \
+		return `RUN ARCH=${TARGETARCH:-$(dpkg --print-architecture)} && \
     if [ "$ARCH" = "arm64" ]; then GO_ARCH="arm64"; else GO_ARCH="amd64"; fi && \
     wget -q https://go.dev/dl/go1.24.5.linux-${GO_ARCH}.tar.gz && \
     tar -C /usr/local -xzf go1.24.5.linux-${GO_ARCH}.tar.gz && \
@@ -179,36 +179,6 @@ func DetectProjectLanguages(dir string) []Language {
 	return languages
 }
 
-// EnsureLanguageTools checks for and installs missing language tools in the container
-func EnsureLanguageTools(containerName string, languages []Language) error {
-	for _, lang := range languages {
-		tool := lang.Tool()
-		if tool == "" {
-			continue
-		}
-
-		// Check if tool exists
-		checkCmd := exec.Command("docker", "exec", containerName, "bash", "-lc",
-			fmt.Sprintf("command -v %s", tool))
-		if err := checkCmd.Run(); err == nil {
-			// Tool already exists
-			continue
-		}
-
-		// Install the tool
-		fmt.Printf("Installing toolchain for %s...\n", lang.Name())
-		installCmd := exec.Command("docker", "exec", containerName, "bash", "-lc", lang.InstallCmd())
-		installCmd.Stdout = os.Stdout
-		installCmd.Stderr = os.Stderr
-
-		if err := installCmd.Run(); err != nil {
-			return fmt.Errorf("failed to install %s: %w", tool, err)
-		}
-	}
-
-	return nil
-}
-
 // SyncNodeModulesFromHost copies node_modules from host to container for Node.js projects
 func SyncNodeModulesFromHost(containerName string, projectDir string, languages []Language) error {
 	// Check if Node.js is in the detected languages
@@ -0,0 +1,73 @@
+package language
+
+import (
+	"fmt"
+	"strings"
+)
+
+// ShimDir holds the commands that install a missing toolchain on first use. It is
+// appended to PATH, so real binaries always take precedence.
+const ShimDir = "/usr/local/lib/agentsandbox/shims"
+
+// installerDir holds the per-language install scripts the shims run
+const installerDir = "/usr/local/lib/agentsandbox/install"
+
+// All returns every supported language
+func All() []Language {
+	return []Language{LanguageRust, LanguageNodeJS, LanguagePython, LanguageGo, LanguagePHP, LanguageRuby}
+}
+
+// Commands returns the commands that trigger installing the language when missing
+func (l Language) Commands() []string {
+	switch l {
+	case LanguageRust:
+		return []string{"cargo", "rustc", "rustup"}
+	case LanguageNodeJS:
+		return []string{"node", "npm", "npx"}
+	case LanguagePython:
+		return []string{"python3", "pip3"}
+	case LanguageGo:
+		return []string{"go", "gofmt"}
+	case LanguagePHP:
+		return []string{"php", "composer"}
+	case LanguageRuby:
+		return []string{"ruby", "gem", "bundle"}
+	default:
+		return nil
+	}
+}
+
+// shimTemplate installs the toolchain once, serialised by a lock so parallel commands
+// do not race, then runs the real command with the shims off PATH
+const shimTemplate = `#!/bin/sh
+PATH=$(printf '%%s' "$PATH" | tr ':' '\n' | grep -vx '%[1]s' | paste -sd: -)
+PATH="$HOME/.cargo/bin:/usr/local/go/bin:$PATH"
+export PATH
+if ! command -v %[2]s >/dev/null 2>&1; then
+    exec 9>/tmp/agentsandbox-install-%[3]s.lock
+    flock 9
+    if ! command -v %[2]s >/dev/null 2>&1; then
+        echo "agentsandbox: installing %[4]s on first use of %[2]s..." >&2
+        bash %[5]s/%[3]s.sh >&2 || { echo "agentsandbox: failed to install %[4]s" >&2; exit 127; }
+    fi
+    exec 9>&-
+fi
+exec %[2]s "$@"
+`
+
+// ShimSetupScript returns a root shell script that installs shims for the commands of
+// languages, skipping commands that already exist
+func ShimSetupScript(languages []Language) string {
+	var b strings.Builder
+	fmt.Fprintf(&b, "set -e\nmkdir -p %s %s\n", ShimDir, installerDir)
+	fmt.Fprintf(&b, "echo 'export PATH=\"$PATH:%s\"' > /etc/profile.d/agentsandbox-shims.sh\n", ShimDir)
+	for _, lang := range languages {
+		fmt.Fprintf(&b, "cat > %s/%s.sh <<'AGENTSANDBOX_EOF'\n%s\nAGENTSANDBOX_EOF\n", installerDir, lang, lang.InstallCmd())
+		for _, cmd := range lang.Commands() {
+			shim := fmt.Sprintf(shimTemplate, ShimDir, cmd, lang, lang.Name(), installerDir)
+			fmt.Fprintf(&b, "if ! su - \"$1\" -c 'command -v %[1]s' >/dev/null 2>&1; then\ncat > %[2]s/%[1]s <<'AGENTSANDBOX_EOF'\n%[3]sAGENTSANDBOX_EOF\nchmod 755 %[2]s/%[1]s\nfi\n",
+				cmd, ShimDir, shim)
+		}
+	}
+	return b.String()
+}
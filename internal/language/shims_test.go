@@ -0,0 +1,27 @@
+package language
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestShimSetupScript(t *testing.T) {
+	script := ShimSetupScript([]Language{LanguageGo})
+
+	for _, want := range []string{
+		"/etc/profile.d/agentsandbox-shims.sh",
+		"cat > /usr/local/lib/agentsandbox/install/go.sh",
+		"go1.24.5.linux",
+		"cat > /usr/local/lib/agentsandbox/shims/go <<",
+		"cat > /usr/local/lib/agentsandbox/shims/gofmt <<",
+		`echo "agentsandbox: installing Go on first use of gofmt..."`,
+		`exec gofmt "$@"`,
+	} {
+		if !strings.Contains(script, want) {
+			t.Errorf("script missing %q:\n%s", want, script)
+		}
+	}
+	if strings.Contains(script, "shims/cargo") {
+		t.Error("script should only shim the given languages")
+	}
+}
@@ -0,0 +1,77 @@
+package logs
+
+import (
+	"bufio"
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+// AuditEntry is a single command recorded by the in-container audit hook
+type AuditEntry struct {
+	Timestamp string `json:"timestamp"`
+	PID       int    `json:"pid"`
+	PPID      int    `json:"ppid"`
+	Cwd       string `json:"cwd"`
+	Command   string `json:"command"`
+	// Malformed holds a line that is not a valid entry, such as one cut off by a
+	// killed shell or written by something other than the hook; the rest is empty
+	Malformed string `json:"-"`
+}
+
+// CommandCount returns how many entries are well-formed commands
+func CommandCount(entries []AuditEntry) int {
+	count := 0
+	for _, entry := range entries {
+		if entry.Malformed == "" {
+			count++
+		}
+	}
+	return count
+}
+
+// AuditSessionID returns the session identifier of an audit log file
+func AuditSessionID(auditFile string) string {
+	name := strings.TrimSuffix(filepath.Base(auditFile), ".jsonl")
+	return strings.TrimPrefix(name, "audit-")
+}
+
+// ParseAuditLog reads the commands recorded in an audit log. Lines that do not decode
+// are returned as Malformed entries in place, so they are shown rather than lost.
+func ParseAuditLog(auditFile string) ([]AuditEntry, error) {
+	file, err := os.Open(auditFile)
+	if err != nil {
+		return nil, err
+	}
+	defer file.Close()
+
+	var entries []AuditEntry
+	scanner := bufio.NewScanner(file)
+	scanner.Buffer(make([]byte, 0, 64*1024), 1024*1024)
+
+	for scanner.Scan() {
+		line := scanner.Text()
+		if line == "" {
+			continue
+		}
+
+		line, err := openLine(line)
+		if err != nil {
+			return nil, err
+		}
+
+		var entry AuditEntry
+		if err := json.Unmarshal([]byte(line), &entry); err != nil {
+			entry = AuditEntry{Malformed: line}
+		}
+
+		entries = append(entries, entry)
+	}
+
+	if err := scanner.Err(); err != nil {
+		return nil, err
+	}
+
+	return entries, nil
+}
@@ -0,0 +1,39 @@
+package logs
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestParseAuditLogReportsMalformedLines(t *testing.T) {
+	auditFile := filepath.Join(t.TempDir(), "audit-20250113-143000.jsonl")
+	log := `{"timestamp":"2025-01-13T14:30:00+0000","pid":7,"ppid":1,"cwd":"/work","command":"printf '\u001b[2K'"}` + "\n" +
+		"{\"timestamp\":\"2025-01-13T14:30:01+0000\",\"pid\":7,\"ppid\":1,\"cwd\":\"/work\",\"command\":\"echo \x1b[2K\"}\n" +
+		`{"timestamp":"2025-01-13T14:30:02+0000","pid":7,"ppid":1,"cwd":"/wo` + "\n" +
+		`{"timestamp":"2025-01-13T14:30:03+0000","pid":7,"ppid":1,"cwd":"/work","command":"make"}` + "\n"
+	if err := os.WriteFile(auditFile, []byte(log), 0600); err != nil {
+		t.Fatal(err)
+	}
+
+	entries, err := ParseAuditLog(auditFile)
+	if err != nil {
+		t.Fatalf("ParseAuditLog() error = %v", err)
+	}
+	if len(entries) != 4 {
+		t.Fatalf("ParseAuditLog() returned %d entries, want 4", len(entries))
+	}
+	if entries[0].Command != "printf '\x1b[2K'" {
+		t.Errorf("entries[0].Command = %q", entries[0].Command)
+	}
+	// A raw control character is invalid JSON; the line is kept, not dropped
+	if entries[1].Malformed == "" || entries[2].Malformed == "" {
+		t.Errorf("entries[1:3] = %+v, want both malformed", entries[1:3])
+	}
+	if entries[3].Command != "make" {
+		t.Errorf("entries[3] = %+v, want the line after the malformed ones", entries[3])
+	}
+	if got := CommandCount(entries); got != 2 {
+		t.Errorf("CommandCount() = %d, want 2", got)
+	}
+}
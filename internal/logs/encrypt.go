@@ -0,0 +1,186 @@
+package logs
+
+import (
+	"bufio"
+	"bytes"
+	"crypto/aes"
+	"crypto/cipher"
+	"crypto/rand"
+	"encoding/base64"
+	"errors"
+	"fmt"
+	"os"
+	"strings"
+	"sync"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/keyring"
+)
+
+// encryptedPrefix marks a log line sealed with the log key. Lines are encrypted one by
+// one so logs can still be appended to and plaintext lines from before stay readable.
+const encryptedPrefix = "agentsandbox-enc:v1:"
+
+// LogKeyEnv holds a base64 log key, used instead of the keyring when set
+const LogKeyEnv = "AGENTSANDBOX_LOG_KEY"
+
+// keyringAccount is the keyring entry holding the base64 log key
+const keyringAccount = "log-encryption-key"
+
+var (
+	keyMu     sync.Mutex
+	cachedKey []byte
+)
+
+// encryptionEnabled reports whether new logs should be written encrypted
+func encryptionEnabled() bool {
+	settings, err := config.LoadSettings()
+	return err == nil && settings.LogEncryption
+}
+
+// LogKey returns the AES-256 key for session logs, from $AGENTSANDBOX_LOG_KEY or the OS
+// keyring. With create, a new key is generated and stored when none exists yet.
+func LogKey(create bool) ([]byte, error) {
+	keyMu.Lock()
+	defer keyMu.Unlock()
+	if cachedKey != nil {
+		return cachedKey, nil
+	}
+
+	encoded := os.Getenv(LogKeyEnv)
+	if encoded == "" {
+		secret, err := keyring.Get(keyringAccount)
+		switch {
+		case err == nil:
+			encoded = secret
+		case errors.Is(err, keyring.ErrNotFound) && create:
+			key := make([]byte, 32)
+			if _, err := rand.Read(key); err != nil {
+				return nil, fmt.Errorf("failed to generate log key: %w", err)
+			}
+			encoded = base64.StdEncoding.EncodeToString(key)
+			if err := keyring.Set(keyringAccount, encoded); err != nil {
+				return nil, err
+			}
+		default:
+			return nil, fmt.Errorf("failed to load log key: %w", err)
+		}
+	}
+
+	key, err := base64.StdEncoding.DecodeString(strings.TrimSpace(encoded))
+	if err != nil || len(key) != 32 {
+		return nil, fmt.Errorf("invalid log key: expected 32 base64-encoded bytes")
+	}
+	cachedKey = key
+	return key, nil
+}
+
+func newGCM(key []byte) (cipher.AEAD, error) {
+	block, err := aes.NewCipher(key)
+	if err != nil {
+		return nil, err
+	}
+	return cipher.NewGCM(block)
+}
+
+// sealLine encrypts one log line
+func sealLine(key, line []byte) ([]byte, error) {
+	gcm, err := newGCM(key)
+	if err != nil {
+		return nil, err
+	}
+	nonce := make([]byte, gcm.NonceSize())
+	if _, err := rand.Read(nonce); err != nil {
+		return nil, err
+	}
+	sealed := gcm.Seal(nonce, nonce, line, nil)
+	return []byte(encryptedPrefix + base64.StdEncoding.EncodeToString(sealed)), nil
+}
+
+// openLine returns a log line in plaintext, decrypting it if it was sealed
+func openLine(line string) (string, error) {
+	if !strings.HasPrefix(line, encryptedPrefix) {
+		return line, nil
+	}
+	key, err := LogKey(false)
+	if err != nil {
+		return "", fmt.Errorf("log is encrypted: %w", err)
+	}
+
+	sealed, err := base64.StdEncoding.DecodeString(strings.TrimPrefix(line, encryptedPrefix))
+	if err != nil {
+		return "", fmt.Errorf("corrupt encrypted log line: %w", err)
+	}
+	gcm, err := newGCM(key)
+	if err != nil {
+		return "", err
+	}
+	if len(sealed) < gcm.NonceSize() {
+		return "", fmt.Errorf("corrupt encrypted log line")
+	}
+	plain, err := gcm.Open(nil, sealed[:gcm.NonceSize()], sealed[gcm.NonceSize():], nil)
+	if err != nil {
+		return "", fmt.Errorf("failed to decrypt log, was it written with another key? %w", err)
+	}
+	return string(plain), nil
+}
+
+// ReadLog returns the contents of a log file with any encrypted lines decrypted
+func ReadLog(path string) ([]byte, error) {
+	file, err := os.Open(path)
+	if err != nil {
+		return nil, err
+	}
+	defer file.Close()
+
+	var out bytes.Buffer
+	scanner := bufio.NewScanner(file)
+	scanner.Buffer(make([]byte, 0, 64*1024), 16*1024*1024)
+	for scanner.Scan() {
+		line, err := openLine(scanner.Text())
+		if err != nil {
+			return nil, err
+		}
+		out.WriteString(line)
+		out.WriteByte('\n')
+	}
+	if err := scanner.Err(); err != nil {
+		return nil, err
+	}
+	return out.Bytes(), nil
+}
+
+// EncryptFile seals every plaintext line of a log file when log encryption is enabled.
+// It is used for logs that are copied into place rather than written by WriteEvents.
+func EncryptFile(path string) error {
+	if !encryptionEnabled() {
+		return nil
+	}
+	key, err := LogKey(true)
+	if err != nil {
+		return err
+	}
+
+	data, err := ReadLog(path)
+	if err != nil {
+		return err
+	}
+	var out bytes.Buffer
+	for _, line := range strings.Split(strings.TrimRight(string(data), "\n"), "\n") {
+		if line == "" {
+			continue
+		}
+		sealed, err := sealLine(key, []byte(line))
+		if err != nil {
+			return err
+		}
+		out.Write(sealed)
+		out.WriteByte('\n')
+	}
+
+	tmp := path + ".tmp"
+	if err := os.WriteFile(tmp, out.Bytes(), 0600); err != nil {
+		return err
+	}
+	return os.Rename(tmp, path)
+}
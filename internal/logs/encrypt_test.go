@@ -0,0 +1,46 @@
+package logs
+
+import (
+	"encoding/base64"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestEncryptedLogs(t *testing.T) {
+	configDir := t.TempDir()
+	t.Setenv("XDG_CONFIG_HOME", configDir)
+	t.Setenv(LogKeyEnv, base64.StdEncoding.EncodeToString(make([]byte, 32)))
+	if err := os.MkdirAll(filepath.Join(configDir, "agentsandbox"), 0755); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(configDir, "agentsandbox", "settings.json"), []byte(`{"log_encryption": true}`), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	logFile := filepath.Join(t.TempDir(), "session.jsonl")
+	// A plaintext line from before encryption was turned on stays readable
+	if err := os.WriteFile(logFile, []byte(`{"timestamp":"t0","level":"info","message":"old"}`+"\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	if err := WriteEvents(logFile, []LogEvent{{Timestamp: "t1", Level: "user", Message: "secret prompt"}}); err != nil {
+		t.Fatal(err)
+	}
+
+	data, err := os.ReadFile(logFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if strings.Contains(string(data), "secret prompt") || !strings.Contains(string(data), encryptedPrefix) {
+		t.Fatalf("log was not encrypted:\n%s", data)
+	}
+
+	events, err := ParseRawLog(logFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(events) != 2 || events[0].Message != "old" || events[1].Message != "secret prompt" {
+		t.Errorf("ParseRawLog() = %+v", events)
+	}
+}
@@ -0,0 +1,106 @@
+package logs
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+	"time"
+)
+
+// NetworkEntry is one request or tunnel made through the session's logging proxy.
+// HTTPS is tunnelled without being decrypted, so only the host and byte counts are known.
+type NetworkEntry struct {
+	Time          time.Time `json:"time"`
+	Method        string    `json:"method"`
+	Host          string    `json:"host"`
+	BytesSent     int64     `json:"bytes_sent"`
+	BytesReceived int64     `json:"bytes_received"`
+	DurationMS    int64     `json:"duration_ms"`
+	Error         string    `json:"error,omitempty"`
+}
+
+// HostSummary totals a session's traffic to one host
+type HostSummary struct {
+	Host          string
+	Connections   int
+	Errors        int
+	BytesSent     int64
+	BytesReceived int64
+	Duration      time.Duration
+}
+
+// NetworkSessionID returns the session identifier of a network log file
+func NetworkSessionID(networkFile string) string {
+	name := strings.TrimSuffix(filepath.Base(networkFile), ".jsonl")
+	return strings.TrimPrefix(name, "network-")
+}
+
+// WriteNetworkLog stores a session's connections, encrypted when log encryption is on
+func WriteNetworkLog(path string, entries []NetworkEntry) error {
+	var b strings.Builder
+	for _, entry := range entries {
+		line, err := json.Marshal(entry)
+		if err != nil {
+			return err
+		}
+		b.Write(line)
+		b.WriteByte('\n')
+	}
+	if err := os.WriteFile(path, []byte(b.String()), 0600); err != nil {
+		return err
+	}
+	return EncryptFile(path)
+}
+
+// ParseNetworkLog reads the connections recorded in a network log
+func ParseNetworkLog(networkFile string) ([]NetworkEntry, error) {
+	data, err := ReadLog(networkFile)
+	if err != nil {
+		return nil, err
+	}
+
+	var entries []NetworkEntry
+	for _, line := range strings.Split(string(data), "\n") {
+		if line == "" {
+			continue
+		}
+		var entry NetworkEntry
+		if err := json.Unmarshal([]byte(line), &entry); err != nil {
+			continue
+		}
+		entries = append(entries, entry)
+	}
+	return entries, nil
+}
+
+// SummarizeNetwork totals the connections per host, busiest first
+func SummarizeNetwork(entries []NetworkEntry) []HostSummary {
+	byHost := make(map[string]*HostSummary)
+	var hosts []*HostSummary
+	for _, entry := range entries {
+		summary, ok := byHost[entry.Host]
+		if !ok {
+			summary = &HostSummary{Host: entry.Host}
+			byHost[entry.Host] = summary
+			hosts = append(hosts, summary)
+		}
+		summary.Connections++
+		if entry.Error != "" {
+			summary.Errors++
+		}
+		summary.BytesSent += entry.BytesSent
+		summary.BytesReceived += entry.BytesReceived
+		summary.Duration += time.Duration(entry.DurationMS) * time.Millisecond
+	}
+
+	sort.SliceStable(hosts, func(i, j int) bool {
+		return hosts[i].BytesSent+hosts[i].BytesReceived > hosts[j].BytesSent+hosts[j].BytesReceived
+	})
+	result := make([]HostSummary, len(hosts))
+	for i, summary := range hosts {
+		result[i] = *summary
+	}
+	return result
+}
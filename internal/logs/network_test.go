@@ -0,0 +1,39 @@
+package logs
+
+import (
+	"path/filepath"
+	"testing"
+	"time"
+)
+
+func TestNetworkLog(t *testing.T) {
+	t.Setenv("HOME", t.TempDir())
+	t.Setenv("XDG_CONFIG_HOME", "")
+	now := time.Now().UTC().Truncate(time.Second)
+	entries := []NetworkEntry{
+		{Time: now, Method: "CONNECT", Host: "registry.npmjs.org:443", BytesSent: 100, BytesReceived: 5000, DurationMS: 200},
+		{Time: now, Method: "CONNECT", Host: "api.github.com:443", BytesSent: 50, BytesReceived: 80, DurationMS: 100},
+		{Time: now, Method: "CONNECT", Host: "registry.npmjs.org:443", BytesSent: 10, BytesReceived: 900, DurationMS: 50, Error: "reset"},
+	}
+
+	path := filepath.Join(t.TempDir(), "network-20250101-120000.jsonl")
+	if err := WriteNetworkLog(path, entries); err != nil {
+		t.Fatal(err)
+	}
+	if id := NetworkSessionID(path); id != "20250101-120000" {
+		t.Errorf("NetworkSessionID() = %q", id)
+	}
+	parsed, err := ParseNetworkLog(path)
+	if err != nil || len(parsed) != 3 || !parsed[0].Time.Equal(now) {
+		t.Fatalf("ParseNetworkLog() = %+v, %v", parsed, err)
+	}
+
+	summary := SummarizeNetwork(parsed)
+	if len(summary) != 2 || summary[0].Host != "registry.npmjs.org:443" {
+		t.Fatalf("SummarizeNetwork() = %+v", summary)
+	}
+	npm := summary[0]
+	if npm.Connections != 2 || npm.Errors != 1 || npm.BytesReceived != 5900 || npm.Duration != 250*time.Millisecond {
+		t.Errorf("unexpected npm summary %+v", npm)
+	}
+}
@@ -31,6 +31,11 @@ func ParseRawLog(logFile string) ([]LogEvent, error) {
 			continue
 		}
 
+		line, err := openLine(line)
+		if err != nil {
+			return nil, err
+		}
+
 		var event LogEvent
 		if err := json.Unmarshal([]byte(line), &event); err != nil {
 			// Skip invalid lines
@@ -47,3 +52,52 @@ func ParseRawLog(logFile string) ([]LogEvent, error) {
 	return events, nil
 }
 
+// Prompts returns the user prompts recorded in a session, in order
+func Prompts(events []LogEvent) []string {
+	var prompts []string
+	for _, event := range events {
+		if isPrompt(event) {
+			prompts = append(prompts, event.Message)
+		}
+	}
+	return prompts
+}
+
+// isPrompt reports whether event is a prompt given to the agent
+func isPrompt(event LogEvent) bool {
+	return (event.Level == "user" || event.Level == "prompt") && event.Message != ""
+}
+
+// WriteEvents appends events to a JSONL log file, encrypting each line when log
+// encryption is enabled
+func WriteEvents(logFile string, events []LogEvent) error {
+	var key []byte
+	if encryptionEnabled() {
+		var err error
+		if key, err = LogKey(true); err != nil {
+			return err
+		}
+	}
+
+	file, err := os.OpenFile(logFile, os.O_CREATE|os.O_WRONLY|os.O_APPEND, 0644)
+	if err != nil {
+		return err
+	}
+	defer file.Close()
+
+	for _, event := range events {
+		line, err := json.Marshal(event)
+		if err != nil {
+			return err
+		}
+		if key != nil {
+			if line, err = sealLine(key, line); err != nil {
+				return err
+			}
+		}
+		if _, err := file.Write(append(line, '\n')); err != nil {
+			return err
+		}
+	}
+	return nil
+}
@@ -0,0 +1,165 @@
+package logs
+
+import (
+	"bytes"
+	"strconv"
+	"strings"
+	"time"
+	"unicode/utf8"
+)
+
+// scriptStartLayout is the format of the START_TIME header in a script(1) timing log
+const scriptStartLayout = "2006-01-02 15:04:05-07:00"
+
+// ParseScriptRecording turns a session recorded by script(1) with separate input and
+// output logs and an advanced-format timing log into events, in the order they
+// happened. Each line typed at the terminal becomes an "input" event and each line of
+// output an "info" event. start is used when the timing log has no START_TIME header.
+func ParseScriptRecording(timing, input, output []byte, start time.Time) []LogEvent {
+	input, output = skipScriptHeader(input), skipScriptHeader(output)
+
+	var events []LogEvent
+	at := start
+	var typed, printed []byte
+	var typedAt, printedAt time.Time
+	flushOutput := func() {
+		if text := RenderTerminal(string(printed)); strings.TrimSpace(text) != "" {
+			events = append(events, LogEvent{Timestamp: printedAt.Format(time.RFC3339), Level: "info", Message: text})
+		}
+		printed = printed[:0]
+	}
+
+	for _, line := range strings.Split(string(timing), "\n") {
+		fields := strings.SplitN(line, " ", 3)
+		if len(fields) != 3 {
+			continue
+		}
+		delay, err := strconv.ParseFloat(fields[1], 64)
+		if err != nil {
+			continue
+		}
+		at = at.Add(time.Duration(delay * float64(time.Second)))
+
+		switch fields[0] {
+		case "H":
+			if name, value, _ := strings.Cut(fields[2], " "); name == "START_TIME" {
+				if t, err := time.Parse(scriptStartLayout, value); err == nil {
+					at = t
+				}
+			}
+		case "I":
+			var chunk []byte
+			chunk, input = takeBytes(input, fields[2])
+			for _, b := range chunk {
+				if b != '\r' && b != '\n' {
+					if len(typed) == 0 {
+						typedAt = at
+					}
+					typed = append(typed, b)
+					continue
+				}
+				// Enter submits the line; the output it produces follows the input event
+				if len(printed) > 0 {
+					flushOutput()
+				}
+				if text := typedText(typed); text != "" {
+					events = append(events, LogEvent{Timestamp: typedAt.Format(time.RFC3339), Level: "input", Message: text})
+				}
+				typed = typed[:0]
+			}
+		case "O":
+			var chunk []byte
+			chunk, output = takeBytes(output, fields[2])
+			for _, b := range chunk {
+				if len(printed) == 0 {
+					printedAt = at
+				}
+				if b == '\n' {
+					flushOutput()
+					continue
+				}
+				printed = append(printed, b)
+			}
+		}
+	}
+	if len(printed) > 0 {
+		flushOutput()
+	}
+	if text := typedText(typed); text != "" {
+		events = append(events, LogEvent{Timestamp: typedAt.Format(time.RFC3339), Level: "input", Message: text})
+	}
+	return events
+}
+
+// skipScriptHeader drops the "Script started on" line script(1) writes before a log
+func skipScriptHeader(data []byte) []byte {
+	if !bytes.HasPrefix(data, []byte("Script started on ")) {
+		return data
+	}
+	if i := bytes.IndexByte(data, '\n'); i >= 0 {
+		return data[i+1:]
+	}
+	return nil
+}
+
+// takeBytes splits the number of bytes named by count off the front of data
+func takeBytes(data []byte, count string) ([]byte, []byte) {
+	n, err := strconv.Atoi(count)
+	if err != nil || n < 0 {
+		return nil, data
+	}
+	if n > len(data) {
+		n = len(data)
+	}
+	return data[:n], data[n:]
+}
+
+// typedText returns what a line of keystrokes leaves behind: backspaces remove the
+// character before them, and escape sequences such as arrow keys and bracketed paste
+// markers are dropped along with other control characters
+func typedText(keys []byte) string {
+	var text []rune
+	for i := 0; i < len(keys); {
+		b := keys[i]
+		switch {
+		case b == 0x1b:
+			i = skipEscape(keys, i)
+			continue
+		case b == 0x7f || b == '\b':
+			if len(text) > 0 {
+				text = text[:len(text)-1]
+			}
+		case b == '\t':
+			text = append(text, '\t')
+		case b < 0x20:
+		default:
+			r, size := utf8.DecodeRune(keys[i:])
+			text = append(text, r)
+			i += size
+			continue
+		}
+		i++
+	}
+	return strings.TrimSpace(string(text))
+}
+
+// skipEscape returns the index after the escape sequence starting at keys[i]: a CSI
+// sequence such as an arrow key, an SS3 key, or ESC and one more byte
+func skipEscape(keys []byte, i int) int {
+	i++
+	if i >= len(keys) {
+		return i
+	}
+	switch keys[i] {
+	case '[':
+		for i++; i < len(keys); i++ {
+			if keys[i] >= 0x40 && keys[i] <= 0x7e {
+				return i + 1
+			}
+		}
+		return i
+	case 'O':
+		return i + 2
+	}
+	return i + 1
+}
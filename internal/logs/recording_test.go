@@ -0,0 +1,40 @@
+package logs
+
+import (
+	"fmt"
+	"testing"
+	"time"
+)
+
+func TestParseScriptRecording(t *testing.T) {
+	header := "Script started on 2025-01-13 14:30:00+00:00 [COMMAND=\"claude\"]\n"
+	welcome := "welcome\n"
+	typed := "fix the bug\x7f\x7f\x7fbugs\r"
+	working := "Working...\r\x1b[2KDone\r\n"
+	arrow := "\x1b[A\r"
+	timing := fmt.Sprintf("H 0.000000 START_TIME 2025-01-13 14:30:00+00:00\nO 0.500000 %d\nI 1.000000 %d\nO 2.000000 %d\nI 1.000000 %d\nH 0.000000 EXIT_CODE 0\n",
+		len(welcome), len(typed), len(working), len(arrow))
+
+	events := ParseScriptRecording([]byte(timing), []byte(header+typed+arrow), []byte(header+welcome+working), time.Time{})
+	want := []LogEvent{
+		{Timestamp: "2025-01-13T14:30:00Z", Level: "info", Message: "welcome"},
+		{Timestamp: "2025-01-13T14:30:01Z", Level: "input", Message: "fix the bugs"},
+		{Timestamp: "2025-01-13T14:30:03Z", Level: "info", Message: "Done"},
+	}
+	if len(events) != len(want) {
+		t.Fatalf("ParseScriptRecording() = %+v, want %+v", events, want)
+	}
+	for i := range want {
+		if events[i].Timestamp != want[i].Timestamp || events[i].Level != want[i].Level || events[i].Message != want[i].Message {
+			t.Errorf("event %d = %+v, want %+v", i, events[i], want[i])
+		}
+	}
+}
+
+func TestParseScriptRecordingWithoutStartTime(t *testing.T) {
+	start := time.Date(2025, 1, 13, 9, 0, 0, 0, time.UTC)
+	events := ParseScriptRecording([]byte("I 2.0 3\n"), []byte("ls\r"), nil, start)
+	if len(events) != 1 || events[0].Message != "ls" || events[0].Timestamp != "2025-01-13T09:00:02Z" {
+		t.Errorf("ParseScriptRecording() = %+v, want ls typed two seconds in", events)
+	}
+}
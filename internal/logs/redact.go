@@ -0,0 +1,49 @@
+package logs
+
+import (
+	"fmt"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/secrets"
+)
+
+// LoadRedactor returns the redaction filter configured in settings. If a user pattern
+// is invalid it is reported and only the built-in patterns are used, so logs are never
+// written out unredacted because of a typo.
+func LoadRedactor() *secrets.Redactor {
+	settings, err := config.LoadSettings()
+	if err != nil {
+		settings = config.DefaultSettings()
+	}
+	redactor, err := settings.Redactor()
+	if err != nil {
+		fmt.Printf("Warning: %v; using the built-in redaction patterns only\n", err)
+		redactor, _ = secrets.NewRedactor(nil)
+	}
+	return redactor
+}
+
+// RedactEvents returns a copy of events with credentials removed from messages and
+// string data
+func RedactEvents(events []LogEvent, redactor *secrets.Redactor) []LogEvent {
+	if redactor == nil {
+		return events
+	}
+
+	redacted := make([]LogEvent, len(events))
+	for i, event := range events {
+		event.Message = redactor.Redact(event.Message)
+		if len(event.Data) > 0 {
+			data := make(map[string]interface{}, len(event.Data))
+			for k, v := range event.Data {
+				if s, ok := v.(string); ok {
+					v = redactor.Redact(s)
+				}
+				data[k] = v
+			}
+			event.Data = data
+		}
+		redacted[i] = event
+	}
+	return redacted
+}
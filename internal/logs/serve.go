@@ -0,0 +1,165 @@
+package logs
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"net"
+	"net/http"
+	"os"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/secrets"
+)
+
+// servePollInterval is how often a served log is checked for new events
+const servePollInterval = time.Second
+
+// liveReloadScript reloads the page when the server has rendered a newer version of the log
+const liveReloadScript = `<script>
+(function () {
+  var version = null;
+  setInterval(function () {
+    fetch('/__version', { cache: 'no-store' }).then(function (r) { return r.text(); }).then(function (v) {
+      if (version === null) { version = v; } else if (v !== version) { location.reload(); }
+    }).catch(function () {});
+  }, 1000);
+})();
+</script>
+`
+
+// previewServer renders a log into a fresh directory whenever it changes and serves
+// the latest rendering
+type previewServer struct {
+	logFile  string
+	redactor *secrets.Redactor
+	tmpDir   string
+
+	mu      sync.Mutex
+	dir     string
+	version int
+}
+
+// Serve renders logFile as HTML and serves it on addr until ctx is cancelled. The page
+// reloads itself as events are appended to the log. started is called with the page URL
+// once the server is listening.
+func Serve(ctx context.Context, logFile, addr string, redactor *secrets.Redactor, started func(url string)) error {
+	tmpDir, err := os.MkdirTemp("", "agentsandbox-serve-*")
+	if err != nil {
+		return err
+	}
+	defer os.RemoveAll(tmpDir)
+
+	s := &previewServer{logFile: logFile, redactor: redactor, tmpDir: tmpDir}
+	if err := s.render(); err != nil {
+		return err
+	}
+
+	listener, err := net.Listen("tcp", addr)
+	if err != nil {
+		return fmt.Errorf("failed to listen on %s: %w", addr, err)
+	}
+	server := &http.Server{Handler: s, ReadHeaderTimeout: 10 * time.Second}
+	go s.watch(ctx)
+	go func() {
+		<-ctx.Done()
+		shutdownCtx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
+		defer cancel()
+		server.Shutdown(shutdownCtx)
+	}()
+
+	started(fmt.Sprintf("http://%s/", listener.Addr()))
+	if err := server.Serve(listener); err != nil && !errors.Is(err, http.ErrServerClosed) {
+		return err
+	}
+	return nil
+}
+
+// render writes the current contents of the log to a new directory and makes it the
+// one being served
+func (s *previewServer) render() error {
+	events, err := ParseRawLog(s.logFile)
+	if err != nil {
+		return fmt.Errorf("failed to parse log file: %w", err)
+	}
+
+	s.mu.Lock()
+	version := s.version + 1
+	s.mu.Unlock()
+
+	dir := filepath.Join(s.tmpDir, strconv.Itoa(version))
+	if err := os.MkdirAll(dir, 0755); err != nil {
+		return err
+	}
+	if err := WriteHTML(RedactEvents(events, s.redactor), filepath.Join(dir, "index.html"), filepath.Base(s.logFile)); err != nil {
+		return fmt.Errorf("failed to generate HTML: %w", err)
+	}
+
+	s.mu.Lock()
+	previous := s.dir
+	s.dir, s.version = dir, version
+	s.mu.Unlock()
+
+	if previous != "" {
+		// Give pages still loading the old rendering a moment to finish
+		time.AfterFunc(10*time.Second, func() { os.RemoveAll(previous) })
+	}
+	return nil
+}
+
+// watch re-renders the log whenever it grows or is rewritten
+func (s *previewServer) watch(ctx context.Context) {
+	var lastSize int64
+	var lastMod time.Time
+	if info, err := os.Stat(s.logFile); err == nil {
+		lastSize, lastMod = info.Size(), info.ModTime()
+	}
+
+	ticker := time.NewTicker(servePollInterval)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-ctx.Done():
+			return
+		case <-ticker.C:
+		}
+
+		info, err := os.Stat(s.logFile)
+		if err != nil || (info.Size() == lastSize && info.ModTime().Equal(lastMod)) {
+			continue
+		}
+		lastSize, lastMod = info.Size(), info.ModTime()
+		if err := s.render(); err != nil {
+			fmt.Printf("Warning: %v\n", err)
+		}
+	}
+}
+
+func (s *previewServer) ServeHTTP(w http.ResponseWriter, r *http.Request) {
+	s.mu.Lock()
+	dir, version := s.dir, s.version
+	s.mu.Unlock()
+
+	switch r.URL.Path {
+	case "/__version":
+		w.Header().Set("Cache-Control", "no-store")
+		fmt.Fprint(w, version)
+	case "/", "/index.html":
+		page, err := os.ReadFile(filepath.Join(dir, "index.html"))
+		if err != nil {
+			http.Error(w, err.Error(), http.StatusInternalServerError)
+			return
+		}
+		html := strings.Replace(string(page), "</body>", liveReloadScript+"</body>", 1)
+		w.Header().Set("Content-Type", "text/html; charset=utf-8")
+		w.Header().Set("Cache-Control", "no-store")
+		fmt.Fprint(w, html)
+	default:
+		w.Header().Set("Cache-Control", "no-store")
+		http.FileServer(http.Dir(dir)).ServeHTTP(w, r)
+	}
+}
@@ -0,0 +1,53 @@
+package logs
+
+import (
+	"net/http/httptest"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestPreviewServerRendersLatestLog(t *testing.T) {
+	t.Setenv("XDG_CONFIG_HOME", t.TempDir())
+	logFile := filepath.Join(t.TempDir(), "session.jsonl")
+	if err := WriteEvents(logFile, []LogEvent{{Timestamp: "2025-01-13T14:30:00Z", Level: "user", Message: "hello"}}); err != nil {
+		t.Fatal(err)
+	}
+
+	s := &previewServer{logFile: logFile, tmpDir: t.TempDir()}
+	if err := s.render(); err != nil {
+		t.Fatal(err)
+	}
+
+	get := func(path string) string {
+		rec := httptest.NewRecorder()
+		s.ServeHTTP(rec, httptest.NewRequest("GET", path, nil))
+		if rec.Code != 200 {
+			t.Fatalf("GET %s = %d", path, rec.Code)
+		}
+		return rec.Body.String()
+	}
+
+	if page := get("/"); !strings.Contains(page, "/__version") {
+		t.Error("page does not include the live reload script")
+	}
+	if chunk := get("/index_files/events-0000.js"); !strings.Contains(chunk, "hello") {
+		t.Errorf("event asset = %q", chunk)
+	}
+	if v := get("/__version"); v != "1" {
+		t.Errorf("version = %q, want 1", v)
+	}
+
+	if err := WriteEvents(logFile, []LogEvent{{Timestamp: "2025-01-13T14:31:00Z", Level: "info", Message: "world"}}); err != nil {
+		t.Fatal(err)
+	}
+	if err := s.render(); err != nil {
+		t.Fatal(err)
+	}
+	if v := get("/__version"); v != "2" {
+		t.Errorf("version after append = %q, want 2", v)
+	}
+	if chunk := get("/index_files/events-0000.js"); !strings.Contains(chunk, "world") {
+		t.Errorf("event asset after append = %q", chunk)
+	}
+}
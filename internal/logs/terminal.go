@@ -0,0 +1,206 @@
+package logs
+
+import (
+	"strconv"
+	"strings"
+)
+
+// RenderTerminal replays terminal output and returns the text left on screen, so
+// spinners, progress bars and redrawn lines appear once, in their final state.
+// Colours and other attributes are dropped.
+func RenderTerminal(s string) string {
+	if !strings.ContainsAny(s, "\x1b\r\b") {
+		return s
+	}
+
+	t := &screen{lines: [][]rune{nil}}
+	runes := []rune(s)
+	for i := 0; i < len(runes); i++ {
+		r := runes[i]
+		switch {
+		case r == '\x1b':
+			i = t.escape(runes, i)
+		case r == '\n':
+			t.moveTo(t.row+1, 0)
+		case r == '\r':
+			t.col = 0
+		case r == '\b':
+			if t.col > 0 {
+				t.col--
+			}
+		case r == '\t':
+			t.col = (t.col/8 + 1) * 8
+		case r < 0x20 || r == 0x7f:
+			// Bell and other controls have no visible effect
+		default:
+			t.put(r)
+		}
+	}
+	return t.String()
+}
+
+// screen is an unbounded terminal canvas. Absolute cursor positions count from top,
+// which moves down whenever the screen is cleared so earlier output is kept.
+type screen struct {
+	lines    [][]rune
+	row, col int
+	top      int
+	savedRow int
+	savedCol int
+}
+
+func (t *screen) moveTo(row, col int) {
+	if row < 0 {
+		row = 0
+	}
+	if col < 0 {
+		col = 0
+	}
+	for len(t.lines) <= row {
+		t.lines = append(t.lines, nil)
+	}
+	t.row, t.col = row, col
+}
+
+func (t *screen) put(r rune) {
+	line := t.lines[t.row]
+	for len(line) <= t.col {
+		line = append(line, ' ')
+	}
+	line[t.col] = r
+	t.lines[t.row] = line
+	t.col++
+}
+
+// escape handles the sequence starting at runes[i] and returns the index of its last rune
+func (t *screen) escape(runes []rune, i int) int {
+	if i+1 >= len(runes) {
+		return i
+	}
+	switch runes[i+1] {
+	case '[':
+		return t.csi(runes, i+2)
+	case ']', 'P', '_', '^':
+		// OSC, DCS and similar strings end with BEL or ST (ESC \)
+		for j := i + 2; j < len(runes); j++ {
+			if runes[j] == '\a' {
+				return j
+			}
+			if runes[j] == '\x1b' && j+1 < len(runes) && runes[j+1] == '\\' {
+				return j + 1
+			}
+		}
+		return len(runes) - 1
+	case '7':
+		t.savedRow, t.savedCol = t.row, t.col
+	case '8':
+		t.moveTo(t.savedRow, t.savedCol)
+	case '(', ')', '*', '+', '#', '%':
+		// Character set selection takes one more rune
+		return i + 2
+	}
+	return i + 1
+}
+
+// csi applies a control sequence whose parameters start at runes[start]
+func (t *screen) csi(runes []rune, start int) int {
+	end := start
+	for end < len(runes) && (runes[end] < 0x40 || runes[end] > 0x7e) {
+		end++
+	}
+	if end >= len(runes) {
+		return len(runes) - 1
+	}
+	params := string(runes[start:end])
+	if strings.HasPrefix(params, "?") || strings.HasPrefix(params, ">") {
+		// Private modes such as cursor visibility do not change the text
+		return end
+	}
+
+	args := strings.Split(params, ";")
+	arg := func(n, def int) int {
+		if n < len(args) {
+			if v, err := strconv.Atoi(args[n]); err == nil && v > 0 {
+				return v
+			}
+		}
+		return def
+	}
+
+	switch runes[end] {
+	case 'A':
+		t.moveTo(t.row-arg(0, 1), t.col)
+	case 'B', 'e':
+		t.moveTo(t.row+arg(0, 1), t.col)
+	case 'C', 'a':
+		t.moveTo(t.row, t.col+arg(0, 1))
+	case 'D':
+		t.moveTo(t.row, t.col-arg(0, 1))
+	case 'E':
+		t.moveTo(t.row+arg(0, 1), 0)
+	case 'F':
+		t.moveTo(t.row-arg(0, 1), 0)
+	case 'G', '`':
+		t.moveTo(t.row, arg(0, 1)-1)
+	case 'd':
+		t.moveTo(t.top+arg(0, 1)-1, t.col)
+	case 'H', 'f':
+		t.moveTo(t.top+arg(0, 1)-1, arg(1, 1)-1)
+	case 'K':
+		t.eraseLine(arg(0, 0))
+	case 'J':
+		t.eraseDisplay(arg(0, 0))
+	case 's':
+		t.savedRow, t.savedCol = t.row, t.col
+	case 'u':
+		t.moveTo(t.savedRow, t.savedCol)
+	}
+	return end
+}
+
+func (t *screen) eraseLine(mode int) {
+	line := t.lines[t.row]
+	switch mode {
+	case 0:
+		if t.col < len(line) {
+			t.lines[t.row] = line[:t.col]
+		}
+	case 1:
+		for i := 0; i <= t.col && i < len(line); i++ {
+			line[i] = ' '
+		}
+	case 2:
+		t.lines[t.row] = nil
+	}
+}
+
+func (t *screen) eraseDisplay(mode int) {
+	switch mode {
+	case 0:
+		t.eraseLine(0)
+		t.lines = t.lines[:t.row+1]
+	case 2, 3:
+		// Start a fresh page below what was shown so far
+		t.trimTrailingBlank()
+		t.top = len(t.lines)
+		if len(t.lines[t.top-1]) == 0 {
+			t.top--
+		}
+		t.moveTo(t.top, 0)
+	}
+}
+
+func (t *screen) trimTrailingBlank() {
+	for len(t.lines) > 1 && len(strings.TrimSpace(string(t.lines[len(t.lines)-1]))) == 0 {
+		t.lines = t.lines[:len(t.lines)-1]
+	}
+}
+
+// String returns the screen's text with trailing spaces removed from each line
+func (t *screen) String() string {
+	out := make([]string, len(t.lines))
+	for i, line := range t.lines {
+		out[i] = strings.TrimRight(string(line), " ")
+	}
+	return strings.Join(out, "\n")
+}
@@ -0,0 +1,30 @@
+package logs
+
+import "testing"
+
+func TestRenderTerminal(t *testing.T) {
+	tests := []struct {
+		name string
+		in   string
+		want string
+	}{
+		{"plain text", "hello\nworld\n", "hello\nworld\n"},
+		{"colours", "\x1b[1;31mred\x1b[0m text", "red text"},
+		{"spinner", "|\r/\r-\rdone", "done"},
+		{"progress", "10%\r50%\r100%\n", "100%\n"},
+		{"backspace", "ab\bc", "ac"},
+		{"cursor left", "abc\x1b[2Dxy", "axy"},
+		{"erase line", "hello\r\x1b[Kbye", "bye"},
+		{"cursor up", "a\nb\n\x1b[2Ac", "c\nb\n"},
+		{"window title", "\x1b]0;title\x07text", "text"},
+		{"hidden cursor", "\x1b[?25lworking\x1b[?25h", "working"},
+		{"clear screen", "old\n\x1b[2J\x1b[Hnew", "old\nnew"},
+		{"absolute position", "\x1b[2;3Hx", "\n  x"},
+	}
+
+	for _, tt := range tests {
+		if got := RenderTerminal(tt.in); got != tt.want {
+			t.Errorf("%s: RenderTerminal(%q) = %q, want %q", tt.name, tt.in, got, tt.want)
+		}
+	}
+}
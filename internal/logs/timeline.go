@@ -0,0 +1,64 @@
+package logs
+
+import "time"
+
+// timelineBuckets is how many slices the viewer's timeline divides a session into
+const timelineBuckets = 120
+
+// timelineBucket summarizes the events in one slice of the session
+type timelineBucket struct {
+	Start   string `json:"start"`
+	Events  int    `json:"events"`
+	Errors  int    `json:"errors"`
+	Prompts int    `json:"prompts"`
+}
+
+// buildTimeline spreads events over equal slices of the session's duration. Events
+// without a parseable timestamp are left out, so the timeline is empty when none have one.
+func buildTimeline(events []LogEvent) []timelineBucket {
+	times := make([]time.Time, len(events))
+	var first, last time.Time
+	for i, event := range events {
+		t, err := time.Parse(time.RFC3339Nano, event.Timestamp)
+		if err != nil {
+			continue
+		}
+		times[i] = t
+		if first.IsZero() || t.Before(first) {
+			first = t
+		}
+		if t.After(last) {
+			last = t
+		}
+	}
+	if first.IsZero() {
+		return []timelineBucket{}
+	}
+
+	count := timelineBuckets
+	span := last.Sub(first)
+	if span < time.Duration(count)*time.Second {
+		// Short sessions get roughly one bucket per second
+		count = int(span/time.Second) + 1
+	}
+	width := span/time.Duration(count) + 1
+
+	buckets := make([]timelineBucket, count)
+	for i := range buckets {
+		buckets[i].Start = first.Add(time.Duration(i) * width).Format(time.RFC3339)
+	}
+	for i, event := range events {
+		if times[i].IsZero() {
+			continue
+		}
+		b := &buckets[int(times[i].Sub(first)/width)]
+		b.Events++
+		if event.Level == "error" {
+			b.Errors++
+		}
+		if isPrompt(event) {
+			b.Prompts++
+		}
+	}
+	return buckets
+}
@@ -1,37 +1,170 @@
 package logs
 
 import (
+	"encoding/json"
 	"fmt"
 	"html/template"
+	"net/url"
 	"os"
+	"path/filepath"
+	"strings"
 )
 
+// chunkSize is how many events each lazily loaded asset holds
+const chunkSize = 500
+
+// chunkInfo describes one events asset in the page's index
+type chunkInfo struct {
+	Src   string `json:"src"`
+	Start string `json:"start"`
+	End   string `json:"end"`
+	Count int    `json:"count"`
+}
+
 const htmlTemplate = `<!DOCTYPE html>
 <html lang="en">
 <head>
     <meta charset="UTF-8">
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
     <title>{{.Title}}</title>
+    <script>
+        // Apply the saved theme before the page renders to avoid a flash
+        document.documentElement.dataset.theme = localStorage.getItem('agentsandbox-theme') ||
+            (window.matchMedia('(prefers-color-scheme: dark)').matches ? 'dark' : 'light');
+    </script>
     <style>
+        :root {
+            --bg: #f5f5f5;
+            --card: white;
+            --text: #333;
+            --muted: #666;
+            --data-bg: #f8f9fa;
+            --shadow: rgba(0,0,0,0.1);
+        }
+        :root[data-theme=dark] {
+            --bg: #1e1e1e;
+            --card: #2a2a2a;
+            --text: #ddd;
+            --muted: #999;
+            --data-bg: #333;
+            --shadow: rgba(0,0,0,0.5);
+        }
         body {
             font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, 'Helvetica Neue', Arial, sans-serif;
             max-width: 1200px;
             margin: 0 auto;
             padding: 20px;
-            background-color: #f5f5f5;
+            background-color: var(--bg);
+            color: var(--text);
         }
         h1 {
-            color: #333;
+            color: var(--text);
             border-bottom: 2px solid #007bff;
             padding-bottom: 10px;
         }
+        .controls {
+            position: sticky;
+            top: 0;
+            z-index: 1;
+            background-color: var(--bg);
+        }
+        .toolbar {
+            display: flex;
+            gap: 10px;
+            align-items: center;
+            padding: 10px 0;
+        }
+        .timeline {
+            display: flex;
+            align-items: flex-end;
+            gap: 1px;
+            height: 40px;
+            flex: 1;
+        }
+        .timeline .bucket {
+            position: relative;
+            flex: 1;
+            height: 100%;
+            cursor: pointer;
+        }
+        .timeline .bucket:hover {
+            background-color: rgba(0,123,255,0.1);
+        }
+        .timeline .bar {
+            position: absolute;
+            bottom: 0;
+            width: 100%;
+            background-color: #17a2b8;
+        }
+        .timeline .bucket.error .bar {
+            background-color: #dc3545;
+        }
+        .timeline .bucket.prompt::before {
+            content: '';
+            position: absolute;
+            top: 0;
+            left: 0;
+            right: 0;
+            height: 4px;
+            background-color: #007bff;
+        }
+        .toolbar input[type=text] {
+            padding: 4px 8px;
+            font-family: monospace;
+        }
+        .filters {
+            display: flex;
+            flex-wrap: wrap;
+            gap: 10px;
+            align-items: center;
+            padding-bottom: 10px;
+            font-size: 0.9em;
+        }
+        .filters #search {
+            flex: 1;
+            min-width: 200px;
+        }
+        .filters #from, .filters #to {
+            width: 9em;
+        }
+        #filter-status {
+            color: var(--muted);
+        }
+        @media print {
+            :root, :root[data-theme=dark] {
+                --bg: white;
+                --card: white;
+                --text: black;
+                --muted: #444;
+                --data-bg: white;
+            }
+            body {
+                max-width: none;
+                padding: 0;
+                font-size: 10pt;
+            }
+            .controls {
+                display: none;
+            }
+            .log-entry {
+                box-shadow: none;
+                border: 1px solid #ccc;
+                border-left-width: 4px;
+                margin: 4px 0;
+                padding: 6px 10px;
+                break-inside: avoid;
+            }
+            .data {
+                border: 1px solid #ddd;
+            }
+        }
         .log-entry {
-            background: white;
+            background: var(--card);
             border-left: 4px solid #007bff;
             margin: 10px 0;
             padding: 15px;
             border-radius: 4px;
-            box-shadow: 0 2px 4px rgba(0,0,0,0.1);
+            box-shadow: 0 2px 4px var(--shadow);
         }
         .log-entry.error {
             border-left-color: #dc3545;
@@ -42,8 +175,11 @@ const htmlTemplate = `<!DOCTYPE html>
         .log-entry.info {
             border-left-color: #17a2b8;
         }
+        .log-entry.highlight {
+            outline: 2px solid #007bff;
+        }
         .timestamp {
-            color: #666;
+            color: var(--muted);
             font-size: 0.9em;
             font-family: monospace;
         }
@@ -71,43 +207,366 @@ const htmlTemplate = `<!DOCTYPE html>
             margin-top: 10px;
             font-size: 1em;
             line-height: 1.5;
+            white-space: pre-wrap;
         }
         .data {
             margin-top: 10px;
             padding: 10px;
-            background-color: #f8f9fa;
+            background-color: var(--data-bg);
             border-radius: 4px;
             font-family: monospace;
             font-size: 0.9em;
+            white-space: pre-wrap;
         }
     </style>
 </head>
 <body>
     <h1>{{.Title}}</h1>
-    <p>Total events: {{len .Events}}</p>
-    
-    {{range .Events}}
-    <div class="log-entry {{.Level}}">
-        <div>
-            <span class="timestamp">{{.Timestamp}}</span>
-            <span class="level {{.Level}}">{{.Level}}</span>
+    <p>Total events: {{.Total}}{{if .Start}} &middot; {{.Start}} to {{.End}}{{end}}</p>
+    <div class="controls">
+        <div class="toolbar">
+            <div id="timeline" class="timeline" title="Activity over the session: bar height is output, red marks errors, blue marks prompts"></div>
+            <input id="jump" type="text" placeholder="Jump to time (HH:MM:SS)">
+            <button id="theme" type="button">Toggle theme</button>
+            <button id="print" type="button">Print</button>
+        </div>
+        <div class="toolbar filters">
+            <input id="search" type="text" placeholder="Search messages">
+            <label><input id="regex" type="checkbox"> Regex</label>
+            <label><input id="case" type="checkbox"> Match case</label>
+            <input id="from" type="text" placeholder="From HH:MM">
+            <input id="to" type="text" placeholder="To HH:MM">
+            <label><input id="only-prompts" type="checkbox"> Only prompts</label>
+            <label><input id="only-errors" type="checkbox"> Only errors</label>
+            <span id="filter-status"></span>
         </div>
-        <div class="message">{{.Message}}</div>
-        {{if .Data}}
-        <div class="data">{{printf "%+v" .Data}}</div>
-        {{end}}
     </div>
-    {{end}}
+    <div id="events"></div>
+    <div id="results" hidden></div>
+
+    <script>
+    (function () {
+        // Events live in separate assets that are loaded as they scroll into view;
+        // chunks far from the viewport are unmounted to keep the page light
+        const chunks = {{.Chunks}};
+        const estimatedHeight = 80;
+        const container = document.getElementById('events');
+        const cache = new Map();
+        const pending = new Map();
+        const rendered = new Set();
+
+        window.agentsandboxChunk = function (i, events) {
+            cache.set(i, events);
+            (pending.get(i) || []).forEach(function (resolve) { resolve(events); });
+            pending.delete(i);
+        };
+
+        function load(i) {
+            if (cache.has(i)) {
+                return Promise.resolve(cache.get(i));
+            }
+            return new Promise(function (resolve) {
+                if (pending.has(i)) {
+                    pending.get(i).push(resolve);
+                    return;
+                }
+                pending.set(i, [resolve]);
+                const script = document.createElement('script');
+                script.src = chunks[i].src;
+                document.head.appendChild(script);
+            });
+        }
+
+        function renderEvent(event) {
+            const entry = document.createElement('div');
+            entry.className = 'log-entry ' + event.level;
+            entry.dataset.time = event.timestamp;
+
+            const header = document.createElement('div');
+            const timestamp = document.createElement('span');
+            timestamp.className = 'timestamp';
+            timestamp.textContent = event.timestamp;
+            const level = document.createElement('span');
+            level.className = 'level ' + event.level;
+            level.textContent = event.level;
+            header.append(timestamp, level);
+
+            const message = document.createElement('div');
+            message.className = 'message';
+            message.textContent = event.message;
+            entry.append(header, message);
+
+            if (event.data) {
+                const data = document.createElement('div');
+                data.className = 'data';
+                data.textContent = JSON.stringify(event.data, null, 2);
+                entry.append(data);
+            }
+            return entry;
+        }
+
+        const sections = chunks.map(function (chunk, i) {
+            const section = document.createElement('section');
+            section.dataset.chunk = i;
+            section.style.minHeight = (chunk.count * estimatedHeight) + 'px';
+            container.appendChild(section);
+            return section;
+        });
+
+        function show(i) {
+            if (rendered.has(i)) {
+                return Promise.resolve();
+            }
+            return load(i).then(function (events) {
+                if (rendered.has(i)) {
+                    return;
+                }
+                const fragment = document.createDocumentFragment();
+                events.forEach(function (event) { fragment.appendChild(renderEvent(event)); });
+                sections[i].replaceChildren(fragment);
+                sections[i].style.minHeight = '';
+                rendered.add(i);
+            });
+        }
+
+        function hide(i) {
+            if (!rendered.has(i)) {
+                return;
+            }
+            // Keep the height so the scroll position does not jump
+            sections[i].style.minHeight = sections[i].offsetHeight + 'px';
+            sections[i].replaceChildren();
+            rendered.delete(i);
+        }
+
+        const observer = new IntersectionObserver(function (entries) {
+            entries.forEach(function (entry) {
+                const i = Number(entry.target.dataset.chunk);
+                if (entry.isIntersecting) {
+                    show(i);
+                } else {
+                    hide(i);
+                }
+            });
+        }, { rootMargin: '2000px 0px' });
+        sections.forEach(function (section) { observer.observe(section); });
+
+        // HH:MM[:SS] is taken as a time on the day the session started
+        function parseTime(value) {
+            const match = /^(\d{1,2}):(\d{2})(?::(\d{2}))?$/.exec(value.trim());
+            if (match && chunks.length > 0) {
+                const day = new Date(chunks[0].start);
+                day.setHours(Number(match[1]), Number(match[2]), Number(match[3] || 0), 0);
+                return day.getTime();
+            }
+            return Date.parse(value);
+        }
+
+        function jumpTo(value) {
+            const target = parseTime(value);
+            if (isNaN(target) || chunks.length === 0) {
+                return;
+            }
+            let i = chunks.findIndex(function (chunk) { return Date.parse(chunk.end) >= target; });
+            if (i < 0) {
+                i = chunks.length - 1;
+            }
+            show(i).then(function () {
+                const entries = sections[i].querySelectorAll('.log-entry');
+                for (const entry of entries) {
+                    if (Date.parse(entry.dataset.time) >= target) {
+                        document.querySelectorAll('.highlight').forEach(function (e) { e.classList.remove('highlight'); });
+                        entry.classList.add('highlight');
+                        entry.scrollIntoView({ block: 'center' });
+                        return;
+                    }
+                }
+                sections[i].scrollIntoView();
+            });
+        }
+
+        document.getElementById('jump').addEventListener('change', function (e) { jumpTo(e.target.value); });
+
+        // Filtering scans every chunk, so it loads them all and renders matches separately
+        const promptLevels = ['user', 'prompt', 'input', 'command'];
+        const maxResults = 1000;
+        const results = document.getElementById('results');
+        const filterStatus = document.getElementById('filter-status');
+        let filterRun = 0;
+        let filterTimer;
+
+        function field(id) {
+            return document.getElementById(id);
+        }
+
+        function buildFilter() {
+            const tests = [];
+            const text = field('search').value;
+            const matchCase = field('case').checked;
+            if (text) {
+                if (field('regex').checked) {
+                    let re;
+                    try {
+                        re = new RegExp(text, matchCase ? '' : 'i');
+                    } catch (e) {
+                        return { error: 'Invalid regular expression: ' + e.message };
+                    }
+                    tests.push(function (event) { return re.test(searchText(event)); });
+                } else {
+                    const needle = matchCase ? text : text.toLowerCase();
+                    tests.push(function (event) {
+                        const haystack = searchText(event);
+                        return (matchCase ? haystack : haystack.toLowerCase()).includes(needle);
+                    });
+                }
+            }
+
+            const bounds = [['from', function (t, limit) { return t >= limit; }], ['to', function (t, limit) { return t <= limit; }]];
+            for (const [id, within] of bounds) {
+                const value = field(id).value.trim();
+                if (!value) {
+                    continue;
+                }
+                let limit = parseTime(value);
+                if (isNaN(limit)) {
+                    return { error: 'Invalid time: ' + value };
+                }
+                // A "to" time without seconds includes the whole minute
+                if (id === 'to' && /^\d{1,2}:\d{2}$/.test(value)) {
+                    limit += 59999;
+                }
+                tests.push(function (event) {
+                    const t = Date.parse(event.timestamp);
+                    return !isNaN(t) && within(t, limit);
+                });
+            }
+
+            if (field('only-prompts').checked) {
+                tests.push(function (event) { return promptLevels.includes(event.level); });
+            }
+            if (field('only-errors').checked) {
+                tests.push(function (event) { return event.level === 'error'; });
+            }
+
+            if (tests.length === 0) {
+                return {};
+            }
+            return { accept: function (event) { return tests.every(function (test) { return test(event); }); } };
+        }
+
+        function searchText(event) {
+            return event.level + ' ' + event.message + (event.data ? ' ' + JSON.stringify(event.data) : '');
+        }
+
+        function applyFilter() {
+            const run = ++filterRun;
+            const filter = buildFilter();
+            if (filter.error) {
+                filterStatus.textContent = filter.error;
+                return;
+            }
+            if (!filter.accept) {
+                results.hidden = true;
+                container.hidden = false;
+                filterStatus.textContent = '';
+                return;
+            }
+
+            filterStatus.textContent = 'Searching...';
+            Promise.all(chunks.map(function (chunk, i) { return load(i); })).then(function (all) {
+                if (run !== filterRun) {
+                    return;
+                }
+                const fragment = document.createDocumentFragment();
+                let matches = 0;
+                all.forEach(function (events) {
+                    events.forEach(function (event) {
+                        if (filter.accept(event) && ++matches <= maxResults) {
+                            fragment.appendChild(renderEvent(event));
+                        }
+                    });
+                });
+                results.replaceChildren(fragment);
+                results.hidden = false;
+                container.hidden = true;
+                filterStatus.textContent = matches + ' matching events' + (matches > maxResults ? ', showing the first ' + maxResults : '') + '; click one to see it in context';
+            });
+        }
+
+        document.querySelectorAll('.filters input').forEach(function (input) {
+            input.addEventListener('input', function () {
+                clearTimeout(filterTimer);
+                filterTimer = setTimeout(applyFilter, 200);
+            });
+        });
+
+        results.addEventListener('click', function (e) {
+            const entry = e.target.closest('.log-entry');
+            if (!entry) {
+                return;
+            }
+            results.hidden = true;
+            container.hidden = false;
+            filterStatus.textContent = 'Showing the match in context; edit the filter to search again';
+            jumpTo(entry.dataset.time);
+        });
+
+        document.getElementById('theme').addEventListener('click', function () {
+            const theme = document.documentElement.dataset.theme === 'dark' ? 'light' : 'dark';
+            document.documentElement.dataset.theme = theme;
+            localStorage.setItem('agentsandbox-theme', theme);
+        });
+
+        // Printing needs every event on the page, not just the chunks near the viewport
+        document.getElementById('print').addEventListener('click', function () {
+            observer.disconnect();
+            results.hidden = true;
+            container.hidden = false;
+            Promise.all(chunks.map(function (chunk, i) { return show(i); })).then(function () {
+                window.print();
+                sections.forEach(function (section) { observer.observe(section); });
+            });
+        });
+
+        // Timeline strip: one bar per slice of the session, click to jump there
+        const timeline = {{.Timeline}};
+        const busiest = Math.max(1, ...timeline.map(function (b) { return b.events; }));
+        const strip = document.getElementById('timeline');
+        timeline.forEach(function (bucket) {
+            const cell = document.createElement('div');
+            cell.className = 'bucket' + (bucket.errors ? ' error' : '') + (bucket.prompts ? ' prompt' : '');
+            cell.title = bucket.start + ': ' + bucket.events + ' events, ' + bucket.errors + ' errors, ' + bucket.prompts + ' prompts';
+            const bar = document.createElement('div');
+            bar.className = 'bar';
+            bar.style.height = (bucket.events ? Math.max(5, 100 * bucket.events / busiest) : 0) + '%';
+            cell.appendChild(bar);
+            cell.addEventListener('click', function () { jumpTo(bucket.start); });
+            strip.appendChild(cell);
+        });
+    })();
+    </script>
 </body>
 </html>`
 
-// WriteHTML generates an HTML file from log events
+// AssetsDir returns the directory WriteHTML stores a page's event assets in
+func AssetsDir(outputPath string) string {
+	return strings.TrimSuffix(outputPath, filepath.Ext(outputPath)) + "_files"
+}
+
+// WriteHTML generates an HTML viewer for log events. The events are written as
+// JavaScript assets in AssetsDir(outputPath) and loaded by the page as they scroll into
+// view, so long sessions do not produce pages too large for the browser.
 func WriteHTML(events []LogEvent, outputPath string, title string) error {
 	tmpl, err := template.New("log").Parse(htmlTemplate)
 	if err != nil {
 		return fmt.Errorf("failed to parse template: %w", err)
 	}
 
+	chunks, err := writeEventAssets(events, AssetsDir(outputPath))
+	if err != nil {
+		return fmt.Errorf("failed to write event assets: %w", err)
+	}
+
 	file, err := os.Create(outputPath)
 	if err != nil {
 		return fmt.Errorf("failed to create output file: %w", err)
@@ -115,11 +574,21 @@ func WriteHTML(events []LogEvent, outputPath string, title string) error {
 	defer file.Close()
 
 	data := struct {
-		Title  string
-		Events []LogEvent
+		Title    string
+		Total    int
+		Start    string
+		End      string
+		Chunks   []chunkInfo
+		Timeline []timelineBucket
 	}{
-		Title:  title,
-		Events: events,
+		Title:    title,
+		Total:    len(events),
+		Chunks:   chunks,
+		Timeline: buildTimeline(events),
+	}
+	if len(events) > 0 {
+		data.Start = events[0].Timestamp
+		data.End = events[len(events)-1].Timestamp
 	}
 
 	if err := tmpl.Execute(file, data); err != nil {
@@ -129,3 +598,46 @@ func WriteHTML(events []LogEvent, outputPath string, title string) error {
 	return nil
 }
 
+// writeEventAssets replaces dir with one script per chunk of events, each handing its
+// events to the page, and returns the index the page uses to find them
+func writeEventAssets(events []LogEvent, dir string) ([]chunkInfo, error) {
+	if err := os.RemoveAll(dir); err != nil {
+		return nil, err
+	}
+	if err := os.MkdirAll(dir, 0755); err != nil {
+		return nil, err
+	}
+
+	chunks := []chunkInfo{}
+	for start := 0; start < len(events); start += chunkSize {
+		end := start + chunkSize
+		if end > len(events) {
+			end = len(events)
+		}
+		// Older logs may hold raw terminal output; show what the terminal displayed
+		chunk := make([]LogEvent, end-start)
+		copy(chunk, events[start:end])
+		for i := range chunk {
+			chunk[i].Message = RenderTerminal(chunk[i].Message)
+		}
+
+		data, err := json.Marshal(chunk)
+		if err != nil {
+			return nil, err
+		}
+		i := len(chunks)
+		name := fmt.Sprintf("events-%04d.js", i)
+		script := fmt.Sprintf("agentsandboxChunk(%d, %s);\n", i, data)
+		if err := os.WriteFile(filepath.Join(dir, name), []byte(script), 0644); err != nil {
+			return nil, err
+		}
+
+		chunks = append(chunks, chunkInfo{
+			Src:   url.PathEscape(filepath.Base(dir)) + "/" + name,
+			Start: chunk[0].Timestamp,
+			End:   chunk[len(chunk)-1].Timestamp,
+			Count: len(chunk),
+		})
+	}
+	return chunks, nil
+}
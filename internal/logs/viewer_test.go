@@ -0,0 +1,73 @@
+package logs
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestWriteHTMLSplitsEventsIntoAssets(t *testing.T) {
+	events := make([]LogEvent, 2*chunkSize+1)
+	for i := range events {
+		events[i] = LogEvent{Timestamp: fmt.Sprintf("2025-01-13T14:%02d:00Z", i%60), Level: "info", Message: fmt.Sprintf("line %d", i)}
+	}
+	events[0].Message = "</script><b>not markup</b>"
+
+	output := filepath.Join(t.TempDir(), "session log.html")
+	if err := WriteHTML(events, output, "session"); err != nil {
+		t.Fatalf("WriteHTML() error = %v", err)
+	}
+
+	page, err := os.ReadFile(output)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if strings.Contains(string(page), "line 1") {
+		t.Error("page inlines events, want them in separate assets")
+	}
+	if !strings.Contains(string(page), `session%20log_files/events-0002.js`) {
+		t.Errorf("page does not reference the last chunk:\n%s", page)
+	}
+
+	assets, err := filepath.Glob(filepath.Join(AssetsDir(output), "events-*.js"))
+	if err != nil || len(assets) != 3 {
+		t.Fatalf("assets = %v, %v; want 3 chunks", assets, err)
+	}
+
+	first, err := os.ReadFile(assets[0])
+	if err != nil {
+		t.Fatal(err)
+	}
+	if !strings.HasPrefix(string(first), "agentsandboxChunk(0, ") {
+		t.Errorf("chunk starts with %.40q", first)
+	}
+	if strings.Contains(string(first), "</script>") {
+		t.Error("chunk contains an unescaped </script>")
+	}
+}
+
+func TestBuildTimeline(t *testing.T) {
+	events := []LogEvent{
+		{Timestamp: "2025-01-13T14:00:00Z", Level: "user", Message: "fix the tests"},
+		{Timestamp: "2025-01-13T14:00:30Z", Level: "info", Message: "running"},
+		{Timestamp: "not a time", Level: "info", Message: "skipped"},
+		{Timestamp: "2025-01-13T15:59:59Z", Level: "error", Message: "failed"},
+	}
+
+	buckets := buildTimeline(events)
+	if len(buckets) != timelineBuckets {
+		t.Fatalf("len(buckets) = %d, want %d", len(buckets), timelineBuckets)
+	}
+	if b := buckets[0]; b.Start != "2025-01-13T14:00:00Z" || b.Events != 2 || b.Prompts != 1 || b.Errors != 0 {
+		t.Errorf("first bucket = %+v", b)
+	}
+	if b := buckets[len(buckets)-1]; b.Events != 1 || b.Errors != 1 {
+		t.Errorf("last bucket = %+v", b)
+	}
+
+	if got := buildTimeline([]LogEvent{{Timestamp: "", Message: "x"}}); len(got) != 0 {
+		t.Errorf("buildTimeline without timestamps = %+v, want empty", got)
+	}
+}
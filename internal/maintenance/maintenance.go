@@ -0,0 +1,68 @@
+package maintenance
+
+import (
+	"fmt"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/clipboard"
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/container"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+// Report summarises what a maintenance pass did
+type Report struct {
+	ClipboardPruned int
+	LogsRemoved     int
+	Stopped         []string
+	StateGC         *state.GCReport
+}
+
+// RunOnce performs a single maintenance pass. Failing tasks are reported as warnings
+// so the remaining ones still run.
+func RunOnce(settings *config.Settings) Report {
+	var report Report
+
+	if dir, err := clipboard.GetClipboardDir(); err == nil {
+		n, err := clipboard.Prune(dir, clipboard.RetentionFromSettings(settings.Clipboard))
+		if err != nil {
+			fmt.Printf("Warning: failed to prune clipboard images: %v\n", err)
+		}
+		report.ClipboardPruned = n
+	}
+
+	if days := settings.Maintenance.LogRetentionDays; days > 0 {
+		n, err := state.CleanupAllOldLogs(days)
+		if err != nil {
+			fmt.Printf("Warning: failed to rotate logs: %v\n", err)
+		}
+		report.LogsRemoved = n
+	}
+
+	if minutes := settings.Maintenance.IdleStopMinutes; minutes > 0 {
+		stopped, err := container.StopIdleContainers(time.Duration(minutes) * time.Minute)
+		if err != nil {
+			fmt.Printf("Warning: failed to stop idle containers: %v\n", err)
+		}
+		report.Stopped = stopped
+	}
+
+	if settings.Maintenance.StateGC {
+		// Never prune without an authoritative container list
+		if existing, err := container.ContainerNames(); err != nil {
+			fmt.Printf("Warning: skipping state cleanup: %v\n", err)
+		} else if gc, err := state.CollectGarbage(existing, false); err != nil {
+			fmt.Printf("Warning: failed to clean up orphaned state: %v\n", err)
+		} else {
+			report.StateGC = gc
+		}
+	}
+
+	if settings.Maintenance.ImageGC {
+		if err := container.CleanupUnusedImages(); err != nil {
+			fmt.Printf("Warning: failed to remove unused images: %v\n", err)
+		}
+	}
+
+	return report
+}
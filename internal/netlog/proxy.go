@@ -0,0 +1,301 @@
+// Package netlog runs an HTTP proxy on the host that records which hosts a sandbox
+// session connects to. HTTPS is tunnelled with CONNECT and never decrypted.
+package netlog
+
+import (
+	"context"
+	"crypto/rand"
+	"crypto/subtle"
+	"encoding/base64"
+	"encoding/hex"
+	"errors"
+	"fmt"
+	"io"
+	"net"
+	"net/http"
+	"net/http/httputil"
+	"os"
+	"strings"
+	"sync"
+	"sync/atomic"
+	"syscall"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+	"github.com/thaodangspace/agentsandbox/internal/credproxy"
+	"github.com/thaodangspace/agentsandbox/internal/logs"
+)
+
+// errBlocked is returned for destinations the proxy refuses to connect to
+var errBlocked = errors.New("destination not allowed")
+
+// cgnat is the carrier-grade NAT range, which net.IP does not count as private
+var cgnat = &net.IPNet{IP: net.IPv4(100, 64, 0, 0), Mask: net.CIDRMask(10, 32)}
+
+// Proxy forwards a session's HTTP requests and CONNECT tunnels, recording the host,
+// byte counts and duration of each. It connects from the host, so it refuses the
+// host's own and local-network addresses, and names the DNS policy would not resolve.
+type Proxy struct {
+	token     string
+	listener  net.Listener
+	server    *http.Server
+	transport *http.Transport
+	policy    config.DNSPolicySettings
+
+	// allowLocal lets tests reach servers on loopback
+	allowLocal bool
+
+	mu      sync.Mutex
+	entries []logs.NetworkEntry
+}
+
+// Start listens on a random port on every interface so containers can reach it through
+// the docker bridge. Clients must authenticate with the session token, so the port is
+// not an open proxy for the rest of the network. Hosts are checked against policy,
+// the sandbox's DNS policy, since the proxy resolves names on the host.
+func Start(policy config.DNSPolicySettings) (*Proxy, error) {
+	b := make([]byte, 24)
+	if _, err := rand.Read(b); err != nil {
+		return nil, err
+	}
+	listener, err := net.Listen("tcp", ":0")
+	if err != nil {
+		return nil, fmt.Errorf("failed to start network logging proxy: %w", err)
+	}
+
+	p := &Proxy{
+		token:    hex.EncodeToString(b),
+		listener: listener,
+		policy:   policy,
+	}
+	p.transport = &http.Transport{DialContext: p.dial}
+	p.server = &http.Server{Handler: p, ReadHeaderTimeout: 10 * time.Second}
+	go func() {
+		if err := p.server.Serve(listener); err != nil && !errors.Is(err, http.ErrServerClosed) {
+			fmt.Fprintf(os.Stderr, "Warning: network logging proxy stopped: %v\n", err)
+		}
+	}()
+	return p, nil
+}
+
+// Port is the port the proxy listens on
+func (p *Proxy) Port() int {
+	return p.listener.Addr().(*net.TCPAddr).Port
+}
+
+// EnvArgs returns docker exec arguments that send the session's HTTP(S) traffic
+// through the proxy. Host-side helpers such as the credential proxy are reached directly.
+func (p *Proxy) EnvArgs() []string {
+	proxyURL := fmt.Sprintf("http://agentsandbox:%s@%s:%d", p.token, credproxy.ContainerHost, p.Port())
+	noProxy := "localhost,127.0.0.1,::1," + credproxy.ContainerHost
+	var args []string
+	for _, env := range []string{"HTTP_PROXY", "HTTPS_PROXY", "http_proxy", "https_proxy"} {
+		args = append(args, "-e", env+"="+proxyURL)
+	}
+	return append(args, "-e", "NO_PROXY="+noProxy, "-e", "no_proxy="+noProxy)
+}
+
+// Close stops the proxy and returns the connections it recorded. Tunnels still open
+// when the session ends are not included.
+func (p *Proxy) Close() []logs.NetworkEntry {
+	ctx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
+	defer cancel()
+	_ = p.server.Shutdown(ctx)
+	p.transport.CloseIdleConnections()
+
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	return append([]logs.NetworkEntry(nil), p.entries...)
+}
+
+func (p *Proxy) record(entry logs.NetworkEntry) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	p.entries = append(p.entries, entry)
+}
+
+// ServeHTTP checks the session token, then tunnels CONNECT requests and forwards
+// plain HTTP ones
+func (p *Proxy) ServeHTTP(w http.ResponseWriter, r *http.Request) {
+	if !p.authorized(r) {
+		w.Header().Set("Proxy-Authenticate", `Basic realm="agentsandbox"`)
+		http.Error(w, "proxy authentication required", http.StatusProxyAuthRequired)
+		return
+	}
+
+	entry := logs.NetworkEntry{Time: time.Now(), Method: r.Method, Host: r.Host}
+	var err error
+	if r.Method == http.MethodConnect {
+		err = p.tunnel(w, r, &entry)
+	} else {
+		err = p.forward(w, r, &entry)
+	}
+	entry.DurationMS = time.Since(entry.Time).Milliseconds()
+	if err != nil {
+		entry.Error = err.Error()
+	}
+	p.record(entry)
+}
+
+func (p *Proxy) authorized(r *http.Request) bool {
+	encoded, ok := strings.CutPrefix(r.Header.Get("Proxy-Authorization"), "Basic ")
+	if !ok {
+		return false
+	}
+	decoded, err := base64.StdEncoding.DecodeString(encoded)
+	if err != nil {
+		return false
+	}
+	_, password, _ := strings.Cut(string(decoded), ":")
+	return subtle.ConstantTimeCompare([]byte(password), []byte(p.token)) == 1
+}
+
+// dial connects to addr unless the DNS policy rules out its name or it resolves to an
+// address on the host or its local networks. The address is checked after resolution
+// so a name cannot be pointed at one.
+func (p *Proxy) dial(ctx context.Context, network, addr string) (net.Conn, error) {
+	host, _, err := net.SplitHostPort(addr)
+	if err != nil {
+		return nil, err
+	}
+	if !hostAllowed(p.policy, host) {
+		return nil, fmt.Errorf("%s: %w by the DNS policy", host, errBlocked)
+	}
+	dialer := &net.Dialer{
+		Timeout: 30 * time.Second,
+		Control: func(_, address string, _ syscall.RawConn) error {
+			ip, _, err := net.SplitHostPort(address)
+			if err != nil {
+				return err
+			}
+			if !p.allowLocal && localIP(net.ParseIP(ip)) {
+				return fmt.Errorf("%s (%s): %w on the host or its local network", host, ip, errBlocked)
+			}
+			return nil
+		},
+	}
+	return dialer.DialContext(ctx, network, addr)
+}
+
+// localIP reports whether ip belongs to the host or a network local to it, including
+// cloud metadata endpoints
+func localIP(ip net.IP) bool {
+	return ip == nil || ip.IsLoopback() || ip.IsPrivate() || ip.IsUnspecified() ||
+		ip.IsLinkLocalUnicast() || ip.IsLinkLocalMulticast() || ip.IsMulticast() || cgnat.Contains(ip)
+}
+
+// hostAllowed reports whether the DNS policy lets the sandbox resolve host. IP
+// addresses are left to the address check.
+func hostAllowed(policy config.DNSPolicySettings, host string) bool {
+	if policy.Mode == "" || net.ParseIP(host) != nil {
+		return true
+	}
+	host = strings.ToLower(strings.TrimSuffix(host, "."))
+	listed := false
+	for _, domain := range policy.Domains {
+		domain = strings.Trim(strings.TrimPrefix(strings.ToLower(strings.TrimSpace(domain)), "*."), ".")
+		if host == domain || strings.HasSuffix(host, "."+domain) {
+			listed = true
+			break
+		}
+	}
+	if policy.Mode == "allow" {
+		return listed
+	}
+	return !listed
+}
+
+// dialStatus is the status to answer a failed connection with
+func dialStatus(err error) int {
+	if errors.Is(err, errBlocked) {
+		return http.StatusForbidden
+	}
+	return http.StatusBadGateway
+}
+
+// tunnel connects the client to r.Host and copies bytes both ways until either side
+// closes
+func (p *Proxy) tunnel(w http.ResponseWriter, r *http.Request, entry *logs.NetworkEntry) error {
+	upstream, err := p.dial(r.Context(), "tcp", r.Host)
+	if err != nil {
+		http.Error(w, err.Error(), dialStatus(err))
+		return err
+	}
+	defer upstream.Close()
+
+	hijacker, ok := w.(http.Hijacker)
+	if !ok {
+		http.Error(w, "tunnelling is not supported", http.StatusInternalServerError)
+		return errors.New("tunnelling is not supported")
+	}
+	client, buffered, err := hijacker.Hijack()
+	if err != nil {
+		return err
+	}
+	defer client.Close()
+	if _, err := io.WriteString(client, "HTTP/1.1 200 Connection Established\r\n\r\n"); err != nil {
+		return err
+	}
+
+	sent := make(chan int64, 1)
+	go func() {
+		// The buffered reader holds anything the client sent after the CONNECT line
+		n, _ := io.Copy(upstream, buffered.Reader)
+		if tcp, ok := upstream.(*net.TCPConn); ok {
+			_ = tcp.CloseWrite()
+		}
+		sent <- n
+	}()
+	entry.BytesReceived, _ = io.Copy(client, upstream)
+	// Unblocks the copy above when the server hung up first
+	client.Close()
+	entry.BytesSent = <-sent
+	return nil
+}
+
+// forward relays a plain HTTP request made with an absolute URL
+func (p *Proxy) forward(w http.ResponseWriter, r *http.Request, entry *logs.NetworkEntry) error {
+	if r.URL.Host == "" {
+		http.Error(w, "not a proxy request", http.StatusBadRequest)
+		return errors.New("not a proxy request")
+	}
+
+	sent := &countingReader{ReadCloser: r.Body}
+	r.Body = sent
+	var received *countingReader
+	var proxyErr error
+	proxy := &httputil.ReverseProxy{
+		// The request already names its destination; Rewrite drops the hop-by-hop
+		// headers, including Proxy-Authorization
+		Rewrite:   func(*httputil.ProxyRequest) {},
+		Transport: p.transport,
+		ModifyResponse: func(resp *http.Response) error {
+			received = &countingReader{ReadCloser: resp.Body}
+			resp.Body = received
+			return nil
+		},
+		ErrorHandler: func(w http.ResponseWriter, r *http.Request, err error) {
+			proxyErr = err
+			http.Error(w, err.Error(), dialStatus(err))
+		},
+	}
+	proxy.ServeHTTP(w, r)
+
+	entry.Host = r.URL.Host
+	entry.BytesSent = sent.n.Load()
+	if received != nil {
+		entry.BytesReceived = received.n.Load()
+	}
+	return proxyErr
+}
+
+type countingReader struct {
+	io.ReadCloser
+	n atomic.Int64
+}
+
+func (c *countingReader) Read(b []byte) (int, error) {
+	n, err := c.ReadCloser.Read(b)
+	c.n.Add(int64(n))
+	return n, err
+}
@@ -0,0 +1,160 @@
+package netlog
+
+import (
+	"fmt"
+	"io"
+	"net"
+	"net/http"
+	"net/http/httptest"
+	"net/url"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/config"
+)
+
+func TestProxyLogsConnections(t *testing.T) {
+	plain := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.Header.Get("Proxy-Authorization") != "" {
+			t.Error("the proxy token was forwarded upstream")
+		}
+		io.WriteString(w, "hello")
+	}))
+	defer plain.Close()
+	secure := httptest.NewTLSServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		io.WriteString(w, "secret")
+	}))
+	defer secure.Close()
+
+	p, err := Start(config.DNSPolicySettings{})
+	if err != nil {
+		t.Fatal(err)
+	}
+	// The test servers listen on loopback
+	p.allowLocal = true
+	if args := strings.Join(p.EnvArgs(), " "); !strings.Contains(args, "HTTPS_PROXY=http://agentsandbox:"+p.token+"@") {
+		t.Errorf("EnvArgs() = %s, want the proxy with its token", args)
+	}
+
+	proxyURL := func(token string) *url.URL {
+		u, _ := url.Parse(fmt.Sprintf("http://agentsandbox:%s@127.0.0.1:%d", token, p.Port()))
+		return u
+	}
+	client := secure.Client()
+	transport := client.Transport.(*http.Transport)
+	transport.Proxy = http.ProxyURL(proxyURL(p.token))
+	transport.DisableKeepAlives = true
+
+	for _, target := range []string{plain.URL, secure.URL} {
+		resp, err := client.Get(target)
+		if err != nil {
+			t.Fatal(err)
+		}
+		io.Copy(io.Discard, resp.Body)
+		resp.Body.Close()
+		if resp.StatusCode != http.StatusOK {
+			t.Errorf("GET %s through the proxy: status %d", target, resp.StatusCode)
+		}
+	}
+
+	transport.Proxy = http.ProxyURL(proxyURL("guess"))
+	if _, err := client.Get(secure.URL); err == nil {
+		t.Error("expected the proxy to refuse a wrong token")
+	}
+
+	// Tunnels are recorded once both sides have hung up
+	deadline := time.Now().Add(5 * time.Second)
+	for {
+		p.mu.Lock()
+		n := len(p.entries)
+		p.mu.Unlock()
+		if n >= 2 || time.Now().After(deadline) {
+			break
+		}
+		time.Sleep(10 * time.Millisecond)
+	}
+	entries := p.Close()
+	if len(entries) != 2 {
+		t.Fatalf("recorded %d connections, want 2: %+v", len(entries), entries)
+	}
+	byMethod := map[string]string{}
+	for _, entry := range entries {
+		byMethod[entry.Method] = entry.Host
+		if entry.BytesReceived == 0 || entry.Error != "" {
+			t.Errorf("unexpected entry %+v", entry)
+		}
+	}
+	if byMethod[http.MethodGet] != strings.TrimPrefix(plain.URL, "http://") || byMethod[http.MethodConnect] != strings.TrimPrefix(secure.URL, "https://") {
+		t.Errorf("recorded hosts %v", byMethod)
+	}
+}
+
+func TestProxyRefusesLocalDestinations(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		t.Error("the proxy connected to a loopback server")
+	}))
+	defer server.Close()
+
+	p, err := Start(config.DNSPolicySettings{})
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer p.Close()
+	proxyURL, _ := url.Parse(fmt.Sprintf("http://agentsandbox:%s@127.0.0.1:%d", p.token, p.Port()))
+	client := &http.Client{Transport: &http.Transport{Proxy: http.ProxyURL(proxyURL)}}
+
+	resp, err := client.Get(server.URL)
+	if err != nil {
+		t.Fatal(err)
+	}
+	resp.Body.Close()
+	if resp.StatusCode != http.StatusForbidden {
+		t.Errorf("GET %s through the proxy: status %d, want %d", server.URL, resp.StatusCode, http.StatusForbidden)
+	}
+}
+
+func TestLocalIP(t *testing.T) {
+	for ip, want := range map[string]bool{
+		"127.0.0.1":       true,
+		"::1":             true,
+		"10.1.2.3":        true,
+		"192.168.1.10":    true,
+		"172.17.0.1":      true,
+		"169.254.169.254": true,
+		"100.100.100.200": true,
+		"fd00::1":         true,
+		"0.0.0.0":         true,
+		"93.184.216.34":   false,
+		"2606:4700::1111": false,
+	} {
+		if got := localIP(net.ParseIP(ip)); got != want {
+			t.Errorf("localIP(%s) = %v, want %v", ip, got, want)
+		}
+	}
+}
+
+func TestHostAllowed(t *testing.T) {
+	allow := config.DNSPolicySettings{Mode: "allow", Domains: []string{"github.com", "*.npmjs.org"}}
+	deny := config.DNSPolicySettings{Mode: "deny", Domains: []string{"evil.example"}}
+	tests := []struct {
+		policy config.DNSPolicySettings
+		host   string
+		want   bool
+	}{
+		{config.DNSPolicySettings{}, "anything.example", true},
+		{allow, "github.com", true},
+		{allow, "api.github.com", true},
+		{allow, "registry.npmjs.org", true},
+		{allow, "notgithub.com", false},
+		{allow, "example.com", false},
+		{deny, "evil.example", false},
+		{deny, "cdn.evil.example", false},
+		{deny, "good.example", true},
+	}
+	for _, tt := range tests {
+		if got := hostAllowed(tt.policy, tt.host); got != tt.want {
+			t.Errorf("hostAllowed(%s %v, %s) = %v, want %v", tt.policy.Mode, tt.policy.Domains, tt.host, got, tt.want)
+		}
+	}
+}
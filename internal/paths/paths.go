@@ -0,0 +1,153 @@
+// Package paths locates agentsandbox's files following the XDG base directory spec
+package paths
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+)
+
+const appName = "agentsandbox"
+
+// configEntries are the legacy entries that stay in the config directory; clipboard
+// images go to the cache directory and everything else is state. The old shared
+// share directory is left where it is for the user to empty.
+var configEntries = map[string]bool{
+	"settings.json": true,
+	"share":         true,
+}
+
+// cacheEntries are the legacy entries that move to the cache directory
+var cacheEntries = map[string]bool{
+	"clipboard": true,
+}
+
+// ConfigDir returns $XDG_CONFIG_HOME/agentsandbox, defaulting to ~/.config/agentsandbox.
+// It holds settings.json.
+func ConfigDir() (string, error) {
+	return baseDir("XDG_CONFIG_HOME", ".config")
+}
+
+// StateDir returns $XDG_STATE_HOME/agentsandbox, defaulting to ~/.local/state/agentsandbox.
+// It holds session logs, per-container state, PID files and crash reports.
+func StateDir() (string, error) {
+	return baseDir("XDG_STATE_HOME", filepath.Join(".local", "state"))
+}
+
+// CacheDir returns $XDG_CACHE_HOME/agentsandbox, defaulting to ~/.cache/agentsandbox.
+// It holds captured clipboard images.
+func CacheDir() (string, error) {
+	return baseDir("XDG_CACHE_HOME", ".cache")
+}
+
+// EnsureDir creates dir if it does not exist and returns it
+func EnsureDir(dir string, err error) (string, error) {
+	if err != nil {
+		return "", err
+	}
+	if err := os.MkdirAll(dir, 0755); err != nil {
+		return "", err
+	}
+	return dir, nil
+}
+
+// baseDir resolves the agentsandbox directory under an XDG base directory. Relative
+// values are ignored as the spec requires.
+func baseDir(env, fallback string) (string, error) {
+	if dir := os.Getenv(env); dir != "" && filepath.IsAbs(dir) {
+		return filepath.Join(dir, appName), nil
+	}
+	homeDir, err := os.UserHomeDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(homeDir, fallback, appName), nil
+}
+
+// legacyDir is where every file lived before XDG directories were supported
+func legacyDir() (string, error) {
+	homeDir, err := os.UserHomeDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(homeDir, ".config", appName), nil
+}
+
+// Move is a legacy entry and the path MigrateLegacy moves it to
+type Move struct {
+	From string
+	To   string
+}
+
+// LegacyMoves returns the moves MigrateLegacy would make. Entries that already exist
+// at their destination are left alone.
+func LegacyMoves() ([]Move, error) {
+	legacy, err := legacyDir()
+	if err != nil {
+		return nil, err
+	}
+	entries, err := os.ReadDir(legacy)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return nil, nil
+		}
+		return nil, err
+	}
+
+	configDir, err := ConfigDir()
+	if err != nil {
+		return nil, err
+	}
+	stateDir, err := StateDir()
+	if err != nil {
+		return nil, err
+	}
+	cacheDir, err := CacheDir()
+	if err != nil {
+		return nil, err
+	}
+
+	var moves []Move
+	for _, entry := range entries {
+		destDir := stateDir
+		switch {
+		case configEntries[entry.Name()]:
+			destDir = configDir
+		case cacheEntries[entry.Name()]:
+			destDir = cacheDir
+		}
+		if destDir == legacy {
+			continue
+		}
+
+		dest := filepath.Join(destDir, entry.Name())
+		if _, err := os.Lstat(dest); err == nil {
+			continue
+		}
+		moves = append(moves, Move{From: filepath.Join(legacy, entry.Name()), To: dest})
+	}
+	return moves, nil
+}
+
+// MigrateLegacy moves files from ~/.config/agentsandbox into the config, state and
+// cache directories, so it is safe to run more than once
+func MigrateLegacy() error {
+	moves, err := LegacyMoves()
+	if err != nil {
+		return err
+	}
+	for _, move := range moves {
+		if err := os.MkdirAll(filepath.Dir(move.To), 0755); err != nil {
+			return err
+		}
+		if err := os.Rename(move.From, move.To); err != nil {
+			return fmt.Errorf("failed to move %s to %s: %w", filepath.Base(move.From), filepath.Dir(move.To), err)
+		}
+	}
+
+	// Only removed once empty, i.e. when the config directory moved as well
+	if legacy, err := legacyDir(); err == nil {
+		os.Remove(legacy)
+	}
+	return nil
+}
@@ -0,0 +1,97 @@
+package paths
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestBaseDirs(t *testing.T) {
+	home := t.TempDir()
+	t.Setenv("HOME", home)
+
+	tests := []struct {
+		name  string
+		env   string
+		value string
+		get   func() (string, error)
+		want  string
+	}{
+		{"config default", "XDG_CONFIG_HOME", "", ConfigDir, filepath.Join(home, ".config", "agentsandbox")},
+		{"config override", "XDG_CONFIG_HOME", "/xdg/config", ConfigDir, "/xdg/config/agentsandbox"},
+		{"state default", "XDG_STATE_HOME", "", StateDir, filepath.Join(home, ".local", "state", "agentsandbox")},
+		{"state override", "XDG_STATE_HOME", "/xdg/state", StateDir, "/xdg/state/agentsandbox"},
+		{"state relative ignored", "XDG_STATE_HOME", "relative/state", StateDir, filepath.Join(home, ".local", "state", "agentsandbox")},
+		{"cache default", "XDG_CACHE_HOME", "", CacheDir, filepath.Join(home, ".cache", "agentsandbox")},
+		{"cache override", "XDG_CACHE_HOME", "/xdg/cache", CacheDir, "/xdg/cache/agentsandbox"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Setenv(tt.env, tt.value)
+			got, err := tt.get()
+			if err != nil {
+				t.Fatal(err)
+			}
+			if got != tt.want {
+				t.Errorf("got %s, want %s", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestMigrateLegacy(t *testing.T) {
+	home := t.TempDir()
+	t.Setenv("HOME", home)
+	t.Setenv("XDG_CONFIG_HOME", "")
+	t.Setenv("XDG_STATE_HOME", "")
+	t.Setenv("XDG_CACHE_HOME", "")
+
+	legacy := filepath.Join(home, ".config", "agentsandbox")
+	for _, rel := range []string{"settings.json", "share/notes.txt", "clipboard/a.png", "logs/app/agentsandbox-app/session.jsonl", "last_container"} {
+		path := filepath.Join(legacy, rel)
+		if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(path, []byte(rel), 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+
+	// An existing destination wins over the legacy copy
+	stateDir := filepath.Join(home, ".local", "state", "agentsandbox")
+	if err := os.MkdirAll(stateDir, 0755); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(stateDir, "last_container"), []byte("newer"), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	if err := MigrateLegacy(); err != nil {
+		t.Fatalf("MigrateLegacy() error = %v", err)
+	}
+	// Running again is harmless
+	if err := MigrateLegacy(); err != nil {
+		t.Fatalf("second MigrateLegacy() error = %v", err)
+	}
+
+	for _, rel := range []string{
+		".config/agentsandbox/settings.json",
+		".config/agentsandbox/share/notes.txt",
+		".cache/agentsandbox/clipboard/a.png",
+		".local/state/agentsandbox/logs/app/agentsandbox-app/session.jsonl",
+	} {
+		if _, err := os.Stat(filepath.Join(home, rel)); err != nil {
+			t.Errorf("expected %s after migration: %v", rel, err)
+		}
+	}
+	for _, rel := range []string{"clipboard", "logs"} {
+		if _, err := os.Stat(filepath.Join(legacy, rel)); !os.IsNotExist(err) {
+			t.Errorf("expected %s to be moved out of the legacy directory", rel)
+		}
+	}
+
+	data, err := os.ReadFile(filepath.Join(stateDir, "last_container"))
+	if err != nil || string(data) != "newer" {
+		t.Errorf("last_container = %q, %v; want the existing file kept", data, err)
+	}
+}
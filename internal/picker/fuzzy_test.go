@@ -0,0 +1,46 @@
+package picker
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestMatch(t *testing.T) {
+	tests := []struct {
+		query string
+		text  string
+		ok    bool
+	}{
+		{"", "anything", true},
+		{"api", "myrepo-api", true},
+		{"MRA", "myrepo-api", true},
+		{"mra", "myrepo-api", true},
+		{"apim", "myrepo-api", false},
+		{"xyz", "myrepo-api", false},
+	}
+	for _, tt := range tests {
+		t.Run(tt.query+"/"+tt.text, func(t *testing.T) {
+			if _, ok := Match(tt.query, tt.text); ok != tt.ok {
+				t.Errorf("Match(%q, %q) ok = %v, want %v", tt.query, tt.text, ok, tt.ok)
+			}
+		})
+	}
+}
+
+func TestFilterRanksContiguousMatchesFirst(t *testing.T) {
+	items := []Item{
+		{Text: "agentsandbox-wide-table"},
+		{Text: "agentsandbox-web   running"},
+		{Text: "agentsandbox-webhooks"},
+	}
+
+	got := Filter("web", items)
+	want := []int{1, 2, 0}
+	if !reflect.DeepEqual(got, want) {
+		t.Errorf("Filter(web) = %v, want %v", got, want)
+	}
+
+	if got := Filter("zzz", items); len(got) != 0 {
+		t.Errorf("Filter(zzz) = %v, want none", got)
+	}
+}
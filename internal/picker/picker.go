@@ -0,0 +1,207 @@
+package picker
+
+import (
+	"bufio"
+	"fmt"
+	"os"
+	"os/exec"
+	"strconv"
+	"strings"
+
+	"github.com/thaodangspace/agentsandbox/internal/ui"
+)
+
+// previewHeight is the number of preview lines shown under the list
+const previewHeight = 10
+
+// Item is one selectable row
+type Item struct {
+	// Text is shown in the list and matched against the query
+	Text string
+	// Preview returns the lines shown for the highlighted item; it may be nil
+	Preview func() []string
+}
+
+// Pick lets the user choose an item with an inline fuzzy finder: type to filter, move
+// with the arrow keys (or Ctrl+P/Ctrl+N), Enter to select and Esc to cancel. Without an
+// interactive terminal it falls back to a numbered prompt. It returns -1 when cancelled.
+func Pick(header string, items []Item) (int, error) {
+	if len(items) == 0 {
+		return -1, nil
+	}
+
+	tty, err := os.OpenFile("/dev/tty", os.O_RDWR, 0)
+	if err != nil {
+		return pickNumber(header, items)
+	}
+	defer tty.Close()
+
+	saved, err := stty(tty, "-g")
+	if err != nil {
+		return pickNumber(header, items)
+	}
+	if _, err := stty(tty, "raw", "-echo"); err != nil {
+		return pickNumber(header, items)
+	}
+	defer stty(tty, saved)
+
+	// Use the alternate screen so the picker leaves no trace behind
+	fmt.Fprint(tty, "\x1b[?1049h\x1b[?25l")
+	defer fmt.Fprint(tty, "\x1b[?25h\x1b[?1049l")
+
+	p := &session{tty: tty, header: header, items: items, previews: make(map[int][]string)}
+	return p.run()
+}
+
+// session holds the state of one interactive pick
+type session struct {
+	tty      *os.File
+	header   string
+	items    []Item
+	query    string
+	selected int
+	previews map[int][]string
+}
+
+func (p *session) run() (int, error) {
+	buf := make([]byte, 32)
+	for {
+		matches := Filter(p.query, p.items)
+		if p.selected >= len(matches) {
+			p.selected = len(matches) - 1
+		}
+		if p.selected < 0 {
+			p.selected = 0
+		}
+		p.render(matches)
+
+		n, err := p.tty.Read(buf)
+		if err != nil {
+			return -1, err
+		}
+		key := buf[:n]
+
+		switch {
+		case string(key) == "\x1b[A" || string(key) == "\x1bOA" || key[0] == 16: // up, Ctrl+P
+			p.selected--
+		case string(key) == "\x1b[B" || string(key) == "\x1bOB" || key[0] == 14: // down, Ctrl+N
+			p.selected++
+		case key[0] == '\r' || key[0] == '\n':
+			if len(matches) == 0 {
+				continue
+			}
+			return matches[p.selected], nil
+		case key[0] == 3 || (key[0] == 27 && n == 1): // Ctrl+C, Esc
+			return -1, nil
+		case key[0] == 127 || key[0] == 8: // backspace
+			if r := []rune(p.query); len(r) > 0 {
+				p.query = string(r[:len(r)-1])
+			}
+			p.selected = 0
+		case key[0] == 21: // Ctrl+U
+			p.query = ""
+			p.selected = 0
+		case key[0] >= 32 && key[0] != 127:
+			p.query += string(key)
+			p.selected = 0
+		}
+	}
+}
+
+func (p *session) render(matches []int) {
+	rows, cols := terminalSize(p.tty)
+	listHeight := rows - previewHeight - 4
+	if listHeight < 3 {
+		listHeight = 3
+	}
+
+	var b strings.Builder
+	b.WriteString("\x1b[H\x1b[2J")
+	fmt.Fprintf(&b, "\x1b[2m  %s\x1b[0m\r\n", ui.Truncate(p.header, cols-2))
+	fmt.Fprintf(&b, "> %s\x1b[7m \x1b[0m  \x1b[2m%d/%d\x1b[0m\r\n", p.query, len(matches), len(p.items))
+
+	// Scroll so the selected row stays visible
+	start := 0
+	if p.selected >= listHeight {
+		start = p.selected - listHeight + 1
+	}
+	for i := start; i < len(matches) && i < start+listHeight; i++ {
+		text := ui.Truncate(p.items[matches[i]].Text, cols-2)
+		if i == p.selected {
+			fmt.Fprintf(&b, "\x1b[7m> %s\x1b[0m\r\n", text)
+		} else {
+			fmt.Fprintf(&b, "  %s\r\n", text)
+		}
+	}
+
+	if len(matches) > 0 {
+		fmt.Fprintf(&b, "\x1b[2m%s\x1b[0m\r\n", ui.Rule(cols))
+		lines := p.preview(matches[p.selected])
+		if len(lines) > previewHeight {
+			lines = lines[len(lines)-previewHeight:]
+		}
+		for _, line := range lines {
+			fmt.Fprintf(&b, "%s\r\n", ui.Truncate(line, cols))
+		}
+	}
+
+	fmt.Fprint(p.tty, b.String())
+}
+
+// preview returns the cached preview lines of an item
+func (p *session) preview(index int) []string {
+	if lines, ok := p.previews[index]; ok {
+		return lines
+	}
+	var lines []string
+	if p.items[index].Preview != nil {
+		lines = p.items[index].Preview()
+	}
+	p.previews[index] = lines
+	return lines
+}
+
+// pickNumber is the fallback prompt used when no terminal is available
+func pickNumber(header string, items []Item) (int, error) {
+	fmt.Printf("\n%-5s %s\n", "No.", header)
+	fmt.Println(strings.Repeat("-", 80))
+	for i, item := range items {
+		fmt.Printf("%-5d %s\n", i+1, item.Text)
+	}
+
+	fmt.Print("Select a container to attach (number, or press Enter to cancel): ")
+	input, _ := bufio.NewReader(os.Stdin).ReadString('\n')
+	input = strings.TrimSpace(input)
+	if input == "" {
+		return -1, nil
+	}
+
+	num, err := strconv.Atoi(input)
+	if err != nil || num < 1 || num > len(items) {
+		return -1, fmt.Errorf("invalid selection %q", input)
+	}
+	return num - 1, nil
+}
+
+// stty runs stty against the terminal and returns its output
+func stty(tty *os.File, args ...string) (string, error) {
+	cmd := exec.Command("stty", args...)
+	cmd.Stdin = tty
+	output, err := cmd.Output()
+	return strings.TrimSpace(string(output)), err
+}
+
+// terminalSize returns the terminal's rows and columns, defaulting to 24x80
+func terminalSize(tty *os.File) (int, int) {
+	output, err := stty(tty, "size")
+	if err == nil {
+		if fields := strings.Fields(output); len(fields) == 2 {
+			rows, err1 := strconv.Atoi(fields[0])
+			cols, err2 := strconv.Atoi(fields[1])
+			if err1 == nil && err2 == nil && rows > 0 && cols > 0 {
+				return rows, cols
+			}
+		}
+	}
+	return 24, 80
+}
@@ -0,0 +1,104 @@
+package prompts
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"regexp"
+	"sort"
+	"strings"
+
+	"github.com/thaodangspace/agentsandbox/internal/git"
+)
+
+// Dir is the project-relative directory holding the prompt library
+const Dir = ".agentsandbox/prompts"
+
+var variablePattern = regexp.MustCompile(`\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}`)
+
+// Prompt is a canned agent task stored as Markdown in the project
+type Prompt struct {
+	Name string
+	Path string
+	// Summary is the first non-empty line of the prompt
+	Summary string
+}
+
+// List returns the prompts in the project's library, sorted by name
+func List(projectDir string) ([]Prompt, error) {
+	matches, err := filepath.Glob(filepath.Join(projectDir, Dir, "*.md"))
+	if err != nil {
+		return nil, err
+	}
+	sort.Strings(matches)
+
+	var prompts []Prompt
+	for _, path := range matches {
+		data, err := os.ReadFile(path)
+		if err != nil {
+			return nil, err
+		}
+		prompts = append(prompts, Prompt{
+			Name:    strings.TrimSuffix(filepath.Base(path), ".md"),
+			Path:    path,
+			Summary: summary(string(data)),
+		})
+	}
+	return prompts, nil
+}
+
+// Load reads a prompt from the library by name (with or without .md), or from a file
+// when name is a path
+func Load(projectDir, name string) (string, error) {
+	path := name
+	if !strings.ContainsRune(name, filepath.Separator) {
+		path = filepath.Join(projectDir, Dir, strings.TrimSuffix(name, ".md")+".md")
+	}
+
+	data, err := os.ReadFile(path)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return "", fmt.Errorf("prompt %q not found in %s", name, filepath.Join(projectDir, Dir))
+		}
+		return "", err
+	}
+	return string(data), nil
+}
+
+// Variables returns the built-in template variables for a project
+func Variables(projectDir string) map[string]string {
+	vars := map[string]string{
+		"project": filepath.Base(projectDir),
+		"branch":  git.CurrentBranch(projectDir),
+	}
+	if files, err := git.ChangedFiles(projectDir); err == nil {
+		vars["files_changed"] = strings.Join(files, "\n")
+	}
+	return vars
+}
+
+// Render substitutes {{name}} placeholders and returns the names it could not resolve,
+// which are left in place
+func Render(text string, vars map[string]string) (string, []string) {
+	var missing []string
+	rendered := variablePattern.ReplaceAllStringFunc(text, func(match string) string {
+		name := variablePattern.FindStringSubmatch(match)[1]
+		if value, ok := vars[name]; ok {
+			return value
+		}
+		missing = append(missing, name)
+		return match
+	})
+	return rendered, missing
+}
+
+// summary returns the first non-empty line without Markdown heading markers
+func summary(text string) string {
+	for _, line := range strings.Split(text, "\n") {
+		line = strings.TrimSpace(strings.TrimLeft(line, "# "))
+		if line != "" {
+			return line
+		}
+	}
+	return ""
+}
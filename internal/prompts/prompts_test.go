@@ -0,0 +1,40 @@
+package prompts
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestRender(t *testing.T) {
+	vars := map[string]string{"branch": "fix-lints", "files_changed": "a.go\nb.go"}
+
+	tests := []struct {
+		name        string
+		text        string
+		want        string
+		wantMissing []string
+	}{
+		{"substitutes", "Fix lints on {{branch}}", "Fix lints on fix-lints", nil},
+		{"spaces", "Files:\n{{ files_changed }}", "Files:\na.go\nb.go", nil},
+		{"missing kept", "Ticket {{ticket}} on {{branch}}", "Ticket {{ticket}} on fix-lints", []string{"ticket"}},
+		{"no placeholders", "Run the tests", "Run the tests", nil},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, missing := Render(tt.text, vars)
+			if got != tt.want {
+				t.Errorf("Render() = %q, want %q", got, tt.want)
+			}
+			if !reflect.DeepEqual(missing, tt.wantMissing) {
+				t.Errorf("Render() missing = %v, want %v", missing, tt.wantMissing)
+			}
+		})
+	}
+}
+
+func TestSummary(t *testing.T) {
+	if got := summary("\n# Fix lints\n\nRun the linter"); got != "Fix lints" {
+		t.Errorf("summary() = %q", got)
+	}
+}
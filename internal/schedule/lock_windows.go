@@ -0,0 +1,10 @@
+//go:build windows
+
+package schedule
+
+import "os"
+
+// lockFile is a no-op on Windows, which has no cron to start overlapping run-due calls
+func lockFile(f *os.File) error {
+	return nil
+}
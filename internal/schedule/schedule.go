@@ -0,0 +1,174 @@
+package schedule
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+	"strconv"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/headless"
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+// Task is a prompt run headlessly in a project's sandbox on a cron schedule
+type Task struct {
+	ID        string    `json:"id"`
+	Cron      string    `json:"cron"`
+	Prompt    string    `json:"prompt"`
+	Dir       string    `json:"dir"`
+	Agent     string    `json:"agent"`
+	CreatedAt time.Time `json:"created_at"`
+	LastRun   time.Time `json:"last_run,omitempty"`
+}
+
+// NextRun returns when the task is next due
+func (t Task) NextRun() (time.Time, error) {
+	c, err := ParseCron(t.Cron)
+	if err != nil {
+		return time.Time{}, err
+	}
+	from := t.LastRun
+	if from.IsZero() {
+		from = t.CreatedAt
+	}
+	return c.Next(from), nil
+}
+
+// Due reports whether the task should run at now
+func (t Task) Due(now time.Time) bool {
+	next, err := t.NextRun()
+	return err == nil && !next.IsZero() && !next.After(now)
+}
+
+// getSchedulesFile returns the path of the persisted task list
+func getSchedulesFile() (string, error) {
+	stateDir, err := state.GetStateDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(stateDir, "schedules.json"), nil
+}
+
+// Load returns all scheduled tasks ordered by ID
+func Load() ([]Task, error) {
+	file, err := getSchedulesFile()
+	if err != nil {
+		return nil, err
+	}
+
+	data, err := os.ReadFile(file)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return nil, nil
+		}
+		return nil, err
+	}
+
+	var tasks []Task
+	if err := json.Unmarshal(data, &tasks); err != nil {
+		return nil, fmt.Errorf("failed to parse %s: %w", file, err)
+	}
+	sort.Slice(tasks, func(i, j int) bool {
+		a, _ := strconv.Atoi(tasks[i].ID)
+		b, _ := strconv.Atoi(tasks[j].ID)
+		return a < b
+	})
+	return tasks, nil
+}
+
+// Save persists the task list
+func Save(tasks []Task) error {
+	file, err := getSchedulesFile()
+	if err != nil {
+		return err
+	}
+
+	data, err := json.MarshalIndent(tasks, "", "  ")
+	if err != nil {
+		return err
+	}
+	return os.WriteFile(file, data, 0644)
+}
+
+// Add validates and stores a new task, assigning it the next free ID
+func Add(task Task) (Task, error) {
+	if _, err := ParseCron(task.Cron); err != nil {
+		return task, err
+	}
+
+	err := update(func(tasks []Task) ([]Task, error) {
+		next := 1
+		for _, t := range tasks {
+			if id, _ := strconv.Atoi(t.ID); id >= next {
+				next = id + 1
+			}
+		}
+		task.ID = strconv.Itoa(next)
+		task.CreatedAt = time.Now()
+		return append(tasks, task), nil
+	})
+	return task, err
+}
+
+// Remove deletes the task with the given ID
+func Remove(id string) error {
+	return update(func(tasks []Task) ([]Task, error) {
+		for i, t := range tasks {
+			if t.ID == id {
+				return append(tasks[:i], tasks[i+1:]...), nil
+			}
+		}
+		return nil, fmt.Errorf("no scheduled task with ID %s", id)
+	})
+}
+
+// ClaimDue marks every task due at now as run and returns them. The task list stays
+// locked from reading to saving, so overlapping run-due calls never claim the same task.
+func ClaimDue(now time.Time) ([]Task, error) {
+	var due []Task
+	err := update(func(tasks []Task) ([]Task, error) {
+		for i := range tasks {
+			if tasks[i].Due(now) {
+				tasks[i].LastRun = now
+				due = append(due, tasks[i])
+			}
+		}
+		return tasks, nil
+	})
+	return due, err
+}
+
+// update applies change to the task list while holding the schedules lock, so
+// concurrent commands cannot overwrite each other's changes
+func update(change func([]Task) ([]Task, error)) error {
+	file, err := getSchedulesFile()
+	if err != nil {
+		return err
+	}
+	lock, err := os.OpenFile(file+".lock", os.O_CREATE|os.O_RDWR, 0644)
+	if err != nil {
+		return fmt.Errorf("failed to open schedules lock: %w", err)
+	}
+	defer lock.Close()
+	if err := lockFile(lock); err != nil {
+		return fmt.Errorf("failed to lock schedules: %w", err)
+	}
+
+	tasks, err := Load()
+	if err != nil {
+		return err
+	}
+	tasks, err = change(tasks)
+	if err != nil {
+		return err
+	}
+	return Save(tasks)
+}
+
+// Run executes the task headlessly and returns its session log
+func Run(task Task) (string, error) {
+	return headless.Run(task.Dir, task.Agent, task.Prompt, map[string]interface{}{"schedule_id": task.ID, "cron": task.Cron})
+}
@@ -0,0 +1,33 @@
+package schedule
+
+import (
+	"testing"
+	"time"
+)
+
+func TestClaimDue(t *testing.T) {
+	t.Setenv("XDG_STATE_HOME", t.TempDir())
+
+	task, err := Add(Task{Cron: "* * * * *", Prompt: "check", Dir: "/project", Agent: "claude"})
+	if err != nil {
+		t.Fatalf("Add: %v", err)
+	}
+
+	now := time.Now().Add(2 * time.Minute)
+	claimed, err := ClaimDue(now)
+	if err != nil {
+		t.Fatalf("ClaimDue: %v", err)
+	}
+	if len(claimed) != 1 || claimed[0].ID != task.ID {
+		t.Fatalf("ClaimDue = %+v, want task %s", claimed, task.ID)
+	}
+
+	// A second run-due at the same moment finds nothing left to start
+	claimed, err = ClaimDue(now)
+	if err != nil {
+		t.Fatalf("ClaimDue: %v", err)
+	}
+	if len(claimed) != 0 {
+		t.Errorf("second ClaimDue = %+v, want none", claimed)
+	}
+}
@@ -0,0 +1,61 @@
+package secrets
+
+import (
+	"fmt"
+	"regexp"
+)
+
+// redacted replaces every credential found by a Redactor
+const redacted = "[REDACTED]"
+
+// builtinRedactions match well-known credential formats. When a pattern has a capture
+// group, the group is kept in front of the placeholder so assignments stay readable.
+var builtinRedactions = []*regexp.Regexp{
+	regexp.MustCompile(`-----BEGIN [A-Z ]*PRIVATE KEY-----[\s\S]*?-----END [A-Z ]*PRIVATE KEY-----`),
+	// AWS access key IDs and secret access keys
+	regexp.MustCompile(`\b(?:AKIA|ASIA)[0-9A-Z]{16}\b`),
+	regexp.MustCompile(`(?i)(aws_secret_access_key["']?\s*[=:]\s*["']?)[A-Za-z0-9/+]{40}`),
+	// GitHub personal, OAuth, app and fine-grained tokens
+	regexp.MustCompile(`\bgh[pousr]_[A-Za-z0-9]{36,}`),
+	regexp.MustCompile(`\bgithub_pat_[A-Za-z0-9_]{22,}`),
+	// OpenAI and Anthropic API keys
+	regexp.MustCompile(`\bsk-[A-Za-z0-9_-]{20,}`),
+	// JSON Web Tokens
+	regexp.MustCompile(`\beyJ[A-Za-z0-9_-]{8,}\.eyJ[A-Za-z0-9_-]{8,}\.[A-Za-z0-9_-]{8,}`),
+}
+
+// Redactor replaces credentials in text with a placeholder. A nil Redactor leaves
+// text unchanged.
+type Redactor struct {
+	patterns []*regexp.Regexp
+}
+
+// NewRedactor returns a Redactor for the built-in credential formats plus the given
+// regular expressions
+func NewRedactor(extra []string) (*Redactor, error) {
+	patterns := append([]*regexp.Regexp(nil), builtinRedactions...)
+	for _, expr := range extra {
+		pattern, err := regexp.Compile(expr)
+		if err != nil {
+			return nil, fmt.Errorf("invalid redaction pattern %q: %w", expr, err)
+		}
+		patterns = append(patterns, pattern)
+	}
+	return &Redactor{patterns: patterns}, nil
+}
+
+// Redact returns s with every credential replaced by [REDACTED]
+func (r *Redactor) Redact(s string) string {
+	if r == nil {
+		return s
+	}
+	for _, pattern := range r.patterns {
+		s = pattern.ReplaceAllStringFunc(s, func(match string) string {
+			if sub := pattern.FindStringSubmatch(match); len(sub) > 1 {
+				return sub[1] + redacted
+			}
+			return redacted
+		})
+	}
+	return s
+}
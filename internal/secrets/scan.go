@@ -0,0 +1,127 @@
+package secrets
+
+import (
+	"bytes"
+	"io/fs"
+	"os"
+	"path"
+	"path/filepath"
+	"sort"
+	"strings"
+)
+
+// maxContentBytes limits how much of a file is read when checking its contents
+const maxContentBytes = 64 * 1024
+
+// Finding is a file that looks like it contains credentials or key material
+type Finding struct {
+	// Path is the slash-separated path relative to the scanned directory
+	Path   string
+	Reason string
+}
+
+// rule matches files by name and optionally requires a marker in their contents
+type rule struct {
+	patterns []string
+	markers  []string
+	reason   string
+}
+
+var rules = []rule{
+	{patterns: []string{"id_rsa", "id_dsa", "id_ecdsa", "id_ed25519"}, reason: "SSH private key"},
+	{patterns: []string{"*.pem", "*.key"}, markers: []string{"PRIVATE KEY"}, reason: "private key"},
+	{patterns: []string{"*.p12", "*.pfx", "*.jks", "*.keystore"}, reason: "certificate keystore"},
+	{patterns: []string{".aws/credentials", ".aws/config"}, reason: "AWS credentials"},
+	{patterns: []string{"kubeconfig", ".kube/config"}, reason: "Kubernetes credentials"},
+	{patterns: []string{".npmrc", ".yarnrc.yml"}, markers: []string{"_authToken", "_auth=", "npmAuthToken"}, reason: "npm registry token"},
+	{patterns: []string{".pypirc", ".netrc", ".git-credentials"}, reason: "stored credentials"},
+	{patterns: []string{".docker/config.json"}, markers: []string{"\"auth\""}, reason: "Docker registry credentials"},
+	{patterns: []string{"*.tfstate"}, reason: "Terraform state"},
+}
+
+// skipDirs are never descended into while scanning
+var skipDirs = map[string]bool{
+	".git":         true,
+	"node_modules": true,
+	"vendor":       true,
+	".venv":        true,
+	"target":       true,
+}
+
+// matches reports whether the rule's name patterns match a relative path.
+// Patterns containing a slash match the end of the path, others match the file name.
+func (r rule) matches(rel string) bool {
+	for _, pattern := range r.patterns {
+		if strings.Contains(pattern, "/") {
+			if rel == pattern || strings.HasSuffix(rel, "/"+pattern) {
+				return true
+			}
+			continue
+		}
+		if ok, _ := path.Match(pattern, path.Base(rel)); ok {
+			return true
+		}
+	}
+	return false
+}
+
+// hasMarker reports whether the file contains one of the rule's markers
+func (r rule) hasMarker(file string) bool {
+	if len(r.markers) == 0 {
+		return true
+	}
+
+	f, err := os.Open(file)
+	if err != nil {
+		return false
+	}
+	defer f.Close()
+
+	buf := make([]byte, maxContentBytes)
+	n, _ := f.Read(buf)
+	for _, marker := range r.markers {
+		if bytes.Contains(buf[:n], []byte(marker)) {
+			return true
+		}
+	}
+	return false
+}
+
+// Scan walks a project directory and returns files that look like key material or credentials
+func Scan(dir string) []Finding {
+	var findings []Finding
+
+	filepath.WalkDir(dir, func(p string, d fs.DirEntry, err error) error {
+		if err != nil {
+			return nil
+		}
+		if d.IsDir() {
+			if p != dir && skipDirs[d.Name()] {
+				return filepath.SkipDir
+			}
+			return nil
+		}
+		if !d.Type().IsRegular() {
+			return nil
+		}
+
+		rel, err := filepath.Rel(dir, p)
+		if err != nil {
+			return nil
+		}
+		rel = filepath.ToSlash(rel)
+
+		for _, r := range rules {
+			if r.matches(rel) && r.hasMarker(p) {
+				findings = append(findings, Finding{Path: rel, Reason: r.reason})
+				break
+			}
+		}
+		return nil
+	})
+
+	sort.Slice(findings, func(i, j int) bool {
+		return findings[i].Path < findings[j].Path
+	})
+	return findings
+}
@@ -0,0 +1,260 @@
+package service
+
+import (
+	"fmt"
+	"html"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"runtime"
+	"strconv"
+	"strings"
+
+	"github.com/thaodangspace/agentsandbox/internal/paths"
+)
+
+// Service is a long-running agentsandbox background process
+type Service struct {
+	Name        string
+	Description string
+	Args        []string
+	// Env lists host variables copied into the unit at install time
+	Env []string
+}
+
+// Services returns the background processes that can be installed
+func Services() []Service {
+	return []Service{
+		{
+			Name:        "clipboard-watcher",
+			Description: "agentsandbox clipboard watcher",
+			Args:        []string{"clipboard", "watch"},
+			Env:         []string{"DISPLAY", "XAUTHORITY", "WAYLAND_DISPLAY"},
+		},
+		{
+			Name:        "maintenance",
+			Description: "agentsandbox maintenance daemon",
+			Args:        []string{"daemon"},
+		},
+	}
+}
+
+// Find returns the service with the given name
+func Find(name string) (Service, error) {
+	var names []string
+	for _, s := range Services() {
+		if s.Name == name {
+			return s, nil
+		}
+		names = append(names, s.Name)
+	}
+	return Service{}, fmt.Errorf("unknown service %q (available: %s)", name, strings.Join(names, ", "))
+}
+
+// Label is the systemd unit name or launchd label of the service
+func (s Service) Label() string {
+	if runtime.GOOS == "darwin" {
+		return "com.agentsandbox." + s.Name
+	}
+	return "agentsandbox-" + s.Name + ".service"
+}
+
+// UnitPath returns where the service definition is written
+func (s Service) UnitPath() (string, error) {
+	homeDir, err := os.UserHomeDir()
+	if err != nil {
+		return "", fmt.Errorf("failed to get home directory: %w", err)
+	}
+
+	switch runtime.GOOS {
+	case "linux":
+		return filepath.Join(homeDir, ".config", "systemd", "user", s.Label()), nil
+	case "darwin":
+		return filepath.Join(homeDir, "Library", "LaunchAgents", s.Label()+".plist"), nil
+	default:
+		return "", fmt.Errorf("services are not supported on %s", runtime.GOOS)
+	}
+}
+
+// Installed reports whether the service definition exists
+func (s Service) Installed() bool {
+	path, err := s.UnitPath()
+	if err != nil {
+		return false
+	}
+	_, err = os.Stat(path)
+	return err == nil
+}
+
+// SystemdUnit renders a systemd user unit that runs executable with the service arguments
+func (s Service) SystemdUnit(executable string, env map[string]string) string {
+	var b strings.Builder
+	fmt.Fprintf(&b, "[Unit]\nDescription=%s\n\n[Service]\n", s.Description)
+	var command []string
+	for _, arg := range append([]string{executable}, s.Args...) {
+		if strings.ContainsAny(arg, " \t\"") {
+			arg = strconv.Quote(arg)
+		}
+		command = append(command, arg)
+	}
+	fmt.Fprintf(&b, "ExecStart=%s\n", strings.Join(command, " "))
+	for _, key := range s.Env {
+		if value, ok := env[key]; ok {
+			fmt.Fprintf(&b, "Environment=%s=%s\n", key, value)
+		}
+	}
+	b.WriteString("Restart=on-failure\nRestartSec=10\n\n[Install]\nWantedBy=default.target\n")
+	return b.String()
+}
+
+// LaunchdPlist renders a launchd agent that runs executable with the service arguments
+func (s Service) LaunchdPlist(executable, logFile string, env map[string]string) string {
+	var b strings.Builder
+	b.WriteString(`<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+`)
+	fmt.Fprintf(&b, "\t<key>Label</key>\n\t<string>%s</string>\n", s.Label())
+	b.WriteString("\t<key>ProgramArguments</key>\n\t<array>\n")
+	for _, arg := range append([]string{executable}, s.Args...) {
+		fmt.Fprintf(&b, "\t\t<string>%s</string>\n", html.EscapeString(arg))
+	}
+	b.WriteString("\t</array>\n")
+
+	var vars []string
+	for _, key := range s.Env {
+		if value, ok := env[key]; ok {
+			vars = append(vars, fmt.Sprintf("\t\t<key>%s</key>\n\t\t<string>%s</string>\n", key, html.EscapeString(value)))
+		}
+	}
+	if len(vars) > 0 {
+		b.WriteString("\t<key>EnvironmentVariables</key>\n\t<dict>\n")
+		b.WriteString(strings.Join(vars, ""))
+		b.WriteString("\t</dict>\n")
+	}
+
+	b.WriteString("\t<key>RunAtLoad</key>\n\t<true/>\n\t<key>KeepAlive</key>\n\t<true/>\n")
+	fmt.Fprintf(&b, "\t<key>StandardOutPath</key>\n\t<string>%s</string>\n", html.EscapeString(logFile))
+	fmt.Fprintf(&b, "\t<key>StandardErrorPath</key>\n\t<string>%s</string>\n", html.EscapeString(logFile))
+	b.WriteString("</dict>\n</plist>\n")
+	return b.String()
+}
+
+// Install writes the service definition and starts it
+func (s Service) Install() (string, error) {
+	path, err := s.UnitPath()
+	if err != nil {
+		return "", err
+	}
+
+	executable, err := os.Executable()
+	if err != nil {
+		return "", fmt.Errorf("failed to locate agentsandbox executable: %w", err)
+	}
+
+	env := make(map[string]string)
+	for _, key := range s.Env {
+		if value := os.Getenv(key); value != "" {
+			env[key] = value
+		}
+	}
+
+	var content string
+	if runtime.GOOS == "darwin" {
+		logFile, err := s.logFile()
+		if err != nil {
+			return "", err
+		}
+		content = s.LaunchdPlist(executable, logFile, env)
+	} else {
+		content = s.SystemdUnit(executable, env)
+	}
+
+	if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+		return "", fmt.Errorf("failed to create %s: %w", filepath.Dir(path), err)
+	}
+	if err := os.WriteFile(path, []byte(content), 0644); err != nil {
+		return "", fmt.Errorf("failed to write %s: %w", path, err)
+	}
+
+	if runtime.GOOS == "darwin" {
+		// Reload in case an older definition is already loaded
+		exec.Command("launchctl", "unload", path).Run()
+		return path, run("launchctl", "load", "-w", path)
+	}
+	if err := run("systemctl", "--user", "daemon-reload"); err != nil {
+		return path, err
+	}
+	return path, run("systemctl", "--user", "enable", "--now", s.Label())
+}
+
+// Uninstall stops the service and removes its definition. It returns false when
+// the service was not installed.
+func (s Service) Uninstall() (bool, error) {
+	path, err := s.UnitPath()
+	if err != nil {
+		return false, err
+	}
+	if !s.Installed() {
+		return false, nil
+	}
+
+	if runtime.GOOS == "darwin" {
+		exec.Command("launchctl", "unload", "-w", path).Run()
+	} else {
+		exec.Command("systemctl", "--user", "disable", "--now", s.Label()).Run()
+	}
+
+	if err := os.Remove(path); err != nil {
+		return false, fmt.Errorf("failed to remove %s: %w", path, err)
+	}
+
+	if runtime.GOOS != "darwin" {
+		exec.Command("systemctl", "--user", "daemon-reload").Run()
+	}
+	return true, nil
+}
+
+// Restart restarts an installed service through the service manager
+func (s Service) Restart() error {
+	if runtime.GOOS == "darwin" {
+		return run("launchctl", "kickstart", "-k", fmt.Sprintf("gui/%d/%s", os.Getuid(), s.Label()))
+	}
+	return run("systemctl", "--user", "restart", s.Label())
+}
+
+// State returns the service manager's view of the service, e.g. "active" or "inactive"
+func (s Service) State() string {
+	if !s.Installed() {
+		return "not installed"
+	}
+
+	if runtime.GOOS == "darwin" {
+		if err := exec.Command("launchctl", "list", s.Label()).Run(); err != nil {
+			return "not loaded"
+		}
+		return "loaded"
+	}
+
+	output, _ := exec.Command("systemctl", "--user", "is-active", s.Label()).Output()
+	if state := strings.TrimSpace(string(output)); state != "" {
+		return state
+	}
+	return "unknown"
+}
+
+func (s Service) logFile() (string, error) {
+	dir, err := paths.EnsureDir(paths.StateDir())
+	if err != nil {
+		return "", fmt.Errorf("failed to get state directory: %w", err)
+	}
+	return filepath.Join(dir, s.Name+".log"), nil
+}
+
+func run(name string, args ...string) error {
+	if output, err := exec.Command(name, args...).CombinedOutput(); err != nil {
+		return fmt.Errorf("%s %s failed: %w\nOutput: %s", name, strings.Join(args, " "), err, string(output))
+	}
+	return nil
+}
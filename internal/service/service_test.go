@@ -0,0 +1,57 @@
+package service
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestSystemdUnit(t *testing.T) {
+	s, err := Find("clipboard-watcher")
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	unit := s.SystemdUnit("/usr/local/bin/agentsandbox", map[string]string{"DISPLAY": ":0"})
+
+	for _, want := range []string{
+		"ExecStart=/usr/local/bin/agentsandbox clipboard watch\n",
+		"Environment=DISPLAY=:0\n",
+		"Restart=on-failure\n",
+		"WantedBy=default.target\n",
+	} {
+		if !strings.Contains(unit, want) {
+			t.Errorf("unit missing %q:\n%s", want, unit)
+		}
+	}
+	if strings.Contains(unit, "XAUTHORITY") {
+		t.Errorf("unit should only include variables that are set:\n%s", unit)
+	}
+}
+
+func TestLaunchdPlist(t *testing.T) {
+	s, err := Find("maintenance")
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	plist := s.LaunchdPlist("/opt/agent sandbox/bin", "/tmp/a&b.log", nil)
+
+	for _, want := range []string{
+		"\t\t<string>/opt/agent sandbox/bin</string>\n\t\t<string>daemon</string>\n",
+		"<string>/tmp/a&amp;b.log</string>",
+		"<key>KeepAlive</key>",
+	} {
+		if !strings.Contains(plist, want) {
+			t.Errorf("plist missing %q:\n%s", want, plist)
+		}
+	}
+	if strings.Contains(plist, "EnvironmentVariables") {
+		t.Errorf("plist should omit an empty environment:\n%s", plist)
+	}
+}
+
+func TestFindUnknown(t *testing.T) {
+	if _, err := Find("web"); err == nil {
+		t.Error("expected an error for an unknown service")
+	}
+}
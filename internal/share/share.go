@@ -0,0 +1,167 @@
+package share
+
+import (
+	"fmt"
+	"io"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"sort"
+
+	"github.com/thaodangspace/agentsandbox/internal/state"
+)
+
+// ContainerPath is where a sandbox's share directory is mounted inside it
+const ContainerPath = "/workspace/.share"
+
+// HelperPath is where the in-container share helper is installed
+const HelperPath = "/usr/local/bin/share"
+
+// HelperScript copies files into the share directory from inside the container,
+// or lists its contents when called without arguments
+const HelperScript = `#!/bin/sh
+set -e
+SHARE_DIR="` + ContainerPath + `"
+if [ "$#" -eq 0 ]; then
+    ls -la "$SHARE_DIR"
+    exit 0
+fi
+for path in "$@"; do
+    cp -r -- "$path" "$SHARE_DIR/"
+    echo "Shared $(basename "$path") -> $SHARE_DIR/$(basename "$path")"
+done
+`
+
+// GetShareDir returns the host directory shared with one container. Each sandbox has
+// its own, kept with the container's state, so files cannot pass between projects.
+func GetShareDir(containerName string) (string, error) {
+	stateDir, err := state.GetStateDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(stateDir, fmt.Sprintf("%s.share", containerName)), nil
+}
+
+// EnsureShareDir creates a container's share directory if it doesn't exist
+func EnsureShareDir(containerName string) (string, error) {
+	shareDir, err := GetShareDir(containerName)
+	if err != nil {
+		return "", err
+	}
+
+	if err := os.MkdirAll(shareDir, 0755); err != nil {
+		return "", fmt.Errorf("failed to create share directory: %w", err)
+	}
+	return shareDir, nil
+}
+
+// RemoveShareDir deletes a container's share directory and everything in it
+func RemoveShareDir(containerName string) error {
+	shareDir, err := GetShareDir(containerName)
+	if err != nil {
+		return err
+	}
+	return os.RemoveAll(shareDir)
+}
+
+// Add copies files or directories into a container's share directory and returns
+// their names there
+func Add(containerName string, paths []string) ([]string, error) {
+	shareDir, err := EnsureShareDir(containerName)
+	if err != nil {
+		return nil, err
+	}
+
+	var added []string
+	for _, path := range paths {
+		name := filepath.Base(filepath.Clean(path))
+		if err := copyPath(path, filepath.Join(shareDir, name)); err != nil {
+			return added, fmt.Errorf("failed to share %s: %w", path, err)
+		}
+		added = append(added, name)
+	}
+	return added, nil
+}
+
+// List returns the top-level entries in a container's share directory in sorted order
+func List(containerName string) ([]string, error) {
+	shareDir, err := GetShareDir(containerName)
+	if err != nil {
+		return nil, err
+	}
+
+	entries, err := os.ReadDir(shareDir)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return nil, nil
+		}
+		return nil, err
+	}
+
+	var names []string
+	for _, entry := range entries {
+		name := entry.Name()
+		if entry.IsDir() {
+			name += "/"
+		}
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	return names, nil
+}
+
+// copyPath copies a file or directory tree to dst, preserving file modes
+func copyPath(src, dst string) error {
+	info, err := os.Stat(src)
+	if err != nil {
+		return err
+	}
+
+	if !info.IsDir() {
+		return copyFile(src, dst, info.Mode())
+	}
+
+	return filepath.WalkDir(src, func(path string, d fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+
+		rel, err := filepath.Rel(src, path)
+		if err != nil {
+			return err
+		}
+		target := filepath.Join(dst, rel)
+
+		info, err := d.Info()
+		if err != nil {
+			return err
+		}
+		switch {
+		case d.IsDir():
+			return os.MkdirAll(target, info.Mode().Perm()|0700)
+		case info.Mode().IsRegular():
+			return copyFile(path, target, info.Mode())
+		default:
+			// Skip symlinks, sockets and devices
+			return nil
+		}
+	})
+}
+
+func copyFile(src, dst string, mode os.FileMode) error {
+	in, err := os.Open(src)
+	if err != nil {
+		return err
+	}
+	defer in.Close()
+
+	out, err := os.OpenFile(dst, os.O_CREATE|os.O_WRONLY|os.O_TRUNC, mode.Perm())
+	if err != nil {
+		return err
+	}
+	if _, err := io.Copy(out, in); err != nil {
+		out.Close()
+		return err
+	}
+	return out.Close()
+}
@@ -0,0 +1,131 @@
+package state
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"regexp"
+	"sort"
+	"strings"
+)
+
+var aliasPattern = regexp.MustCompile(`^[a-zA-Z0-9][a-zA-Z0-9_.-]*$`)
+
+// ValidateAlias checks that a session name can be used in place of a container name
+func ValidateAlias(alias string) error {
+	if !aliasPattern.MatchString(alias) {
+		return fmt.Errorf("invalid name %q: use letters, digits, '.', '_' and '-'", alias)
+	}
+	if strings.HasPrefix(alias, "agentsandbox-") {
+		return fmt.Errorf("invalid name %q: the agentsandbox- prefix is reserved for container names", alias)
+	}
+	return nil
+}
+
+func aliasesFile() (string, error) {
+	stateDir, err := GetStateDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(stateDir, "aliases.json"), nil
+}
+
+// LoadAliases returns the session names mapped to their container names
+func LoadAliases() (map[string]string, error) {
+	path, err := aliasesFile()
+	if err != nil {
+		return nil, err
+	}
+
+	aliases := make(map[string]string)
+	data, err := os.ReadFile(path)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return aliases, nil
+		}
+		return nil, err
+	}
+	if err := json.Unmarshal(data, &aliases); err != nil {
+		return nil, fmt.Errorf("failed to parse %s: %w", path, err)
+	}
+	return aliases, nil
+}
+
+func saveAliases(aliases map[string]string) error {
+	path, err := aliasesFile()
+	if err != nil {
+		return err
+	}
+	data, err := json.MarshalIndent(aliases, "", "  ")
+	if err != nil {
+		return err
+	}
+	return os.WriteFile(path, data, 0644)
+}
+
+// SaveAlias names a container. A container has at most one name, and a name that
+// belonged to another container is reassigned.
+func SaveAlias(alias, containerName string) error {
+	if err := ValidateAlias(alias); err != nil {
+		return err
+	}
+	aliases, err := LoadAliases()
+	if err != nil {
+		return err
+	}
+	for name, c := range aliases {
+		if c == containerName {
+			delete(aliases, name)
+		}
+	}
+	aliases[alias] = containerName
+	return saveAliases(aliases)
+}
+
+// ResolveAlias returns the container a session name refers to, or "" if there is none
+func ResolveAlias(alias string) string {
+	aliases, err := LoadAliases()
+	if err != nil {
+		return ""
+	}
+	return aliases[alias]
+}
+
+// AliasFor returns the session name of a container, or "" if it has none
+func AliasFor(containerName string) string {
+	aliases, err := LoadAliases()
+	if err != nil {
+		return ""
+	}
+	var names []string
+	for name, c := range aliases {
+		if c == containerName {
+			names = append(names, name)
+		}
+	}
+	sort.Strings(names)
+	if len(names) == 0 {
+		return ""
+	}
+	return names[0]
+}
+
+// RemoveAliases forgets every session name of a removed container
+func RemoveAliases(containerName string) error {
+	aliases, err := LoadAliases()
+	if err != nil {
+		return err
+	}
+	changed := false
+	for name, c := range aliases {
+		if c == containerName {
+			delete(aliases, name)
+			changed = true
+		}
+	}
+	if !changed {
+		return nil
+	}
+	return saveAliases(aliases)
+}
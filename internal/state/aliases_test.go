@@ -0,0 +1,53 @@
+package state
+
+import "testing"
+
+func TestValidateAlias(t *testing.T) {
+	tests := []struct {
+		alias string
+		valid bool
+	}{
+		{"feature-login", true},
+		{"v1.2_fix", true},
+		{"", false},
+		{"-leading", false},
+		{"has space", false},
+		{"agentsandbox-myrepo", false},
+	}
+	for _, tt := range tests {
+		t.Run(tt.alias, func(t *testing.T) {
+			if err := ValidateAlias(tt.alias); (err == nil) != tt.valid {
+				t.Errorf("ValidateAlias(%q) error = %v, want valid %v", tt.alias, err, tt.valid)
+			}
+		})
+	}
+}
+
+func TestSaveAlias(t *testing.T) {
+	t.Setenv("HOME", t.TempDir())
+	t.Setenv("XDG_STATE_HOME", "")
+
+	if err := SaveAlias("login", "agentsandbox-app"); err != nil {
+		t.Fatal(err)
+	}
+	// Renaming the container drops its old name
+	if err := SaveAlias("signup", "agentsandbox-app"); err != nil {
+		t.Fatal(err)
+	}
+	if got := ResolveAlias("login"); got != "" {
+		t.Errorf("old name still resolves to %q", got)
+	}
+	if got := ResolveAlias("signup"); got != "agentsandbox-app" {
+		t.Errorf("ResolveAlias(signup) = %q", got)
+	}
+	if got := AliasFor("agentsandbox-app"); got != "signup" {
+		t.Errorf("AliasFor = %q", got)
+	}
+
+	if err := RemoveAliases("agentsandbox-app"); err != nil {
+		t.Fatal(err)
+	}
+	if got := ResolveAlias("signup"); got != "" {
+		t.Errorf("removed name still resolves to %q", got)
+	}
+}
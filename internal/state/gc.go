@@ -0,0 +1,179 @@
+package state
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+	"time"
+)
+
+// ImportedMarker marks a logs directory whose sessions came from an imported bundle,
+// so it is kept even though its container never existed locally
+const ImportedMarker = ".imported"
+
+// containerFileSuffixes are the per-container files kept in the state directory
+var containerFileSuffixes = []string{".command.json", ".worktree", ".warm.json", ".postmortem.json", ".github.json"}
+
+// GCReport lists state left behind by containers that no longer exist
+type GCReport struct {
+	// Files are per-container state files that were removed
+	Files []string
+	// Archived maps a logs directory to where it was moved
+	Archived map[string]string
+	// EmptyLogDirs are logs directories with nothing left in them that were removed
+	EmptyLogDirs []string
+	// LastContainer is the stale last_container entry that was cleared, if any
+	LastContainer string
+	// Aliases are session names whose container is gone
+	Aliases []string
+}
+
+// Empty reports whether nothing was found to clean up
+func (r *GCReport) Empty() bool {
+	return len(r.Files) == 0 && len(r.Archived) == 0 && len(r.EmptyLogDirs) == 0 && r.LastContainer == "" && len(r.Aliases) == 0
+}
+
+// CollectGarbage removes state for containers missing from existing. Session logs are
+// moved to <state>/archive rather than deleted. With dryRun nothing is changed.
+func CollectGarbage(existing map[string]bool, dryRun bool) (*GCReport, error) {
+	stateDir, err := GetStateDir()
+	if err != nil {
+		return nil, err
+	}
+	report := &GCReport{Archived: make(map[string]string)}
+
+	entries, err := os.ReadDir(stateDir)
+	if err != nil {
+		return nil, err
+	}
+	for _, entry := range entries {
+		if entry.IsDir() {
+			continue
+		}
+		name, ok := containerForFile(entry.Name())
+		if !ok || existing[name] {
+			continue
+		}
+		path := filepath.Join(stateDir, entry.Name())
+		if !dryRun {
+			if err := os.Remove(path); err != nil && !os.IsNotExist(err) {
+				return report, err
+			}
+		}
+		report.Files = append(report.Files, path)
+	}
+
+	if last, err := LoadLastContainer(); err == nil && last != "" && !existing[strings.TrimSpace(last)] {
+		if !dryRun {
+			if err := ClearLastContainer(); err != nil {
+				return report, err
+			}
+		}
+		report.LastContainer = strings.TrimSpace(last)
+	}
+
+	if aliases, err := LoadAliases(); err == nil {
+		for alias, containerName := range aliases {
+			if !existing[containerName] {
+				delete(aliases, alias)
+				report.Aliases = append(report.Aliases, alias)
+			}
+		}
+		sort.Strings(report.Aliases)
+		if len(report.Aliases) > 0 && !dryRun {
+			if err := saveAliases(aliases); err != nil {
+				return report, err
+			}
+		}
+	}
+
+	if err := collectLogDirs(stateDir, existing, dryRun, report); err != nil {
+		return report, err
+	}
+	return report, nil
+}
+
+// containerForFile returns the container a state file belongs to
+func containerForFile(fileName string) (string, bool) {
+	for _, suffix := range containerFileSuffixes {
+		if name := strings.TrimSuffix(fileName, suffix); name != fileName && name != "" {
+			return name, true
+		}
+	}
+	return "", false
+}
+
+func collectLogDirs(stateDir string, existing map[string]bool, dryRun bool, report *GCReport) error {
+	logsRoot := filepath.Join(stateDir, "logs")
+	projects, err := os.ReadDir(logsRoot)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return nil
+		}
+		return err
+	}
+
+	for _, project := range projects {
+		if !project.IsDir() {
+			continue
+		}
+		projectDir := filepath.Join(logsRoot, project.Name())
+		containers, err := os.ReadDir(projectDir)
+		if err != nil {
+			continue
+		}
+
+		for _, c := range containers {
+			if !c.IsDir() || existing[c.Name()] {
+				continue
+			}
+			dir := filepath.Join(projectDir, c.Name())
+			if _, err := os.Stat(filepath.Join(dir, ImportedMarker)); err == nil {
+				continue
+			}
+
+			files, err := os.ReadDir(dir)
+			if err != nil {
+				continue
+			}
+			if len(files) == 0 {
+				if !dryRun {
+					if err := os.Remove(dir); err != nil {
+						return err
+					}
+				}
+				report.EmptyLogDirs = append(report.EmptyLogDirs, dir)
+				continue
+			}
+
+			dest := archivePath(stateDir, project.Name(), c.Name())
+			if !dryRun {
+				if err := os.MkdirAll(filepath.Dir(dest), 0755); err != nil {
+					return err
+				}
+				if err := os.Rename(dir, dest); err != nil {
+					return fmt.Errorf("failed to archive %s: %w", dir, err)
+				}
+			}
+			report.Archived[dir] = dest
+		}
+
+		if !dryRun {
+			// Drop project directories left empty; Remove fails harmlessly otherwise
+			os.Remove(projectDir)
+		}
+	}
+
+	return nil
+}
+
+// archivePath picks a destination under <state>/archive that does not exist yet
+func archivePath(stateDir, project, containerName string) string {
+	dest := filepath.Join(stateDir, "archive", project, containerName)
+	if _, err := os.Stat(dest); os.IsNotExist(err) {
+		return dest
+	}
+	return fmt.Sprintf("%s-%s", dest, time.Now().Format("20060102-150405"))
+}
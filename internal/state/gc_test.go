@@ -0,0 +1,87 @@
+package state
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestCollectGarbage(t *testing.T) {
+	home := t.TempDir()
+	t.Setenv("HOME", home)
+	t.Setenv("XDG_STATE_HOME", "")
+	stateDir := filepath.Join(home, ".local", "state", "agentsandbox")
+
+	write := func(rel, content string) {
+		t.Helper()
+		path := filepath.Join(stateDir, rel)
+		if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(path, []byte(content), 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+
+	write("agentsandbox-live.command.json", "{}")
+	write("agentsandbox-gone.command.json", "{}")
+	write("agentsandbox-gone.warm.json", "{}")
+	write("settings.json", "{}")
+	write("last_container", "agentsandbox-gone")
+	write("logs/live/agentsandbox-live/session.jsonl", "{}")
+	write("logs/gone/agentsandbox-gone/session.jsonl", "{}")
+	write("logs/shared/agentsandbox-imported/session.jsonl", "{}")
+	write("logs/shared/agentsandbox-imported/"+ImportedMarker, "")
+	if err := os.MkdirAll(filepath.Join(stateDir, "logs", "empty", "agentsandbox-empty"), 0755); err != nil {
+		t.Fatal(err)
+	}
+
+	existing := map[string]bool{"agentsandbox-live": true}
+
+	dry, err := CollectGarbage(existing, true)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(dry.Files) != 2 || len(dry.Archived) != 1 || len(dry.EmptyLogDirs) != 1 || dry.LastContainer != "agentsandbox-gone" {
+		t.Fatalf("unexpected dry-run report: %+v", dry)
+	}
+	if _, err := os.Stat(filepath.Join(stateDir, "agentsandbox-gone.warm.json")); err != nil {
+		t.Fatalf("dry run removed a file: %v", err)
+	}
+
+	if _, err := CollectGarbage(existing, false); err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		rel    string
+		exists bool
+	}{
+		{"agentsandbox-live.command.json", true},
+		{"settings.json", true},
+		{"logs/live/agentsandbox-live/session.jsonl", true},
+		{"logs/shared/agentsandbox-imported/session.jsonl", true},
+		{"agentsandbox-gone.command.json", false},
+		{"agentsandbox-gone.warm.json", false},
+		{"last_container", false},
+		{"logs/gone", false},
+		{"logs/empty", false},
+		{"archive/gone/agentsandbox-gone/session.jsonl", true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.rel, func(t *testing.T) {
+			_, err := os.Stat(filepath.Join(stateDir, tt.rel))
+			if exists := err == nil; exists != tt.exists {
+				t.Errorf("exists = %v, want %v", exists, tt.exists)
+			}
+		})
+	}
+
+	again, err := CollectGarbage(existing, false)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if !again.Empty() {
+		t.Errorf("second pass should find nothing, got %+v", again)
+	}
+}
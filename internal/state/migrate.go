@@ -0,0 +1,252 @@
+package state
+
+import (
+	"fmt"
+	"io"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/paths"
+)
+
+// migration upgrades the state layout from version-1 to version. Both functions must
+// be safe to run on state that is already in the new layout.
+type migration struct {
+	version     int
+	description string
+	// plan lists the changes apply would make
+	plan func(stateDir string) ([]string, error)
+	// sources lists the files and directories apply changes outside the state directory,
+	// so they can be backed up first
+	sources func(stateDir string) ([]string, error)
+	apply   func(stateDir string) error
+}
+
+// migrations are applied in order; append new ones with the next version number
+var migrations = []migration{
+	{
+		version:     1,
+		description: "Move files out of ~/.config/agentsandbox into the XDG config, state and cache directories",
+		plan:        planLegacyMoves,
+		sources:     legacySources,
+		apply:       func(string) error { return paths.MigrateLegacy() },
+	},
+}
+
+// CurrentVersion is the state layout this build writes
+func CurrentVersion() int {
+	return migrations[len(migrations)-1].version
+}
+
+// MigrationStep is a migration that is pending or was applied
+type MigrationStep struct {
+	Version     int
+	Description string
+	Changes     []string
+}
+
+// MigrationReport describes a state migration
+type MigrationReport struct {
+	From  int
+	To    int
+	Steps []MigrationStep
+	// Backup is where the state files, and the files the migrations change, were copied
+	// before migrating, if anywhere
+	Backup string
+}
+
+// Changed reports whether any migration had something to change
+func (r *MigrationReport) Changed() bool {
+	for _, step := range r.Steps {
+		if len(step.Changes) > 0 {
+			return true
+		}
+	}
+	return false
+}
+
+func versionFile(stateDir string) string {
+	return filepath.Join(stateDir, "version")
+}
+
+// loadVersion returns the recorded state version; state without one predates versioning
+func loadVersion(stateDir string) (int, error) {
+	data, err := os.ReadFile(versionFile(stateDir))
+	if err != nil {
+		if os.IsNotExist(err) {
+			return 0, nil
+		}
+		return 0, err
+	}
+	version, err := strconv.Atoi(strings.TrimSpace(string(data)))
+	if err != nil {
+		return 0, fmt.Errorf("invalid state version in %s: %w", versionFile(stateDir), err)
+	}
+	return version, nil
+}
+
+func saveVersion(stateDir string, version int) error {
+	return os.WriteFile(versionFile(stateDir), []byte(strconv.Itoa(version)+"\n"), 0644)
+}
+
+// Migrate brings the state directory up to CurrentVersion. State files and whatever
+// the pending migrations change are backed up to <state>/backups before anything is
+// changed. With dryRun it only reports the
+// pending migrations and what they would change.
+func Migrate(dryRun bool) (*MigrationReport, error) {
+	stateDir, err := GetStateDir()
+	if err != nil {
+		return nil, err
+	}
+	from, err := loadVersion(stateDir)
+	if err != nil {
+		return nil, err
+	}
+	report := &MigrationReport{From: from, To: CurrentVersion()}
+	if from > report.To {
+		return report, fmt.Errorf("state version %d is newer than this agentsandbox supports (%d); upgrade agentsandbox", from, report.To)
+	}
+
+	var pending []migration
+	var sources []string
+	for _, m := range migrations {
+		if m.version <= from {
+			continue
+		}
+		changes, err := m.plan(stateDir)
+		if err != nil {
+			return report, fmt.Errorf("failed to plan state migration %d: %w", m.version, err)
+		}
+		if m.sources != nil {
+			touched, err := m.sources(stateDir)
+			if err != nil {
+				return report, fmt.Errorf("failed to plan state migration %d: %w", m.version, err)
+			}
+			sources = append(sources, touched...)
+		}
+		pending = append(pending, m)
+		report.Steps = append(report.Steps, MigrationStep{Version: m.version, Description: m.description, Changes: changes})
+	}
+	if dryRun || len(pending) == 0 {
+		return report, nil
+	}
+
+	if report.Changed() {
+		backup, err := backupState(stateDir, from, sources)
+		if err != nil {
+			return report, fmt.Errorf("failed to back up state: %w", err)
+		}
+		report.Backup = backup
+	}
+	for _, m := range pending {
+		if err := m.apply(stateDir); err != nil {
+			return report, fmt.Errorf("state migration %d failed: %w", m.version, err)
+		}
+		if err := saveVersion(stateDir, m.version); err != nil {
+			return report, err
+		}
+	}
+	return report, nil
+}
+
+// backupState copies the files directly in the state directory, which hold the
+// per-container and global state, to <state>/backups/v<version>-<time>. Logs are
+// only ever added to, so they are not copied. Sources from elsewhere, such as the
+// legacy config directory, are copied in full under files/ at their absolute path.
+func backupState(stateDir string, version int, sources []string) (string, error) {
+	entries, err := os.ReadDir(stateDir)
+	if err != nil {
+		return "", err
+	}
+	backup := filepath.Join(stateDir, "backups", fmt.Sprintf("v%d-%s", version, time.Now().Format("20060102-150405")))
+	if err := os.MkdirAll(backup, 0755); err != nil {
+		return "", err
+	}
+	for _, entry := range entries {
+		if !entry.Type().IsRegular() {
+			continue
+		}
+		if err := copyFile(filepath.Join(stateDir, entry.Name()), filepath.Join(backup, entry.Name())); err != nil {
+			return "", err
+		}
+	}
+	for _, source := range sources {
+		rel := strings.TrimPrefix(filepath.Clean(source), filepath.VolumeName(source))
+		if err := copyTree(source, filepath.Join(backup, "files", rel)); err != nil {
+			return "", err
+		}
+	}
+	return backup, nil
+}
+
+// copyTree copies a file or directory tree to dst, skipping anything that is neither
+// a regular file nor a directory
+func copyTree(src, dst string) error {
+	return filepath.WalkDir(src, func(path string, d fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+		rel, err := filepath.Rel(src, path)
+		if err != nil {
+			return err
+		}
+		target := filepath.Join(dst, rel)
+		switch {
+		case d.IsDir():
+			return os.MkdirAll(target, 0755)
+		case d.Type().IsRegular():
+			if err := os.MkdirAll(filepath.Dir(target), 0755); err != nil {
+				return err
+			}
+			return copyFile(path, target)
+		default:
+			return nil
+		}
+	})
+}
+
+func copyFile(src, dst string) error {
+	in, err := os.Open(src)
+	if err != nil {
+		return err
+	}
+	defer in.Close()
+	out, err := os.Create(dst)
+	if err != nil {
+		return err
+	}
+	if _, err := io.Copy(out, in); err != nil {
+		out.Close()
+		return err
+	}
+	return out.Close()
+}
+
+// legacySources returns the entries planLegacyMoves would move
+func legacySources(string) ([]string, error) {
+	moves, err := paths.LegacyMoves()
+	if err != nil {
+		return nil, err
+	}
+	sources := make([]string, len(moves))
+	for i, move := range moves {
+		sources[i] = move.From
+	}
+	return sources, nil
+}
+
+func planLegacyMoves(string) ([]string, error) {
+	moves, err := paths.LegacyMoves()
+	if err != nil {
+		return nil, err
+	}
+	changes := make([]string, len(moves))
+	for i, move := range moves {
+		changes[i] = fmt.Sprintf("move %s to %s", move.From, move.To)
+	}
+	return changes, nil
+}
@@ -0,0 +1,76 @@
+package state
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestMigrate(t *testing.T) {
+	home := t.TempDir()
+	t.Setenv("HOME", home)
+	t.Setenv("XDG_CONFIG_HOME", "")
+	t.Setenv("XDG_STATE_HOME", "")
+	t.Setenv("XDG_CACHE_HOME", "")
+	stateDir := filepath.Join(home, ".local", "state", "agentsandbox")
+
+	legacy := filepath.Join(home, ".config", "agentsandbox")
+	if err := os.MkdirAll(legacy, 0755); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(legacy, "last_container"), []byte("agentsandbox-app"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.MkdirAll(stateDir, 0755); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(stateDir, "aliases.json"), []byte("{}"), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	dry, err := Migrate(true)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if dry.From != 0 || dry.To != CurrentVersion() || !dry.Changed() || dry.Backup != "" {
+		t.Fatalf("unexpected dry-run report: %+v", dry)
+	}
+	if _, err := os.Stat(filepath.Join(legacy, "last_container")); err != nil {
+		t.Fatalf("dry run moved a file: %v", err)
+	}
+
+	report, err := Migrate(false)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if report.Backup == "" {
+		t.Fatalf("expected a backup: %+v", report)
+	}
+	if _, err := os.Stat(filepath.Join(report.Backup, "aliases.json")); err != nil {
+		t.Errorf("expected aliases.json in the backup: %v", err)
+	}
+	// The legacy files were moved away, so the backup must hold their old contents
+	backedUp, err := os.ReadFile(filepath.Join(report.Backup, "files", legacy, "last_container"))
+	if err != nil || string(backedUp) != "agentsandbox-app" {
+		t.Errorf("expected the legacy last_container in the backup, got %q, %v", backedUp, err)
+	}
+	if last, err := LoadLastContainer(); err != nil || last != "agentsandbox-app" {
+		t.Errorf("LoadLastContainer() = %q, %v; want the migrated entry", last, err)
+	}
+
+	// Once recorded, the version stops migrations from running again
+	again, err := Migrate(false)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if again.From != CurrentVersion() || len(again.Steps) != 0 {
+		t.Errorf("expected nothing pending: %+v", again)
+	}
+
+	if err := saveVersion(stateDir, CurrentVersion()+1); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := Migrate(false); err == nil {
+		t.Error("expected an error for state newer than this build")
+	}
+}
@@ -0,0 +1,88 @@
+package state
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+)
+
+// ProjectDefaults holds the agent and flags last used to start a sandbox for a project
+type ProjectDefaults struct {
+	Agent     string   `json:"agent,omitempty"`
+	Shell     bool     `json:"shell,omitempty"`
+	Continue  bool     `json:"continue,omitempty"`
+	AgentArgs []string `json:"agent_args,omitempty"`
+}
+
+func projectsFile() (string, error) {
+	stateDir, err := GetStateDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(stateDir, "projects.json"), nil
+}
+
+func loadProjects() (map[string]ProjectDefaults, error) {
+	path, err := projectsFile()
+	if err != nil {
+		return nil, err
+	}
+
+	projects := make(map[string]ProjectDefaults)
+	data, err := os.ReadFile(path)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return projects, nil
+		}
+		return nil, err
+	}
+	if err := json.Unmarshal(data, &projects); err != nil {
+		return nil, fmt.Errorf("failed to parse %s: %w", path, err)
+	}
+	return projects, nil
+}
+
+func saveProjects(projects map[string]ProjectDefaults) error {
+	path, err := projectsFile()
+	if err != nil {
+		return err
+	}
+	data, err := json.MarshalIndent(projects, "", "  ")
+	if err != nil {
+		return err
+	}
+	return os.WriteFile(path, data, 0644)
+}
+
+// LoadProjectDefaults returns what was last used for projectDir; it is empty for a new project
+func LoadProjectDefaults(projectDir string) (ProjectDefaults, error) {
+	projects, err := loadProjects()
+	if err != nil {
+		return ProjectDefaults{}, err
+	}
+	return projects[projectDir], nil
+}
+
+// SaveProjectDefaults records the agent and flags last used for projectDir
+func SaveProjectDefaults(projectDir string, defaults ProjectDefaults) error {
+	projects, err := loadProjects()
+	if err != nil {
+		return err
+	}
+	projects[projectDir] = defaults
+	return saveProjects(projects)
+}
+
+// ClearProjectDefaults forgets what was last used for projectDir
+func ClearProjectDefaults(projectDir string) error {
+	projects, err := loadProjects()
+	if err != nil {
+		return err
+	}
+	if _, ok := projects[projectDir]; !ok {
+		return nil
+	}
+	delete(projects, projectDir)
+	return saveProjects(projects)
+}
@@ -0,0 +1,33 @@
+package state
+
+import "testing"
+
+func TestProjectDefaults(t *testing.T) {
+	t.Setenv("HOME", t.TempDir())
+	t.Setenv("XDG_STATE_HOME", "")
+
+	if d, err := LoadProjectDefaults("/src/app"); err != nil || d.Agent != "" {
+		t.Fatalf("new project should have no defaults, got %+v, %v", d, err)
+	}
+	saved := ProjectDefaults{Agent: "codex", Shell: true, AgentArgs: []string{"--model", "o3"}}
+	if err := SaveProjectDefaults("/src/app", saved); err != nil {
+		t.Fatal(err)
+	}
+	if err := SaveProjectDefaults("/src/api", ProjectDefaults{Agent: "gemini"}); err != nil {
+		t.Fatal(err)
+	}
+	d, _ := LoadProjectDefaults("/src/app")
+	if d.Agent != "codex" || !d.Shell || d.Continue || len(d.AgentArgs) != 2 {
+		t.Errorf("unexpected defaults for /src/app: %+v", d)
+	}
+
+	if err := ClearProjectDefaults("/src/app"); err != nil {
+		t.Fatal(err)
+	}
+	if d, _ := LoadProjectDefaults("/src/app"); d.Agent != "" {
+		t.Errorf("defaults should be cleared, got %+v", d)
+	}
+	if d, _ := LoadProjectDefaults("/src/api"); d.Agent != "gemini" {
+		t.Errorf("other projects should be kept, got %+v", d)
+	}
+}
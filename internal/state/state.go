@@ -5,20 +5,15 @@ import (
 	"fmt"
 	"os"
 	"path/filepath"
+	"strings"
 	"time"
+
+	"github.com/thaodangspace/agentsandbox/internal/paths"
 )
 
-// GetStateDir returns the state directory path
+// GetStateDir returns the state directory path, creating it if needed
 func GetStateDir() (string, error) {
-	homeDir, err := os.UserHomeDir()
-	if err != nil {
-		return "", err
-	}
-	stateDir := filepath.Join(homeDir, ".config", "agentsandbox")
-	if err := os.MkdirAll(stateDir, 0755); err != nil {
-		return "", err
-	}
-	return stateDir, nil
+	return paths.EnsureDir(paths.StateDir())
 }
 
 // SaveLastContainer saves the name of the last used container
@@ -151,7 +146,8 @@ func ListSessionLogs(containerName, currentDir string) ([]string, error) {
 
 	var logs []string
 	for _, entry := range entries {
-		if !entry.IsDir() && filepath.Ext(entry.Name()) == ".jsonl" {
+		if !entry.IsDir() && filepath.Ext(entry.Name()) == ".jsonl" &&
+			!strings.HasPrefix(entry.Name(), "audit-") && !strings.HasPrefix(entry.Name(), "network-") {
 			logs = append(logs, filepath.Join(logsDir, entry.Name()))
 		}
 	}
@@ -159,6 +155,64 @@ func ListSessionLogs(containerName, currentDir string) ([]string, error) {
 	return logs, nil
 }
 
+// LastSessionTime returns when a container's most recent session log was last written
+func LastSessionTime(containerName, currentDir string) time.Time {
+	logFiles, err := ListSessionLogs(containerName, currentDir)
+	if err != nil {
+		return time.Time{}
+	}
+
+	var last time.Time
+	for _, file := range logFiles {
+		if info, err := os.Stat(file); err == nil && info.ModTime().After(last) {
+			last = info.ModTime()
+		}
+	}
+	return last
+}
+
+// ListAuditLogs lists the command audit logs recorded for a container
+func ListAuditLogs(containerName, currentDir string) ([]string, error) {
+	logsDir, err := GetLogsDir(containerName, currentDir)
+	if err != nil {
+		return nil, err
+	}
+
+	matches, err := filepath.Glob(filepath.Join(logsDir, "audit-*.jsonl"))
+	if err != nil {
+		return nil, err
+	}
+	return matches, nil
+}
+
+// ListNetworkLogs lists the network logs recorded for a container
+func ListNetworkLogs(containerName, currentDir string) ([]string, error) {
+	logsDir, err := GetLogsDir(containerName, currentDir)
+	if err != nil {
+		return nil, err
+	}
+
+	matches, err := filepath.Glob(filepath.Join(logsDir, "network-*.jsonl"))
+	if err != nil {
+		return nil, err
+	}
+	return matches, nil
+}
+
+// ListTestResults lists the test results recorded for a container, oldest first
+func ListTestResults(containerName, currentDir string) ([]string, error) {
+	logsDir, err := GetLogsDir(containerName, currentDir)
+	if err != nil {
+		return nil, err
+	}
+
+	matches, err := filepath.Glob(filepath.Join(logsDir, "tests-*.json"))
+	if err != nil {
+		return nil, err
+	}
+	return matches, nil
+}
+
 // CleanupOldLogs removes log files older than the specified number of days
 func CleanupOldLogs(containerName, currentDir string, days int) (int, error) {
 	logsDir, err := GetLogsDir(containerName, currentDir)
@@ -199,6 +253,45 @@ func CleanupOldLogs(containerName, currentDir string, days int) (int, error) {
 	return deleted, nil
 }
 
+// CleanupAllOldLogs removes log files older than days for every project and container
+func CleanupAllOldLogs(days int) (int, error) {
+	stateDir, err := GetStateDir()
+	if err != nil {
+		return 0, err
+	}
+
+	projects, err := os.ReadDir(filepath.Join(stateDir, "logs"))
+	if err != nil {
+		if os.IsNotExist(err) {
+			return 0, nil
+		}
+		return 0, err
+	}
+
+	deleted := 0
+	for _, project := range projects {
+		if !project.IsDir() {
+			continue
+		}
+		containers, err := os.ReadDir(filepath.Join(stateDir, "logs", project.Name()))
+		if err != nil {
+			continue
+		}
+		for _, c := range containers {
+			if !c.IsDir() {
+				continue
+			}
+			n, err := CleanupOldLogs(c.Name(), project.Name(), days)
+			if err != nil {
+				return deleted, err
+			}
+			deleted += n
+		}
+	}
+
+	return deleted, nil
+}
+
 // ContainerRunCommand stores information about how a container was started
 type ContainerRunCommand struct {
 	Command   []string  `json:"command"`
@@ -251,3 +344,94 @@ func LoadContainerRunCommand(containerName string) (*ContainerRunCommand, error)
 }
 
 
+
+// SaveContainerWorktree records the worktree created automatically for a container
+func SaveContainerWorktree(containerName, worktreePath string) error {
+	stateDir, err := GetStateDir()
+	if err != nil {
+		return err
+	}
+
+	worktreeFile := filepath.Join(stateDir, fmt.Sprintf("%s.worktree", containerName))
+	return os.WriteFile(worktreeFile, []byte(worktreePath), 0644)
+}
+
+// LoadContainerWorktree returns the automatic worktree of a container, or "" if it has none
+func LoadContainerWorktree(containerName string) (string, error) {
+	stateDir, err := GetStateDir()
+	if err != nil {
+		return "", err
+	}
+
+	data, err := os.ReadFile(filepath.Join(stateDir, fmt.Sprintf("%s.worktree", containerName)))
+	if err != nil {
+		if os.IsNotExist(err) {
+			return "", nil
+		}
+		return "", err
+	}
+
+	return strings.TrimSpace(string(data)), nil
+}
+
+// ClearContainerWorktree forgets the automatic worktree of a container
+func ClearContainerWorktree(containerName string) error {
+	stateDir, err := GetStateDir()
+	if err != nil {
+		return err
+	}
+
+	worktreeFile := filepath.Join(stateDir, fmt.Sprintf("%s.worktree", containerName))
+	if err := os.Remove(worktreeFile); err != nil && !os.IsNotExist(err) {
+		return err
+	}
+
+	return nil
+}
+
+// IssueLink records the issue a sandbox was started from
+type IssueLink struct {
+	Number    int       `json:"number"`
+	Title     string    `json:"title"`
+	URL       string    `json:"url"`
+	Branch    string    `json:"branch"`
+	Timestamp time.Time `json:"timestamp"`
+}
+
+// SaveIssueLink stores the issue a container was started from next to its session logs
+func SaveIssueLink(containerName, currentDir string, link IssueLink) error {
+	logsDir, err := GetLogsDir(containerName, currentDir)
+	if err != nil {
+		return err
+	}
+
+	data, err := json.MarshalIndent(link, "", "  ")
+	if err != nil {
+		return err
+	}
+
+	return os.WriteFile(filepath.Join(logsDir, "issue.json"), data, 0644)
+}
+
+// LoadIssueLink returns the issue a container was started from, or nil if there is none
+func LoadIssueLink(containerName, currentDir string) (*IssueLink, error) {
+	logsDir, err := GetLogsDir(containerName, currentDir)
+	if err != nil {
+		return nil, err
+	}
+
+	data, err := os.ReadFile(filepath.Join(logsDir, "issue.json"))
+	if err != nil {
+		if os.IsNotExist(err) {
+			return nil, nil
+		}
+		return nil, err
+	}
+
+	var link IssueLink
+	if err := json.Unmarshal(data, &link); err != nil {
+		return nil, err
+	}
+
+	return &link, nil
+}
@@ -0,0 +1,88 @@
+package state
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+)
+
+// Trust levels decide how locked down a project's sandboxes are
+const (
+	// TrustTrusted uses the settings as they are
+	TrustTrusted = "trusted"
+	// TrustRestricted drops skip-permission flags, sudo and shared networks, and
+	// always masks env files, sensitive files and git hooks
+	TrustRestricted = "restricted"
+	// TrustUntrusted additionally mounts the project read-only without any network
+	TrustUntrusted = "untrusted"
+)
+
+// ValidateTrust checks that level is a known trust level
+func ValidateTrust(level string) error {
+	switch level {
+	case TrustTrusted, TrustRestricted, TrustUntrusted:
+		return nil
+	}
+	return fmt.Errorf("invalid trust level %q: use trusted, restricted or untrusted", level)
+}
+
+func trustFile() (string, error) {
+	stateDir, err := GetStateDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(stateDir, "trust.json"), nil
+}
+
+func loadTrustLevels() (map[string]string, error) {
+	path, err := trustFile()
+	if err != nil {
+		return nil, err
+	}
+
+	levels := make(map[string]string)
+	data, err := os.ReadFile(path)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return levels, nil
+		}
+		return nil, err
+	}
+	if err := json.Unmarshal(data, &levels); err != nil {
+		return nil, fmt.Errorf("failed to parse %s: %w", path, err)
+	}
+	return levels, nil
+}
+
+// LoadTrust returns the trust level recorded for projectDir; it is empty for a project
+// that has not been decided on yet
+func LoadTrust(projectDir string) (string, error) {
+	levels, err := loadTrustLevels()
+	if err != nil {
+		return "", err
+	}
+	return levels[projectDir], nil
+}
+
+// SaveTrust records the trust level for projectDir
+func SaveTrust(projectDir, level string) error {
+	if err := ValidateTrust(level); err != nil {
+		return err
+	}
+	levels, err := loadTrustLevels()
+	if err != nil {
+		return err
+	}
+	levels[projectDir] = level
+
+	path, err := trustFile()
+	if err != nil {
+		return err
+	}
+	data, err := json.MarshalIndent(levels, "", "  ")
+	if err != nil {
+		return err
+	}
+	return os.WriteFile(path, data, 0644)
+}
@@ -0,0 +1,27 @@
+package state
+
+import "testing"
+
+func TestTrust(t *testing.T) {
+	t.Setenv("HOME", t.TempDir())
+	t.Setenv("XDG_STATE_HOME", "")
+
+	if level, err := LoadTrust("/src/app"); err != nil || level != "" {
+		t.Fatalf("new project should have no trust level, got %q, %v", level, err)
+	}
+	if err := SaveTrust("/src/app", TrustUntrusted); err != nil {
+		t.Fatal(err)
+	}
+	if err := SaveTrust("/src/api", TrustTrusted); err != nil {
+		t.Fatal(err)
+	}
+	if level, _ := LoadTrust("/src/app"); level != TrustUntrusted {
+		t.Errorf("trust for /src/app = %q, want untrusted", level)
+	}
+	if err := SaveTrust("/src/app", "sort of"); err == nil {
+		t.Error("expected an invalid trust level to be rejected")
+	}
+	if level, _ := LoadTrust("/src/api"); level != TrustTrusted {
+		t.Errorf("trust for /src/api = %q, want trusted", level)
+	}
+}
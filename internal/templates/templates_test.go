@@ -0,0 +1,34 @@
+package templates
+
+import (
+	"testing"
+
+	"github.com/thaodangspace/agentsandbox/internal/language"
+)
+
+func TestGet(t *testing.T) {
+	for _, name := range []string{"rails", "nextjs", "ml", "go-service", "Rails"} {
+		if _, err := Get(name); err != nil {
+			t.Errorf("Get(%q) error = %v", name, err)
+		}
+	}
+
+	if _, err := Get("cobol"); err == nil {
+		t.Error("Get(\"cobol\") expected error")
+	}
+}
+
+func TestMergeLanguages(t *testing.T) {
+	tmpl, _ := Get("rails")
+	got := tmpl.MergeLanguages([]language.Language{language.LanguageNodeJS, language.LanguageGo})
+
+	want := []language.Language{language.LanguageNodeJS, language.LanguageGo, language.LanguageRuby}
+	if len(got) != len(want) {
+		t.Fatalf("MergeLanguages() = %v, want %v", got, want)
+	}
+	for i := range want {
+		if got[i] != want[i] {
+			t.Fatalf("MergeLanguages() = %v, want %v", got, want)
+		}
+	}
+}
@@ -0,0 +1,225 @@
+package testrun
+
+import (
+	"encoding/json"
+	"encoding/xml"
+	"fmt"
+	"regexp"
+	"strconv"
+	"strings"
+)
+
+// junitSuite is a <testsuite>; a <testsuites> root decodes into it as well
+type junitSuite struct {
+	Name   string       `xml:"name,attr"`
+	Cases  []junitCase  `xml:"testcase"`
+	Suites []junitSuite `xml:"testsuite"`
+}
+
+type junitCase struct {
+	Name      string        `xml:"name,attr"`
+	Classname string        `xml:"classname,attr"`
+	Time      string        `xml:"time,attr"`
+	Failure   *junitMessage `xml:"failure"`
+	Error     *junitMessage `xml:"error"`
+	Skipped   *junitMessage `xml:"skipped"`
+}
+
+type junitMessage struct {
+	Message string `xml:"message,attr"`
+	Text    string `xml:",chardata"`
+}
+
+// ParseJUnit reads the cases of a JUnit XML report
+func ParseJUnit(data []byte) ([]Case, error) {
+	var root junitSuite
+	if err := xml.Unmarshal(data, &root); err != nil {
+		return nil, fmt.Errorf("invalid JUnit report: %w", err)
+	}
+	var cases []Case
+	var walk func(s junitSuite)
+	walk = func(s junitSuite) {
+		for _, tc := range s.Cases {
+			c := Case{Suite: tc.Classname, Name: tc.Name, Status: StatusPassed}
+			if c.Suite == "" {
+				c.Suite = s.Name
+			}
+			if seconds, err := strconv.ParseFloat(tc.Time, 64); err == nil {
+				c.DurationMS = int64(seconds * 1000)
+			}
+			switch {
+			case tc.Failure != nil:
+				c.Status, c.Message = StatusFailed, junitText(tc.Failure)
+			case tc.Error != nil:
+				c.Status, c.Message = StatusFailed, junitText(tc.Error)
+			case tc.Skipped != nil:
+				c.Status, c.Message = StatusSkipped, junitText(tc.Skipped)
+			}
+			cases = append(cases, c)
+		}
+		for _, child := range s.Suites {
+			walk(child)
+		}
+	}
+	walk(root)
+	return cases, nil
+}
+
+func junitText(m *junitMessage) string {
+	if text := strings.TrimSpace(m.Text); text != "" {
+		return text
+	}
+	return m.Message
+}
+
+// jestReport is the part of jest's --json output that describes each test
+type jestReport struct {
+	TestResults []struct {
+		Name             string `json:"name"`
+		AssertionResults []struct {
+			FullName        string   `json:"fullName"`
+			Status          string   `json:"status"`
+			Duration        *float64 `json:"duration"`
+			FailureMessages []string `json:"failureMessages"`
+		} `json:"assertionResults"`
+	} `json:"testResults"`
+}
+
+// ParseJest reads the cases of a jest --json report
+func ParseJest(data []byte) ([]Case, error) {
+	var report jestReport
+	if err := json.Unmarshal(data, &report); err != nil {
+		return nil, fmt.Errorf("invalid jest report: %w", err)
+	}
+	var cases []Case
+	for _, file := range report.TestResults {
+		for _, a := range file.AssertionResults {
+			c := Case{Suite: file.Name, Name: a.FullName, Message: strings.Join(a.FailureMessages, "\n")}
+			switch a.Status {
+			case "passed":
+				c.Status = StatusPassed
+			case "failed":
+				c.Status = StatusFailed
+			default:
+				// pending, skipped, todo and disabled
+				c.Status = StatusSkipped
+			}
+			if a.Duration != nil {
+				c.DurationMS = int64(*a.Duration)
+			}
+			cases = append(cases, c)
+		}
+	}
+	return cases, nil
+}
+
+var (
+	cargoResult  = regexp.MustCompile(`^test (\S+) \.\.\. (ok|FAILED|ignored)`)
+	cargoSection = regexp.MustCompile(`^---- (\S+) stdout ----$`)
+)
+
+// ParseCargo reads the cases from `cargo test` output, with the captured output of
+// failed tests as their message
+func ParseCargo(output string) []Case {
+	var cases []Case
+	index := make(map[string]int)
+	messages := make(map[string]*strings.Builder)
+	var current *strings.Builder
+
+	for _, line := range strings.Split(output, "\n") {
+		line = strings.TrimRight(line, "\r")
+		if m := cargoResult.FindStringSubmatch(line); m != nil {
+			status := StatusPassed
+			switch m[2] {
+			case "FAILED":
+				status = StatusFailed
+			case "ignored":
+				status = StatusSkipped
+			}
+			index[m[1]] = len(cases)
+			cases = append(cases, Case{Name: m[1], Status: status})
+			continue
+		}
+		if m := cargoSection.FindStringSubmatch(line); m != nil {
+			current = &strings.Builder{}
+			messages[m[1]] = current
+			continue
+		}
+		if line == "failures:" || strings.HasPrefix(line, "test result:") {
+			current = nil
+			continue
+		}
+		if current != nil {
+			current.WriteString(line)
+			current.WriteByte('\n')
+		}
+	}
+	for name, msg := range messages {
+		if i, ok := index[name]; ok {
+			cases[i].Message = strings.TrimSpace(msg.String())
+		}
+	}
+	return cases
+}
+
+var (
+	goResult  = regexp.MustCompile(`^\s*--- (PASS|FAIL|SKIP): (\S+) \(([0-9.]+)s\)`)
+	goPackage = regexp.MustCompile(`^(?:ok|FAIL)\s+(\S+)\s`)
+)
+
+// ParseGo reads the cases from `go test -v` output. Output printed before a failing
+// test's result line is kept as its message.
+func ParseGo(output string) []Case {
+	var cases []Case
+	var pending []int
+	var logs []string
+	for _, line := range strings.Split(output, "\n") {
+		line = strings.TrimRight(line, "\r")
+		if m := goResult.FindStringSubmatch(line); m != nil {
+			c := Case{Name: m[2], Status: StatusPassed}
+			switch m[1] {
+			case "FAIL":
+				c.Status = StatusFailed
+				c.Message = strings.TrimSpace(strings.Join(logs, "\n"))
+			case "SKIP":
+				c.Status = StatusSkipped
+			}
+			if seconds, err := strconv.ParseFloat(m[3], 64); err == nil {
+				c.DurationMS = int64(seconds * 1000)
+			}
+			pending = append(pending, len(cases))
+			cases = append(cases, c)
+			logs = nil
+			continue
+		}
+		if m := goPackage.FindStringSubmatch(line); m != nil {
+			for _, i := range pending {
+				cases[i].Suite = m[1]
+			}
+			pending, logs = nil, nil
+			continue
+		}
+		if strings.HasPrefix(line, "=== ") {
+			logs = nil
+			continue
+		}
+		logs = append(logs, strings.TrimSpace(line))
+	}
+	return cases
+}
+
+// Parse reads the cases of a run from its output or report, depending on the format.
+// FormatText has no cases.
+func Parse(format, output string, report []byte) ([]Case, error) {
+	switch format {
+	case FormatJUnit:
+		return ParseJUnit(report)
+	case FormatJest:
+		return ParseJest(report)
+	case FormatCargo:
+		return ParseCargo(output), nil
+	case FormatGo:
+		return ParseGo(output), nil
+	}
+	return nil, nil
+}
@@ -0,0 +1,90 @@
+package testrun
+
+import "testing"
+
+func TestParseJUnit(t *testing.T) {
+	report := `<?xml version="1.0"?>
+<testsuites>
+  <testsuite name="tests.test_api">
+    <testcase classname="tests.test_api" name="test_ok" time="0.012"/>
+    <testcase classname="tests.test_api" name="test_bad" time="0.5">
+      <failure message="assert 1 == 2">AssertionError: assert 1 == 2</failure>
+    </testcase>
+    <testcase classname="tests.test_api" name="test_later"><skipped message="todo"/></testcase>
+  </testsuite>
+</testsuites>`
+	cases, err := ParseJUnit([]byte(report))
+	if err != nil {
+		t.Fatal(err)
+	}
+	var r Result
+	r.SetCases(cases)
+	if r.Passed != 1 || r.Failed != 1 || r.Skipped != 1 {
+		t.Fatalf("totals = %d/%d/%d, want 1/1/1: %+v", r.Passed, r.Failed, r.Skipped, cases)
+	}
+	if cases[1].Message != "AssertionError: assert 1 == 2" || cases[1].DurationMS != 500 {
+		t.Errorf("failed case = %+v", cases[1])
+	}
+}
+
+func TestParseJest(t *testing.T) {
+	report := `{"testResults": [{"name": "/app/sum.test.js", "assertionResults": [
+		{"fullName": "sum adds", "status": "passed", "duration": 3, "failureMessages": []},
+		{"fullName": "sum overflows", "status": "failed", "duration": 5, "failureMessages": ["Expected: 3"]},
+		{"fullName": "sum later", "status": "todo", "failureMessages": []}
+	]}]}`
+	cases, err := ParseJest([]byte(report))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(cases) != 3 || cases[1].Status != StatusFailed || cases[1].Message != "Expected: 3" || cases[2].Status != StatusSkipped {
+		t.Errorf("ParseJest() = %+v", cases)
+	}
+}
+
+func TestParseCargo(t *testing.T) {
+	output := `running 3 tests
+test parser::tests::parses ... ok
+test parser::tests::rejects ... FAILED
+test slow ... ignored
+
+failures:
+
+---- parser::tests::rejects stdout ----
+thread 'parser::tests::rejects' panicked at src/parser.rs:10:9:
+expected an error
+
+failures:
+    parser::tests::rejects
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out
+`
+	cases := ParseCargo(output)
+	if len(cases) != 3 {
+		t.Fatalf("ParseCargo() = %+v", cases)
+	}
+	if cases[1].Status != StatusFailed || cases[1].Message != "thread 'parser::tests::rejects' panicked at src/parser.rs:10:9:\nexpected an error" {
+		t.Errorf("failed case = %+v", cases[1])
+	}
+	if cases[2].Status != StatusSkipped {
+		t.Errorf("ignored case = %+v", cases[2])
+	}
+}
+
+func TestParseGo(t *testing.T) {
+	output := `=== RUN   TestAdd
+--- PASS: TestAdd (0.00s)
+=== RUN   TestSub
+    math_test.go:12: got 1, want 2
+--- FAIL: TestSub (0.01s)
+FAIL
+FAIL	example.com/math	0.012s
+`
+	cases := ParseGo(output)
+	if len(cases) != 2 {
+		t.Fatalf("ParseGo() = %+v", cases)
+	}
+	if cases[1].Status != StatusFailed || cases[1].Message != "math_test.go:12: got 1, want 2" || cases[1].Suite != "example.com/math" {
+		t.Errorf("failed case = %+v", cases[1])
+	}
+}
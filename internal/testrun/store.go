@@ -0,0 +1,33 @@
+package testrun
+
+import (
+	"encoding/json"
+	"os"
+
+	"github.com/thaodangspace/agentsandbox/internal/logs"
+)
+
+// Save writes a result next to the session logs, encrypted when log encryption is on
+func Save(path string, result *Result) error {
+	data, err := json.MarshalIndent(result, "", "  ")
+	if err != nil {
+		return err
+	}
+	if err := os.WriteFile(path, data, 0600); err != nil {
+		return err
+	}
+	return logs.EncryptFile(path)
+}
+
+// Load reads a stored result
+func Load(path string) (*Result, error) {
+	data, err := logs.ReadLog(path)
+	if err != nil {
+		return nil, err
+	}
+	var result Result
+	if err := json.Unmarshal(data, &result); err != nil {
+		return nil, err
+	}
+	return &result, nil
+}
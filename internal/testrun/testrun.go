@@ -0,0 +1,122 @@
+package testrun
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+)
+
+// Report formats a test command's results can be read from
+const (
+	// FormatJUnit reads a JUnit XML report written to the command's report path
+	FormatJUnit = "junit"
+	// FormatJest reads the JSON report jest writes with --json --outputFile
+	FormatJest = "jest"
+	// FormatCargo parses `cargo test` output
+	FormatCargo = "cargo"
+	// FormatGo parses `go test -v` output
+	FormatGo = "go"
+	// FormatText only records whether the command succeeded
+	FormatText = "text"
+)
+
+// Test case statuses
+const (
+	StatusPassed  = "passed"
+	StatusFailed  = "failed"
+	StatusSkipped = "skipped"
+)
+
+// Command is a test command and where its results come from. Report formats write
+// to Report inside the container.
+type Command struct {
+	Run    string `json:"run"`
+	Format string `json:"format"`
+	Report string `json:"report,omitempty"`
+}
+
+// Case is one test's outcome
+type Case struct {
+	Suite      string `json:"suite,omitempty"`
+	Name       string `json:"name"`
+	Status     string `json:"status"`
+	Message    string `json:"message,omitempty"`
+	DurationMS int64  `json:"duration_ms,omitempty"`
+}
+
+// Result is a test run stored with the container's logs
+type Result struct {
+	Container string `json:"container"`
+	// Session is the agent session the run followed, if any
+	Session    string    `json:"session,omitempty"`
+	Command    string    `json:"command"`
+	Format     string    `json:"format"`
+	Started    time.Time `json:"started"`
+	DurationMS int64     `json:"duration_ms"`
+	ExitCode   int       `json:"exit_code"`
+	Passed     int       `json:"passed"`
+	Failed     int       `json:"failed"`
+	Skipped    int       `json:"skipped"`
+	Cases      []Case    `json:"cases"`
+}
+
+// SetCases records the cases and their totals
+func (r *Result) SetCases(cases []Case) {
+	r.Cases = cases
+	r.Passed, r.Failed, r.Skipped = 0, 0, 0
+	for _, c := range cases {
+		switch c.Status {
+		case StatusPassed:
+			r.Passed++
+		case StatusFailed:
+			r.Failed++
+		case StatusSkipped:
+			r.Skipped++
+		}
+	}
+}
+
+// OK reports whether the run succeeded
+func (r *Result) OK() bool {
+	return r.ExitCode == 0 && r.Failed == 0
+}
+
+// reportPath is where report formats write inside the container
+const reportPath = "/tmp/agentsandbox-test"
+
+// Detect returns the test command for the project in dir, or nil when none is known
+func Detect(dir string) *Command {
+	exists := func(rel string) bool {
+		_, err := os.Stat(filepath.Join(dir, rel))
+		return err == nil
+	}
+	packageJSON := ""
+	if data, err := os.ReadFile(filepath.Join(dir, "package.json")); err == nil {
+		packageJSON = string(data)
+	}
+
+	switch {
+	case exists("Cargo.toml"):
+		return &Command{Run: "cargo test --no-fail-fast", Format: FormatCargo}
+	case exists("go.mod"):
+		return &Command{Run: "go test -v ./...", Format: FormatGo}
+	case strings.Contains(packageJSON, `"jest"`):
+		report := reportPath + "/jest.json"
+		return &Command{Run: "npx jest --ci --json --outputFile=" + report, Format: FormatJest, Report: report}
+	case strings.Contains(packageJSON, `"vitest"`):
+		report := reportPath + "/junit.xml"
+		return &Command{Run: "npx vitest run --reporter=default --reporter=junit --outputFile.junit=" + report, Format: FormatJUnit, Report: report}
+	case packageJSON != "":
+		return &Command{Run: "npm test", Format: FormatText}
+	case exists("pyproject.toml") || exists("requirements.txt") || exists("pytest.ini"):
+		report := reportPath + "/junit.xml"
+		return &Command{Run: "python3 -m pytest --junitxml=" + report, Format: FormatJUnit, Report: report}
+	case exists("phpunit.xml") || exists("phpunit.xml.dist"):
+		report := reportPath + "/junit.xml"
+		return &Command{Run: "vendor/bin/phpunit --log-junit " + report, Format: FormatJUnit, Report: report}
+	case exists("Gemfile"):
+		return &Command{Run: "bundle exec rake test", Format: FormatText}
+	}
+	return nil
+}
@@ -0,0 +1,163 @@
+package ui
+
+import (
+	"fmt"
+	"io"
+	"os"
+	"sync"
+	"time"
+)
+
+// spinnerFrames are drawn in turn while a step runs, or asciiFrames with --no-emoji
+var (
+	spinnerFrames = []string{"⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"}
+	asciiFrames   = []string{"|", "/", "-", "\\"}
+)
+
+// Progress shows a sequence of long-running steps on out. On a terminal the running
+// step has a spinner and its elapsed time, and output written to the step appears
+// above it; otherwise each step is printed once when it starts. Every step is
+// reported with how long it took when it ends.
+type Progress struct {
+	out     io.Writer
+	animate bool
+	step    *progressStep
+}
+
+// progressStep is the step a Progress is running
+type progressStep struct {
+	title   string
+	started time.Time
+	out     io.Writer
+
+	// Set only while animating
+	stop    chan struct{}
+	stopped chan struct{}
+	mu      sync.Mutex
+	frame   int
+	lineEnd bool
+}
+
+// NewProgress returns a Progress writing to out. It animates when out is a terminal
+// and spinners are not turned off with --plain.
+func NewProgress(out io.Writer) *Progress {
+	file, ok := out.(*os.File)
+	return &Progress{out: out, animate: spinners && ok && IsTerminal(file)}
+}
+
+// Start ends the running step, if any, as done and starts the next one. The step's
+// own output must be written to the returned writer, which keeps it clear of the
+// spinner; it is safe for concurrent use.
+func (p *Progress) Start(title string) io.Writer {
+	p.End(nil)
+	step := &progressStep{title: title, started: time.Now(), out: p.out}
+	p.step = step
+	if !p.animate {
+		fmt.Fprintf(p.out, "%s...\n", title)
+		return p.out
+	}
+
+	step.stop = make(chan struct{})
+	step.stopped = make(chan struct{})
+	step.lineEnd = true
+	step.draw()
+	go step.spin()
+	return step
+}
+
+// End finishes the running step, reporting it as failed when err is not nil. It does
+// nothing when no step is running, so it can also be deferred to clean up after an
+// early return.
+func (p *Progress) End(err error) {
+	step := p.step
+	if step == nil {
+		return
+	}
+	p.step = nil
+
+	if step.stop != nil {
+		close(step.stop)
+		<-step.stopped
+		step.clear()
+	}
+
+	elapsed := time.Since(step.started).Round(100 * time.Millisecond)
+	if err != nil {
+		fmt.Fprintf(p.out, "%s%s (%s)\n", Failure(), step.title, elapsed)
+		return
+	}
+	fmt.Fprintf(p.out, "%s%s (%s)\n", Check(), step.title, elapsed)
+}
+
+// Write prints the step's output above the spinner line
+func (s *progressStep) Write(b []byte) (int, error) {
+	if len(b) == 0 {
+		return 0, nil
+	}
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if s.lineEnd {
+		io.WriteString(s.out, "\r\x1b[K")
+	}
+	n, err := s.out.Write(b)
+	s.lineEnd = b[len(b)-1] == '\n'
+	if s.lineEnd {
+		select {
+		case <-s.stop:
+		default:
+			s.drawLocked()
+		}
+	}
+	return n, err
+}
+
+// spin redraws the spinner until the step ends
+func (s *progressStep) spin() {
+	defer close(s.stopped)
+	ticker := time.NewTicker(100 * time.Millisecond)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-s.stop:
+			return
+		case <-ticker.C:
+			s.mu.Lock()
+			s.frame++
+			// Partial lines are left alone until they are finished
+			if s.lineEnd {
+				s.drawLocked()
+			}
+			s.mu.Unlock()
+		}
+	}
+}
+
+func (s *progressStep) draw() {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	s.drawLocked()
+}
+
+func (s *progressStep) drawLocked() {
+	frames := spinnerFrames
+	if !emoji {
+		frames = asciiFrames
+	}
+	frame := frames[s.frame%len(frames)]
+	if color {
+		frame = "\x1b[36m" + frame + "\x1b[0m"
+	}
+	elapsed := time.Since(s.started).Truncate(time.Second)
+	fmt.Fprintf(s.out, "\r\x1b[K%s %s %s", frame, s.title, elapsed)
+}
+
+// clear removes the spinner line, or ends a partial line left by the step's output
+func (s *progressStep) clear() {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if s.lineEnd {
+		io.WriteString(s.out, "\r\x1b[K")
+	} else {
+		io.WriteString(s.out, "\n")
+	}
+}
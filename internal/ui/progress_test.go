@@ -0,0 +1,43 @@
+package ui
+
+import (
+	"bytes"
+	"errors"
+	"fmt"
+	"regexp"
+	"testing"
+)
+
+func TestProgressPlain(t *testing.T) {
+	t.Cleanup(func() { Configure(false, false) })
+	Configure(true, false)
+
+	var output bytes.Buffer
+	progress := NewProgress(&output)
+	out := progress.Start("Building image")
+	fmt.Fprintln(out, "Using cached base layer")
+	progress.Start("Starting container")
+	progress.End(errors.New("failed"))
+	progress.End(nil)
+
+	want := regexp.MustCompile(`^Building image\.\.\.\nUsing cached base layer\n- Building image \(\S+\)\nStarting container\.\.\.\nError: Starting container \(\S+\)\n$`)
+	if !want.Match(output.Bytes()) {
+		t.Errorf("unexpected progress output:\n%s", output.String())
+	}
+}
+
+func TestProgressStepWriter(t *testing.T) {
+	t.Cleanup(func() { Configure(false, false) })
+	Configure(true, false)
+
+	var output bytes.Buffer
+	step := &progressStep{title: "Setting up", out: &output, stop: make(chan struct{}), lineEnd: true}
+	fmt.Fprint(step, "partial")
+	fmt.Fprintln(step, " line")
+
+	// Output replaces the spinner line, which is drawn again once the line is finished
+	want := regexp.MustCompile(`^\r\x1b\[Kpartial line\n\r\x1b\[K\| Setting up 0s$`)
+	if !want.Match(output.Bytes()) {
+		t.Errorf("unexpected step output: %q", output.String())
+	}
+}
@@ -0,0 +1,95 @@
+package ui
+
+import (
+	"os"
+	"strings"
+
+	"github.com/mattn/go-runewidth"
+)
+
+var (
+	// emoji is off with --no-emoji, "output": "plain" or on terminals that cannot draw it
+	emoji = true
+	// color is off with NO_COLOR or "output": "plain"
+	color = true
+	// spinners are off with --plain, "output": "plain" or on dumb terminals
+	spinners = true
+)
+
+// Configure sets how output is decorated. It is called once flags and settings are known.
+func Configure(plain, noEmoji bool) {
+	term := os.Getenv("TERM")
+	// The Linux console and dumb terminals have no wide-character or emoji glyphs
+	basic := term == "dumb" || term == "linux"
+	emoji = !plain && !noEmoji && !basic
+	color = !plain && os.Getenv("NO_COLOR") == "" && term != "dumb"
+	spinners = !plain && term != "dumb"
+}
+
+// Success prefixes a message reporting that something worked
+func Success() string {
+	if emoji {
+		return "✅ "
+	}
+	return ""
+}
+
+// Check prefixes a completed step in a list of steps
+func Check() string {
+	if emoji {
+		return "✓ "
+	}
+	return "- "
+}
+
+// Warning prefixes a message that needs the user's attention
+func Warning() string {
+	if emoji {
+		// The emoji is drawn two columns wide but many terminals advance the cursor only one
+		return "⚠️  "
+	}
+	return "Warning: "
+}
+
+// Failure prefixes a message reporting that something failed
+func Failure() string {
+	if emoji {
+		return "❌ "
+	}
+	return "Error: "
+}
+
+// Rule returns a horizontal line width columns wide
+func Rule(width int) string {
+	if emoji {
+		return strings.Repeat("─", width)
+	}
+	return strings.Repeat("-", width)
+}
+
+// Truncate shortens s to at most width terminal columns, counting wide characters
+// such as CJK and emoji as two
+func Truncate(s string, width int) string {
+	if width <= 0 {
+		return ""
+	}
+	return runewidth.Truncate(s, width, "…")
+}
+
+// Colorize wraps s in the ANSI SGR code (e.g. "32" for green) when writing colour to
+// stdout is enabled
+func Colorize(code, s string) string {
+	if !color || !IsTerminal(os.Stdout) {
+		return s
+	}
+	return "\x1b[" + code + "m" + s + "\x1b[0m"
+}
+
+// IsTerminal reports whether f is attached to a terminal
+func IsTerminal(f *os.File) bool {
+	info, err := f.Stat()
+	if err != nil {
+		return false
+	}
+	return info.Mode()&os.ModeCharDevice != 0
+}
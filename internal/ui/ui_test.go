@@ -0,0 +1,45 @@
+package ui
+
+import "testing"
+
+func TestConfigure(t *testing.T) {
+	t.Cleanup(func() { Configure(false, false) })
+
+	t.Setenv("TERM", "xterm-256color")
+	t.Setenv("NO_COLOR", "")
+	Configure(false, false)
+	if Warning() != "⚠️  " || Rule(2) != "──" {
+		t.Errorf("emoji output expected, got %q %q", Warning(), Rule(2))
+	}
+
+	Configure(false, true)
+	if Warning() != "Warning: " || Success() != "" || Rule(2) != "--" {
+		t.Errorf("--no-emoji output expected, got %q %q %q", Warning(), Success(), Rule(2))
+	}
+
+	t.Setenv("TERM", "linux")
+	Configure(false, false)
+	if Failure() != "Error: " {
+		t.Errorf("the Linux console should get plain output, got %q", Failure())
+	}
+}
+
+func TestTruncate(t *testing.T) {
+	for _, tt := range []struct {
+		s     string
+		width int
+		want  string
+	}{
+		{"short", 10, "short"},
+		{"exactly ten", 11, "exactly ten"},
+		{"a longer prompt", 8, "a longe…"},
+		// Wide characters take two columns and are never cut in half
+		{"修复登录页面的错误", 7, "修复登…"},
+		{"fix 🐛 in login", 6, "fix …"},
+		{"anything", 0, ""},
+	} {
+		if got := Truncate(tt.s, tt.width); got != tt.want {
+			t.Errorf("Truncate(%q, %d) = %q, want %q", tt.s, tt.width, got, tt.want)
+		}
+	}
+}